@@ -0,0 +1,442 @@
+//! Building the accessibility tree of a movie
+
+use crate::avm1::{Activation as Avm1Activation, ActivationIdentifier, Value as Avm1Value};
+use crate::avm2::globals::slots::flash_accessibility_accessibility_implementation as implementation_slots;
+use crate::avm2::globals::slots::flash_accessibility_accessibility_properties as properties_slots;
+use crate::avm2::globals::slots::flash_display_display_object as display_object_slots;
+use crate::avm2::globals::slots::flash_display_interactive_object as interactive_object_slots;
+use crate::avm2::object::TObject;
+use crate::avm2::{Activation as Avm2Activation, Object as Avm2Object, Value as Avm2Value};
+use crate::backend::accessibility::{
+    AccessibilityAction, AccessibilityNode, AccessibilityNodeId, AccessibilityRequest,
+    AccessibilityRole, AccessibilityTree,
+};
+use crate::context::UpdateContext;
+use crate::display_object::{
+    DisplayObject, TDisplayObject, TDisplayObjectContainer, TInteractiveObject,
+};
+use crate::events::ClipEvent;
+use crate::string::AvmString;
+use ruffle_macros::istr;
+use ruffle_render::matrix::Matrix;
+use swf::{Rectangle, Twips};
+
+/// MSAA roles returned by `AccessibilityImplementation.get_accRole`.
+const ROLE_SYSTEM_STATICTEXT: u32 = 0x29;
+const ROLE_SYSTEM_TEXT: u32 = 0x2a;
+const ROLE_SYSTEM_PUSHBUTTON: u32 = 0x2b;
+
+/// Keeps track of what the accessibility tree was built from.
+#[derive(Default)]
+pub struct AccessibilityTracker {
+    /// The identifier given to the next display object which needs one.
+    next_node_id: u64,
+
+    /// Whether the tree has to be rebuilt, for reasons that can't be seen
+    /// on the stage, such as `Accessibility.updateProperties`.
+    invalidated: bool,
+
+    /// The node which had the focus when the tree was last built.
+    focus: Option<AccessibilityNodeId>,
+}
+
+impl AccessibilityTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes the tree be rebuilt on its next update.
+    pub fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+}
+
+/// The accessibility properties a movie gave to a display object.
+///
+/// These are `accessibilityProperties` in AVM2 and `_accProps` in AVM1,
+/// as well as what the `accessibilityImplementation` of an AVM2 object reports.
+#[derive(Default)]
+struct AccessibilityProperties {
+    name: String,
+    description: String,
+    shortcut: String,
+
+    /// The object and its children are hidden from assistive technologies.
+    silent: bool,
+
+    /// The children of the object are hidden from assistive technologies.
+    force_simple: bool,
+
+    /// The role given by an accessibility implementation.
+    role: Option<AccessibilityRole>,
+
+    /// The value given by an accessibility implementation.
+    value: Option<String>,
+}
+
+impl AccessibilityProperties {
+    fn of<'gc>(object: DisplayObject<'gc>, context: &mut UpdateContext<'gc>) -> Self {
+        if let Some(avm2_object) = object.object2().as_object() {
+            let mut properties = Self::of_avm2(avm2_object);
+            if let Some(implementation) = implementation_of(object) {
+                properties.consult_implementation(implementation, context);
+            }
+            return properties;
+        }
+
+        if let Avm1Value::Object(avm1_object) = object.object() {
+            let mut activation = Avm1Activation::from_nothing(
+                context,
+                ActivationIdentifier::root("[Accessibility]"),
+                object,
+            );
+            let Some(Avm1Value::Object(properties)) =
+                avm1_object.get_local_stored(istr!("_accProps"), &mut activation, false)
+            else {
+                return Self::default();
+            };
+
+            let swf_version = activation.swf_version();
+            let mut get = |name| {
+                properties
+                    .get_local_stored(name, &mut activation, false)
+                    .unwrap_or(Avm1Value::Undefined)
+            };
+            let name = get(istr!("name"));
+            let description = get(istr!("description"));
+            let shortcut = get(istr!("shortcut"));
+            let silent = get(istr!("silent"));
+            let force_simple = get(istr!("forceSimple"));
+            return Self {
+                name: avm1_string(name, &mut activation),
+                description: avm1_string(description, &mut activation),
+                shortcut: avm1_string(shortcut, &mut activation),
+                silent: silent.as_bool(swf_version),
+                force_simple: force_simple.as_bool(swf_version),
+                role: None,
+                value: None,
+            };
+        }
+
+        Self::default()
+    }
+
+    /// Reads the `accessibilityProperties` stored by an AVM2 object.
+    fn of_avm2(object: Avm2Object<'_>) -> Self {
+        let Some(properties) = object
+            .get_slot(display_object_slots::_ACCESSIBILITY_PROPERTIES)
+            .as_object()
+        else {
+            return Self::default();
+        };
+
+        let string = |slot| match properties.get_slot(slot) {
+            Avm2Value::String(string) => string.to_string(),
+            _ => String::new(),
+        };
+        Self {
+            name: string(properties_slots::NAME),
+            description: string(properties_slots::DESCRIPTION),
+            shortcut: string(properties_slots::SHORTCUT),
+            silent: properties
+                .get_slot(properties_slots::SILENT)
+                .coerce_to_boolean(),
+            force_simple: properties
+                .get_slot(properties_slots::FORCE_SIMPLE)
+                .coerce_to_boolean(),
+            role: None,
+            value: None,
+        }
+    }
+
+    /// Asks an `AccessibilityImplementation` about the object it implements.
+    fn consult_implementation<'gc>(
+        &mut self,
+        implementation: Avm2Object<'gc>,
+        context: &mut UpdateContext<'gc>,
+    ) {
+        let mut activation = Avm2Activation::from_nothing(context);
+
+        if let Some(Avm2Value::String(name)) =
+            call_implementation(implementation, istr!("get_accName"), &mut activation)
+        {
+            self.name = name.to_string();
+        }
+        if let Some(Avm2Value::String(value)) =
+            call_implementation(implementation, istr!("get_accValue"), &mut activation)
+        {
+            self.value = Some(value.to_string());
+        }
+        if let Some(role @ (Avm2Value::Integer(_) | Avm2Value::Number(_))) =
+            call_implementation(implementation, istr!("get_accRole"), &mut activation)
+        {
+            self.role = Some(match role.as_u32() {
+                ROLE_SYSTEM_STATICTEXT => AccessibilityRole::Text,
+                ROLE_SYSTEM_TEXT => AccessibilityRole::InputText,
+                ROLE_SYSTEM_PUSHBUTTON => AccessibilityRole::Button,
+                _ => AccessibilityRole::Graphic,
+            });
+        }
+    }
+}
+
+fn avm1_string<'gc>(value: Avm1Value<'gc>, activation: &mut Avm1Activation<'_, 'gc>) -> String {
+    match value {
+        // Objects and clips aren't converted, as that could run their `toString`.
+        Avm1Value::Undefined | Avm1Value::Null | Avm1Value::Object(_) | Avm1Value::MovieClip(_) => {
+            String::new()
+        }
+        value => value
+            .coerce_to_string(activation)
+            .map(|s| s.to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// The `accessibilityImplementation` of an AVM2 object, unless it's a stub.
+fn implementation_of<'gc>(object: DisplayObject<'gc>) -> Option<Avm2Object<'gc>> {
+    object.as_interactive()?;
+    let implementation = object
+        .object2()
+        .as_object()?
+        .get_slot(interactive_object_slots::_ACCESSIBILITY_IMPL)
+        .as_object()?;
+    let is_stub = implementation
+        .get_slot(implementation_slots::STUB)
+        .coerce_to_boolean();
+    (!is_stub).then_some(implementation)
+}
+
+/// Calls a method of an `AccessibilityImplementation` for the object itself (child ID 0).
+fn call_implementation<'gc>(
+    implementation: Avm2Object<'gc>,
+    method: AvmString<'gc>,
+    activation: &mut Avm2Activation<'_, 'gc>,
+) -> Option<Avm2Value<'gc>> {
+    match Avm2Value::from(implementation).call_public_property(method, &[0.into()], activation) {
+        Ok(Avm2Value::Null | Avm2Value::Undefined) => None,
+        Ok(value) => Some(value),
+        Err(e) => {
+            tracing::warn!("Error calling AccessibilityImplementation.{method}: {e:?}");
+            None
+        }
+    }
+}
+
+/// The identifier of the node of a display object.
+///
+/// Identifiers are given out in order the first time they're needed,
+/// and kept for as long as the object exists.
+pub fn node_id<'gc>(
+    object: DisplayObject<'gc>,
+    context: &mut UpdateContext<'gc>,
+) -> AccessibilityNodeId {
+    if let Some(id) = object.accessibility_node_id() {
+        return id;
+    }
+
+    let tracker = &mut *context.accessibility_tracker;
+    tracker.next_node_id += 1;
+    let id = AccessibilityNodeId(tracker.next_node_id);
+    object.set_accessibility_node_id(id);
+    id
+}
+
+/// Whether the accessibility tree may have changed since it was last built.
+fn is_invalidated(context: &mut UpdateContext<'_>) -> bool {
+    let focus = context
+        .focus_tracker
+        .get()
+        .map(|focus| node_id(focus.as_displayobject(), context));
+    let tracker = &mut *context.accessibility_tracker;
+    let focus_changed = std::mem::replace(&mut tracker.focus, focus) != focus;
+    let invalidated = std::mem::take(&mut tracker.invalidated);
+    invalidated || focus_changed
+}
+
+/// Builds the accessibility tree of the movie currently on the stage,
+/// unless nothing has changed since it was last built.
+pub fn update_tree(context: &mut UpdateContext<'_>, force: bool) -> Option<AccessibilityTree> {
+    let invalidated = is_invalidated(context);
+    (force || invalidated).then(|| build_tree(context))
+}
+
+/// Builds the accessibility tree of the movie currently on the stage.
+pub fn build_tree(context: &mut UpdateContext<'_>) -> AccessibilityTree {
+    let stage = context.stage;
+    let view_matrix = stage.view_matrix();
+    let viewport = context.renderer.viewport_dimensions();
+
+    let mut root = AccessibilityNode {
+        id: node_id(stage.into(), context),
+        role: AccessibilityRole::Movie,
+        name: String::new(),
+        description: String::new(),
+        shortcut: String::new(),
+        value: None,
+        bounds: Rectangle {
+            x_min: Twips::ZERO,
+            y_min: Twips::ZERO,
+            x_max: Twips::from_pixels_i32(viewport.width as i32),
+            y_max: Twips::from_pixels_i32(viewport.height as i32),
+        },
+        children: Vec::new(),
+    };
+    add_children(stage.into(), &mut root.children, view_matrix, context);
+
+    let mut tree = AccessibilityTree {
+        root,
+        focus: None,
+        tab_order: Vec::new(),
+    };
+
+    let focus_tracker = context.focus_tracker;
+    tree.focus = focus_tracker
+        .get()
+        .map(|focus| node_id(focus.as_displayobject(), context))
+        .filter(|&id| tree.node(id).is_some());
+    tree.tab_order = focus_tracker
+        .tab_order(context)
+        .iter()
+        .map(|object| node_id(object.as_displayobject(), context))
+        .filter(|&id| tree.node(id).is_some())
+        .collect();
+    tree
+}
+
+fn add_children<'gc>(
+    object: DisplayObject<'gc>,
+    nodes: &mut Vec<AccessibilityNode>,
+    view_matrix: Matrix,
+    context: &mut UpdateContext<'gc>,
+) {
+    if let Some(container) = object.as_container() {
+        for child in container.iter_render_list() {
+            add_object(child, nodes, view_matrix, context);
+        }
+    }
+}
+
+fn add_object<'gc>(
+    object: DisplayObject<'gc>,
+    nodes: &mut Vec<AccessibilityNode>,
+    view_matrix: Matrix,
+    context: &mut UpdateContext<'gc>,
+) {
+    if !object.visible() {
+        return;
+    }
+
+    let properties = AccessibilityProperties::of(object, context);
+    if properties.silent {
+        return;
+    }
+
+    let Some(role) = role_of(object, &properties, context) else {
+        // Objects which aren't exposed themselves still expose their children.
+        add_children(object, nodes, view_matrix, context);
+        return;
+    };
+
+    let value = properties.value.or_else(|| {
+        object
+            .as_edit_text()
+            .filter(|edit_text| !edit_text.is_password())
+            .map(|edit_text| edit_text.text().to_string())
+    });
+
+    let mut node = AccessibilityNode {
+        id: node_id(object, context),
+        role,
+        name: properties.name,
+        description: properties.description,
+        shortcut: properties.shortcut,
+        value,
+        bounds: view_matrix * object.world_bounds(),
+        children: Vec::new(),
+    };
+
+    // Buttons and text fields are always exposed as a whole.
+    if role == AccessibilityRole::Graphic && !properties.force_simple {
+        add_children(object, &mut node.children, view_matrix, context);
+    }
+    nodes.push(node);
+}
+
+fn role_of<'gc>(
+    object: DisplayObject<'gc>,
+    properties: &AccessibilityProperties,
+    context: &mut UpdateContext<'gc>,
+) -> Option<AccessibilityRole> {
+    if properties.role.is_some() {
+        return properties.role;
+    }
+
+    if let Some(edit_text) = object.as_edit_text() {
+        return Some(if edit_text.is_editable() {
+            AccessibilityRole::InputText
+        } else {
+            AccessibilityRole::Text
+        });
+    }
+
+    if object.as_avm1_button().is_some() || object.as_avm2_button().is_some() {
+        return Some(AccessibilityRole::Button);
+    }
+
+    if let Some(clip) = object.as_movie_clip() {
+        if clip.is_button_mode(context) {
+            return Some(AccessibilityRole::Button);
+        }
+    }
+
+    (!properties.name.is_empty()).then_some(AccessibilityRole::Graphic)
+}
+
+/// Finds the display object of a node on the stage.
+pub fn find_object<'gc>(
+    object: DisplayObject<'gc>,
+    id: AccessibilityNodeId,
+) -> Option<DisplayObject<'gc>> {
+    if object.accessibility_node_id() == Some(id) {
+        return Some(object);
+    }
+    object
+        .as_container()?
+        .iter_render_list()
+        .find_map(|child| find_object(child, id))
+}
+
+/// Performs an action requested by an assistive technology.
+pub fn handle_request(context: &mut UpdateContext<'_>, request: AccessibilityRequest) {
+    let Some(object) =
+        find_object(context.stage.into(), request.node).and_then(|object| object.as_interactive())
+    else {
+        return;
+    };
+
+    match request.action {
+        AccessibilityAction::Focus => {
+            let focus_tracker = context.focus_tracker;
+            focus_tracker.set(Some(object), context);
+        }
+        AccessibilityAction::Click => {
+            // Objects with their own implementation know what their default action is.
+            if let Some(implementation) = implementation_of(object.as_displayobject()) {
+                let mut activation = Avm2Activation::from_nothing(context);
+                call_implementation(implementation, istr!("accDoDefaultAction"), &mut activation);
+                return;
+            }
+
+            // Otherwise, the object is clicked as if by the mouse.
+            for event in [
+                ClipEvent::Press { index: 0 },
+                ClipEvent::MouseUpInside,
+                ClipEvent::Release { index: 0 },
+            ] {
+                object.handle_clip_event(context, event);
+                object.event_dispatch_to_avm2(context, event);
+            }
+        }
+    }
+}
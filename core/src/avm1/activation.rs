@@ -1317,10 +1317,16 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                         url,
                         NavigationMethod::from_send_vars_method(action.send_vars_method()),
                     );
+                    let sandboxed_movie = self
+                        .context
+                        .security_sandbox
+                        .is_strict()
+                        .then(|| self.base_clip().movie());
                     let future = self.context.load_manager.load_form_into_object(
                         self.context.player.clone(),
                         target_obj,
                         request,
+                        sandboxed_movie,
                     );
                     self.context.navigator.spawn_future(future);
                 }
@@ -1,10 +1,10 @@
 //! Accessibility class
 
+use crate::accessibility::node_id;
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Object, Value};
-use crate::avm1_stub;
 use crate::string::StringContext;
 
 const OBJECT_DECLS: &[Declaration] = declare_properties! {
@@ -18,16 +18,34 @@ pub fn is_active<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "Accessibility", "isActive");
-    Ok(Value::Bool(false))
+    Ok(activation.context.accessibility.is_active().into())
 }
 
 pub fn send_event<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "Accessibility", "sendEvent");
+    let child_id = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_u32(activation)?;
+    let event_type = args
+        .get(2)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_u32(activation)?;
+
+    let source = match args.first() {
+        Some(Value::Object(source)) => source.as_display_object(),
+        _ => None,
+    };
+    if let Some(source) = source {
+        let id = node_id(source, activation.context);
+        activation
+            .context
+            .accessibility
+            .send_event(id, child_id, event_type);
+    }
     Ok(Value::Undefined)
 }
 
@@ -36,7 +54,7 @@ pub fn update_properties<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "Accessibility", "updateProperties");
+    activation.context.accessibility_tracker.invalidate();
     Ok(Value::Undefined)
 }
 
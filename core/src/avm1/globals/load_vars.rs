@@ -253,10 +253,16 @@ fn spawn_load_var_fetch<'gc>(
         Request::get(url.to_utf8_lossy().into_owned())
     };
 
+    let sandboxed_movie = activation
+        .context
+        .security_sandbox
+        .is_strict()
+        .then(|| activation.base_clip().movie());
     let future = activation.context.load_manager.load_form_into_load_vars(
         activation.context.player.clone(),
         loader_object,
         request,
+        sandboxed_movie,
     );
     activation.context.navigator.spawn_future(future);
 
@@ -1629,10 +1629,16 @@ fn load_variables<'gc>(
     let method = NavigationMethod::from_method_str(&method.coerce_to_string(activation)?);
     let target = target.object().coerce_to_object(activation);
     let request = activation.object_into_request(target, url, method);
+    let sandboxed_movie = activation
+        .context
+        .security_sandbox
+        .is_strict()
+        .then(|| activation.base_clip().movie());
     let future = activation.context.load_manager.load_form_into_object(
         activation.context.player.clone(),
         target,
         request,
+        sandboxed_movie,
     );
     activation.context.navigator.spawn_future(future);
 
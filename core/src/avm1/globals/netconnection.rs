@@ -8,6 +8,7 @@ use crate::context::UpdateContext;
 use crate::net_connection::{NetConnectionHandle, NetConnections, ResponderCallback};
 use crate::string::{AvmString, StringContext};
 use flash_lso::packet::Header;
+use flash_lso::types::AMFVersion;
 use flash_lso::types::ObjectId;
 use flash_lso::types::Value as AMFValue;
 use gc_arena::{Collect, Gc};
@@ -49,6 +50,15 @@ impl<'gc> NetConnection<'gc> {
         context: &mut UpdateContext<'gc>,
         this: Object<'gc>,
         code: &'static str,
+    ) -> Result<(), Error<'gc>> {
+        Self::on_status_info_event(context, this, vec![("code", code), ("level", "status")])
+    }
+
+    /// Calls `onStatus` with an info object made of the given properties.
+    pub fn on_status_info_event(
+        context: &mut UpdateContext<'gc>,
+        this: Object<'gc>,
+        info: Vec<(&str, &str)>,
     ) -> Result<(), Error<'gc>> {
         let Some(root_clip) = context.stage.root_clip() else {
             tracing::warn!("Ignored NetConnection callback as there's no root movie");
@@ -63,9 +73,11 @@ impl<'gc> NetConnection<'gc> {
         let event = constructor
             .construct(&mut activation, &[])?
             .coerce_to_object(&mut activation);
-        let code = AvmString::new_utf8(activation.gc(), code);
-        event.set(istr!("code"), code.into(), &mut activation)?;
-        event.set(istr!("level"), istr!("status").into(), &mut activation)?;
+        for (key, value) in info {
+            let key = AvmString::new_utf8(activation.gc(), key);
+            let value = AvmString::new_utf8(activation.gc(), value);
+            event.set(key, value.into(), &mut activation)?;
+        }
         this.call_method(
             istr!("onStatus"),
             &[event.into()],
@@ -98,6 +110,46 @@ impl<'gc> NetConnection<'gc> {
         Ok(())
     }
 
+    /// Calls a method on the `NetConnection` on behalf of the server it's
+    /// connected to.
+    pub fn call_client_method(
+        context: &mut UpdateContext<'gc>,
+        this: Object<'gc>,
+        method: &str,
+        arguments: &[Rc<flash_lso::types::Value>],
+    ) -> Result<(), Error<'gc>> {
+        let Some(root_clip) = context.stage.root_clip() else {
+            tracing::warn!("Ignored NetConnection call as there's no root movie");
+            return Ok(());
+        };
+        let mut activation = Activation::from_nothing(
+            context,
+            ActivationIdentifier::root("[NetConnection call]"),
+            root_clip,
+        );
+        let reader = flash_lso::read::Reader::default();
+        let mut reference_cache = BTreeMap::default();
+        let arguments: Vec<_> = arguments
+            .iter()
+            .map(|argument| {
+                deserialize_value(
+                    &mut activation,
+                    argument,
+                    &reader.amf0_decoder,
+                    &mut reference_cache,
+                )
+            })
+            .collect();
+        let method = AvmString::new_utf8(activation.gc(), method);
+        this.call_method(
+            method,
+            &arguments,
+            &mut activation,
+            ExecutionReason::Special,
+        )?;
+        Ok(())
+    }
+
     pub fn send_callback(
         context: &mut UpdateContext<'gc>,
         responder: Object<'gc>,
@@ -316,17 +368,42 @@ fn connect<'gc>(
     }
 
     let url = args[0].coerce_to_string(activation)?;
+    let sandboxed_movie = activation
+        .context
+        .security_sandbox
+        .is_strict()
+        .then(|| activation.base_clip().movie());
     if url.starts_with(WStr::from_units(b"http://"))
         || url.starts_with(WStr::from_units(b"https://"))
     {
         // HTTP(S) is for Flash Remoting, which is just POST requests to the URL.
-        NetConnections::connect_to_flash_remoting(activation.context, this, url.to_string());
+        NetConnections::connect_to_flash_remoting(
+            activation.context,
+            this,
+            url.to_string(),
+            sandboxed_movie,
+        );
+    } else if url.starts_with(WStr::from_units(b"rtmp")) {
+        let mut arguments = Vec::new();
+        for arg in &args[1..] {
+            arguments.push(Rc::new(serialize(activation, *arg)));
+        }
+
+        // AVM1 only ever uses AMF0.
+        NetConnections::connect_to_rtmp(
+            activation.context,
+            this,
+            url.to_string(),
+            AMFVersion::AMF0,
+            arguments,
+            sandboxed_movie,
+        );
     } else {
         avm1_stub!(
             activation,
             "NetConnection",
             "connect",
-            "with non-null, non-http command"
+            "with non-null, non-http, non-rtmp command"
         );
     }
 
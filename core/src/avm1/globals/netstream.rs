@@ -1,19 +1,25 @@
 use crate::avm1::function::FunctionObject;
+use crate::avm1::globals::netconnection::NetConnection;
 use crate::avm1::object::NativeObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Activation, Error, Object, Value};
-use crate::avm1_stub;
 use crate::streams::NetStream;
 use crate::string::StringContext;
 
 pub fn constructor<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let netstream = NetStream::new(activation.gc(), Some(this.into()));
     this.set_native(activation.gc(), NativeObject::NetStream(netstream));
 
+    if let Some(Value::Object(connection)) = args.get(0) {
+        if NetConnection::cast((*connection).into()).is_some() {
+            netstream.set_connection(activation.gc(), (*connection).into());
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -22,6 +28,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "bufferTime" => property(get_buffer_time);
     "bytesLoaded" => property(get_bytes_loaded);
     "bytesTotal" => property(get_bytes_total);
+    "currentFps" => property(get_current_fps);
     "time" => property(get_time);
     "play" => method(play; DONT_ENUM | DONT_DELETE);
     "pause" => method(pause; DONT_ENUM | DONT_DELETE);
@@ -30,14 +37,12 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
 };
 
 fn get_buffer_length<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let NativeObject::NetStream(ns) = this.native() {
-        avm1_stub!(activation, "NetStream", "bufferLength");
-
-        return Ok(ns.buffer_time().into());
+        return Ok(ns.buffer_length().into());
     }
 
     Ok(Value::Undefined)
@@ -79,6 +84,18 @@ fn get_bytes_total<'gc>(
     Ok(Value::Undefined)
 }
 
+fn get_current_fps<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::NetStream(ns) = this.native() {
+        return Ok(ns.current_fps().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 fn play<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -142,8 +159,6 @@ fn set_buffer_time<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let NativeObject::NetStream(ns) = this.native() {
-        avm1_stub!(activation, "NetStream", "setBufferTime");
-
         let buffer_time = args
             .get(0)
             .cloned()
@@ -1,23 +1,30 @@
 use crate::avm1::function::FunctionObject;
+use crate::avm1::globals::array::ArrayBuilder;
+use crate::avm1::globals::netconnection::NetConnection;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
-use crate::avm1::{Activation, Attribute, Error, NativeObject, Object, Value};
+use crate::avm1::{
+    Activation, ActivationIdentifier, Attribute, Error, ExecutionReason, NativeObject, Object,
+    Value,
+};
 use crate::avm1_stub;
+use crate::context::UpdateContext;
 use crate::display_object::TDisplayObject;
+use crate::net_connection::{RemoteSharedObjectHandle, SharedObjectTarget, SyncUpdate};
 use crate::string::{AvmString, StringContext};
 use flash_lso::amf0::read::AMF0Decoder;
 use flash_lso::amf0::writer::{Amf0Writer, CacheKey, ObjWriter};
-use flash_lso::types::{Lso, ObjectId, Reference, Value as AmfValue};
+use flash_lso::types::{Element, Lso, ObjectId, Reference, Value as AmfValue};
 use gc_arena::{Collect, GcCell};
 use ruffle_macros::istr;
 use std::borrow::Cow;
 use std::collections::BTreeMap;
+use std::rc::Rc;
 
 #[derive(Default, Clone, Collect)]
 #[collect(require_static)]
 pub struct SharedObject {
     /// The local name of this shared object
     name: Option<String>,
-    // In future this will also handle remote SharedObjects
 }
 
 impl SharedObject {
@@ -445,10 +452,72 @@ fn get_local<'gc>(
 fn get_remote<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "getRemote");
-    Ok(Value::Undefined)
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .to_string();
+
+    const INVALID_CHARS: &str = "~%&\\;:\"',<>?# ";
+    if name.contains(|c| INVALID_CHARS.contains(c)) {
+        tracing::error!("SharedObject::get_remote: Invalid character in name");
+        return Ok(Value::Null);
+    }
+
+    let uri = match args.get(1) {
+        None | Some(Value::Undefined) | Some(Value::Null) => String::new(),
+        Some(uri) => uri
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .to_string(),
+    };
+    // A string persistence means the object is also stored locally, which we
+    // don't support; it's persistent on the server either way.
+    let persistent = args
+        .get(2)
+        .unwrap_or(&Value::Undefined)
+        .as_bool(activation.swf_version());
+
+    // Check if this is referencing an existing shared object
+    if let Some(SharedObjectTarget::Avm1(so)) = activation
+        .context
+        .net_connections
+        .find_shared_object(&uri, &name)
+    {
+        return Ok(so.into());
+    }
+
+    let constructor = activation
+        .context
+        .avm1
+        .prototypes()
+        .shared_object_constructor;
+    let this = constructor
+        .construct(activation, &[])?
+        .coerce_to_object(activation);
+
+    let data = Object::new(
+        &activation.context.strings,
+        Some(activation.context.avm1.prototypes().object),
+    );
+    this.define_value(
+        activation.gc(),
+        istr!("data"),
+        data.into(),
+        Attribute::DONT_DELETE,
+    );
+
+    activation.context.net_connections.add_shared_object(
+        SharedObjectTarget::Avm1(this),
+        uri,
+        name,
+        persistent,
+    );
+
+    Ok(this.into())
 }
 
 fn clear<'gc>(
@@ -464,6 +533,15 @@ fn clear<'gc>(
         data.delete(activation, *k);
     }
 
+    // A remote SharedObject is also disconnected.
+    if let Some(handle) = remote_handle(activation, this) {
+        activation
+            .context
+            .net_connections
+            .close_shared_object(handle);
+        return Ok(Value::Undefined);
+    }
+
     if let NativeObject::SharedObject(shared_object) = this.native() {
         let name = shared_object.read().name();
         activation.context.storage.remove_key(&name);
@@ -474,20 +552,45 @@ fn clear<'gc>(
 
 fn close<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "close");
+    match remote_handle(activation, this) {
+        Some(handle) => activation
+            .context
+            .net_connections
+            .close_shared_object(handle),
+        None => avm1_stub!(activation, "SharedObject", "close"),
+    }
     Ok(Value::Undefined)
 }
 
 fn connect<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "connect");
-    Ok(Value::Undefined)
+    let Some(handle) = remote_handle(activation, this) else {
+        avm1_stub!(
+            activation,
+            "SharedObject",
+            "connect",
+            "with local SharedObject"
+        );
+        return Ok(false.into());
+    };
+
+    let connection = args
+        .get(0)
+        .and_then(|connection| NetConnection::cast(*connection))
+        .and_then(|connection| connection.handle());
+    let connected = connection.is_some_and(|connection| {
+        activation
+            .context
+            .net_connections
+            .connect_shared_object(handle, connection)
+    });
+    Ok(connected.into())
 }
 
 pub(crate) fn flush<'gc>(
@@ -498,6 +601,10 @@ pub(crate) fn flush<'gc>(
     let NativeObject::SharedObject(shared_object) = this.native() else {
         return Ok(Value::Undefined);
     };
+    // Remote SharedObjects are stored by the server.
+    if remote_handle(activation, this).is_some() {
+        return Ok(true.into());
+    }
     let name = shared_object.read().name();
     let data = this
         .get(istr!("data"), activation)?
@@ -537,40 +644,253 @@ fn get_size<'gc>(
 
 fn send<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "send");
+    let Some(handle) = remote_handle(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+
+    let values = args
+        .iter()
+        .map(|arg| Rc::new(serialize(activation, *arg)))
+        .collect();
+    activation
+        .context
+        .net_connections
+        .send_to_shared_object(handle, values);
+
     Ok(Value::Undefined)
 }
 
 fn set_fps<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "setFps");
-    Ok(Value::Undefined)
+    let fps = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation)?;
+
+    let Some(handle) = remote_handle(activation, this) else {
+        return Ok(false.into());
+    };
+    Ok(activation
+        .context
+        .net_connections
+        .set_shared_object_fps(handle, fps)
+        .into())
 }
 
 fn on_status<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "onStatus");
+    // Does nothing by default; movies override it to receive status events.
     Ok(Value::Undefined)
 }
 
 fn on_sync<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "onSync");
+    // Does nothing by default; movies override it to receive changes.
     Ok(Value::Undefined)
 }
 
+fn remote_handle<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Option<RemoteSharedObjectHandle> {
+    activation
+        .context
+        .net_connections
+        .shared_object_handle(SharedObjectTarget::Avm1(this))
+}
+
+/// Creates an activation for calling into a remote SharedObject from outside
+/// of any script.
+fn remote_activation<'a, 'gc>(
+    context: &'a mut UpdateContext<'gc>,
+    name: &'static str,
+) -> Option<Activation<'a, 'gc>> {
+    let Some(root_clip) = context.stage.root_clip() else {
+        tracing::warn!("Ignored SharedObject callback as there's no root movie");
+        return None;
+    };
+    Some(Activation::from_nothing(
+        context,
+        ActivationIdentifier::root(name),
+        root_clip,
+    ))
+}
+
+/// Serializes the slots of a remote SharedObject's data, to find the changes
+/// to send to the server.
+pub fn serialize_slots<'gc>(
+    context: &mut UpdateContext<'gc>,
+    this: Object<'gc>,
+) -> Option<Vec<Element>> {
+    let mut activation = remote_activation(context, "[SharedObject sync]")?;
+    let data = this.get(istr!("data"), &mut activation).ok()?;
+    let Value::Object(data) = data else {
+        return None;
+    };
+    Some(new_lso(&mut activation, "", data).body)
+}
+
+/// Applies changes from the server to a remote SharedObject's data, returning
+/// the change list for `onSync`.
+pub fn apply_sync<'gc>(
+    context: &mut UpdateContext<'gc>,
+    this: Object<'gc>,
+    updates: Vec<SyncUpdate>,
+) -> Option<Object<'gc>> {
+    let mut activation = remote_activation(context, "[SharedObject sync]")?;
+    let data = this
+        .get(istr!("data"), &mut activation)
+        .ok()?
+        .coerce_to_object(&mut activation);
+    let reader = flash_lso::read::Reader::default();
+
+    let mut changes = Vec::with_capacity(updates.len());
+    for update in updates {
+        let (code, name, old_value) = match update {
+            SyncUpdate::Clear => {
+                for k in &data.get_keys(&mut activation, false) {
+                    data.delete(&mut activation, *k);
+                }
+                ("clear", None, None)
+            }
+            SyncUpdate::Change(name, value, code) => {
+                let name = AvmString::new_utf8(activation.gc(), name);
+                let old_value = data.get(name, &mut activation).ok()?;
+                let value = deserialize_value(
+                    &mut activation,
+                    &value,
+                    &reader.amf0_decoder,
+                    &mut BTreeMap::default(),
+                );
+                data.set(name, value, &mut activation).ok()?;
+                (code, Some(name), Some(old_value))
+            }
+            SyncUpdate::Success(name) => {
+                let name = AvmString::new_utf8(activation.gc(), name);
+                ("success", Some(name), None)
+            }
+            SyncUpdate::Delete(name) => {
+                let name = AvmString::new_utf8(activation.gc(), name);
+                let old_value = data.get(name, &mut activation).ok()?;
+                data.delete(&mut activation, name);
+                ("delete", Some(name), Some(old_value))
+            }
+        };
+
+        let change = Object::new(
+            &activation.context.strings,
+            Some(activation.context.avm1.prototypes().object),
+        );
+        let code = AvmString::new_utf8(activation.gc(), code);
+        change
+            .set(istr!("code"), code.into(), &mut activation)
+            .ok()?;
+        if let Some(name) = name {
+            change
+                .set(istr!("name"), name.into(), &mut activation)
+                .ok()?;
+        }
+        if let Some(old_value) = old_value {
+            let key = AvmString::new_utf8(activation.gc(), "oldValue");
+            change.set(key, old_value, &mut activation).ok()?;
+        }
+        changes.push(change.into());
+    }
+
+    Some(ArrayBuilder::new(&activation).with(changes))
+}
+
+/// Calls `onSync` with the changes made to a remote SharedObject.
+pub fn on_sync_event<'gc>(
+    context: &mut UpdateContext<'gc>,
+    this: Object<'gc>,
+    change_list: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    let Some(mut activation) = remote_activation(context, "[SharedObject onSync]") else {
+        return Ok(());
+    };
+    let method = AvmString::new_utf8(activation.gc(), "onSync");
+    this.call_method(
+        method,
+        &[change_list.into()],
+        &mut activation,
+        ExecutionReason::Special,
+    )?;
+    Ok(())
+}
+
+/// Calls `onStatus` with an info object made of the given properties.
+pub fn on_status_event<'gc>(
+    context: &mut UpdateContext<'gc>,
+    this: Object<'gc>,
+    info: Vec<(&str, &str)>,
+) -> Result<(), Error<'gc>> {
+    let Some(mut activation) = remote_activation(context, "[SharedObject onStatus]") else {
+        return Ok(());
+    };
+    let event = Object::new(
+        &activation.context.strings,
+        Some(activation.context.avm1.prototypes().object),
+    );
+    for (key, value) in info {
+        let key = AvmString::new_utf8(activation.gc(), key);
+        let value = AvmString::new_utf8(activation.gc(), value);
+        event.set(key, value.into(), &mut activation)?;
+    }
+    this.call_method(
+        istr!("onStatus"),
+        &[event.into()],
+        &mut activation,
+        ExecutionReason::Special,
+    )?;
+    Ok(())
+}
+
+/// Calls a method of a remote SharedObject, on behalf of `send`.
+pub fn call_handler<'gc>(
+    context: &mut UpdateContext<'gc>,
+    this: Object<'gc>,
+    method: &str,
+    arguments: &[Rc<AmfValue>],
+) -> Result<(), Error<'gc>> {
+    let Some(mut activation) = remote_activation(context, "[SharedObject send]") else {
+        return Ok(());
+    };
+    let reader = flash_lso::read::Reader::default();
+    let mut reference_cache = BTreeMap::default();
+    let arguments: Vec<_> = arguments
+        .iter()
+        .map(|argument| {
+            deserialize_value(
+                &mut activation,
+                argument,
+                &reader.amf0_decoder,
+                &mut reference_cache,
+            )
+        })
+        .collect();
+    let method = AvmString::new_utf8(activation.gc(), method);
+    this.call_method(
+        method,
+        &arguments,
+        &mut activation,
+        ExecutionReason::Special,
+    )?;
+    Ok(())
+}
+
 fn constructor<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -354,11 +354,17 @@ fn load_sound<'gc>(
                 }
             }
             sound.set_is_streaming(is_streaming);
+            let sandboxed_movie = activation
+                .context
+                .security_sandbox
+                .is_strict()
+                .then(|| activation.base_clip().movie());
             let future = activation.context.load_manager.load_sound_avm1(
                 activation.context.player.clone(),
                 this,
                 Request::get(url.to_utf8_lossy().into_owned()),
                 is_streaming,
+                sandboxed_movie,
             );
             activation.context.navigator.spawn_future(future);
         }
@@ -166,10 +166,16 @@ fn load<'gc>(
 
     let request = Request::get(url.to_utf8_lossy().into_owned());
 
+    let sandboxed_movie = activation
+        .context
+        .security_sandbox
+        .is_strict()
+        .then(|| activation.base_clip().movie());
     let future = activation.context.load_manager.load_stylesheet(
         activation.context.player.clone(),
         this,
         request,
+        sandboxed_movie,
     );
     activation.context.navigator.spawn_future(future);
 
@@ -565,10 +565,16 @@ fn spawn_xml_fetch<'gc>(
         this.set(loaded_string, false.into(), activation)?;
     }
 
+    let sandboxed_movie = activation
+        .context
+        .security_sandbox
+        .is_strict()
+        .then(|| activation.base_clip().movie());
     let future = activation.context.load_manager.load_form_into_load_vars(
         activation.context.player.clone(),
         loader_object,
         request,
+        sandboxed_movie,
     );
     activation.context.navigator.spawn_future(future);
 
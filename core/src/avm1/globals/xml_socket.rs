@@ -5,7 +5,7 @@ use crate::avm1::{property_decl::Declaration, Object};
 use crate::avm1::{Activation, Error, ExecutionReason, Value};
 use crate::context::UpdateContext;
 use crate::display_object::TDisplayObject;
-use crate::socket::SocketHandle;
+use crate::socket::{SandboxedConnection, SocketHandle};
 use crate::string::{AvmString, StringContext};
 use gc_arena::{Collect, Gc};
 use ruffle_macros::istr;
@@ -141,11 +141,27 @@ pub fn connect<'gc>(
             .unwrap_or(&Value::Undefined)
             .coerce_to_u16(activation)?;
 
+        let sandboxed =
+            activation
+                .context
+                .security_sandbox
+                .is_strict()
+                .then(|| SandboxedConnection {
+                    player: activation.context.player.clone(),
+                    movie: activation.base_clip().movie(),
+                });
+
         let UpdateContext {
             sockets, navigator, ..
         } = activation.context;
 
-        sockets.connect_avm1(*navigator, this, host.to_utf8_lossy().into_owned(), port);
+        sockets.connect_avm1(
+            *navigator,
+            this,
+            host.to_utf8_lossy().into_owned(),
+            port,
+            sandboxed,
+        );
 
         // NOTE: At this point we do not know if the connection will succeed
         //       because connecting is an asynchronous process, so we just return true.
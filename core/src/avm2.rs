@@ -63,6 +63,7 @@ pub mod property;
 mod property_map;
 mod qname;
 mod regexp;
+mod sampler;
 mod scope;
 pub mod script;
 #[cfg(feature = "known_stubs")]
@@ -91,6 +92,7 @@ pub use crate::avm2::object::{
     StageObject, TObject,
 };
 pub use crate::avm2::qname::QName;
+pub use crate::avm2::sampler::Sampler;
 pub use crate::avm2::value::Value;
 
 use self::api_version::ApiVersion;
@@ -122,6 +124,9 @@ pub struct Avm2<'gc> {
     /// The current call stack of the player.
     call_stack: GcRefLock<'gc, CallStack<'gc>>,
 
+    /// The state of `flash.sampler`.
+    sampler: Sampler<'gc>,
+
     /// This domain is used exclusively for classes from playerglobals
     playerglobals_domain: Domain<'gc>,
 
@@ -213,6 +218,7 @@ impl<'gc> Avm2<'gc> {
             stack: Vec::with_capacity(PREALLOCATED_STACK_SIZE),
             scope_stack: Vec::new(),
             call_stack: GcRefLock::new(mc, CallStack::new().into()),
+            sampler: Sampler::new(),
             playerglobals_domain,
             stage_domain,
             system_classes: None,
@@ -377,6 +383,20 @@ impl<'gc> Avm2<'gc> {
         });
     }
 
+    /// Runs the `flash.sampler` callback if the sample buffer filled up
+    /// since the last time it was run.
+    pub fn run_sampler_callback(context: &mut UpdateContext<'gc>) {
+        let Some(callback) = context.avm2.sampler.take_pending_callback() else {
+            return;
+        };
+
+        let domain = context.avm2.stage_domain();
+        let mut activation = Activation::from_domain(context, domain);
+        if let Err(e) = Value::from(callback).call(&mut activation, Value::Null, &[]) {
+            tracing::error!("Unhandled AVM2 error in sampler callback: {e:?}");
+        }
+    }
+
     /// Dispatch an event on an object.
     ///
     /// This will become its own self-contained activation and swallow
@@ -524,6 +544,41 @@ impl<'gc> Avm2<'gc> {
             .retain(|x| x.upgrade(context.gc_context).is_some());
     }
 
+    /// Determine if dispatching an event of the given type at `target` would
+    /// call any listeners on it or its display list ancestors.
+    pub fn will_trigger(target: Object<'gc>, event: AvmString<'gc>) -> bool {
+        events::will_trigger(target, event)
+    }
+
+    /// Remove the broadcast and `Stage` event listeners whose handlers were
+    /// defined in `domain` or one of its child domains.
+    ///
+    /// Broadcast listeners keep firing on objects that are no longer on the
+    /// display list, so they must be removed when unloading a movie.
+    pub fn remove_listeners_in_domain(context: &mut UpdateContext<'gc>, domain: Domain<'gc>) {
+        let gc_context = context.gc_context;
+        let listeners: Vec<_> = context
+            .avm2
+            .broadcast_list
+            .iter()
+            .flat_map(|(event_name, bucket)| {
+                bucket
+                    .iter()
+                    .filter_map(|object| object.upgrade(gc_context))
+                    .map(|object| (*event_name, object))
+            })
+            .collect();
+        let stage = context.stage.object2().as_object();
+
+        let mut activation = Activation::from_nothing(context);
+        for (event_name, object) in listeners {
+            events::remove_listeners_in_domain(&mut activation, object, Some(event_name), domain);
+        }
+        if let Some(stage) = stage {
+            events::remove_listeners_in_domain(&mut activation, stage, None, domain);
+        }
+    }
+
     pub fn run_stack_frame_for_callable(
         callable: Object<'gc>,
         receiver: Value<'gc>,
@@ -713,8 +768,18 @@ impl<'gc> Avm2<'gc> {
     }
 
     /// Pushes an executable on the call stack
-    pub fn push_call(&self, mc: &Mutation<'gc>, method: Method<'gc>, class: Option<Class<'gc>>) {
-        self.call_stack.borrow_mut(mc).push(method, class)
+    pub fn push_call(
+        &mut self,
+        mc: &Mutation<'gc>,
+        method: Method<'gc>,
+        class: Option<Class<'gc>>,
+    ) {
+        self.call_stack.borrow_mut(mc).push(method, class);
+
+        if self.sampler.is_sampling() {
+            self.sampler
+                .on_method_call(method, &self.call_stack.borrow());
+        }
     }
 
     /// Pushes script initializer (global init) on the call stack
@@ -731,6 +796,24 @@ impl<'gc> Avm2<'gc> {
         self.call_stack
     }
 
+    pub fn sampler(&self) -> &Sampler<'gc> {
+        &self.sampler
+    }
+
+    pub fn sampler_mut(&mut self) -> &mut Sampler<'gc> {
+        &mut self.sampler
+    }
+
+    /// Records the allocation of a new object with `flash.sampler`, if
+    /// sampling is active.
+    #[inline]
+    pub fn record_allocation(&mut self, object: Object<'gc>) {
+        if self.sampler.is_sampling() {
+            self.sampler
+                .on_new_object(object, &self.call_stack.borrow());
+        }
+    }
+
     // See: https://doc.rust-lang.org/std/vec/struct.Vec.html#method.push_within_capacity
     #[inline(always)]
     fn push_internal(&mut self, value: Value<'gc>) {
@@ -1724,6 +1724,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn op_new_object(&mut self, num_args: u32) -> Result<(), Error<'gc>> {
         let object = ScriptObject::new_object(self);
 
+        self.avm2().record_allocation(object);
+
         for _ in 0..num_args {
             let value = self.pop_stack();
             let name = self.pop_stack();
@@ -1764,6 +1766,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
         let new_class = ClassObject::from_class(self, class, base_class)?;
 
+        self.avm2().record_allocation(new_class.into());
+
         self.push_stack(new_class);
 
         Ok(())
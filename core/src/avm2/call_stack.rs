@@ -61,6 +61,26 @@ impl<'gc> CallStack<'gc> {
         }
     }
 
+    /// Returns the display name of every frame on the stack, innermost first.
+    ///
+    /// This is used by `flash.sampler` to build `StackFrame` objects.
+    pub fn frame_names(&self) -> Vec<WString> {
+        self.stack
+            .iter()
+            .rev()
+            .map(|call| {
+                let mut name = WString::new();
+                match call {
+                    CallNode::GlobalInit(_) => name.push_utf8("global$init"),
+                    CallNode::Method { method, class } => {
+                        display_function(&mut name, *method, *class)
+                    }
+                }
+                name
+            })
+            .collect()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.stack.is_empty()
     }
@@ -143,6 +143,19 @@ impl<'gc> Domain<'gc> {
         self.0.read().parent
     }
 
+    /// Determine if this domain is `ancestor`, or a (transitive) child of it.
+    pub fn is_within(self, ancestor: Domain<'gc>) -> bool {
+        let mut domain = Some(self);
+        while let Some(current) = domain {
+            if current == ancestor {
+                return true;
+            }
+            domain = current.parent_domain();
+        }
+
+        false
+    }
+
     /// Determine if something has been defined within the current domain (including parents)
     pub fn has_definition(self, name: QName<'gc>) -> bool {
         let read = self.0.read();
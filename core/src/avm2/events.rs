@@ -1,6 +1,7 @@
 //! Core event structure
 
 use crate::avm2::activation::Activation;
+use crate::avm2::domain::Domain;
 use crate::avm2::globals::slots::flash_events_event_dispatcher as slots;
 use crate::avm2::object::{EventObject, Object, TObject};
 use crate::avm2::value::Value;
@@ -254,6 +255,25 @@ impl<'gc> DispatchList<'gc> {
         }
     }
 
+    /// Remove every event handler that `keep` rejects.
+    ///
+    /// If `event` is given, only the handlers for that event are considered.
+    pub fn retain_event_listeners(
+        &mut self,
+        event: Option<AvmString<'gc>>,
+        mut keep: impl FnMut(Object<'gc>) -> bool,
+    ) {
+        for (name, event_sheaf) in self.0.iter_mut() {
+            if event.is_some_and(|event| event != *name) {
+                continue;
+            }
+
+            for (_prio, set) in event_sheaf.iter_mut() {
+                set.retain(|h| keep(h.handler));
+            }
+        }
+    }
+
     /// Determine if there are any event listeners in this dispatch list.
     pub fn has_event_listener(&self, event: AvmString<'gc>) -> bool {
         if let Some(event_sheaf) = self.get_event(event) {
@@ -332,6 +352,34 @@ impl Hash for EventHandler<'_> {
     }
 }
 
+/// Remove the listeners of an `EventDispatcher` whose handler functions were
+/// defined in `domain` or one of its child domains.
+///
+/// If `event` is given, only the listeners for that event are removed.
+pub fn remove_listeners_in_domain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    dispatcher: Object<'gc>,
+    event: Option<AvmString<'gc>>,
+    domain: Domain<'gc>,
+) {
+    let Some(dispatch_list) = dispatcher.get_slot(slots::DISPATCH_LIST).as_object() else {
+        return;
+    };
+
+    if let Some(mut dispatch_list) = dispatch_list.as_dispatch_mut(activation.gc()) {
+        dispatch_list.retain_event_listeners(event, |handler| {
+            handler.as_function_object().is_none_or(|function| {
+                !function
+                    .executable()
+                    .as_method()
+                    .translation_unit()
+                    .domain()
+                    .is_within(domain)
+            })
+        });
+    }
+}
+
 /// Retrieve the parent of a given `EventDispatcher`.
 ///
 /// `EventDispatcher` does not provide a generic way for it's subclasses to
@@ -350,6 +398,25 @@ pub fn parent_of(target: Object<'_>) -> Option<Object<'_>> {
     None
 }
 
+/// Determine if dispatching an event at `target` would call any listeners on
+/// it or its display list ancestors, like `EventDispatcher.willTrigger`.
+pub fn will_trigger<'gc>(target: Object<'gc>, event: AvmString<'gc>) -> bool {
+    let mut target = Some(target);
+    while let Some(object) = target {
+        if let Some(dispatch_list) = object.get_slot(slots::DISPATCH_LIST).as_object() {
+            if dispatch_list
+                .as_dispatch()
+                .is_some_and(|list| list.has_event_listener(event))
+            {
+                return true;
+            }
+        }
+        target = parent_of(object);
+    }
+
+    false
+}
+
 /// Call all of the event handlers on a given target.
 ///
 /// The `target` is the current target of the `event`. `event` must be a valid
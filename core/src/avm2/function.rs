@@ -95,6 +95,14 @@ impl<'gc> BoundMethod<'gc> {
         self.method
     }
 
+    pub fn scope(&self) -> ScopeChain<'gc> {
+        self.scope
+    }
+
+    pub fn bound_receiver(&self) -> Option<Value<'gc>> {
+        self.bound_receiver
+    }
+
     pub fn debug_full_name(&self) -> WString {
         let mut output = WString::new();
         display_function(&mut output, self.as_method(), self.bound_class());
@@ -105,6 +105,7 @@ pub struct SystemClasses<'gc> {
     pub progressevent: ClassObject<'gc>,
     pub httpstatusevent: ClassObject<'gc>,
     pub textevent: ClassObject<'gc>,
+    pub imeevent: ClassObject<'gc>,
     pub errorevent: ClassObject<'gc>,
     pub ioerrorevent: ClassObject<'gc>,
     pub securityerrorevent: ClassObject<'gc>,
@@ -166,12 +167,22 @@ pub struct SystemClasses<'gc> {
     pub font: ClassObject<'gc>,
     pub textline: ClassObject<'gc>,
     pub sampledataevent: ClassObject<'gc>,
+    pub activityevent: ClassObject<'gc>,
+    pub datagramsocketdataevent: ClassObject<'gc>,
+    pub syncevent: ClassObject<'gc>,
+    pub netstreaminfo: ClassObject<'gc>,
+    pub camera: ClassObject<'gc>,
+    pub microphone: ClassObject<'gc>,
     pub avm1movie: ClassObject<'gc>,
     pub focusevent: ClassObject<'gc>,
     pub dictionary: ClassObject<'gc>,
     pub id3info: ClassObject<'gc>,
     pub textrun: ClassObject<'gc>,
     pub sharedobject: ClassObject<'gc>,
+    pub sample: ClassObject<'gc>,
+    pub newobjectsample: ClassObject<'gc>,
+    pub deleteobjectsample: ClassObject<'gc>,
+    pub stackframe: ClassObject<'gc>,
 }
 
 #[derive(Clone, Collect)]
@@ -268,6 +279,7 @@ impl<'gc> SystemClasses<'gc> {
             progressevent: object,
             httpstatusevent: object,
             textevent: object,
+            imeevent: object,
             errorevent: object,
             ioerrorevent: object,
             securityerrorevent: object,
@@ -329,12 +341,22 @@ impl<'gc> SystemClasses<'gc> {
             font: object,
             textline: object,
             sampledataevent: object,
+            activityevent: object,
+            datagramsocketdataevent: object,
+            syncevent: object,
+            netstreaminfo: object,
+            camera: object,
+            microphone: object,
             avm1movie: object,
             focusevent: object,
             dictionary: object,
             id3info: object,
             textrun: object,
             sharedobject: object,
+            sample: object,
+            newobjectsample: object,
+            deleteobjectsample: object,
+            stackframe: object,
         }
     }
 }
@@ -670,9 +692,17 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ),
             ("flash.errors", "IOError", ioerror),
             ("flash.errors", "EOFError", eoferror),
+            ("flash.events", "ActivityEvent", activityevent),
+            (
+                "flash.events",
+                "DatagramSocketDataEvent",
+                datagramsocketdataevent
+            ),
             ("flash.events", "Event", event),
             ("flash.events", "EventDispatcher", eventdispatcher),
+            ("flash.events", "SyncEvent", syncevent),
             ("flash.events", "TextEvent", textevent),
+            ("flash.events", "IMEEvent", imeevent),
             ("flash.events", "ErrorEvent", errorevent),
             ("flash.events", "KeyboardEvent", keyboardevent),
             ("flash.events", "ProgressEvent", progressevent),
@@ -694,14 +724,21 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.geom", "Rectangle", rectangle),
             ("flash.geom", "Transform", transform),
             ("flash.geom", "ColorTransform", colortransform),
+            ("flash.media", "Camera", camera),
             ("flash.media", "ID3Info", id3info),
+            ("flash.media", "Microphone", microphone),
             ("flash.media", "SoundChannel", soundchannel),
             ("flash.media", "SoundTransform", soundtransform),
             ("flash.media", "Video", video),
             ("flash.net", "URLVariables", urlvariables),
             ("flash.net", "FileReference", filereference),
             ("flash.net", "FileFilter", filefilter),
+            ("flash.net", "NetStreamInfo", netstreaminfo),
             ("flash.net", "SharedObject", sharedobject),
+            ("flash.sampler", "Sample", sample),
+            ("flash.sampler", "NewObjectSample", newobjectsample),
+            ("flash.sampler", "DeleteObjectSample", deleteobjectsample),
+            ("flash.sampler", "StackFrame", stackframe),
             ("flash.utils", "ByteArray", bytearray),
             ("flash.utils", "Dictionary", dictionary),
             ("flash.system", "ApplicationDomain", application_domain),
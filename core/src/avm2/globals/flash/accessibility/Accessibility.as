@@ -1,20 +1,11 @@
 package flash.accessibility {
-    import __ruffle__.stub_method;
     import flash.display.DisplayObject;
 
     public final class Accessibility {
-        private static var _active:Boolean;
+        public static native function sendEvent(source:DisplayObject, childID:uint, eventType:uint, nonHTML:Boolean = false):void;
 
-        public static function sendEvent(source:DisplayObject, childID:uint, eventType:uint, nonHTML:Boolean = false):void {
-            stub_method("flash.accessibility.Accessibility", "sendEvent");
-        }
+        public static native function updateProperties():void;
 
-        public static function updateProperties():void {
-            stub_method("flash.accessibility.Accessibility", "updateProperties");
-        }
-
-        public static function get active():Boolean {
-            return _active;
-        }
+        public static native function get active():Boolean;
     }
 }
@@ -3,6 +3,7 @@ package flash.accessibility {
 
     public class AccessibilityImplementation {
         public var errno: uint;
+        [Ruffle(NativeAccessible)]
         public var stub: Boolean;
 
         public function AccessibilityImplementation() {
@@ -1,9 +1,14 @@
 package flash.accessibility {
     public class AccessibilityProperties {
+        [Ruffle(NativeAccessible)]
         public var name: String;
+        [Ruffle(NativeAccessible)]
         public var description: String;
+        [Ruffle(NativeAccessible)]
         public var shortcut: String;
+        [Ruffle(NativeAccessible)]
         public var silent: Boolean;
+        [Ruffle(NativeAccessible)]
         public var forceSimple: Boolean;
         public var noAutoLabeling: Boolean;
 
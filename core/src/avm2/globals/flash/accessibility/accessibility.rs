@@ -0,0 +1,45 @@
+//! `flash.accessibility.Accessibility` native methods
+
+use crate::accessibility::node_id;
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Value};
+
+/// Implements `Accessibility.active`
+pub fn get_active<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.accessibility.is_active().into())
+}
+
+/// Implements `Accessibility.sendEvent`
+pub fn send_event<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let source = args.get_object(activation, 0, "source")?;
+    let child_id = args.get_u32(activation, 1)?;
+    let event_type = args.get_u32(activation, 2)?;
+
+    if let Some(source) = source.as_display_object() {
+        let id = node_id(source, activation.context);
+        activation
+            .context
+            .accessibility
+            .send_event(id, child_id, event_type);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `Accessibility.updateProperties`
+pub fn update_properties<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.accessibility_tracker.invalidate();
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,5 @@
+//! `flash.desktop` namespace
+
+pub mod clipboard;
+pub mod native_application;
+pub mod native_process;
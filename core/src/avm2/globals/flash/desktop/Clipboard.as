@@ -1,12 +1,22 @@
 package flash.desktop {
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
-    import flash.system.System;
+    import flash.display.BitmapData;
+    import flash.filesystem.File;
+    import flash.utils.ByteArray;
 
+    // The general clipboard is backed by the system clipboard for the standard formats.
+    // Data in custom formats, data for other clipboards and deferred data handlers are kept here.
     public class Clipboard {
-        private static var _generalClipboard = new Clipboard();
+        private static var _generalClipboard:Clipboard = null;
+
+        private var _general:Boolean = false;
+        private var _data:Object = {};
+        private var _handlers:Object = {};
 
         public static function get generalClipboard(): Clipboard {
+            if (Clipboard._generalClipboard == null) {
+                Clipboard._generalClipboard = new Clipboard();
+                Clipboard._generalClipboard._general = true;
+            }
             return Clipboard._generalClipboard;
         }
 
@@ -14,41 +24,111 @@ package flash.desktop {
             // TODO: This should only be callable in AIR
         }
 
+        private static native function getSystemFormats():Array;
+        private static native function getSystemData(format:String):Object;
+        private static native function setSystemData(format:String, data:Object):Boolean;
+        // Clears every format if `format` is null.
+        private static native function clearSystemData(format:String):void;
+
+        private static function isSystemFormat(format:String):Boolean {
+            return format == ClipboardFormats.TEXT_FORMAT ||
+                format == ClipboardFormats.HTML_FORMAT ||
+                format == ClipboardFormats.RICH_TEXT_FORMAT ||
+                format == ClipboardFormats.URL_FORMAT ||
+                format == ClipboardFormats.FILE_LIST_FORMAT ||
+                format == ClipboardFormats.BITMAP_FORMAT;
+        }
+
+        private function usesSystem(format:String):Boolean {
+            return this._general && isSystemFormat(format);
+        }
+
         public function get formats(): Array {
-            stub_getter("flash.desktop.Clipboard", "formats");
-            return new Array();
+            var formats:Array = this._general ? getSystemFormats() : [];
+            var format:String;
+            for (format in this._data) {
+                if (formats.indexOf(format) < 0) {
+                    formats.push(format);
+                }
+            }
+            for (format in this._handlers) {
+                if (formats.indexOf(format) < 0) {
+                    formats.push(format);
+                }
+            }
+            return formats;
         }
 
         public function clear(): void {
-            stub_method("flash.desktop.Clipboard", "clear");
+            this._data = {};
+            this._handlers = {};
+            if (this._general) {
+                clearSystemData(null);
+            }
         }
 
         public function clearData(format: String): void {
-            stub_method("flash.desktop.Clipboard", "clearData");
+            delete this._data[format];
+            delete this._handlers[format];
+            if (this.usesSystem(format)) {
+                clearSystemData(format);
+            }
         }
 
         public function getData(format: String, transferMode: String = ClipboardTransferMode.ORIGINAL_PREFERRED): Object {
-            stub_method("flash.desktop.Clipboard", "getData");
-            return null;
+            if (this._handlers.hasOwnProperty(format)) {
+                // The handler is only called once; its result replaces it.
+                var handler:Function = this._handlers[format];
+                delete this._handlers[format];
+                this.setData(format, handler());
+            }
+
+            if (!this.usesSystem(format)) {
+                return this._data.hasOwnProperty(format) ? this._data[format] : null;
+            }
+
+            var data:Object = getSystemData(format);
+            if (format == ClipboardFormats.FILE_LIST_FORMAT && data != null) {
+                var files:Array = [];
+                for each (var path:String in data) {
+                    files.push(new File(path));
+                }
+                return files;
+            }
+            return data;
         }
 
         public function hasFormat(format: String): Boolean {
-            stub_method("flash.desktop.Clipboard", "hasFormat");
-            return false;
+            return this.formats.indexOf(format) >= 0;
         }
 
         public function setData(format: String, data: Object, serializable: Boolean = true): Boolean {
-            stub_method("flash.desktop.Clipboard", "setData");
-            if (format == ClipboardFormats.TEXT_FORMAT) {
-                System.setClipboard(data);
+            delete this._handlers[format];
+            if (!this.usesSystem(format)) {
+                this._data[format] = data;
                 return true;
             }
-            return false;
+
+            if (format == ClipboardFormats.FILE_LIST_FORMAT) {
+                var files:Array = data as Array;
+                if (files == null) {
+                    return false;
+                }
+                var paths:Array = [];
+                for each (var file:File in files) {
+                    paths.push(file.nativePath);
+                }
+                data = paths;
+            } else if (format == ClipboardFormats.BITMAP_FORMAT && !(data is BitmapData)) {
+                return false;
+            }
+            return setSystemData(format, data);
         }
 
         public function setDataHandler(format: String, handler: Function, serializable: Boolean = true): Boolean {
-            stub_method("flash.desktop.Clipboard", "setDataHandler");
-            return false;
+            delete this._data[format];
+            this._handlers[format] = handler;
+            return true;
         }
     }
 }
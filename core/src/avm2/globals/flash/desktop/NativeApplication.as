@@ -3,10 +3,8 @@ package flash.desktop
   import flash.display.NativeWindow;
   import flash.events.InvokeEvent;
   import flash.events.Event;
-  import flash.events.TimerEvent;
   import flash.events.EventDispatcher;
-  import flash.system.Security;
-  import flash.utils.Timer;
+  import flash.filesystem.File;
   import flash.utils.setTimeout;
   import __ruffle__.stub_method;
   import __ruffle__.stub_getter;
@@ -19,16 +17,38 @@ package flash.desktop
 
     private var _openedWindows:Array = [];
 
-    private var _idleThreshold:int = 300;
+    private var _applicationDescriptor:XML;
+
+    // The invocation the application was launched with, which is
+    // delivered once a listener for it is registered.
+    private var _pendingInvoke:InvokeEvent;
 
     public function NativeApplication()
     {
       super();
-      // TODO
-      setTimeout(function():void
+      this.registerApplication();
+
+      var directory:String = getCurrentDirectory();
+      this._pendingInvoke = new InvokeEvent(
+        InvokeEvent.INVOKE,
+        false,
+        false,
+        directory != null ? new File(directory) : null,
+        getArguments()
+      );
+
+      var descriptor:String = getDescriptor();
+      if (descriptor != null)
       {
-        dispatchEvent(new InvokeEvent(InvokeEvent.INVOKE, false, false, null, []));
-      }, 500);
+        try
+        {
+          this._applicationDescriptor = new XML(descriptor);
+        }
+        catch (e:Error)
+        {
+          // A malformed descriptor is treated as if there was none.
+        }
+      }
     }
 
     public static function get nativeApplication():NativeApplication
@@ -38,6 +58,27 @@ package flash.desktop
       return _instance;
     }
 
+    private native function registerApplication():void;
+    private static native function getArguments():Array;
+    private static native function getCurrentDirectory():String;
+    private static native function getDescriptor():String;
+
+    override public function addEventListener(type:String, listener:Function, useCapture:Boolean = false, priority:int = 0, useWeakReference:Boolean = false):void
+    {
+      super.addEventListener(type, listener, useCapture, priority, useWeakReference);
+
+      if (type == InvokeEvent.INVOKE && this._pendingInvoke != null)
+      {
+        var event:InvokeEvent = this._pendingInvoke;
+        this._pendingInvoke = null;
+        var self:NativeApplication = this;
+        setTimeout(function():void
+        {
+          self.dispatchEvent(event);
+        }, 0);
+      }
+    }
+
     public static function get supportsMenu():Boolean
     {
       stub_getter("flash.desktop.NativeApplication", "supportsMenu");
@@ -70,10 +111,7 @@ package flash.desktop
       return false;
     }
 
-    public function exit(exitCode:int = 0):void
-    {
-      stub_method("flash.desktop.NativeApplication", "exit");
-    }
+    public native function exit(exitCode:int = 0):void;
 
     public function get runtimeVersion():String
     {
@@ -89,7 +127,18 @@ package flash.desktop
 
     public function get applicationID():String
     {
-      stub_getter("flash.desktop.NativeApplication", "applicationID");
+      if (this._applicationDescriptor == null)
+      {
+        return "";
+      }
+      // The descriptor's namespace depends on the AIR version it targets.
+      for each (var child:XML in this._applicationDescriptor.children())
+      {
+        if (child.localName() == "id")
+        {
+          return child.toString();
+        }
+      }
       return "";
     }
 
@@ -101,8 +150,7 @@ package flash.desktop
 
     public function get applicationDescriptor():XML
     {
-      stub_getter("flash.desktop.NativeApplication", "applicationDescriptor");
-      return null;
+      return this._applicationDescriptor != null ? this._applicationDescriptor.copy() : null;
     }
 
     public function get autoExit():Boolean
@@ -153,33 +201,21 @@ package flash.desktop
 
     public function get activeWindow():NativeWindow
     {
-      stub_getter("flash.desktop.NativeApplication", "activeWindow");
-      return _openedWindows[0];
+      // Only the primary window can be focused.
+      return this.isActive && this._openedWindows.length > 0 ? this._openedWindows[0] : null;
     }
 
+    // Windows add themselves to this when they're created, and remove themselves when closed.
     public function get openedWindows():Array
     {
-      stub_getter("flash.desktop.NativeApplication", "openedWindows");
-      return _openedWindows;
+      return this._openedWindows;
     }
 
-    public function get timeSinceLastUserInput():int
-    {
-      stub_getter("flash.desktop.NativeApplication", "timeSinceLastUserInput");
-      return 100;
-    }
+    public native function get timeSinceLastUserInput():int;
 
-    public function get idleThreshold():int
-    {
-      stub_getter("flash.desktop.NativeApplication", "idleThreshold");
-      return this._idleThreshold;
-    }
+    public native function get idleThreshold():int;
 
-    public function set idleThreshold(value:int):void
-    {
-      stub_setter("flash.desktop.NativeApplication", "idleThreshold");
-      this._idleThreshold = value;
-    }
+    public native function set idleThreshold(value:int):void;
 
     public function copy():Boolean
     {
@@ -256,10 +292,6 @@ package flash.desktop
 
     // [API("721")] Ruffle doesn't support this API Version
     [API("681")]
-    public function get isActive():Boolean
-    {
-      stub_getter("flash.desktop.NativeApplication", "isActive");
-      return true;
-    }
+    public native function get isActive():Boolean;
   }
 }
@@ -1,20 +1,98 @@
 package flash.desktop {
-    import __ruffle__.stub_method;
-
+    import flash.errors.IllegalOperationError;
+    import flash.events.Event;
     import flash.events.EventDispatcher;
+    import flash.events.NativeProcessExitEvent;
+    import flash.utils.ByteArray;
+    import flash.utils.IDataInput;
+    import flash.utils.IDataOutput;
+    import flash.utils.setTimeout;
 
+    // Output is appended to the `standardOutput` and `standardError` buffers as it arrives,
+    // and anything written to `standardInput` is sent to the process once per frame.
     [API("668")]
     public class NativeProcess extends EventDispatcher {
+        private var _running:Boolean = false;
+
+        [Ruffle(NativeAccessible)]
+        private var _standardOutput:ByteArray = new ByteArray();
+
+        [Ruffle(NativeAccessible)]
+        private var _standardError:ByteArray = new ByteArray();
+
+        [Ruffle(NativeAccessible)]
+        private var _standardInput:ByteArray = new ByteArray();
+
         public function NativeProcess() {
             super();
         }
 
-        public static function get isSupported():Boolean {
-            return false;
+        public static native function get isSupported():Boolean;
+
+        public function get running():Boolean {
+            return this._running;
         }
 
+        public function get standardOutput():IDataInput {
+            return this._standardOutput;
+        }
+
+        public function get standardError():IDataInput {
+            return this._standardError;
+        }
+
+        public function get standardInput():IDataOutput {
+            return this._standardInput;
+        }
+
+        private native function startProcess(executable:String, arguments:Array, workingDirectory:String):void;
+        private native function closeStandardInput():void;
+        private native function exitProcess(force:Boolean):void;
+
         public function start(info:NativeProcessStartupInfo):void {
-            stub_method("flash.desktop.NativeProcess", "start");
+            if (this._running) {
+                throw new IllegalOperationError("Error #3213: Cannot start a NativeProcess that is already running.", 3213);
+            }
+            if (info.executable == null) {
+                throw new ArgumentError("Error #3214: NativeProcessStartupInfo.executable does not specify a valid executable file.", 3214);
+            }
+
+            var args:Array = [];
+            if (info.arguments != null) {
+                for each (var arg:String in info.arguments) {
+                    args.push(arg);
+                }
+            }
+            var workingDirectory:String = info.workingDirectory != null ? info.workingDirectory.nativePath : null;
+
+            this._standardOutput = new ByteArray();
+            this._standardError = new ByteArray();
+            this._standardInput = new ByteArray();
+            this.startProcess(info.executable.nativePath, args, workingDirectory);
+            this._running = true;
+        }
+
+        public function closeInput():void {
+            if (!this._running) {
+                return;
+            }
+            this.closeStandardInput();
+            var self:NativeProcess = this;
+            setTimeout(function():void {
+                self.dispatchEvent(new Event(Event.STANDARD_INPUT_CLOSE));
+            }, 0);
+        }
+
+        public function exit(force:Boolean = false):void {
+            if (this._running) {
+                this.exitProcess(force);
+            }
+        }
+
+        [Ruffle(NativeCallable)]
+        internal function processExited(exitCode:Number):void {
+            this._running = false;
+            this.dispatchEvent(new NativeProcessExitEvent(NativeProcessExitEvent.EXIT, false, false, exitCode));
         }
     }
 }
@@ -0,0 +1,169 @@
+//! `flash.desktop.Clipboard` builtin
+//!
+//! These natives only deal with the system clipboard, which backs `Clipboard.generalClipboard`.
+//! Custom formats and deferred data are handled in ActionScript.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::object::{ArrayObject, BitmapDataObject, ByteArrayObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::ui::{ClipboardData, ClipboardFormat};
+use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper, Color};
+use crate::string::AvmString;
+use gc_arena::GcCell;
+
+fn clipboard_format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+) -> Result<Option<ClipboardFormat>, Error<'gc>> {
+    let format = args.get_string(activation, 0)?;
+    Ok(ClipboardFormat::from_name(&format.to_utf8_lossy()))
+}
+
+pub fn get_system_formats<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let formats = activation.context.ui.clipboard_formats();
+    let formats = formats
+        .into_iter()
+        .map(|format| AvmString::new_utf8(activation.gc(), format.name()).into());
+    let storage = ArrayStorage::from_iter(formats.collect::<Vec<Value<'gc>>>());
+    Ok(ArrayObject::from_storage(activation, storage).into())
+}
+
+pub fn get_system_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(format) = clipboard_format(activation, args)? else {
+        return Ok(Value::Null);
+    };
+    let Some(data) = activation.context.ui.clipboard_data(format) else {
+        return Ok(Value::Null);
+    };
+
+    Ok(match data {
+        ClipboardData::Text(text)
+        | ClipboardData::Html(text)
+        | ClipboardData::Url(text)
+        | ClipboardData::FlashRichText(text) => AvmString::new_utf8(activation.gc(), text).into(),
+        ClipboardData::RichText(bytes) => {
+            ByteArrayObject::from_storage(activation, ByteArrayStorage::from_vec(bytes))?.into()
+        }
+        ClipboardData::FileList(paths) => {
+            // These are turned into `File`s by the caller.
+            let paths = paths
+                .into_iter()
+                .map(|path| AvmString::new_utf8(activation.gc(), path).into())
+                .collect::<Vec<Value<'gc>>>();
+            ArrayObject::from_storage(activation, ArrayStorage::from_iter(paths)).into()
+        }
+        ClipboardData::Bitmap {
+            width,
+            height,
+            rgba,
+        } => {
+            let pixels = rgba
+                .chunks_exact(4)
+                .map(|p| Color::argb(p[3], p[0], p[1], p[2]).to_premultiplied_alpha(true))
+                .collect();
+            let bitmap_data = BitmapData::new_with_pixels(width, height, true, pixels);
+            let bitmap_data = BitmapDataWrapper::new(GcCell::new(activation.gc(), bitmap_data));
+            let class = activation.avm2().classes().bitmapdata;
+            BitmapDataObject::from_bitmap_data_internal(activation, bitmap_data, class)?.into()
+        }
+    })
+}
+
+pub fn set_system_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(format) = clipboard_format(activation, args)? else {
+        return Ok(false.into());
+    };
+    let value = args.get_value(1);
+
+    let data = match format {
+        ClipboardFormat::Text => {
+            ClipboardData::Text(value.coerce_to_string(activation)?.to_string())
+        }
+        ClipboardFormat::Html => {
+            ClipboardData::Html(value.coerce_to_string(activation)?.to_string())
+        }
+        ClipboardFormat::Url => ClipboardData::Url(value.coerce_to_string(activation)?.to_string()),
+        ClipboardFormat::FlashRichText => {
+            ClipboardData::FlashRichText(value.coerce_to_string(activation)?.to_string())
+        }
+        ClipboardFormat::RichText => {
+            let bytes = match value
+                .as_object()
+                .and_then(|o| o.as_bytearray().map(|b| b.bytes().to_vec()))
+            {
+                Some(bytes) => bytes,
+                None => value
+                    .coerce_to_string(activation)?
+                    .to_utf8_lossy()
+                    .into_owned()
+                    .into_bytes(),
+            };
+            ClipboardData::RichText(bytes)
+        }
+        ClipboardFormat::FileList => {
+            // The caller has already turned the `File`s into native paths.
+            let Some(paths) = value
+                .as_object()
+                .and_then(|o| o.as_array_storage().map(|a| a.iter().collect::<Vec<_>>()))
+            else {
+                return Ok(false.into());
+            };
+            let mut files = Vec::with_capacity(paths.len());
+            for path in paths.into_iter().flatten() {
+                files.push(path.coerce_to_string(activation)?.to_string());
+            }
+            ClipboardData::FileList(files)
+        }
+        ClipboardFormat::Bitmap => {
+            let Some(bitmap) = value.as_object().and_then(|o| o.as_bitmap_data()) else {
+                return Ok(false.into());
+            };
+            bitmap.check_valid(activation)?;
+
+            let bitmap = bitmap.sync(activation.context.renderer);
+            let bitmap = bitmap.read();
+            let mut rgba = Vec::with_capacity(bitmap.pixels().len() * 4);
+            for pixel in bitmap.pixels() {
+                let pixel = pixel.to_un_multiplied_alpha();
+                rgba.extend_from_slice(&[pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]);
+            }
+            ClipboardData::Bitmap {
+                width: bitmap.width(),
+                height: bitmap.height(),
+                rgba,
+            }
+        }
+    };
+
+    activation.context.ui.set_clipboard_data(data);
+    Ok(true.into())
+}
+
+pub fn clear_system_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if matches!(args.get_value(0), Value::Null) {
+        activation.context.ui.clear_clipboard();
+    } else if let Some(format) = clipboard_format(activation, args)? {
+        activation.context.ui.clear_clipboard_format(format);
+    }
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,128 @@
+//! `flash.desktop.NativeApplication` builtin
+//!
+//! The lifecycle events themselves are dispatched from `crate::native_application`.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::error::{make_error_2004, Error2004Type};
+use crate::avm2::object::ArrayObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::events::PlayerNotification;
+use crate::string::AvmString;
+use std::time::Duration;
+
+/// The range of `idleThreshold` values accepted by AIR, in seconds.
+const IDLE_THRESHOLD_RANGE: std::ops::RangeInclusive<i32> = 5..=86400;
+
+pub fn register_application<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this
+        .as_object()
+        .expect("NativeApplication should be an object");
+    activation.context.native_application.set_avm2_object(this);
+    Ok(Value::Undefined)
+}
+
+pub fn get_arguments<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let arguments = activation
+        .context
+        .native_application
+        .launch()
+        .arguments
+        .clone();
+    let arguments = arguments
+        .iter()
+        .map(|argument| AvmString::new_utf8(activation.gc(), argument).into())
+        .collect::<Vec<Value<'gc>>>();
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_iter(arguments)).into())
+}
+
+pub fn get_current_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let launch = activation.context.native_application.launch();
+    Ok(match launch.current_directory.clone() {
+        Some(directory) => AvmString::new_utf8(activation.gc(), directory).into(),
+        None => Value::Null,
+    })
+}
+
+pub fn get_descriptor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let launch = activation.context.native_application.launch();
+    Ok(match launch.descriptor.clone() {
+        Some(descriptor) => AvmString::new_utf8(activation.gc(), descriptor).into(),
+        None => Value::Null,
+    })
+}
+
+pub fn exit<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let exit_code = args.get_i32(activation, 0)?;
+    activation
+        .context
+        .send_notification(PlayerNotification::Exit(exit_code));
+    Ok(Value::Undefined)
+}
+
+pub fn get_is_active<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.native_application.is_active().into())
+}
+
+pub fn get_time_since_last_user_input<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let elapsed = activation
+        .context
+        .native_application
+        .time_since_last_user_input();
+    Ok((elapsed.as_secs().min(i32::MAX as u64) as i32).into())
+}
+
+pub fn get_idle_threshold<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let idle_threshold = activation.context.native_application.idle_threshold();
+    Ok((idle_threshold.as_secs() as i32).into())
+}
+
+pub fn set_idle_threshold<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let idle_threshold = args.get_i32(activation, 0)?;
+    if !IDLE_THRESHOLD_RANGE.contains(&idle_threshold) {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    }
+    activation
+        .context
+        .native_application
+        .set_idle_threshold(Duration::from_secs(idle_threshold as u64));
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,117 @@
+//! `flash.desktop.NativeProcess` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::{argument_error, illegal_operation_error};
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::process::{ProcessError, ProcessStartupInfo};
+use crate::native_process::NativeProcesses;
+
+fn start_error<'gc>(activation: &mut Activation<'_, 'gc>, error: ProcessError) -> Error<'gc> {
+    let result = match error {
+        ProcessError::InvalidExecutable => argument_error(
+            activation,
+            "Error #3214: NativeProcessStartupInfo.executable does not specify a valid executable file.",
+            3214,
+        ),
+        ProcessError::Unsupported => illegal_operation_error(
+            activation,
+            &format!("Error #3219: The NativeProcess could not be started. '{error}'"),
+            3219,
+        ),
+    };
+    match result {
+        Ok(err) => Error::AvmError(err),
+        Err(e) => e,
+    }
+}
+
+pub fn get_is_supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.process.is_supported().into())
+}
+
+pub fn start_process<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let executable = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let arguments = args.get_object(activation, 1, "arguments")?;
+    let working_directory = match args.get_value(2) {
+        Value::Null | Value::Undefined => None,
+        value => Some(
+            value
+                .coerce_to_string(activation)?
+                .to_utf8_lossy()
+                .into_owned(),
+        ),
+    };
+
+    let values: Vec<Value<'gc>> = arguments
+        .as_array_storage()
+        .map(|storage| {
+            storage
+                .iter()
+                .map(|v| v.unwrap_or(Value::Undefined))
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut arguments = Vec::with_capacity(values.len());
+    for value in values {
+        arguments.push(
+            value
+                .coerce_to_string(activation)?
+                .to_utf8_lossy()
+                .into_owned(),
+        );
+    }
+
+    let info = ProcessStartupInfo {
+        executable,
+        arguments,
+        working_directory,
+    };
+    let handle = activation
+        .context
+        .process
+        .start(info)
+        .map_err(|e| start_error(activation, e))?;
+    activation.context.native_processes.add(handle, this);
+    Ok(Value::Undefined)
+}
+
+pub fn close_standard_input<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(handle) = activation.context.native_processes.handle_for(this) {
+        NativeProcesses::flush_standard_input(activation.context, handle, this);
+        activation.context.process.close_standard_input(handle);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn exit_process<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let force = args.get_bool(0);
+
+    if let Some(handle) = activation.context.native_processes.handle_for(this) {
+        activation.context.process.exit(handle, force);
+    }
+    Ok(Value::Undefined)
+}
@@ -2,6 +2,7 @@ package flash.display {
 
     import __ruffle__.stub_method;
 
+    import flash.accessibility.Accessibility;
     import flash.accessibility.AccessibilityProperties;
     import flash.geom.Rectangle;
     import flash.geom.Transform;
@@ -15,6 +16,7 @@ package flash.display {
 
     [Ruffle(Abstract)]
     public class DisplayObject extends EventDispatcher implements IBitmapDrawable {
+        [Ruffle(NativeAccessible)]
         private var _accessibilityProperties:AccessibilityProperties;
 
         public native function DisplayObject();
@@ -24,6 +26,7 @@ package flash.display {
         }
         public function set accessibilityProperties(value:AccessibilityProperties):void {
             this._accessibilityProperties = value;
+            Accessibility.updateProperties();
         }
 
         public native function get alpha():Number;
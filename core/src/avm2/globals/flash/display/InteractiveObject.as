@@ -2,12 +2,14 @@ package flash.display {
     import __ruffle__.stub_method;
     import __ruffle__.stub_setter;
 
+    import flash.accessibility.Accessibility;
     import flash.accessibility.AccessibilityImplementation;
     import flash.geom.Rectangle;
     import flash.ui.ContextMenu;
 
     [Ruffle(Abstract)]
     public class InteractiveObject extends DisplayObject {
+        [Ruffle(NativeAccessible)]
         private var _accessibilityImpl:AccessibilityImplementation = null;
         private var _needsSoftKeyboard:Boolean = false;
         private var _softKeyboardInputAreaOfInterest:Rectangle = null;
@@ -16,8 +18,8 @@ package flash.display {
             return this._accessibilityImpl;
         }
         public function set accessibilityImplementation(value:AccessibilityImplementation):void {
-            stub_setter("flash.display.InteractiveObject", "accessibilityImplementation");
             this._accessibilityImpl = value;
+            Accessibility.updateProperties();
         }
 
         public native function get mouseEnabled():Boolean;
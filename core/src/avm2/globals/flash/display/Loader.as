@@ -18,9 +18,7 @@ package flash.display {
             return this._contentLoaderInfo;
         }
 
-        public function get content():DisplayObject {
-            return this._contentLoaderInfo.content;
-        }
+        public native function get content():DisplayObject;
 
         public native function load(request: URLRequest, context: LoaderContext = null):void;
 
@@ -28,10 +26,8 @@ package flash.display {
 
         public native function unload():void;
 
-        public function unloadAndStop(gc:Boolean = true):void {
-            stub_method("flash.display.Loader", "unloadAndStop");
-            this.unload();
-        }
+        // The `gc` hint is ignored, as we cannot force a garbage collection.
+        public native function unloadAndStop(gc:Boolean = true):void;
 
         public function close():void {
             stub_method("flash.display.Loader", "close");
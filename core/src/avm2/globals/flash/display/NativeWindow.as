@@ -2,15 +2,18 @@ package flash.display
 {
   import flash.geom.Point;
   import flash.geom.Rectangle;
+  import flash.errors.IllegalOperationError;
   import flash.events.NativeWindowBoundsEvent;
+  import flash.events.NativeWindowDisplayStateEvent;
   import flash.events.Event;
   import flash.events.EventDispatcher;
   import flash.desktop.NativeApplication;
   import __ruffle__.stub_method;
   import __ruffle__.stub_getter;
-  import __ruffle__.stub_setter;
   import __ruffle__.stub_constructor;
 
+  // Only the primary window (the one created for the stage) is backed by a real window,
+  // through the UI backend. Other windows keep track of their bounds and display state themselves.
   [API("661")]
   public class NativeWindow extends EventDispatcher
   {
@@ -22,7 +25,14 @@ package flash.display
     public var alwaysInFront:Boolean = true;
     public var visible:Boolean = true;
 
+    // The last known bounds and display state, used to detect changes.
     private var _bounds:Rectangle;
+    private var _displayState:String;
+
+    // The bounds and display state of windows which aren't the primary window.
+    private var _localBounds:Rectangle = new Rectangle();
+    private var _localDisplayState:String = NativeWindowDisplayState.NORMAL;
+
     private var _maximizable:Boolean;
     private var _minimizable:Boolean;
     private var _resizable:Boolean;
@@ -34,16 +44,20 @@ package flash.display
 
     public function NativeWindow(initOptions:NativeWindowInitOptions, _stage:Stage = null)
     {
-      stub_constructor("flash.display.NativeWindow");
       NativeApplication.nativeApplication.openedWindows.push(this);
       if (_stage)
       {
         this._stage = _stage;
+        var self:NativeWindow = this;
         _stage.addEventListener(Event.RESIZE, function(e:Event):void
         {
-          dispatchEvent(new NativeWindowBoundsEvent(NativeWindowBoundsEvent.RESIZE, false, false, _bounds, _bounds = new Rectangle(x, y, width, height)));
+          self.updateWindow();
         });
       }
+      else
+      {
+        stub_constructor("flash.display.NativeWindow");
+      }
 
       _maximizable = initOptions.maximizable;
       _minimizable = initOptions.minimizable;
@@ -51,64 +65,193 @@ package flash.display
       _systemChrome = initOptions.systemChrome;
       _transparent = initOptions.transparent;
       _type = initOptions.type;
+
+      _bounds = currentBounds();
+      _displayState = currentDisplayState();
+    }
+
+    private static native function getWindowBounds():Rectangle;
+    private static native function setWindowBounds(x:int, y:int, width:uint, height:uint):void;
+    private static native function getWindowDisplayState():String;
+    private static native function setWindowDisplayState(state:String):void;
+    private static native function startWindowMove():Boolean;
+    private static native function startWindowResize(edge:String):Boolean;
+    private static native function closeWindow():void;
+
+    private function get isPrimary():Boolean
+    {
+      return _stage != null;
+    }
+
+    private function currentBounds():Rectangle
+    {
+      if (!isPrimary)
+      {
+        return _localBounds.clone();
+      }
+
+      var bounds:Rectangle = getWindowBounds();
+      if (bounds == null)
+      {
+        // We're not displayed in a window we know about, so the window is the stage.
+        bounds = new Rectangle(0, 0, _stage.stageWidth, _stage.stageHeight);
+      }
+      return bounds;
+    }
+
+    private function currentDisplayState():String
+    {
+      return isPrimary ? getWindowDisplayState() : _localDisplayState;
+    }
+
+    // Called whenever the window may have changed, including by the player
+    // when the user moves, resizes, minimizes or maximizes the window.
+    [Ruffle(NativeCallable)]
+    internal function updateWindow():void
+    {
+      if (_closed)
+      {
+        return;
+      }
+
+      var beforeState:String = _displayState;
+      var afterState:String = currentDisplayState();
+      _displayState = afterState;
+      if (beforeState != afterState)
+      {
+        dispatchEvent(new NativeWindowDisplayStateEvent(NativeWindowDisplayStateEvent.DISPLAY_STATE_CHANGE, false, false, beforeState, afterState));
+      }
+
+      var before:Rectangle = _bounds;
+      var after:Rectangle = currentBounds();
+      _bounds = after;
+      if (before.x != after.x || before.y != after.y)
+      {
+        dispatchEvent(new NativeWindowBoundsEvent(NativeWindowBoundsEvent.MOVE, false, false, before.clone(), after.clone()));
+      }
+      if (before.width != after.width || before.height != after.height)
+      {
+        dispatchEvent(new NativeWindowBoundsEvent(NativeWindowBoundsEvent.RESIZE, false, false, before.clone(), after.clone()));
+      }
+    }
+
+    private function checkClosed():void
+    {
+      if (_closed)
+      {
+        throw new IllegalOperationError("Error #3200: Cannot perform operation on closed window.", 3200);
+      }
+    }
+
+    private function changeBounds(x:Number, y:Number, width:Number, height:Number):void
+    {
+      checkClosed();
+
+      width = Math.max(minSize.x, Math.min(maxSize.x, width));
+      height = Math.max(minSize.y, Math.min(maxSize.y, height));
+      var before:Rectangle = _bounds.clone();
+      var after:Rectangle = new Rectangle(x, y, width, height);
+
+      if ((before.x != after.x || before.y != after.y) &&
+          !dispatchEvent(new NativeWindowBoundsEvent(NativeWindowBoundsEvent.MOVING, false, true, before, after)))
+      {
+        return;
+      }
+      if ((before.width != after.width || before.height != after.height) &&
+          !dispatchEvent(new NativeWindowBoundsEvent(NativeWindowBoundsEvent.RESIZING, false, true, before, after)))
+      {
+        return;
+      }
+
+      if (isPrimary)
+      {
+        setWindowBounds(after.x, after.y, after.width, after.height);
+      }
+      else
+      {
+        _localBounds = after;
+        _localDisplayState = NativeWindowDisplayState.NORMAL;
+      }
+      updateWindow();
+    }
+
+    private function changeDisplayState(state:String):void
+    {
+      checkClosed();
+
+      if (state == _displayState ||
+          !dispatchEvent(new NativeWindowDisplayStateEvent(NativeWindowDisplayStateEvent.DISPLAY_STATE_CHANGING, false, true, _displayState, state)))
+      {
+        return;
+      }
+
+      if (isPrimary)
+      {
+        setWindowDisplayState(state);
+      }
+      else
+      {
+        _localDisplayState = state;
+      }
+      updateWindow();
     }
 
     public function get width():Number
     {
-      stub_getter("flash.display.NativeWindow", "width");
-      return _stage.stageWidth;
+      return currentBounds().width;
     }
 
     public function set width(value:Number):void
     {
-      stub_setter("flash.display.NativeWindow", "width");
-      _stage.stageWidth = value;
+      var bounds:Rectangle = currentBounds();
+      changeBounds(bounds.x, bounds.y, value, bounds.height);
     }
 
     public function get height():Number
     {
-      stub_getter("flash.display.NativeWindow", "height");
-      return _stage.stageHeight;
+      return currentBounds().height;
     }
 
     public function set height(value:Number):void
     {
-      stub_setter("flash.display.NativeWindow", "height");
-      _stage.stageHeight = value;
+      var bounds:Rectangle = currentBounds();
+      changeBounds(bounds.x, bounds.y, bounds.width, value);
     }
 
     public function get x():Number
     {
-      stub_getter("flash.display.NativeWindow", "x");
-      return _stage.x;
+      return currentBounds().x;
     }
 
     public function set x(value:Number):void
     {
-      stub_setter("flash.display.NativeWindow", "x");
+      var bounds:Rectangle = currentBounds();
+      changeBounds(value, bounds.y, bounds.width, bounds.height);
     }
 
     public function get y():Number
     {
-      stub_getter("flash.display.NativeWindow", "y");
-      return _stage.y;
+      return currentBounds().y;
     }
 
     public function set y(value:Number):void
     {
-      stub_setter("flash.display.NativeWindow", "y");
+      var bounds:Rectangle = currentBounds();
+      changeBounds(bounds.x, value, bounds.width, bounds.height);
     }
 
     public function get bounds():Rectangle
     {
-      stub_getter("flash.display.NativeWindow", "bounds");
-      return _bounds;
+      return currentBounds();
     }
 
     public function set bounds(value:Rectangle):void
     {
-      stub_setter("flash.display.NativeWindow", "bounds");
-      _bounds = value;
+      if (value == null)
+      {
+        throw new TypeError("Error #2007: Parameter rect must be non-null.", 2007);
+      }
+      changeBounds(value.x, value.y, value.width, value.height);
     }
 
     public function get maximizable():Boolean
@@ -162,21 +305,32 @@ package flash.display
     // Closes this window.
     public function close():void
     {
-      stub_method("flash.display.NativeWindow", "close");
-      if (dispatchEvent(new Event(Event.CLOSING, false, true)))
+      if (_closed || !dispatchEvent(new Event(Event.CLOSING, false, true)))
       {
-        _closed = true;
-        dispatchEvent(new Event(Event.CLOSE));
-        dispatchEvent(new Event(Event.DEACTIVATE));
+        return;
       }
 
+      _closed = true;
+      var windows:Array = NativeApplication.nativeApplication.openedWindows;
+      var index:int = windows.indexOf(this);
+      if (index >= 0)
+      {
+        windows.splice(index, 1);
+      }
+      dispatchEvent(new Event(Event.CLOSE));
+      dispatchEvent(new Event(Event.DEACTIVATE));
+
+      if (isPrimary)
+      {
+        closeWindow();
+      }
     }
 
     // Converts a point in pixel coordinates relative to the origin of the window stage (a global point in terms of the display list), to a point on the virtual desktop.
     public function globalToScreen(globalPoint:Point):Point
     {
-      stub_method("flash.display.NativeWindow", "globalToScreen");
-      return null;
+      var bounds:Rectangle = currentBounds();
+      return new Point(bounds.x + globalPoint.x, bounds.y + globalPoint.y);
     }
 
     // Returns a list of the NativeWindow objects that are owned by this window.
@@ -190,13 +344,13 @@ package flash.display
     // Maximizes this window.
     public function maximize():void
     {
-      stub_method("flash.display.NativeWindow", "maximize");
+      changeDisplayState(NativeWindowDisplayState.MAXIMIZED);
     }
 
     // Minimizes this window.
     public function minimize():void
     {
-      stub_method("flash.display.NativeWindow", "minimize");
+      changeDisplayState(NativeWindowDisplayState.MINIMIZED);
     }
 
     // Triggers a visual cue through the operating system that an event of interest has occurred.
@@ -236,21 +390,25 @@ package flash.display
     // Restores this window from either a minimized or a maximized state.
     public function restore():void
     {
-      stub_method("flash.display.NativeWindow", "restore");
+      changeDisplayState(NativeWindowDisplayState.NORMAL);
     }
 
     // Starts a system-controlled move of this window.
     public function startMove():Boolean
     {
-      stub_method("flash.display.NativeWindow", "startMove");
-      return false;
+      checkClosed();
+      return isPrimary && startWindowMove();
     }
 
     // Starts a system-controlled resize operation of this window.
     public function startResize(edgeOrCorner:String = "BR"):Boolean
     {
-      stub_method("flash.display.NativeWindow", "startResize");
-      return false;
+      checkClosed();
+      if (edgeOrCorner == NativeWindowResize.NONE || !_resizable)
+      {
+        return false;
+      }
+      return isPrimary && startWindowResize(edgeOrCorner);
     }
 
     public function get active():Boolean
@@ -266,8 +424,7 @@ package flash.display
 
     public function get displayState():String
     {
-      stub_getter("flash.display.NativeWindow", "displayState");
-      return "normal";
+      return currentDisplayState();
     }
 
     [API("668")]
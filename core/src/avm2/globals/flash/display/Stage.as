@@ -14,6 +14,7 @@ package flash.display {
     public class Stage extends DisplayObjectContainer {
         private var _colorCorrection:String = ColorCorrection.DEFAULT;
         private var _mouseLock:Boolean = false;
+        [Ruffle(NativeAccessible)]
         private var _nativeWindow:NativeWindow;
         private var _fullScreenSourceRect:Rectangle;
 
@@ -334,7 +335,6 @@ package flash.display {
 
         [API("661")]
         public function get nativeWindow():NativeWindow {
-            stub_getter("flash.display.Stage", "nativeWindow");
             if (!this._nativeWindow) {
                 this._nativeWindow = new NativeWindow(new NativeWindowInitOptions(), this);
             }
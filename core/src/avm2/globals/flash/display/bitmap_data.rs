@@ -3,7 +3,8 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::error::{
-    argument_error, make_error_2004, make_error_2007, make_error_2008, range_error, Error2004Type,
+    argument_error, make_error_2004, make_error_2007, make_error_2008, range_error, security_error,
+    Error2004Type,
 };
 use crate::avm2::filters::FilterAvm2Ext;
 use crate::avm2::globals::slots::{
@@ -22,7 +23,7 @@ use crate::bitmap::bitmap_data::{
 use crate::bitmap::bitmap_data::{BitmapDataDrawError, IBitmapDrawable};
 use crate::bitmap::{is_size_valid, operations};
 use crate::character::{Character, CompressedBitmap};
-use crate::display_object::TDisplayObject;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::ecma_conversions::round_to_even;
 use crate::swf::BlendMode;
 use gc_arena::GcCell;
@@ -36,7 +37,7 @@ use swf::{Rectangle, Twips};
 // and `y + height` as floating point operations before
 // `round_to_even`, which is needed to match Flash Player's
 // rounding behavior.
-fn get_rectangle_x_y_width_height<'gc>(
+pub fn get_rectangle_x_y_width_height<'gc>(
     activation: &mut Activation<'_, 'gc>,
     rectangle: Object<'gc>,
 ) -> Result<(i32, i32, i32, i32), Error<'gc>> {
@@ -924,6 +925,37 @@ pub fn hit_test<'gc>(
     Ok(false.into())
 }
 
+/// Throws if the sandbox doesn't let the calling movie draw all of `source`.
+fn check_draw_access<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    source: &IBitmapDrawable<'gc>,
+) -> Result<(), Error<'gc>> {
+    let IBitmapDrawable::DisplayObject(source) = *source else {
+        return Ok(());
+    };
+    let caller = activation.caller_movie_or_root();
+    let mut objects: Vec<DisplayObject<'gc>> = vec![source];
+    while let Some(object) = objects.pop() {
+        let movie = object.movie();
+        if !activation
+            .context
+            .security_sandbox
+            .can_access_content(&caller, &movie)
+        {
+            let message = format!(
+                "Error #2123: Security sandbox violation: BitmapData.draw: {} cannot access {}. No policy files granted access.",
+                caller.url(),
+                movie.url()
+            );
+            return Err(Error::AvmError(security_error(activation, &message, 2123)?));
+        }
+        if let Some(container) = object.as_container() {
+            objects.extend(container.iter_render_list());
+        }
+    }
+    Ok(())
+}
+
 /// Implements `BitmapData.draw`
 pub fn draw<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -983,6 +1015,8 @@ pub fn draw<'gc>(
         // are inaccessible
         bitmap_data.check_valid(activation)?;
 
+        check_draw_access(activation, &source)?;
+
         // Do this last, so that we only call `overwrite_cpu_pixels_from_gpu`
         // if we're actually going to draw something.
         let quality = activation.context.stage.quality();
@@ -1060,6 +1094,8 @@ pub fn draw_with_quality<'gc>(
             return Err(format!("BitmapData.drawWithQuality: unexpected source {source:?}").into());
         };
 
+        check_draw_access(activation, &source)?;
+
         // Unknown quality defaults to stage's quality
         let quality = if let Some(quality) = args.try_get_string(activation, 6)? {
             match quality.parse() {
@@ -5,6 +5,7 @@ use indexmap::IndexMap;
 use crate::avm2::activation::Activation;
 use crate::avm2::error::make_error_2007;
 use crate::avm2::globals::flash::display::display_object::initialize_for_allocator;
+use crate::avm2::globals::flash::display::loader_info;
 use crate::avm2::globals::slots::flash_display_loader as loader_slots;
 use crate::avm2::globals::slots::flash_net_url_request as url_request_slots;
 use crate::avm2::globals::slots::flash_net_url_request_header as url_request_header_slots;
@@ -14,7 +15,7 @@ use crate::avm2::object::TObject;
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::ClassObject;
-use crate::avm2::{Error, Object};
+use crate::avm2::{Domain, Error, Object};
 use crate::avm2_stub_method;
 use crate::backend::navigator::{NavigationMethod, Request};
 use crate::display_object::LoaderDisplay;
@@ -55,6 +56,22 @@ pub fn loader_allocator<'gc>(
     Ok(loader)
 }
 
+/// `Loader.content` getter
+///
+/// This is native so that `LoaderInfo.content` checks the security sandbox
+/// against the movie calling the getter, rather than playerglobal.
+pub fn get_content<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let loader_info = this.get_slot(loader_slots::_CONTENT_LOADER_INFO);
+
+    loader_info::content(activation, loader_info, "Loader.content")
+}
+
 pub fn load<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
@@ -65,6 +82,23 @@ pub fn load<'gc>(
     let url_request = args.get_object(activation, 0, "request")?;
     let context = args.try_get_object(activation, 1);
 
+    let request = request_from_url_request(activation, url_request)?;
+    let default_domain = activation
+        .caller_domain()
+        .expect("Missing caller domain in Loader.load");
+    load_request(activation, this, request, context, default_domain);
+
+    Ok(Value::Undefined)
+}
+
+/// Start loading the content of a `Loader` from the given request.
+pub fn load_request<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    request: Request,
+    context: Option<Object<'gc>>,
+    default_domain: Domain<'gc>,
+) {
     let loader_info = this
         .get_slot(loader_slots::_CONTENT_LOADER_INFO)
         .as_object()
@@ -102,8 +136,6 @@ pub fn load<'gc>(
         activation.gc(),
     );
 
-    let request = request_from_url_request(activation, url_request)?;
-
     let url = request.url().to_string();
     let future = activation.context.load_manager.load_movie_into_clip(
         activation.context.player.clone(),
@@ -113,14 +145,10 @@ pub fn load<'gc>(
         MovieLoaderVMData::Avm2 {
             loader_info: *loader_info,
             context,
-            default_domain: activation
-                .caller_domain()
-                .expect("Missing caller domain in Loader.load"),
+            default_domain,
         },
     );
     activation.context.navigator.spawn_future(future);
-
-    Ok(Value::Undefined)
 }
 
 pub fn request_from_url_request<'gc>(
@@ -291,9 +319,6 @@ pub fn unload<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
-    // TODO: Broadcast an "unload" event on the LoaderInfo
-    avm2_stub_method!(activation, "flash.display.Loader", "unload");
-
     let loader_info = this
         .get_slot(loader_slots::_CONTENT_LOADER_INFO)
         .as_object()
@@ -305,3 +330,22 @@ pub fn unload<'gc>(
 
     Ok(Value::Undefined)
 }
+
+pub fn unload_and_stop<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let loader_info = this
+        .get_slot(loader_slots::_CONTENT_LOADER_INFO)
+        .as_object()
+        .unwrap();
+
+    let loader_info = loader_info.as_loader_info_object().unwrap();
+
+    loader_info.unload_and_stop(activation);
+
+    Ok(Value::Undefined)
+}
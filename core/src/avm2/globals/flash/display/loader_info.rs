@@ -2,16 +2,18 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::bytearray::Endian;
-use crate::avm2::error::error;
-use crate::avm2::object::{DomainObject, LoaderStream, ScriptObject, TObject};
+use crate::avm2::error::{error, security_error};
+use crate::avm2::object::{DomainObject, LoaderInfoObject, LoaderStream, ScriptObject, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::display_object::TDisplayObject;
 use crate::loader::ContentType;
+use crate::sandbox::SecuritySandbox;
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 use crate::{avm2_stub_getter, avm2_stub_method};
+use std::sync::Arc;
 use swf::{write_swf, Compression};
-use url::Url;
 
 const INSUFFICIENT: &str =
     "Error #2099: The loading object is not sufficiently loaded to provide this information.";
@@ -50,6 +52,7 @@ pub fn get_application_domain<'gc>(
     if let Some(loader_stream) = this.as_loader_info_object().map(|o| o.loader_stream()) {
         match &*loader_stream {
             LoaderStream::NotYetLoaded(movie, _, _) => {
+                check_content_access(activation, movie, "LoaderInfo.applicationDomain")?;
                 let domain = activation
                     .context
                     .library
@@ -65,6 +68,7 @@ pub fn get_application_domain<'gc>(
 
             // A loaded SWF will always have an AVM2 domain present.
             LoaderStream::Swf(movie, _) => {
+                check_content_access(activation, movie, "LoaderInfo.applicationDomain")?;
                 let domain = activation
                     .context
                     .library
@@ -128,11 +132,44 @@ pub fn get_bytes_loaded<'gc>(
     }
 }
 
+/// Throws if the sandbox doesn't let the calling movie access `movie`
+/// through `api`.
+pub fn check_content_access<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    movie: &SwfMovie,
+    api: &str,
+) -> Result<(), Error<'gc>> {
+    let caller = activation.caller_movie_or_root();
+    if activation
+        .context
+        .security_sandbox
+        .can_access_content(&caller, movie)
+    {
+        return Ok(());
+    }
+    let message = format!(
+        "Error #2121: Security sandbox violation: {api}: {} cannot access {}. This may be worked around by calling Security.allowDomain.",
+        caller.url(),
+        movie.url()
+    );
+    Err(Error::AvmError(security_error(activation, &message, 2121)?))
+}
+
 /// `content` getter
 pub fn get_content<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
     _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    content(activation, this, "LoaderInfo.content")
+}
+
+/// Implements `LoaderInfo.content` and `Loader.content`, which differ only in
+/// the name reported by security errors.
+pub fn content<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    api: &str,
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
@@ -144,6 +181,8 @@ pub fn get_content<'gc>(
     let loader_stream = loader_info.loader_stream();
     match &*loader_stream {
         LoaderStream::Swf(_, root) | LoaderStream::NotYetLoaded(_, Some(root), _) => {
+            check_content_access(activation, &root.movie(), api)?;
+
             if root.movie().is_action_script_3() || !root.movie().is_movie() {
                 Ok(root.object2())
             } else {
@@ -238,6 +277,12 @@ pub fn get_is_url_inaccessible<'gc>(
     Ok(false.into())
 }
 
+/// The movie of the `Loader` that loaded a `LoaderInfo`, if any.
+fn loader_movie<'gc>(loader_info: LoaderInfoObject<'gc>) -> Option<Arc<SwfMovie>> {
+    let loader = loader_info.loader()?;
+    Some(loader.as_display_object()?.movie())
+}
+
 /// `sameDomain` getter
 pub fn get_same_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -246,14 +291,14 @@ pub fn get_same_domain<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this = this.as_object().unwrap();
 
-    if let Some(loader_stream) = this.as_loader_info_object().map(|o| o.loader_stream()) {
-        match &*loader_stream {
+    if let Some(loader_info) = this.as_loader_info_object() {
+        match &*loader_info.loader_stream() {
             LoaderStream::NotYetLoaded(_, _, _) => {
                 return Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?));
             }
-            LoaderStream::Swf(_root, _) => {
-                avm2_stub_getter!(activation, "flash.display.LoaderInfo", "sameDomain");
-                return Ok(false.into());
+            LoaderStream::Swf(root, _) => {
+                let parent_movie = loader_movie(*loader_info).unwrap_or_else(|| root.clone());
+                return Ok(SecuritySandbox::is_same_domain(&parent_movie, root).into());
             }
         }
     }
@@ -276,21 +321,9 @@ pub fn get_child_allows_parent<'gc>(
             Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?))
         }
         LoaderStream::Swf(root, _) => {
-            // TODO: respect allowDomain() and polices.
-            avm2_stub_getter!(activation, "flash.display.LoaderInfo", "childAllowsParent");
-
-            let loader = loader_info.loader().expect("Loader should be Some");
-            let loader = loader.as_display_object().expect("Loader is a DO");
-            let parent_movie = loader.movie();
-
-            if let Ok(child_url) = Url::parse(root.url()) {
-                if let Ok(parent_url) = Url::parse(parent_movie.url()) {
-                    if child_url.host() == parent_url.host() {
-                        return Ok(true.into());
-                    }
-                }
-            }
-            Ok(false.into())
+            let parent_movie = loader_movie(*loader_info).expect("Loader should be Some");
+            let sandbox = &activation.context.security_sandbox;
+            Ok(sandbox.can_access_movie(&parent_movie, root).into())
         }
     }
 }
@@ -310,21 +343,9 @@ pub fn get_parent_allows_child<'gc>(
             Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?))
         }
         LoaderStream::Swf(root, _) => {
-            // TODO: respect allowDomain() and polices.
-            avm2_stub_getter!(activation, "flash.display.LoaderInfo", "parentAllowsChild");
-
-            let loader = loader_info.loader().expect("Loader should be Some");
-            let loader = loader.as_display_object().expect("Loader is a DO");
-            let parent_movie = loader.movie();
-
-            if let Ok(child_url) = Url::parse(root.url()) {
-                if let Ok(parent_url) = Url::parse(parent_movie.url()) {
-                    if child_url.host() == parent_url.host() {
-                        return Ok(true.into());
-                    }
-                }
-            }
-            Ok(false.into())
+            let parent_movie = loader_movie(*loader_info).expect("Loader should be Some");
+            let sandbox = &activation.context.security_sandbox;
+            Ok(sandbox.can_access_movie(root, &parent_movie).into())
         }
     }
 }
@@ -423,6 +444,7 @@ pub fn get_bytes<'gc>(
         LoaderStream::NotYetLoaded(swf, Some(dobj), _) => (swf, dobj),
         LoaderStream::Swf(root, dobj) => (root, dobj),
     };
+    check_content_access(activation, root, "LoaderInfo.bytes")?;
 
     let ba = activation
         .avm2()
@@ -0,0 +1,103 @@
+//! `flash.display.NativeWindow` builtin/prototype
+//!
+//! Only the primary window is backed by the UI backend, so these natives all act on it.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::make_error_2008;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::ui::{WindowBounds, WindowDisplayState, WindowEdge};
+use crate::string::AvmString;
+
+pub fn get_window_bounds<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(bounds) = activation.context.ui.window_bounds() else {
+        return Ok(Value::Null);
+    };
+
+    let args = &[
+        bounds.x.into(),
+        bounds.y.into(),
+        bounds.width.into(),
+        bounds.height.into(),
+    ];
+    activation
+        .avm2()
+        .classes()
+        .rectangle
+        .construct(activation, args)
+}
+
+pub fn set_window_bounds<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let bounds = WindowBounds {
+        x: args.get_i32(activation, 0)?,
+        y: args.get_i32(activation, 1)?,
+        width: args.get_u32(activation, 2)?,
+        height: args.get_u32(activation, 3)?,
+    };
+    activation.context.ui.set_window_bounds(bounds);
+    Ok(Value::Undefined)
+}
+
+pub fn get_window_display_state<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let state = activation.context.ui.window_display_state();
+    Ok(AvmString::new_utf8(activation.gc(), state.name()).into())
+}
+
+pub fn set_window_display_state<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let state = args.get_string(activation, 0)?;
+    let state = if &state == b"maximized" {
+        WindowDisplayState::Maximized
+    } else if &state == b"minimized" {
+        WindowDisplayState::Minimized
+    } else {
+        WindowDisplayState::Normal
+    };
+    activation.context.ui.set_window_display_state(state);
+    Ok(Value::Undefined)
+}
+
+pub fn start_window_move<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.start_window_move().into())
+}
+
+pub fn start_window_resize<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let edge = args.get_string(activation, 0)?;
+    let Some(edge) = WindowEdge::from_name(&edge.to_utf8_lossy()) else {
+        return Err(make_error_2008(activation, "edgeOrCorner"));
+    };
+    Ok(activation.context.ui.start_window_resize(edge).into())
+}
+
+pub fn close_window<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.ui.close_window();
+    Ok(Value::Undefined)
+}
@@ -69,6 +69,15 @@ package flash.events {
 
 		public static const SOUND_COMPLETE:String = "soundComplete";
 
+		[API("668")]
+		public static const STANDARD_ERROR_CLOSE:String = "standardErrorClose";
+
+		[API("668")]
+		public static const STANDARD_INPUT_CLOSE:String = "standardInputClose";
+
+		[API("668")]
+		public static const STANDARD_OUTPUT_CLOSE:String = "standardOutputClose";
+
 		public static const TAB_CHILDREN_CHANGE:String = "tabChildrenChange";
 
 		public static const TAB_ENABLED_CHANGE:String = "tabEnabledChange";
@@ -0,0 +1,32 @@
+package flash.events {
+    import flash.text.ime.IIMEClient;
+
+    public class IMEEvent extends TextEvent {
+        public static const IME_COMPOSITION:String = "imeComposition";
+
+        private var _imeClient:IIMEClient;
+
+        public function IMEEvent(type:String, bubbles:Boolean = false, cancelable:Boolean = false, text:String = "", imeClient:IIMEClient = null)
+        {
+            super(type,bubbles,cancelable,text);
+            this._imeClient = imeClient;
+        }
+
+        public function get imeClient():IIMEClient {
+            return this._imeClient;
+        }
+        public function set imeClient(value:IIMEClient):void {
+            this._imeClient = value;
+        }
+
+        override public function clone() : Event
+        {
+            return new IMEEvent(this.type,this.bubbles,this.cancelable,this.text,this.imeClient);
+        }
+
+        override public function toString() : String
+        {
+            return this.formatToString("IMEEvent","type","bubbles","cancelable","eventPhase","text","imeClient");
+        }
+    }
+}
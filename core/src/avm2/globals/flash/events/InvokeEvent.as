@@ -33,7 +33,7 @@ package flash.events
     // [override] Creates a new copy of this event.
     override public function clone():Event
     {
-      return new InvokeEvent(this.type, this.bubbles, this.cancelable, this.arguments, this.reason);
+      return new InvokeEvent(this.type, this.bubbles, this.cancelable, this.currentDirectory, this.arguments, this.reason);
     }
 
     public function get arguments():Array
@@ -0,0 +1,37 @@
+package flash.events
+{
+  [API("668")]
+  public class NativeProcessExitEvent extends Event
+  {
+    public static const EXIT:String = "exit";
+
+    // The exit code of the process, or NaN if it was killed.
+    private var _exitCode:Number;
+
+    public function NativeProcessExitEvent(type:String, bubbles:Boolean = false, cancelable:Boolean = false, exitCode:Number = NaN)
+    {
+      super(type, bubbles, cancelable);
+      this._exitCode = exitCode;
+    }
+
+    override public function clone():Event
+    {
+      return new NativeProcessExitEvent(this.type, this.bubbles, this.cancelable, this.exitCode);
+    }
+
+    override public function toString():String
+    {
+      return this.formatToString("NativeProcessExitEvent", "type", "bubbles", "cancelable", "eventPhase", "exitCode");
+    }
+
+    public function get exitCode():Number
+    {
+      return this._exitCode;
+    }
+
+    public function set exitCode(value:Number):void
+    {
+      this._exitCode = value;
+    }
+  }
+}
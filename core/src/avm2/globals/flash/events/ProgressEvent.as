@@ -2,6 +2,12 @@ package flash.events {
     public class ProgressEvent extends Event {
         public static const PROGRESS:String = "progress";
         public static const SOCKET_DATA:String = "socketData";
+        [API("668")]
+        public static const STANDARD_ERROR_DATA:String = "standardErrorData";
+        [API("668")]
+        public static const STANDARD_INPUT_PROGRESS:String = "standardInputProgress";
+        [API("668")]
+        public static const STANDARD_OUTPUT_DATA:String = "standardOutputData";
 
         private var _bytesLoaded:Number;
         private var _bytesTotal:Number;
@@ -0,0 +1,4 @@
+//! `flash.filesystem` namespace
+
+pub mod file;
+pub mod file_stream;
@@ -1,36 +1,241 @@
 package flash.filesystem {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
     import __ruffle__.stub_method;
 
+    import flash.events.Event;
+    import flash.events.FileListEvent;
+    import flash.events.IOErrorEvent;
+    import flash.events.ProgressEvent;
     import flash.net.FileReference;
+    import flash.utils.ByteArray;
+    import flash.utils.setTimeout;
 
     [API("661")]
     public class File extends FileReference {
-        private static var _applicationDirectory:File = null;
+        // The sandboxed directory this file is located in ("app", "app-storage" or "documents"),
+        // or null if this file is located outside of the sandbox.
+        [Ruffle(NativeAccessible)]
+        internal var _root:String = null;
+
+        // The path of this file relative to `_root`, with components separated by "/".
+        // For files outside of the sandbox, this is the path as given by the movie.
+        [Ruffle(NativeAccessible)]
+        internal var _path:String = "";
+
+        private var _data:ByteArray = null;
 
         public function File(path:String = null) {
-            stub_constructor("flash.filesystem.File");
+            if (path != null) {
+                this.nativePath = path;
+            }
+        }
+
+        private static function sandboxed(root:String):File {
+            var file:File = new File();
+            file._root = root;
+            return file;
         }
 
         public static function get applicationDirectory():File {
-            if (_applicationDirectory === null) {
-                _applicationDirectory = new File();
+            return sandboxed("app");
+        }
+
+        public static function get applicationStorageDirectory():File {
+            return sandboxed("app-storage");
+        }
+
+        public static function get documentsDirectory():File {
+            return sandboxed("documents");
+        }
+
+        public static native function get separator():String;
+
+        public native function get nativePath():String;
+        public native function set nativePath(value:String):void;
+
+        public native function get url():String;
+        public native function set url(value:String):void;
+
+        public native function get exists():Boolean;
+
+        public native function get isDirectory():Boolean;
+
+        public function get isHidden():Boolean {
+            return this.name.charAt(0) == ".";
+        }
+
+        public function get isPackage():Boolean {
+            return false;
+        }
+
+        public function get isSymbolicLink():Boolean {
+            return false;
+        }
+
+        public function get parent():File {
+            if (this._root == null || this._path == "") {
+                return null;
             }
 
-            return _applicationDirectory;
+            var file:File = new File();
+            file._root = this._root;
+            file._path = this._path.substring(0, Math.max(0, this._path.lastIndexOf("/")));
+            return file;
+        }
+
+        override public function get name():String {
+            var path:String = this.nativePath;
+            return path.substring(Math.max(path.lastIndexOf("/"), path.lastIndexOf("\\")) + 1);
+        }
+
+        override public function get type():String {
+            var name:String = this.name;
+            var index:int = name.lastIndexOf(".");
+            return index < 0 ? null : name.substring(index);
+        }
+
+        override public native function get size():Number;
+
+        override public native function get creationDate():Date;
+
+        override public native function get modificationDate():Date;
+
+        override public function get data():ByteArray {
+            return this._data;
+        }
+
+        public function clone():File {
+            var file:File = new File();
+            file._root = this._root;
+            file._path = this._path;
+            return file;
+        }
+
+        public function canonicalize():void {
+            // Paths within the sandbox are always canonical.
         }
 
         public function resolvePath(path:String):File {
-            stub_method("flash.filesystem.File", "resolvePath");
+            var file:File = new File();
+            file.resolveFrom(this, path);
+            return file;
+        }
+
+        private native function resolveFrom(base:File, path:String):void;
+
+        public function getRelativePath(ref:FileReference, useDotDot:Boolean = false):String {
+            var other:File = ref as File;
+            if (other == null || other._root == null || other._root != this._root) {
+                return null;
+            }
+
+            var from:Array = this._path == "" ? [] : this._path.split("/");
+            var to:Array = other._path == "" ? [] : other._path.split("/");
+            var common:int = 0;
+            while (common < from.length && common < to.length && from[common] == to[common]) {
+                common++;
+            }
+            if (common < from.length && !useDotDot) {
+                return null;
+            }
 
-            return new File();
+            var result:Array = [];
+            for (var i:int = common; i < from.length; i++) {
+                result.push("..");
+            }
+            return result.concat(to.slice(common)).join("/");
         }
 
-        public function get exists():Boolean {
-            stub_getter("flash.filesystem.File", "exists");
+        public native function createDirectory():void;
 
-            return false;
+        public function getDirectoryListing():Array {
+            var files:Array = [];
+            for each (var name:String in this.listDirectory()) {
+                files.push(this.resolvePath(name));
+            }
+            return files;
+        }
+
+        private native function listDirectory():Array;
+
+        public function getDirectoryListingAsync():void {
+            this.runAsync(this.getDirectoryListing, function(files:Array):Event {
+                return new FileListEvent(FileListEvent.DIRECTORY_LISTING, false, false, files);
+            });
+        }
+
+        public native function copyTo(newLocation:FileReference, overwrite:Boolean = false):void;
+
+        public function copyToAsync(newLocation:FileReference, overwrite:Boolean = false):void {
+            var self:File = this;
+            this.runAsync(function():void {
+                self.copyTo(newLocation, overwrite);
+            });
+        }
+
+        public native function moveTo(newLocation:FileReference, overwrite:Boolean = false):void;
+
+        public function moveToAsync(newLocation:FileReference, overwrite:Boolean = false):void {
+            var self:File = this;
+            this.runAsync(function():void {
+                self.moveTo(newLocation, overwrite);
+            });
+        }
+
+        public native function deleteFile():void;
+
+        public function deleteFileAsync():void {
+            this.runAsync(this.deleteFile);
+        }
+
+        public native function deleteDirectory(deleteDirectoryContents:Boolean = false):void;
+
+        public function deleteDirectoryAsync(deleteDirectoryContents:Boolean = false):void {
+            var self:File = this;
+            this.runAsync(function():void {
+                self.deleteDirectory(deleteDirectoryContents);
+            });
+        }
+
+        public function moveToTrash():void {
+            stub_method("flash.filesystem.File", "moveToTrash");
+            if (this.isDirectory) {
+                this.deleteDirectory(true);
+            } else {
+                this.deleteFile();
+            }
+        }
+
+        override public function load():void {
+            var self:File = this;
+            this.runAsync(function():ByteArray {
+                var stream:FileStream = new FileStream();
+                stream.open(self, FileMode.READ);
+                var data:ByteArray = new ByteArray();
+                stream.readBytes(data);
+                stream.close();
+                return data;
+            }, function(data:ByteArray):Event {
+                self._data = data;
+                self.dispatchEvent(new Event(Event.OPEN));
+                self.dispatchEvent(new ProgressEvent(ProgressEvent.PROGRESS, false, false, data.length, data.length));
+                return new Event(Event.COMPLETE);
+            });
+        }
+
+        // Runs `operation` after the current frame, then dispatches the event returned by `complete`
+        // (or a plain `complete` event), or an `ioError` event if the operation failed.
+        private function runAsync(operation:Function, complete:Function = null):void {
+            var self:File = this;
+            setTimeout(function():void {
+                var result:*;
+                try {
+                    result = operation();
+                } catch (e:Error) {
+                    self.dispatchEvent(new IOErrorEvent(IOErrorEvent.IO_ERROR, false, false, e.message, e.errorID));
+                    return;
+                }
+                self.dispatchEvent(complete != null ? complete(result) : new Event(Event.COMPLETE));
+            }, 0);
         }
     }
 }
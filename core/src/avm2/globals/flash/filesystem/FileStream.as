@@ -0,0 +1,307 @@
+package flash.filesystem {
+    import flash.errors.IOError;
+    import flash.events.Event;
+    import flash.events.EventDispatcher;
+    import flash.events.IOErrorEvent;
+    import flash.events.OutputProgressEvent;
+    import flash.events.ProgressEvent;
+    import flash.utils.ByteArray;
+    import flash.utils.IDataInput;
+    import flash.utils.IDataOutput;
+    import flash.utils.setTimeout;
+
+    // The contents of the open file are kept in `_buffer`, which all reads are served from.
+    // Writes go to both `_buffer` and the file itself.
+    [API("661")]
+    public class FileStream extends EventDispatcher implements IDataInput, IDataOutput {
+        private var _buffer:ByteArray = new ByteArray();
+        private var _file:File = null;
+        private var _mode:String = null;
+        private var _async:Boolean = false;
+        private var _readAhead:Number = Infinity;
+
+        public function FileStream() {
+        }
+
+        public function open(file:File, fileMode:String):void {
+            this.openInternal(file, fileMode);
+            this._async = false;
+        }
+
+        public function openAsync(file:File, fileMode:String):void {
+            this._async = true;
+
+            var self:FileStream = this;
+            try {
+                this.openInternal(file, fileMode);
+            } catch (e:IOError) {
+                setTimeout(function():void {
+                    self.dispatchEvent(new IOErrorEvent(IOErrorEvent.IO_ERROR, false, false, e.message, e.errorID));
+                }, 0);
+                return;
+            }
+
+            if (fileMode == FileMode.READ || fileMode == FileMode.UPDATE) {
+                var length:uint = this._buffer.length;
+                setTimeout(function():void {
+                    if (self._file != null) {
+                        self.dispatchEvent(new ProgressEvent(ProgressEvent.PROGRESS, false, false, length, length));
+                        self.dispatchEvent(new Event(Event.COMPLETE));
+                    }
+                }, 0);
+            }
+        }
+
+        private function openInternal(file:File, fileMode:String):void {
+            if (file == null) {
+                throw new TypeError("Error #2007: Parameter file must be non-null.", 2007);
+            }
+
+            this._file = null;
+            openFile(file, fileMode, this._buffer);
+            this._file = file;
+            this._mode = fileMode;
+            if (fileMode == FileMode.APPEND) {
+                this._buffer.position = this._buffer.length;
+            }
+        }
+
+        private static native function openFile(file:File, mode:String, buffer:ByteArray):void;
+        private static native function writeRange(file:File, buffer:ByteArray, start:uint, end:uint):void;
+        private static native function truncateFile(file:File, length:uint):void;
+
+        public function close():void {
+            this._file = null;
+            this._mode = null;
+            this._buffer.clear();
+
+            if (this._async) {
+                var self:FileStream = this;
+                setTimeout(function():void {
+                    self.dispatchEvent(new Event(Event.CLOSE));
+                }, 0);
+            }
+        }
+
+        public function get bytesAvailable():uint {
+            return this._file == null ? 0 : this._buffer.bytesAvailable;
+        }
+
+        public function get endian():String {
+            return this._buffer.endian;
+        }
+        public function set endian(value:String):void {
+            this._buffer.endian = value;
+        }
+
+        public function get objectEncoding():uint {
+            return this._buffer.objectEncoding;
+        }
+        public function set objectEncoding(value:uint):void {
+            this._buffer.objectEncoding = value;
+        }
+
+        public function get position():Number {
+            return this._buffer.position;
+        }
+        public function set position(value:Number):void {
+            this._buffer.position = value;
+        }
+
+        public function get readAhead():Number {
+            return this._readAhead;
+        }
+        public function set readAhead(value:Number):void {
+            this._readAhead = value;
+        }
+
+        public function truncate():void {
+            this.checkWritable();
+            this._buffer.length = this._buffer.position;
+            truncateFile(this._file, this._buffer.length);
+        }
+
+        private function checkOpen():void {
+            if (this._file == null) {
+                throw new IOError("Error #2029: This URLStream object does not have a stream opened.", 2029);
+            }
+        }
+
+        private function checkReadable():void {
+            this.checkOpen();
+            if (this._mode == FileMode.WRITE || this._mode == FileMode.APPEND) {
+                throw new IOError("Error #3001: File or directory access denied.", 3001);
+            }
+        }
+
+        private function checkWritable():void {
+            this.checkOpen();
+            if (this._mode == FileMode.READ) {
+                throw new IOError("Error #3001: File or directory access denied.", 3001);
+            }
+        }
+
+        // Returns the position that the following write will start at.
+        private function beginWrite():uint {
+            this.checkWritable();
+            if (this._mode == FileMode.APPEND) {
+                this._buffer.position = this._buffer.length;
+            }
+            return this._buffer.position;
+        }
+
+        private function endWrite(start:uint):void {
+            var end:uint = this._buffer.position;
+            writeRange(this._file, this._buffer, start, end);
+
+            if (this._async) {
+                var self:FileStream = this;
+                setTimeout(function():void {
+                    self.dispatchEvent(new OutputProgressEvent(OutputProgressEvent.OUTPUT_PROGRESS, false, false, 0, end - start));
+                }, 0);
+            }
+        }
+
+        public function readBoolean():Boolean {
+            this.checkReadable();
+            return this._buffer.readBoolean();
+        }
+
+        public function readByte():int {
+            this.checkReadable();
+            return this._buffer.readByte();
+        }
+
+        public function readBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
+            this.checkReadable();
+            this._buffer.readBytes(bytes, offset, length);
+        }
+
+        public function readDouble():Number {
+            this.checkReadable();
+            return this._buffer.readDouble();
+        }
+
+        public function readFloat():Number {
+            this.checkReadable();
+            return this._buffer.readFloat();
+        }
+
+        public function readInt():int {
+            this.checkReadable();
+            return this._buffer.readInt();
+        }
+
+        public function readMultiByte(length:uint, charSet:String):String {
+            this.checkReadable();
+            return this._buffer.readMultiByte(length, charSet);
+        }
+
+        public function readObject():* {
+            this.checkReadable();
+            return this._buffer.readObject();
+        }
+
+        public function readShort():int {
+            this.checkReadable();
+            return this._buffer.readShort();
+        }
+
+        public function readUnsignedByte():uint {
+            this.checkReadable();
+            return this._buffer.readUnsignedByte();
+        }
+
+        public function readUnsignedInt():uint {
+            this.checkReadable();
+            return this._buffer.readUnsignedInt();
+        }
+
+        public function readUnsignedShort():uint {
+            this.checkReadable();
+            return this._buffer.readUnsignedShort();
+        }
+
+        public function readUTF():String {
+            this.checkReadable();
+            return this._buffer.readUTF();
+        }
+
+        public function readUTFBytes(length:uint):String {
+            this.checkReadable();
+            return this._buffer.readUTFBytes(length);
+        }
+
+        public function writeBoolean(value:Boolean):void {
+            var start:uint = this.beginWrite();
+            this._buffer.writeBoolean(value);
+            this.endWrite(start);
+        }
+
+        public function writeByte(value:int):void {
+            var start:uint = this.beginWrite();
+            this._buffer.writeByte(value);
+            this.endWrite(start);
+        }
+
+        public function writeBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
+            var start:uint = this.beginWrite();
+            this._buffer.writeBytes(bytes, offset, length);
+            this.endWrite(start);
+        }
+
+        public function writeDouble(value:Number):void {
+            var start:uint = this.beginWrite();
+            this._buffer.writeDouble(value);
+            this.endWrite(start);
+        }
+
+        public function writeFloat(value:Number):void {
+            var start:uint = this.beginWrite();
+            this._buffer.writeFloat(value);
+            this.endWrite(start);
+        }
+
+        public function writeInt(value:int):void {
+            var start:uint = this.beginWrite();
+            this._buffer.writeInt(value);
+            this.endWrite(start);
+        }
+
+        public function writeMultiByte(value:String, charSet:String):void {
+            var start:uint = this.beginWrite();
+            this._buffer.writeMultiByte(value, charSet);
+            this.endWrite(start);
+        }
+
+        public function writeObject(object:*):void {
+            var start:uint = this.beginWrite();
+            this._buffer.writeObject(object);
+            this.endWrite(start);
+        }
+
+        public function writeShort(value:int):void {
+            var start:uint = this.beginWrite();
+            this._buffer.writeShort(value);
+            this.endWrite(start);
+        }
+
+        public function writeUnsignedInt(value:uint):void {
+            var start:uint = this.beginWrite();
+            this._buffer.writeUnsignedInt(value);
+            this.endWrite(start);
+        }
+
+        public function writeUTF(value:String):void {
+            var start:uint = this.beginWrite();
+            this._buffer.writeUTF(value);
+            this.endWrite(start);
+        }
+
+        public function writeUTFBytes(value:String):void {
+            var start:uint = this.beginWrite();
+            this._buffer.writeUTFBytes(value);
+            this.endWrite(start);
+        }
+    }
+}
@@ -0,0 +1,512 @@
+//! `flash.filesystem.File` builtin
+
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::error::{io_error, make_error_2004, security_error, Error2004Type};
+use crate::avm2::globals::slots::flash_filesystem_file as file_slots;
+use crate::avm2::object::{ArrayObject, DateObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Value};
+use crate::backend::filesystem::{
+    normalize_path, FileMetadata, FileSystemBackend, FileSystemRoot, SandboxPath,
+};
+use crate::string::AvmString;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+use std::io::ErrorKind;
+
+/// Characters which are percent-encoded in `File.url`.
+const URL_PATH: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// The location that a `File` object refers to.
+pub enum FileLocation {
+    Sandboxed(SandboxPath),
+
+    /// A path outside of the sandbox, which content is not allowed to access.
+    Unsandboxed(String),
+}
+
+impl FileLocation {
+    fn native_path(&self, filesystem: &dyn FileSystemBackend) -> String {
+        match self {
+            FileLocation::Sandboxed(path) => filesystem.native_path(path),
+            FileLocation::Unsandboxed(path) => path.clone(),
+        }
+    }
+
+    fn url(&self, filesystem: &dyn FileSystemBackend) -> String {
+        match self {
+            FileLocation::Sandboxed(path) if path.root != FileSystemRoot::Documents => {
+                let encoded = utf8_percent_encode(&path.path, URL_PATH);
+                format!("{}:/{encoded}", path.root.name())
+            }
+            _ => {
+                let native_path = self.native_path(filesystem).replace('\\', "/");
+                let encoded = utf8_percent_encode(&native_path, URL_PATH).to_string();
+                if encoded.starts_with('/') {
+                    format!("file://{encoded}")
+                } else {
+                    format!("file:///{encoded}")
+                }
+            }
+        }
+    }
+}
+
+fn sandboxed(root: FileSystemRoot, path: &str, original: &str) -> FileLocation {
+    match normalize_path(path) {
+        Some(path) => FileLocation::Sandboxed(SandboxPath::new(root, path)),
+        None => FileLocation::Unsandboxed(original.to_string()),
+    }
+}
+
+fn from_native_path(filesystem: &dyn FileSystemBackend, native_path: &str) -> FileLocation {
+    match filesystem.resolve_native_path(native_path) {
+        Some(path) => sandboxed(path.root, &path.path, native_path),
+        None => FileLocation::Unsandboxed(native_path.to_string()),
+    }
+}
+
+/// Parses an `app:`, `app-storage:` or `file:` URL.
+fn from_url(filesystem: &dyn FileSystemBackend, url: &str) -> Option<FileLocation> {
+    for root in [
+        FileSystemRoot::Application,
+        FileSystemRoot::ApplicationStorage,
+    ] {
+        if let Some(path) = url
+            .strip_prefix(root.name())
+            .and_then(|u| u.strip_prefix(':'))
+        {
+            let path = percent_decode_str(path).decode_utf8_lossy();
+            return Some(sandboxed(root, &path, url));
+        }
+    }
+
+    let path = url.strip_prefix("file://")?;
+    let mut native_path = percent_decode_str(path).decode_utf8_lossy().into_owned();
+    // Windows paths are given as `file:///C:/...`.
+    if native_path.get(2..3) == Some(":") {
+        native_path.remove(0);
+    }
+    Some(from_native_path(filesystem, &native_path))
+}
+
+/// Parses a URL or absolute native path, returning `None` for relative paths.
+fn from_absolute_path(filesystem: &dyn FileSystemBackend, path: &str) -> Option<FileLocation> {
+    if let Some(location) = from_url(filesystem, path) {
+        return Some(location);
+    }
+
+    let is_absolute = path.starts_with(['/', '\\']) || path.get(1..2) == Some(":");
+    is_absolute.then(|| from_native_path(filesystem, path))
+}
+
+/// Reads the location that a `File` object refers to.
+pub fn file_location<'gc>(file: Object<'gc>) -> FileLocation {
+    let path = match file.get_slot(file_slots::_PATH) {
+        Value::String(path) => path.to_utf8_lossy().into_owned(),
+        _ => String::new(),
+    };
+    let root = match file.get_slot(file_slots::_ROOT) {
+        Value::String(root) => FileSystemRoot::from_name(&root.to_utf8_lossy()),
+        _ => None,
+    };
+
+    match root {
+        Some(root) => FileLocation::Sandboxed(SandboxPath::new(root, path)),
+        None => FileLocation::Unsandboxed(path),
+    }
+}
+
+fn set_file_location<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    file: Object<'gc>,
+    location: FileLocation,
+) {
+    let (root, path) = match location {
+        FileLocation::Sandboxed(path) => (
+            AvmString::new_utf8(activation.gc(), path.root.name()).into(),
+            path.path,
+        ),
+        FileLocation::Unsandboxed(path) => (Value::Null, path),
+    };
+    let path = AvmString::new_utf8(activation.gc(), path).into();
+    file.set_slot_no_coerce(file_slots::_ROOT, root, activation.gc());
+    file.set_slot_no_coerce(file_slots::_PATH, path, activation.gc());
+}
+
+fn access_denied_error<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+    match security_error(
+        activation,
+        "Error #3001: File or directory access denied.",
+        3001,
+    ) {
+        Ok(err) => Error::AvmError(err),
+        Err(e) => e,
+    }
+}
+
+fn file_io_error<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    message: &str,
+    code: u32,
+) -> Error<'gc> {
+    match io_error(activation, message, code) {
+        Ok(err) => Error::AvmError(err),
+        Err(e) => e,
+    }
+}
+
+/// Converts an error from the filesystem backend into an `IOError`.
+pub fn file_error<'gc>(activation: &mut Activation<'_, 'gc>, error: std::io::Error) -> Error<'gc> {
+    let (message, code) = match error.kind() {
+        ErrorKind::NotFound => ("Error #3003: File or directory does not exist.", 3003),
+        ErrorKind::AlreadyExists => ("Error #3002: File or directory exists.", 3002),
+        ErrorKind::IsADirectory => ("Error #3006: Not a file.", 3006),
+        ErrorKind::NotADirectory => ("Error #3007: Not a directory.", 3007),
+        ErrorKind::DirectoryNotEmpty => ("Error #3010: Directory is not empty.", 3010),
+        ErrorKind::InvalidInput => (
+            "Error #3014: Cannot copy or move a file or directory to overwrite a containing directory.",
+            3014,
+        ),
+        _ => ("Error #3001: File or directory access denied.", 3001),
+    };
+    file_io_error(activation, message, code)
+}
+
+/// The sandboxed location of a `File`, or an error if it lies outside of the sandbox.
+pub fn sandbox_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    file: Object<'gc>,
+) -> Result<SandboxPath, Error<'gc>> {
+    match file_location(file) {
+        FileLocation::Sandboxed(path) => Ok(path),
+        FileLocation::Unsandboxed(_) => Err(access_denied_error(activation)),
+    }
+}
+
+/// Like `sandbox_path`, but also requires content to be allowed to modify the location.
+pub fn writable_sandbox_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    file: Object<'gc>,
+) -> Result<SandboxPath, Error<'gc>> {
+    let path = sandbox_path(activation, file)?;
+    if !path.root.is_writable() {
+        return Err(access_denied_error(activation));
+    }
+    Ok(path)
+}
+
+fn metadata<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+) -> Result<FileMetadata, Error<'gc>> {
+    let this = this.as_object().unwrap();
+    let path = sandbox_path(activation, this)?;
+    activation
+        .context
+        .filesystem
+        .metadata(&path)
+        .map_err(|e| file_error(activation, e))
+}
+
+pub fn get_separator<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let separator = activation.context.filesystem.separator();
+    Ok(AvmString::new_utf8(activation.gc(), separator).into())
+}
+
+pub fn get_native_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let native_path = file_location(this).native_path(activation.context.filesystem);
+    Ok(AvmString::new_utf8(activation.gc(), native_path).into())
+}
+
+pub fn set_native_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let native_path = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let Some(location) = from_absolute_path(activation.context.filesystem, &native_path) else {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    };
+    set_file_location(activation, this, location);
+    Ok(Value::Undefined)
+}
+
+pub fn get_url<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let url = file_location(this).url(activation.context.filesystem);
+    Ok(AvmString::new_utf8(activation.gc(), url).into())
+}
+
+pub fn set_url<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let url = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let Some(location) = from_url(activation.context.filesystem, &url) else {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    };
+    set_file_location(activation, this, location);
+    Ok(Value::Undefined)
+}
+
+pub fn resolve_from<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let base = args.get_object(activation, 0, "base")?;
+    let path = args.get_string(activation, 1)?.to_utf8_lossy().into_owned();
+
+    let filesystem = &*activation.context.filesystem;
+    let location = match from_absolute_path(filesystem, &path) {
+        Some(location) => location,
+        None => match file_location(base) {
+            FileLocation::Sandboxed(base) => {
+                let original = format!(
+                    "{}{}{path}",
+                    filesystem.native_path(&base),
+                    filesystem.separator()
+                );
+                sandboxed(base.root, &format!("{}/{path}", base.path), &original)
+            }
+            FileLocation::Unsandboxed(base) => {
+                FileLocation::Unsandboxed(format!("{base}{}{path}", filesystem.separator()))
+            }
+        },
+    };
+    set_file_location(activation, this, location);
+    Ok(Value::Undefined)
+}
+
+pub fn get_exists<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let exists = match file_location(this) {
+        FileLocation::Sandboxed(path) => activation.context.filesystem.metadata(&path).is_ok(),
+        FileLocation::Unsandboxed(_) => false,
+    };
+    Ok(exists.into())
+}
+
+pub fn get_is_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let is_directory = match file_location(this) {
+        FileLocation::Sandboxed(path) => activation
+            .context
+            .filesystem
+            .metadata(&path)
+            .is_ok_and(|metadata| metadata.is_directory),
+        FileLocation::Unsandboxed(_) => false,
+    };
+    Ok(is_directory.into())
+}
+
+pub fn get_size<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let metadata = metadata(activation, this)?;
+    Ok((metadata.size as f64).into())
+}
+
+pub fn get_creation_date<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match metadata(activation, this)?.creation_time {
+        Some(time) => Ok(DateObject::from_date_time(activation, time)?.into()),
+        None => Ok(Value::Null),
+    }
+}
+
+pub fn get_modification_date<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match metadata(activation, this)?.modification_time {
+        Some(time) => Ok(DateObject::from_date_time(activation, time)?.into()),
+        None => Ok(Value::Null),
+    }
+}
+
+pub fn create_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let path = writable_sandbox_path(activation, this)?;
+    activation
+        .context
+        .filesystem
+        .create_directory(&path)
+        .map_err(|e| file_error(activation, e))?;
+    Ok(Value::Undefined)
+}
+
+pub fn list_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let path = sandbox_path(activation, this)?;
+    let mut names = activation
+        .context
+        .filesystem
+        .read_directory(&path)
+        .map_err(|e| file_error(activation, e))?;
+    names.sort();
+
+    let names = names
+        .into_iter()
+        .map(|name| Value::from(AvmString::new_utf8(activation.gc(), name)));
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_iter(names)).into())
+}
+
+/// Checks and returns the source and destination of a copy or move.
+fn transfer_paths<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+    is_move: bool,
+) -> Result<(SandboxPath, SandboxPath), Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let new_location = args.get_object(activation, 0, "newLocation")?;
+    let overwrite = args.get_bool(1);
+
+    let from = if is_move {
+        writable_sandbox_path(activation, this)?
+    } else {
+        sandbox_path(activation, this)?
+    };
+    let to = writable_sandbox_path(activation, new_location)?;
+
+    let filesystem = &*activation.context.filesystem;
+    if let Err(e) = filesystem.metadata(&from) {
+        return Err(file_error(activation, e));
+    }
+    if from.contains(&to) || (to.contains(&from) && from != to) {
+        return Err(file_error(activation, ErrorKind::InvalidInput.into()));
+    }
+    if !overwrite && filesystem.metadata(&to).is_ok() {
+        return Err(file_error(activation, ErrorKind::AlreadyExists.into()));
+    }
+
+    Ok((from, to))
+}
+
+pub fn copy_to<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (from, to) = transfer_paths(activation, this, args, false)?;
+    activation
+        .context
+        .filesystem
+        .copy(&from, &to)
+        .map_err(|e| file_error(activation, e))?;
+    Ok(Value::Undefined)
+}
+
+pub fn move_to<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (from, to) = transfer_paths(activation, this, args, true)?;
+    activation
+        .context
+        .filesystem
+        .rename(&from, &to)
+        .map_err(|e| file_error(activation, e))?;
+    Ok(Value::Undefined)
+}
+
+pub fn delete_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let path = writable_sandbox_path(activation, this)?;
+    let filesystem = &mut *activation.context.filesystem;
+    let result = match filesystem.metadata(&path) {
+        Ok(metadata) if metadata.is_directory => Err(ErrorKind::IsADirectory.into()),
+        Ok(_) => filesystem.delete(&path),
+        Err(e) => Err(e),
+    };
+    result.map_err(|e| file_error(activation, e))?;
+    Ok(Value::Undefined)
+}
+
+pub fn delete_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let delete_directory_contents = args.get_bool(0);
+
+    let path = writable_sandbox_path(activation, this)?;
+    let filesystem = &mut *activation.context.filesystem;
+    let result = match filesystem.read_directory(&path) {
+        Ok(entries) if !entries.is_empty() && !delete_directory_contents => {
+            Err(ErrorKind::DirectoryNotEmpty.into())
+        }
+        Ok(_) => filesystem.delete(&path),
+        Err(e) => Err(e),
+    };
+    result.map_err(|e| file_error(activation, e))?;
+    Ok(Value::Undefined)
+}
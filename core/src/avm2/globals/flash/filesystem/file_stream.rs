@@ -0,0 +1,94 @@
+//! `flash.filesystem.FileStream` builtin
+
+use crate::avm2::error::make_error_2008;
+use crate::avm2::globals::flash::filesystem::file::{
+    file_error, sandbox_path, writable_sandbox_path,
+};
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Value};
+use std::io::ErrorKind;
+
+pub fn open_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let file = args.get_object(activation, 0, "file")?;
+    let mode = args.get_string(activation, 1)?;
+    let buffer = args.get_object(activation, 2, "buffer")?;
+
+    let contents = match &*mode.to_utf8_lossy() {
+        "read" => {
+            let path = sandbox_path(activation, file)?;
+            activation.context.filesystem.read(&path)
+        }
+        "write" => {
+            let path = writable_sandbox_path(activation, file)?;
+            let filesystem = &mut *activation.context.filesystem;
+            filesystem.truncate(&path, 0).map(|()| Vec::new())
+        }
+        "append" | "update" => {
+            let path = writable_sandbox_path(activation, file)?;
+            let filesystem = &mut *activation.context.filesystem;
+            match filesystem.read(&path) {
+                Err(e) if e.kind() == ErrorKind::NotFound => {
+                    filesystem.truncate(&path, 0).map(|()| Vec::new())
+                }
+                result => result,
+            }
+        }
+        _ => return Err(make_error_2008(activation, "fileMode")),
+    };
+    let contents = contents.map_err(|e| file_error(activation, e))?;
+
+    let mut buffer = buffer
+        .as_bytearray_mut()
+        .expect("FileStream buffer should be a ByteArray");
+    buffer.clear();
+    buffer
+        .write_bytes(&contents)
+        .map_err(|e| e.to_avm(activation))?;
+    buffer.set_position(0);
+    Ok(Value::Undefined)
+}
+
+pub fn write_range<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let file = args.get_object(activation, 0, "file")?;
+    let buffer = args.get_object(activation, 1, "buffer")?;
+    let start = args.get_u32(activation, 2)? as usize;
+    let end = args.get_u32(activation, 3)? as usize;
+
+    let path = writable_sandbox_path(activation, file)?;
+    let data = buffer
+        .as_bytearray()
+        .and_then(|buffer| buffer.bytes().get(start..end).map(<[u8]>::to_vec))
+        .unwrap_or_default();
+    activation
+        .context
+        .filesystem
+        .write(&path, start as u64, &data)
+        .map_err(|e| file_error(activation, e))?;
+    Ok(Value::Undefined)
+}
+
+pub fn truncate_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let file = args.get_object(activation, 0, "file")?;
+    let length = args.get_u32(activation, 1)?;
+
+    let path = writable_sandbox_path(activation, file)?;
+    activation
+        .context
+        .filesystem
+        .truncate(&path, length.into())
+        .map_err(|e| file_error(activation, e))?;
+    Ok(Value::Undefined)
+}
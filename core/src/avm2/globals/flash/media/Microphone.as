@@ -1,160 +1,124 @@
 package flash.media {
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_setter;
     import __ruffle__.stub_method;
 
     import flash.events.EventDispatcher;
 
     public final class Microphone extends EventDispatcher {
+        // These only affect encoding of published streams, which we don't support.
+        private var _codec:String = SoundCodec.NELLYMOSER;
+        private var _enableVAD:Boolean = true;
+        private var _encodeQuality:int = 6;
+        private var _enhancedOptions:MicrophoneEnhancedOptions = new MicrophoneEnhancedOptions();
+        private var _framesPerPacket:int = 2;
+        private var _noiseSuppressionLevel:int = -30;
+        private var _soundTransform:SoundTransform = new SoundTransform();
+        private var _useEchoSuppression:Boolean = false;
+
         [API("672")]
         public static function getEnhancedMicrophone(index:int = -1):Microphone {
-            stub_method("flash.media.Microphone", "getEnhancedMicrophone");
-            return new Microphone();
+            return getMicrophone(index);
         }
 
-        public static function getMicrophone(index:int = -1):Microphone {
-            stub_method("flash.media.Microphone", "getMicrophone");
-            return new Microphone();
-        }
+        public static native function getMicrophone(index:int = -1):Microphone;
 
         public function setLoopBack(isLooped:Boolean = true):void {
             stub_method("flash.media.Microphone", "setLoopBack");
         }
 
-        public function setSilenceLevel(silenceLevel:Number, timeout:int = -1):void {
-            stub_method("flash.media.Microphone", "setSilenceLevel");
-        }
+        public native function setSilenceLevel(silenceLevel:Number, timeout:int = -1):void;
 
         public function setUseEchoSuppression(isEchoSuppressed:Boolean):void {
-            stub_method("flash.media.Microphone", "setUseEchoSuppression");
+            this._useEchoSuppression = isEchoSuppressed;
         }
 
-        public function get activityLevel():Number {
-            stub_getter("flash.media.Microphone", "activityLevel");
-            return 0.0;
-        }
+        public native function get activityLevel():Number;
 
         public function get codec():String {
-            stub_getter("flash.media.Microphone", "codec");
-            return "";
+            return this._codec;
         }
 
         public function set codec(codec:String) {
-            stub_setter("flash.media.Microphone", "codec");
+            this._codec = codec;
+            if (codec == SoundCodec.SPEEX) {
+                // Speex always encodes at 16 kHz.
+                this.rate = 16;
+            }
         }
 
         public function get enableVAD():Boolean {
-            stub_getter("flash.media.Microphone", "enableVAD");
-            return false;
+            return this._enableVAD;
         }
 
         public function set enableVAD(isEnabled:Boolean) {
-            stub_setter("flash.media.Microphone", "enableVAD");
+            this._enableVAD = isEnabled;
         }
 
         public function get encodeQuality():int {
-            stub_getter("flash.media.Microphone", "encodeQuality");
-            return 0;
+            return this._encodeQuality;
         }
 
         public function set encodeQuality(quality:int) {
-            stub_setter("flash.media.Microphone", "encodeQuality");
+            this._encodeQuality = quality;
         }
 
         [API("672")]
         public function get enhancedOptions():MicrophoneEnhancedOptions {
-            stub_getter("flash.media.Microphone", "enhancedOptions");
-            return new MicrophoneEnhancedOptions();
+            return this._enhancedOptions;
         }
 
         [API("672")]
         public function set enhancedOptions(params:MicrophoneEnhancedOptions) {
-            stub_setter("flash.media.Microphone", "enhancedOptions");
+            this._enhancedOptions = params;
         }
 
         public function get framesPerPacket():int {
-            stub_getter("flash.media.Microphone", "framesPerPacket");
-            return 0;
+            return this._framesPerPacket;
         }
 
         public function set framesPerPacket(fpp:int) {
-            stub_setter("flash.media.Microphone", "framesPerPacket");
+            this._framesPerPacket = fpp;
         }
 
-        public function get gain():Number {
-            stub_getter("flash.media.Microphone", "gain");
-            return 0.0;
-        }
+        public native function get gain():Number;
 
-        public function set gain(gain:Number) {
-            stub_setter("flash.media.Microphone", "gain");
-        }
+        public native function set gain(gain:Number);
 
-        public function get index():int {
-            stub_getter("flash.media.Microphone", "index");
-            return 0;
-        }
+        public native function get index():int;
 
-        public static function get isSupported():Boolean {
-            stub_getter("flash.media.Microphone", "isSupported");
-            return false;
-        }
+        public static native function get isSupported():Boolean;
 
-        public function get muted():Boolean {
-            stub_getter("flash.media.Microphone", "muted");
-            return true;
-        }
+        public native function get muted():Boolean;
 
-        public function get name():String {
-            stub_getter("flash.media.Microphone", "name");
-            return "";
-        }
+        public native function get name():String;
 
-        public static function get names():Array {
-            stub_getter("flash.media.Microphone", "names");
-            return [];
-        }
+        public static native function get names():Array;
 
         public function get noiseSuppressionLevel():int {
-            stub_getter("flash.media.Microphone", "noiseSuppressionLevel");
-            return 0;
+            return this._noiseSuppressionLevel;
         }
 
         public function set noiseSuppressionLevel(level:int) {
-            stub_setter("flash.media.Microphone", "noiseSuppressionLevel");
+            this._noiseSuppressionLevel = level;
         }
 
-        public function get rate():int {
-            stub_getter("flash.media.Microphone", "rate");
-            return 0;
-        }
+        public native function get rate():int;
 
-        public function set rate(level:int) {
-            stub_setter("flash.media.Microphone", "rate");
-        }
+        public native function set rate(level:int);
 
-        public function get silenceLevel():Number {
-            stub_getter("flash.media.Microphone", "silenceLevel");
-            return 0;
-        }
+        public native function get silenceLevel():Number;
 
-        public function get silenceTimeout():int {
-            stub_getter("flash.media.Microphone", "silenceTimeout");
-            return 0;
-        }
+        public native function get silenceTimeout():int;
 
         public function get soundTransform():flash.media.SoundTransform {
-            stub_getter("flash.media.Microphone", "soundTransform");
-            return new SoundTransform();
+            return this._soundTransform;
         }
 
         public function set soundTransform(tf:flash.media.SoundTransform) {
-            stub_setter("flash.media.Microphone", "soundTransform");
+            this._soundTransform = tf;
         }
 
         public function get useEchoSuppression():Boolean {
-            stub_getter("flash.media.Microphone", "useEchoSuppression");
-            return false;
+            return this._useEchoSuppression;
         }
     }
 }
@@ -48,6 +48,8 @@ package flash.media {
 
         public native function attachNetStream(netStream:NetStream):void;
 
+        public native function attachCamera(camera:Camera):void;
+
         public function clear():void {
             stub_method("flash.media.Video", "clear");
         }
@@ -0,0 +1,361 @@
+//! `flash.media.Camera` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::display::bitmap_data::get_rectangle_x_y_width_height;
+use crate::avm2::object::{ArrayObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{ArrayStorage, Error};
+use crate::backend::capture::CameraMode;
+use crate::bitmap::bitmap_data::Color;
+use crate::capture::{Camera, CaptureDevices};
+use crate::string::AvmString;
+use ruffle_render::bitmap::{Bitmap, PixelRegion};
+
+fn this_camera<'gc>(activation: &mut Activation<'_, 'gc>, this: Value<'gc>) -> Option<Camera<'gc>> {
+    let this = this.as_object()?;
+    activation.context.capture_devices.camera_for_object(this)
+}
+
+/// Returns the pixels of `frame` within the given rectangle as ARGB, row by
+/// row. The rectangle is clamped to the frame.
+fn frame_pixels(frame: &Bitmap, x: i32, y: i32, width: i32, height: i32) -> Vec<u32> {
+    let mut region = PixelRegion::for_region_i32(x, y, width, height);
+    region.clamp(frame.width(), frame.height());
+
+    let data = frame.data();
+    let mut pixels = Vec::with_capacity((region.width() * region.height()) as usize);
+    for y in region.y_min..region.y_max {
+        for x in region.x_min..region.x_max {
+            let i = ((y * frame.width() + x) * 4) as usize;
+            let color = Color::argb(0xFF, data[i], data[i + 1], data[i + 2]);
+            pixels.push(color.into());
+        }
+    }
+    pixels
+}
+
+/// Implements `Camera.getCamera`
+pub fn get_camera<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Cameras are named by their index in `Camera.names`.
+    let index = match args.try_get_string(activation, 0)? {
+        Some(name) => match name.to_string().parse::<usize>() {
+            Ok(index) => index,
+            Err(_) => return Ok(Value::Null),
+        },
+        None => 0,
+    };
+
+    let Some(camera) = CaptureDevices::camera(activation.context, index) else {
+        return Ok(Value::Null);
+    };
+
+    if let Some(object) = camera.avm2_object() {
+        return Ok(object.into());
+    }
+
+    let object = activation
+        .avm2()
+        .classes()
+        .camera
+        .construct(activation, &[])?;
+    camera.set_avm2_object(activation.context, object.as_object().unwrap());
+    Ok(object)
+}
+
+/// Implements `Camera.names`
+pub fn get_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names: Vec<Value<'gc>> = activation
+        .context
+        .capture
+        .camera_names()
+        .iter()
+        .map(|name| AvmString::new_utf8(activation.gc(), name).into())
+        .collect();
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_iter(names)).into())
+}
+
+/// Implements `Camera.isSupported`
+pub fn get_is_supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((!activation.context.capture.camera_names().is_empty()).into())
+}
+
+/// Implements `Camera.activityLevel`
+pub fn get_activity_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this_camera(activation, this)
+        .map(|camera| camera.activity_level())
+        .unwrap_or(-1.0)
+        .into())
+}
+
+/// Implements `Camera.currentFPS`
+pub fn get_current_fps<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this_camera(activation, this)
+        .map(|camera| camera.current_fps())
+        .unwrap_or(0.0)
+        .into())
+}
+
+/// Implements `Camera.fps`
+pub fn get_fps<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mode = this_camera(activation, this)
+        .map(|camera| camera.mode())
+        .unwrap_or_default();
+    Ok(mode.fps.into())
+}
+
+/// Implements `Camera.width`
+pub fn get_width<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mode = this_camera(activation, this)
+        .map(|camera| camera.mode())
+        .unwrap_or_default();
+    Ok(mode.width.into())
+}
+
+/// Implements `Camera.height`
+pub fn get_height<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mode = this_camera(activation, this)
+        .map(|camera| camera.mode())
+        .unwrap_or_default();
+    Ok(mode.height.into())
+}
+
+/// Implements `Camera.index`
+pub fn get_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this_camera(activation, this)
+        .map(|camera| camera.index() as i32)
+        .unwrap_or(-1)
+        .into())
+}
+
+/// Implements `Camera.motionLevel`
+pub fn get_motion_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this_camera(activation, this)
+        .map(|camera| camera.motion_level())
+        .unwrap_or(50)
+        .into())
+}
+
+/// Implements `Camera.motionTimeout`
+pub fn get_motion_timeout<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this_camera(activation, this)
+        .map(|camera| camera.motion_timeout())
+        .unwrap_or(2000)
+        .into())
+}
+
+/// Implements `Camera.muted`
+pub fn get_muted<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this_camera(activation, this)
+        .is_none_or(|camera| camera.is_muted())
+        .into())
+}
+
+/// Implements `Camera.name`
+pub fn get_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(camera) = this_camera(activation, this) else {
+        return Ok(Value::Null);
+    };
+
+    Ok(AvmString::new_utf8(activation.gc(), camera.name()).into())
+}
+
+/// Implements `Camera.setMode`
+pub fn set_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let width = args.get_i32(activation, 0)?;
+    let height = args.get_i32(activation, 1)?;
+    let fps = args.get_f64(activation, 2)?;
+
+    if let Some(camera) = this_camera(activation, this) {
+        let mode = CameraMode {
+            width: width.max(1) as u32,
+            height: height.max(1) as u32,
+            fps: if fps > 0.0 { fps } else { camera.mode().fps },
+        };
+        camera.set_mode(activation.context, mode);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.setMotionLevel`
+pub fn set_motion_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let motion_level = args.get_i32(activation, 0)?;
+    let timeout = args.get_i32(activation, 1)?;
+
+    if let Some(camera) = this_camera(activation, this) {
+        camera.set_motion_level(motion_level, timeout);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.drawToBitmapData`
+pub fn draw_to_bitmap_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let destination = args.get_object(activation, 0, "destination")?;
+    let Some(bitmap_data) = destination.as_bitmap_data() else {
+        return Ok(Value::Undefined);
+    };
+    bitmap_data.check_valid(activation)?;
+
+    let Some(camera) = this_camera(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let frame = camera.frame();
+    let Some(frame) = frame.as_ref() else {
+        return Ok(Value::Undefined);
+    };
+
+    let pixels = frame_pixels(
+        frame,
+        0,
+        0,
+        bitmap_data.width() as i32,
+        bitmap_data.height() as i32,
+    );
+    let mut region = PixelRegion::for_whole_size(frame.width(), frame.height());
+    region.clamp(bitmap_data.width(), bitmap_data.height());
+
+    let mc = activation.gc();
+    let target = if region.width() == bitmap_data.width() && region.height() == bitmap_data.height()
+    {
+        // We're replacing every pixel, so there's no need to sync from the GPU.
+        bitmap_data.overwrite_cpu_pixels_from_gpu(mc).0
+    } else {
+        bitmap_data.sync(activation.context.renderer)
+    };
+    let mut write = target.write(mc);
+    let mut pixels = pixels.into_iter();
+    for y in region.y_min..region.y_max {
+        for x in region.x_min..region.x_max {
+            if let Some(color) = pixels.next() {
+                write.set_pixel32_raw(x, y, Color::from(color));
+            }
+        }
+    }
+    write.set_cpu_dirty(mc, region);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.copyToByteArray`
+pub fn copy_to_byte_array<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let rect = args.get_object(activation, 0, "rect")?;
+    let (x, y, width, height) = get_rectangle_x_y_width_height(activation, rect)?;
+    let destination = args.get_object(activation, 1, "destination")?;
+
+    let Some(camera) = this_camera(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let pixels = match camera.frame().as_ref() {
+        Some(frame) => frame_pixels(frame, x, y, width, height),
+        None => return Ok(Value::Undefined),
+    };
+
+    if let Some(mut bytearray) = destination.as_bytearray_mut() {
+        for pixel in pixels {
+            bytearray
+                .write_unsigned_int(pixel)
+                .map_err(|e| e.to_avm(activation))?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.copyToVector`
+pub fn copy_to_vector<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let rect = args.get_object(activation, 0, "rect")?;
+    let (x, y, width, height) = get_rectangle_x_y_width_height(activation, rect)?;
+    let destination = args.get_object(activation, 1, "destination")?;
+
+    let Some(camera) = this_camera(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let pixels = match camera.frame().as_ref() {
+        Some(frame) => frame_pixels(frame, x, y, width, height),
+        None => return Ok(Value::Undefined),
+    };
+
+    if let Some(mut vector) = destination.as_vector_storage_mut(activation.gc()) {
+        vector.resize(pixels.len(), activation)?;
+        for (i, pixel) in pixels.into_iter().enumerate() {
+            vector.set(i, pixel.into(), activation)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,215 @@
+//! `flash.media.Microphone` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::ArrayObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{ArrayStorage, Error};
+use crate::capture::{CaptureDevices, Microphone};
+use crate::string::AvmString;
+
+fn this_microphone<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+) -> Option<Microphone<'gc>> {
+    let this = this.as_object()?;
+    activation
+        .context
+        .capture_devices
+        .microphone_for_object(this)
+}
+
+/// Implements `Microphone.getMicrophone` and `Microphone.getEnhancedMicrophone`
+pub fn get_microphone<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // -1 requests the default microphone.
+    let index = args.get_i32(activation, 0)?.max(0) as usize;
+
+    let Some(microphone) = CaptureDevices::microphone(activation.context, index) else {
+        return Ok(Value::Null);
+    };
+
+    if let Some(object) = microphone.avm2_object() {
+        return Ok(object.into());
+    }
+
+    let object = activation
+        .avm2()
+        .classes()
+        .microphone
+        .construct(activation, &[])?;
+    microphone.set_avm2_object(activation.context, object.as_object().unwrap());
+    Ok(object)
+}
+
+/// Implements `Microphone.names`
+pub fn get_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names: Vec<Value<'gc>> = activation
+        .context
+        .capture
+        .microphone_names()
+        .iter()
+        .map(|name| AvmString::new_utf8(activation.gc(), name).into())
+        .collect();
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_iter(names)).into())
+}
+
+/// Implements `Microphone.isSupported`
+pub fn get_is_supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((!activation.context.capture.microphone_names().is_empty()).into())
+}
+
+/// Implements `Microphone.activityLevel`
+pub fn get_activity_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this_microphone(activation, this)
+        .map(|microphone| microphone.activity_level())
+        .unwrap_or(-1.0)
+        .into())
+}
+
+/// Implements `Microphone.gain`'s getter
+pub fn get_gain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this_microphone(activation, this)
+        .map(|microphone| microphone.gain())
+        .unwrap_or(50.0)
+        .into())
+}
+
+/// Implements `Microphone.gain`'s setter
+pub fn set_gain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let gain = args.get_f64(activation, 0)?;
+
+    if let Some(microphone) = this_microphone(activation, this) {
+        microphone.set_gain(gain);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.index`
+pub fn get_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this_microphone(activation, this)
+        .map(|microphone| microphone.index() as i32)
+        .unwrap_or(-1)
+        .into())
+}
+
+/// Implements `Microphone.muted`
+pub fn get_muted<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this_microphone(activation, this)
+        .is_none_or(|microphone| microphone.is_muted())
+        .into())
+}
+
+/// Implements `Microphone.name`
+pub fn get_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(microphone) = this_microphone(activation, this) else {
+        return Ok(Value::Null);
+    };
+
+    Ok(AvmString::new_utf8(activation.gc(), microphone.name()).into())
+}
+
+/// Implements `Microphone.rate`'s getter
+pub fn get_rate<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this_microphone(activation, this)
+        .map(|microphone| microphone.rate())
+        .unwrap_or(8)
+        .into())
+}
+
+/// Implements `Microphone.rate`'s setter
+pub fn set_rate<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let rate = args.get_i32(activation, 0)?;
+
+    if let Some(microphone) = this_microphone(activation, this) {
+        microphone.set_rate(activation.context, rate.max(0) as u32);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.silenceLevel`
+pub fn get_silence_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this_microphone(activation, this)
+        .map(|microphone| microphone.silence_level())
+        .unwrap_or(10.0)
+        .into())
+}
+
+/// Implements `Microphone.silenceTimeout`
+pub fn get_silence_timeout<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this_microphone(activation, this)
+        .map(|microphone| microphone.silence_timeout())
+        .unwrap_or(2000)
+        .into())
+}
+
+/// Implements `Microphone.setSilenceLevel`
+pub fn set_silence_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let silence_level = args.get_f64(activation, 0)?;
+    // A negative timeout leaves the current timeout unchanged.
+    let timeout = args.get_i32(activation, 1)?;
+
+    if let Some(microphone) = this_microphone(activation, this) {
+        microphone.set_silence_level(silence_level, (timeout >= 0).then_some(timeout));
+    }
+
+    Ok(Value::Undefined)
+}
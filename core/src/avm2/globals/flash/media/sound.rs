@@ -259,11 +259,17 @@ pub fn load<'gc>(
         avm2_stub_method!(activation, "flash.media.Sound", "load", "with context");
     }
 
+    let sandboxed_movie = activation
+        .context
+        .security_sandbox
+        .is_strict()
+        .then(|| activation.caller_movie_or_root());
     let future = activation.context.load_manager.load_sound_avm2(
         activation.context.player.clone(),
         this_object,
         // FIXME: Set options from the `URLRequest`.
         Request::get(url.to_string()),
+        sandboxed_movie,
     );
     activation.context.navigator.spawn_future(future);
     this.set_loading_state(SoundLoadingState::Loading);
@@ -1,6 +1,7 @@
 //! `flash.media.SoundMixer` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::display::loader_info::check_content_access;
 use crate::avm2::object::TObject;
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
@@ -96,6 +97,12 @@ pub fn compute_spectrum<'gc>(
     _this: Value<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    // The spectrum reveals the audio of every playing sound, so all of them
+    // have to be accessible.
+    for movie in activation.context.audio_manager.sound_movies() {
+        check_content_access(activation, &movie, "SoundMixer.computeSpectrum")?;
+    }
+
     let arg0 = args.get_object(activation, 0, "sound")?;
     let mut bytearray = arg0.as_bytearray_mut().unwrap();
     let mut hist = activation.context.audio.get_sample_history();
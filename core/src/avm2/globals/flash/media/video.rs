@@ -89,3 +89,21 @@ pub fn attach_net_stream<'gc>(
 
     Ok(Value::Undefined)
 }
+
+pub fn attach_camera<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(video) = this.as_display_object().and_then(|dobj| dobj.as_video()) {
+        let camera = args
+            .try_get_object(activation, 0)
+            .and_then(|o| activation.context.capture_devices.camera_for_object(o));
+
+        video.attach_camera(activation.context, camera);
+    }
+
+    Ok(Value::Undefined)
+}
@@ -8,6 +8,7 @@ use crate::avm2::{Activation, Error, Object, Value};
 use crate::backend::navigator::NavigationMethod;
 use indexmap::IndexMap;
 
+pub mod datagram_socket;
 pub mod file_reference;
 pub mod local_connection;
 pub mod net_connection;
@@ -17,6 +18,7 @@ pub mod responder;
 pub mod shared_object;
 pub mod socket;
 pub mod url_loader;
+pub mod url_stream;
 pub mod xml_socket;
 
 fn object_to_index_map<'gc>(
@@ -1,5 +1,33 @@
 package flash.net {
+    import flash.events.EventDispatcher;
+    import flash.utils.ByteArray;
+
     [API("668")] // AIR 2.0
-    public class DatagramSocket {
+    public class DatagramSocket extends EventDispatcher {
+        public static function get isSupported():Boolean {
+            return true;
+        }
+
+        public native function get bound():Boolean;
+
+        public native function get connected():Boolean;
+
+        public native function get localAddress():String;
+
+        public native function get localPort():int;
+
+        public native function get remoteAddress():String;
+
+        public native function get remotePort():int;
+
+        public native function bind(localPort:int = 0, localAddress:String = "0.0.0.0"):void;
+
+        public native function close():void;
+
+        public native function connect(remoteAddress:String, remotePort:int):void;
+
+        public native function receive():void;
+
+        public native function send(bytes:ByteArray, offset:uint = 0, length:uint = 0, address:String = null, port:int = 0):void;
     }
 }
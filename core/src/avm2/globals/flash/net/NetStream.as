@@ -18,15 +18,14 @@ package flash.net {
         public static const DIRECT_CONNECTIONS:String = "directConnections";
 
         public function NetStream(connection:NetConnection, peer:String = CONNECT_TO_FMS) {
+            this.init(connection);
         }
 
-        public function appendBytes(bytes:ByteArray) {
-            stub_method("flash.net.NetStream", "appendBytes");
-        }
+        private native function init(connection:NetConnection):void;
 
-        public function appendBytesAction(action:String) {
-            stub_method("flash.net.NetStream", "appendBytesAction");
-        }
+        public native function appendBytes(bytes:ByteArray):void;
+
+        public native function appendBytesAction(action:String):void;
 
         public function attach(connection:NetConnection) {
             stub_method("flash.net.NetStream", "attach");
@@ -40,9 +39,7 @@ package flash.net {
             stub_method("flash.net.NetStream", "attachCamera");
         }
 
-        public function close() {
-            stub_method("flash.net.NetStream", "close");
-        }
+        public native function close():void;
 
         [API("674")]
         public function dispose() {
@@ -120,10 +117,7 @@ package flash.net {
             stub_setter("flash.net.NetStream", "audioSampleAccess");
         }
 
-        public function get backBufferLength():Number {
-            stub_getter("flash.net.NetStream", "backBufferLength");
-            return 0.0;
-        }
+        public native function get backBufferLength():Number;
 
         public function get backBufferTime():Number {
             stub_getter("flash.net.NetStream", "backBufferTime");
@@ -134,28 +128,15 @@ package flash.net {
             stub_setter("flash.net.NetStream", "backBufferTime");
         }
 
-        public function get bufferLength():Number {
-            stub_getter("flash.net.NetStream", "bufferLength");
-            return 0.0;
-        }
+        public native function get bufferLength():Number;
 
-        public function get bufferTime():Number {
-            stub_getter("flash.net.NetStream", "bufferTime");
-            return 0.0;
-        }
+        public native function get bufferTime():Number;
 
-        public function set bufferTime(time:Number) {
-            stub_setter("flash.net.NetStream", "bufferTime");
-        }
+        public native function set bufferTime(time:Number);
 
-        public function get bufferTimeMax():Number {
-            stub_getter("flash.net.NetStream", "bufferTimeMax");
-            return 0.0;
-        }
+        public native function get bufferTimeMax():Number;
 
-        public function set bufferTimeMax(time:Number) {
-            stub_setter("flash.net.NetStream", "bufferTimeMax");
-        }
+        public native function set bufferTimeMax(time:Number);
 
         public native function get bytesLoaded():uint;
 
@@ -174,10 +155,7 @@ package flash.net {
 
         public native function set client(client:Object);
 
-        public function get currentFPS():Number {
-            stub_getter("flash.net.NetStream", "currentFPS");
-            return 0.0;
-        }
+        public native function get currentFPS():Number;
 
         public function get dataReliable():Boolean {
             stub_getter("flash.net.NetStream", "dataReliable");
@@ -207,10 +185,7 @@ package flash.net {
             stub_setter("flash.net.NetStream", "inBufferSeek");
         }
 
-        public function get info():NetStreamInfo {
-            stub_getter("flash.net.NetStream", "info");
-            return new NetStreamInfo();
-        }
+        public native function get info():NetStreamInfo;
 
         public function get liveDelay():Number {
             stub_getter("flash.net.NetStream", "liveDelay");
@@ -14,7 +14,12 @@ package flash.net {
         // you will need to adjust the serialization and deserialization code
         // to work with AMF0.
 
+        // SharedObjects are created without running the constructor, so this
+        // can't be initialized to `this`.
+        private var _client:Object;
+
         public static native function getLocal(name:String, localPath:String = null, secure:Boolean = false):SharedObject;
+        public static native function getRemote(name:String, remotePath:String = null, persistence:Object = false, secure:Boolean = false):SharedObject;
 
         public native function get size():uint;
         public native function get objectEncoding():uint;
@@ -24,10 +29,24 @@ package flash.net {
         public native function close():void;
         public native function clear():void;
 
+        public native function connect(myConnection:NetConnection, params:String = null):void;
+        public native function send(...arguments):void;
+        public native function set fps(updatesPerSecond:Number):void;
+        public native function setDirty(propertyName:String):void;
+
+        public function get client():Object {
+            return this._client == null ? this : this._client;
+        }
+        public function set client(value:Object):void {
+            if (value == null) {
+                throw new TypeError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            this._client = value;
+        }
+
         public function setProperty(propertyName:String, value:Object = null):void {
             this.data[propertyName] = value;
-            // This should also mark remote SharedObjects as dirty,
-            // but we don't support them yet
+            this.setDirty(propertyName);
         }
 
         public native function get data():Object;
@@ -15,6 +15,7 @@ package flash.net {
 
     public class URLStream extends EventDispatcher implements IDataInput {
         private var _endian:String = Endian.BIG_ENDIAN;
+        [Ruffle(NativeAccessible)]
         private var _connected:Boolean = false;
 
         // FIXME - we currently implement `URLStream` using a `URLLoader`,
@@ -23,6 +24,7 @@ package flash.net {
         // We should write an actual "streaming" implementation that exposes
         // content as it comes in over the network, but this will require changes
         // to `NavigatorBackend`. See https://github.com/ruffle-rs/ruffle/pull/11046
+        [Ruffle(NativeAccessible)]
         private var _loader:URLLoader = new URLLoader();
 
         public function URLStream() {
@@ -79,10 +81,8 @@ package flash.net {
             }
         }
 
-        public function load(request:URLRequest):void {
-            this._loader.load(request);
-            this._connected = true;
-        }
+        // This is native so that the security sandbox sees the calling movie.
+        public native function load(request:URLRequest):void;
 
         public function close():void {
             this._loader.close();
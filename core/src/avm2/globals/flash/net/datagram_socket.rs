@@ -0,0 +1,360 @@
+//! `flash.net.DatagramSocket` native methods
+
+use crate::avm2::error::{
+    illegal_operation_error, io_error, make_error_2004, make_error_2006, make_error_2007,
+    range_error, Error2004Type,
+};
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::context::UpdateContext;
+use crate::datagram_socket::{Datagram, DatagramSocketHandle};
+use crate::socket::SandboxedConnection;
+use crate::string::AvmString;
+use std::net::IpAddr;
+
+/// Implements `DatagramSocket.bound`
+pub fn get_bound<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    Ok(socket_handle(activation, this).is_some().into())
+}
+
+/// Implements `DatagramSocket.connected`
+pub fn get_connected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let connected = socket_handle(activation, this).is_some_and(|handle| {
+        activation
+            .context
+            .datagram_sockets
+            .remote_address(handle)
+            .is_some()
+    });
+    Ok(connected.into())
+}
+
+/// Implements `DatagramSocket.localAddress`
+pub fn get_local_address<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let Some(handle) = socket_handle(activation, this) else {
+        return Ok(Value::Null);
+    };
+
+    match activation.context.datagram_sockets.local_address(handle) {
+        Some((address, _)) => Ok(AvmString::new_utf8(activation.gc(), address).into()),
+        None => Ok(Value::Null),
+    }
+}
+
+/// Implements `DatagramSocket.localPort`
+pub fn get_local_port<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let port = socket_handle(activation, this)
+        .and_then(|handle| activation.context.datagram_sockets.local_address(handle))
+        .map_or(0, |(_, port)| port);
+    Ok(port.into())
+}
+
+/// Implements `DatagramSocket.remoteAddress`
+pub fn get_remote_address<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let Some(handle) = socket_handle(activation, this) else {
+        return Ok(Value::Null);
+    };
+
+    match activation.context.datagram_sockets.remote_address(handle) {
+        Some((address, _)) => Ok(AvmString::new_utf8(activation.gc(), address).into()),
+        None => Ok(Value::Null),
+    }
+}
+
+/// Implements `DatagramSocket.remotePort`
+pub fn get_remote_port<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let port = socket_handle(activation, this)
+        .and_then(|handle| activation.context.datagram_sockets.remote_address(handle))
+        .map_or(0, |(_, port)| port);
+    Ok(port.into())
+}
+
+/// Implements `DatagramSocket.bind`
+pub fn bind<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let port = args.get_i32(activation, 0)?;
+    let port = u16::try_from(port).map_err(|_| invalid_port_number(activation))?;
+    let address = args.get_string(activation, 1)?;
+    let address = parse_address(activation, address)?;
+
+    if socket_handle(activation, this).is_some() {
+        return Err(invalid_socket_error(activation));
+    }
+
+    bind_socket(activation, this, address, port)?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DatagramSocket.connect`
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let address = args.get_string(activation, 0)?;
+    let address = parse_address(activation, address)?;
+    let port = args.get_i32(activation, 1)?;
+    let port = remote_port(activation, port)?;
+
+    let handle = match socket_handle(activation, this) {
+        Some(handle) => handle,
+        // Like the OS, connecting an unbound socket binds it to any free port.
+        None => bind_socket(activation, this, "0.0.0.0".to_string(), 0)?,
+    };
+
+    activation
+        .context
+        .datagram_sockets
+        .connect(handle, address, port);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DatagramSocket.receive`
+pub fn receive<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let handle = socket_handle(activation, this).ok_or_else(|| invalid_socket_error(activation))?;
+    activation.context.datagram_sockets.receive(handle);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DatagramSocket.send`
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let bytes = args
+        .try_get_object(activation, 0)
+        .ok_or_else(|| make_error_2007(activation, "bytes"))?;
+    let offset = args.get_u32(activation, 1)? as usize;
+    let length = args.get_u32(activation, 2)? as usize;
+
+    let data = match bytes.as_bytearray() {
+        Some(bytearray) => {
+            let bytes = bytearray.bytes();
+            // A length of 0 sends everything after the offset.
+            let end = if length == 0 {
+                Some(bytes.len())
+            } else {
+                offset.checked_add(length)
+            };
+            end.and_then(|end| bytes.get(offset..end))
+                .map(|data| data.to_vec())
+        }
+        None => return Ok(Value::Undefined),
+    };
+    let Some(data) = data else {
+        return Err(make_error_2006(activation));
+    };
+
+    let handle = socket_handle(activation, this);
+    let remote = handle.and_then(|handle| {
+        activation
+            .context
+            .datagram_sockets
+            .remote_address(handle)
+            .map(|(address, port)| (address.to_string(), port))
+    });
+
+    let address = args.try_get_string(activation, 3)?;
+    let port = args.get_i32(activation, 4)?;
+    let (address, port) = match remote {
+        Some(remote) if address.is_none() && port == 0 => remote,
+        // A connected socket can only send to the address it's connected to.
+        Some(_) => return Err(already_connected_error(activation)),
+        None => {
+            let Some(address) = address else {
+                return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+            };
+            (
+                parse_address(activation, address)?,
+                remote_port(activation, port)?,
+            )
+        }
+    };
+
+    let handle = match handle {
+        Some(handle) => handle,
+        // Like the OS, sending from an unbound socket binds it to any free port.
+        None => bind_socket(activation, this, "0.0.0.0".to_string(), 0)?,
+    };
+
+    let sandboxed = activation
+        .context
+        .security_sandbox
+        .is_strict()
+        .then(|| SandboxedConnection {
+            player: activation.context.player.clone(),
+            movie: activation.caller_movie_or_root(),
+        });
+
+    let UpdateContext {
+        datagram_sockets,
+        navigator,
+        ..
+    } = activation.context;
+
+    datagram_sockets.send(
+        *navigator,
+        handle,
+        Datagram {
+            address,
+            port,
+            data,
+        },
+        sandboxed,
+    );
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DatagramSocket.close`
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let handle = socket_handle(activation, this).ok_or_else(|| invalid_socket_error(activation))?;
+    activation.context.datagram_sockets.close(handle);
+
+    Ok(Value::Undefined)
+}
+
+fn socket_handle<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Option<DatagramSocketHandle> {
+    activation.context.datagram_sockets.handle_for_object(this)
+}
+
+fn bind_socket<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    address: String,
+    port: u16,
+) -> Result<DatagramSocketHandle, Error<'gc>> {
+    let UpdateContext {
+        datagram_sockets,
+        navigator,
+        ..
+    } = activation.context;
+
+    datagram_sockets
+        .bind(*navigator, this, address, port)
+        .ok_or_else(|| socket_error(activation))
+}
+
+/// Parses an IP address, which can't be a host name.
+fn parse_address<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    address: AvmString<'gc>,
+) -> Result<String, Error<'gc>> {
+    match address.to_utf8_lossy().parse::<IpAddr>() {
+        Ok(address) => Ok(address.to_string()),
+        Err(_) => Err(make_error_2004(activation, Error2004Type::ArgumentError)),
+    }
+}
+
+/// Checks the port of a remote address, which can't be 0.
+fn remote_port<'gc>(activation: &mut Activation<'_, 'gc>, port: i32) -> Result<u16, Error<'gc>> {
+    u16::try_from(port)
+        .ok()
+        .filter(|port| *port != 0)
+        .ok_or_else(|| invalid_port_number(activation))
+}
+
+fn invalid_socket_error<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+    match io_error(
+        activation,
+        "Error #2002: Operation attempted on invalid socket.",
+        2002,
+    ) {
+        Ok(err) => Error::AvmError(err),
+        Err(e) => e,
+    }
+}
+
+fn invalid_port_number<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+    match range_error(
+        activation,
+        "Error #2003: Invalid socket port number specified.",
+        2003,
+    ) {
+        Ok(err) => Error::AvmError(err),
+        Err(e) => e,
+    }
+}
+
+fn socket_error<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+    match io_error(activation, "Error #2031: Socket Error.", 2031) {
+        Ok(err) => Error::AvmError(err),
+        Err(e) => e,
+    }
+}
+
+fn already_connected_error<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+    match illegal_operation_error(
+        activation,
+        "Error #2002: Operation attempted on invalid socket.",
+        2002,
+    ) {
+        Ok(err) => Error::AvmError(err),
+        Err(e) => e,
+    }
+}
@@ -14,6 +14,7 @@ use flash_lso::types::AMFVersion;
 use flash_lso::types::ObjectId;
 use flash_lso::types::Value as AMFValue;
 use fnv::FnvHashMap;
+use ruffle_macros::istr;
 use ruffle_wstr::WStr;
 use std::rc::Rc;
 
@@ -34,17 +35,55 @@ pub fn connect<'gc>(
     }
 
     let url = args.get_string(activation, 0)?;
+    let sandboxed_movie = activation
+        .context
+        .security_sandbox
+        .is_strict()
+        .then(|| activation.caller_movie_or_root());
     if url.starts_with(WStr::from_units(b"http://"))
         || url.starts_with(WStr::from_units(b"https://"))
     {
         // HTTP(S) is for Flash Remoting, which is just POST requests to the URL.
-        NetConnections::connect_to_flash_remoting(activation.context, connection, url.to_string());
+        NetConnections::connect_to_flash_remoting(
+            activation.context,
+            connection,
+            url.to_string(),
+            sandboxed_movie,
+        );
+    } else if url.starts_with(WStr::from_units(b"rtmp")) {
+        let object_encoding = Value::from(this)
+            .get_public_property(istr!("objectEncoding"), activation)?
+            .coerce_to_u32(activation)?;
+        let object_encoding = if object_encoding == 3 {
+            AMFVersion::AMF3
+        } else {
+            AMFVersion::AMF0
+        };
+
+        let mut arguments = Vec::new();
+        let mut object_table = FnvHashMap::default();
+        for arg in &args[1..] {
+            if let Some(value) =
+                serialize_value(activation, *arg, object_encoding, &mut object_table)
+            {
+                arguments.push(Rc::new(value));
+            }
+        }
+
+        NetConnections::connect_to_rtmp(
+            activation.context,
+            connection,
+            url.to_string(),
+            object_encoding,
+            arguments,
+            sandboxed_movie,
+        );
     } else {
         avm2_stub_method!(
             activation,
             "flash.net.NetConnection",
             "connect",
-            "with non-null, non-http command"
+            "with non-null, non-http, non-rtmp command"
         );
     }
 
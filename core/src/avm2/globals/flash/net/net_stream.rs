@@ -1,9 +1,174 @@
-use crate::avm2::error::{make_error_2004, Error2004Type};
+use crate::avm2::error::{make_error_2004, make_error_2007, make_error_2008, Error2004Type};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, TObject, Value};
+use crate::streams::AppendBytesAction;
 
 pub use crate::avm2::object::netstream_allocator as net_stream_allocator;
 
+/// Implements `flash.net.NetStream`'s `init` method, which is called from the constructor
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let connection = args
+        .try_get_object(activation, 0)
+        .and_then(|o| o.as_net_connection());
+    if let (Some(ns), Some(connection)) = (this.as_netstream(), connection) {
+        ns.set_connection(activation.gc(), connection.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn append_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let bytes = args
+        .try_get_object(activation, 0)
+        .ok_or_else(|| make_error_2007(activation, "bytes"))?;
+
+    let data = bytes
+        .as_bytearray()
+        .map(|bytearray| bytearray.bytes().to_vec());
+    if let (Some(ns), Some(mut data)) = (this.as_netstream(), data) {
+        ns.append_bytes(activation.context, &mut data);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn append_bytes_action<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let action = args.get_string(activation, 0)?;
+    let action = if &action == b"resetBegin" {
+        AppendBytesAction::ResetBegin
+    } else if &action == b"resetSeek" {
+        AppendBytesAction::ResetSeek
+    } else if &action == b"endSequence" {
+        AppendBytesAction::EndSequence
+    } else {
+        return Err(make_error_2008(activation, "action"));
+    };
+
+    if let Some(ns) = this.as_netstream() {
+        ns.append_bytes_action(activation.context, action);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_back_buffer_length<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(ns) = this.as_netstream() {
+        return Ok(ns.back_buffer_length().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_buffer_length<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(ns) = this.as_netstream() {
+        return Ok(ns.buffer_length().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_buffer_time<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(ns) = this.as_netstream() {
+        return Ok(ns.buffer_time().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_buffer_time<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(ns) = this.as_netstream() {
+        let time = args.get_f64(activation, 0)?;
+        ns.set_buffer_time(time);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_buffer_time_max<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(ns) = this.as_netstream() {
+        return Ok(ns.buffer_time_max().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_buffer_time_max<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(ns) = this.as_netstream() {
+        let time = args.get_f64(activation, 0)?;
+        ns.set_buffer_time_max(time);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(ns) = this.as_netstream() {
+        ns.close(activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_bytes_loaded<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
@@ -32,6 +197,67 @@ pub fn get_bytes_total<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_current_fps<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(ns) = this.as_netstream() {
+        return Ok(ns.current_fps().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let Some(ns) = this.as_netstream() else {
+        return Ok(Value::Undefined);
+    };
+
+    let info = ns.info();
+    let args = [
+        info.bytes_per_second.total().into(),
+        info.byte_count.total().into(),
+        info.max_bytes_per_second.into(),
+        info.bytes_per_second.audio.into(),
+        info.byte_count.audio.into(),
+        info.bytes_per_second.video.into(),
+        info.byte_count.video.into(),
+        info.bytes_per_second.data.into(),
+        info.byte_count.data.into(),
+        info.bytes_per_second.total().into(),
+        // We never drop frames, nor lose packets.
+        0.into(),
+        info.buffer_byte_length.audio.into(),
+        info.buffer_byte_length.video.into(),
+        info.buffer_byte_length.data.into(),
+        info.buffer_length.into(),
+        info.buffer_length.into(),
+        info.buffer_length.into(),
+        0.into(),
+        0.into(),
+        0.into(),
+        Value::Null,
+        Value::Null,
+        Value::Null,
+        Value::Null,
+        false.into(),
+    ];
+    activation
+        .avm2()
+        .classes()
+        .netstreaminfo
+        .construct(activation, &args)
+}
+
 pub fn play<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
@@ -47,6 +273,7 @@ pub fn play<'gc>(
             .map(|v| v.coerce_to_string(activation))
             .transpose()?;
 
+        ns.set_avm2_domain(activation.gc(), activation.caller_domain());
         ns.play(activation.context, name);
     }
 
@@ -1,13 +1,20 @@
 //! `flash.net.SharedObject` builtin/prototype
 
-use crate::avm2::error::error;
+use crate::avm2::amf::{deserialize_value, recursive_serialize, serialize_value};
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::error::{error, make_error_2126};
 pub use crate::avm2::object::shared_object_allocator;
-use crate::avm2::object::{ScriptObject, SharedObjectObject, TObject};
-use crate::avm2::{Activation, Error, Object, Value};
+use crate::avm2::object::{ArrayObject, EventObject, ScriptObject, SharedObjectObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Avm2, Error, Object, Value};
+use crate::context::UpdateContext;
+use crate::net_connection::{RemoteSharedObjectHandle, SharedObjectTarget, SyncUpdate};
+use crate::string::AvmString;
 use crate::{avm2_stub_getter, avm2_stub_method, avm2_stub_setter};
-use flash_lso::types::{AMFVersion, Lso};
+use flash_lso::types::{AMFVersion, Element, Lso, Value as AmfValue};
 use ruffle_macros::istr;
 use std::borrow::Cow;
+use std::rc::Rc;
 
 fn new_lso<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -174,6 +181,49 @@ pub fn get_local<'gc>(
     Ok(created_shared_object.into())
 }
 
+pub fn get_remote<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?.to_utf8_lossy().to_string();
+
+    const INVALID_CHARS: &str = "~%&\\;:\"',<>?# ";
+    if name.contains(|c| INVALID_CHARS.contains(c)) {
+        tracing::error!("SharedObject::get_remote: Invalid character in name");
+        return Ok(Value::Null);
+    }
+
+    let uri = args
+        .try_get_string(activation, 1)?
+        .map(|uri| uri.to_utf8_lossy().to_string())
+        .unwrap_or_default();
+    // A string persistence means the object is also stored locally, which we
+    // don't support; it's persistent on the server either way.
+    let persistent = args.get_value(2).coerce_to_boolean();
+
+    // Check if this is referencing an existing shared object
+    if let Some(SharedObjectTarget::Avm2(so)) = activation
+        .context
+        .net_connections
+        .find_shared_object(&uri, &name)
+    {
+        return Ok(so.into());
+    }
+
+    let data = ScriptObject::new_object(activation);
+    let created_shared_object =
+        SharedObjectObject::from_data_and_name(activation, data, name.clone());
+    activation.context.net_connections.add_shared_object(
+        SharedObjectTarget::Avm2(created_shared_object.into()),
+        uri,
+        name,
+        persistent,
+    );
+
+    Ok(created_shared_object.into())
+}
+
 pub fn get_data<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
@@ -195,6 +245,11 @@ pub fn flush<'gc>(
 
     let shared_object = this.as_shared_object().unwrap();
 
+    // Remote SharedObjects are stored by the server.
+    if remote_handle(activation, this).is_some() {
+        return Ok(istr!("flushed").into());
+    }
+
     let data = shared_object.data();
     let name = shared_object.name();
 
@@ -241,10 +296,18 @@ pub fn get_size<'gc>(
 
 pub fn close<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
+    this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.net.SharedObject", "close");
+    let this = this.as_object().unwrap();
+
+    match remote_handle(activation, this) {
+        Some(handle) => activation
+            .context
+            .net_connections
+            .close_shared_object(handle),
+        None => avm2_stub_method!(activation, "flash.net.SharedObject", "close"),
+    }
     Ok(Value::Undefined)
 }
 
@@ -260,6 +323,15 @@ pub fn clear<'gc>(
     // Clear the local data object.
     shared_object.reset_data(activation);
 
+    // A remote SharedObject is also disconnected.
+    if let Some(handle) = remote_handle(activation, this) {
+        activation
+            .context
+            .net_connections
+            .close_shared_object(handle);
+        return Ok(Value::Undefined);
+    }
+
     // Delete data from storage backend.
     let name = shared_object.name();
     activation.context.storage.remove_key(name);
@@ -284,3 +356,243 @@ pub fn set_object_encoding<'gc>(
     avm2_stub_setter!(activation, "flash.net.SharedObject", "objectEncoding");
     Ok(Value::Undefined)
 }
+
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let connection = args.get_object(activation, 0, "myConnection")?;
+    let Some(handle) = remote_handle(activation, this) else {
+        avm2_stub_method!(
+            activation,
+            "flash.net.SharedObject",
+            "connect",
+            "with local SharedObject"
+        );
+        return Ok(Value::Undefined);
+    };
+
+    let connection = connection
+        .as_net_connection()
+        .and_then(|connection| connection.handle());
+    let connected = connection.is_some_and(|connection| {
+        activation
+            .context
+            .net_connections
+            .connect_shared_object(handle, connection)
+    });
+    if !connected {
+        return Err(make_error_2126(activation));
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let Some(handle) = remote_handle(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+
+    let mut values = Vec::with_capacity(args.len());
+    for arg in args {
+        let value = serialize_value(activation, *arg, AMFVersion::AMF0, &mut Default::default())
+            .unwrap_or(AmfValue::Undefined);
+        values.push(Rc::new(value));
+    }
+    activation
+        .context
+        .net_connections
+        .send_to_shared_object(handle, values);
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_fps<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let fps = args.get_f64(activation, 0)?;
+    if let Some(handle) = remote_handle(activation, this) {
+        activation
+            .context
+            .net_connections
+            .set_shared_object_fps(handle, fps);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_dirty<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let name = args.get_string(activation, 0)?;
+    if let Some(handle) = remote_handle(activation, this) {
+        activation
+            .context
+            .net_connections
+            .set_shared_object_dirty(handle, name.to_utf8_lossy().to_string());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn remote_handle<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Option<RemoteSharedObjectHandle> {
+    activation
+        .context
+        .net_connections
+        .shared_object_handle(SharedObjectTarget::Avm2(this))
+}
+
+/// Serializes the slots of a remote SharedObject's data, to find the changes
+/// to send to the server.
+pub fn serialize_slots<'gc>(
+    context: &mut UpdateContext<'gc>,
+    this: Object<'gc>,
+) -> Option<Vec<Element>> {
+    let mut activation = Activation::from_nothing(context);
+    let data = this.as_shared_object()?.data();
+
+    let mut elements = Vec::new();
+    match recursive_serialize(
+        &mut activation,
+        data,
+        &mut elements,
+        None,
+        AMFVersion::AMF0,
+        &mut Default::default(),
+    ) {
+        Ok(()) => Some(elements),
+        Err(e) => {
+            tracing::error!("Couldn't serialize SharedObject data: {e:?}");
+            None
+        }
+    }
+}
+
+/// Applies changes from the server to a remote SharedObject's data, returning
+/// the change list for its `sync` event.
+pub fn apply_sync<'gc>(
+    context: &mut UpdateContext<'gc>,
+    this: Object<'gc>,
+    updates: Vec<SyncUpdate>,
+) -> Option<Object<'gc>> {
+    let mut activation = Activation::from_nothing(context);
+    match apply_sync_updates(&mut activation, this, updates) {
+        Ok(change_list) => Some(change_list),
+        Err(e) => {
+            tracing::error!("Couldn't apply SharedObject changes: {e:?}");
+            None
+        }
+    }
+}
+
+fn apply_sync_updates<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    updates: Vec<SyncUpdate>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let shared_object = this.as_shared_object().unwrap();
+
+    let mut changes = Vec::with_capacity(updates.len());
+    for update in updates {
+        let (code, name, old_value) = match update {
+            SyncUpdate::Clear => {
+                shared_object.reset_data(activation);
+                ("clear", None, None)
+            }
+            SyncUpdate::Change(name, value, code) => {
+                let data = shared_object.data();
+                let name = AvmString::new_utf8(activation.gc(), name);
+                let old_value = data.get_string_property_local(name, activation)?;
+                let value = deserialize_value(activation, &value)?;
+                data.set_string_property_local(name, value, activation)?;
+                (code, Some(name), Some(old_value))
+            }
+            SyncUpdate::Success(name) => {
+                let name = AvmString::new_utf8(activation.gc(), name);
+                ("success", Some(name), None)
+            }
+            SyncUpdate::Delete(name) => {
+                let data = shared_object.data();
+                let name = AvmString::new_utf8(activation.gc(), name);
+                let old_value = data.get_string_property_local(name, activation)?;
+                data.delete_string_property_local(name, activation)?;
+                ("delete", Some(name), Some(old_value))
+            }
+        };
+
+        let change = ScriptObject::new_object(activation);
+        let code = AvmString::new_utf8(activation.gc(), code);
+        change.set_string_property_local(istr!("code"), code.into(), activation)?;
+        if let Some(name) = name {
+            change.set_string_property_local(istr!("name"), name.into(), activation)?;
+        }
+        if let Some(old_value) = old_value {
+            let key = AvmString::new_utf8(activation.gc(), "oldValue");
+            change.set_string_property_local(key, old_value, activation)?;
+        }
+        changes.push(change.into());
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&changes)).into())
+}
+
+/// Dispatches the `sync` event of a remote SharedObject.
+pub fn dispatch_sync<'gc>(
+    context: &mut UpdateContext<'gc>,
+    this: Object<'gc>,
+    change_list: Object<'gc>,
+) {
+    let mut activation = Activation::from_nothing(context);
+    let class = activation.avm2().classes().syncevent;
+    let event_type = AvmString::new_utf8(activation.gc(), "sync");
+    let event = EventObject::from_class_and_args(
+        &mut activation,
+        class,
+        &[
+            event_type.into(),
+            false.into(),
+            false.into(),
+            change_list.into(),
+        ],
+    );
+    Avm2::dispatch_event(activation.context, event, this);
+}
+
+/// Calls a method of a remote SharedObject's client, on behalf of `send`.
+pub fn call_handler<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    method: &str,
+    arguments: &[Rc<AmfValue>],
+) -> Result<(), Error<'gc>> {
+    let client = Value::from(this).get_public_property(istr!("client"), activation)?;
+
+    let mut args = Vec::with_capacity(arguments.len());
+    for argument in arguments {
+        args.push(deserialize_value(activation, argument)?);
+    }
+
+    let method = AvmString::new_utf8(activation.gc(), method);
+    client.call_public_property(method, &args, activation)?;
+    Ok(())
+}
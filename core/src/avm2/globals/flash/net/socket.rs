@@ -7,6 +7,7 @@ use crate::avm2::parameters::ParametersExt;
 use crate::avm2::string::AvmString;
 use crate::avm2::{Activation, Error, TObject, Value};
 use crate::context::UpdateContext;
+use crate::socket::SandboxedConnection;
 use encoding_rs::Encoding;
 use encoding_rs::UTF_8;
 use flash_lso::amf0::read::AMF0Decoder;
@@ -44,11 +45,26 @@ pub fn connect<'gc>(
         .try_into()
         .map_err(|_| invalid_port_number(activation))?;
 
+    let sandboxed = activation
+        .context
+        .security_sandbox
+        .is_strict()
+        .then(|| SandboxedConnection {
+            player: activation.context.player.clone(),
+            movie: activation.caller_movie_or_root(),
+        });
+
     let UpdateContext {
         sockets, navigator, ..
     } = activation.context;
 
-    sockets.connect_avm2(*navigator, socket, host.to_utf8_lossy().into_owned(), port);
+    sockets.connect_avm2(
+        *navigator,
+        socket,
+        host.to_utf8_lossy().into_owned(),
+        port,
+        sandboxed,
+    );
 
     Ok(Value::Undefined)
 }
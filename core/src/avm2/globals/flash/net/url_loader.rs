@@ -19,17 +19,23 @@ pub fn load<'gc>(
     spawn_fetch(activation, this, request)
 }
 
-fn spawn_fetch<'gc>(
+pub fn spawn_fetch<'gc>(
     activation: &mut Activation<'_, 'gc>,
     loader_object: Object<'gc>,
     url_request: Object<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
     let request = request_from_url_request(activation, url_request)?;
+    let sandboxed_movie = activation
+        .context
+        .security_sandbox
+        .is_strict()
+        .then(|| activation.caller_movie_or_root());
 
     let future = activation.context.load_manager.load_data_into_url_loader(
         activation.context.player.clone(),
         loader_object,
         request,
+        sandboxed_movie,
     );
     activation.context.navigator.spawn_future(future);
     Ok(Value::Undefined)
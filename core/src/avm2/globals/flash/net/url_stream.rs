@@ -0,0 +1,29 @@
+//! `flash.net.URLStream` native function definitions
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::net::url_loader::spawn_fetch;
+use crate::avm2::globals::slots::flash_net_url_stream as url_stream_slots;
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+/// Native function definition for `URLStream.load`
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let request = args.get_object(activation, 0, "request")?;
+    let loader = this
+        .get_slot(url_stream_slots::_LOADER)
+        .as_object()
+        .expect("URLStream should have a URLLoader");
+
+    spawn_fetch(activation, loader, request)?;
+    this.set_slot(url_stream_slots::_CONNECTED, true.into(), activation)?;
+
+    Ok(Value::Undefined)
+}
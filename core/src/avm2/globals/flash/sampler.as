@@ -1,82 +1,35 @@
 package flash.sampler {
-    import __ruffle__.stub_method;
-
-    public function clearSamples(): void {
-        stub_method("flash.sampler", "clearSamples");
-    }
-
-    public function getGetterInvocationCount(obj: Object, name: QName): Number {
-        stub_method("flash.sampler", "getGetterInvocationCount");
-        return -1;
-    }
-
-    public function getInvocationCount(obj: Object, name: QName): Number {
-        stub_method("flash.sampler", "getInvocationCount");
-        return -1;
-    }
-
-    public function getLexicalScopes(fun: Function): Array {
-        stub_method("flash.sampler", "getLexicalScopes");
-        return null;
-    }
-
-    public function getMasterString(str: String): String {
-        stub_method("flash.sampler", "getMasterString");
-        return null;
-    }
-
-    public function getMemberNames(obj: Object, instanceNames: Boolean = false): Object {
-        stub_method("flash.sampler", "getMemberNames");
-        return null;
-    }
-
-    public function getSampleCount(): Number {
-        stub_method("flash.sampler", "getSampleCount");
-        return 0;
-    }
-
-    public function getSamples(): Object {
-        stub_method("flash.sampler", "getSamples");
-        return {};
-    }
-
-    public function getSavedThis(fun: Function): Object {
-        stub_method("flash.sampler", "getSavedThis");
-        return undefined;
-    }
-
-    public function getSetterInvocationCount(obj: Object, name: QName): Number {
-        stub_method("flash.sampler", "getSetterInvocationCount");
-        return -1;
-    }
-
-    public function getSize(param1: *): Number {
-        stub_method("flash.sampler", "getSize");
-        return 0;
-    }
-
-    public function isGetterSetter(obj: Object, name: QName): Boolean {
-        stub_method("flash.sampler", "isGetterSetter");
-        return false;
-    }
-
-    public function pauseSampling(): void {
-        stub_method("flash.sampler", "pauseSampling");
-    }
-
-    public function sampleInternalAllocs(everything: Boolean): void {
-        stub_method("flash.sampler", "sampleInternalAllocs");
-    }
-
-    public function setSamplerCallback(fun: Function): void {
-        stub_method("flash.sampler", "setSamplerCallback");
-    }
-
-    public function startSampling(): void {
-        stub_method("flash.sampler", "startSampling");
-    }
-
-    public function stopSampling():void {
-        stub_method("flash.sampler", "stopSampling");
-    }
+    public native function clearSamples():void;
+
+    public native function getGetterInvocationCount(obj:Object, name:QName):Number;
+
+    public native function getInvocationCount(obj:Object, name:QName):Number;
+
+    public native function getLexicalScopes(fun:Function):Array;
+
+    public native function getMasterString(str:String):String;
+
+    public native function getMemberNames(obj:Object, instanceNames:Boolean = false):Object;
+
+    public native function getSampleCount():Number;
+
+    public native function getSamples():Object;
+
+    public native function getSavedThis(fun:Function):Object;
+
+    public native function getSetterInvocationCount(obj:Object, name:QName):Number;
+
+    public native function getSize(o:*):Number;
+
+    public native function isGetterSetter(obj:Object, name:QName):Boolean;
+
+    public native function pauseSampling():void;
+
+    public native function sampleInternalAllocs(everything:Boolean):void;
+
+    public native function setSamplerCallback(fun:Function):void;
+
+    public native function startSampling():void;
+
+    public native function stopSampling():void;
 }
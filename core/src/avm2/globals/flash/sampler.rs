@@ -0,0 +1,410 @@
+//! `flash.sampler` namespace
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::dynamic_map::DynamicKey;
+use crate::avm2::globals::slots::flash_sampler_delete_object_sample as delete_object_sample_slots;
+use crate::avm2::globals::slots::flash_sampler_new_object_sample as new_object_sample_slots;
+use crate::avm2::globals::slots::flash_sampler_sample as sample_slots;
+use crate::avm2::globals::slots::flash_sampler_stack_frame as stack_frame_slots;
+use crate::avm2::object::{ArrayObject, Object, QNameObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::property::Property;
+use crate::avm2::sampler::{value_size, SampleFrame, SampleKind};
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Multiname};
+use crate::string::AvmString;
+
+pub mod new_object_sample;
+
+/// Implements `flash.sampler.startSampling`
+pub fn start_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.avm2().sampler_mut().start();
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.stopSampling`
+pub fn stop_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.avm2().sampler_mut().stop();
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.pauseSampling`
+pub fn pause_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.avm2().sampler_mut().pause();
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.clearSamples`
+pub fn clear_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.avm2().sampler_mut().clear();
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.sampleInternalAllocs`
+pub fn sample_internal_allocs<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let everything = args.get_bool(0);
+
+    activation
+        .avm2()
+        .sampler_mut()
+        .set_sample_internal_allocs(everything);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.setSamplerCallback`
+pub fn set_sampler_callback<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let callback = args.try_get_object(activation, 0);
+
+    activation.avm2().sampler_mut().set_callback(callback);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.getSampleCount`
+pub fn get_sample_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mc = activation.gc();
+    let sampler = activation.avm2().sampler_mut();
+    sampler.collect_deleted_objects(mc);
+
+    Ok((sampler.sample_count() as f64).into())
+}
+
+/// Implements `flash.sampler.getSamples`
+pub fn get_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mc = activation.gc();
+    let sampler = activation.avm2().sampler_mut();
+    sampler.collect_deleted_objects(mc);
+    let records = sampler.samples().to_vec();
+
+    // Don't sample the objects we're about to create to represent the samples.
+    let was_sampling = sampler.is_sampling();
+    sampler.pause();
+
+    let start_time = activation.context.start_time;
+    let mut samples = Vec::with_capacity(records.len());
+    for record in records {
+        let time = record.time.duration_since(start_time).as_micros() as f64;
+        let stack = stack_to_array(activation, &record.stack)?;
+
+        let sample = match record.kind {
+            SampleKind::Stack => {
+                let sample_class = activation.avm2().classes().sample;
+                sample_class.construct(activation, &[])?
+            }
+            SampleKind::NewObject { id, class, size } => {
+                let sample_class = activation.avm2().classes().newobjectsample;
+                let sample = sample_class.construct(activation, &[])?;
+                let sample_object = sample.as_object().unwrap();
+
+                let class_object = class.and_then(|c| c.class_object());
+                sample_object.set_slot_no_coerce(
+                    new_object_sample_slots::ID,
+                    (id as f64).into(),
+                    mc,
+                );
+                sample_object.set_slot_no_coerce(
+                    new_object_sample_slots::TYPE,
+                    class_object.map_or(Value::Null, Value::from),
+                    mc,
+                );
+                sample_object.set_slot_no_coerce(new_object_sample_slots::_SIZE, size.into(), mc);
+
+                sample
+            }
+            SampleKind::DeleteObject { id, size } => {
+                let sample_class = activation.avm2().classes().deleteobjectsample;
+                let sample = sample_class.construct(activation, &[])?;
+                let sample_object = sample.as_object().unwrap();
+
+                sample_object.set_slot_no_coerce(
+                    delete_object_sample_slots::ID,
+                    (id as f64).into(),
+                    mc,
+                );
+                sample_object.set_slot_no_coerce(delete_object_sample_slots::SIZE, size.into(), mc);
+
+                sample
+            }
+        };
+
+        let sample_object = sample.as_object().unwrap();
+        sample_object.set_slot_no_coerce(sample_slots::TIME, time.into(), mc);
+        sample_object.set_slot_no_coerce(sample_slots::STACK, stack, mc);
+
+        samples.push(sample);
+    }
+
+    if was_sampling {
+        activation.avm2().sampler_mut().start();
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_iter(samples)).into())
+}
+
+fn stack_to_array<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    stack: &[SampleFrame],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if stack.is_empty() {
+        return Ok(Value::Null);
+    }
+
+    let mut frames = Vec::with_capacity(stack.len());
+    for frame in stack {
+        let frame_class = activation.avm2().classes().stackframe;
+        let frame_object = frame_class.construct(activation, &[])?.as_object().unwrap();
+
+        let name = AvmString::new(activation.gc(), frame.name.clone());
+        frame_object.set_slot_no_coerce(stack_frame_slots::NAME, name.into(), activation.gc());
+
+        frames.push(frame_object);
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_iter(frames)).into())
+}
+
+/// Implements `flash.sampler.getSize`
+pub fn get_size<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(value_size(args.get_value(0)).into())
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum InvocationKind {
+    Method,
+    Getter,
+    Setter,
+}
+
+fn invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    kind: InvocationKind,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(object) = args.try_get_object(activation, 0) else {
+        return Ok(Value::Integer(-1));
+    };
+
+    let Some(name) = args
+        .try_get_object(activation, 1)
+        .and_then(|name| name.as_qname_object())
+    else {
+        // Without a name, count the invocations of the constructor.
+        let constructor = object
+            .as_class_object()
+            .and_then(|class| class.inner_class_definition().instance_init());
+
+        return Ok(match constructor {
+            Some(method) if kind == InvocationKind::Method => method.invocation_count().into(),
+            _ => Value::Integer(-1),
+        });
+    };
+    let name = name.name().clone();
+
+    let vtable = object.vtable();
+    let method = match (vtable.get_trait(&name), kind) {
+        (Some(Property::Method { disp_id }), InvocationKind::Method) => vtable.get_method(disp_id),
+        (Some(Property::Virtual { get, .. }), InvocationKind::Getter) => {
+            get.and_then(|disp_id| vtable.get_method(disp_id))
+        }
+        (Some(Property::Virtual { set, .. }), InvocationKind::Setter) => {
+            set.and_then(|disp_id| vtable.get_method(disp_id))
+        }
+        (None, InvocationKind::Method) => {
+            // This may be a function stored in a dynamic property.
+            Value::from(object)
+                .get_property(&name, activation)?
+                .as_object()
+                .and_then(|function| function.as_function_object())
+                .map(|function| function.executable().as_method())
+        }
+        _ => None,
+    };
+
+    Ok(match method {
+        Some(method) => method.invocation_count().into(),
+        None => Value::Integer(-1),
+    })
+}
+
+/// Implements `flash.sampler.getInvocationCount`
+pub fn get_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    invocation_count(activation, args, InvocationKind::Method)
+}
+
+/// Implements `flash.sampler.getGetterInvocationCount`
+pub fn get_getter_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    invocation_count(activation, args, InvocationKind::Getter)
+}
+
+/// Implements `flash.sampler.getSetterInvocationCount`
+pub fn get_setter_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    invocation_count(activation, args, InvocationKind::Setter)
+}
+
+/// Implements `flash.sampler.isGetterSetter`
+pub fn is_getter_setter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = args.get_object(activation, 0, "obj")?;
+    let name = args.get_object(activation, 1, "name")?;
+
+    let Some(name) = name.as_qname_object() else {
+        return Ok(false.into());
+    };
+    let name = name.name().clone();
+
+    Ok(matches!(
+        object.vtable().get_trait(&name),
+        Some(Property::Virtual { .. })
+    )
+    .into())
+}
+
+/// Implements `flash.sampler.getMemberNames`
+pub fn get_member_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(object) = args.try_get_object(activation, 0) else {
+        return Ok(Value::Null);
+    };
+    let instance_names = args.get_bool(1);
+
+    let class_object = object.as_class_object().filter(|_| instance_names);
+    let vtable = match class_object {
+        Some(class_object) => class_object.instance_vtable(),
+        None => object.vtable(),
+    };
+
+    let mut names: Vec<Multiname<'gc>> = vtable
+        .resolved_traits()
+        .iter()
+        .map(|(name, ns, _)| Multiname::new(ns, name))
+        .collect();
+
+    if class_object.is_none() {
+        let public = activation.avm2().namespaces.public_all();
+        let mc = activation.gc();
+        for key in object.base().values().as_hashmap().keys() {
+            let name = match key {
+                DynamicKey::String(name) => *name,
+                DynamicKey::Uint(index) => AvmString::new_utf8(mc, index.to_string()),
+                DynamicKey::Object(_) => continue,
+            };
+            names.push(Multiname::new(public, name));
+        }
+    }
+
+    let names: Vec<Object<'gc>> = names
+        .into_iter()
+        .map(|name| QNameObject::from_name(activation, name).into())
+        .collect();
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_iter(names)).into())
+}
+
+/// Implements `flash.sampler.getLexicalScopes`
+pub fn get_lexical_scopes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(function) = args
+        .try_get_object(activation, 0)
+        .and_then(|function| function.as_function_object())
+    else {
+        return Ok(Value::Null);
+    };
+
+    let scope = function.executable().scope();
+    let scopes: Vec<Value<'gc>> = (0..)
+        .map_while(|index| scope.get(index))
+        .map(|scope| scope.values())
+        .collect();
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_iter(scopes)).into())
+}
+
+/// Implements `flash.sampler.getSavedThis`
+pub fn get_saved_this<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let receiver = args
+        .try_get_object(activation, 0)
+        .and_then(|function| function.as_function_object())
+        .and_then(|function| function.executable().bound_receiver());
+
+    Ok(receiver.unwrap_or(Value::Null))
+}
+
+/// Implements `flash.sampler.getMasterString`
+pub fn get_master_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let master = args
+        .try_get_string(activation, 0)?
+        .and_then(|string| string.owner());
+
+    Ok(master.map_or(Value::Null, Value::from))
+}
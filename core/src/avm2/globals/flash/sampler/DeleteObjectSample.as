@@ -1,6 +1,9 @@
 package flash.sampler {
     public final class DeleteObjectSample extends Sample {
+        [Ruffle(NativeAccessible)]
         public const id:Number;
+
+        [Ruffle(NativeAccessible)]
         public const size:Number;
     }
 }
@@ -0,0 +1,25 @@
+//! `flash.sampler.NewObjectSample` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::slots::flash_sampler_new_object_sample as new_object_sample_slots;
+use crate::avm2::object::TObject;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+/// Implements `NewObjectSample.object`'s getter
+pub fn get_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let id = this
+        .get_slot(new_object_sample_slots::ID)
+        .coerce_to_number(activation)?;
+
+    let mc = activation.gc();
+    let object = activation.avm2().sampler().tracked_object(id as u64, mc);
+
+    Ok(object.map_or(Value::Undefined, Value::from))
+}
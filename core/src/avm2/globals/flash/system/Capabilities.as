@@ -14,10 +14,7 @@ package flash.system {
             return "x86";
         }
 
-        public static function get hasAccessibility():Boolean {
-            stub_getter("flash.system.Capabilities", "hasAccessibility");
-            return false;
-        }
+        public static native function get hasAccessibility():Boolean;
 
         public static function get hasAudio():Boolean {
             stub_getter("flash.system.Capabilities", "hasAudio");
@@ -6,72 +6,65 @@
 package flash.system
 {
     import flash.events.EventDispatcher;
-    import __ruffle__.stub_method;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_setter;
 
     public final class IME extends EventDispatcher
     {
         // The conversion mode of the current IME.
-        private static var _conversionMode: String = "ALPHANUMERIC_HALF";
+        // Ruffle can't change the mode of the platform IME, so this only remembers what the movie asked for.
+        private static var _conversionMode: String = IMEConversionMode.ALPHANUMERIC_HALF;
 
-        // Indicates whether the system IME is enabled (true) or disabled (false).
-        private static var _enabled: Boolean;
+        public function IME()
+        {
+            super();
+            this.registerIme();
+        }
 
-        // The isSupported property is set to true if the IME class is available on the current platform, otherwise it is set to false.
-        private static var _isSupported: Boolean;
+        private native function registerIme():void;
 
         // Causes the runtime to abandon any composition that is in progress.
-        public static function compositionAbandoned():void
-        {
-            stub_method("flash.system.IME", "compositionAbandoned");
-        }
+        public static native function compositionAbandoned():void;
 
         // Call this method when the selection within the composition has been updated, either interactively or programmatically.
-        public static function compositionSelectionChanged(start:int, end:int):void
-        {
-            stub_method("flash.system.IME", "compositionSelectionChanged");
-        }
+        public static native function compositionSelectionChanged(start:int, end:int):void;
 
         // Instructs the IME to select the first candidate for the current composition string.
-        public static function doConversion():void
-        {
-            stub_method("flash.system.IME", "doConversion");
-        }
+        // Ruffle has no access to the candidates of the platform IME, so the composition is committed as is.
+        public static native function doConversion():void;
 
         // Sets the IME composition string.
-        public static function setCompositionString(composition:String):void
-        {
-            stub_method("flash.system.IME", "setCompositionString");
-        }
+        public static native function setCompositionString(composition:String):void;
 
-        public function get isSupported() : Boolean
+        // Text fields always accept input from the platform IME.
+        public static function get isSupported() : Boolean
         {
-            return _isSupported;
+            return true;
         }
 
-        public static function get enabled():Boolean
-        {
-            stub_getter("flash.system.IME", "enabled");
-            return _enabled;
-        }
+        public static native function get enabled():Boolean;
 
-        public static function set enabled(value:Boolean):void
-        {
-            stub_setter("flash.system.IME", "enabled");
-            _enabled = value;
-        }
+        public static native function set enabled(value:Boolean):void;
 
         public static function get conversionMode():String
         {
-            stub_getter("flash.system.IME", "conversionMode");
             return _conversionMode;
         }
 
         public static function set conversionMode(value:String):void
         {
-            stub_setter("flash.system.IME", "conversionMode");
-            _conversionMode = value;
+            switch (value)
+            {
+                case IMEConversionMode.ALPHANUMERIC_FULL:
+                case IMEConversionMode.ALPHANUMERIC_HALF:
+                case IMEConversionMode.CHINESE:
+                case IMEConversionMode.JAPANESE_HIRAGANA:
+                case IMEConversionMode.JAPANESE_KATAKANA_FULL:
+                case IMEConversionMode.JAPANESE_KATAKANA_HALF:
+                case IMEConversionMode.KOREAN:
+                    _conversionMode = value;
+                    break;
+                default:
+                    throw new ArgumentError("Error #2008: Parameter conversionMode must be one of the accepted values.", 2008);
+            }
         }
     }
 }
@@ -13,6 +13,15 @@ package flash.system {
 
         public static native function setClipboard(string:String): void;
 
+        private static var _ime:IME;
+
+        public static function get ime(): IME {
+            if (!_ime) {
+                _ime = new IME();
+            }
+            return _ime;
+        }
+
         public static function disposeXML(node:XML):void {
             stub_method("flash.system.System", "disposeXML");
         }
@@ -2,6 +2,20 @@
 
 use crate::avm2::{Activation, AvmString, Error, Value};
 use crate::player::PlayerRuntime;
+use crate::system_properties::SystemCapabilities;
+
+/// Implements `flash.system.Capabilities.hasAccessibility`
+pub fn get_has_accessibility<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation
+        .context
+        .system
+        .has_capability(SystemCapabilities::ACCESSIBILITY)
+        .into())
+}
 
 /// Implements `flash.system.Capabilities.os`
 pub fn get_os<'gc>(
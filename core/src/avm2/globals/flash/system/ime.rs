@@ -0,0 +1,88 @@
+//! `flash.system.IME` native methods
+//!
+//! `imeComposition` events are dispatched from `crate::ime`.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::events::ImeEvent;
+use crate::ime::Ime;
+
+pub fn register_ime<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().expect("IME should be an object");
+    activation.context.ime.set_avm2_object(this);
+    Ok(Value::Undefined)
+}
+
+pub fn get_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ime.is_enabled().into())
+}
+
+pub fn set_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let enabled = args.get_bool(0);
+    Ime::set_enabled(activation.context, enabled);
+    Ok(Value::Undefined)
+}
+
+pub fn set_composition_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let composition = args.get_string(activation, 0)?.to_string();
+    if let Some(text) = activation.context.focus_tracker.get_as_edit_text() {
+        // The caret is placed at the end of the composition.
+        let cursor = Some((composition.len(), composition.len()));
+        text.ime(ImeEvent::Preedit(composition, cursor), activation.context);
+        text.update_ime_cursor_area(activation.context);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn composition_selection_changed<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let start = args.get_i32(activation, 0)?.max(0) as usize;
+    let end = args.get_i32(activation, 1)?.max(0) as usize;
+    if let Some(text) = activation.context.focus_tracker.get_as_edit_text() {
+        text.select_composition(start, end, activation.context);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn composition_abandoned<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(text) = activation.context.focus_tracker.get_as_edit_text() {
+        text.ensure_ime_finished(activation.context);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn do_conversion<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(text) = activation.context.focus_tracker.get_as_edit_text() {
+        text.ensure_ime_committed(activation.context);
+    }
+    Ok(Value::Undefined)
+}
@@ -1,10 +1,11 @@
 //! `flash.system.Security` native methods
 
 use crate::avm2::activation::Activation;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2_stub_method;
-use crate::sandbox::SandboxType;
+use crate::sandbox::{self, SandboxType};
 use crate::string::AvmString;
 use url::Url;
 
@@ -54,27 +55,80 @@ pub fn get_sandbox_type<'gc>(
 pub fn allow_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Value<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "allowDomain");
+    let movie = activation.caller_movie_or_root();
+    for domain in args {
+        let domain = domain
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .into_owned();
+        // A URL may be given instead of a domain, in which case its host is used.
+        let domain = match Url::parse(&domain) {
+            Ok(url) if url.has_host() => url.host_str().unwrap_or_default().to_string(),
+            _ => domain,
+        };
+        activation
+            .context
+            .security_sandbox
+            .allow_domain(&movie, domain);
+    }
     Ok(Value::Undefined)
 }
 
 pub fn allow_insecure_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
-    _args: &[Value<'gc>],
+    this: Value<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "allowInsecureDomain");
-    Ok(Value::Undefined)
+    // Ruffle doesn't distinguish between HTTP and HTTPS content here.
+    allow_domain(activation, this, args)
 }
 
 pub fn load_policy_file<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Value<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "loadPolicyFile");
+    let url = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    if !activation.context.security_sandbox.is_strict() {
+        // Policy files are only ever consulted when the sandbox is enforced.
+        return Ok(Value::Undefined);
+    }
+    let Ok(url) = activation.context.navigator.resolve_url(&url) else {
+        tracing::warn!("Security.loadPolicyFile: Invalid URL {url}");
+        return Ok(Value::Undefined);
+    };
+    let Some(player) = activation.context.player.upgrade() else {
+        return Ok(Value::Undefined);
+    };
+
+    if url.scheme() == "xmlsocket" {
+        let (Some(host), Some(port)) = (url.host_str(), url.port()) else {
+            tracing::warn!("Security.loadPolicyFile: Invalid socket policy URL {url}");
+            return Ok(Value::Undefined);
+        };
+        let host = host.to_string();
+        activation
+            .context
+            .security_sandbox
+            .set_socket_policy_port(host.clone(), port);
+        activation
+            .context
+            .navigator
+            .spawn_future(Box::pin(async move {
+                sandbox::load_socket_policy(player, host, port).await;
+                Ok(())
+            }));
+    } else {
+        activation
+            .context
+            .navigator
+            .spawn_future(Box::pin(async move {
+                sandbox::load_url_policy(player, url).await;
+                Ok(())
+            }));
+    }
     Ok(Value::Undefined)
 }
 
@@ -3,13 +3,10 @@ package flash.text {
     import flash.display.DisplayObject;
     import flash.geom.Rectangle;
     import __ruffle__.stub_getter;
-    import __ruffle__.stub_setter;
     import __ruffle__.stub_method;
 
     [Ruffle(InstanceAllocator)]
     public class TextField extends InteractiveObject {
-        internal var _useRichTextClipboard:Boolean;
-
         public native function get alwaysShowSelection():Boolean;
         public native function set alwaysShowSelection(value:Boolean):void;
 
@@ -88,14 +85,8 @@ package flash.text {
         public native function get type():String;
         public native function set type(value:String):void;
 
-        public function get useRichTextClipboard():Boolean {
-            return this._useRichTextClipboard;
-        }
-
-        public function set useRichTextClipboard(value:Boolean):void {
-            this._useRichTextClipboard = value;
-            stub_setter("flash.text.TextField", "useRichTextClipboard");
-        }
+        public native function get useRichTextClipboard():Boolean;
+        public native function set useRichTextClipboard(value:Boolean):void;
 
         public native function get wordWrap():Boolean;
         public native function set wordWrap(value:Boolean):void;
@@ -130,9 +121,7 @@ package flash.text {
 
         public native function get selectedText():String;
 
-        public function insertXMLText(beginIndex:int, endIndex:int, text:String, paste:Boolean = false):void {
-            stub_method("flash.text.TextField", "insertXMLText");
-        }
+        public native function insertXMLText(beginIndex:int, endIndex:int, text:String, paste:Boolean = false):void;
 
         public native function getCharIndexAtPoint(x:Number, y:Number):int;
 
@@ -146,10 +135,7 @@ package flash.text {
 
         public native function getFirstCharInParagraph(charIndex:int):int;
 
-        public function getImageReference(id:String):DisplayObject {
-            stub_method("flash.text.TextField", "getImageReference");
-            return null;
-        }
+        public native function getImageReference(id:String):DisplayObject;
 
         public native function getLineIndexAtPoint(x:Number, y:Number):int;
 
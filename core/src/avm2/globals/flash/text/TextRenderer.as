@@ -5,46 +5,27 @@
 
 package flash.text
 {
-    import __ruffle__.stub_method;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_setter;
-
     public final class TextRenderer
     {
-        // Controls the rendering of advanced anti-aliased text.
-        private static var _displayMode: String = "default";
-
-        // The adaptively sampled distance fields (ADFs) quality level for advanced anti-aliasing.
-        private static var _maxLevel: int = 4;
-
         // Sets a custom continuous stroke modulation (CSM) lookup table for a font.
         public static function setAdvancedAntiAliasingTable(fontName:String, fontStyle:String, colorType:String, advancedAntiAliasingTable:Array):void
         {
-            stub_method("flash.text.TextRenderer", "setAdvancedAntiAliasingTable");
-        }
-
-        public static function get displayMode():String
-        {
-            stub_getter("flash.text.TextRenderer", "displayMode");
-            return _displaymode;
+            var table:Array = [];
+            for each (var settings:CSMSettings in advancedAntiAliasingTable) {
+                table.push(settings.fontSize, settings.insideCutoff, settings.outsideCutoff);
+            }
+            setCSMTable(fontName, fontStyle, colorType, table);
         }
 
-        public static function set displayMode(value:String):void
-        {
-            stub_setter("flash.text.TextRenderer", "displayMode");
-            _displayMode = value;
-        }
+        // Takes the table as a flat list of fontSize, insideCutoff and outsideCutoff values.
+        private static native function setCSMTable(fontName:String, fontStyle:String, colorType:String, table:Array):void;
 
-        public static function get maxLevel():int
-        {
-            stub_getter("flash.text.TextRenderer", "maxLevel");
-            return _maxLevel;
-        }
+        // Controls the rendering of advanced anti-aliased text.
+        public static native function get displayMode():String;
+        public static native function set displayMode(value:String):void;
 
-        public static function set maxLevel(value:int):void
-        {
-            stub_setter("flash.text.TextRenderer", "maxLevel");
-            _maxLevel = value;
-        }
+        // The adaptively sampled distance fields (ADFs) quality level for advanced anti-aliasing.
+        public static native function get maxLevel():int;
+        public static native function set maxLevel(value:int):void;
     }
 }
@@ -6,7 +6,6 @@ use crate::avm2::object::{FontObject, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::{ArrayObject, ArrayStorage, Error};
-use crate::avm2_stub_method;
 use crate::string::AvmString;
 
 pub use crate::avm2::object::font_allocator;
@@ -98,12 +97,11 @@ pub fn enumerate_fonts<'gc>(
     let mut fonts: Vec<Font<'gc>> = Vec::new();
 
     if args.get_bool(0) {
-        // We could include the ones we know about, but what to do for the ones that weren't eagerly loaded?
-        avm2_stub_method!(
-            activation,
-            "flash.text.Font",
-            "enumerateFonts",
-            "with device fonts"
+        fonts.append(
+            &mut activation
+                .context
+                .library
+                .available_device_fonts(activation.context.ui, activation.gc()),
         );
     }
 
@@ -10,7 +10,6 @@ use crate::avm2::{ArrayObject, ArrayStorage, Error};
 use crate::display_object::{AutoSizeMode, EditText, TDisplayObject, TextSelection};
 use crate::html::TextFormat;
 use crate::string::AvmString;
-use crate::{avm2_stub_getter, avm2_stub_setter};
 use ruffle_macros::istr;
 use swf::{Color, Point};
 
@@ -62,6 +61,43 @@ pub fn set_always_show_selection<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_use_rich_text_clipboard<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    else {
+        return Ok(Value::Undefined);
+    };
+
+    Ok(this.use_rich_text_clipboard().into())
+}
+
+pub fn set_use_rich_text_clipboard<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    else {
+        return Ok(Value::Undefined);
+    };
+
+    let value = args.get_bool(0);
+    this.set_use_rich_text_clipboard(value);
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_auto_size<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
@@ -864,6 +900,31 @@ pub fn replace_text<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn insert_xml_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let begin_index = args.get_u32(activation, 0)? as usize;
+        let end_index = args.get_u32(activation, 1)? as usize;
+        let text = args.get_string_non_null(activation, 2, "text")?;
+        let paste = args.get_bool(3);
+
+        let text_length = this.text_length();
+        let begin_index = begin_index.min(text_length);
+        let end_index = end_index.clamp(begin_index, text_length);
+        this.insert_xml_text(begin_index, end_index, &text, paste, activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_caret_index<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
@@ -1499,20 +1560,36 @@ pub fn set_max_chars<'gc>(
 }
 
 pub fn get_mouse_wheel_enabled<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.text.TextField", "mouseWheelEnabled");
-    Ok(true.into())
+    let this = this.as_object().unwrap();
+
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.is_mouse_wheel_enabled().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
 pub fn set_mouse_wheel_enabled<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Value<'gc>,
-    _args: &[Value<'gc>],
+    _activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.text.TextField", "mouseWheelEnabled");
+    let this = this.as_object().unwrap();
+
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        this.set_mouse_wheel_enabled(args.get_bool(0));
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -1712,6 +1789,30 @@ pub fn get_first_char_in_paragraph<'gc>(
         .into())
 }
 
+pub fn get_image_reference<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_object().unwrap();
+
+    let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    else {
+        return Ok(Value::Null);
+    };
+
+    let Some(id) = args.try_get_string(activation, 0)? else {
+        return Ok(Value::Null);
+    };
+
+    Ok(this
+        .image_reference(&id)
+        .map(|image| image.object2())
+        .unwrap_or(Value::Null))
+}
+
 pub fn get_paragraph_length<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Value<'gc>,
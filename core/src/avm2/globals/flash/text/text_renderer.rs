@@ -0,0 +1,118 @@
+//! `flash.text.TextRenderer` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::make_error_2008;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::font::{CsmSettings, TextColorType, TextDisplayMode};
+use crate::string::AvmString;
+
+/// Implements `TextRenderer.setAdvancedAntiAliasingTable`.
+pub fn set_csm_table<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let font_name = args.get_string_non_null(activation, 0, "fontName")?;
+    let font_style = args.get_string_non_null(activation, 1, "fontStyle")?;
+    let (is_bold, is_italic) = if &font_style == b"regular" {
+        (false, false)
+    } else if &font_style == b"bold" {
+        (true, false)
+    } else if &font_style == b"italic" {
+        (false, true)
+    } else if &font_style == b"boldItalic" {
+        (true, true)
+    } else {
+        return Err(make_error_2008(activation, "fontStyle"));
+    };
+    let color_type = args.get_string_non_null(activation, 2, "colorType")?;
+    let Some(color_type) = TextColorType::from_wstr(&color_type) else {
+        return Err(make_error_2008(activation, "colorType"));
+    };
+
+    let values: Vec<Value<'gc>> = args
+        .get_object(activation, 3, "table")?
+        .as_array_storage()
+        .map(|storage| {
+            storage
+                .iter()
+                .map(|v| v.unwrap_or(Value::Undefined))
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut table = Vec::with_capacity(values.len() / 3);
+    for settings in values.chunks_exact(3) {
+        table.push(CsmSettings {
+            font_size: settings[0].coerce_to_number(activation)? as f32,
+            inside_cutoff: settings[1].coerce_to_number(activation)? as f32,
+            outside_cutoff: settings[2].coerce_to_number(activation)? as f32,
+        });
+    }
+
+    activation.context.library.text_renderer_mut().set_table(
+        &font_name.to_utf8_lossy(),
+        is_bold,
+        is_italic,
+        color_type,
+        table,
+    );
+    Ok(Value::Undefined)
+}
+
+pub fn get_display_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let display_mode = activation.context.library.text_renderer().display_mode();
+    Ok(AvmString::new_utf8(activation.gc(), display_mode.as_str()).into())
+}
+
+pub fn set_display_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let display_mode = args.get_string_non_null(activation, 0, "displayMode")?;
+    let Some(display_mode) = TextDisplayMode::from_wstr(&display_mode) else {
+        return Err(make_error_2008(activation, "displayMode"));
+    };
+    activation
+        .context
+        .library
+        .text_renderer_mut()
+        .set_display_mode(display_mode);
+    Ok(Value::Undefined)
+}
+
+pub fn get_max_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation
+        .context
+        .library
+        .text_renderer()
+        .max_level()
+        .into())
+}
+
+pub fn set_max_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let max_level = args.get_i32(activation, 0)?;
+    if !(4..=7).contains(&max_level) {
+        return Err(make_error_2008(activation, "maxLevel"));
+    }
+    activation
+        .context
+        .library
+        .text_renderer_mut()
+        .set_max_level(max_level);
+    Ok(Value::Undefined)
+}
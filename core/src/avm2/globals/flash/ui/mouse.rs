@@ -1,8 +1,18 @@
 //! `flash.ui.Mouse` builtin
 
 use crate::avm2::activation::Activation;
+use crate::avm2::error::{make_error_2007, make_error_2008};
+use crate::avm2::globals::slots::flash_geom_point as point_slots;
+use crate::avm2::globals::slots::flash_ui_mouse_cursor_data as cursor_data_slots;
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::backend::ui::{CustomCursor, MouseCursor};
+use crate::string::AvmString;
+
+/// The fastest a custom cursor can animate, which is also the highest stage frame rate.
+const MAX_CURSOR_FRAME_RATE: f64 = 1000.0;
 
 pub fn hide<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -21,3 +31,158 @@ pub fn show<'gc>(
     activation.context.ui.set_mouse_visible(true);
     Ok(Value::Undefined)
 }
+
+pub fn get_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mouse_data = &activation.context.mouse_data;
+    let name = match mouse_data.cursor_override {
+        None => "auto",
+        Some(MouseCursor::Arrow) => "arrow",
+        Some(MouseCursor::Hand) => "button",
+        Some(MouseCursor::IBeam) => "ibeam",
+        Some(MouseCursor::Grab) => "hand",
+        Some(MouseCursor::Custom(id)) => mouse_data
+            .registered_cursors
+            .iter()
+            .find(|(_, registered)| **registered == id)
+            .map(|(name, _)| name.as_str())
+            .unwrap_or("auto"),
+    };
+    Ok(AvmString::new_utf8(activation.gc(), name).into())
+}
+
+pub fn set_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?;
+    let cursor = match &*name.to_utf8_lossy() {
+        "auto" => None,
+        "arrow" => Some(MouseCursor::Arrow),
+        "button" => Some(MouseCursor::Hand),
+        "ibeam" => Some(MouseCursor::IBeam),
+        "hand" => Some(MouseCursor::Grab),
+        name => match activation.context.mouse_data.registered_cursors.get(name) {
+            Some(id) => Some(MouseCursor::Custom(*id)),
+            None => return Err(make_error_2008(activation, "cursor")),
+        },
+    };
+    let mouse_data = &mut *activation.context.mouse_data;
+    mouse_data.cursor_override = cursor;
+    mouse_data.update_cursor(activation.context.ui);
+    Ok(Value::Undefined)
+}
+
+pub fn get_supports_native_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.supports_custom_cursors().into())
+}
+
+pub fn register_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?;
+    let cursor_data = args.get_object(activation, 1, "cursor")?;
+
+    let Some(data) = cursor_data.get_slot(cursor_data_slots::_DATA).as_object() else {
+        return Err(make_error_2007(activation, "data"));
+    };
+    let bitmaps = data
+        .as_vector_storage()
+        .map(|storage| {
+            storage
+                .iter()
+                .map(|frame| frame.as_object().and_then(|frame| frame.as_bitmap_data()))
+                .collect::<Option<Vec<_>>>()
+        })
+        .unwrap_or_default();
+    let Some(bitmaps) = bitmaps.filter(|bitmaps| !bitmaps.is_empty()) else {
+        return Err(make_error_2008(activation, "data"));
+    };
+
+    let (width, height) = (bitmaps[0].width(), bitmaps[0].height());
+    let mut frames = Vec::with_capacity(bitmaps.len());
+    for bitmap in bitmaps {
+        bitmap.check_valid(activation)?;
+        if bitmap.width() != width || bitmap.height() != height {
+            return Err(make_error_2008(activation, "data"));
+        }
+
+        let bitmap = bitmap.sync(activation.context.renderer);
+        let bitmap = bitmap.read();
+        let mut frame = Vec::with_capacity(bitmap.pixels().len() * 4);
+        for pixel in bitmap.pixels() {
+            let pixel = pixel.to_un_multiplied_alpha();
+            frame.extend_from_slice(&[pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]);
+        }
+        frames.push(frame);
+    }
+
+    let frame_rate = cursor_data
+        .get_slot(cursor_data_slots::_FRAME_RATE)
+        .coerce_to_number(activation)?;
+    let hotspot = match cursor_data
+        .get_slot(cursor_data_slots::_HOT_SPOT)
+        .as_object()
+    {
+        Some(point) => {
+            let x = point
+                .get_slot(point_slots::X)
+                .coerce_to_number(activation)?;
+            let y = point
+                .get_slot(point_slots::Y)
+                .coerce_to_number(activation)?;
+            (
+                (x.max(0.0) as u32).min(width.saturating_sub(1)),
+                (y.max(0.0) as u32).min(height.saturating_sub(1)),
+            )
+        }
+        None => (0, 0),
+    };
+
+    let id = activation
+        .context
+        .mouse_data
+        .custom_cursor_id(&name.to_utf8_lossy());
+    activation.context.ui.register_mouse_cursor(
+        id,
+        CustomCursor {
+            width,
+            height,
+            frames,
+            hotspot,
+            frame_rate: if frame_rate.is_finite() {
+                frame_rate.clamp(0.0, MAX_CURSOR_FRAME_RATE)
+            } else {
+                0.0
+            },
+        },
+    );
+    Ok(Value::Undefined)
+}
+
+pub fn unregister_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?;
+    let mouse_data = &mut *activation.context.mouse_data;
+    if let Some(id) = mouse_data.registered_cursors.remove(&*name.to_utf8_lossy()) {
+        if mouse_data.cursor_override == Some(MouseCursor::Custom(id)) {
+            mouse_data.cursor_override = None;
+            mouse_data.update_cursor(activation.context.ui);
+        }
+        activation.context.ui.unregister_mouse_cursor(id);
+    }
+    Ok(Value::Undefined)
+}
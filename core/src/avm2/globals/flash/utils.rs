@@ -44,6 +44,8 @@ pub fn set_interval<'gc>(
             .as_object()
             .ok_or("setInterval: argument 0 is not an object")?,
         params: params.to_vec(),
+        domain: activation.caller_domain(),
+        timer: None,
     };
     let interval = args
         .get(1)
@@ -87,6 +89,8 @@ pub fn set_timeout<'gc>(
             .as_object()
             .ok_or("setTimeout: argument 0 is not an object")?,
         params: params.to_vec(),
+        domain: activation.caller_domain(),
+        timer: None,
     };
     let interval = args
         .get(1)
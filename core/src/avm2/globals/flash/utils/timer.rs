@@ -50,6 +50,8 @@ pub fn start<'gc>(
             TimerCallback::Avm2Callback {
                 closure: on_update,
                 params: vec![],
+                domain: activation.caller_domain(),
+                timer: Some(this),
             },
             delay,
             false,
@@ -87,6 +87,7 @@ include "flash/display/MorphShape.as"
 include "flash/display/NativeMenu.as"
 include "flash/display/NativeMenuItem.as"
 include "flash/display/NativeWindowDisplayState.as"
+include "flash/display/NativeWindowResize.as"
 include "flash/display/NativeWindowSystemChrome.as"
 include "flash/display/NativeWindowType.as"
 include "flash/display/NativeWindowInitOptions.as"
@@ -151,6 +152,7 @@ include "flash/errors/StackOverflowError.as"
 // Event needs to come before its subclasses
 include "flash/events/Event.as"
 include "flash/events/TextEvent.as"
+include "flash/events/IMEEvent.as"
 include "flash/events/ActivityEvent.as"
 include "flash/events/ErrorEvent.as"
 include "flash/events/GestureEvent.as"
@@ -164,12 +166,14 @@ include "flash/events/AVPauseAtPeriodEndEvent.as"
 include "flash/events/AVStatusEvent.as"
 include "flash/events/ContextMenuEvent.as"
 include "flash/events/DataEvent.as"
+include "flash/events/DatagramSocketDataEvent.as"
 include "flash/events/DRMAuthenticationCompleteEvent.as"
 include "flash/events/DRMAuthenticationErrorEvent.as"
 include "flash/events/DRMLicenseRequestEvent.as"
 include "flash/events/DRMReturnVoucherCompleteEvent.as"
 include "flash/events/DRMReturnVoucherErrorEvent.as"
 include "flash/events/EventPhase.as"
+include "flash/events/FileListEvent.as"
 include "flash/events/FocusEvent.as"
 include "flash/events/FullScreenEvent.as"
 include "flash/events/GameInputEvent.as"
@@ -178,11 +182,13 @@ include "flash/events/HTTPStatusEvent.as"
 include "flash/events/IOErrorEvent.as"
 include "flash/events/InvokeEvent.as"
 include "flash/events/KeyboardEvent.as"
+include "flash/events/NativeProcessExitEvent.as"
 include "flash/events/NativeWindowBoundsEvent.as"
 include "flash/events/NativeWindowDisplayStateEvent.as"
 include "flash/events/NetDataEvent.as"
 include "flash/events/NetFilterEvent.as"
 include "flash/events/NetStatusEvent.as"
+include "flash/events/OutputProgressEvent.as"
 include "flash/events/PressAndTapGestureEvent.as"
 include "flash/events/ProgressEvent.as"
 include "flash/events/SampleDataEvent.as"
@@ -309,6 +315,8 @@ include "flash/net/URLVariables.as"
 include "flash/net/XMLSocket.as"
 
 include "flash/filesystem/File.as" // File extends FileReference
+include "flash/filesystem/FileMode.as"
+include "flash/filesystem/FileStream.as"
 
 include "flash/net/drm/AuthenticationMethod.as"
 include "flash/net/drm/LoadVoucherSetting.as"
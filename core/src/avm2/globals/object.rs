@@ -21,6 +21,7 @@ pub fn object_constructor<'gc>(
     }
 
     let constructed_object = ScriptObject::new_object(activation);
+    activation.avm2().record_allocation(constructed_object);
     Ok(constructed_object.into())
 }
 
@@ -13,7 +13,7 @@ use gc_arena::barrier::{unlock, Write};
 use gc_arena::lock::RefLock;
 use gc_arena::{Collect, Gc};
 use std::borrow::Cow;
-use std::cell::Ref;
+use std::cell::{Cell, Ref};
 use std::rc::Rc;
 use std::sync::Arc;
 use swf::avm2::types::{
@@ -134,6 +134,10 @@ struct MethodData<'gc> {
     /// This is true when the method is a free-standing function and none of the
     /// declared arguments have a type or a default value.
     is_unchecked: bool,
+
+    /// How many times this method was invoked while `flash.sampler` was
+    /// sampling.
+    invocation_count: Cell<u32>,
 }
 
 impl PartialEq for Method<'_> {
@@ -203,6 +207,7 @@ impl<'gc> Method<'gc> {
                 resolved_info: RefLock::new(None),
                 is_function,
                 is_unchecked: is_function && all_params_unchecked,
+                invocation_count: Cell::new(0),
             },
         )))
     }
@@ -279,6 +284,16 @@ impl<'gc> Method<'gc> {
         resolved_info.as_ref().unwrap().return_type
     }
 
+    /// The number of times this method was invoked while sampling.
+    pub fn invocation_count(self) -> u32 {
+        self.0.invocation_count.get()
+    }
+
+    pub fn increment_invocation_count(self) {
+        let count = &self.0.invocation_count;
+        count.set(count.get().saturating_add(1));
+    }
+
     pub fn is_info_resolved(self) -> bool {
         let resolved_info = self.0.resolved_info.borrow();
 
@@ -75,13 +75,15 @@ impl<'gc> ArrayObject<'gc> {
         let class = activation.avm2().classes().array;
         let base = ScriptObjectData::new(class);
 
-        ArrayObject(Gc::new(
+        let object = ArrayObject(Gc::new(
             activation.gc(),
             ArrayObjectData {
                 base,
                 array: RefLock::new(array),
             },
-        ))
+        ));
+        activation.avm2().record_allocation(object.into());
+        object
     }
 
     pub fn array_storage(&self) -> Ref<ArrayStorage<'gc>> {
@@ -656,6 +656,8 @@ impl<'gc> ClassObject<'gc> {
 
             let instance = instance_allocator(self, activation)?;
 
+            activation.avm2().record_allocation(instance);
+
             self.call_init(instance.into(), arguments, activation)?;
 
             Ok(instance.into())
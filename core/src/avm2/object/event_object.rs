@@ -227,6 +227,29 @@ impl<'gc> EventObject<'gc> {
         )
     }
 
+    pub fn ime_event(
+        activation: &mut Activation<'_, 'gc>,
+        event_type: &str,
+        text: AvmString<'gc>,
+    ) -> EventObject<'gc> {
+        let event_type = AvmString::new_utf8(activation.gc(), event_type);
+
+        let ime_event_cls = activation.avm2().classes().imeevent;
+        Self::from_class_and_args(
+            activation,
+            ime_event_cls,
+            &[
+                event_type.into(),
+                // bubbles
+                false.into(),
+                // cancelable
+                false.into(),
+                // text
+                text.into(),
+            ],
+        )
+    }
+
     pub fn net_status_event(
         activation: &mut Activation<'_, 'gc>,
         info: Vec<(&str, &str)>,
@@ -334,6 +357,27 @@ impl<'gc> EventObject<'gc> {
         )
     }
 
+    pub fn security_error_event(
+        activation: &mut Activation<'_, 'gc>,
+        error_msg: &str,
+        error_code: u32,
+    ) -> EventObject<'gc> {
+        let event_name = istr!("securityError");
+        let error_msg = AvmString::new_utf8(activation.gc(), error_msg);
+        let security_error_event_cls = activation.avm2().classes().securityerrorevent;
+        Self::from_class_and_args(
+            activation,
+            security_error_event_cls,
+            &[
+                event_name.into(),
+                false.into(),
+                false.into(),
+                error_msg.into(),
+                error_code.into(),
+            ],
+        )
+    }
+
     pub fn http_status_event(
         activation: &mut Activation<'_, 'gc>,
         status: u16,
@@ -75,14 +75,16 @@ impl<'gc> FunctionObject<'gc> {
 
         let es3_proto = ScriptObject::new_object(activation);
 
-        FunctionObject(Gc::new(
+        let function = FunctionObject(Gc::new(
             activation.gc(),
             FunctionObjectData {
                 base: ScriptObjectData::new(fn_class),
                 exec,
                 prototype: Lock::new(Some(es3_proto)),
             },
-        ))
+        ));
+        activation.avm2().record_allocation(function.into());
+        function
     }
 
     pub fn call(
@@ -1,12 +1,15 @@
 //! Loader-info object
 
 use crate::avm2::activation::Activation;
+use crate::avm2::globals::slots::flash_utils_timer as timer_slots;
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{EventObject, Object, ObjectPtr, StageObject, TObject};
-use crate::avm2::{Avm2, Error, Value};
+use crate::avm2::{Avm2, Domain, Error, Value};
+use crate::avm2_stub_method;
 use crate::context::UpdateContext;
-use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::loader::ContentType;
+use crate::streams::StreamManager;
 use crate::tag_utils::SwfMovie;
 use crate::utils::HasPrefixField;
 use core::fmt;
@@ -348,7 +351,62 @@ impl<'gc> LoaderInfoObject<'gc> {
         self.0.cached_avm1movie.get().unwrap()
     }
 
+    /// Unload the content of this loader, like `Loader.unload`.
+    ///
+    /// The content is removed from the `Loader` and its timelines are
+    /// stopped. If the content had finished loading, an `unload` event is
+    /// dispatched on this `LoaderInfo`.
     pub fn unload(&self, activation: &mut Activation<'_, 'gc>) {
+        self.unload_content(activation, false);
+    }
+
+    /// Unload the content of this loader, like `Loader.unloadAndStop`.
+    ///
+    /// On top of what `unload` does, this stops the sounds and streams of the
+    /// content, and removes the timers and broadcast or `Stage` listeners
+    /// that its code registered.
+    pub fn unload_and_stop(&self, activation: &mut Activation<'_, 'gc>) {
+        self.unload_content(activation, true);
+    }
+
+    fn unload_content(&self, activation: &mut Activation<'_, 'gc>, stop: bool) {
+        let (content, was_loaded) = match &*self.loader_stream() {
+            LoaderStream::Swf(movie, root) => (Some((movie.clone(), *root)), true),
+            LoaderStream::NotYetLoaded(movie, root, _) => {
+                (root.map(|root| (movie.clone(), root)), false)
+            }
+        };
+
+        if let (true, Some((movie, root))) = (stop, &content) {
+            // This has to happen while the content is still parented to us.
+            activation.context.stop_sounds_on_parent_and_children(*root);
+
+            if let Some(domain) = self.content_domain(activation, movie.clone()) {
+                Avm2::remove_listeners_in_domain(activation.context, domain);
+                let timers = activation
+                    .context
+                    .timers
+                    .remove_avm2_timers_in_domain(domain);
+                for timer in timers {
+                    timer.set_slot_no_coerce(timer_slots::_TIMER_ID, (-1).into(), activation.gc());
+                }
+                activation
+                    .context
+                    .audio_manager
+                    .stop_sounds_in_avm2_domain(activation.context.audio, domain);
+                StreamManager::close_streams_in_domain(activation.context, domain);
+            } else if movie.is_action_script_3() {
+                // The content shares its domain with the movie owning the
+                // loader, so its listeners, timers and streams are kept.
+                avm2_stub_method!(
+                    activation,
+                    "flash.display.Loader",
+                    "unloadAndStop",
+                    "with content loaded into the loader's ApplicationDomain"
+                );
+            }
+        }
+
         // Reset properties
         let empty_swf = Arc::new(SwfMovie::empty(activation.context.swf.version()));
         let loader_stream = LoaderStream::NotYetLoaded(empty_swf, None, false);
@@ -368,6 +426,50 @@ impl<'gc> LoaderInfoObject<'gc> {
             Value::Object(loader),
             &[0.into()],
         );
+
+        if let Some((_, root)) = content {
+            stop_timelines(activation.context, root);
+        }
+
+        if was_loaded {
+            let unload_evt = EventObject::bare_default_event(activation.context, "unload");
+            Avm2::dispatch_event(activation.context, unload_evt, (*self).into());
+        }
+    }
+
+    /// The domain that the code of the loaded movie runs in.
+    ///
+    /// This is `None` if the movie was loaded into the domain of the movie
+    /// that owns the `Loader`: that domain is shared, so nothing in it can be
+    /// attributed to the loaded content alone.
+    fn content_domain(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        movie: Arc<SwfMovie>,
+    ) -> Option<Domain<'gc>> {
+        let library = &activation.context.library;
+        let domain = library.library_for_movie(movie)?.try_avm2_domain()?;
+        let loader_domain = self
+            .0
+            .loader
+            .and_then(|loader| loader.as_display_object())
+            .and_then(|loader| library.library_for_movie(loader.movie()))
+            .and_then(|library| library.try_avm2_domain());
+
+        (loader_domain != Some(domain)).then_some(domain)
+    }
+}
+
+/// Stop the timeline of a display object and of all of its descendants.
+fn stop_timelines<'gc>(context: &mut UpdateContext<'gc>, object: DisplayObject<'gc>) {
+    if let Some(clip) = object.as_movie_clip() {
+        clip.stop(context);
+    }
+
+    if let Some(container) = object.as_container() {
+        for child in container.iter_render_list() {
+            stop_timelines(context, child);
+        }
     }
 }
 
@@ -1,6 +1,7 @@
 //! Object representation for sounds
 
 use crate::avm2::activation::Activation;
+use crate::avm2::domain::Domain;
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
@@ -8,10 +9,12 @@ use crate::avm2::Error;
 use crate::backend::audio::SoundInstanceHandle;
 use crate::context::UpdateContext;
 use crate::display_object::SoundTransform;
+use crate::tag_utils::SwfMovie;
 use crate::utils::HasPrefixField;
 use core::fmt;
 use gc_arena::{Collect, Gc, GcWeak};
 use std::cell::{Cell, RefCell};
+use std::sync::Arc;
 
 /// A class instance allocator that allocates SoundChannel objects.
 pub fn sound_channel_allocator<'gc>(
@@ -29,6 +32,8 @@ pub fn sound_channel_allocator<'gc>(
                 should_stop: false,
             }),
             position: Cell::new(0.0),
+            owner_domain: None,
+            owner_movie: None,
         },
     ))
     .into())
@@ -62,6 +67,12 @@ pub struct SoundChannelObjectData<'gc> {
 
     /// Position of the last playing sound in milliseconds.
     position: Cell<f64>,
+
+    /// The domain of the code that started the sound.
+    owner_domain: Option<Domain<'gc>>,
+
+    /// The movie of the code that started the sound.
+    owner_movie: Option<Arc<SwfMovie>>,
 }
 
 pub enum SoundChannelData {
@@ -89,6 +100,8 @@ impl<'gc> SoundChannelObject<'gc> {
                     should_stop: false,
                 }),
                 position: Cell::new(0.0),
+                owner_domain: activation.caller_domain(),
+                owner_movie: activation.caller_movie(),
             },
         ));
 
@@ -111,6 +124,16 @@ impl<'gc> SoundChannelObject<'gc> {
         self.0.position.get()
     }
 
+    /// The domain of the code that started the sound, if known.
+    pub fn owner_domain(self) -> Option<Domain<'gc>> {
+        self.0.owner_domain
+    }
+
+    /// The movie of the code that started the sound, if known.
+    pub fn owner_movie(self) -> Option<Arc<SwfMovie>> {
+        self.0.owner_movie.clone()
+    }
+
     pub fn instance(self) -> Option<SoundInstanceHandle> {
         let sound_channel_data = self.0.sound_channel_data.borrow();
         match &*sound_channel_data {
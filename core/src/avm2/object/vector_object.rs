@@ -82,6 +82,7 @@ impl<'gc> VectorObject<'gc> {
             },
         ))
         .into();
+        activation.avm2().record_allocation(object);
 
         Ok(object)
     }
@@ -0,0 +1,422 @@
+//! Memory and call sampling, as exposed by `flash.sampler`
+
+use crate::avm2::call_stack::CallStack;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::object::{Object, TObject, WeakObject};
+use crate::avm2::value::Value;
+use crate::string::{AvmString, WString};
+use gc_arena::{Collect, Mutation};
+use web_time::{Duration, Instant};
+
+/// How often a `Sample` containing the current call stack is taken while
+/// code is running.
+const STACK_SAMPLE_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Once this many samples are buffered, the sampler callback (if any) is
+/// invoked so that the movie can drain the buffer.
+const SAMPLE_BUFFER_SIZE: usize = 4096;
+
+/// The maximum number of buffered samples. Once reached, the oldest
+/// `SAMPLE_BUFFER_SIZE` samples are discarded, so that movies which never
+/// drain the buffer don't use unbounded memory.
+const MAX_BUFFERED_SAMPLES: usize = SAMPLE_BUFFER_SIZE * 64;
+
+/// The maximum number of objects tracked for `DeleteObjectSample`s. Once
+/// reached, the oldest `SAMPLE_BUFFER_SIZE` objects stop being tracked, like
+/// the samples above.
+const MAX_TRACKED_OBJECTS: usize = MAX_BUFFERED_SAMPLES;
+
+/// Approximate fixed cost of a single object, in bytes.
+const OBJECT_OVERHEAD: f64 = 40.0;
+
+/// Approximate cost of a single slot or dynamic property, in bytes.
+const PROPERTY_SIZE: f64 = 16.0;
+
+/// Approximate fixed cost of a single string, in bytes.
+const STRING_OVERHEAD: f64 = 24.0;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum SamplerState {
+    Stopped,
+    Running,
+    Paused,
+}
+
+/// A single frame of a sampled call stack.
+#[derive(Clone, Debug)]
+pub struct SampleFrame {
+    pub name: WString,
+}
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub enum SampleKind<'gc> {
+    /// A periodic sample of the call stack.
+    Stack,
+
+    /// An object was allocated.
+    NewObject {
+        id: u64,
+        class: Option<Class<'gc>>,
+        size: f64,
+    },
+
+    /// A previously allocated object was garbage collected.
+    DeleteObject { id: u64, size: f64 },
+}
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct SampleRecord<'gc> {
+    #[collect(require_static)]
+    pub time: Instant,
+
+    #[collect(require_static)]
+    pub stack: Vec<SampleFrame>,
+
+    pub kind: SampleKind<'gc>,
+}
+
+/// An object whose allocation was sampled, and which we still need to
+/// report a `DeleteObjectSample` for.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct TrackedObject<'gc> {
+    id: u64,
+    object: WeakObject<'gc>,
+    size: f64,
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct Sampler<'gc> {
+    #[collect(require_static)]
+    state: SamplerState,
+
+    samples: Vec<SampleRecord<'gc>>,
+
+    tracked_objects: Vec<TrackedObject<'gc>>,
+
+    next_object_id: u64,
+
+    #[collect(require_static)]
+    last_stack_sample: Option<Instant>,
+
+    /// Whether allocations made internally by the player (rather than by
+    /// ActionScript code) are recorded.
+    sample_internal_allocs: bool,
+
+    /// The function set with `setSamplerCallback`.
+    callback: Option<Object<'gc>>,
+
+    /// Whether the sample buffer filled up since the callback was last run.
+    callback_pending: bool,
+}
+
+impl<'gc> Sampler<'gc> {
+    pub fn new() -> Self {
+        Self {
+            state: SamplerState::Stopped,
+            samples: Vec::new(),
+            tracked_objects: Vec::new(),
+            next_object_id: 1,
+            last_stack_sample: None,
+            sample_internal_allocs: false,
+            callback: None,
+            callback_pending: false,
+        }
+    }
+
+    /// Whether new samples are currently being recorded.
+    #[inline]
+    pub fn is_sampling(&self) -> bool {
+        self.state == SamplerState::Running
+    }
+
+    pub fn start(&mut self) {
+        self.state = SamplerState::Running;
+        self.last_stack_sample = None;
+    }
+
+    pub fn pause(&mut self) {
+        if self.state == SamplerState::Running {
+            self.state = SamplerState::Paused;
+        }
+    }
+
+    /// Stops sampling. All buffered samples are discarded, and objects
+    /// allocated before this point will no longer be tracked.
+    pub fn stop(&mut self) {
+        self.state = SamplerState::Stopped;
+        self.clear();
+        self.tracked_objects.clear();
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.callback_pending = false;
+    }
+
+    pub fn set_sample_internal_allocs(&mut self, sample_internal_allocs: bool) {
+        self.sample_internal_allocs = sample_internal_allocs;
+    }
+
+    pub fn sample_internal_allocs(&self) -> bool {
+        self.sample_internal_allocs
+    }
+
+    pub fn set_callback(&mut self, callback: Option<Object<'gc>>) {
+        self.callback = callback;
+    }
+
+    /// Returns the sampler callback if the sample buffer filled up since the
+    /// last time this was called.
+    pub fn take_pending_callback(&mut self) -> Option<Object<'gc>> {
+        if std::mem::take(&mut self.callback_pending) {
+            self.callback
+        } else {
+            None
+        }
+    }
+
+    /// Called whenever a method is pushed onto the call stack.
+    ///
+    /// This counts method invocations and periodically records the current
+    /// call stack.
+    pub fn on_method_call(&mut self, method: Method<'gc>, call_stack: &CallStack<'gc>) {
+        if !self.is_sampling() {
+            return;
+        }
+
+        method.increment_invocation_count();
+
+        let now = Instant::now();
+        let due = self
+            .last_stack_sample
+            .is_none_or(|last| now.duration_since(last) >= STACK_SAMPLE_INTERVAL);
+        if due {
+            self.last_stack_sample = Some(now);
+            self.push_sample(SampleRecord {
+                time: now,
+                stack: stack_frames(call_stack),
+                kind: SampleKind::Stack,
+            });
+        }
+    }
+
+    /// Called whenever an object is allocated.
+    pub fn on_new_object(&mut self, object: Object<'gc>, call_stack: &CallStack<'gc>) {
+        if !self.is_sampling() {
+            return;
+        }
+
+        if !self.sample_internal_allocs && call_stack.is_empty() {
+            // Allocations made with no ActionScript code on the stack were
+            // made by the player itself (events, display objects, ...).
+            return;
+        }
+
+        let id = self.next_object_id;
+        self.next_object_id += 1;
+
+        let size = object_size(object);
+
+        if self.tracked_objects.len() >= MAX_TRACKED_OBJECTS {
+            self.tracked_objects.drain(..SAMPLE_BUFFER_SIZE);
+        }
+        self.tracked_objects.push(TrackedObject {
+            id,
+            object: object.downgrade(),
+            size,
+        });
+
+        self.push_sample(SampleRecord {
+            time: Instant::now(),
+            stack: stack_frames(call_stack),
+            kind: SampleKind::NewObject {
+                id,
+                class: Some(object.instance_class()),
+                size,
+            },
+        });
+    }
+
+    /// Records a `DeleteObjectSample` for every tracked object that has been
+    /// garbage collected since the last call.
+    pub fn collect_deleted_objects(&mut self, mc: &Mutation<'gc>) {
+        if self.state == SamplerState::Stopped {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut deleted = Vec::new();
+        self.tracked_objects.retain(|tracked| {
+            if tracked.object.upgrade(mc).is_some() {
+                true
+            } else {
+                deleted.push((tracked.id, tracked.size));
+                false
+            }
+        });
+
+        for (id, size) in deleted {
+            self.push_sample(SampleRecord {
+                time: now,
+                stack: Vec::new(),
+                kind: SampleKind::DeleteObject { id, size },
+            });
+        }
+    }
+
+    /// Looks up a tracked object by the id reported in its `NewObjectSample`.
+    pub fn tracked_object(&self, id: u64, mc: &Mutation<'gc>) -> Option<Object<'gc>> {
+        self.tracked_objects
+            .iter()
+            .find(|tracked| tracked.id == id)
+            .and_then(|tracked| tracked.object.upgrade(mc))
+    }
+
+    pub fn samples(&self) -> &[SampleRecord<'gc>] {
+        &self.samples
+    }
+
+    pub fn sample_count(&self) -> usize {
+        self.samples.len()
+    }
+
+    fn push_sample(&mut self, sample: SampleRecord<'gc>) {
+        if self.samples.len() >= MAX_BUFFERED_SAMPLES {
+            self.samples.drain(..SAMPLE_BUFFER_SIZE);
+        }
+        self.samples.push(sample);
+
+        if self.samples.len() % SAMPLE_BUFFER_SIZE == 0 && self.callback.is_some() {
+            self.callback_pending = true;
+        }
+    }
+}
+
+impl Default for Sampler<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn stack_frames(call_stack: &CallStack<'_>) -> Vec<SampleFrame> {
+    call_stack
+        .frame_names()
+        .into_iter()
+        .map(|name| SampleFrame { name })
+        .collect()
+}
+
+/// Approximates the amount of memory used by a value, in bytes.
+///
+/// Ruffle's object layout differs from Flash Player's, so this is only an
+/// estimate meant to be useful for relative comparisons (e.g. leak hunting).
+pub fn value_size(value: Value<'_>) -> f64 {
+    match value {
+        Value::Undefined | Value::Null | Value::Bool(_) => 4.0,
+        Value::Integer(_) => 4.0,
+        Value::Number(_) => 8.0,
+        Value::String(string) => string_size(string),
+        Value::Object(object) => object_size(object),
+    }
+}
+
+fn string_size(string: AvmString<'_>) -> f64 {
+    if string.is_dependent() {
+        // Dependent strings share the buffer of their master string.
+        return STRING_OVERHEAD;
+    }
+
+    let char_size = if string.is_wide() { 2.0 } else { 1.0 };
+    STRING_OVERHEAD + string.len() as f64 * char_size
+}
+
+fn object_size(object: Object<'_>) -> f64 {
+    let slots = object.vtable().default_slots().len();
+    let dynamic_properties = object.base().values().as_hashmap().len();
+    let mut size = OBJECT_OVERHEAD + (slots + dynamic_properties) as f64 * PROPERTY_SIZE;
+
+    if let Some(array) = object.as_array_storage() {
+        size += array.length() as f64 * 8.0;
+    } else if let Some(vector) = object.as_vector_storage() {
+        size += vector.length() as f64 * 8.0;
+    } else if let Some(bytearray) = object.as_bytearray() {
+        size += bytearray.len() as f64;
+    } else if let Some(bitmap_data) = object.as_bitmap_data() {
+        size += (bitmap_data.width() * bitmap_data.height() * 4) as f64;
+    }
+
+    size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delete_sample(id: u64) -> SampleRecord<'static> {
+        SampleRecord {
+            time: Instant::now(),
+            stack: Vec::new(),
+            kind: SampleKind::DeleteObject { id, size: 0.0 },
+        }
+    }
+
+    fn sample_id(sample: &SampleRecord<'_>) -> u64 {
+        match sample.kind {
+            SampleKind::DeleteObject { id, .. } => id,
+            _ => panic!("expected a DeleteObject sample"),
+        }
+    }
+
+    #[test]
+    fn buffer_is_bounded() {
+        let mut sampler = Sampler::new();
+        sampler.start();
+
+        let count = (MAX_BUFFERED_SAMPLES + SAMPLE_BUFFER_SIZE / 2) as u64;
+        for id in 0..count {
+            sampler.push_sample(delete_sample(id));
+        }
+
+        let samples = sampler.samples();
+        assert!(samples.len() <= MAX_BUFFERED_SAMPLES);
+        assert_eq!(count - 1, sample_id(samples.last().unwrap()));
+        assert_eq!(SAMPLE_BUFFER_SIZE as u64, sample_id(&samples[0]));
+    }
+
+    #[test]
+    fn no_callback_pending_without_callback() {
+        let mut sampler = Sampler::new();
+        sampler.start();
+
+        for id in 0..SAMPLE_BUFFER_SIZE as u64 {
+            sampler.push_sample(delete_sample(id));
+        }
+        // Without a callback, there's nothing to run.
+        assert!(sampler.take_pending_callback().is_none());
+        assert_eq!(SAMPLE_BUFFER_SIZE, sampler.sample_count());
+
+        sampler.clear();
+        assert_eq!(0, sampler.sample_count());
+    }
+
+    #[test]
+    fn stop_discards_samples() {
+        let mut sampler = Sampler::new();
+        sampler.start();
+        sampler.push_sample(delete_sample(1));
+        assert!(sampler.is_sampling());
+
+        sampler.pause();
+        assert!(!sampler.is_sampling());
+        assert_eq!(1, sampler.sample_count());
+
+        sampler.stop();
+        assert!(!sampler.is_sampling());
+        assert_eq!(0, sampler.sample_count());
+    }
+}
@@ -1,5 +1,9 @@
+pub mod accessibility;
 pub mod audio;
+pub mod capture;
+pub mod filesystem;
 pub mod log;
 pub mod navigator;
+pub mod process;
 pub mod storage;
 pub mod ui;
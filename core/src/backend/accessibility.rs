@@ -0,0 +1,142 @@
+//! Accessibility tree of a movie, as exposed to assistive technologies
+
+use swf::{Rectangle, Twips};
+
+/// Identifies a node of the accessibility tree.
+///
+/// Nodes keep their identifier for as long as their display object exists.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AccessibilityNodeId(pub u64);
+
+/// What kind of object a node represents.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AccessibilityRole {
+    /// The movie itself, which is the root of the tree.
+    Movie,
+
+    /// A button, or a clip acting as one.
+    Button,
+
+    /// A text field that can't be edited.
+    Text,
+
+    /// A text field that can be edited.
+    InputText,
+
+    /// A clip given a name through its accessibility properties.
+    Graphic,
+}
+
+/// An object of the movie exposed to assistive technologies.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilityNode {
+    pub id: AccessibilityNodeId,
+
+    pub role: AccessibilityRole,
+
+    /// The name from `AccessibilityProperties.name`.
+    pub name: String,
+
+    /// The description from `AccessibilityProperties.description`.
+    pub description: String,
+
+    /// The keyboard shortcut from `AccessibilityProperties.shortcut`.
+    pub shortcut: String,
+
+    /// The text of a text field, if it may be read out.
+    pub value: Option<String>,
+
+    /// The bounds of the object in viewport coordinates.
+    pub bounds: Rectangle<Twips>,
+
+    pub children: Vec<AccessibilityNode>,
+}
+
+/// The accessibility tree of a movie.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilityTree {
+    pub root: AccessibilityNode,
+
+    /// The node having the keyboard focus, if any.
+    pub focus: Option<AccessibilityNodeId>,
+
+    /// The focusable nodes in the order they're visited with the tab key.
+    pub tab_order: Vec<AccessibilityNodeId>,
+}
+
+impl AccessibilityTree {
+    /// Finds the node with the given identifier.
+    pub fn node(&self, id: AccessibilityNodeId) -> Option<&AccessibilityNode> {
+        fn find(node: &AccessibilityNode, id: AccessibilityNodeId) -> Option<&AccessibilityNode> {
+            if node.id == id {
+                return Some(node);
+            }
+            node.children.iter().find_map(|child| find(child, id))
+        }
+        find(&self.root, id)
+    }
+}
+
+/// Something an assistive technology asked to be done with a node.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AccessibilityAction {
+    /// Move the keyboard focus to the node.
+    Focus,
+
+    /// Perform the default action of the node, such as clicking a button.
+    Click,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AccessibilityRequest {
+    pub node: AccessibilityNodeId,
+    pub action: AccessibilityAction,
+}
+
+/// Publishes the accessibility tree of a movie to assistive technologies.
+///
+/// While the backend is active, the core rebuilds the tree after every frame
+/// and sends it whenever it has changed.
+pub trait AccessibilityBackend {
+    /// Whether the platform can expose the movie to assistive technologies at all.
+    ///
+    /// This is reported by `Capabilities.hasAccessibility`.
+    fn is_supported(&self) -> bool;
+
+    /// Whether an assistive technology is currently interested in the tree.
+    ///
+    /// This is reported by `Accessibility.active`.
+    fn is_active(&self) -> bool;
+
+    fn update_tree(&mut self, tree: &AccessibilityTree);
+
+    /// Called by `Accessibility.sendEvent`, with the MSAA event type and child ID given by the movie.
+    fn send_event(&mut self, _node: AccessibilityNodeId, _child_id: u32, _event_type: u32) {}
+
+    /// Returns the actions requested by assistive technologies since the last call.
+    fn poll_requests(&mut self) -> Vec<AccessibilityRequest> {
+        Vec::new()
+    }
+}
+
+/// AccessibilityBackend that doesn't expose anything.
+#[derive(Default)]
+pub struct NullAccessibilityBackend {}
+
+impl NullAccessibilityBackend {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl AccessibilityBackend for NullAccessibilityBackend {
+    fn is_supported(&self) -> bool {
+        false
+    }
+
+    fn is_active(&self) -> bool {
+        false
+    }
+
+    fn update_tree(&mut self, _tree: &AccessibilityTree) {}
+}
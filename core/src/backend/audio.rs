@@ -2,14 +2,16 @@ use std::any::Any;
 
 use crate::{
     avm1::{NativeObject, Object as Avm1Object},
-    avm2::{Avm2, EventObject as Avm2EventObject, SoundChannelObject},
+    avm2::{Avm2, Domain as Avm2Domain, EventObject as Avm2EventObject, SoundChannelObject},
     buffer::Substream,
     context::UpdateContext,
     display_object::{self, DisplayObject, MovieClip, TDisplayObject},
     string::AvmString,
+    tag_utils::SwfMovie,
 };
 use gc_arena::Collect;
 use slotmap::{new_key_type, Key, SlotMap};
+use std::sync::Arc;
 
 #[cfg(feature = "audio")]
 pub mod decoders;
@@ -556,6 +558,36 @@ impl<'gc> AudioManager<'gc> {
         });
     }
 
+    /// Stops any sound whose AVM2 `SoundChannel` was created by code from
+    /// `domain` or one of its child domains.
+    pub fn stop_sounds_in_avm2_domain(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        domain: Avm2Domain<'gc>,
+    ) {
+        self.sounds.retain(move |sound| {
+            let owner_domain = sound.avm2_object.and_then(|channel| channel.owner_domain());
+            if owner_domain.is_some_and(|owner| owner.is_within(domain)) {
+                audio.stop_sound(sound.instance);
+                return false;
+            }
+            true
+        });
+    }
+
+    /// The movies that the currently playing sounds belong to, where known.
+    pub fn sound_movies(&self) -> Vec<Arc<SwfMovie>> {
+        self.sounds
+            .iter()
+            .filter_map(|sound| {
+                sound
+                    .display_object
+                    .map(|object| object.movie())
+                    .or_else(|| sound.avm2_object.and_then(|channel| channel.owner_movie()))
+            })
+            .collect()
+    }
+
     pub fn stop_all_sounds(&mut self, audio: &mut dyn AudioBackend) {
         self.sounds.clear();
         audio.stop_all_sounds();
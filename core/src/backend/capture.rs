@@ -0,0 +1,108 @@
+//! Camera and microphone capture
+
+mod file;
+
+pub use file::{CaptureFileError, FileCamera, FileCaptureBackend, FileMicrophone};
+
+use ruffle_render::bitmap::Bitmap;
+
+/// A camera capture mode, as requested with `Camera.setMode`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraMode {
+    pub width: u32,
+    pub height: u32,
+
+    /// The number of frames per second.
+    pub fps: f64,
+}
+
+impl Default for CameraMode {
+    /// The mode a `Camera` starts out with in Flash Player.
+    fn default() -> Self {
+        Self {
+            width: 160,
+            height: 120,
+            fps: 15.0,
+        }
+    }
+}
+
+/// A source of camera frames and microphone audio.
+///
+/// Devices are addressed by their index in `camera_names` and
+/// `microphone_names` respectively. The core polls open devices once per
+/// tick.
+pub trait CaptureBackend {
+    /// The names of all available cameras, in index order.
+    fn camera_names(&self) -> Vec<String>;
+
+    /// Starts capturing from the camera at `index`.
+    ///
+    /// The backend should pick the supported mode closest to `mode`, and
+    /// return it. `None` is returned if the camera could not be opened.
+    fn open_camera(&mut self, index: usize, mode: CameraMode) -> Option<CameraMode>;
+
+    /// Stops capturing from the camera at `index`.
+    fn close_camera(&mut self, index: usize);
+
+    /// Returns the latest frame captured by the camera at `index`, if a new
+    /// frame has been captured since the last call.
+    fn poll_camera_frame(&mut self, index: usize) -> Option<Bitmap>;
+
+    /// The names of all available microphones, in index order.
+    fn microphone_names(&self) -> Vec<String>;
+
+    /// Starts capturing mono audio at `sample_rate` Hz from the microphone at
+    /// `index`. Returns `false` if the microphone could not be opened.
+    fn open_microphone(&mut self, index: usize, sample_rate: u32) -> bool;
+
+    /// Stops capturing from the microphone at `index`.
+    fn close_microphone(&mut self, index: usize);
+
+    /// Appends all samples captured by the microphone at `index` since the
+    /// last call to `output`.
+    fn poll_microphone_samples(&mut self, index: usize, output: &mut Vec<f32>);
+
+    /// Advances the capture clock by `dt` milliseconds.
+    ///
+    /// Backends capturing from real devices may ignore this.
+    fn tick(&mut self, _dt: f64) {}
+}
+
+/// CaptureBackend that has no devices.
+#[derive(Default)]
+pub struct NullCaptureBackend {}
+
+impl NullCaptureBackend {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl CaptureBackend for NullCaptureBackend {
+    fn camera_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn open_camera(&mut self, _index: usize, _mode: CameraMode) -> Option<CameraMode> {
+        None
+    }
+
+    fn close_camera(&mut self, _index: usize) {}
+
+    fn poll_camera_frame(&mut self, _index: usize) -> Option<Bitmap> {
+        None
+    }
+
+    fn microphone_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn open_microphone(&mut self, _index: usize, _sample_rate: u32) -> bool {
+        false
+    }
+
+    fn close_microphone(&mut self, _index: usize) {}
+
+    fn poll_microphone_samples(&mut self, _index: usize, _output: &mut Vec<f32>) {}
+}
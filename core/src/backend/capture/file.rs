@@ -0,0 +1,617 @@
+//! A capture backend that plays back media files instead of using real
+//! devices, which makes camera and microphone content testable offline.
+
+use super::{CameraMode, CaptureBackend};
+use byteorder::{ByteOrder, LittleEndian};
+use ruffle_render::bitmap::{Bitmap, BitmapFormat};
+use ruffle_render::utils::{decode_define_bits_jpeg, determine_jpeg_tag_format, JpegTagFormat};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CaptureFileError {
+    #[error("Invalid Y4M file: {0}")]
+    InvalidY4m(&'static str),
+
+    #[error("Unsupported Y4M colorspace: {0}")]
+    UnsupportedY4mColorspace(String),
+
+    #[error("Frame {0} is not a PNG image")]
+    NotPng(usize),
+
+    #[error("Couldn't decode frame {0}: {1}")]
+    InvalidPng(usize, ruffle_render::error::Error),
+
+    #[error("Invalid WAV file: {0}")]
+    InvalidWav(&'static str),
+
+    #[error("Unsupported WAV format {format} with {bits_per_sample} bits per sample")]
+    UnsupportedWavFormat { format: u16, bits_per_sample: u16 },
+
+    #[error("The file contains no frames or samples")]
+    Empty,
+}
+
+/// A virtual camera that loops over a sequence of decoded frames.
+pub struct FileCamera {
+    name: String,
+    frames: Vec<Bitmap>,
+    fps: f64,
+
+    /// The playback position in milliseconds, if the camera is open.
+    time: Option<f64>,
+
+    /// The index of the frame that was last returned from `poll_frame`.
+    last_frame: Option<usize>,
+}
+
+impl FileCamera {
+    /// Creates a camera from the contents of a YUV4MPEG2 file.
+    ///
+    /// The frame rate of the camera is taken from the file header.
+    pub fn from_y4m(name: impl Into<String>, data: &[u8]) -> Result<Self, CaptureFileError> {
+        let (header, mut data) =
+            split_line(data).ok_or(CaptureFileError::InvalidY4m("missing stream header"))?;
+        let mut params = header.split(|&b| b == b' ');
+        if params.next() != Some(b"YUV4MPEG2") {
+            return Err(CaptureFileError::InvalidY4m("missing YUV4MPEG2 signature"));
+        }
+
+        let mut width = None;
+        let mut height = None;
+        let mut fps = 30.0;
+        let mut colorspace = "420jpeg".to_string();
+        for param in params.filter(|param| !param.is_empty()) {
+            let value = String::from_utf8_lossy(&param[1..]);
+            match param[0] {
+                b'W' => width = value.parse::<u32>().ok(),
+                b'H' => height = value.parse::<u32>().ok(),
+                b'F' => {
+                    let (numerator, denominator) = value
+                        .split_once(':')
+                        .ok_or(CaptureFileError::InvalidY4m("invalid frame rate"))?;
+                    let numerator: f64 = numerator
+                        .parse()
+                        .map_err(|_| CaptureFileError::InvalidY4m("invalid frame rate"))?;
+                    let denominator: f64 = denominator
+                        .parse()
+                        .map_err(|_| CaptureFileError::InvalidY4m("invalid frame rate"))?;
+                    if numerator > 0.0 && denominator > 0.0 {
+                        fps = numerator / denominator;
+                    }
+                }
+                b'C' => colorspace = value.into_owned(),
+                // Interlacing, aspect ratio and extensions don't matter to us.
+                _ => {}
+            }
+        }
+
+        let (width, height) = width
+            .zip(height)
+            .filter(|&(width, height)| width > 0 && height > 0)
+            .ok_or(CaptureFileError::InvalidY4m("missing frame dimensions"))?;
+        let luma_len = (width as usize)
+            .checked_mul(height as usize)
+            .ok_or(CaptureFileError::InvalidY4m("frame dimensions too large"))?;
+        // This can't overflow when `luma_len` didn't.
+        let chroma_len = (width.div_ceil(2) as usize) * (height.div_ceil(2) as usize);
+        let frame_len = match colorspace.as_str() {
+            "420jpeg" | "420paldv" | "420mpeg2" | "420" => chroma_len
+                .checked_mul(2)
+                .and_then(|len| len.checked_add(luma_len)),
+            "mono" => Some(luma_len),
+            "444" => luma_len.checked_mul(3),
+            _ => return Err(CaptureFileError::UnsupportedY4mColorspace(colorspace)),
+        }
+        .ok_or(CaptureFileError::InvalidY4m("frame dimensions too large"))?;
+
+        let mut frames = Vec::new();
+        while !data.is_empty() {
+            let (frame_header, rest) =
+                split_line(data).ok_or(CaptureFileError::InvalidY4m("truncated frame header"))?;
+            if !frame_header.starts_with(b"FRAME") {
+                return Err(CaptureFileError::InvalidY4m("missing frame header"));
+            }
+            if rest.len() < frame_len {
+                return Err(CaptureFileError::InvalidY4m("truncated frame"));
+            }
+            let (frame, rest) = rest.split_at(frame_len);
+            data = rest;
+
+            let yuv = match colorspace.as_str() {
+                "mono" => {
+                    let mut yuv = frame.to_vec();
+                    yuv.resize(luma_len + chroma_len * 2, 128);
+                    yuv
+                }
+                "444" => subsample_444(frame, width as usize, height as usize),
+                _ => frame.to_vec(),
+            };
+            frames.push(Bitmap::new(width, height, BitmapFormat::Yuv420p, yuv));
+        }
+
+        Self::new(name, frames, fps)
+    }
+
+    /// Creates a camera from a sequence of PNG images, played back at `fps`
+    /// frames per second.
+    pub fn from_png_sequence<'a>(
+        name: impl Into<String>,
+        images: impl IntoIterator<Item = &'a [u8]>,
+        fps: f64,
+    ) -> Result<Self, CaptureFileError> {
+        let frames = images
+            .into_iter()
+            .enumerate()
+            .map(|(i, data)| {
+                if determine_jpeg_tag_format(data) != JpegTagFormat::Png {
+                    return Err(CaptureFileError::NotPng(i));
+                }
+                decode_define_bits_jpeg(data, None).map_err(|e| CaptureFileError::InvalidPng(i, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::new(name, frames, fps)
+    }
+
+    fn new(
+        name: impl Into<String>,
+        frames: Vec<Bitmap>,
+        fps: f64,
+    ) -> Result<Self, CaptureFileError> {
+        if frames.is_empty() {
+            return Err(CaptureFileError::Empty);
+        }
+
+        Ok(Self {
+            name: name.into(),
+            frames,
+            fps: if fps > 0.0 { fps } else { 30.0 },
+            time: None,
+            last_frame: None,
+        })
+    }
+
+    /// The mode this camera captures in, regardless of the requested mode.
+    fn mode(&self) -> CameraMode {
+        CameraMode {
+            width: self.frames[0].width(),
+            height: self.frames[0].height(),
+            fps: self.fps,
+        }
+    }
+
+    fn poll_frame(&mut self) -> Option<Bitmap> {
+        let time = self.time?;
+        let index = (time * self.fps / 1000.0) as usize % self.frames.len();
+        if self.last_frame == Some(index) {
+            return None;
+        }
+
+        self.last_frame = Some(index);
+        Some(self.frames[index].clone())
+    }
+}
+
+/// A virtual microphone that loops over the samples of a WAV file.
+pub struct FileMicrophone {
+    name: String,
+
+    /// The samples of the file, mixed down to mono.
+    samples: Vec<f32>,
+    sample_rate: u32,
+    playback: Option<MicrophonePlayback>,
+}
+
+struct MicrophonePlayback {
+    /// The sample rate requested by the player.
+    output_rate: u32,
+
+    /// The read position in `samples`, in fractional source samples.
+    position: f64,
+
+    /// The number of output samples that are due but haven't been polled yet.
+    pending: f64,
+}
+
+impl FileMicrophone {
+    /// Creates a microphone from the contents of a WAV file.
+    ///
+    /// 8, 16, 24 and 32-bit integer PCM as well as 32-bit float data is
+    /// supported. All channels are mixed down to mono.
+    pub fn from_wav(name: impl Into<String>, data: &[u8]) -> Result<Self, CaptureFileError> {
+        if data.len() < 12 || &data[0..4] != b"RIFF" || &data[8..12] != b"WAVE" {
+            return Err(CaptureFileError::InvalidWav("missing RIFF/WAVE header"));
+        }
+
+        let mut format = None;
+        let mut sample_data = None;
+        let mut chunks = &data[12..];
+        while chunks.len() >= 8 {
+            let id = &chunks[0..4];
+            let len = LittleEndian::read_u32(&chunks[4..8]) as usize;
+            let end = len.checked_add(8);
+            let body = end
+                .and_then(|end| chunks.get(8..end))
+                // Some writers don't fill in the length of the data chunk.
+                .unwrap_or(&chunks[8..]);
+            match id {
+                b"fmt " if body.len() >= 16 => format = Some(body),
+                b"data" => sample_data = Some(body),
+                _ => {}
+            }
+            // Chunks are padded to an even length.
+            chunks = end
+                .and_then(|end| end.checked_add(len & 1))
+                .and_then(|next| chunks.get(next..))
+                .unwrap_or_default();
+        }
+
+        let format = format.ok_or(CaptureFileError::InvalidWav("missing fmt chunk"))?;
+        let sample_data = sample_data.ok_or(CaptureFileError::InvalidWav("missing data chunk"))?;
+
+        let mut format_tag = LittleEndian::read_u16(&format[0..2]);
+        let channels = LittleEndian::read_u16(&format[2..4]) as usize;
+        let sample_rate = LittleEndian::read_u32(&format[4..8]);
+        let bits_per_sample = LittleEndian::read_u16(&format[14..16]);
+        if format_tag == 0xFFFE && format.len() >= 26 {
+            // WAVE_FORMAT_EXTENSIBLE; the actual format is the first two
+            // bytes of the sub-format GUID.
+            format_tag = LittleEndian::read_u16(&format[24..26]);
+        }
+        if channels == 0 || sample_rate == 0 {
+            return Err(CaptureFileError::InvalidWav("invalid fmt chunk"));
+        }
+
+        let decode: fn(&[u8]) -> f32 = match (format_tag, bits_per_sample) {
+            (1, 8) => |b| (b[0] as f32 - 128.0) / 128.0,
+            (1, 16) => |b| LittleEndian::read_i16(b) as f32 / 32768.0,
+            (1, 24) => |b| LittleEndian::read_i24(b) as f32 / 8388608.0,
+            (1, 32) => |b| LittleEndian::read_i32(b) as f32 / 2147483648.0,
+            (3, 32) => LittleEndian::read_f32,
+            (format, bits_per_sample) => {
+                return Err(CaptureFileError::UnsupportedWavFormat {
+                    format,
+                    bits_per_sample,
+                })
+            }
+        };
+
+        let sample_size = bits_per_sample as usize / 8;
+        let samples: Vec<f32> = sample_data
+            .chunks_exact(sample_size * channels)
+            .map(|frame| {
+                let sum: f32 = frame.chunks_exact(sample_size).map(decode).sum();
+                sum / channels as f32
+            })
+            .collect();
+        if samples.is_empty() {
+            return Err(CaptureFileError::Empty);
+        }
+
+        Ok(Self {
+            name: name.into(),
+            samples,
+            sample_rate,
+            playback: None,
+        })
+    }
+
+    fn poll_samples(&mut self, output: &mut Vec<f32>) {
+        let Some(playback) = &mut self.playback else {
+            return;
+        };
+
+        let step = self.sample_rate as f64 / playback.output_rate as f64;
+        let len = self.samples.len();
+        while playback.pending >= 1.0 {
+            // Linearly interpolate between the two nearest source samples.
+            let index = playback.position as usize;
+            let fraction = (playback.position - index as f64) as f32;
+            let current = self.samples[index % len];
+            let next = self.samples[(index + 1) % len];
+            output.push(current + (next - current) * fraction);
+
+            playback.position = (playback.position + step) % len as f64;
+            playback.pending -= 1.0;
+        }
+    }
+}
+
+/// CaptureBackend that plays back files through virtual devices.
+///
+/// Playback is driven by `CaptureBackend::tick`, so captured media is
+/// deterministic with respect to the player's clock. All media loops.
+#[derive(Default)]
+pub struct FileCaptureBackend {
+    cameras: Vec<FileCamera>,
+    microphones: Vec<FileMicrophone>,
+}
+
+impl FileCaptureBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_camera(mut self, camera: FileCamera) -> Self {
+        self.cameras.push(camera);
+        self
+    }
+
+    pub fn with_microphone(mut self, microphone: FileMicrophone) -> Self {
+        self.microphones.push(microphone);
+        self
+    }
+}
+
+impl CaptureBackend for FileCaptureBackend {
+    fn camera_names(&self) -> Vec<String> {
+        self.cameras.iter().map(|c| c.name.clone()).collect()
+    }
+
+    fn open_camera(&mut self, index: usize, _mode: CameraMode) -> Option<CameraMode> {
+        let camera = self.cameras.get_mut(index)?;
+        if camera.time.is_none() {
+            camera.time = Some(0.0);
+            camera.last_frame = None;
+        }
+        Some(camera.mode())
+    }
+
+    fn close_camera(&mut self, index: usize) {
+        if let Some(camera) = self.cameras.get_mut(index) {
+            camera.time = None;
+        }
+    }
+
+    fn poll_camera_frame(&mut self, index: usize) -> Option<Bitmap> {
+        self.cameras.get_mut(index)?.poll_frame()
+    }
+
+    fn microphone_names(&self) -> Vec<String> {
+        self.microphones.iter().map(|m| m.name.clone()).collect()
+    }
+
+    fn open_microphone(&mut self, index: usize, sample_rate: u32) -> bool {
+        let Some(microphone) = self.microphones.get_mut(index) else {
+            return false;
+        };
+        if sample_rate == 0 {
+            return false;
+        }
+
+        match &mut microphone.playback {
+            // Changing the rate of an open microphone keeps its position.
+            Some(playback) => playback.output_rate = sample_rate,
+            None => {
+                microphone.playback = Some(MicrophonePlayback {
+                    output_rate: sample_rate,
+                    position: 0.0,
+                    pending: 0.0,
+                })
+            }
+        }
+        true
+    }
+
+    fn close_microphone(&mut self, index: usize) {
+        if let Some(microphone) = self.microphones.get_mut(index) {
+            microphone.playback = None;
+        }
+    }
+
+    fn poll_microphone_samples(&mut self, index: usize, output: &mut Vec<f32>) {
+        if let Some(microphone) = self.microphones.get_mut(index) {
+            microphone.poll_samples(output);
+        }
+    }
+
+    fn tick(&mut self, dt: f64) {
+        for camera in &mut self.cameras {
+            if let Some(time) = &mut camera.time {
+                *time += dt;
+            }
+        }
+
+        for microphone in &mut self.microphones {
+            if let Some(playback) = &mut microphone.playback {
+                playback.pending += dt * playback.output_rate as f64 / 1000.0;
+            }
+        }
+    }
+}
+
+/// Splits off the first `\n`-terminated line of `data`.
+fn split_line(data: &[u8]) -> Option<(&[u8], &[u8])> {
+    let end = data.iter().position(|&b| b == b'\n')?;
+    Some((&data[..end], &data[end + 1..]))
+}
+
+/// Converts a planar YUV 4:4:4 frame to planar YUV 4:2:0.
+fn subsample_444(frame: &[u8], width: usize, height: usize) -> Vec<u8> {
+    let luma_len = width * height;
+    let chroma_width = width.div_ceil(2);
+    let chroma_height = height.div_ceil(2);
+
+    let mut yuv = frame[..luma_len].to_vec();
+    for plane in [&frame[luma_len..luma_len * 2], &frame[luma_len * 2..]] {
+        for y in 0..chroma_height {
+            for x in 0..chroma_width {
+                yuv.push(plane[y * 2 * width + x * 2]);
+            }
+        }
+    }
+    yuv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn y4m(header: &str, frames: &[&[u8]]) -> Vec<u8> {
+        let mut data = format!("{header}\n").into_bytes();
+        for frame in frames {
+            data.extend_from_slice(b"FRAME\n");
+            data.extend_from_slice(frame);
+        }
+        data
+    }
+
+    fn wav(format: u16, channels: u16, rate: u32, bits: u16, samples: &[u8]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(b"RIFF");
+        data.extend_from_slice(&(36 + samples.len() as u32).to_le_bytes());
+        data.extend_from_slice(b"WAVEfmt ");
+        data.extend_from_slice(&16u32.to_le_bytes());
+        data.extend_from_slice(&format.to_le_bytes());
+        data.extend_from_slice(&channels.to_le_bytes());
+        data.extend_from_slice(&rate.to_le_bytes());
+        let block_align = channels * bits / 8;
+        data.extend_from_slice(&(rate * block_align as u32).to_le_bytes());
+        data.extend_from_slice(&block_align.to_le_bytes());
+        data.extend_from_slice(&bits.to_le_bytes());
+        data.extend_from_slice(b"data");
+        data.extend_from_slice(&(samples.len() as u32).to_le_bytes());
+        data.extend_from_slice(samples);
+        data
+    }
+
+    #[test]
+    fn y4m_frames() {
+        let data = y4m(
+            "YUV4MPEG2 W2 H2 F10:1 Ip A1:1 C420jpeg",
+            &[&[0; 6], &[255; 6]],
+        );
+        let camera = FileCamera::from_y4m("cam", &data).unwrap();
+        assert_eq!(camera.frames.len(), 2);
+        assert_eq!(
+            camera.mode(),
+            CameraMode {
+                width: 2,
+                height: 2,
+                fps: 10.0
+            }
+        );
+        assert_eq!(camera.frames[1].data(), &[255; 6]);
+    }
+
+    #[test]
+    fn y4m_mono() {
+        let data = y4m("YUV4MPEG2 W2 H2 Cmono", &[&[16, 32, 64, 128]]);
+        let camera = FileCamera::from_y4m("cam", &data).unwrap();
+        assert_eq!(camera.frames[0].data(), &[16, 32, 64, 128, 128, 128]);
+    }
+
+    #[test]
+    fn y4m_errors() {
+        assert!(matches!(
+            FileCamera::from_y4m("cam", b"RIFF\n"),
+            Err(CaptureFileError::InvalidY4m(_))
+        ));
+        assert!(matches!(
+            FileCamera::from_y4m("cam", &y4m("YUV4MPEG2 W2 H2", &[&[0; 5]])),
+            Err(CaptureFileError::InvalidY4m("truncated frame"))
+        ));
+        assert!(matches!(
+            FileCamera::from_y4m("cam", &y4m("YUV4MPEG2 W2 H2 C422", &[&[0; 8]])),
+            Err(CaptureFileError::UnsupportedY4mColorspace(_))
+        ));
+        assert!(matches!(
+            FileCamera::from_y4m(
+                "cam",
+                &y4m("YUV4MPEG2 W4294967295 H4294967295 C444", &[&[0; 8]])
+            ),
+            Err(CaptureFileError::InvalidY4m("frame dimensions too large"))
+        ));
+        assert!(matches!(
+            FileCamera::from_y4m("cam", &y4m("YUV4MPEG2 W2 H2", &[])),
+            Err(CaptureFileError::Empty)
+        ));
+    }
+
+    #[test]
+    fn camera_playback() {
+        let data = y4m("YUV4MPEG2 W2 H2 F10:1", &[&[0; 6], &[1; 6], &[2; 6]]);
+        let mut backend =
+            FileCaptureBackend::new().with_camera(FileCamera::from_y4m("cam", &data).unwrap());
+        assert_eq!(backend.camera_names(), vec!["cam".to_string()]);
+
+        // Closed cameras don't produce frames.
+        assert!(backend.poll_camera_frame(0).is_none());
+
+        backend.open_camera(0, CameraMode::default()).unwrap();
+        assert_eq!(backend.poll_camera_frame(0).unwrap().data(), &[0; 6]);
+        assert!(backend.poll_camera_frame(0).is_none());
+
+        backend.tick(50.0);
+        assert!(backend.poll_camera_frame(0).is_none());
+        backend.tick(50.0);
+        assert_eq!(backend.poll_camera_frame(0).unwrap().data(), &[1; 6]);
+
+        // Playback loops.
+        backend.tick(200.0);
+        assert_eq!(backend.poll_camera_frame(0).unwrap().data(), &[0; 6]);
+
+        assert!(backend.open_camera(1, CameraMode::default()).is_none());
+    }
+
+    #[test]
+    fn wav_formats() {
+        let samples = [0x00, 0x40, 0x00, 0xC0];
+        let microphone = FileMicrophone::from_wav("mic", &wav(1, 1, 8000, 16, &samples)).unwrap();
+        assert_eq!(microphone.sample_rate, 8000);
+        assert_eq!(microphone.samples, vec![0.5, -0.5]);
+
+        // Stereo is mixed down to mono.
+        let microphone = FileMicrophone::from_wav("mic", &wav(1, 2, 8000, 16, &samples)).unwrap();
+        assert_eq!(microphone.samples, vec![0.0]);
+
+        let samples = [0.25f32.to_le_bytes(), (-1.0f32).to_le_bytes()].concat();
+        let microphone = FileMicrophone::from_wav("mic", &wav(3, 1, 8000, 32, &samples)).unwrap();
+        assert_eq!(microphone.samples, vec![0.25, -1.0]);
+
+        let microphone = FileMicrophone::from_wav("mic", &wav(1, 1, 8000, 8, &[128, 0])).unwrap();
+        assert_eq!(microphone.samples, vec![0.0, -1.0]);
+
+        assert!(matches!(
+            FileMicrophone::from_wav("mic", &wav(2, 1, 8000, 4, &[0])),
+            Err(CaptureFileError::UnsupportedWavFormat { format: 2, .. })
+        ));
+        assert!(matches!(
+            FileMicrophone::from_wav("mic", b"RIFF"),
+            Err(CaptureFileError::InvalidWav(_))
+        ));
+    }
+
+    #[test]
+    fn microphone_playback() {
+        let samples: Vec<u8> = [0i16, 1000, 2000, 3000]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        let mut backend = FileCaptureBackend::new().with_microphone(
+            FileMicrophone::from_wav("mic", &wav(1, 1, 4000, 16, &samples)).unwrap(),
+        );
+
+        let mut output = Vec::new();
+        assert!(backend.open_microphone(0, 8000));
+        backend.tick(0.5);
+        backend.poll_microphone_samples(0, &mut output);
+
+        // 0.5ms at 8kHz is 4 samples, upsampled from 2 source samples.
+        let expected: Vec<f32> = [0.0, 500.0, 1000.0, 1500.0]
+            .iter()
+            .map(|s| s / 32768.0)
+            .collect();
+        assert_eq!(output, expected);
+
+        // Nothing new is captured until time passes.
+        output.clear();
+        backend.poll_microphone_samples(0, &mut output);
+        assert!(output.is_empty());
+
+        backend.close_microphone(0);
+        backend.tick(10.0);
+        backend.poll_microphone_samples(0, &mut output);
+        assert!(output.is_empty());
+    }
+}
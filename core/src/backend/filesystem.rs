@@ -0,0 +1,431 @@
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::io::{Error, ErrorKind};
+
+/// The largest file `MemoryFileSystemBackend` will hold.
+const MAX_MEMORY_FILE_SIZE: usize = 256 * 1024 * 1024;
+
+/// One of the sandboxed directories that AIR content may access through
+/// `flash.filesystem.File`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FileSystemRoot {
+    /// `File.applicationDirectory`, which is read-only.
+    Application,
+
+    /// `File.applicationStorageDirectory`.
+    ApplicationStorage,
+
+    /// `File.documentsDirectory`.
+    Documents,
+}
+
+impl FileSystemRoot {
+    pub const ALL: [FileSystemRoot; 3] = [
+        FileSystemRoot::Application,
+        FileSystemRoot::ApplicationStorage,
+        FileSystemRoot::Documents,
+    ];
+
+    /// The name used for this root by AS code.
+    pub fn name(self) -> &'static str {
+        match self {
+            FileSystemRoot::Application => "app",
+            FileSystemRoot::ApplicationStorage => "app-storage",
+            FileSystemRoot::Documents => "documents",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|root| root.name() == name)
+    }
+
+    /// Whether content may modify files within this root.
+    pub fn is_writable(self) -> bool {
+        self != FileSystemRoot::Application
+    }
+}
+
+/// A location within the sandboxed filesystem.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SandboxPath {
+    pub root: FileSystemRoot,
+
+    /// The path relative to the root, with components separated by `/`.
+    ///
+    /// This is empty for the root itself, and never contains `.` or `..` components.
+    pub path: String,
+}
+
+impl SandboxPath {
+    pub fn new(root: FileSystemRoot, path: impl Into<String>) -> Self {
+        Self {
+            root,
+            path: path.into(),
+        }
+    }
+
+    /// The components of the path, excluding the root.
+    pub fn components(&self) -> impl Iterator<Item = &str> {
+        self.path
+            .split('/')
+            .filter(|component| !component.is_empty())
+    }
+
+    /// The location containing this one, or `None` for a root.
+    pub fn parent(&self) -> Option<SandboxPath> {
+        if self.path.is_empty() {
+            return None;
+        }
+        let parent = self.path.rsplit_once('/').map_or("", |(parent, _)| parent);
+        Some(SandboxPath::new(self.root, parent))
+    }
+
+    /// Appends a single component to this path.
+    pub fn join(&self, name: &str) -> SandboxPath {
+        if self.path.is_empty() {
+            SandboxPath::new(self.root, name)
+        } else {
+            SandboxPath::new(self.root, format!("{}/{name}", self.path))
+        }
+    }
+
+    /// Whether `other` is this location, or located anywhere inside of it.
+    pub fn contains(&self, other: &SandboxPath) -> bool {
+        self.root == other.root
+            && (self.path.is_empty()
+                || other.path == self.path
+                || other
+                    .path
+                    .strip_prefix(&self.path)
+                    .is_some_and(|rest| rest.starts_with('/')))
+    }
+}
+
+/// Resolves `.` and `..` components, returning `None` if the path escapes its root.
+pub fn normalize_path(path: &str) -> Option<String> {
+    let mut components = vec![];
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop()?;
+            }
+            component => components.push(component),
+        }
+    }
+    Some(components.join("/"))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileMetadata {
+    pub is_directory: bool,
+    pub size: u64,
+    pub creation_time: Option<DateTime<Utc>>,
+    pub modification_time: Option<DateTime<Utc>>,
+}
+
+/// Provides AIR content with access to files within a sandbox.
+///
+/// Operations which create files also create any missing parent directories.
+pub trait FileSystemBackend {
+    /// The separator between components of native paths, as shown to content
+    /// as `File.separator`.
+    fn separator(&self) -> &'static str {
+        "/"
+    }
+
+    /// The path shown to content as `File.nativePath` for a location.
+    fn native_path(&self, path: &SandboxPath) -> String;
+
+    /// Maps a path previously returned by `native_path` back to its location.
+    ///
+    /// Returns `None` if the path lies outside of the sandbox.
+    fn resolve_native_path(&self, native_path: &str) -> Option<SandboxPath>;
+
+    fn metadata(&self, path: &SandboxPath) -> Result<FileMetadata, Error>;
+
+    /// Reads the entire contents of a file.
+    fn read(&self, path: &SandboxPath) -> Result<Vec<u8>, Error>;
+
+    /// Writes data to a file at the given offset, creating the file if it
+    /// doesn't exist yet.
+    fn write(&mut self, path: &SandboxPath, offset: u64, data: &[u8]) -> Result<(), Error>;
+
+    /// Resizes a file to the given length, creating the file if it doesn't
+    /// exist yet.
+    fn truncate(&mut self, path: &SandboxPath, length: u64) -> Result<(), Error>;
+
+    fn create_directory(&mut self, path: &SandboxPath) -> Result<(), Error>;
+
+    /// Lists the names of all entries within a directory.
+    fn read_directory(&self, path: &SandboxPath) -> Result<Vec<String>, Error>;
+
+    /// Copies a file or directory (including its contents), replacing anything
+    /// already at the destination.
+    fn copy(&mut self, from: &SandboxPath, to: &SandboxPath) -> Result<(), Error>;
+
+    /// Moves a file or directory, replacing anything already at the destination.
+    fn rename(&mut self, from: &SandboxPath, to: &SandboxPath) -> Result<(), Error>;
+
+    /// Deletes a file or directory, including its contents.
+    fn delete(&mut self, path: &SandboxPath) -> Result<(), Error>;
+}
+
+enum MemoryEntry {
+    File {
+        data: Vec<u8>,
+        creation_time: DateTime<Utc>,
+        modification_time: DateTime<Utc>,
+    },
+    Directory {
+        creation_time: DateTime<Utc>,
+    },
+}
+
+/// A filesystem which only lives as long as the player, for frontends
+/// without access to a real one.
+#[derive(Default)]
+pub struct MemoryFileSystemBackend {
+    entries: BTreeMap<SandboxPath, MemoryEntry>,
+}
+
+impl MemoryFileSystemBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn is_directory(&self, path: &SandboxPath) -> bool {
+        path.path.is_empty()
+            || matches!(self.entries.get(path), Some(MemoryEntry::Directory { .. }))
+    }
+
+    fn create_parents(&mut self, path: &SandboxPath) -> Result<(), Error> {
+        match path.parent() {
+            Some(parent) => self.create_directory(&parent),
+            None => Ok(()),
+        }
+    }
+
+    fn truncate_to_at_least(&mut self, path: &SandboxPath, length: usize) -> Result<(), Error> {
+        let current = match self.entries.get(path) {
+            Some(MemoryEntry::File { data, .. }) => data.len(),
+            _ => 0,
+        };
+        self.truncate(path, current.max(length) as u64)
+    }
+
+    /// All entries located at or within the given path.
+    fn entries_within(&self, path: &SandboxPath) -> Vec<SandboxPath> {
+        self.entries
+            .keys()
+            .filter(|entry| path.contains(entry))
+            .cloned()
+            .collect()
+    }
+}
+
+impl FileSystemBackend for MemoryFileSystemBackend {
+    fn native_path(&self, path: &SandboxPath) -> String {
+        let mut native_path = format!("/{}", path.root.name());
+        for component in path.components() {
+            native_path.push('/');
+            native_path.push_str(component);
+        }
+        native_path
+    }
+
+    fn resolve_native_path(&self, native_path: &str) -> Option<SandboxPath> {
+        let native_path = native_path.strip_prefix('/')?;
+        let (root, path) = native_path.split_once('/').unwrap_or((native_path, ""));
+        Some(SandboxPath::new(
+            FileSystemRoot::from_name(root)?,
+            normalize_path(path)?,
+        ))
+    }
+
+    fn metadata(&self, path: &SandboxPath) -> Result<FileMetadata, Error> {
+        if path.path.is_empty() {
+            return Ok(FileMetadata {
+                is_directory: true,
+                size: 0,
+                creation_time: None,
+                modification_time: None,
+            });
+        }
+
+        match self.entries.get(path) {
+            Some(MemoryEntry::File {
+                data,
+                creation_time,
+                modification_time,
+            }) => Ok(FileMetadata {
+                is_directory: false,
+                size: data.len() as u64,
+                creation_time: Some(*creation_time),
+                modification_time: Some(*modification_time),
+            }),
+            Some(MemoryEntry::Directory { creation_time }) => Ok(FileMetadata {
+                is_directory: true,
+                size: 0,
+                creation_time: Some(*creation_time),
+                modification_time: Some(*creation_time),
+            }),
+            None => Err(ErrorKind::NotFound.into()),
+        }
+    }
+
+    fn read(&self, path: &SandboxPath) -> Result<Vec<u8>, Error> {
+        match self.entries.get(path) {
+            Some(MemoryEntry::File { data, .. }) => Ok(data.clone()),
+            Some(MemoryEntry::Directory { .. }) => Err(ErrorKind::IsADirectory.into()),
+            None if path.path.is_empty() => Err(ErrorKind::IsADirectory.into()),
+            None => Err(ErrorKind::NotFound.into()),
+        }
+    }
+
+    fn write(&mut self, path: &SandboxPath, offset: u64, data: &[u8]) -> Result<(), Error> {
+        let end = usize::try_from(offset)
+            .ok()
+            .and_then(|offset| offset.checked_add(data.len()))
+            .filter(|end| *end <= MAX_MEMORY_FILE_SIZE)
+            .ok_or_else(|| Error::from(ErrorKind::FileTooLarge))?;
+        self.truncate_to_at_least(path, end)?;
+        if let Some(MemoryEntry::File {
+            data: contents,
+            modification_time,
+            ..
+        }) = self.entries.get_mut(path)
+        {
+            contents[end - data.len()..end].copy_from_slice(data);
+            *modification_time = Utc::now();
+        }
+        Ok(())
+    }
+
+    fn truncate(&mut self, path: &SandboxPath, length: u64) -> Result<(), Error> {
+        if self.is_directory(path) {
+            return Err(ErrorKind::IsADirectory.into());
+        }
+        let length = usize::try_from(length)
+            .ok()
+            .filter(|length| *length <= MAX_MEMORY_FILE_SIZE)
+            .ok_or_else(|| Error::from(ErrorKind::FileTooLarge))?;
+        self.create_parents(path)?;
+
+        let now = Utc::now();
+        match self.entries.get_mut(path) {
+            Some(MemoryEntry::File {
+                data,
+                modification_time,
+                ..
+            }) => {
+                data.resize(length, 0);
+                *modification_time = now;
+            }
+            _ => {
+                self.entries.insert(
+                    path.clone(),
+                    MemoryEntry::File {
+                        data: vec![0; length],
+                        creation_time: now,
+                        modification_time: now,
+                    },
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn create_directory(&mut self, path: &SandboxPath) -> Result<(), Error> {
+        if path.path.is_empty() {
+            return Ok(());
+        }
+
+        match self.entries.get(path) {
+            Some(MemoryEntry::Directory { .. }) => Ok(()),
+            Some(MemoryEntry::File { .. }) => Err(ErrorKind::AlreadyExists.into()),
+            None => {
+                self.create_parents(path)?;
+                self.entries.insert(
+                    path.clone(),
+                    MemoryEntry::Directory {
+                        creation_time: Utc::now(),
+                    },
+                );
+                Ok(())
+            }
+        }
+    }
+
+    fn read_directory(&self, path: &SandboxPath) -> Result<Vec<String>, Error> {
+        if !self.is_directory(path) {
+            return Err(match self.entries.get(path) {
+                Some(_) => ErrorKind::NotADirectory.into(),
+                None => ErrorKind::NotFound.into(),
+            });
+        }
+
+        Ok(self
+            .entries
+            .keys()
+            .filter(|entry| entry.parent().as_ref() == Some(path))
+            .filter_map(|entry| entry.components().last().map(str::to_string))
+            .collect())
+    }
+
+    fn copy(&mut self, from: &SandboxPath, to: &SandboxPath) -> Result<(), Error> {
+        if from.contains(to) {
+            return Err(ErrorKind::InvalidInput.into());
+        }
+        let sources = self.entries_within(from);
+        if sources.is_empty() {
+            return Err(ErrorKind::NotFound.into());
+        }
+
+        if self.entries.contains_key(to) {
+            self.delete(to)?;
+        }
+        self.create_parents(to)?;
+
+        let now = Utc::now();
+        for source in sources {
+            let destination = SandboxPath::new(
+                to.root,
+                format!("{}{}", to.path, &source.path[from.path.len()..]),
+            );
+            let entry = match &self.entries[&source] {
+                MemoryEntry::File { data, .. } => MemoryEntry::File {
+                    data: data.clone(),
+                    creation_time: now,
+                    modification_time: now,
+                },
+                MemoryEntry::Directory { .. } => MemoryEntry::Directory { creation_time: now },
+            };
+            self.entries.insert(destination, entry);
+        }
+        Ok(())
+    }
+
+    fn rename(&mut self, from: &SandboxPath, to: &SandboxPath) -> Result<(), Error> {
+        if from == to {
+            return Ok(());
+        }
+        self.copy(from, to)?;
+        self.delete(from)
+    }
+
+    fn delete(&mut self, path: &SandboxPath) -> Result<(), Error> {
+        if path.path.is_empty() {
+            return Err(ErrorKind::PermissionDenied.into());
+        }
+
+        let entries = self.entries_within(path);
+        if entries.is_empty() {
+            return Err(ErrorKind::NotFound.into());
+        }
+        for entry in entries {
+            self.entries.remove(&entry);
+        }
+        Ok(())
+    }
+}
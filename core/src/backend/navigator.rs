@@ -1,5 +1,10 @@
 //! Browser-related platform functions
 
+mod udp_loopback;
+
+pub use udp_loopback::UdpLoopback;
+
+use crate::datagram_socket::{Datagram, DatagramSocketAction, DatagramSocketHandle};
 use crate::loader::Error;
 use crate::socket::{ConnectionState, SocketAction, SocketHandle};
 use crate::string::WStr;
@@ -301,6 +306,27 @@ pub trait NavigatorBackend: Any {
         receiver: Receiver<Vec<u8>>,
         sender: Sender<SocketAction>,
     );
+
+    /// Handle any UDP socket bind request
+    ///
+    /// Returns the address and port the socket was actually bound to (binding to port 0 picks
+    /// a free port), or `None` if the socket couldn't be bound.
+    ///
+    /// Datagrams received from `receiver` should be sent to their destination.
+    ///
+    /// Use [DatagramSocketAction::Data] to send received datagrams to AVM side.
+    ///
+    /// Use [DatagramSocketAction::Close] to close the socket on AVM side.
+    ///
+    /// When the Sender of the Receiver is dropped then this task should end.
+    fn bind_udp_socket(
+        &mut self,
+        address: String,
+        port: u16,
+        handle: DatagramSocketHandle,
+        receiver: Receiver<Datagram>,
+        sender: Sender<DatagramSocketAction>,
+    ) -> Option<(String, u16)>;
 }
 
 #[cfg(not(target_family = "wasm"))]
@@ -444,6 +470,17 @@ impl NavigatorBackend for NullNavigatorBackend {
             .try_send(SocketAction::Connect(handle, ConnectionState::Failed))
             .expect("working channel send");
     }
+
+    fn bind_udp_socket(
+        &mut self,
+        _address: String,
+        _port: u16,
+        _handle: DatagramSocketHandle,
+        _receiver: Receiver<Datagram>,
+        _sender: Sender<DatagramSocketAction>,
+    ) -> Option<(String, u16)> {
+        None
+    }
 }
 
 // The following functions are helper functions used in different
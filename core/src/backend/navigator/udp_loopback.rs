@@ -0,0 +1,178 @@
+use crate::datagram_socket::{Datagram, DatagramSocketAction, DatagramSocketHandle};
+use crate::loader::Error;
+use async_channel::{Receiver, Sender};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use super::OwnedFuture;
+
+/// The first port handed out when binding to port 0.
+const FIRST_EPHEMERAL_PORT: u16 = 49152;
+
+/// The address datagrams delivered through a `UdpLoopback` appear to come from.
+const LOOPBACK_ADDRESS: &str = "127.0.0.1";
+
+/// The bound sockets, by port.
+type BoundSockets = HashMap<u16, (DatagramSocketHandle, Sender<DatagramSocketAction>)>;
+
+/// An in-process UDP network, for `NavigatorBackend`s that can't or shouldn't
+/// touch the real network (e.g. tests).
+///
+/// Sockets bound to the same `UdpLoopback` can send datagrams to each other.
+/// Datagrams are routed by port only, so any destination address (including
+/// broadcast addresses) reaches the socket bound to that port. Datagrams to
+/// ports nobody is bound to are dropped, just like on a real network.
+#[derive(Clone, Default)]
+pub struct UdpLoopback(Rc<RefCell<BoundSockets>>);
+
+impl UdpLoopback {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Binds a socket to `port`, or to a free port if it is 0.
+    ///
+    /// On success, returns the bound address and port, along with a future
+    /// that delivers the datagrams sent through `receiver` until the socket is
+    /// closed.
+    pub fn bind(
+        &self,
+        address: String,
+        port: u16,
+        handle: DatagramSocketHandle,
+        receiver: Receiver<Datagram>,
+        sender: Sender<DatagramSocketAction>,
+    ) -> Option<((String, u16), OwnedFuture<(), Error>)> {
+        let mut sockets = self.0.borrow_mut();
+
+        let port = if port == 0 {
+            (FIRST_EPHEMERAL_PORT..=u16::MAX).find(|port| !sockets.contains_key(port))?
+        } else if sockets.contains_key(&port) {
+            tracing::warn!("UDP port {} is already in use", port);
+            return None;
+        } else {
+            port
+        };
+        sockets.insert(port, (handle, sender));
+
+        let network = self.clone();
+        let future = Box::pin(async move {
+            while let Ok(datagram) = receiver.recv().await {
+                let destination = network.0.borrow().get(&datagram.port).cloned();
+                if let Some((handle, sender)) = destination {
+                    let received = Datagram {
+                        address: LOOPBACK_ADDRESS.to_string(),
+                        port,
+                        data: datagram.data,
+                    };
+                    // The destination may have been closed in the meantime.
+                    let _ = sender.try_send(DatagramSocketAction::Data(handle, received));
+                }
+            }
+
+            // The socket was closed, so free up its port.
+            network.0.borrow_mut().remove(&port);
+            Ok(())
+        });
+
+        Some(((address, port), future))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::navigator::NullExecutor;
+    use async_channel::unbounded;
+    use slotmap::SlotMap;
+
+    #[test]
+    fn loopback_delivery() {
+        let mut executor = NullExecutor::new();
+        let spawner = executor.spawner();
+        let network = UdpLoopback::new();
+        let mut handles: SlotMap<DatagramSocketHandle, ()> = SlotMap::with_key();
+        let (action_sender, action_receiver) = unbounded();
+
+        let first = handles.insert(());
+        let (first_sender, first_receiver) = unbounded();
+        let ((address, first_port), future) = network
+            .bind(
+                "0.0.0.0".to_string(),
+                0,
+                first,
+                first_receiver,
+                action_sender.clone(),
+            )
+            .expect("bind to a free port");
+        spawner.spawn_local(future);
+        assert_eq!(address, "0.0.0.0");
+        assert_eq!(first_port, FIRST_EPHEMERAL_PORT);
+
+        let second = handles.insert(());
+        let (second_sender, second_receiver) = unbounded();
+        assert!(network
+            .bind(
+                "127.0.0.1".to_string(),
+                first_port,
+                second,
+                unbounded().1,
+                action_sender.clone(),
+            )
+            .is_none());
+        let ((_, second_port), future) = network
+            .bind(
+                "127.0.0.1".to_string(),
+                4000,
+                second,
+                second_receiver,
+                action_sender.clone(),
+            )
+            .expect("bind to a free port");
+        spawner.spawn_local(future);
+        assert_eq!(second_port, 4000);
+
+        first_sender
+            .try_send(Datagram {
+                address: "255.255.255.255".to_string(),
+                port: 4000,
+                data: b"hello".to_vec(),
+            })
+            .unwrap();
+        second_sender
+            .try_send(Datagram {
+                address: "127.0.0.1".to_string(),
+                port: 1234,
+                data: b"nobody".to_vec(),
+            })
+            .unwrap();
+        executor.run();
+
+        assert_eq!(
+            action_receiver.try_recv(),
+            Ok(DatagramSocketAction::Data(
+                second,
+                Datagram {
+                    address: LOOPBACK_ADDRESS.to_string(),
+                    port: first_port,
+                    data: b"hello".to_vec(),
+                }
+            ))
+        );
+        assert!(action_receiver.try_recv().is_err());
+
+        // Closing a socket frees its port.
+        drop(second_sender);
+        executor.run();
+        assert!(network
+            .bind(
+                "127.0.0.1".to_string(),
+                4000,
+                second,
+                unbounded().1,
+                action_sender,
+            )
+            .is_some());
+    }
+}
@@ -0,0 +1,107 @@
+//! Native processes started by AIR content
+
+use std::fmt;
+
+/// Identifies a process started through a `ProcessBackend`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ProcessHandle(pub u32);
+
+/// What to start, as given by `NativeProcessStartupInfo`.
+#[derive(Clone, Debug)]
+pub struct ProcessStartupInfo {
+    /// The native path of the executable.
+    pub executable: String,
+
+    pub arguments: Vec<String>,
+
+    /// The native path of the directory to start the process in.
+    pub working_directory: Option<String>,
+}
+
+/// Something that happened to a process since it was last polled.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProcessEvent {
+    StandardOutput(Vec<u8>),
+    StandardError(Vec<u8>),
+    StandardOutputClosed,
+    StandardErrorClosed,
+
+    /// The process exited, with no code if it was killed or never started.
+    ///
+    /// This is the last event of a process, and is only sent once both
+    /// of its output streams have been closed.
+    Exit(Option<i32>),
+}
+
+#[derive(Debug)]
+pub enum ProcessError {
+    /// Starting processes isn't supported, or isn't allowed by the user.
+    Unsupported,
+
+    /// The executable doesn't exist or isn't allowed to be started.
+    InvalidExecutable,
+}
+
+impl fmt::Display for ProcessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProcessError::Unsupported => write!(f, "native processes are not supported"),
+            ProcessError::InvalidExecutable => write!(f, "invalid executable"),
+        }
+    }
+}
+
+/// Starts and communicates with native processes.
+///
+/// Starting a process may need to wait for the user's permission, so a
+/// handle is returned straight away, and standard input written before the
+/// process has actually started is buffered. A process which isn't allowed to
+/// run exits without a code. The core polls running processes once per tick.
+pub trait ProcessBackend {
+    /// Whether processes can be started at all.
+    fn is_supported(&self) -> bool;
+
+    fn start(&mut self, info: ProcessStartupInfo) -> Result<ProcessHandle, ProcessError>;
+
+    /// Queues data to be written to the standard input of a process.
+    fn write_standard_input(&mut self, handle: ProcessHandle, data: &[u8]);
+
+    /// Closes the standard input of a process once all queued data is written.
+    fn close_standard_input(&mut self, handle: ProcessHandle);
+
+    /// Asks a process to exit, or kills it if `force` is set.
+    fn exit(&mut self, handle: ProcessHandle, force: bool);
+
+    /// Appends everything that happened to a process since the last call to `output`.
+    ///
+    /// After `ProcessEvent::Exit` is returned the handle is no longer valid.
+    fn poll(&mut self, handle: ProcessHandle, output: &mut Vec<ProcessEvent>);
+}
+
+/// ProcessBackend that can't start anything.
+#[derive(Default)]
+pub struct NullProcessBackend {}
+
+impl NullProcessBackend {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ProcessBackend for NullProcessBackend {
+    fn is_supported(&self) -> bool {
+        false
+    }
+
+    fn start(&mut self, _info: ProcessStartupInfo) -> Result<ProcessHandle, ProcessError> {
+        Err(ProcessError::Unsupported)
+    }
+
+    fn write_standard_input(&mut self, _handle: ProcessHandle, _data: &[u8]) {}
+
+    fn close_standard_input(&mut self, _handle: ProcessHandle) {}
+
+    fn exit(&mut self, _handle: ProcessHandle, _force: bool) {}
+
+    fn poll(&mut self, _handle: ProcessHandle, _output: &mut Vec<ProcessEvent>) {}
+}
@@ -3,7 +3,7 @@ pub use crate::loader::Error as DialogLoaderError;
 use chrono::{DateTime, Utc};
 use fluent_templates::loader::langid;
 pub use fluent_templates::LanguageIdentifier;
-use std::{any::Any, borrow::Cow};
+use std::{any::Any, borrow::Cow, time::Duration};
 use url::Url;
 
 pub type FullscreenError = Cow<'static, str>;
@@ -23,6 +23,15 @@ pub enum FontDefinition<'a> {
     },
 }
 
+/// A device font installed on the system.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeviceFontDescriptor {
+    /// The family name of the font, as it may be requested by a movie.
+    pub name: String,
+    pub is_bold: bool,
+    pub is_italic: bool,
+}
+
 /// A filter specifying a category that can be selected from a file chooser dialog
 pub struct FileFilter {
     /// The description of the category
@@ -66,6 +75,21 @@ pub trait UiBackend: Any {
     /// Changes the mouse cursor image.
     fn set_mouse_cursor(&mut self, cursor: MouseCursor);
 
+    /// Whether this backend can display custom cursors, as registered with
+    /// `register_mouse_cursor`.
+    fn supports_custom_cursors(&self) -> bool {
+        false
+    }
+
+    /// Registers a custom cursor image, which may then be displayed with
+    /// `set_mouse_cursor(MouseCursor::Custom(id))`.
+    ///
+    /// Registering a cursor with an existing ID replaces it.
+    fn register_mouse_cursor(&mut self, _id: CustomCursorId, _cursor: CustomCursor) {}
+
+    /// Forgets a custom cursor image.
+    fn unregister_mouse_cursor(&mut self, _id: CustomCursorId) {}
+
     /// Get the clipboard content
     fn clipboard_content(&mut self) -> String;
 
@@ -77,6 +101,47 @@ pub trait UiBackend: Any {
     /// Sets the clipboard to the given content.
     fn set_clipboard_content(&mut self, content: String);
 
+    /// The formats of the data currently on the clipboard.
+    ///
+    /// Backends which only support plain text don't need to implement this,
+    /// nor the other methods dealing with clipboard formats.
+    fn clipboard_formats(&mut self) -> Vec<ClipboardFormat> {
+        if self.clipboard_content().is_empty() {
+            vec![]
+        } else {
+            vec![ClipboardFormat::Text]
+        }
+    }
+
+    /// Reads the data in the given format from the clipboard, if any.
+    fn clipboard_data(&mut self, format: ClipboardFormat) -> Option<ClipboardData> {
+        if format != ClipboardFormat::Text {
+            return None;
+        }
+        let text = self.clipboard_content();
+        (!text.is_empty()).then_some(ClipboardData::Text(text))
+    }
+
+    /// Places data on the clipboard, replacing any data in the same format
+    /// but keeping data in other formats.
+    fn set_clipboard_data(&mut self, data: ClipboardData) {
+        if let ClipboardData::Text(text) = data {
+            self.set_clipboard_content(text);
+        }
+    }
+
+    /// Removes the data in the given format from the clipboard.
+    fn clear_clipboard_format(&mut self, format: ClipboardFormat) {
+        if format == ClipboardFormat::Text {
+            self.set_clipboard_content(String::new());
+        }
+    }
+
+    /// Removes all data from the clipboard.
+    fn clear_clipboard(&mut self) {
+        self.set_clipboard_content(String::new());
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError>;
 
     /// Displays a message about an error during root movie download.
@@ -109,6 +174,14 @@ pub trait UiBackend: Any {
         register: &mut dyn FnMut(FontDefinition),
     );
 
+    /// Lists the device fonts installed on the system, such as for `Font.enumerateFonts(true)`.
+    ///
+    /// The fonts themselves are only loaded once requested through `load_device_font`.
+    /// Backends that cannot discover fonts return an empty list.
+    fn device_fonts(&self) -> Vec<DeviceFontDescriptor> {
+        Vec::new()
+    }
+
     /// Displays a file selection dialog, returning None if the dialog cannot be displayed
     /// (e.g because it is already open)
     /// * `filters` represents a list of filters to the possible file types that can be selected
@@ -126,6 +199,287 @@ pub trait UiBackend: Any {
 
     /// Mark that any previously open dialog has been closed
     fn close_file_dialog(&mut self);
+
+    /// The bounds of the window the player is displayed in, in screen pixels.
+    ///
+    /// Returns `None` if the player isn't displayed in a window which can be managed,
+    /// in which case AIR's `NativeWindow` falls back to the size of the stage.
+    fn window_bounds(&self) -> Option<WindowBounds> {
+        None
+    }
+
+    /// Moves and resizes the window the player is displayed in.
+    fn set_window_bounds(&mut self, _bounds: WindowBounds) {}
+
+    fn window_display_state(&self) -> WindowDisplayState {
+        WindowDisplayState::Normal
+    }
+
+    /// Minimizes, maximizes or restores the window the player is displayed in.
+    fn set_window_display_state(&mut self, _state: WindowDisplayState) {}
+
+    /// Starts a move of the window driven by the mouse, as used by windows with custom chrome.
+    ///
+    /// Returns `false` if the window can't be moved this way.
+    fn start_window_move(&mut self) -> bool {
+        false
+    }
+
+    /// Starts a resize of the window from the given edge or corner, driven by the mouse.
+    ///
+    /// Returns `false` if the window can't be resized this way.
+    fn start_window_resize(&mut self, _edge: WindowEdge) -> bool {
+        false
+    }
+
+    /// Requests that the window the player is displayed in be closed.
+    fn close_window(&mut self) {}
+}
+
+/// A format of data on the clipboard, as used by AIR's `ClipboardFormats`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ClipboardFormat {
+    Text,
+    Html,
+    RichText,
+    Url,
+    FileList,
+    Bitmap,
+
+    /// Text copied from a text field along with its formatting.
+    FlashRichText,
+}
+
+impl ClipboardFormat {
+    pub const ALL: [ClipboardFormat; 7] = [
+        ClipboardFormat::Text,
+        ClipboardFormat::Html,
+        ClipboardFormat::RichText,
+        ClipboardFormat::Url,
+        ClipboardFormat::FileList,
+        ClipboardFormat::Bitmap,
+        ClipboardFormat::FlashRichText,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ClipboardFormat::Text => "air:text",
+            ClipboardFormat::Html => "air:html",
+            ClipboardFormat::RichText => "air:rtf",
+            ClipboardFormat::Url => "air:url",
+            ClipboardFormat::FileList => "air:file list",
+            ClipboardFormat::Bitmap => "air:bitmap",
+            ClipboardFormat::FlashRichText => "flash:rich text",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.name() == name)
+    }
+}
+
+/// Data on the clipboard.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClipboardData {
+    Text(String),
+    Html(String),
+
+    /// A document in Rich Text Format.
+    RichText(Vec<u8>),
+
+    Url(String),
+
+    /// The native paths of a list of files.
+    FileList(Vec<String>),
+
+    /// An image, as rows of non-premultiplied RGBA pixels.
+    Bitmap {
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    },
+
+    /// A Flash XML fragment, as produced by text fields using `useRichTextClipboard`.
+    FlashRichText(String),
+}
+
+impl ClipboardData {
+    pub fn format(&self) -> ClipboardFormat {
+        match self {
+            ClipboardData::Text(_) => ClipboardFormat::Text,
+            ClipboardData::Html(_) => ClipboardFormat::Html,
+            ClipboardData::RichText(_) => ClipboardFormat::RichText,
+            ClipboardData::Url(_) => ClipboardFormat::Url,
+            ClipboardData::FileList(_) => ClipboardFormat::FileList,
+            ClipboardData::Bitmap { .. } => ClipboardFormat::Bitmap,
+            ClipboardData::FlashRichText(_) => ClipboardFormat::FlashRichText,
+        }
+    }
+}
+
+/// A clipboard which keeps its data in memory, for backends without access to
+/// a system clipboard, or as a cache of formats that the system clipboard can't hold.
+#[derive(Default)]
+pub struct MemoryClipboard {
+    data: Vec<ClipboardData>,
+}
+
+impl MemoryClipboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The plain text on the clipboard, or an empty string if there is none.
+    pub fn text(&self) -> String {
+        match self.get(ClipboardFormat::Text) {
+            Some(ClipboardData::Text(text)) => text.clone(),
+            _ => String::new(),
+        }
+    }
+
+    pub fn formats(&self) -> Vec<ClipboardFormat> {
+        self.data.iter().map(ClipboardData::format).collect()
+    }
+
+    pub fn get(&self, format: ClipboardFormat) -> Option<&ClipboardData> {
+        self.data.iter().find(|data| data.format() == format)
+    }
+
+    pub fn set(&mut self, data: ClipboardData) {
+        self.remove(data.format());
+        self.data.push(data);
+    }
+
+    pub fn remove(&mut self, format: ClipboardFormat) {
+        self.data.retain(|data| data.format() != format);
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+}
+
+/// The position and size of a window, in screen pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WindowBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// The display state of a window, as used by AIR's `NativeWindowDisplayState`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowDisplayState {
+    Normal,
+    Maximized,
+    Minimized,
+}
+
+impl WindowDisplayState {
+    pub fn name(self) -> &'static str {
+        match self {
+            WindowDisplayState::Normal => "normal",
+            WindowDisplayState::Maximized => "maximized",
+            WindowDisplayState::Minimized => "minimized",
+        }
+    }
+}
+
+/// An edge or corner of a window, as used by AIR's `NativeWindowResize`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl WindowEdge {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "T" => WindowEdge::Top,
+            "B" => WindowEdge::Bottom,
+            "L" => WindowEdge::Left,
+            "R" => WindowEdge::Right,
+            "TL" => WindowEdge::TopLeft,
+            "TR" => WindowEdge::TopRight,
+            "BL" => WindowEdge::BottomLeft,
+            "BR" => WindowEdge::BottomRight,
+            _ => return None,
+        })
+    }
+}
+
+/// A simulated window, for backends which don't display the player in a real window.
+///
+/// Changes are applied immediately: maximizing fills the screen, and restoring
+/// brings back the bounds from before the window was maximized.
+pub struct HeadlessWindow {
+    bounds: WindowBounds,
+    restored_bounds: WindowBounds,
+    screen_size: (u32, u32),
+    display_state: WindowDisplayState,
+}
+
+impl HeadlessWindow {
+    pub fn new(bounds: WindowBounds, screen_size: (u32, u32)) -> Self {
+        Self {
+            bounds,
+            restored_bounds: bounds,
+            screen_size,
+            display_state: WindowDisplayState::Normal,
+        }
+    }
+
+    pub fn bounds(&self) -> WindowBounds {
+        self.bounds
+    }
+
+    pub fn set_bounds(&mut self, bounds: WindowBounds) {
+        self.bounds = bounds;
+        self.restored_bounds = bounds;
+        self.display_state = WindowDisplayState::Normal;
+    }
+
+    pub fn display_state(&self) -> WindowDisplayState {
+        self.display_state
+    }
+
+    pub fn set_display_state(&mut self, state: WindowDisplayState) {
+        if self.display_state == WindowDisplayState::Normal {
+            self.restored_bounds = self.bounds;
+        }
+        self.bounds = match state {
+            WindowDisplayState::Maximized => WindowBounds {
+                x: 0,
+                y: 0,
+                width: self.screen_size.0,
+                height: self.screen_size.1,
+            },
+            WindowDisplayState::Normal | WindowDisplayState::Minimized => self.restored_bounds,
+        };
+        self.display_state = state;
+    }
+}
+
+impl Default for HeadlessWindow {
+    fn default() -> Self {
+        // The default size of a Flash stage, on a 1080p screen.
+        Self::new(
+            WindowBounds {
+                x: 0,
+                y: 0,
+                width: 550,
+                height: 400,
+            },
+            (1920, 1080),
+        )
+    }
 }
 
 /// A mouse cursor icon displayed by the Flash Player.
@@ -147,6 +501,59 @@ pub enum MouseCursor {
     /// The grabby-dragging hand icon.
     /// Equivalent to AS3 `MouseCursor.HAND`.
     Grab,
+
+    /// A custom cursor registered with `UiBackend::register_mouse_cursor`.
+    /// Equivalent to a cursor name passed to AS3 `Mouse.registerCursor`.
+    Custom(CustomCursorId),
+}
+
+/// An identifier of a custom cursor registered with a `UiBackend`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CustomCursorId(pub u32);
+
+/// A custom cursor image, which may be animated.
+/// Corresponds to AS3 `MouseCursorData`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomCursor {
+    /// The width of every frame, in pixels.
+    pub width: u32,
+
+    /// The height of every frame, in pixels.
+    pub height: u32,
+
+    /// The frames of the cursor, as non-premultiplied RGBA pixel data.
+    pub frames: Vec<Vec<u8>>,
+
+    /// The position of the pointer within the image, in pixels.
+    pub hotspot: (u32, u32),
+
+    /// The number of frames shown per second, for animated cursors.
+    pub frame_rate: f64,
+}
+
+impl CustomCursor {
+    /// The index of the frame to show once the cursor has been displayed for
+    /// `elapsed` time.
+    pub fn frame_at(&self, elapsed: Duration) -> usize {
+        if self.frames.len() <= 1 || self.frame_rate <= 0.0 {
+            return 0;
+        }
+
+        let frame = (elapsed.as_secs_f64() * self.frame_rate) as usize;
+        frame % self.frames.len()
+    }
+
+    /// How long each frame of an animated cursor is shown, or `None` if the
+    /// cursor is not animated.
+    ///
+    /// A frame rate so low that a frame would outlast any `Duration` doesn't animate.
+    pub fn frame_duration(&self) -> Option<Duration> {
+        if self.frames.len() <= 1 || self.frame_rate <= 0.0 {
+            return None;
+        }
+
+        Duration::try_from_secs_f64(1.0 / self.frame_rate).ok()
+    }
 }
 
 /// UiBackend that does nothing.
@@ -0,0 +1,687 @@
+//! Camera and Microphone implementation
+//!
+//! Devices are provided by the `CaptureBackend`; this module keeps track of
+//! the devices a movie has requested and turns captured data into frames and
+//! events.
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::object::{ByteArrayObject, EventObject};
+use crate::avm2::{Activation as Avm2Activation, Avm2, Object as Avm2Object};
+use crate::backend::capture::CameraMode;
+use crate::context::UpdateContext;
+use gc_arena::barrier::unlock;
+use gc_arena::{Collect, Gc, Lock};
+use ruffle_macros::istr;
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapInfo, PixelRegion};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt;
+
+/// The sample rates a `Microphone` can capture at, in kHz, along with the
+/// corresponding rate in Hz.
+const MICROPHONE_RATES: [(u32, u32); 6] = [
+    (5, 5512),
+    (8, 8000),
+    (11, 11025),
+    (16, 16000),
+    (22, 22050),
+    (44, 44100),
+];
+
+/// All cameras and microphones that have been requested by the movie.
+///
+/// Each device is only ever represented by a single `Camera` or `Microphone`,
+/// which is shared by every `getCamera` or `getMicrophone` call for it.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct CaptureDevices<'gc> {
+    cameras: Vec<Camera<'gc>>,
+    microphones: Vec<Microphone<'gc>>,
+}
+
+impl<'gc> CaptureDevices<'gc> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the camera at `index`, opening it if necessary.
+    ///
+    /// Returns `None` if there is no such camera.
+    pub fn camera(context: &mut UpdateContext<'gc>, index: usize) -> Option<Camera<'gc>> {
+        if let Some(camera) = context
+            .capture_devices
+            .cameras
+            .iter()
+            .find(|camera| camera.index() == index)
+        {
+            return Some(*camera);
+        }
+
+        let name = context.capture.camera_names().into_iter().nth(index)?;
+        let camera = Camera(Gc::new(
+            context.gc(),
+            CameraData {
+                index,
+                name,
+                avm2_object: Lock::new(None),
+                requested_mode: Cell::new(CameraMode::default()),
+                mode: Cell::new(None),
+                frame: RefCell::new(None),
+                frame_bitmap: RefCell::new(None),
+                activity_level: Cell::new(-1.0),
+                motion_level: Cell::new(50),
+                motion_timeout: Cell::new(2000),
+                active: Cell::new(false),
+                last_motion: Cell::new(None),
+                time: Cell::new(0.0),
+                frame_times: RefCell::new(VecDeque::new()),
+            },
+        ));
+        camera.open(context);
+        context.capture_devices.cameras.push(camera);
+        Some(camera)
+    }
+
+    /// Returns the microphone at `index`, opening it if necessary.
+    ///
+    /// Returns `None` if there is no such microphone.
+    pub fn microphone(context: &mut UpdateContext<'gc>, index: usize) -> Option<Microphone<'gc>> {
+        if let Some(microphone) = context
+            .capture_devices
+            .microphones
+            .iter()
+            .find(|microphone| microphone.index() == index)
+        {
+            return Some(*microphone);
+        }
+
+        let name = context.capture.microphone_names().into_iter().nth(index)?;
+        let microphone = Microphone(Gc::new(
+            context.gc(),
+            MicrophoneData {
+                index,
+                name,
+                avm2_object: Lock::new(None),
+                rate: Cell::new(8),
+                open: Cell::new(false),
+                gain: Cell::new(50.0),
+                silence_level: Cell::new(10.0),
+                silence_timeout: Cell::new(2000),
+                activity_level: Cell::new(-1.0),
+                active: Cell::new(false),
+                last_activity: Cell::new(None),
+                time: Cell::new(0.0),
+                position: Cell::new(0.0),
+            },
+        ));
+        microphone.open(context);
+        context.capture_devices.microphones.push(microphone);
+        Some(microphone)
+    }
+
+    /// Finds the camera that belongs to an AVM2 `Camera` object.
+    pub fn camera_for_object(&self, object: Avm2Object<'gc>) -> Option<Camera<'gc>> {
+        self.cameras.iter().copied().find(|camera| {
+            camera
+                .avm2_object()
+                .is_some_and(|o| Avm2Object::ptr_eq(o, object))
+        })
+    }
+
+    /// Finds the microphone that belongs to an AVM2 `Microphone` object.
+    pub fn microphone_for_object(&self, object: Avm2Object<'gc>) -> Option<Microphone<'gc>> {
+        self.microphones.iter().copied().find(|microphone| {
+            microphone
+                .avm2_object()
+                .is_some_and(|o| Avm2Object::ptr_eq(o, object))
+        })
+    }
+
+    /// Process all open devices.
+    ///
+    /// `dt` is the number of milliseconds elapsed since the last tick.
+    pub fn tick(context: &mut UpdateContext<'gc>, dt: f64) {
+        if context.capture_devices.cameras.is_empty()
+            && context.capture_devices.microphones.is_empty()
+        {
+            return;
+        }
+
+        context.capture.tick(dt);
+
+        let cameras = context.capture_devices.cameras.clone();
+        for camera in cameras {
+            camera.tick(context, dt);
+        }
+
+        let microphones = context.capture_devices.microphones.clone();
+        for microphone in microphones {
+            microphone.tick(context, dt);
+        }
+    }
+}
+
+/// A camera, corresponding to the AVM2 `Camera` class.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct Camera<'gc>(Gc<'gc, CameraData<'gc>>);
+
+impl fmt::Debug for Camera<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Camera")
+            .field("ptr", &Gc::as_ptr(self.0))
+            .field("name", &self.0.name)
+            .finish()
+    }
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct CameraData<'gc> {
+    #[collect(require_static)]
+    index: usize,
+
+    #[collect(require_static)]
+    name: String,
+
+    avm2_object: Lock<Option<Avm2Object<'gc>>>,
+
+    /// The mode requested with `setMode`.
+    #[collect(require_static)]
+    requested_mode: Cell<CameraMode>,
+
+    /// The mode the backend is actually capturing in, if the camera is open.
+    #[collect(require_static)]
+    mode: Cell<Option<CameraMode>>,
+
+    /// The latest captured frame, in RGBA.
+    #[collect(require_static)]
+    frame: RefCell<Option<Bitmap>>,
+
+    /// The latest captured frame, as registered with the renderer.
+    ///
+    /// Any `Video`s this camera is attached to display this bitmap.
+    #[collect(require_static)]
+    frame_bitmap: RefCell<Option<BitmapInfo>>,
+
+    /// The amount of motion in the latest frame, from 0 to 100, or -1 if
+    /// nothing was captured yet.
+    activity_level: Cell<f64>,
+
+    motion_level: Cell<i32>,
+
+    /// The time in milliseconds without motion after which the camera is
+    /// considered inactive.
+    motion_timeout: Cell<i32>,
+
+    /// Whether motion was detected within the motion timeout.
+    active: Cell<bool>,
+
+    /// The value of `time` when motion was last detected.
+    last_motion: Cell<Option<f64>>,
+
+    /// The number of milliseconds this camera has been ticked for.
+    time: Cell<f64>,
+
+    /// The value of `time` for every frame captured within the last second.
+    #[collect(require_static)]
+    frame_times: RefCell<VecDeque<f64>>,
+}
+
+impl<'gc> Camera<'gc> {
+    pub fn index(self) -> usize {
+        self.0.index
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    pub fn avm2_object(self) -> Option<Avm2Object<'gc>> {
+        self.0.avm2_object.get()
+    }
+
+    pub fn set_avm2_object(self, context: &mut UpdateContext<'gc>, object: Avm2Object<'gc>) {
+        unlock!(Gc::write(context.gc(), self.0), CameraData, avm2_object).set(Some(object));
+    }
+
+    /// The mode the camera captures in, or the requested mode if it isn't
+    /// capturing.
+    pub fn mode(self) -> CameraMode {
+        self.0.mode.get().unwrap_or(self.0.requested_mode.get())
+    }
+
+    /// Whether the user (or backend) denied access to the camera.
+    pub fn is_muted(self) -> bool {
+        self.0.mode.get().is_none()
+    }
+
+    pub fn set_mode(self, context: &mut UpdateContext<'gc>, mode: CameraMode) {
+        self.0.requested_mode.set(mode);
+        self.open(context);
+    }
+
+    fn open(self, context: &mut UpdateContext<'gc>) {
+        let mode = context
+            .capture
+            .open_camera(self.0.index, self.0.requested_mode.get());
+        self.0.mode.set(mode);
+    }
+
+    pub fn activity_level(self) -> f64 {
+        self.0.activity_level.get()
+    }
+
+    pub fn motion_level(self) -> i32 {
+        self.0.motion_level.get()
+    }
+
+    pub fn motion_timeout(self) -> i32 {
+        self.0.motion_timeout.get()
+    }
+
+    pub fn set_motion_level(self, motion_level: i32, motion_timeout: i32) {
+        self.0.motion_level.set(motion_level.clamp(0, 100));
+        self.0.motion_timeout.set(motion_timeout.max(0));
+    }
+
+    /// The number of frames captured within the last second.
+    pub fn current_fps(self) -> f64 {
+        self.0.frame_times.borrow().len() as f64
+    }
+
+    /// The latest captured frame, in RGBA.
+    pub fn frame(&self) -> std::cell::Ref<'_, Option<Bitmap>> {
+        self.0.frame.borrow()
+    }
+
+    /// The latest captured frame, as registered with the renderer.
+    pub fn frame_bitmap(self) -> Option<BitmapInfo> {
+        self.0.frame_bitmap.borrow().clone()
+    }
+
+    fn tick(self, context: &mut UpdateContext<'gc>, dt: f64) {
+        if self.is_muted() {
+            return;
+        }
+
+        let time = self.0.time.get() + dt;
+        self.0.time.set(time);
+
+        {
+            let mut frame_times = self.0.frame_times.borrow_mut();
+            while frame_times.front().is_some_and(|&t| time - t >= 1000.0) {
+                frame_times.pop_front();
+            }
+        }
+
+        if let Some(frame) = context.capture.poll_camera_frame(self.0.index) {
+            let frame = frame.to_rgba();
+            self.0.frame_times.borrow_mut().push_back(time);
+
+            let activity_level = self
+                .0
+                .frame
+                .borrow()
+                .as_ref()
+                .map(|previous| motion_between(previous, &frame))
+                .unwrap_or(0.0);
+            self.0.activity_level.set(activity_level);
+            if activity_level >= self.0.motion_level.get() as f64 {
+                self.0.last_motion.set(Some(time));
+            }
+
+            self.upload_frame(context, &frame);
+            self.0.frame.replace(Some(frame));
+            *context.needs_render = true;
+
+            if let Some(object) = self.avm2_object() {
+                let event = EventObject::bare_default_event(context, "videoFrame");
+                Avm2::dispatch_event(context, event, object);
+            }
+        }
+
+        let active = self
+            .0
+            .last_motion
+            .get()
+            .is_some_and(|last| time - last < self.0.motion_timeout.get() as f64);
+        if active != self.0.active.get() {
+            self.0.active.set(active);
+            if let Some(object) = self.avm2_object() {
+                dispatch_activity_event(context, object, active);
+            }
+        }
+    }
+
+    fn upload_frame(self, context: &mut UpdateContext<'gc>, frame: &Bitmap) {
+        let mut frame_bitmap = self.0.frame_bitmap.borrow_mut();
+        let result = match &*frame_bitmap {
+            Some(info)
+                if info.width as u32 == frame.width() && info.height as u32 == frame.height() =>
+            {
+                context.renderer.update_texture(
+                    &info.handle,
+                    frame.clone(),
+                    PixelRegion::for_whole_size(frame.width(), frame.height()),
+                )
+            }
+            _ => context
+                .renderer
+                .register_bitmap(frame.clone())
+                .map(|handle| {
+                    *frame_bitmap = Some(BitmapInfo {
+                        handle,
+                        width: frame.width() as u16,
+                        height: frame.height() as u16,
+                    });
+                }),
+        };
+
+        if let Err(e) = result {
+            tracing::error!("Uploading camera frame failed: {}", e);
+        }
+    }
+}
+
+/// A microphone, corresponding to the AVM2 `Microphone` class.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct Microphone<'gc>(Gc<'gc, MicrophoneData<'gc>>);
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct MicrophoneData<'gc> {
+    #[collect(require_static)]
+    index: usize,
+
+    #[collect(require_static)]
+    name: String,
+
+    avm2_object: Lock<Option<Avm2Object<'gc>>>,
+
+    /// The capture rate in kHz, as exposed by `Microphone.rate`.
+    rate: Cell<u32>,
+
+    /// Whether the backend is capturing from this microphone.
+    open: Cell<bool>,
+
+    /// The amount by which the signal is boosted, from 0 to 100.
+    ///
+    /// 50 leaves the signal unchanged.
+    gain: Cell<f64>,
+
+    silence_level: Cell<f64>,
+
+    /// The time in milliseconds below the silence level after which the
+    /// microphone is considered inactive.
+    silence_timeout: Cell<i32>,
+
+    /// The loudness of the latest captured samples, from 0 to 100, or -1 if
+    /// nothing was captured yet.
+    activity_level: Cell<f64>,
+
+    /// Whether sound was detected within the silence timeout.
+    active: Cell<bool>,
+
+    /// The value of `time` when sound was last detected.
+    last_activity: Cell<Option<f64>>,
+
+    /// The number of milliseconds this microphone has been ticked for.
+    time: Cell<f64>,
+
+    /// The number of samples delivered in `SampleDataEvent`s so far.
+    position: Cell<f64>,
+}
+
+impl<'gc> Microphone<'gc> {
+    pub fn index(self) -> usize {
+        self.0.index
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    pub fn avm2_object(self) -> Option<Avm2Object<'gc>> {
+        self.0.avm2_object.get()
+    }
+
+    pub fn set_avm2_object(self, context: &mut UpdateContext<'gc>, object: Avm2Object<'gc>) {
+        unlock!(Gc::write(context.gc(), self.0), MicrophoneData, avm2_object).set(Some(object));
+    }
+
+    /// Whether the user (or backend) denied access to the microphone.
+    pub fn is_muted(self) -> bool {
+        !self.0.open.get()
+    }
+
+    /// The capture rate in kHz.
+    pub fn rate(self) -> u32 {
+        self.0.rate.get()
+    }
+
+    /// Sets the capture rate in kHz, rounding to the closest supported rate.
+    pub fn set_rate(self, context: &mut UpdateContext<'gc>, rate: u32) {
+        let (rate, _) = MICROPHONE_RATES
+            .iter()
+            .copied()
+            .min_by_key(|(khz, _)| khz.abs_diff(rate))
+            .expect("MICROPHONE_RATES is not empty");
+        if rate != self.0.rate.get() {
+            self.0.rate.set(rate);
+            self.open(context);
+        }
+    }
+
+    fn open(self, context: &mut UpdateContext<'gc>) {
+        let rate = self.0.rate.get();
+        let (_, hz) = MICROPHONE_RATES
+            .iter()
+            .copied()
+            .find(|&(khz, _)| khz == rate)
+            .unwrap_or((8, 8000));
+        let open = context.capture.open_microphone(self.0.index, hz);
+        self.0.open.set(open);
+    }
+
+    pub fn gain(self) -> f64 {
+        self.0.gain.get()
+    }
+
+    pub fn set_gain(self, gain: f64) {
+        self.0.gain.set(gain.clamp(0.0, 100.0));
+    }
+
+    pub fn silence_level(self) -> f64 {
+        self.0.silence_level.get()
+    }
+
+    pub fn silence_timeout(self) -> i32 {
+        self.0.silence_timeout.get()
+    }
+
+    pub fn set_silence_level(self, silence_level: f64, silence_timeout: Option<i32>) {
+        self.0.silence_level.set(silence_level.clamp(0.0, 100.0));
+        if let Some(silence_timeout) = silence_timeout {
+            self.0.silence_timeout.set(silence_timeout.max(0));
+        }
+    }
+
+    pub fn activity_level(self) -> f64 {
+        self.0.activity_level.get()
+    }
+
+    fn tick(self, context: &mut UpdateContext<'gc>, dt: f64) {
+        if self.is_muted() {
+            return;
+        }
+
+        let time = self.0.time.get() + dt;
+        self.0.time.set(time);
+
+        let mut samples = Vec::new();
+        context
+            .capture
+            .poll_microphone_samples(self.0.index, &mut samples);
+
+        if !samples.is_empty() {
+            let gain = self.0.gain.get() as f32 / 50.0;
+            for sample in &mut samples {
+                *sample = (*sample * gain).clamp(-1.0, 1.0);
+            }
+
+            let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+            let activity_level = (rms as f64 * 100.0).min(100.0);
+            self.0.activity_level.set(activity_level);
+            if activity_level >= self.0.silence_level.get() {
+                self.0.last_activity.set(Some(time));
+            }
+
+            if let Some(object) = self.avm2_object() {
+                self.dispatch_sample_data(context, object, &samples);
+            }
+        }
+
+        let active = self
+            .0
+            .last_activity
+            .get()
+            .is_some_and(|last| time - last < self.0.silence_timeout.get() as f64);
+        if active != self.0.active.get() {
+            self.0.active.set(active);
+            if let Some(object) = self.avm2_object() {
+                dispatch_activity_event(context, object, active);
+            }
+        }
+    }
+
+    fn dispatch_sample_data(
+        self,
+        context: &mut UpdateContext<'gc>,
+        object: Avm2Object<'gc>,
+        samples: &[f32],
+    ) {
+        let position = self.0.position.get();
+        self.0.position.set(position + samples.len() as f64);
+
+        let mut storage = ByteArrayStorage::new();
+        for &sample in samples {
+            storage
+                .write_float(sample)
+                .expect("Writing to a growable ByteArray can't fail");
+        }
+        storage.set_position(0);
+
+        let mut activation = Avm2Activation::from_nothing(context);
+        let event = match ByteArrayObject::from_storage(&mut activation, storage) {
+            Ok(data) => {
+                let class = activation.avm2().classes().sampledataevent;
+                EventObject::from_class_and_args(
+                    &mut activation,
+                    class,
+                    &[
+                        istr!("sampleData").into(),
+                        false.into(),
+                        false.into(),
+                        position.into(),
+                        data.into(),
+                    ],
+                )
+            }
+            Err(e) => {
+                tracing::error!("Couldn't create microphone sample data: {:?}", e);
+                return;
+            }
+        };
+
+        Avm2::dispatch_event(activation.context, event, object);
+    }
+}
+
+fn dispatch_activity_event<'gc>(
+    context: &mut UpdateContext<'gc>,
+    object: Avm2Object<'gc>,
+    activating: bool,
+) {
+    let mut activation = Avm2Activation::from_nothing(context);
+    let class = activation.avm2().classes().activityevent;
+    let event = EventObject::from_class_and_args(
+        &mut activation,
+        class,
+        &[
+            istr!("activity").into(),
+            false.into(),
+            false.into(),
+            activating.into(),
+        ],
+    );
+    Avm2::dispatch_event(activation.context, event, object);
+}
+
+/// Estimates the amount of motion between two RGBA frames, from 0 to 100.
+///
+/// This is the mean difference in luminance, sampling every fourth pixel.
+fn motion_between(previous: &Bitmap, current: &Bitmap) -> f64 {
+    if previous.width() != current.width()
+        || previous.height() != current.height()
+        || current.format() != BitmapFormat::Rgba
+        || previous.format() != BitmapFormat::Rgba
+    {
+        return 100.0;
+    }
+
+    fn luma(pixel: &[u8]) -> i32 {
+        (pixel[0] as i32 * 77 + pixel[1] as i32 * 150 + pixel[2] as i32 * 29) >> 8
+    }
+
+    let mut total = 0u64;
+    let mut count = 0u64;
+    for (a, b) in previous
+        .data()
+        .chunks_exact(16)
+        .zip(current.data().chunks_exact(16))
+    {
+        total += luma(a).abs_diff(luma(b)) as u64;
+        count += 1;
+    }
+
+    if count == 0 {
+        return 0.0;
+    }
+
+    // A mean difference of a quarter of the luminance range is considered
+    // full motion.
+    (total as f64 / count as f64 * 100.0 / 64.0).min(100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32, rgba: [u8; 4]) -> Bitmap {
+        let data = rgba.repeat((width * height) as usize);
+        Bitmap::new(width, height, BitmapFormat::Rgba, data)
+    }
+
+    #[test]
+    fn motion_between_frames() {
+        let red = solid(4, 4, [255, 0, 0, 255]);
+        let blue = solid(4, 4, [0, 0, 255, 255]);
+        let white = solid(4, 4, [255, 255, 255, 255]);
+        let black = solid(4, 4, [0, 0, 0, 255]);
+
+        assert_eq!(0.0, motion_between(&red, &red));
+        assert_eq!(75.0, motion_between(&red, &blue));
+        assert_eq!(75.0, motion_between(&blue, &red));
+        assert_eq!(100.0, motion_between(&black, &white));
+    }
+
+    #[test]
+    fn motion_between_mismatched_frames() {
+        let small = solid(2, 2, [0, 0, 0, 255]);
+        let large = solid(4, 4, [0, 0, 0, 255]);
+
+        assert_eq!(100.0, motion_between(&small, &large));
+    }
+}
@@ -1,5 +1,6 @@
 //! Contexts and helper types passed between functions.
 
+use crate::accessibility::AccessibilityTracker;
 use crate::avm1::Activation;
 use crate::avm1::ActivationIdentifier;
 use crate::avm1::Attribute;
@@ -11,26 +12,36 @@ use crate::avm2::Activation as Avm2Activation;
 use crate::avm2::TObject as _;
 use crate::avm2::{Avm2, Object as Avm2Object, SoundChannelObject};
 use crate::backend::{
+    accessibility::AccessibilityBackend,
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
+    capture::CaptureBackend,
+    filesystem::FileSystemBackend,
     log::LogBackend,
     navigator::NavigatorBackend,
+    process::ProcessBackend,
     storage::StorageBackend,
     ui::UiBackend,
 };
+use crate::capture::CaptureDevices;
 use crate::context_menu::ContextMenuState;
+use crate::datagram_socket::DatagramSockets;
 use crate::display_object::{EditText, MovieClip, SoundTransform, Stage};
 use crate::events::PlayerNotification;
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
 use crate::frame_lifecycle::FramePhase;
+use crate::ime::Ime;
 use crate::input::InputManager;
 use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::local_connection::LocalConnections;
+use crate::native_application::NativeApplication;
+use crate::native_process::NativeProcesses;
 use crate::net_connection::NetConnections;
 use crate::player::PostFrameCallback;
 use crate::player::{MouseData, Player};
 use crate::prelude::*;
+use crate::sandbox::SecuritySandbox;
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::HasStringContext;
@@ -116,6 +127,24 @@ pub struct UpdateContext<'gc> {
     /// The video backend, used for video decoding
     pub video: &'gc mut dyn VideoBackend,
 
+    /// The capture backend, used to access cameras and microphones.
+    pub capture: &'gc mut dyn CaptureBackend,
+
+    /// The filesystem backend, used by AIR content to access sandboxed files.
+    pub filesystem: &'gc mut dyn FileSystemBackend,
+
+    /// The process backend, used by AIR content to start native processes.
+    pub process: &'gc mut dyn ProcessBackend,
+
+    /// The accessibility backend, used to expose the movie to assistive technologies.
+    pub accessibility: &'gc mut dyn AccessibilityBackend,
+
+    /// Keeps track of what the accessibility tree was built from.
+    pub accessibility_tracker: &'gc mut AccessibilityTracker,
+
+    /// The security sandbox, deciding what movies may access across domains.
+    pub security_sandbox: &'gc mut SecuritySandbox,
+
     /// The RNG, used by the AVM `RandomNumber` opcode, `Math.random(),` and `random()`.
     pub rng: &'gc mut SmallRng,
 
@@ -212,8 +241,22 @@ pub struct UpdateContext<'gc> {
     /// Manager of in-progress media streams.
     pub stream_manager: &'gc mut StreamManager<'gc>,
 
+    /// Cameras and microphones requested by the movie.
+    pub capture_devices: &'gc mut CaptureDevices<'gc>,
+
+    /// The lifecycle state of an AIR application.
+    pub native_application: &'gc mut NativeApplication<'gc>,
+
+    /// The input method state of the movie.
+    pub ime: &'gc mut Ime<'gc>,
+
+    /// Native processes started by the movie.
+    pub native_processes: &'gc mut NativeProcesses<'gc>,
+
     pub sockets: &'gc mut Sockets<'gc>,
 
+    pub datagram_sockets: &'gc mut DatagramSockets<'gc>,
+
     /// List of active NetConnection instances.
     pub net_connections: &'gc mut NetConnections<'gc>,
 
@@ -442,6 +485,7 @@ impl<'gc> UpdateContext<'gc> {
 
         // Clean up the stage before loading another root movie.
         self.sockets.close_all();
+        self.datagram_sockets.close_all();
         self.timers.remove_all();
 
         self.set_root_movie(movie);
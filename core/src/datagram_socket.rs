@@ -0,0 +1,333 @@
+//! UDP sockets, as used by AIR's `flash.net.DatagramSocket`.
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::object::{ByteArrayObject, EventObject};
+use crate::avm2::{Activation as Avm2Activation, Avm2, Object as Avm2Object};
+use crate::backend::navigator::NavigatorBackend;
+use crate::context::UpdateContext;
+use crate::sandbox;
+use crate::socket::SandboxedConnection;
+use crate::string::AvmString;
+use async_channel::{unbounded, Receiver, Sender};
+use gc_arena::collect::Trace;
+use gc_arena::Collect;
+use ruffle_macros::istr;
+use slotmap::{new_key_type, SlotMap};
+use std::collections::VecDeque;
+
+/// The maximum number of datagrams kept for a socket which isn't receiving.
+///
+/// Once this is reached, the oldest datagrams are dropped, as UDP doesn't
+/// guarantee delivery anyway.
+const MAX_PENDING_DATAGRAMS: usize = 1024;
+
+new_key_type! {
+    pub struct DatagramSocketHandle;
+}
+
+/// A single UDP packet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Datagram {
+    /// The IP address this datagram is sent to, or was received from.
+    pub address: String,
+
+    /// The port this datagram is sent to, or was received from.
+    pub port: u16,
+
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DatagramSocketAction {
+    /// A datagram was received by the socket.
+    Data(DatagramSocketHandle, Datagram),
+
+    /// The socket was closed by the backend, e.g. because the user denied
+    /// network access.
+    Close(DatagramSocketHandle),
+}
+
+struct DatagramSocket<'gc> {
+    target: Avm2Object<'gc>,
+
+    /// Datagrams sent to this channel are sent by the backend.
+    ///
+    /// Dropping it closes the socket.
+    sender: Sender<Datagram>,
+
+    local_address: String,
+    local_port: u16,
+
+    /// The address and port set by `connect`.
+    ///
+    /// Once connected, datagrams from any other peer are dropped.
+    remote: Option<(String, u16)>,
+
+    /// Whether `receive` was called, and datagrams should be dispatched.
+    receiving: bool,
+
+    /// Datagrams which have been received but not dispatched yet.
+    pending: VecDeque<Datagram>,
+}
+
+impl DatagramSocket<'_> {
+    /// Whether a datagram from this peer should be received.
+    fn accepts(&self, datagram: &Datagram) -> bool {
+        match &self.remote {
+            Some((address, port)) => *address == datagram.address && *port == datagram.port,
+            None => true,
+        }
+    }
+
+    fn push_pending(&mut self, datagram: Datagram) {
+        if !self.accepts(&datagram) {
+            return;
+        }
+        if self.pending.len() >= MAX_PENDING_DATAGRAMS {
+            self.pending.pop_front();
+        }
+        self.pending.push_back(datagram);
+    }
+}
+
+/// Manages the collection of DatagramSockets.
+///
+/// A `DatagramSocket` object is only known here while it is bound; closing it
+/// makes it unbound again.
+pub struct DatagramSockets<'gc> {
+    sockets: SlotMap<DatagramSocketHandle, DatagramSocket<'gc>>,
+
+    receiver: Receiver<DatagramSocketAction>,
+    sender: Sender<DatagramSocketAction>,
+}
+
+unsafe impl<'gc> Collect<'gc> for DatagramSockets<'gc> {
+    fn trace<C: Trace<'gc>>(&self, cc: &mut C) {
+        for (_, socket) in self.sockets.iter() {
+            cc.trace(&socket.target);
+        }
+    }
+}
+
+impl<'gc> DatagramSockets<'gc> {
+    pub fn empty() -> Self {
+        let (sender, receiver) = unbounded();
+
+        Self {
+            sockets: SlotMap::with_key(),
+            receiver,
+            sender,
+        }
+    }
+
+    /// Finds the socket a `DatagramSocket` object is bound with.
+    pub fn handle_for_object(&self, target: Avm2Object<'gc>) -> Option<DatagramSocketHandle> {
+        self.sockets
+            .iter()
+            .find(|(_, socket)| Avm2Object::ptr_eq(socket.target, target))
+            .map(|(handle, _)| handle)
+    }
+
+    /// Binds a socket for `target` to a local address and port.
+    ///
+    /// Returns `None` if the backend couldn't bind the socket.
+    pub fn bind(
+        &mut self,
+        backend: &mut dyn NavigatorBackend,
+        target: Avm2Object<'gc>,
+        address: String,
+        port: u16,
+    ) -> Option<DatagramSocketHandle> {
+        let (sender, receiver) = unbounded();
+
+        let handle = self.sockets.insert(DatagramSocket {
+            target,
+            sender,
+            local_address: String::new(),
+            local_port: 0,
+            remote: None,
+            receiving: false,
+            pending: VecDeque::new(),
+        });
+
+        match backend.bind_udp_socket(address, port, handle, receiver, self.sender.clone()) {
+            Some((local_address, local_port)) => {
+                let socket = &mut self.sockets[handle];
+                socket.local_address = local_address;
+                socket.local_port = local_port;
+                Some(handle)
+            }
+            None => {
+                self.sockets.remove(handle);
+                None
+            }
+        }
+    }
+
+    /// The address and port the socket is bound to.
+    pub fn local_address(&self, handle: DatagramSocketHandle) -> Option<(&str, u16)> {
+        self.sockets
+            .get(handle)
+            .map(|socket| (socket.local_address.as_str(), socket.local_port))
+    }
+
+    /// The address and port the socket is connected to, if any.
+    pub fn remote_address(&self, handle: DatagramSocketHandle) -> Option<(&str, u16)> {
+        self.sockets
+            .get(handle)
+            .and_then(|socket| socket.remote.as_ref())
+            .map(|(address, port)| (address.as_str(), *port))
+    }
+
+    /// Sets the address and port that datagrams are sent to by default.
+    ///
+    /// UDP is connectionless, so this doesn't involve the backend.
+    pub fn connect(&mut self, handle: DatagramSocketHandle, address: String, port: u16) {
+        if let Some(socket) = self.sockets.get_mut(handle) {
+            socket.remote = Some((address, port));
+            let pending = std::mem::take(&mut socket.pending);
+            socket.pending = pending
+                .into_iter()
+                .filter(|datagram| socket.accepts(datagram))
+                .collect();
+        }
+    }
+
+    /// Starts dispatching received datagrams, including ones which were
+    /// received before.
+    pub fn receive(&mut self, handle: DatagramSocketHandle) {
+        if let Some(socket) = self.sockets.get_mut(handle) {
+            socket.receiving = true;
+        }
+    }
+
+    /// Sends a datagram from the socket.
+    ///
+    /// If `sandboxed` is given, the datagram is only sent once the security
+    /// sandbox allows the movie to reach its destination, and dropped otherwise.
+    pub fn send(
+        &mut self,
+        backend: &mut dyn NavigatorBackend,
+        handle: DatagramSocketHandle,
+        datagram: Datagram,
+        sandboxed: Option<SandboxedConnection>,
+    ) {
+        let Some(socket) = self.sockets.get(handle) else {
+            return;
+        };
+        let sender = socket.sender.clone();
+        let Some((player, movie)) =
+            sandboxed.and_then(|sandboxed| Some((sandboxed.player.upgrade()?, sandboxed.movie)))
+        else {
+            send_datagram(&sender, datagram);
+            return;
+        };
+
+        backend.spawn_future(Box::pin(async move {
+            if sandbox::request_socket_access(&player, &movie, &datagram.address, datagram.port)
+                .await
+            {
+                send_datagram(&sender, datagram);
+            } else {
+                tracing::warn!(
+                    "Security sandbox violation: {} cannot send datagrams to {}:{}",
+                    movie.url(),
+                    datagram.address,
+                    datagram.port
+                );
+            }
+            Ok(())
+        }));
+    }
+
+    pub fn close_all(&mut self) {
+        // NOTE: Dropping the senders stops the backend from using the sockets.
+        self.sockets.clear();
+    }
+
+    pub fn close(&mut self, handle: DatagramSocketHandle) {
+        self.sockets.remove(handle);
+    }
+
+    pub fn update_sockets(context: &mut UpdateContext<'gc>) {
+        let mut closed = vec![];
+
+        while let Ok(action) = context.datagram_sockets.receiver.try_recv() {
+            match action {
+                DatagramSocketAction::Data(handle, datagram) => {
+                    // Socket may have been closed before we could receive the datagram.
+                    if let Some(socket) = context.datagram_sockets.sockets.get_mut(handle) {
+                        socket.push_pending(datagram);
+                    }
+                }
+                DatagramSocketAction::Close(handle) => {
+                    if let Some(socket) = context.datagram_sockets.sockets.remove(handle) {
+                        closed.push(socket.target);
+                    }
+                }
+            }
+        }
+
+        let mut received = vec![];
+        for (_, socket) in context.datagram_sockets.sockets.iter_mut() {
+            if socket.receiving {
+                for datagram in socket.pending.drain(..) {
+                    received.push((
+                        socket.target,
+                        socket.local_address.clone(),
+                        socket.local_port,
+                        datagram,
+                    ));
+                }
+            }
+        }
+
+        for (target, local_address, local_port, datagram) in received {
+            let mut activation = Avm2Activation::from_nothing(context);
+
+            let data = match ByteArrayObject::from_storage(
+                &mut activation,
+                ByteArrayStorage::from_vec(datagram.data),
+            ) {
+                Ok(data) => data,
+                Err(e) => {
+                    tracing::error!("Couldn't create datagram data: {:?}", e);
+                    continue;
+                }
+            };
+
+            let src_address = AvmString::new_utf8(activation.gc(), datagram.address);
+            let dst_address = AvmString::new_utf8(activation.gc(), local_address);
+            let class = activation.avm2().classes().datagramsocketdataevent;
+            let data_evt = EventObject::from_class_and_args(
+                &mut activation,
+                class,
+                &[
+                    istr!("data").into(),
+                    false.into(),
+                    false.into(),
+                    src_address.into(),
+                    datagram.port.into(),
+                    dst_address.into(),
+                    local_port.into(),
+                    data.into(),
+                ],
+            );
+
+            Avm2::dispatch_event(activation.context, data_evt, target);
+        }
+
+        for target in closed {
+            let close_evt = EventObject::bare_default_event(context, "close");
+            Avm2::dispatch_event(context, close_evt, target);
+        }
+    }
+}
+
+fn send_datagram(sender: &Sender<Datagram>, datagram: Datagram) {
+    // We use an unbounded channel, so this should only ever error if the channel is closed
+    // (the receiver was dropped)
+    if let Err(e) = sender.try_send(datagram) {
+        tracing::error!("Failed to send datagram: {:?}", e);
+    }
+}
@@ -5,6 +5,7 @@ use crate::avm2::{
     Activation as Avm2Activation, Avm2, Error as Avm2Error, EventObject as Avm2EventObject,
     Multiname as Avm2Multiname, Object as Avm2Object, TObject as Avm2TObject, Value as Avm2Value,
 };
+use crate::backend::accessibility::AccessibilityNodeId;
 use crate::context::{RenderContext, UpdateContext};
 use crate::drawing::Drawing;
 use crate::loader::LoadManager;
@@ -257,6 +258,11 @@ pub struct DisplayObjectBase<'gc> {
     /// None means not cached, Some means cached.
     #[collect(require_static)]
     cache: Option<BitmapCache>,
+
+    /// The identifier of the node exposing this object to assistive technologies,
+    /// given out the first time the object appears in the accessibility tree.
+    #[collect(require_static)]
+    accessibility_node_id: Cell<Option<AccessibilityNodeId>>,
 }
 
 impl Default for DisplayObjectBase<'_> {
@@ -286,6 +292,7 @@ impl Default for DisplayObjectBase<'_> {
             next_scroll_rect: Cell::new(Default::default()),
             scaling_grid: Cell::new(Default::default()),
             cache: None,
+            accessibility_node_id: Cell::new(None),
         }
     }
 }
@@ -1554,6 +1561,15 @@ pub trait TDisplayObject<'gc>:
         self.base_mut(gc_context).set_name(name)
     }
 
+    /// The identifier of the node exposing this object to assistive technologies, if any.
+    fn accessibility_node_id(&self) -> Option<AccessibilityNodeId> {
+        self.base().accessibility_node_id.get()
+    }
+
+    fn set_accessibility_node_id(&self, id: AccessibilityNodeId) {
+        self.base().accessibility_node_id.set(Some(id));
+    }
+
     fn filters(&self) -> Vec<Filter> {
         self.base().filters()
     }
@@ -2475,6 +2491,12 @@ pub trait TDisplayObject<'gc>:
         }
     }
 
+    /// Whether this object or one of its descendants has visually changed since it was last rendered.
+    fn is_cached_bitmap_invalidated(&self) -> bool {
+        self.base()
+            .contains_flag(DisplayObjectFlags::CACHE_INVALIDATED)
+    }
+
     /// Inform this object and its ancestors that it has visually changed and must be redrawn.
     /// If this object or any ancestor is marked as cacheAsBitmap, it will invalidate that cache.
     fn invalidate_cached_bitmap(&self, mc: &Mutation<'gc>) {
@@ -6,25 +6,29 @@ use crate::avm1::{
 };
 use crate::avm2::object::{
     ClassObject as Avm2ClassObject, EventObject as Avm2EventObject, Object as Avm2Object,
-    StageObject as Avm2StageObject, StyleSheetObject as Avm2StyleSheetObject,
+    StageObject as Avm2StageObject, StyleSheetObject as Avm2StyleSheetObject, TObject,
 };
 use crate::avm2::{Activation as Avm2Activation, Avm2};
-use crate::backend::ui::MouseCursor;
+use crate::backend::navigator::Request;
+use crate::backend::ui::{ClipboardData, ClipboardFormat, MouseCursor};
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::interactive::{
     InteractiveObject, InteractiveObjectBase, TInteractiveObject,
 };
-use crate::display_object::{Avm1TextFieldBinding, DisplayObjectBase, DisplayObjectPtr};
+use crate::display_object::{Avm1TextFieldBinding, DisplayObjectBase, DisplayObjectPtr, MovieClip};
 use crate::events::{
     ClipEvent, ClipEventResult, ImeCursorArea, ImeEvent, ImeNotification, ImePurpose,
-    PlayerNotification, TextControlCode,
+    MouseWheelDelta, PlayerNotification, TextControlCode,
 };
-use crate::font::{FontType, Glyph, TextRenderSettings};
+use crate::font::{AdvancedGlyphRenderer, FontType, Glyph, TextRenderSettings};
 use crate::html;
 use crate::html::StyleSheet;
 use crate::html::{
-    FormatSpans, Layout, LayoutBox, LayoutContent, LayoutLine, LayoutMetrics, Position, TextFormat,
+    FormatSpans, Layout, LayoutBox, LayoutContent, LayoutLine, LayoutMetrics, LinkState, Position,
+    Size, TextFormat,
 };
+use crate::ime::Ime;
+use crate::loader::MovieLoaderVMData;
 use crate::prelude::*;
 use crate::string::{utils as string_utils, AvmString, SwfStrExt as _, WStr, WString};
 use crate::tag_utils::SwfMovie;
@@ -41,6 +45,7 @@ use ruffle_render::transform::Transform;
 use ruffle_wstr::WStrToUtf8;
 use std::cell::{Cell, Ref, RefCell, RefMut};
 use std::collections::VecDeque;
+use std::ops::Range;
 use std::sync::Arc;
 use swf::ColorTransform;
 use unic_segment::WordBoundIndices;
@@ -161,6 +166,10 @@ pub struct EditTextData<'gc> {
     /// Indicates if the text is scrollable using the mouse wheel.
     mouse_wheel_enabled: Cell<bool>,
 
+    /// The fraction of a line scrolled by the mouse wheel that hasn't been
+    /// applied to `scroll` yet.
+    mouse_wheel_remainder: Cell<f64>,
+
     /// Flags indicating the text field's settings.
     flags: Cell<EditTextFlag>,
 
@@ -192,6 +201,14 @@ pub struct EditTextData<'gc> {
     original_html_text: RefCell<Option<WString>>,
 
     ime_data: RefCell<Option<ImeData>>,
+
+    /// The link under the mouse and whether it's being pressed, used to
+    /// apply the `a:hover` and `a:active` styles of the style sheet.
+    link_state: Cell<Option<(usize, LinkState)>>,
+
+    /// Display objects showing the images embedded in the text,
+    /// in the order they appear in.
+    images: RefLock<Vec<EditTextImage<'gc>>>,
 }
 
 impl EditTextData<'_> {
@@ -226,6 +243,7 @@ impl EditTextData<'_> {
             text,
             default_format,
             self.style_sheet.get().style_sheet(),
+            self.link_state.get(),
             self.flags.get().contains(EditTextFlag::MULTILINE),
             self.flags.get().contains(EditTextFlag::CONDENSE_WHITE),
             self.shared.swf.version(),
@@ -267,6 +285,7 @@ impl<'gc> EditText<'gc> {
                 &text,
                 default_format,
                 None,
+                None,
                 swf_tag.is_multiline(),
                 false,
                 swf_movie.version(),
@@ -303,6 +322,7 @@ impl<'gc> EditText<'gc> {
             context,
             swf_movie.clone(),
             content_width,
+            &[],
             !swf_tag.is_read_only(),
             is_word_wrap,
             font_type,
@@ -363,6 +383,7 @@ impl<'gc> EditText<'gc> {
                 scroll: Cell::new(1),
                 max_chars: Cell::new(swf_tag.max_length().unwrap_or_default() as i32),
                 mouse_wheel_enabled: Cell::new(true),
+                mouse_wheel_remainder: Cell::new(0.0),
                 is_fte: Cell::new(false),
                 restrict: RefCell::new(EditTextRestrict::allow_all()),
                 last_click: Cell::new(None),
@@ -370,7 +391,9 @@ impl<'gc> EditText<'gc> {
                 style_sheet: Lock::new(EditTextStyleSheet::None),
                 original_html_text: RefCell::new(None),
                 ime_data: RefCell::new(None),
+                link_state: Cell::new(None),
                 avm1_text_field_bindings: RefLock::new(Vec::new()),
+                images: RefLock::new(Vec::new()),
             },
         ));
 
@@ -666,6 +689,14 @@ impl<'gc> EditText<'gc> {
         self.set_flag(EditTextFlag::ALWAYS_SHOW_SELECTION, value);
     }
 
+    pub fn use_rich_text_clipboard(self) -> bool {
+        self.contains_flag(EditTextFlag::USE_RICH_TEXT_CLIPBOARD)
+    }
+
+    pub fn set_use_rich_text_clipboard(self, value: bool) {
+        self.set_flag(EditTextFlag::USE_RICH_TEXT_CLIPBOARD, value);
+    }
+
     pub fn is_device_font(self) -> bool {
         !self.contains_flag(EditTextFlag::USE_OUTLINES)
     }
@@ -890,11 +921,17 @@ impl<'gc> EditText<'gc> {
             None
         };
 
+        drop(text_spans);
+        self.sync_images(context);
+        let image_sizes = self.image_sizes();
+        let text_spans = self.0.text_spans.borrow();
+
         let new_layout = html::lower_from_text_spans(
             &text_spans,
             context,
             movie,
             content_width,
+            &image_sizes,
             !self.0.flags.get().contains(EditTextFlag::READ_ONLY),
             is_word_wrap,
             self.0.font_type(),
@@ -936,6 +973,233 @@ impl<'gc> EditText<'gc> {
         self.invalidate_cached_bitmap(context.gc());
     }
 
+    /// Create display objects for images embedded in the text, reusing the
+    /// ones of images that are still present.
+    fn sync_images(self, context: &mut UpdateContext<'gc>) {
+        if self.0.object.get().is_none() {
+            // Images are only loaded by text fields that exist in a VM,
+            // not by library prototypes.
+            return;
+        }
+
+        let html_images: Vec<_> = self
+            .0
+            .text_spans
+            .borrow()
+            .images()
+            .iter()
+            .map(|image| (image.src.clone(), image.id.clone()))
+            .collect();
+        if html_images.is_empty() && self.0.images.borrow().is_empty() {
+            return;
+        }
+
+        let mut old_images = std::mem::take(
+            &mut *unlock!(Gc::write(context.gc(), self.0), EditTextData, images).borrow_mut(),
+        );
+        let mut images = Vec::with_capacity(html_images.len());
+        for (src, id) in html_images {
+            if let Some(index) = old_images
+                .iter()
+                .position(|image| image.src == src && image.id == id)
+            {
+                images.push(old_images.remove(index));
+                continue;
+            }
+
+            let display_object = self.instantiate_image(context, &src);
+            if let Some(display_object) = display_object {
+                display_object.set_parent(context, Some(self.into()));
+            }
+            images.push(EditTextImage {
+                src,
+                id,
+                display_object,
+                natural_size: Cell::new(None),
+            });
+        }
+
+        for image in old_images {
+            if let Some(display_object) = image.display_object {
+                display_object.set_parent(context, None);
+            }
+        }
+
+        *unlock!(Gc::write(context.gc(), self.0), EditTextData, images).borrow_mut() = images;
+    }
+
+    /// Create a display object for an image embedded in the text.
+    ///
+    /// The source is either the name of a library symbol, or the URL of an
+    /// image or SWF to load.
+    fn instantiate_image(
+        self,
+        context: &mut UpdateContext<'gc>,
+        src: &WStr,
+    ) -> Option<DisplayObject<'gc>> {
+        let movie = self.movie();
+        let name = AvmString::new(context.gc(), src);
+        let url = src.to_utf8_lossy().into_owned();
+
+        if movie.is_action_script_3() {
+            let mut activation = Avm2Activation::from_nothing(context);
+            let domain = activation
+                .context
+                .library
+                .library_for_movie(movie)
+                .map(|library| library.avm2_domain())
+                .unwrap_or_else(|| activation.context.avm2.stage_domain());
+
+            let symbol = domain
+                .get_defined_value_handling_vector(&mut activation, name)
+                .ok()
+                .and_then(|value| value.as_object())
+                .and_then(|object| object.as_class_object())
+                .and_then(|class| class.construct(&mut activation, &[]).ok())
+                .and_then(|value| value.as_object())
+                .and_then(|object| object.as_display_object());
+            if symbol.is_some() {
+                return symbol;
+            }
+
+            let loader_class = activation.avm2().classes().loader;
+            let loader = match loader_class.construct(&mut activation, &[]) {
+                Ok(loader) => loader.as_object()?,
+                Err(e) => {
+                    tracing::error!("Couldn't create a Loader for an image in text: {e:?}");
+                    return None;
+                }
+            };
+            crate::avm2::globals::flash::display::loader::load_request(
+                &mut activation,
+                loader,
+                Request::get(url),
+                None,
+                domain,
+            );
+            loader.as_display_object()
+        } else {
+            let gc = context.gc();
+            let symbol = context
+                .library
+                .library_for_movie(movie.clone())
+                .filter(|library| library.character_by_export_name(name).is_some())
+                .and_then(|library| library.instantiate_by_export_name(name, gc));
+            if let Some(symbol) = symbol {
+                symbol.post_instantiation(context, None, Instantiator::Avm1, false);
+                return Some(symbol);
+            }
+
+            let clip = MovieClip::new(movie, context.gc());
+            clip.post_instantiation(context, None, Instantiator::Avm1, false);
+            let future = context.load_manager.load_movie_into_clip(
+                context.player.clone(),
+                clip.into(),
+                Request::get(url),
+                None,
+                MovieLoaderVMData::Avm1 { broadcaster: None },
+            );
+            context.navigator.spawn_future(future);
+            Some(clip.into())
+        }
+    }
+
+    /// Sizes of images embedded in the text, as they should be laid out.
+    fn image_sizes(self) -> Vec<Size<Twips>> {
+        let text_spans = self.0.text_spans.borrow();
+        let images = self.0.images.borrow();
+        text_spans
+            .images()
+            .iter()
+            .zip(images.iter())
+            .map(|(html_image, image)| {
+                let natural_size = image.current_natural_size();
+                image.natural_size.set(Some(natural_size));
+                Size::from((
+                    html_image
+                        .width
+                        .map_or(natural_size.width(), Twips::from_pixels),
+                    html_image
+                        .height
+                        .map_or(natural_size.height(), Twips::from_pixels),
+                ))
+            })
+            .collect()
+    }
+
+    /// Relayout the text when any image has changed its size,
+    /// e.g. after it has been loaded.
+    fn relayout_if_images_resized(self, context: &mut UpdateContext<'gc>) {
+        let resized = self
+            .0
+            .images
+            .borrow()
+            .iter()
+            .any(|image| image.natural_size.get() != Some(image.current_natural_size()));
+        if resized {
+            self.relayout(context);
+        }
+    }
+
+    /// Returns the display object of the image embedded in the text with the given ID.
+    pub fn image_reference(self, id: &WStr) -> Option<DisplayObject<'gc>> {
+        self.0
+            .images
+            .borrow()
+            .iter()
+            .find(|image| image.id == id)
+            .and_then(|image| image.display_object)
+    }
+
+    /// Display objects of images embedded in the text.
+    fn image_display_objects(self) -> Vec<DisplayObject<'gc>> {
+        self.0
+            .images
+            .borrow()
+            .iter()
+            .filter_map(|image| image.display_object)
+            .collect()
+    }
+
+    /// Position images according to the layout and render them.
+    fn render_images(self, context: &mut RenderContext<'_, 'gc>) {
+        let layout_to_local = self.layout_to_local_matrix();
+        let layout = self.0.layout.borrow();
+        let images = self.0.images.borrow();
+        for layout_image in layout.images() {
+            let Some(image) = images.get(layout_image.index()) else {
+                continue;
+            };
+            let Some(display_object) = image.display_object else {
+                continue;
+            };
+
+            let bounds = layout_image.bounds();
+            let natural_size = image
+                .natural_size
+                .get()
+                .unwrap_or_else(|| Size::from((bounds.width(), bounds.height())));
+            let scale = |size: Twips, natural_size: Twips| {
+                if natural_size > Twips::ZERO {
+                    size.get() as f32 / natural_size.get() as f32
+                } else {
+                    1.0
+                }
+            };
+            let matrix = layout_to_local
+                * Matrix::translate(bounds.offset_x(), bounds.offset_y())
+                * Matrix::scale(
+                    scale(bounds.width(), natural_size.width()),
+                    scale(bounds.height(), natural_size.height()),
+                );
+            if *display_object.base().matrix() != matrix {
+                display_object.set_matrix(context.gc(), matrix);
+            }
+
+            display_object.render(context);
+        }
+    }
+
     /// Apply lazily calculated autosize bounds.
     ///
     /// They should be applied only in specific places, as they influence
@@ -1160,16 +1424,24 @@ impl<'gc> EditText<'gc> {
             line.leading()
         };
 
-        let x_start = line
-            .char_x_bounds(local_start)
-            .map(|b| b.0)
-            .unwrap_or_else(|| line_bounds.offset_x());
-        let x_end = line
-            .char_x_bounds(local_end - 1)
-            .map(|b| b.1)
-            .unwrap_or_else(|| line_bounds.extent_x());
+        let x_ranges: Vec<(Twips, Twips)> = if line.has_right_to_left_text() {
+            // Selected characters of bidi text may not be next to each other,
+            // so each box has its own part of the selection.
+            line.boxes_iter()
+                .filter_map(|b| b.range_x_bounds(local_start..local_end))
+                .collect()
+        } else {
+            let x_start = line
+                .char_x_bounds(local_start)
+                .map(|b| b.0)
+                .unwrap_or_else(|| line_bounds.offset_x());
+            let x_end = line
+                .char_x_bounds(local_end - 1)
+                .map(|b| b.1)
+                .unwrap_or_else(|| line_bounds.extent_x());
+            vec![(x_start, x_end)]
+        };
 
-        let width = x_end - x_start;
         let height = line_bounds.height() + leading;
 
         let color = if self.has_focus() {
@@ -1177,14 +1449,17 @@ impl<'gc> EditText<'gc> {
         } else {
             Color::GRAY
         };
-        let selection_box = context.transform_stack.transform().matrix
-            * Matrix::create_box(
-                width.to_pixels() as f32,
-                height.to_pixels() as f32,
-                x_start,
-                line_bounds.origin().y(),
-            );
-        context.commands.draw_rect(color, selection_box);
+        for (x_start, x_end) in x_ranges {
+            let width = x_end - x_start;
+            let selection_box = context.transform_stack.transform().matrix
+                * Matrix::create_box(
+                    width.to_pixels() as f32,
+                    height.to_pixels() as f32,
+                    x_start,
+                    line_bounds.origin().y(),
+                );
+            context.commands.draw_rect(color, selection_box);
+        }
     }
 
     fn render_layout_line(self, context: &mut RenderContext<'_, 'gc>, line: &LayoutLine<'gc>) {
@@ -1254,44 +1529,58 @@ impl<'gc> EditText<'gc> {
             let descent = font.get_descent_for_height(params.height());
             let baseline_adjustment = baseline - params.height();
             let caret_height = baseline + descent;
-            let mut caret_x = Twips::ZERO;
+            let mut advanced_renderer = AdvancedGlyphRenderer::new(font, self.render_settings());
             font.evaluate(
                 text,
                 self.text_transform(color, baseline_adjustment),
                 params,
-                |pos, transform, glyph: &Glyph, advance, x| {
-                    if let Some(glyph_shape_handle) = glyph.shape_handle(context.renderer) {
-                        // If it's highlighted, override the color.
-                        if matches!(visible_selection, Some(visible_selection) if visible_selection.contains(start + pos)) {
-                            // Set text color to white
-                            context.transform_stack.push(&Transform {
-                                matrix: transform.matrix,
-                                color_transform: ColorTransform::IDENTITY,
-                            });
-                        } else {
-                            context.transform_stack.push(transform);
-                        }
-
-                        // Render glyph.
-                        context
-                            .commands
-                            .render_shape(glyph_shape_handle, context.transform_stack.transform());
-                        context.transform_stack.pop();
+                |pos, transform, glyph: &Glyph, _advance, _x| {
+                    // If it's highlighted, override the color.
+                    if matches!(visible_selection, Some(visible_selection) if visible_selection.contains(start + pos)) {
+                        // Set text color to white
+                        context.transform_stack.push(&Transform {
+                            matrix: transform.matrix,
+                            color_transform: ColorTransform::IDENTITY,
+                        });
+                    } else {
+                        context.transform_stack.push(transform);
                     }
 
-                    // Update caret position
-                    if let Some(caret) = caret {
-                        if pos == caret {
-                            caret_x = x;
-                        } else if caret > 0 && pos == caret - 1 {
-                            // The caret may be rendered at the end, after all glyphs.
-                            caret_x = x + advance;
+                    // Render glyph.
+                    let rendered = advanced_renderer
+                        .as_mut()
+                        .is_some_and(|renderer| renderer.render_glyph(context, glyph));
+                    if !rendered {
+                        if let Some(glyph_shape_handle) = glyph.shape_handle(context.renderer) {
+                            context.commands.render_shape(
+                                glyph_shape_handle,
+                                context.transform_stack.transform(),
+                            );
                         }
                     }
+                    context.transform_stack.pop();
                 },
             );
+            if let Some(advanced_renderer) = advanced_renderer {
+                advanced_renderer.finish(context);
+            }
+
+            // Text being composed with an input method is underlined.
+            if let Some((x_start, x_end)) = self
+                .composition_range()
+                .and_then(|composition| lbox.range_x_bounds(composition))
+            {
+                self.render_composition_underline(
+                    context,
+                    x_start - origin.x(),
+                    x_end - origin.x(),
+                    baseline + Twips::ONE_PX,
+                    color,
+                );
+            }
 
-            if caret.is_some() {
+            if let Some(caret) = caret {
+                let caret_x = lbox.caret_x(start + caret).unwrap_or_default();
                 self.render_caret(context, caret_x, caret_height, color);
             }
         }
@@ -1323,6 +1612,21 @@ impl<'gc> EditText<'gc> {
         context.commands.draw_line(color, caret);
     }
 
+    fn render_composition_underline(
+        self,
+        context: &mut RenderContext<'_, 'gc>,
+        x_start: Twips,
+        x_end: Twips,
+        y: Twips,
+        color: Color,
+    ) {
+        let mut underline = context.transform_stack.transform().matrix
+            * Matrix::create_box((x_end - x_start).to_pixels() as f32, 1.0, x_start, y);
+        let pixel_snapping = EditTextPixelSnapping::new(context.stage.quality());
+        pixel_snapping.apply(&mut underline);
+        context.commands.draw_line(color, underline);
+    }
+
     /// Attempts to bind this text field to a property of a display object.
     /// If we find a parent display object matching the given path, we register oursevles and a property name with it.
     /// `set_text` will be called by the stage object whenever the property changes.
@@ -1522,6 +1826,90 @@ impl<'gc> EditText<'gc> {
         self.invalidate_cached_bitmap(context.gc());
     }
 
+    /// Whether the mouse wheel scrolls this text field.
+    pub fn is_mouse_wheel_scrollable(self) -> bool {
+        self.is_mouse_wheel_enabled() && self.maxscroll() > 1
+    }
+
+    /// Scroll this text field vertically in response to the mouse wheel.
+    ///
+    /// Deltas of a fraction of a line, as reported by smooth scrolling devices,
+    /// are accumulated until they add up to whole lines.
+    pub fn scroll_by_mouse_wheel(self, context: &mut UpdateContext<'gc>, delta: MouseWheelDelta) {
+        let delta = delta.lines();
+        if !self.is_mouse_wheel_enabled() || !delta.is_finite() {
+            return;
+        }
+
+        let mut lines = self.0.mouse_wheel_remainder.get();
+        // Scrolling in the other direction discards what's left of the previous one.
+        if lines * delta > 0.0 {
+            lines = 0.0;
+        }
+        lines -= delta;
+        let whole_lines = lines.trunc();
+        self.0.mouse_wheel_remainder.set(lines - whole_lines);
+        if whole_lines == 0.0 {
+            return;
+        }
+
+        let old_scroll = self.scroll();
+        self.set_scroll(old_scroll as f64 + whole_lines, context);
+        if self.scroll() != old_scroll {
+            let mut activation = Avm1Activation::from_nothing(
+                context,
+                ActivationIdentifier::root("[On Scroller]"),
+                self.into(),
+            );
+            self.on_scroller(&mut activation);
+        }
+    }
+
+    /// Find the text field scrolled by the mouse wheel, which is the topmost
+    /// object under the mouse cursor if that's a text field.
+    ///
+    /// Text fields don't have to be interactive to be scrolled, so they are
+    /// also looked for inside of the hovered object.
+    pub fn mouse_wheel_target(context: &mut UpdateContext<'gc>) -> Option<Self> {
+        fn topmost_in<'gc>(
+            context: &mut UpdateContext<'gc>,
+            container: DisplayObjectContainer<'gc>,
+            point: Point<Twips>,
+        ) -> Option<Option<EditText<'gc>>> {
+            for child in container.iter_render_list().rev() {
+                if child.clip_depth() > 0
+                    || !child.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK)
+                {
+                    continue;
+                }
+                if let Some(text) = child.as_edit_text() {
+                    return Some(text.mouse_enabled().then_some(text));
+                }
+                return Some(
+                    child
+                        .as_container()
+                        .and_then(|container| topmost_in(context, container, point))
+                        .flatten(),
+                );
+            }
+            None
+        }
+
+        let point = *context.mouse_position;
+        let hovered = context
+            .mouse_data
+            .hovered
+            .map(|hovered| hovered.as_displayobject());
+        if let Some(text) = hovered.and_then(|hovered| hovered.as_edit_text()) {
+            return Some(text);
+        }
+        let container = match hovered {
+            Some(hovered) => hovered.as_container()?,
+            None => context.stage.into(),
+        };
+        topmost_in(context, container, point).flatten()
+    }
+
     pub fn max_chars(self) -> i32 {
         self.0.max_chars.get()
     }
@@ -1549,16 +1937,11 @@ impl<'gc> EditText<'gc> {
         let line = layout.lines().get(line_index)?;
 
         // ...then find the box within that line that is the closest match to the X position.
-        let mut closest_layout_box: Option<&LayoutBox<'gc>> = None;
-        for layout_box in line.boxes_iter() {
-            if layout_box.is_text_box() {
-                if position.x >= layout_box.bounds().offset_x() || closest_layout_box.is_none() {
-                    closest_layout_box = Some(layout_box);
-                } else {
-                    break;
-                }
-            }
-        }
+        let text_boxes: Vec<&LayoutBox<'gc>> =
+            line.boxes_iter().filter(|b| b.is_text_box()).collect();
+        let closest_layout_box =
+            closest_box_index(text_boxes.iter().map(|b| b.bounds().offset_x()), position.x)
+                .map(|index| text_boxes[index]);
 
         if let Some(layout_box) = closest_layout_box {
             let origin = layout_box.bounds().origin();
@@ -1569,7 +1952,7 @@ impl<'gc> EditText<'gc> {
             if let Some((text, _tf, font, params, color)) =
                 layout_box.as_renderable_text(self.0.text_spans.borrow().text())
             {
-                let mut result = 0;
+                let mut glyphs = Vec::new();
                 let baseline_adjustment =
                     font.get_baseline_for_height(params.height()) - params.height();
                 font.evaluate(
@@ -1577,15 +1960,11 @@ impl<'gc> EditText<'gc> {
                     self.text_transform(color, baseline_adjustment),
                     params,
                     |pos, _transform, _glyph: &Glyph, advance, x| {
-                        if local_position.x >= x {
-                            if local_position.x > x + (advance / 2) {
-                                result = string_utils::next_char_boundary(text, pos);
-                            } else {
-                                result = pos;
-                            }
-                        }
+                        glyphs.push((pos, x, advance));
                     },
                 );
+                let result =
+                    caret_index_at_x(text, glyphs, local_position.x, params.is_right_to_left());
                 if let LayoutContent::Text { start, .. } = layout_box.content() {
                     return Some(result + start);
                 }
@@ -1644,6 +2023,97 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    /// Places the given range of text on the clipboard, both as plain text and,
+    /// for backends which support it, as HTML keeping its formatting.
+    ///
+    /// With `useRichTextClipboard`, a Flash XML fragment is added as well,
+    /// so that pasting into another text field restores the formatting.
+    fn copy_to_clipboard(self, from: usize, to: usize, context: &mut UpdateContext<'gc>) {
+        let text = &self.text()[from..to];
+        context.ui.set_clipboard_content(text.to_string());
+
+        let spans = self.0.text_spans.borrow().slice(from, to);
+        context
+            .ui
+            .set_clipboard_data(ClipboardData::Html(spans.to_html().to_string()));
+        if self.use_rich_text_clipboard() {
+            context.ui.set_clipboard_data(ClipboardData::FlashRichText(
+                spans.to_flash_xml().to_string(),
+            ));
+        }
+    }
+
+    /// The formatted text on the clipboard, if this field pastes rich text
+    /// and the clipboard holds text copied from a field using `useRichTextClipboard`.
+    fn clipboard_rich_text(self, context: &mut UpdateContext<'gc>) -> Option<FormatSpans> {
+        if !self.use_rich_text_clipboard() {
+            return None;
+        }
+        let Some(ClipboardData::FlashRichText(xml)) =
+            context.ui.clipboard_data(ClipboardFormat::FlashRichText)
+        else {
+            return None;
+        };
+        let default_format = self.0.text_spans.borrow().default_format().clone();
+        FormatSpans::from_flash_xml(&WString::from_utf8(&xml), default_format)
+    }
+
+    /// Inserts formatted text over the selection as if the user typed it.
+    ///
+    /// The formatting is only kept if the text was inserted unchanged,
+    /// i.e. none of it was rejected by `restrict`, `maxChars` or a
+    /// cancelled `textInput` event.
+    fn paste_rich_text(self, spans: &FormatSpans, context: &mut UpdateContext<'gc>) {
+        let Some(selection) = self.selection() else {
+            return;
+        };
+        let start = selection.start();
+        let end = start + spans.text().len();
+
+        self.text_input(spans.text().to_string(), context);
+
+        let text = self.text();
+        if self.selection().map(|selection| selection.start()) == Some(end)
+            && end <= text.len()
+            && text[start..end] == *spans.text()
+        {
+            self.0
+                .text_spans
+                .borrow_mut()
+                .replace_with_spans(start, end, spans);
+            self.relayout(context);
+        }
+    }
+
+    /// Replaces the given range of text with a Flash XML fragment, keeping its formatting.
+    ///
+    /// When `paste` is true, the text is inserted as if the user pasted it,
+    /// which respects `restrict` and `maxChars`. Malformed fragments are ignored.
+    pub fn insert_xml_text(
+        self,
+        from: usize,
+        to: usize,
+        xml: &WStr,
+        paste: bool,
+        context: &mut UpdateContext<'gc>,
+    ) {
+        let default_format = self.0.text_spans.borrow().default_format().clone();
+        let Some(spans) = FormatSpans::from_flash_xml(xml, default_format) else {
+            return;
+        };
+
+        if paste {
+            self.set_selection(Some(TextSelection::for_range(from, to)), context.gc());
+            self.paste_rich_text(&spans, context);
+        } else {
+            self.0
+                .text_spans
+                .borrow_mut()
+                .replace_with_spans(from, to, &spans);
+            self.relayout(context);
+        }
+    }
+
     pub fn text_control_input(
         self,
         control_code: TextControlCode,
@@ -1657,6 +2127,14 @@ impl<'gc> EditText<'gc> {
             return;
         };
 
+        // Arrow keys move the caret visually, which in right-to-left text
+        // is the opposite of the logical direction.
+        let control_code = if self.is_right_to_left_at(selection.to) {
+            control_code.mirrored()
+        } else {
+            control_code
+        };
+
         let mut changed = false;
         let is_selectable = self.is_selectable();
         match control_code {
@@ -1716,8 +2194,7 @@ impl<'gc> EditText<'gc> {
                 );
             }
             TextControlCode::Copy => {
-                let text = &self.text()[selection.start()..selection.end()];
-                context.ui.set_clipboard_content(text.to_string());
+                self.copy_to_clipboard(selection.start(), selection.end(), context);
             }
             TextControlCode::Paste => 'paste: {
                 let text = context.ui.clipboard_content();
@@ -1729,11 +2206,17 @@ impl<'gc> EditText<'gc> {
                     break 'paste;
                 }
 
-                self.text_input(text, context);
+                // Rich text is only used when it matches the plain text,
+                // as the clipboard may have been overwritten by another application.
+                match self.clipboard_rich_text(context) {
+                    Some(spans) if spans.text().to_string() == text => {
+                        self.paste_rich_text(&spans, context)
+                    }
+                    _ => self.text_input(text, context),
+                }
             }
             TextControlCode::Cut => {
-                let text = &self.text()[selection.start()..selection.end()];
-                context.ui.set_clipboard_content(text.to_string());
+                self.copy_to_clipboard(selection.start(), selection.end(), context);
 
                 self.replace_text(selection.start(), selection.end(), WStr::empty(), context);
                 if is_selectable {
@@ -1831,8 +2314,28 @@ impl<'gc> EditText<'gc> {
                 });
                 self.set_selection(new_selection, context.gc());
             }
-            ImeEvent::Commit(text) => self.text_input(text, context),
+            ImeEvent::Commit(text) => {
+                let composition = AvmString::new_utf8(context.gc(), &text);
+                self.text_input(text, context);
+                Ime::composition_finished(context, composition);
+            }
+        };
+    }
+
+    /// The range of the text that is being composed with an input method.
+    fn composition_range(self) -> Option<Range<usize>> {
+        let ime_data = self.0.ime_data.borrow();
+        ime_data.as_ref().map(|data| data.ime_start..data.ime_end)
+    }
+
+    /// Selects the given range of the composition, as requested by `IME.compositionSelectionChanged`.
+    pub fn select_composition(self, start: usize, end: usize, context: &mut UpdateContext<'gc>) {
+        let Some(range) = self.composition_range() else {
+            return;
         };
+        let start = (range.start + start).min(range.end);
+        let end = (range.start + end).clamp(start, range.end);
+        self.set_selection(Some(TextSelection::for_range(start, end)), context.gc());
     }
 
     fn ensure_ime_started(self, context: &mut UpdateContext<'gc>) -> ImeData {
@@ -1854,7 +2357,8 @@ impl<'gc> EditText<'gc> {
         ime_data
     }
 
-    fn ensure_ime_finished(self, context: &mut UpdateContext<'gc>) {
+    /// Discards the composition in progress, if any.
+    pub fn ensure_ime_finished(self, context: &mut UpdateContext<'gc>) {
         let Some(ImeData {
             ime_start, ime_end, ..
         }) = *self.0.ime_data.borrow()
@@ -1867,13 +2371,54 @@ impl<'gc> EditText<'gc> {
         self.0.ime_data.take();
     }
 
-    fn ensure_ime_committed(self, context: &mut UpdateContext<'gc>) {
+    /// Inserts the composition in progress, if any, as if the user committed it.
+    pub fn ensure_ime_committed(self, context: &mut UpdateContext<'gc>) {
         let Some(ImeData { text, .. }) = self.0.ime_data.borrow().clone() else {
             return;
         };
 
         self.ensure_ime_finished(context);
+        let composition = AvmString::new_utf8(context.gc(), &text);
         self.text_input(text, context);
+        Ime::composition_finished(context, composition);
+    }
+
+    /// Tells the frontend whether it should let the user type into this field with an input method.
+    ///
+    /// This depends on both the focus and `IME.enabled`.
+    pub fn notify_ime(self, context: &mut UpdateContext<'gc>) {
+        let notification = if self.has_focus() && context.ime.is_enabled() {
+            ImeNotification::ImeReady {
+                purpose: if self.is_password() {
+                    ImePurpose::Password
+                } else {
+                    ImePurpose::Standard
+                },
+                cursor_area: self.ime_cursor_area(),
+            }
+        } else {
+            ImeNotification::ImeNotReady
+        };
+        context.send_notification(PlayerNotification::ImeNotification(notification));
+    }
+
+    /// Moves the candidate window of the input method next to the caret.
+    pub fn update_ime_cursor_area(self, context: &mut UpdateContext<'gc>) {
+        if self.has_focus() && context.ime.is_enabled() {
+            context.send_notification(PlayerNotification::ImeNotification(
+                ImeNotification::ImeCursorAreaUpdated(self.ime_cursor_area()),
+            ));
+        }
+    }
+
+    /// Whether the caret at the given position is in right-to-left text.
+    fn is_right_to_left_at(self, position: usize) -> bool {
+        let layout = self.0.layout.borrow();
+        [Some(position), position.checked_sub(1)]
+            .into_iter()
+            .flatten()
+            .find_map(|position| layout.find_box_by_position(position))
+            .is_some_and(|layout_box| layout_box.is_right_to_left())
     }
 
     /// Find the new position in the text for the given control code.
@@ -2101,8 +2646,10 @@ impl<'gc> EditText<'gc> {
                 activation,
                 ExecutionReason::Special,
             );
+        } else if let Avm2Value::Object(object) = self.object2() {
+            let scroll_evt = Avm2EventObject::bare_default_event(activation.context, "scroll");
+            Avm2::dispatch_event(activation.context, scroll_evt, object);
         }
-        //TODO: Implement this for Avm2
     }
 
     /// Construct the text field's AVM1 representation.
@@ -2429,6 +2976,54 @@ impl<'gc> EditText<'gc> {
         })
     }
 
+    /// Find the `<a>` tag that the link at the given point was lowered from.
+    fn link_index_at(self, point: Point<Twips>) -> Option<usize> {
+        let mut position = self.global_to_local(point)?;
+        position.x += Self::GUTTER + Twips::from_pixels(self.0.hscroll.get());
+        position.y += Self::GUTTER + self.0.vertical_scroll_offset();
+
+        let layout = self.0.layout.borrow();
+        let link_box = layout.boxes_iter().find(|layout| {
+            layout.is_link()
+                && layout
+                    .bounds()
+                    .contains(Position::from((position.x, position.y)))
+        })?;
+        self.0.text_spans.borrow().link_index_at(link_box.start())
+    }
+
+    /// Update the link that the `a:hover` and `a:active` styles of the
+    /// style sheet apply to, after the mouse has moved or been pressed.
+    fn update_link_state(self, context: &mut UpdateContext<'gc>) {
+        if self.0.style_sheet.get().is_none() {
+            return;
+        }
+
+        let is_hovered =
+            InteractiveObject::option_ptr_eq(context.mouse_data.hovered, self.as_interactive());
+        let is_pressed =
+            InteractiveObject::option_ptr_eq(context.mouse_data.pressed, self.as_interactive());
+        let link_state = self
+            .link_index_at(*context.mouse_position)
+            .filter(|_| is_hovered || is_pressed)
+            .map(|index| {
+                if is_pressed {
+                    (index, LinkState::Active)
+                } else {
+                    (index, LinkState::Hover)
+                }
+            });
+        if self.0.link_state.replace(link_state) == link_state {
+            return;
+        }
+
+        let original_html_text = self.0.original_html_text.borrow().clone();
+        if let Some(html) = original_html_text {
+            self.0.parse_html(&html);
+            self.relayout(context);
+        }
+    }
+
     fn handle_click(self, click_index: usize, position: usize, context: &mut UpdateContext<'gc>) {
         if !self.is_selectable() {
             return;
@@ -2501,15 +3096,36 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
     }
 
     /// Construct objects placed on this frame.
+    fn enter_frame(&self, context: &mut UpdateContext<'gc>) {
+        for image in self.image_display_objects() {
+            image.enter_frame(context);
+        }
+    }
+
     fn construct_frame(&self, context: &mut UpdateContext<'gc>) {
         if self.movie().is_action_script_3() && matches!(self.object2(), Avm2Value::Null) {
             self.construct_as_avm2_object(context, (*self).into());
             self.on_construction_complete(context);
+            if !self.0.text_spans.borrow().images().is_empty() {
+                self.relayout(context);
+            }
         }
+
+        for image in self.image_display_objects() {
+            image.construct_frame(context);
+        }
+        self.relayout_if_images_resized(context);
     }
 
-    fn run_frame_avm1(&self, _context: &mut UpdateContext) {
-        // Noop
+    fn run_frame_scripts(self, context: &mut UpdateContext<'gc>) {
+        for image in self.image_display_objects() {
+            image.run_frame_scripts(context);
+        }
+    }
+
+    fn run_frame_avm1(&self, context: &mut UpdateContext<'gc>) {
+        // Images run their frames themselves, as they're on the execution list.
+        self.relayout_if_images_resized(context);
     }
 
     fn as_edit_text(&self) -> Option<EditText<'gc>> {
@@ -2532,6 +3148,9 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         if !self.movie().is_action_script_3() {
             context.avm1.add_to_exec_list(context.gc(), (*self).into());
             self.construct_as_avm1_object(context, run_frame);
+            if !self.0.text_spans.borrow().images().is_empty() {
+                self.relayout(context);
+            }
         }
     }
 
@@ -2701,6 +3320,8 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
 
         context.transform_stack.pop();
 
+        self.render_images(context);
+
         context.commands.deactivate_mask();
         context.commands.draw_rect(
             Color::WHITE,
@@ -2736,6 +3357,10 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
                 .retain(|&text_field| !DisplayObject::ptr_eq(text_field.into(), (*self).into()));
         }
 
+        for image in self.image_display_objects() {
+            image.avm1_unload(context);
+        }
+
         self.set_avm1_removed(true);
     }
 
@@ -2895,9 +3520,37 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    /// The area of the caret in stage coordinates, or of the whole field if
+    /// the caret isn't laid out.
+    ///
+    /// While composing, this is the start of the composition, so that the
+    /// candidate window doesn't move as the composition changes.
     fn ime_cursor_area(self) -> ImeCursorArea {
-        // TODO We should be smarter here and return an area closer to the cursor.
-        let bounds = self.world_bounds();
+        let position = self
+            .composition_range()
+            .map(|range| range.start)
+            .or_else(|| self.selection().map(|selection| selection.to));
+        let caret_bounds = position.and_then(|position| {
+            let layout = self.0.layout.borrow();
+            let (lbox, position) = [Some(position), position.checked_sub(1)]
+                .into_iter()
+                .flatten()
+                .find_map(|p| Some((layout.find_box_by_position(p)?, position)))?;
+            let bounds = lbox.bounds();
+            let x = bounds.origin().x() + lbox.caret_x(position).unwrap_or_else(|| bounds.width());
+            Some(Rectangle {
+                x_min: x,
+                y_min: bounds.offset_y(),
+                x_max: x + Twips::ONE_PX,
+                y_max: bounds.extent_y(),
+            })
+        });
+        let bounds = match caret_bounds {
+            Some(caret_bounds) => {
+                self.local_to_global_matrix() * self.layout_to_local_matrix() * caret_bounds
+            }
+            None => self.world_bounds(),
+        };
         ImeCursorArea {
             x: bounds.x_min.to_pixels(),
             y: bounds.y_min.to_pixels(),
@@ -2926,9 +3579,12 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         event: ClipEvent,
     ) -> ClipEventResult {
         match event {
-            ClipEvent::Press { .. } | ClipEvent::MouseWheel { .. } | ClipEvent::MouseMove => {
-                ClipEventResult::Handled
-            }
+            ClipEvent::Press { .. }
+            | ClipEvent::Release { .. }
+            | ClipEvent::ReleaseOutside
+            | ClipEvent::RollOver { .. }
+            | ClipEvent::RollOut { .. }
+            | ClipEvent::MouseMove => ClipEventResult::Handled,
             _ => ClipEventResult::NotHandled,
         }
     }
@@ -2938,22 +3594,9 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         context: &mut UpdateContext<'gc>,
         event: ClipEvent<'gc>,
     ) -> ClipEventResult {
-        if let ClipEvent::MouseWheel { delta } = event {
-            if self.is_mouse_wheel_enabled() {
-                let new_scroll = self.scroll() as f64 - delta.lines();
-                self.set_scroll(new_scroll, context);
-
-                let mut activation = Avm1Activation::from_nothing(
-                    context,
-                    ActivationIdentifier::root("[On Scroller]"),
-                    self.into(),
-                );
-                self.on_scroller(&mut activation);
-            }
-            return ClipEventResult::Handled;
-        }
-
         if let ClipEvent::Press { index } = event {
+            self.update_link_state(context);
+
             // We can't hold self as any link may end up modifying this object, so pull the info out
             let mut link_to_open = None;
 
@@ -3003,6 +3646,15 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
             }
         }
 
+        if let ClipEvent::MouseMove
+        | ClipEvent::Release { .. }
+        | ClipEvent::ReleaseOutside
+        | ClipEvent::RollOver { .. }
+        | ClipEvent::RollOut { .. } = event
+        {
+            self.update_link_state(context);
+        }
+
         ClipEventResult::NotHandled
     }
 
@@ -3086,18 +3738,7 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         }
 
         // Notify about IME
-        context.send_notification(PlayerNotification::ImeNotification(if focused {
-            ImeNotification::ImeReady {
-                purpose: if self.is_password() {
-                    ImePurpose::Password
-                } else {
-                    ImePurpose::Standard
-                },
-                cursor_area: self.ime_cursor_area(),
-            }
-        } else {
-            ImeNotification::ImeNotReady
-        }));
+        self.notify_ime(context);
     }
 
     fn is_focusable_by_mouse(&self, _context: &mut UpdateContext<'gc>) -> bool {
@@ -3129,6 +3770,7 @@ bitflags::bitflags! {
         const HAS_BACKGROUND = 1 << 1;
         const CONDENSE_WHITE = 1 << 13;
         const ALWAYS_SHOW_SELECTION = 1 << 14;
+        const USE_RICH_TEXT_CLIPBOARD = 1 << 15;
 
         // The following bits need to match `swf::EditTextFlag`.
         const READ_ONLY = 1 << 3;
@@ -3607,9 +4249,161 @@ impl Default for EditTextStyleSheet<'_> {
     }
 }
 
+/// A display object showing an image embedded in the text with `<img>`.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct EditTextImage<'gc> {
+    #[collect(require_static)]
+    src: WString,
+
+    #[collect(require_static)]
+    id: WString,
+
+    /// The display object the image is loaded into.
+    ///
+    /// `None` when it couldn't be created.
+    display_object: Option<DisplayObject<'gc>>,
+
+    /// The size of the display object when text was last laid out.
+    #[collect(require_static)]
+    natural_size: Cell<Option<Size<Twips>>>,
+}
+
+impl EditTextImage<'_> {
+    fn current_natural_size(&self) -> Size<Twips> {
+        let bounds = self
+            .display_object
+            .map(|display_object| display_object.bounds())
+            .unwrap_or_default();
+        Size::from((bounds.width(), bounds.height()))
+    }
+}
+
 #[derive(Clone, Debug)]
 struct ImeData {
     ime_start: usize,
     ime_end: usize,
     text: String,
 }
+
+/// Find the index of the box closest to the given X position, from the X offsets of the boxes.
+///
+/// That's the rightmost box starting at or before the position, or the leftmost box
+/// if all of them start after it. Boxes of bidi text are not positioned in logical order,
+/// so all of them are checked.
+fn closest_box_index(offsets: impl IntoIterator<Item = Twips>, x: Twips) -> Option<usize> {
+    let mut closest: Option<(usize, Twips)> = None;
+    for (index, offset_x) in offsets.into_iter().enumerate() {
+        let is_closer = match closest {
+            None => true,
+            Some((_, closest_offset_x)) => {
+                if x >= offset_x {
+                    closest_offset_x > x || offset_x >= closest_offset_x
+                } else {
+                    closest_offset_x > x && offset_x < closest_offset_x
+                }
+            }
+        };
+        if is_closer {
+            closest = Some((index, offset_x));
+        }
+    }
+    closest.map(|(index, _)| index)
+}
+
+/// Find the caret index closest to the given X position within the text of a box.
+///
+/// Glyphs are given as `(position, x, advance)` in the order they were evaluated,
+/// which is from the left. Characters are divided in half: the caret goes before
+/// a character when its logical start half is hit, which in right-to-left text is
+/// the right half.
+fn caret_index_at_x(
+    text: &WStr,
+    glyphs: impl IntoIterator<Item = (usize, Twips, Twips)>,
+    x: Twips,
+    right_to_left: bool,
+) -> usize {
+    // Glyphs of right-to-left text are evaluated from its end.
+    let mut result = if right_to_left { text.len() } else { 0 };
+    for (pos, glyph_x, advance) in glyphs {
+        if x >= glyph_x {
+            let past_half = x > glyph_x + (advance / 2);
+            if past_half != right_to_left {
+                result = string_utils::next_char_boundary(text, pos);
+            } else {
+                result = pos;
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{caret_index_at_x, closest_box_index};
+    use crate::events::TextControlCode;
+    use crate::string::WStr;
+    use swf::Twips;
+
+    fn px(pixels: i32) -> Twips {
+        Twips::from_pixels_i32(pixels)
+    }
+
+    #[test]
+    fn closest_box_of_left_to_right_line() {
+        let offsets = [px(0), px(50), px(100)];
+        assert_eq!(closest_box_index(offsets, px(-5)), Some(0));
+        assert_eq!(closest_box_index(offsets, px(60)), Some(1));
+        assert_eq!(closest_box_index(offsets, px(150)), Some(2));
+        assert_eq!(closest_box_index([], px(10)), None);
+    }
+
+    #[test]
+    fn closest_box_of_right_to_left_line() {
+        // Boxes are in logical order, which is displayed from the right.
+        let offsets = [px(100), px(50), px(0)];
+        assert_eq!(closest_box_index(offsets, px(-5)), Some(2));
+        assert_eq!(closest_box_index(offsets, px(60)), Some(1));
+        assert_eq!(closest_box_index(offsets, px(150)), Some(0));
+    }
+
+    #[test]
+    fn caret_in_left_to_right_text() {
+        let text = WStr::from_units(b"abc");
+        let glyphs = [(0, px(0), px(10)), (1, px(10), px(10)), (2, px(20), px(10))];
+        assert_eq!(caret_index_at_x(text, glyphs, px(-1), false), 0);
+        assert_eq!(caret_index_at_x(text, glyphs, px(4), false), 0);
+        assert_eq!(caret_index_at_x(text, glyphs, px(6), false), 1);
+        assert_eq!(caret_index_at_x(text, glyphs, px(24), false), 2);
+        assert_eq!(caret_index_at_x(text, glyphs, px(40), false), 3);
+    }
+
+    #[test]
+    fn caret_in_right_to_left_text() {
+        // The glyphs are evaluated from the left, so from the end of the text.
+        let text = WStr::from_units(b"abc");
+        let glyphs = [(2, px(0), px(10)), (1, px(10), px(10)), (0, px(20), px(10))];
+        assert_eq!(caret_index_at_x(text, glyphs, px(-1), true), 3);
+        assert_eq!(caret_index_at_x(text, glyphs, px(4), true), 3);
+        assert_eq!(caret_index_at_x(text, glyphs, px(6), true), 2);
+        assert_eq!(caret_index_at_x(text, glyphs, px(14), true), 2);
+        assert_eq!(caret_index_at_x(text, glyphs, px(21), true), 1);
+        assert_eq!(caret_index_at_x(text, glyphs, px(40), true), 0);
+    }
+
+    #[test]
+    fn arrow_keys_in_right_to_left_text() {
+        assert_eq!(
+            TextControlCode::MoveLeft.mirrored(),
+            TextControlCode::MoveRight
+        );
+        assert_eq!(
+            TextControlCode::SelectRightWord.mirrored(),
+            TextControlCode::SelectLeftWord
+        );
+        assert_eq!(
+            TextControlCode::MoveRightLine.mirrored(),
+            TextControlCode::MoveRightLine
+        );
+    }
+}
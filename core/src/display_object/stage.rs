@@ -1,6 +1,8 @@
 //! Root stage impl
 
 use crate::avm1::Object as Avm1Object;
+use crate::avm2::globals::methods::flash_display_native_window as native_window_methods;
+use crate::avm2::globals::slots::flash_display_stage as stage_slots;
 use crate::avm2::object::TObject;
 use crate::avm2::{
     Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject, Object as Avm2Object,
@@ -203,7 +205,6 @@ impl<'gc> Stage<'gc> {
             .unwrap_or(Matrix::ZERO)
     }
 
-    #[allow(dead_code)]
     pub fn view_matrix(self) -> Matrix {
         self.0.viewport_matrix.get()
     }
@@ -728,6 +729,27 @@ impl<'gc> Stage<'gc> {
         }
     }
 
+    /// Lets the AIR `NativeWindow` of this stage, if one was created, know that the
+    /// bounds or display state of its window may have changed.
+    ///
+    /// The window compares them against the last known values and fires any change events.
+    pub fn update_native_window(self, context: &mut UpdateContext<'gc>) {
+        let Avm2Value::Object(stage) = self.object2() else {
+            return;
+        };
+        let native_window = stage.get_slot(stage_slots::_NATIVE_WINDOW);
+        if !matches!(native_window, Avm2Value::Object(_)) {
+            return;
+        }
+
+        let mut activation = Avm2Activation::from_nothing(context);
+        if let Err(e) =
+            native_window.call_method(native_window_methods::UPDATE_WINDOW, &[], &mut activation)
+        {
+            tracing::error!("Error updating NativeWindow: {e:?}");
+        }
+    }
+
     pub fn focus_tracker(self) -> FocusTracker<'gc> {
         self.0.focus_tracker
     }
@@ -842,6 +864,8 @@ impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
             }
         }
 
+        // The stage isn't rendered as a child, so its flag isn't reset by `pre_render`.
+        self.base().clear_invalidate_flag();
         render_base((*self).into(), context);
 
         self.focus_tracker().render_highlight(context);
@@ -3,7 +3,7 @@ use crate::avm2::{
 };
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr};
-use crate::font::TextRenderSettings;
+use crate::font::{AdvancedGlyphRenderer, TextRenderSettings};
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
@@ -178,20 +178,30 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
                 transform.matrix.a = scale;
                 transform.matrix.d = scale;
                 transform.color_transform.set_mult_color(&color);
+                let mut advanced_renderer =
+                    AdvancedGlyphRenderer::new(font, *self.0.render_settings.borrow());
                 for c in &block.glyphs {
                     if let Some(glyph) = font.get_glyph(c.index as usize) {
-                        if let Some(glyph_shape_handle) = glyph.shape_handle(context.renderer) {
-                            context.transform_stack.push(&transform);
-                            context.commands.render_shape(
-                                glyph_shape_handle,
-                                context.transform_stack.transform(),
-                            );
-                            context.transform_stack.pop();
+                        context.transform_stack.push(&transform);
+                        let rendered = advanced_renderer
+                            .as_mut()
+                            .is_some_and(|renderer| renderer.render_glyph(context, glyph));
+                        if !rendered {
+                            if let Some(glyph_shape_handle) = glyph.shape_handle(context.renderer) {
+                                context.commands.render_shape(
+                                    glyph_shape_handle,
+                                    context.transform_stack.transform(),
+                                );
+                            }
                         }
+                        context.transform_stack.pop();
 
                         transform.matrix.tx += Twips::new(c.advance);
                     }
                 }
+                if let Some(advanced_renderer) = advanced_renderer {
+                    advanced_renderer.finish(context);
+                }
             }
         }
         context.transform_stack.pop();
@@ -5,6 +5,7 @@ use crate::avm2::{
     Activation as Avm2Activation, Object as Avm2Object, StageObject as Avm2StageObject,
     Value as Avm2Value,
 };
+use crate::capture::Camera;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{Avm1TextFieldBinding, DisplayObjectBase, DisplayObjectPtr};
 use crate::prelude::*;
@@ -101,7 +102,7 @@ pub enum VideoSource<'gc> {
     /// particular character. If you need to mutate the video source, consider
     /// reallocating a new source for your specific video instead.
     ///
-    /// This warning does not apply to `NetStream` or `Unconnected` videos,
+    /// This warning does not apply to `NetStream`, `Camera` or `Unconnected` videos,
     /// which are never aliased.
     Swf(Gc<'gc, SwfVideoSource>),
     /// An attached NetStream.
@@ -109,6 +110,11 @@ pub enum VideoSource<'gc> {
         /// The stream the video is downloaded from.
         stream: NetStream<'gc>,
     },
+    /// An attached Camera.
+    Camera {
+        /// The camera whose latest frame is displayed.
+        camera: Camera<'gc>,
+    },
     Unconnected,
 }
 
@@ -203,6 +209,20 @@ impl<'gc> Video<'gc> {
         self.0.keyframes.replace(BTreeSet::new());
     }
 
+    /// Convert this Video into a Camera sourced video, or disconnect it if
+    /// `camera` is `None`.
+    ///
+    /// Existing video state related to the old video stream will be dropped.
+    pub fn attach_camera(self, context: &mut UpdateContext<'gc>, camera: Option<Camera<'gc>>) {
+        let source = match camera {
+            Some(camera) => VideoSource::Camera { camera },
+            None => VideoSource::Unconnected,
+        };
+        self.set_source(context, source);
+        self.0.stream.set(VideoStream::Uninstantiated(0));
+        self.0.keyframes.replace(BTreeSet::new());
+    }
+
     /// Preload frame data from an SWF.
     ///
     /// This function yields an error if this video player is not playing an
@@ -222,6 +242,7 @@ impl<'gc> Video<'gc> {
                 frames.insert(tag.frame_num.into(), (subslice.start, subslice.end));
             }
             VideoSource::NetStream { .. } => {}
+            VideoSource::Camera { .. } => {}
             VideoSource::Unconnected { .. } => {}
         }
     }
@@ -245,6 +266,7 @@ impl<'gc> Video<'gc> {
         let num_frames = match self.0.source.get() {
             VideoSource::Swf(swf_source) => swf_source.streamdef.num_frames as usize,
             VideoSource::NetStream { .. } => return,
+            VideoSource::Camera { .. } => return,
             VideoSource::Unconnected { .. } => return,
         };
 
@@ -331,6 +353,7 @@ impl<'gc> Video<'gc> {
                 }
             },
             VideoSource::NetStream { .. } => return,
+            VideoSource::Camera { .. } => return,
             VideoSource::Unconnected { .. } => return,
         };
 
@@ -428,6 +451,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 }
             }
             VideoSource::NetStream { .. } => return,
+            VideoSource::Camera { .. } => return,
             VideoSource::Unconnected { .. } => return,
         };
 
@@ -484,6 +508,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         match self.0.source.get() {
             VideoSource::Swf(swf_source) => swf_source.streamdef.id,
             VideoSource::NetStream { .. } => 0,
+            VideoSource::Camera { .. } => 0,
             VideoSource::Unconnected { .. } => 0,
         }
     }
@@ -525,6 +550,13 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 stream.last_decoded_bitmap(),
                 None,
             ),
+            VideoSource::Camera { camera } => (
+                false,
+                None,
+                self.0.movie.version(),
+                camera.frame_bitmap(),
+                None,
+            ),
             VideoSource::Unconnected { .. } => return context.transform_stack.pop(),
         };
 
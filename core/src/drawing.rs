@@ -340,6 +340,19 @@ impl Drawing {
         }
     }
 
+    /// Returns the commands and winding rule of each fill of this drawing,
+    /// including the fill currently being drawn.
+    pub fn fills(&self) -> impl Iterator<Item = (&[DrawCommand], FillRule)> {
+        self.paths
+            .iter()
+            .filter_map(|path| match path {
+                DrawingPath::Fill(fill) => Some(fill),
+                DrawingPath::Line(_) => None,
+            })
+            .chain(&self.current_fill)
+            .map(|fill| (&fill.commands[..], fill.rule))
+    }
+
     pub fn self_bounds(&self) -> Rectangle<Twips> {
         self.shape_bounds
     }
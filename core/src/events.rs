@@ -466,6 +466,24 @@ impl TextControlCode {
                 | Self::DeleteWord
         )
     }
+
+    /// Get the control code moving in the opposite horizontal direction.
+    ///
+    /// Only moves by characters and words are mirrored, as lines and
+    /// documents have a logical start and end.
+    pub fn mirrored(self) -> Self {
+        match self {
+            Self::MoveLeft => Self::MoveRight,
+            Self::MoveLeftWord => Self::MoveRightWord,
+            Self::MoveRight => Self::MoveLeft,
+            Self::MoveRightWord => Self::MoveLeftWord,
+            Self::SelectLeft => Self::SelectRight,
+            Self::SelectLeftWord => Self::SelectRightWord,
+            Self::SelectRight => Self::SelectLeft,
+            Self::SelectRightWord => Self::SelectLeftWord,
+            _ => self,
+        }
+    }
 }
 
 /// Input method allows inputting non-Latin characters on a Latin keyboard.
@@ -1154,6 +1172,9 @@ pub enum KeyLocation {
 #[derive(Debug, Clone)]
 pub enum PlayerNotification {
     ImeNotification(ImeNotification),
+
+    /// The movie asked the application to exit with the given status code.
+    Exit(i32),
 }
 
 #[derive(Debug, Clone)]
@@ -7,13 +7,24 @@ use ruffle_render::backend::null::NullBitmapSource;
 use ruffle_render::backend::{RenderBackend, ShapeHandle};
 use ruffle_render::shape_utils::{DrawCommand, FillRule};
 use ruffle_render::transform::Transform;
+use self_cell::self_cell;
 use std::borrow::Cow;
 use std::cell::{OnceCell, RefCell};
+use std::char::DecodeUtf16Error;
+use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::ops::Range;
 use swf::FillStyle;
 
 pub use swf::TextGridFit;
 
+mod advanced;
+
+use advanced::GlyphBitmapCache;
+pub use advanced::{
+    AdvancedGlyphRenderer, CsmSettings, TextColorType, TextDisplayMode, TextRenderer,
+};
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum DefaultFont {
     /// `_sans`, a Sans-Serif font (similar to Helvetica or Arial)
@@ -69,6 +80,12 @@ pub struct EvalParameters {
     /// pairs of letters, separate from the ordinary width between glyphs. This
     /// parameter allows enabling or disabling that feature.
     kerning: bool,
+
+    /// Whether the text is laid out from right to left.
+    ///
+    /// This is set for text in an odd bidi embedding level, and makes glyphs
+    /// be produced in visual order.
+    right_to_left: bool,
 }
 
 impl EvalParameters {
@@ -79,6 +96,7 @@ impl EvalParameters {
             height,
             letter_spacing,
             kerning,
+            right_to_left: false,
         }
     }
 
@@ -89,6 +107,15 @@ impl EvalParameters {
             height: Twips::from_pixels(span.font.size),
             letter_spacing: Twips::from_pixels(span.font.letter_spacing),
             kerning: span.font.kerning,
+            right_to_left: false,
+        }
+    }
+
+    /// Get the same parameters, but evaluating text in the given direction.
+    pub fn with_right_to_left(self, right_to_left: bool) -> Self {
+        Self {
+            right_to_left,
+            ..self
         }
     }
 
@@ -96,6 +123,11 @@ impl EvalParameters {
     pub fn height(&self) -> Twips {
         self.height
     }
+
+    /// Whether the text is evaluated from right to left.
+    pub fn is_right_to_left(&self) -> bool {
+        self.right_to_left
+    }
 }
 
 struct GlyphToDrawing<'a>(&'a mut Drawing);
@@ -138,18 +170,35 @@ impl ttf_parser::OutlineBuilder for GlyphToDrawing<'_> {
     }
 }
 
+type ParsedFace<'a> = rustybuzz::Face<'a>;
+
+self_cell!(
+    /// A font file along with its parsed face, which serves both glyph outlines and shaping.
+    struct FontFile {
+        owner: Cow<'static, [u8]>,
+
+        #[covariant]
+        dependent: ParsedFace,
+    }
+);
+
+impl fmt::Debug for FontFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FontFile")
+            .field("len", &self.borrow_owner().len())
+            .finish()
+    }
+}
+
 /// Represents a raw font file (ie .ttf).
-/// This should be shared and reused where possible, and it's reparsed every time a new glyph is required.
-///
-/// Parsing of a font is near-free (according to [ttf_parser::Face::parse]), but the storage isn't.
+/// This should be shared and reused where possible; the file is parsed once, when it's loaded.
 ///
 /// Font files may contain multiple individual font faces, but those font faces may reuse the same
 /// Glyph from the same file. For this reason, glyphs are reused where possible.
 #[derive(Debug)]
 pub struct FontFace {
-    bytes: Cow<'static, [u8]>,
+    file: FontFile,
     glyphs: Vec<OnceCell<Option<Glyph>>>,
-    font_index: u32,
 
     ascender: i32,
     descender: i32,
@@ -165,8 +214,10 @@ impl FontFace {
     ) -> Result<Self, ttf_parser::FaceParsingError> {
         // TODO: Support font collections
 
-        // We validate that the font is good here, so we can just `.expect()` it later
-        let face = ttf_parser::Face::parse(&bytes, font_index)?;
+        let file = FontFile::try_new(bytes, |bytes| {
+            ttf_parser::Face::parse(bytes, font_index).map(rustybuzz::Face::from_face)
+        })?;
+        let face = file.borrow_dependent();
 
         let ascender = face.ascender() as i32;
         let descender = -face.descender() as i32;
@@ -187,8 +238,7 @@ impl FontFace {
             .unwrap_or_default();
 
         Ok(Self {
-            bytes,
-            font_index,
+            file,
             glyphs,
             ascender,
             descender,
@@ -199,45 +249,93 @@ impl FontFace {
     }
 
     pub fn get_glyph(&self, character: char) -> Option<&Glyph> {
-        let face = ttf_parser::Face::parse(&self.bytes, self.font_index)
-            .expect("Font was already checked to be valid");
-        if let Some(glyph_id) = face.glyph_index(character) {
-            return self.glyphs[glyph_id.0 as usize]
-                .get_or_init(|| {
-                    let mut drawing = Drawing::new();
-                    // TTF uses NonZero
-                    drawing.new_fill(
-                        Some(FillStyle::Color(Color::WHITE)),
-                        Some(FillRule::NonZero),
-                    );
-                    if face
-                        .outline_glyph(glyph_id, &mut GlyphToDrawing(&mut drawing))
-                        .is_some()
-                    {
-                        let advance = face.glyph_hor_advance(glyph_id).map_or_else(
-                            || drawing.self_bounds().width(),
-                            |a| Twips::new(a as i32),
-                        );
-                        Some(Glyph {
-                            shape_handle: Default::default(),
-                            shape: GlyphShape::Drawing(Box::new(drawing)),
-                            advance,
-                            character,
-                        })
-                    } else {
-                        let advance = Twips::new(face.glyph_hor_advance(glyph_id)? as i32);
-                        // If we have advance, then this is either an image, SVG or simply missing (ie whitespace)
-                        Some(Glyph {
-                            shape_handle: Default::default(),
-                            shape: GlyphShape::None,
-                            advance,
-                            character,
-                        })
-                    }
-                })
-                .as_ref();
+        let glyph_id = self.file.borrow_dependent().glyph_index(character)?;
+        self.get_glyph_by_id(glyph_id, character)
+    }
+
+    fn get_glyph_by_id(&self, glyph_id: ttf_parser::GlyphId, character: char) -> Option<&Glyph> {
+        let face = self.file.borrow_dependent();
+        self.glyphs
+            .get(glyph_id.0 as usize)?
+            .get_or_init(|| {
+                let mut drawing = Drawing::new();
+                // TTF uses NonZero
+                drawing.new_fill(
+                    Some(FillStyle::Color(Color::WHITE)),
+                    Some(FillRule::NonZero),
+                );
+                if face
+                    .outline_glyph(glyph_id, &mut GlyphToDrawing(&mut drawing))
+                    .is_some()
+                {
+                    let advance = face
+                        .glyph_hor_advance(glyph_id)
+                        .map_or_else(|| drawing.self_bounds().width(), |a| Twips::new(a as i32));
+                    Some(Glyph {
+                        shape_handle: Default::default(),
+                        bitmap_cache: Default::default(),
+                        shape: GlyphShape::Drawing(Box::new(drawing)),
+                        advance,
+                        character,
+                    })
+                } else {
+                    let advance = Twips::new(face.glyph_hor_advance(glyph_id)? as i32);
+                    // If we have advance, then this is either an image, SVG or simply missing (ie whitespace)
+                    Some(Glyph {
+                        shape_handle: Default::default(),
+                        bitmap_cache: Default::default(),
+                        shape: GlyphShape::None,
+                        advance,
+                        character,
+                    })
+                }
+            })
+            .as_ref()
+    }
+
+    /// Shape the given text using the OpenType layout tables of this font.
+    ///
+    /// This applies ligatures, contextual forms (e.g. Arabic joining),
+    /// reordering of Indic scripts and mark positioning. The resulting glyphs
+    /// are in visual order, which for right-to-left text means they're
+    /// reversed relative to the text.
+    fn shape(&self, text: &WStr, params: EvalParameters) -> Vec<ShapedGlyph> {
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        for (pos, c) in text.char_indices() {
+            buffer.add(c.unwrap_or(char::REPLACEMENT_CHARACTER), pos as u32);
         }
-        None
+        buffer.set_direction(if params.right_to_left {
+            rustybuzz::Direction::RightToLeft
+        } else {
+            rustybuzz::Direction::LeftToRight
+        });
+        buffer.guess_segment_properties();
+
+        let features = if params.kerning {
+            Vec::new()
+        } else {
+            vec![rustybuzz::Feature::new(
+                rustybuzz::ttf_parser::Tag::from_bytes(b"kern"),
+                0,
+                ..,
+            )]
+        };
+
+        let output = rustybuzz::shape(self.file.borrow_dependent(), &features, buffer);
+        output
+            .glyph_infos()
+            .iter()
+            .zip(output.glyph_positions())
+            .map(|(info, position)| ShapedGlyph {
+                glyph_id: ttf_parser::GlyphId(info.glyph_id as u16),
+                cluster: info.cluster as usize,
+                advance: Twips::new(position.x_advance),
+                offset: Point::new(
+                    Twips::new(position.x_offset),
+                    Twips::new(-position.y_offset),
+                ),
+            })
+            .collect()
     }
 
     pub fn has_kerning_info(&self) -> bool {
@@ -245,8 +343,7 @@ impl FontFace {
     }
 
     pub fn get_kerning_offset(&self, left: char, right: char) -> Twips {
-        let face = ttf_parser::Face::parse(&self.bytes, self.font_index)
-            .expect("Font was already checked to be valid");
+        let face = self.file.borrow_dependent();
 
         if let (Some(left_glyph), Some(right_glyph)) =
             (face.glyph_index(left), face.glyph_index(right))
@@ -266,6 +363,63 @@ impl FontFace {
     }
 }
 
+/// A glyph produced by shaping text with [`FontFace::shape`].
+struct ShapedGlyph {
+    glyph_id: ttf_parser::GlyphId,
+
+    /// The position in text of the first character this glyph was produced from.
+    cluster: usize,
+
+    /// The advance of this glyph, in EM-square coordinates.
+    advance: Twips,
+
+    /// The offset at which this glyph is drawn, in EM-square coordinates.
+    offset: Point<Twips>,
+}
+
+/// Whether the given character needs OpenType shaping to be displayed properly.
+///
+/// Text consisting only of other characters is laid out glyph by glyph.
+fn requires_shaping(c: char) -> bool {
+    matches!(
+        c as u32,
+        // Combining diacritical marks
+        0x0300..=0x036F
+            // Hebrew, Arabic, Syriac, Thaana, NKo, Samaritan, Mandaic
+            | 0x0590..=0x08FF
+            // Indic scripts
+            | 0x0900..=0x0DFF
+            // Thai, Lao, Tibetan, Myanmar
+            | 0x0E00..=0x109F
+            // Khmer, Mongolian
+            | 0x1780..=0x18AF
+            // Zero-width joiners and directional marks
+            | 0x200C..=0x200F
+            // Hebrew and Arabic presentation forms
+            | 0xFB1D..=0xFDFF
+            | 0xFE70..=0xFEFF
+    )
+}
+
+/// Whether the character belongs to the same grapheme or word as the
+/// preceding one, so it should be rendered using the same font.
+fn is_cluster_continuation(c: char) -> bool {
+    c.is_whitespace()
+        || matches!(
+            c as u32,
+            0x0300..=0x036F // Combining Diacritical Marks
+                | 0x1AB0..=0x1AFF
+                | 0x1DC0..=0x1DFF
+                | 0x200C..=0x200D // Zero-width (non-)joiner
+                | 0x20D0..=0x20FF
+                | 0xFE00..=0xFE0F // Variation selectors
+                | 0xFE20..=0xFE2F
+                | 0x1F3FB..=0x1F3FF // Emoji skin tone modifiers
+                | 0xE0020..=0xE007F // Tags
+                | 0xE0100..=0xE01EF
+        )
+}
+
 #[derive(Debug)]
 pub enum GlyphSource {
     Memory {
@@ -445,6 +599,7 @@ impl<'gc> Font<'gc> {
 
                 let glyph = Glyph {
                     shape_handle: None.into(),
+                    bitmap_cache: Default::default(),
                     advance: Twips::new(swf_glyph.advance.into()),
                     shape: GlyphShape::Swf(RefCell::new(Box::new(SwfGlyphOrShape::Glyph(
                         swf_glyph,
@@ -548,6 +703,11 @@ impl<'gc> Font<'gc> {
         ))
     }
 
+    /// Whether both handles refer to the same font.
+    pub fn ptr_eq(a: Font<'gc>, b: Font<'gc>) -> bool {
+        Gc::ptr_eq(a.0, b.0)
+    }
+
     /// Returns whether this font contains glyph shapes.
     /// If not, this font should be rendered as a device font.
     pub fn has_glyphs(&self) -> bool {
@@ -578,6 +738,22 @@ impl<'gc> Font<'gc> {
         true
     }
 
+    /// Returns the distinct characters of the given string that this font has
+    /// no glyphs for and that would be rendered with a fallback font.
+    pub fn missing_chars(&self, text: &WStr) -> Vec<char> {
+        let mut missing = Vec::new();
+        for c in text.chars() {
+            let c = c.unwrap_or(char::REPLACEMENT_CHARACTER);
+            if !is_cluster_continuation(c)
+                && self.get_glyph_for_char(c).is_none()
+                && !missing.contains(&c)
+            {
+                missing.push(c);
+            }
+        }
+        missing
+    }
+
     /// Returns whether this font contains kerning information.
     pub fn has_kerning_info(&self) -> bool {
         self.0.glyphs.has_kerning_info()
@@ -626,12 +802,17 @@ impl<'gc> Font<'gc> {
     ///
     /// It's guaranteed that this function will iterate over all characters
     /// from the text, irrespectively of whether they have a glyph or not.
+    ///
+    /// Glyphs are always produced in visual order. When the text is evaluated
+    /// from right to left, or it has to be shaped (which happens for device
+    /// fonts and complex scripts), positions of characters may not be
+    /// increasing, and a character may produce multiple glyphs.
     pub fn evaluate<FGlyph>(
         &self,
         text: &WStr, // TODO: take an `IntoIterator<Item=char>`, to not depend on string representation?
         mut transform: Transform,
         params: EvalParameters,
-        mut glyph_func: FGlyph,
+        glyph_func: FGlyph,
     ) where
         FGlyph: FnMut(usize, &Transform, &Glyph, Twips, Twips),
     {
@@ -641,9 +822,38 @@ impl<'gc> Font<'gc> {
         transform.matrix.a = scale;
         transform.matrix.d = scale;
 
+        if let GlyphSource::FontFace(face) = &self.0.glyphs {
+            if params.right_to_left || text.chars().any(|c| c.is_ok_and(requires_shaping)) {
+                self.evaluate_shaped(face, text, transform, scale, params, glyph_func);
+                return;
+            }
+        }
+
         // TODO [KJ] I'm not sure whether we should iterate over characters here or over code units.
         //   I suspect Flash Player does not support full UTF-16 when displaying and laying out text.
-        let mut char_indices = text.char_indices().peekable();
+        if params.right_to_left {
+            // Fonts without layout tables can only display right-to-left text
+            // by drawing its characters in reverse.
+            let mut char_indices: Vec<_> = text.char_indices().collect();
+            char_indices.reverse();
+            self.evaluate_chars(char_indices, transform, scale, params, glyph_func);
+        } else {
+            self.evaluate_chars(text.char_indices(), transform, scale, params, glyph_func);
+        }
+    }
+
+    /// Evaluate characters glyph by glyph, in the order they are given.
+    fn evaluate_chars<FGlyph>(
+        &self,
+        char_indices: impl IntoIterator<Item = (usize, Result<char, DecodeUtf16Error>)>,
+        mut transform: Transform,
+        scale: f32,
+        params: EvalParameters,
+        mut glyph_func: FGlyph,
+    ) where
+        FGlyph: FnMut(usize, &Transform, &Glyph, Twips, Twips),
+    {
+        let mut char_indices = char_indices.into_iter().peekable();
         let has_kerning_info = self.has_kerning_info();
         let mut x = Twips::ZERO;
         while let Some((pos, c)) = char_indices.next() {
@@ -655,19 +865,7 @@ impl<'gc> Font<'gc> {
                     let next_char = next_char.unwrap_or(char::REPLACEMENT_CHARACTER);
                     advance += self.get_kerning_offset(c, next_char);
                 }
-                let twips_advance = if self.font_type() == FontType::Device {
-                    let unspaced_advance =
-                        round_to_pixel(Twips::new((advance.get() as f32 * scale) as i32));
-                    let spaced_advance =
-                        unspaced_advance + params.letter_spacing.round_to_pixel_ties_even();
-                    if spaced_advance > Twips::ZERO {
-                        spaced_advance
-                    } else {
-                        unspaced_advance
-                    }
-                } else {
-                    Twips::new((advance.get() as f32 * scale) as i32) + params.letter_spacing
-                };
+                let twips_advance = self.scale_advance(advance, scale, params);
 
                 glyph_func(pos, &transform, glyph, twips_advance, x);
 
@@ -682,6 +880,130 @@ impl<'gc> Font<'gc> {
         }
     }
 
+    /// Evaluate text shaped by the font face, in visual order.
+    ///
+    /// All glyphs produced for a cluster of characters are reported at the
+    /// position of its first character. The remaining characters of the
+    /// cluster (e.g. ones that formed a ligature) are reported as empty
+    /// glyphs at its logical end.
+    fn evaluate_shaped<FGlyph>(
+        &self,
+        face: &FontFace,
+        text: &WStr,
+        mut transform: Transform,
+        scale: f32,
+        params: EvalParameters,
+        mut glyph_func: FGlyph,
+    ) where
+        FGlyph: FnMut(usize, &Transform, &Glyph, Twips, Twips),
+    {
+        let shaped = face.shape(text, params);
+        let char_indices: Vec<(usize, char)> = text
+            .char_indices()
+            .map(|(pos, c)| (pos, c.unwrap_or(char::REPLACEMENT_CHARACTER)))
+            .collect();
+
+        // Clusters are sorted by their first character, so that we know which
+        // characters were merged into each of them.
+        let mut clusters: Vec<usize> = shaped.iter().map(|g| g.cluster).collect();
+        clusters.sort_unstable();
+        clusters.dedup();
+
+        // The index of the first character of each cluster, found in a single pass over the text.
+        let mut first_chars = Vec::with_capacity(clusters.len() + 1);
+        let mut char_index = 0;
+        for &cluster in &clusters {
+            while char_indices
+                .get(char_index)
+                .is_some_and(|&(pos, _)| pos < cluster)
+            {
+                char_index += 1;
+            }
+            first_chars.push(char_index);
+        }
+        first_chars.push(char_indices.len());
+
+        let mut x = Twips::ZERO;
+        let mut i = 0;
+        while i < shaped.len() {
+            let cluster = shaped[i].cluster;
+            let index = clusters
+                .binary_search(&cluster)
+                .expect("Clusters were collected from the shaped glyphs");
+            let cluster_chars = &char_indices[first_chars[index]..first_chars[index + 1]];
+            let cluster_x = x;
+
+            let character = cluster_chars
+                .first()
+                .filter(|&&(pos, _)| pos == cluster)
+                .map_or(char::REPLACEMENT_CHARACTER, |&(_, c)| c);
+
+            while let Some(shaped_glyph) = shaped.get(i).filter(|g| g.cluster == cluster) {
+                // Characters missing from the font are shaped into the
+                // `.notdef` glyph, which we don't display, just like when
+                // evaluating glyph by glyph.
+                let glyph = (shaped_glyph.glyph_id.0 != 0)
+                    .then(|| face.get_glyph_by_id(shaped_glyph.glyph_id, character))
+                    .flatten();
+                let empty_glyph;
+                let (glyph, twips_advance) = match glyph {
+                    // Marks are positioned using offsets, and letter spacing doesn't apply to them.
+                    Some(glyph) if shaped_glyph.advance == Twips::ZERO => (glyph, Twips::ZERO),
+                    Some(glyph) => (
+                        glyph,
+                        self.scale_advance(shaped_glyph.advance, scale, params),
+                    ),
+                    None => {
+                        empty_glyph = Glyph::empty(character);
+                        (&empty_glyph, Twips::ZERO)
+                    }
+                };
+
+                let mut glyph_transform = transform.clone();
+                glyph_transform.matrix.tx +=
+                    Twips::new((shaped_glyph.offset.x.get() as f32 * scale) as i32);
+                glyph_transform.matrix.ty +=
+                    Twips::new((shaped_glyph.offset.y.get() as f32 * scale) as i32);
+
+                glyph_func(cluster, &glyph_transform, glyph, twips_advance, x);
+
+                // Step horizontally.
+                transform.matrix.tx += twips_advance;
+                x += twips_advance;
+                i += 1;
+            }
+
+            // The logical end of a right-to-left cluster is on its left side.
+            let cluster_end_x = if params.right_to_left { cluster_x } else { x };
+            for &(pos, c) in cluster_chars.iter().filter(|&&(pos, _)| pos != cluster) {
+                glyph_func(
+                    pos,
+                    &transform,
+                    &Glyph::empty(c),
+                    Twips::ZERO,
+                    cluster_end_x,
+                );
+            }
+        }
+    }
+
+    /// Convert an advance in EM-square coordinates to twips, applying letter spacing.
+    fn scale_advance(&self, advance: Twips, scale: f32, params: EvalParameters) -> Twips {
+        if self.font_type() == FontType::Device {
+            let unspaced_advance =
+                round_to_pixel(Twips::new((advance.get() as f32 * scale) as i32));
+            let spaced_advance =
+                unspaced_advance + params.letter_spacing.round_to_pixel_ties_even();
+            if spaced_advance > Twips::ZERO {
+                spaced_advance
+            } else {
+                unspaced_advance
+            }
+        } else {
+            Twips::new((advance.get() as f32 * scale) as i32) + params.letter_spacing
+        }
+    }
+
     /// Measure a particular string's width.
     pub fn measure(&self, text: &WStr, params: EvalParameters) -> Twips {
         let mut width = Twips::ZERO;
@@ -698,6 +1020,65 @@ impl<'gc> Font<'gc> {
         width
     }
 
+    /// Split text into runs of characters rendered with the same font.
+    ///
+    /// Characters this font has no glyphs for are rendered with the first of
+    /// the `fallbacks` that has them. Whitespace and combining characters stay
+    /// in the run of the preceding character, and characters that no font has
+    /// glyphs for are left to this font.
+    pub fn split_by_fallbacks(
+        self,
+        text: &WStr,
+        fallbacks: &[Font<'gc>],
+    ) -> Vec<(Range<usize>, Font<'gc>)> {
+        if fallbacks.is_empty() {
+            return vec![(0..text.len(), self)];
+        }
+
+        let mut runs: Vec<(Range<usize>, Font<'gc>)> = Vec::new();
+        for (pos, c) in text.char_indices() {
+            let c = c.unwrap_or(char::REPLACEMENT_CHARACTER);
+            let current = runs.last().map(|(_, font)| *font);
+            let font = match current {
+                Some(current) if is_cluster_continuation(c) => current,
+                _ if self.get_glyph_for_char(c).is_some() => self,
+                _ => fallbacks
+                    .iter()
+                    .copied()
+                    .find(|font| font.get_glyph_for_char(c).is_some())
+                    .unwrap_or(self),
+            };
+
+            if current.is_some_and(|current| Font::ptr_eq(current, font)) {
+                continue;
+            }
+            if let Some((range, _)) = runs.last_mut() {
+                range.end = pos;
+            }
+            runs.push((pos..text.len(), font));
+        }
+
+        if runs.is_empty() {
+            runs.push((0..text.len(), self));
+        }
+        runs
+    }
+
+    /// Measure a particular string's width, using fallback fonts for
+    /// characters this font has no glyphs for.
+    pub fn measure_with_fallbacks(
+        self,
+        text: &WStr,
+        params: EvalParameters,
+        fallbacks: &[Font<'gc>],
+    ) -> Twips {
+        self.split_by_fallbacks(text, fallbacks)
+            .into_iter()
+            .fold(Twips::ZERO, |width, (range, font)| {
+                width + font.measure(&text[range], params)
+            })
+    }
+
     /// Given a line of text, find the first breakpoint within the text.
     ///
     /// This function assumes only `" "` is valid whitespace to split words on,
@@ -718,6 +1099,7 @@ impl<'gc> Font<'gc> {
         &self,
         text: &WStr,
         params: EvalParameters,
+        fallbacks: &[Font<'gc>],
         width: Twips,
         offset: Twips,
         mut is_start_of_line: bool,
@@ -733,10 +1115,11 @@ impl<'gc> Font<'gc> {
             let word_start = word.offset_in(text).unwrap();
             let word_end = word_start + word.len();
 
-            let measure = self.measure(
+            let measure = self.measure_with_fallbacks(
                 // +1 is fine because ' ' is 1 unit
                 text.slice(word_start..word_end + 1).unwrap_or(word),
                 params,
+                fallbacks,
             );
 
             if is_start_of_line && measure > remaining_width {
@@ -755,7 +1138,8 @@ impl<'gc> Font<'gc> {
                     char_index = word_start + prev_frag_end;
 
                     if let Some((frag_end, _)) = char_iter.next() {
-                        last_passing_breakpoint = self.measure(&cur_slice[..frag_end], params);
+                        last_passing_breakpoint =
+                            self.measure_with_fallbacks(&cur_slice[..frag_end], params, fallbacks);
 
                         prev_frag_end = frag_end;
                     } else {
@@ -857,6 +1241,9 @@ pub struct Glyph {
     // It's a double option; the outer one is "have we registered", the inner one is option because it may not exist
     shape_handle: RefCell<Option<Option<ShapeHandle>>>,
 
+    // Bitmaps of this glyph rendered with advanced anti-aliasing.
+    bitmap_cache: GlyphBitmapCache,
+
     shape: GlyphShape,
     advance: Twips,
 
@@ -869,6 +1256,7 @@ impl Glyph {
     pub fn empty(character: char) -> Self {
         Self {
             shape_handle: Default::default(),
+            bitmap_cache: Default::default(),
             shape: GlyphShape::None,
             advance: Twips::ZERO,
             character,
@@ -1001,8 +1389,7 @@ pub enum TextRenderSettings {
     /// This text should render with the advanced rendering engine.
     /// Set via "Anti-alias for readability" in the Flash IDE.
     /// The parameters are set via the CSMTextSettings SWF tag.
-    /// Ruffle does not support this currently, but this also affects
-    /// hit-testing behavior.
+    /// This also affects hit-testing behavior.
     Advanced {
         grid_fit: TextGridFit,
         thickness: f32,
@@ -1196,8 +1583,14 @@ mod tests {
         with_device_font(|_mc, df| {
             let params = EvalParameters::from_parts(Twips::from_pixels(12.0), Twips::ZERO, true);
             let string = WStr::from_units(b"abcdefghijklmnopqrstuv");
-            let breakpoint =
-                df.wrap_line(string, params, Twips::from_pixels(200.0), Twips::ZERO, true);
+            let breakpoint = df.wrap_line(
+                string,
+                params,
+                &[],
+                Twips::from_pixels(200.0),
+                Twips::ZERO,
+                true,
+            );
 
             assert_eq!(None, breakpoint);
         });
@@ -1209,8 +1602,14 @@ mod tests {
             let params = EvalParameters::from_parts(Twips::from_pixels(12.0), Twips::ZERO, true);
             let string = WStr::from_units(b"abcd efgh ijkl mnop");
             let mut last_bp = 0;
-            let breakpoint =
-                df.wrap_line(string, params, Twips::from_pixels(35.0), Twips::ZERO, true);
+            let breakpoint = df.wrap_line(
+                string,
+                params,
+                &[],
+                Twips::from_pixels(35.0),
+                Twips::ZERO,
+                true,
+            );
 
             assert_eq!(Some(4), breakpoint);
 
@@ -1219,6 +1618,7 @@ mod tests {
             let breakpoint2 = df.wrap_line(
                 &string[last_bp..],
                 params,
+                &[],
                 Twips::from_pixels(35.0),
                 Twips::ZERO,
                 true,
@@ -1231,6 +1631,7 @@ mod tests {
             let breakpoint3 = df.wrap_line(
                 &string[last_bp..],
                 params,
+                &[],
                 Twips::from_pixels(35.0),
                 Twips::ZERO,
                 true,
@@ -1243,6 +1644,7 @@ mod tests {
             let breakpoint4 = df.wrap_line(
                 &string[last_bp..],
                 params,
+                &[],
                 Twips::from_pixels(35.0),
                 Twips::ZERO,
                 true,
@@ -1260,6 +1662,7 @@ mod tests {
             let breakpoint = df.wrap_line(
                 string,
                 params,
+                &[],
                 Twips::from_pixels(30.0),
                 Twips::from_pixels(29.0),
                 false,
@@ -1275,8 +1678,14 @@ mod tests {
             let params = EvalParameters::from_parts(Twips::from_pixels(12.0), Twips::ZERO, true);
             let string = WStr::from_units(b"abcdi j kl mnop q rstuv");
             let mut last_bp = 0;
-            let breakpoint =
-                df.wrap_line(string, params, Twips::from_pixels(37.0), Twips::ZERO, true);
+            let breakpoint = df.wrap_line(
+                string,
+                params,
+                &[],
+                Twips::from_pixels(37.0),
+                Twips::ZERO,
+                true,
+            );
 
             assert_eq!(Some(5), breakpoint);
 
@@ -1285,6 +1694,7 @@ mod tests {
             let breakpoint2 = df.wrap_line(
                 &string[last_bp..],
                 params,
+                &[],
                 Twips::from_pixels(37.0),
                 Twips::ZERO,
                 true,
@@ -1297,6 +1707,7 @@ mod tests {
             let breakpoint3 = df.wrap_line(
                 &string[last_bp..],
                 params,
+                &[],
                 Twips::from_pixels(37.0),
                 Twips::ZERO,
                 true,
@@ -1309,6 +1720,7 @@ mod tests {
             let breakpoint4 = df.wrap_line(
                 &string[last_bp..],
                 params,
+                &[],
                 Twips::from_pixels(37.0),
                 Twips::ZERO,
                 true,
@@ -1321,6 +1733,7 @@ mod tests {
             let breakpoint5 = df.wrap_line(
                 &string[last_bp..],
                 params,
+                &[],
                 Twips::from_pixels(37.0),
                 Twips::ZERO,
                 true,
@@ -1329,4 +1742,56 @@ mod tests {
             assert_eq!(None, breakpoint5);
         });
     }
+
+    #[test]
+    fn evaluate_right_to_left() {
+        with_device_font(|_mc, df| {
+            let params = EvalParameters::from_parts(Twips::from_pixels(12.0), Twips::ZERO, true)
+                .with_right_to_left(true);
+            let string = WStr::from_units(b"abc");
+            let mut glyphs = Vec::new();
+            df.evaluate(
+                string,
+                Default::default(),
+                params,
+                |pos, _transform, _glyph, advance, x| {
+                    glyphs.push((pos, x, advance));
+                },
+            );
+
+            let positions: Vec<usize> = glyphs.iter().map(|&(pos, _, _)| pos).collect();
+            assert_eq!(vec![2, 1, 0], positions);
+            for window in glyphs.windows(2) {
+                assert_eq!(window[0].1 + window[0].2, window[1].1);
+            }
+        });
+    }
+
+    #[test]
+    fn split_by_fallbacks() {
+        with_device_font(|mc, df| {
+            let empty = Font::empty_font(mc, "Empty", false, false, FontType::Device);
+            let string = WStr::from_units(b"ab cd");
+
+            let runs = empty.split_by_fallbacks(string, &[df]);
+            assert_eq!(1, runs.len());
+            assert_eq!(0..5, runs[0].0);
+            assert!(Font::ptr_eq(df, runs[0].1));
+
+            let runs = df.split_by_fallbacks(string, &[empty]);
+            assert_eq!(1, runs.len());
+            assert!(Font::ptr_eq(df, runs[0].1));
+        });
+    }
+
+    #[test]
+    fn missing_chars() {
+        with_device_font(|mc, df| {
+            let empty = Font::empty_font(mc, "Empty", false, false, FontType::Device);
+            let string = WStr::from_units(b"abba c");
+
+            assert_eq!(vec!['a', 'b', 'c'], empty.missing_chars(string));
+            assert!(df.missing_chars(string).is_empty());
+        });
+    }
 }
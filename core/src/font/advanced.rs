@@ -0,0 +1,849 @@
+//! Advanced anti-aliasing ("anti-alias for readability") text rendering.
+//!
+//! Flash Player renders advanced anti-aliased text from distance fields of
+//! each glyph at the size it appears on screen, optionally fitted to the pixel
+//! grid, and maps distances to opacity using continuous stroke modulation
+//! (CSM) cutoffs. We do the same on the CPU: glyph outlines are flattened,
+//! the signed distance to the outline is computed for every pixel, and the
+//! resulting bitmaps are cached on each [`Glyph`].
+
+use crate::context::RenderContext;
+use crate::font::{Font, FontDescriptor, Glyph, GlyphShape, TextRenderSettings};
+use crate::prelude::*;
+use crate::string::WStr;
+use fnv::FnvHashMap;
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapHandle, PixelSnapping};
+use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::shape_utils::{DistilledShape, DrawCommand, DrawPath, FillRule};
+use ruffle_render::transform::Transform;
+use std::cell::RefCell;
+use swf::{BlendMode, ColorTransform, Fixed8, TextGridFit};
+
+/// Text larger than this many pixels is rendered as shapes, like in Flash Player.
+const MAX_FONT_SIZE: f32 = 255.0;
+
+/// The number of rasterized variants kept for a single glyph.
+const MAX_CACHED_BITMAPS: usize = 32;
+
+/// How far outside of a glyph, in pixels, its bitmap may extend.
+const MAX_MARGIN: f32 = 4.0;
+
+/// The mode used to display advanced anti-aliased text, set through
+/// `TextRenderer.displayMode`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TextDisplayMode {
+    /// Let the player choose. Ruffle can't detect the kind of display, so
+    /// this renders like [`TextDisplayMode::Crt`].
+    #[default]
+    Default,
+
+    /// Subpixel anti-aliasing, for LCD displays with RGB subpixels.
+    Lcd,
+
+    /// Grayscale anti-aliasing.
+    Crt,
+}
+
+impl TextDisplayMode {
+    pub fn from_wstr(mode: &WStr) -> Option<Self> {
+        if mode == b"default" {
+            Some(Self::Default)
+        } else if mode == b"lcd" {
+            Some(Self::Lcd)
+        } else if mode == b"crt" {
+            Some(Self::Crt)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Lcd => "lcd",
+            Self::Crt => "crt",
+        }
+    }
+}
+
+/// Whether a CSM table is meant for dark text on a light background or the
+/// other way around, see `flash.text.TextColorType`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TextColorType {
+    Dark,
+    Light,
+}
+
+impl TextColorType {
+    pub fn from_wstr(color_type: &WStr) -> Option<Self> {
+        if color_type == b"dark" {
+            Some(Self::Dark)
+        } else if color_type == b"light" {
+            Some(Self::Light)
+        } else {
+            None
+        }
+    }
+
+    fn from_color(color: Color) -> Self {
+        let luminance =
+            0.299 * f32::from(color.r) + 0.587 * f32::from(color.g) + 0.114 * f32::from(color.b);
+        if luminance < 128.0 {
+            Self::Dark
+        } else {
+            Self::Light
+        }
+    }
+}
+
+/// An entry of a CSM table, see `flash.text.CSMSettings`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CsmSettings {
+    /// The font size, in pixels, these cutoffs are meant for.
+    pub font_size: f32,
+
+    /// The distance from the outline, in pixels, past which the inside of a
+    /// glyph is fully opaque.
+    pub inside_cutoff: f32,
+
+    /// The distance from the outline, in pixels, past which the outside of a
+    /// glyph is fully transparent. Distances outside the glyph are negative.
+    pub outside_cutoff: f32,
+}
+
+/// The global state of the advanced anti-aliasing renderer, controlled by
+/// `flash.text.TextRenderer`.
+#[derive(Debug)]
+pub struct TextRenderer {
+    display_mode: TextDisplayMode,
+
+    /// The ADF quality level. Distances are computed exactly, so this is
+    /// only remembered for ActionScript.
+    max_level: i32,
+
+    /// Custom CSM tables, keyed by lowercase font name, boldness, italicness
+    /// and color type. Entries are sorted by font size.
+    tables: FnvHashMap<(String, bool, bool, TextColorType), Vec<CsmSettings>>,
+}
+
+impl Default for TextRenderer {
+    fn default() -> Self {
+        Self {
+            display_mode: TextDisplayMode::Default,
+            max_level: 4,
+            tables: Default::default(),
+        }
+    }
+}
+
+impl TextRenderer {
+    pub fn display_mode(&self) -> TextDisplayMode {
+        self.display_mode
+    }
+
+    pub fn set_display_mode(&mut self, display_mode: TextDisplayMode) {
+        self.display_mode = display_mode;
+    }
+
+    pub fn max_level(&self) -> i32 {
+        self.max_level
+    }
+
+    pub fn set_max_level(&mut self, max_level: i32) {
+        self.max_level = max_level;
+    }
+
+    /// Sets the CSM table used for a font with the given style and color type.
+    pub fn set_table(
+        &mut self,
+        font_name: &str,
+        is_bold: bool,
+        is_italic: bool,
+        color_type: TextColorType,
+        mut table: Vec<CsmSettings>,
+    ) {
+        table.sort_by(|a, b| a.font_size.total_cmp(&b.font_size));
+        let key = (font_name.to_lowercase(), is_bold, is_italic, color_type);
+        if table.is_empty() {
+            self.tables.remove(&key);
+        } else {
+            self.tables.insert(key, table);
+        }
+    }
+
+    /// Returns the cutoffs to use for a font at the given size in pixels,
+    /// interpolating between the entries of its CSM table.
+    fn cutoffs(
+        &self,
+        descriptor: &FontDescriptor,
+        font_size: f32,
+        color_type: TextColorType,
+    ) -> Cutoffs {
+        let key = (
+            descriptor.lowercase_name.clone(),
+            descriptor.is_bold,
+            descriptor.is_italic,
+            color_type,
+        );
+        let Some(table) = self.tables.get(&key) else {
+            return Cutoffs::DEFAULT;
+        };
+
+        let next = table.partition_point(|entry| entry.font_size < font_size);
+        let (low, high) = match (next.checked_sub(1), table.get(next)) {
+            (Some(previous), Some(next)) => (table[previous], *next),
+            (Some(previous), None) => (table[previous], table[previous]),
+            (None, Some(next)) => (*next, *next),
+            (None, None) => return Cutoffs::DEFAULT,
+        };
+        let t = if high.font_size > low.font_size {
+            (font_size - low.font_size) / (high.font_size - low.font_size)
+        } else {
+            0.0
+        };
+        Cutoffs {
+            inside: low.inside_cutoff + (high.inside_cutoff - low.inside_cutoff) * t,
+            outside: low.outside_cutoff + (high.outside_cutoff - low.outside_cutoff) * t,
+        }
+    }
+}
+
+/// Distances from a glyph outline, in pixels, between which its opacity
+/// goes from transparent to opaque.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Cutoffs {
+    inside: f32,
+    outside: f32,
+}
+
+impl Cutoffs {
+    /// Without a CSM table, opacity follows the area of a pixel inside the
+    /// glyph, like regular anti-aliasing.
+    const DEFAULT: Self = Self {
+        inside: 0.5,
+        outside: -0.5,
+    };
+
+    /// Applies the thickness and sharpness of a text field.
+    ///
+    /// A thickness of 200 makes strokes half a pixel bolder on each side,
+    /// and every 200 of sharpness halves the width of the blurred edge.
+    fn adjusted(self, settings: TextRenderSettings) -> Self {
+        let center = (self.inside + self.outside) / 2.0 - settings.thickness() / 400.0;
+        let half_width = ((self.inside - self.outside) / 2.0).max(1.0 / 64.0)
+            * (-settings.sharpness() / 200.0).exp2();
+        Self {
+            inside: center + half_width,
+            outside: center - half_width,
+        }
+    }
+
+    fn alpha(self, distance: f32) -> f32 {
+        ((distance - self.outside) / (self.inside - self.outside)).clamp(0.0, 1.0)
+    }
+}
+
+/// Identifies one rasterized variant of a glyph.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct BitmapKey {
+    /// The size of the EM square in 1/64 pixels.
+    em_width: u32,
+    em_height: u32,
+
+    /// The offset of the glyph from the pixel grid in 1/12 pixels.
+    phase_x: u8,
+    phase_y: u8,
+
+    /// The cutoffs in 1/256 pixels.
+    inside: i32,
+    outside: i32,
+
+    lcd: bool,
+}
+
+impl BitmapKey {
+    fn cutoffs(&self) -> Cutoffs {
+        Cutoffs {
+            inside: self.inside as f32 / 256.0,
+            outside: self.outside as f32 / 256.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct GlyphBitmap {
+    handle: BitmapHandle,
+
+    /// The position of the bitmap relative to the glyph origin, in pixels.
+    x: i32,
+    y: i32,
+}
+
+/// The bitmaps of a glyph rendered with advanced anti-aliasing.
+#[derive(Clone, Debug, Default)]
+pub(super) struct GlyphBitmapCache(RefCell<FnvHashMap<BitmapKey, Option<GlyphBitmap>>>);
+
+impl GlyphBitmapCache {
+    fn get_or_insert_with(
+        &self,
+        key: BitmapKey,
+        f: impl FnOnce() -> Option<GlyphBitmap>,
+    ) -> Option<GlyphBitmap> {
+        let mut bitmaps = self.0.borrow_mut();
+        if let Some(bitmap) = bitmaps.get(&key) {
+            return bitmap.clone();
+        }
+        // Animated text can be rendered at many different sizes, don't keep them all.
+        if bitmaps.len() >= MAX_CACHED_BITMAPS {
+            bitmaps.clear();
+        }
+        bitmaps.entry(key).or_insert_with(f).clone()
+    }
+}
+
+/// Renders the glyphs of a font with advanced anti-aliasing.
+///
+/// Glyphs rendered in LCD mode are collected and drawn when [`Self::finish`]
+/// is called, since they have to be blended with what's below them.
+pub struct AdvancedGlyphRenderer<'gc> {
+    font: Font<'gc>,
+    settings: TextRenderSettings,
+    lcd_glyphs: Vec<(BitmapHandle, Transform)>,
+}
+
+impl<'gc> AdvancedGlyphRenderer<'gc> {
+    /// Returns a renderer for the glyphs of `font`, if text with these
+    /// settings uses advanced anti-aliasing.
+    pub fn new(font: Font<'gc>, settings: TextRenderSettings) -> Option<Self> {
+        // Flash Player only uses advanced anti-aliasing for embedded fonts.
+        if !settings.is_advanced() || !font.font_type().is_embedded() {
+            return None;
+        }
+        Some(Self {
+            font,
+            settings,
+            lcd_glyphs: Vec::new(),
+        })
+    }
+
+    /// Renders a glyph with the current transform of the render context.
+    ///
+    /// Returns `false` when the glyph can't be rendered with advanced
+    /// anti-aliasing, and should be rendered as a shape instead.
+    pub fn render_glyph(&mut self, context: &mut RenderContext<'_, 'gc>, glyph: &Glyph) -> bool {
+        let transform = context.transform_stack.transform();
+        let matrix = transform.matrix;
+
+        // Rotated, skewed and flipped text is rendered as shapes.
+        if matrix.b != 0.0 || matrix.c != 0.0 || matrix.a <= 0.0 || matrix.d <= 0.0 {
+            return false;
+        }
+        let em_width = matrix.a * self.font.scale() / 20.0;
+        let em_height = matrix.d * self.font.scale() / 20.0;
+        if em_width > MAX_FONT_SIZE || em_height > MAX_FONT_SIZE {
+            return false;
+        }
+
+        let text_renderer = context.library.text_renderer();
+        let color_type = TextColorType::from_color(&transform.color_transform * Color::WHITE);
+        let cutoffs = text_renderer
+            .cutoffs(self.font.descriptor(), em_height, color_type)
+            .adjusted(self.settings);
+        // Subpixel rendering needs to know the background, which offscreen
+        // surfaces such as `BitmapData.draw` targets may not have.
+        let lcd = text_renderer.display_mode() == TextDisplayMode::Lcd && !context.is_offscreen;
+
+        // Grid fitting snaps the glyph origin. Without it, the origin is still
+        // quantized so that bitmaps can be reused.
+        let (step_x, step_y) = match self.settings.grid_fit() {
+            TextGridFit::Pixel => (1.0, 1.0),
+            TextGridFit::SubPixel => (1.0 / 3.0, 1.0),
+            TextGridFit::None => (0.25, 0.25),
+        };
+        let x = (matrix.tx.to_pixels() as f32 / step_x).round() * step_x;
+        let y = (matrix.ty.to_pixels() as f32 / step_y).round() * step_y;
+        let (origin_x, origin_y) = (x.floor(), y.floor());
+
+        let key = BitmapKey {
+            em_width: (em_width * 64.0).round() as u32,
+            em_height: (em_height * 64.0).round() as u32,
+            phase_x: ((x - origin_x) * 12.0).round() as u8,
+            phase_y: ((y - origin_y) * 12.0).round() as u8,
+            inside: (cutoffs.inside * 256.0).round() as i32,
+            outside: (cutoffs.outside * 256.0).round() as i32,
+            lcd,
+        };
+        let font_scale = self.font.scale();
+        let bitmap = glyph.bitmap_cache.get_or_insert_with(key, || {
+            let pixels = rasterize_glyph(&glyph.shape, font_scale, key)?;
+            let bitmap = Bitmap::new(pixels.width, pixels.height, BitmapFormat::Rgba, pixels.data);
+            match context.renderer.register_bitmap(bitmap) {
+                Ok(handle) => Some(GlyphBitmap {
+                    handle,
+                    x: pixels.x,
+                    y: pixels.y,
+                }),
+                Err(e) => {
+                    tracing::error!("Failed to register glyph bitmap: {e}");
+                    None
+                }
+            }
+        });
+
+        // Glyphs without outlines have nothing to draw.
+        if let Some(bitmap) = bitmap {
+            let transform = Transform {
+                matrix: Matrix::translate(
+                    Twips::from_pixels(f64::from(origin_x as i32 + bitmap.x)),
+                    Twips::from_pixels(f64::from(origin_y as i32 + bitmap.y)),
+                ),
+                color_transform: transform.color_transform,
+            };
+            if lcd {
+                self.lcd_glyphs.push((bitmap.handle, transform));
+            } else {
+                context.commands.render_bitmap(
+                    bitmap.handle,
+                    transform,
+                    false,
+                    PixelSnapping::Never,
+                );
+            }
+        }
+        true
+    }
+
+    /// Draws the glyphs that were rendered in LCD mode.
+    pub fn finish(self, context: &mut RenderContext<'_, 'gc>) {
+        if self.lcd_glyphs.is_empty() {
+            return;
+        }
+
+        // Each color channel of a glyph bitmap holds the coverage of that
+        // subpixel. The background is first darkened by the coverage of each
+        // channel, then the text color is added in the same proportion.
+        let mut darken = CommandList::new();
+        let mut colorize = CommandList::new();
+        for (handle, transform) in self.lcd_glyphs {
+            let color = &transform.color_transform * Color::WHITE;
+            let alpha = Fixed8::from_f32(f32::from(color.a) / 255.0);
+
+            darken.render_bitmap(
+                handle.clone(),
+                Transform {
+                    matrix: transform.matrix,
+                    color_transform: ColorTransform {
+                        r_multiply: Fixed8::from_f32(-1.0),
+                        g_multiply: Fixed8::from_f32(-1.0),
+                        b_multiply: Fixed8::from_f32(-1.0),
+                        a_multiply: alpha,
+                        r_add: 255,
+                        g_add: 255,
+                        b_add: 255,
+                        a_add: 0,
+                    },
+                },
+                false,
+                PixelSnapping::Never,
+            );
+
+            colorize.render_bitmap(
+                handle,
+                Transform {
+                    matrix: transform.matrix,
+                    color_transform: ColorTransform::multiply_from(color),
+                },
+                false,
+                PixelSnapping::Never,
+            );
+        }
+        context
+            .commands
+            .blend(darken, RenderBlendMode::Builtin(BlendMode::Multiply));
+        context
+            .commands
+            .blend(colorize, RenderBlendMode::Builtin(BlendMode::Add));
+    }
+}
+
+/// Premultiplied RGBA pixels of a rasterized glyph.
+#[derive(Debug)]
+struct GlyphPixels {
+    /// The position of the pixels relative to the glyph origin.
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+fn rasterize_glyph(shape: &GlyphShape, font_scale: f32, key: BitmapKey) -> Option<GlyphPixels> {
+    let scale_x = key.em_width as f32 / 64.0 / font_scale;
+    let scale_y = key.em_height as f32 / 64.0 / font_scale;
+    let phase_x = f32::from(key.phase_x) / 12.0;
+    let phase_y = f32::from(key.phase_y) / 12.0;
+    let to_pixels = |point: Point<Twips>| {
+        (
+            point.x.get() as f32 * scale_x + phase_x,
+            point.y.get() as f32 * scale_y + phase_y,
+        )
+    };
+
+    let mut outlines = Vec::new();
+    match shape {
+        GlyphShape::Swf(glyph) => {
+            let mut glyph = glyph.borrow_mut();
+            let shape = DistilledShape::from(&*glyph.shape());
+            for path in &shape.paths {
+                if let DrawPath::Fill {
+                    commands,
+                    winding_rule,
+                    ..
+                } = path
+                {
+                    outlines.push(Outline::new(commands, *winding_rule, &to_pixels));
+                }
+            }
+        }
+        GlyphShape::Drawing(drawing) => {
+            for (commands, rule) in drawing.fills() {
+                outlines.push(Outline::new(commands, rule, &to_pixels));
+            }
+        }
+        GlyphShape::None => {}
+    }
+
+    rasterize(&outlines, key.cutoffs(), key.lcd)
+}
+
+/// A line segment of a glyph outline, in pixels.
+#[derive(Clone, Copy, Debug)]
+struct Segment {
+    from: (f32, f32),
+    to: (f32, f32),
+}
+
+impl Segment {
+    fn distance(&self, x: f32, y: f32) -> f32 {
+        let (dx, dy) = (self.to.0 - self.from.0, self.to.1 - self.from.1);
+        let length = dx * dx + dy * dy;
+        let t = if length > 0.0 {
+            (((x - self.from.0) * dx + (y - self.from.1) * dy) / length).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        (x - self.from.0 - t * dx).hypot(y - self.from.1 - t * dy)
+    }
+}
+
+/// A flattened glyph fill, made of closed contours.
+#[derive(Debug)]
+struct Outline {
+    segments: Vec<Segment>,
+    rule: FillRule,
+}
+
+impl Outline {
+    fn new(
+        commands: &[DrawCommand],
+        rule: FillRule,
+        to_pixels: impl Fn(Point<Twips>) -> (f32, f32),
+    ) -> Self {
+        fn lerp(a: (f32, f32), b: (f32, f32), t: f32) -> (f32, f32) {
+            (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+        }
+
+        fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+            (b.0 - a.0).hypot(b.1 - a.1)
+        }
+
+        /// Curves are split into segments about a pixel long.
+        fn steps(length: f32) -> u32 {
+            (length.ceil() as u32).clamp(1, 32)
+        }
+
+        fn line_to(segments: &mut Vec<Segment>, cursor: &mut (f32, f32), to: (f32, f32)) {
+            if *cursor != to {
+                segments.push(Segment { from: *cursor, to });
+            }
+            *cursor = to;
+        }
+
+        let mut segments = Vec::new();
+        let mut start = (0.0, 0.0);
+        let mut cursor = start;
+
+        for command in commands {
+            match *command {
+                DrawCommand::MoveTo(point) => {
+                    line_to(&mut segments, &mut cursor, start);
+                    start = to_pixels(point);
+                    cursor = start;
+                }
+                DrawCommand::LineTo(point) => {
+                    line_to(&mut segments, &mut cursor, to_pixels(point));
+                }
+                DrawCommand::QuadraticCurveTo { control, anchor } => {
+                    let from = cursor;
+                    let (control, anchor) = (to_pixels(control), to_pixels(anchor));
+                    let steps = steps(distance(from, control) + distance(control, anchor));
+                    for step in 1..=steps {
+                        let t = step as f32 / steps as f32;
+                        let point = lerp(lerp(from, control, t), lerp(control, anchor, t), t);
+                        line_to(&mut segments, &mut cursor, point);
+                    }
+                }
+                DrawCommand::CubicCurveTo {
+                    control_a,
+                    control_b,
+                    anchor,
+                } => {
+                    let from = cursor;
+                    let (control_a, control_b, anchor) = (
+                        to_pixels(control_a),
+                        to_pixels(control_b),
+                        to_pixels(anchor),
+                    );
+                    let steps = steps(
+                        distance(from, control_a)
+                            + distance(control_a, control_b)
+                            + distance(control_b, anchor),
+                    );
+                    for step in 1..=steps {
+                        let t = step as f32 / steps as f32;
+                        let ab = lerp(from, control_a, t);
+                        let bc = lerp(control_a, control_b, t);
+                        let cd = lerp(control_b, anchor, t);
+                        let point = lerp(lerp(ab, bc, t), lerp(bc, cd, t), t);
+                        line_to(&mut segments, &mut cursor, point);
+                    }
+                }
+            }
+        }
+        line_to(&mut segments, &mut cursor, start);
+
+        Self { segments, rule }
+    }
+
+    /// Returns where a horizontal line crosses this outline, sorted by x,
+    /// along with the winding direction of each crossing.
+    fn crossings(&self, y: f32) -> Vec<(f32, i32)> {
+        let mut crossings: Vec<_> = self
+            .segments
+            .iter()
+            .filter_map(|segment| {
+                let (top, bottom, direction) = if segment.from.1 < segment.to.1 {
+                    (segment.from, segment.to, 1)
+                } else {
+                    (segment.to, segment.from, -1)
+                };
+                (top.1 <= y && y < bottom.1).then(|| {
+                    let x = top.0 + (y - top.1) * (bottom.0 - top.0) / (bottom.1 - top.1);
+                    (x, direction)
+                })
+            })
+            .collect();
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+        crossings
+    }
+
+    /// Whether a point is inside this outline, given the crossings of its row.
+    fn contains(&self, crossings: &[(f32, i32)], x: f32) -> bool {
+        let winding: i32 = crossings
+            .iter()
+            .take_while(|(crossing, _)| *crossing < x)
+            .map(|(_, direction)| direction)
+            .sum();
+        match self.rule {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+/// Rasterizes outlines in pixel space, mapping the signed distance of each
+/// pixel center from the outlines to opacity.
+///
+/// In LCD mode, the distance is measured at each of the three subpixels, and
+/// each color channel holds the opacity of its subpixel.
+fn rasterize(outlines: &[Outline], cutoffs: Cutoffs, lcd: bool) -> Option<GlyphPixels> {
+    let points = outlines
+        .iter()
+        .flat_map(|outline| &outline.segments)
+        .map(|segment| segment.from);
+    let (min_x, min_y, max_x, max_y) = points.fold(
+        (
+            f32::INFINITY,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::NEG_INFINITY,
+        ),
+        |(min_x, min_y, max_x, max_y), (x, y)| {
+            (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+        },
+    );
+    if min_x > max_x || min_y > max_y {
+        return None;
+    }
+
+    let margin = (-cutoffs.outside).clamp(0.0, MAX_MARGIN).ceil() as i32 + 1;
+    let left = min_x.floor() as i32 - margin;
+    let top = min_y.floor() as i32 - margin;
+    let width = (max_x.ceil() as i32 + margin - left) as u32;
+    let height = (max_y.ceil() as i32 + margin - top) as u32;
+
+    let subpixels: &[f32] = if lcd {
+        &[1.0 / 6.0, 0.5, 5.0 / 6.0]
+    } else {
+        &[0.5]
+    };
+    let mut data = Vec::with_capacity(width as usize * height as usize * 4);
+    for row in 0..height {
+        let y = (top + row as i32) as f32 + 0.5;
+        let crossings: Vec<_> = outlines
+            .iter()
+            .map(|outline| outline.crossings(y))
+            .collect();
+        for column in 0..width {
+            let mut alpha = [0.0; 3];
+            for (value, subpixel) in alpha.iter_mut().zip(subpixels) {
+                let x = (left + column as i32) as f32 + subpixel;
+                let inside = outlines
+                    .iter()
+                    .zip(&crossings)
+                    .any(|(outline, crossings)| outline.contains(crossings, x));
+                let distance = outlines
+                    .iter()
+                    .flat_map(|outline| &outline.segments)
+                    .map(|segment| segment.distance(x, y))
+                    .fold(f32::INFINITY, f32::min);
+                *value = cutoffs.alpha(if inside { distance } else { -distance });
+            }
+            let [r, g, b] = if lcd { alpha } else { [alpha[0]; 3] };
+            let a = r.max(g).max(b);
+            data.extend([r, g, b, a].map(|value| (value * 255.0).round() as u8));
+        }
+    }
+
+    Some(GlyphPixels {
+        x: left,
+        y: top,
+        width,
+        height,
+        data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: i32, y: i32, size: i32) -> Outline {
+        let point = |x: i32, y: i32| Point::new(Twips::new(x), Twips::new(y));
+        let commands = [
+            DrawCommand::MoveTo(point(x, y)),
+            DrawCommand::LineTo(point(x + size, y)),
+            DrawCommand::LineTo(point(x + size, y + size)),
+            DrawCommand::LineTo(point(x, y + size)),
+        ];
+        Outline::new(&commands, FillRule::NonZero, |point| {
+            (point.x.get() as f32, point.y.get() as f32)
+        })
+    }
+
+    fn alpha_at(pixels: &GlyphPixels, x: i32, y: i32) -> [u8; 4] {
+        let index = ((y - pixels.y) as usize * pixels.width as usize + (x - pixels.x) as usize) * 4;
+        pixels.data[index..index + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn rasterize_pixel_aligned_square() {
+        let pixels = rasterize(&[square(2, 2, 3)], Cutoffs::DEFAULT, false).unwrap();
+        assert_eq!(alpha_at(&pixels, 3, 3), [255; 4]);
+        assert_eq!(alpha_at(&pixels, 2, 2), [255; 4]);
+        assert_eq!(alpha_at(&pixels, 1, 3), [0; 4]);
+        assert_eq!(alpha_at(&pixels, 5, 3), [0; 4]);
+    }
+
+    #[test]
+    fn rasterize_thickness() {
+        let settings = TextRenderSettings::default()
+            .with_advanced_rendering()
+            .with_thickness(200.0);
+        let cutoffs = Cutoffs::DEFAULT.adjusted(settings);
+        let pixels = rasterize(&[square(2, 2, 3)], cutoffs, false).unwrap();
+        // The pixels next to the square are now half covered.
+        assert_eq!(alpha_at(&pixels, 1, 3), [128; 4]);
+        assert_eq!(alpha_at(&pixels, 0, 3), [0; 4]);
+    }
+
+    #[test]
+    fn rasterize_lcd_subpixels() {
+        let outline = Outline::new(
+            &[
+                DrawCommand::MoveTo(Point::new(Twips::new(0), Twips::new(0))),
+                DrawCommand::LineTo(Point::new(Twips::new(2), Twips::new(0))),
+                DrawCommand::LineTo(Point::new(Twips::new(2), Twips::new(4))),
+                DrawCommand::LineTo(Point::new(Twips::new(0), Twips::new(4))),
+            ],
+            FillRule::NonZero,
+            // Shift the outline by half a pixel.
+            |point| (point.x.get() as f32 + 0.5, point.y.get() as f32),
+        );
+        let pixels = rasterize(&[outline], Cutoffs::DEFAULT, true).unwrap();
+        let [r, g, b, a] = alpha_at(&pixels, 0, 2);
+        assert!(r < g && g < b);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn csm_table_interpolation() {
+        let mut text_renderer = TextRenderer::default();
+        let descriptor = FontDescriptor::from_parts("Test Font", false, false);
+        text_renderer.set_table(
+            "test font",
+            false,
+            false,
+            TextColorType::Dark,
+            vec![
+                CsmSettings {
+                    font_size: 20.0,
+                    inside_cutoff: 1.0,
+                    outside_cutoff: -1.0,
+                },
+                CsmSettings {
+                    font_size: 10.0,
+                    inside_cutoff: 0.5,
+                    outside_cutoff: 0.0,
+                },
+            ],
+        );
+
+        let cutoffs = |size| text_renderer.cutoffs(&descriptor, size, TextColorType::Dark);
+        assert_eq!(
+            cutoffs(5.0),
+            Cutoffs {
+                inside: 0.5,
+                outside: 0.0
+            }
+        );
+        assert_eq!(
+            cutoffs(15.0),
+            Cutoffs {
+                inside: 0.75,
+                outside: -0.5
+            }
+        );
+        assert_eq!(
+            cutoffs(30.0),
+            Cutoffs {
+                inside: 1.0,
+                outside: -1.0
+            }
+        );
+        assert_eq!(
+            text_renderer.cutoffs(&descriptor, 15.0, TextColorType::Light),
+            Cutoffs::DEFAULT
+        );
+    }
+}
@@ -105,6 +105,8 @@ pub fn run_all_phases_avm2(context: &mut UpdateContext<'_>) {
     // a result of a RemoveObject tag - see `cleanup_dead_orphans` for details.
     Avm2::cleanup_dead_orphans(context);
 
+    Avm2::run_sampler_callback(context);
+
     *context.frame_phase = FramePhase::Idle;
 }
 
@@ -5,12 +5,14 @@ mod iterators;
 mod layout;
 mod text_format;
 
-pub use dimensions::Position;
+pub use dimensions::{Position, Size};
 pub use layout::{
-    lower_from_text_spans, Layout, LayoutBox, LayoutContent, LayoutLine, LayoutMetrics,
+    lower_from_text_spans, Layout, LayoutBox, LayoutContent, LayoutImage, LayoutLine, LayoutMetrics,
 };
 pub use style_sheet::{parse_font_list, transform_dashes_to_camel_case, CssStream, StyleSheet};
-pub use text_format::{FormatSpans, TextDisplay, TextFormat, TextSpan};
+pub use text_format::{
+    FormatSpans, HtmlImage, ImageAlign, LinkState, TextDisplay, TextFormat, TextSpan,
+};
 
 mod style_sheet;
 #[cfg(test)]
@@ -4,7 +4,7 @@ use crate::context::UpdateContext;
 use crate::drawing::Drawing;
 use crate::font::{EvalParameters, Font, FontType};
 use crate::html::dimensions::{BoxBounds, Position, Size};
-use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::html::text_format::{FormatSpans, HtmlImage, ImageAlign, TextFormat, TextSpan};
 use crate::string::{utils as string_utils, WStr};
 use crate::tag_utils::SwfMovie;
 use crate::DefaultFont;
@@ -48,6 +48,71 @@ fn draw_underline(
     )));
 }
 
+/// Resolve bidi embedding levels of each code unit of the given text.
+///
+/// Paragraphs are determined and resolved separately, according to the
+/// Unicode Bidirectional Algorithm. An empty list is returned for text which
+/// doesn't contain any right-to-left characters.
+pub(super) fn resolve_bidi_levels(text: &WStr) -> Vec<u8> {
+    let char_indices: Vec<(usize, char)> = text
+        .char_indices()
+        .map(|(pos, c)| (pos, c.unwrap_or(char::REPLACEMENT_CHARACTER)))
+        .collect();
+    let utf8: String = char_indices.iter().map(|&(_, c)| c).collect();
+    let bidi_info = unicode_bidi::BidiInfo::new(&utf8, None);
+    if !bidi_info.has_rtl() {
+        return Vec::new();
+    }
+
+    let mut levels = vec![0; text.len()];
+    let mut byte_offset = 0;
+    for (i, &(pos, c)) in char_indices.iter().enumerate() {
+        let next_pos = char_indices.get(i + 1).map_or(text.len(), |&(pos, _)| pos);
+        levels[pos..next_pos].fill(bidi_info.levels[byte_offset].number());
+        byte_offset += c.len_utf8();
+    }
+    levels
+}
+
+/// Find the visual order of runs of text from their bidi embedding levels, in logical order.
+///
+/// From the highest level to the lowest odd level, any contiguous sequence
+/// of runs at that level or higher is reversed.
+pub(super) fn visual_order(levels: &[u8]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..levels.len()).collect();
+    let (Some(&min_level), Some(&max_level)) = (levels.iter().min(), levels.iter().max()) else {
+        return order;
+    };
+
+    let lowest_odd_level = min_level | 1;
+    for level in (lowest_odd_level..=max_level).rev() {
+        let mut i = 0;
+        while i < order.len() {
+            if levels[order[i]] < level {
+                i += 1;
+                continue;
+            }
+            let run_end = (i..order.len())
+                .find(|&j| levels[order[j]] < level)
+                .unwrap_or(order.len());
+            order[i..run_end].reverse();
+            i = run_end;
+        }
+    }
+    order
+}
+
+/// An image placed at the side of the text field, which text flows around.
+struct ImageFloat {
+    align: ImageAlign,
+
+    /// The horizontal space taken by the image, including its spacing.
+    width: Twips,
+
+    /// The vertical position at which text may use the space again.
+    bottom: Twips,
+}
+
 /// Contains information relating to the current layout operation.
 pub struct LayoutContext<'a, 'gc> {
     /// The movie this layout context is pulling fonts from.
@@ -75,9 +140,17 @@ pub struct LayoutContext<'a, 'gc> {
     /// The resolved font object to use when measuring text.
     font: Option<Font<'gc>>,
 
+    /// Fonts used for characters that the resolved font has no glyphs for.
+    fallback_fonts: Vec<Font<'gc>>,
+
     /// The underlying bundle of text being formatted.
     text: &'a WStr,
 
+    /// Bidi embedding levels of each code unit of the text.
+    ///
+    /// Empty when the text is entirely left-to-right.
+    bidi_levels: Vec<u8>,
+
     /// The highest font size observed within the current line.
     max_font_size: Twips,
 
@@ -130,13 +203,30 @@ pub struct LayoutContext<'a, 'gc> {
 
     /// The total width of the text field being laid out.
     max_bounds: Twips,
+
+    /// Images embedded in the text being laid out.
+    images: &'a [HtmlImage],
+
+    /// The sizes of embedded images, in the same order as `images`.
+    ///
+    /// Images without a known size are laid out with their declared size.
+    image_sizes: &'a [Size<Twips>],
+
+    /// Images that have been placed so far.
+    laid_out_images: Vec<LayoutImage>,
+
+    /// Placed images that text may still have to flow around.
+    floats: Vec<ImageFloat>,
 }
 
 impl<'a, 'gc> LayoutContext<'a, 'gc> {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         movie: Arc<SwfMovie>,
         max_bounds: Twips,
         text: &'a WStr,
+        images: &'a [HtmlImage],
+        image_sizes: &'a [Size<Twips>],
         is_input: bool,
         is_word_wrap: bool,
         font_type: FontType,
@@ -145,7 +235,9 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             movie,
             cursor: Default::default(),
             font: None,
+            fallback_fonts: Vec::new(),
             text,
+            bidi_levels: resolve_bidi_levels(text),
             max_font_size: Default::default(),
             max_ascent: Default::default(),
             max_descent: Default::default(),
@@ -159,6 +251,10 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             has_line_break: false,
             current_line_span: Default::default(),
             max_bounds,
+            images,
+            image_sizes,
+            laid_out_images: Vec::new(),
+            floats: Vec::new(),
             is_input,
             is_word_wrap,
             font_type,
@@ -166,8 +262,28 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     }
 
     fn lay_out_spans(&mut self, context: &mut UpdateContext<'gc>, fs: &'a FormatSpans) {
-        for (span_start, _end, span_text, span) in fs.iter_spans() {
-            self.lay_out_span(context, span_start, span_text, span);
+        for (span_start, span_end, span_text, span) in fs.iter_spans() {
+            // Spans are laid out in pieces separated by images,
+            // so that text after an image flows around it.
+            let mut piece_start = span_start;
+            loop {
+                let piece_end = self
+                    .images
+                    .iter()
+                    .map(|image| image.position)
+                    .find(|&position| piece_start < position && position < span_end)
+                    .unwrap_or(span_end);
+                self.lay_out_span(
+                    context,
+                    piece_start,
+                    &span_text[piece_start - span_start..piece_end - span_start],
+                    span,
+                );
+                piece_start = piece_end;
+                if piece_start >= span_end {
+                    break;
+                }
+            }
         }
     }
 
@@ -180,7 +296,9 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     ) {
         let font = self.resolve_font(context, span);
         self.font = Some(font);
+        self.fallback_fonts = self.resolve_fallback_fonts(context, span, span_text, font);
         self.newspan(span);
+        self.place_images(span_start);
 
         let params = EvalParameters::from_span(span);
 
@@ -212,6 +330,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
                 while let Some(breakpoint) = font.wrap_line(
                     &text[last_breakpoint..],
                     params,
+                    &self.fallback_fonts,
                     width,
                     offset,
                     self.is_start_of_line(),
@@ -303,7 +422,12 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         let mut line_drawing = Drawing::new();
         let mut has_underline: bool = false;
 
-        for linebox in self.boxes.iter() {
+        // Underlines are extended from left to right, which may differ from
+        // the logical order of boxes in bidi text.
+        let mut boxes: Vec<&LayoutBox<'gc>> = self.boxes.iter().collect();
+        boxes.sort_by_key(|b| b.bounds().offset_x());
+
+        for linebox in boxes {
             if linebox.is_text_box() {
                 if let Some((_t, tf, font, params, color)) = linebox.as_renderable_text(self.text) {
                     let underline_baseline =
@@ -422,9 +546,12 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
         let mut line_size_bounds = line_size_bounds.unwrap_or_default();
 
+        let (left_float_width, right_float_width) = self.float_widths();
         let left_adjustment =
-            Self::left_alignment_offset(&self.current_line_span, self.is_first_line);
-        let right_adjustment = Twips::from_pixels(self.current_line_span.right_margin);
+            Self::left_alignment_offset(&self.current_line_span, self.is_first_line)
+                + left_float_width;
+        let right_adjustment =
+            Twips::from_pixels(self.current_line_span.right_margin) + right_float_width;
 
         let misalignment =
             self.max_bounds - left_adjustment - right_adjustment - line_size_bounds.width();
@@ -467,6 +594,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             box_count += 1;
         }
 
+        self.reorder_line();
         self.append_underlines();
 
         line_size_bounds +=
@@ -488,6 +616,51 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         self.flush_line(end);
     }
 
+    /// Reorder text boxes of the current line visually, according to their
+    /// bidi embedding levels.
+    ///
+    /// Boxes are kept in logical order, only their positions change. Space
+    /// between boxes (e.g. caused by tabs or justification) is preserved.
+    fn reorder_line(&mut self) {
+        let text_boxes: Vec<usize> = (0..self.boxes.len())
+            .filter(|&i| self.boxes[i].is_text_box())
+            .collect();
+        let levels: Vec<u8> = text_boxes
+            .iter()
+            .map(|&i| self.boxes[i].bidi_level())
+            .collect();
+        let order = visual_order(&levels);
+        if order
+            .iter()
+            .enumerate()
+            .all(|(visual_index, &k)| visual_index == k)
+        {
+            return;
+        }
+
+        let gaps: Vec<Twips> = text_boxes
+            .iter()
+            .enumerate()
+            .map(|(k, &i)| match k.checked_sub(1) {
+                Some(prev) => {
+                    self.boxes[i].bounds.offset_x() - self.boxes[text_boxes[prev]].bounds.extent_x()
+                }
+                None => Twips::ZERO,
+            })
+            .collect();
+
+        let mut x = self.boxes[text_boxes[0]].bounds.offset_x();
+        for (visual_index, &k) in order.iter().enumerate() {
+            if visual_index > 0 {
+                x += gaps[k];
+            }
+            let linebox = &mut self.boxes[text_boxes[k]];
+            let offset = x - linebox.bounds.offset_x();
+            linebox.bounds += Position::from((offset, Twips::ZERO));
+            x += linebox.bounds.width();
+        }
+    }
+
     fn flush_line(&mut self, end: usize) {
         if self.boxes.is_empty() {
             return;
@@ -757,6 +930,62 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         }
     }
 
+    /// Resolve the fonts used for characters that the given device font of
+    /// the span has no glyphs for.
+    ///
+    /// These are the other implementations of default fonts named by the
+    /// span, followed by the configured fallback fonts. Nothing is resolved
+    /// when the font has glyphs for all characters of the span.
+    fn resolve_fallback_fonts(
+        &mut self,
+        context: &mut UpdateContext<'gc>,
+        span: &TextSpan,
+        span_text: &WStr,
+        font: Font<'gc>,
+    ) -> Vec<Font<'gc>> {
+        // Embedded fonts never fall back to other fonts.
+        if font.font_type() != FontType::Device {
+            return Vec::new();
+        }
+
+        let missing = font.missing_chars(span_text);
+        if missing.is_empty() {
+            return Vec::new();
+        }
+
+        let mut fallbacks = Vec::new();
+        for font_name in span.font.face.to_utf8_lossy().split(',') {
+            if let Some(default_font) = DefaultFont::from_name(font_name.trim()) {
+                fallbacks.extend(context.library.default_font(
+                    default_font,
+                    span.style.bold,
+                    span.style.italic,
+                    context.ui,
+                    context.renderer,
+                    context.gc_context,
+                ));
+            }
+        }
+        fallbacks.extend(context.library.font_fallbacks(
+            &missing,
+            span.style.bold,
+            span.style.italic,
+            context.ui,
+            context.renderer,
+            context.gc_context,
+        ));
+
+        let mut result: Vec<Font<'gc>> = Vec::with_capacity(fallbacks.len());
+        for fallback in fallbacks {
+            if !Font::ptr_eq(fallback, font)
+                && !result.iter().any(|&other| Font::ptr_eq(other, fallback))
+            {
+                result.push(fallback);
+            }
+        }
+        result
+    }
+
     /// Append text to the current line of the ongoing layout operation.
     ///
     /// The text given may or may not be separated into fragments, depending on
@@ -789,15 +1018,85 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     ///
     /// This function bypasses the text fragmentation necessary for justify to
     /// work, and it should only be called internally.
+    ///
+    /// The fragment is further split into runs of the same bidi level, so
+    /// that each box is laid out in a single direction.
     fn append_text_fragment(&mut self, text: &'a WStr, start: usize, end: usize, span: &TextSpan) {
+        let mut run_start = 0;
+        loop {
+            let level = self.bidi_level(start + run_start);
+            let run_end = (run_start + 1..text.len())
+                .find(|&i| self.bidi_level(start + i) != level)
+                .unwrap_or(text.len());
+
+            self.append_text_run(
+                &text[run_start..run_end],
+                start + run_start,
+                start + run_end,
+                level,
+                span,
+            );
+
+            run_start = run_end;
+            if run_start >= text.len() {
+                break;
+            }
+        }
+    }
+
+    /// The bidi embedding level of the character at the given position.
+    fn bidi_level(&self, position: usize) -> u8 {
+        self.bidi_levels.get(position).copied().unwrap_or(0)
+    }
+
+    /// Append a run of text in a single direction to the current line.
+    ///
+    /// The run is further split into boxes of characters rendered with the
+    /// same font, as characters missing from the resolved font are rendered
+    /// with one of the fallback fonts.
+    fn append_text_run(
+        &mut self,
+        text: &'a WStr,
+        start: usize,
+        end: usize,
+        bidi_level: u8,
+        span: &TextSpan,
+    ) {
         let font = self.font.expect("text fragment requires a font");
-        let params = EvalParameters::from_span(span);
+        for (range, font) in font.split_by_fallbacks(text, &self.fallback_fonts) {
+            let run_end = if range.end == text.len() {
+                end
+            } else {
+                start + range.end
+            };
+            self.append_font_run(
+                &text[range.clone()],
+                start + range.start,
+                run_end,
+                bidi_level,
+                font,
+                span,
+            );
+        }
+    }
+
+    /// Append a run of text in a single direction and font to the current line.
+    fn append_font_run(
+        &mut self,
+        text: &'a WStr,
+        start: usize,
+        end: usize,
+        bidi_level: u8,
+        font: Font<'gc>,
+        span: &TextSpan,
+    ) {
+        let params = EvalParameters::from_span(span).with_right_to_left(bidi_level % 2 == 1);
         let ascent = font.get_baseline_for_height(params.height());
         let descent = font.get_descent_for_height(params.height());
         let text_width = font.measure(text, params);
         let box_origin = self.cursor - (Twips::ZERO, ascent).into();
 
-        let mut new_box = LayoutBox::from_text(text, start, end, font, span);
+        let mut new_box = LayoutBox::from_text(text, start, end, bidi_level, font, span);
         new_box.bounds = BoxBounds::from_position_and_size(
             box_origin,
             Size::from((text_width, ascent + descent)),
@@ -887,15 +1186,83 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     ///
     /// Offsets returned by this function should not be considered final;
     fn wrap_dimensions(&self, current_span: &TextSpan) -> (Twips, Twips) {
-        let width = self.max_bounds - Twips::from_pixels(self.current_line_span.right_margin);
+        let (left_float_width, right_float_width) = self.float_widths();
+        let width = self.max_bounds
+            - Twips::from_pixels(self.current_line_span.right_margin)
+            - right_float_width;
         let offset = Self::left_alignment_offset(current_span, self.is_first_line);
 
-        (width, offset + self.cursor.x())
+        (width, offset + left_float_width + self.cursor.x())
+    }
+
+    /// Calculate the horizontal space taken by images on the left and right
+    /// side of the current line.
+    fn float_widths(&self) -> (Twips, Twips) {
+        let line_top = self.cursor.y();
+        self.floats
+            .iter()
+            .filter(|float| float.bottom > line_top)
+            .fold(
+                (Twips::ZERO, Twips::ZERO),
+                |(left, right), float| match float.align {
+                    ImageAlign::Left => (left + float.width, right),
+                    ImageAlign::Right => (left, right + float.width),
+                },
+            )
+    }
+
+    /// Place all images positioned before or at the given position of text.
+    ///
+    /// Images are placed at the side of the current line, next to images
+    /// that are already there. Text of the following lines flows around them
+    /// until their bottom edge is reached.
+    fn place_images(&mut self, position: usize) {
+        while let Some(image) = self.images.get(self.laid_out_images.len()) {
+            if image.position > position {
+                break;
+            }
+
+            let index = self.laid_out_images.len();
+            let size = self.image_sizes.get(index).copied().unwrap_or_else(|| {
+                Size::from((
+                    Twips::from_pixels(image.width.unwrap_or_default()),
+                    Twips::from_pixels(image.height.unwrap_or_default()),
+                ))
+            });
+            let hspace = Twips::from_pixels(image.hspace);
+            let vspace = Twips::from_pixels(image.vspace);
+
+            let (left_float_width, right_float_width) = self.float_widths();
+            let x = match image.align {
+                ImageAlign::Left => {
+                    Self::left_alignment_offset(&self.current_line_span, self.is_first_line)
+                        + left_float_width
+                }
+                ImageAlign::Right => {
+                    self.max_bounds
+                        - Twips::from_pixels(self.current_line_span.right_margin)
+                        - right_float_width
+                        - size.width()
+                }
+            };
+            let bounds =
+                BoxBounds::from_position_and_size(Position::from((x, self.cursor.y())), size);
+
+            self.floats.push(ImageFloat {
+                align: image.align,
+                width: size.width() + hspace,
+                bottom: bounds.extent_y() + vspace,
+            });
+            Self::extend_bounds(&mut self.bounds, bounds);
+            Self::extend_bounds(&mut self.text_size_bounds, bounds);
+            self.laid_out_images.push(LayoutImage { index, bounds });
+        }
     }
 
     /// Destroy the layout context, returning the newly constructed layout list.
     fn end_layout(mut self, context: &mut UpdateContext<'gc>, fs: &'a FormatSpans) -> Layout<'gc> {
         let last_span = fs.last_span().expect("At least one span should be present");
+        self.place_images(fs.displayed_text().len());
         self.fixup_line(context, true, true, fs.displayed_text().len(), last_span);
 
         let text_size = self.text_size_bounds.unwrap_or_default();
@@ -903,6 +1270,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             bounds: self.bounds.unwrap_or_default(),
             text_size: Size::from((text_size.width(), text_size.height())),
             lines: self.lines,
+            images: self.laid_out_images,
         }
     }
 
@@ -912,11 +1280,16 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 }
 
 /// Construct a new layout from text spans.
+///
+/// `image_sizes` contains the sizes of images embedded in the text, in the
+/// order they appear in. Images without a size use their declared size.
+#[allow(clippy::too_many_arguments)]
 pub fn lower_from_text_spans<'gc>(
     fs: &FormatSpans,
     context: &mut UpdateContext<'gc>,
     movie: Arc<SwfMovie>,
     requested_width: Option<Twips>,
+    image_sizes: &[Size<Twips>],
     is_input: bool,
     is_word_wrap: bool,
     font_type: FontType,
@@ -930,6 +1303,7 @@ pub fn lower_from_text_spans<'gc>(
             context,
             movie.clone(),
             Twips::ZERO,
+            image_sizes,
             is_input,
             false,
             font_type,
@@ -946,17 +1320,20 @@ pub fn lower_from_text_spans<'gc>(
         context,
         movie,
         requested_width,
+        image_sizes,
         is_input,
         is_word_wrap,
         font_type,
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 fn lower_from_text_spans_known_width<'gc>(
     fs: &FormatSpans,
     context: &mut UpdateContext<'gc>,
     movie: Arc<SwfMovie>,
     bounds: Twips,
+    image_sizes: &[Size<Twips>],
     is_input: bool,
     is_word_wrap: bool,
     font_type: FontType,
@@ -965,6 +1342,8 @@ fn lower_from_text_spans_known_width<'gc>(
         movie,
         bounds,
         fs.displayed_text(),
+        fs.images(),
+        image_sizes,
         is_input,
         is_word_wrap,
         font_type,
@@ -987,6 +1366,9 @@ pub struct Layout<'gc> {
     text_size: Size<Twips>,
 
     lines: Vec<LayoutLine<'gc>>,
+
+    #[collect(require_static)]
+    images: Vec<LayoutImage>,
 }
 
 impl<'gc> Layout<'gc> {
@@ -1004,6 +1386,11 @@ impl<'gc> Layout<'gc> {
         &self.lines
     }
 
+    /// Images embedded in the text, in the order they appear in.
+    pub fn images(&self) -> &[LayoutImage] {
+        &self.images
+    }
+
     pub fn boxes_iter(&self) -> LayoutBoxIter<'_, 'gc> {
         LayoutBoxIter {
             lines_iter: self.lines.iter(),
@@ -1063,6 +1450,33 @@ impl<'gc> Layout<'gc> {
         let line = self.lines.get(line_index)?;
         line.char_bounds(position)
     }
+
+    /// Returns the box containing the given char.
+    pub fn find_box_by_position(&self, position: usize) -> Option<&LayoutBox<'gc>> {
+        let line_index = self.find_line_index_by_position(position)?;
+        let line = self.lines.get(line_index)?;
+        let box_index = line.find_box_index_by_position(position)?;
+        line.boxes.get(box_index)
+    }
+}
+
+/// A `LayoutImage` represents an image placed next to text.
+#[derive(Clone, Debug)]
+pub struct LayoutImage {
+    /// The index of the image in [`FormatSpans::images`].
+    index: usize,
+
+    bounds: BoxBounds<Twips>,
+}
+
+impl LayoutImage {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn bounds(&self) -> BoxBounds<Twips> {
+        self.bounds
+    }
 }
 
 /// A `LayoutLine` represents a single line of text.
@@ -1178,8 +1592,12 @@ impl<'gc> LayoutLine<'gc> {
         // and its bounds will end where the next character starts.
         // If it's not a space or the text is not justified, it won't change the value.
         // TODO [KJ] We need to test this behavior with letter spacing or kerning enabled.
-        if layout_box.end() == position + 1 {
-            if let Some(next_box) = self.boxes.get(box_index + 1) {
+        if layout_box.end() == position + 1 && !layout_box.is_right_to_left() {
+            if let Some(next_box) = self
+                .boxes
+                .get(box_index + 1)
+                .filter(|b| !b.is_right_to_left())
+            {
                 if let Some(next_start) = next_box.char_x_bounds(position + 1).map(|(s, _)| s) {
                     end = next_start;
                 }
@@ -1189,6 +1607,13 @@ impl<'gc> LayoutLine<'gc> {
         Some((start, end))
     }
 
+    /// Whether this line contains right-to-left text.
+    ///
+    /// Characters of such lines may not be displayed in logical order.
+    pub fn has_right_to_left_text(&self) -> bool {
+        self.boxes.iter().any(|b| b.is_right_to_left())
+    }
+
     /// Returns char bounds of the given char relative to the whole layout.
     pub fn char_bounds(&self, position: usize) -> Option<Rectangle<Twips>> {
         let (x_min, x_max) = self.char_x_bounds(position)?;
@@ -1254,7 +1679,12 @@ pub enum LayoutContent<'gc> {
         #[collect(require_static)]
         color: swf::Color,
 
-        /// List of end positions (relative to this box) for each character.
+        /// The bidi embedding level of the text.
+        ///
+        /// Odd levels are laid out from right to left.
+        bidi_level: u8,
+
+        /// List of x-axis bounds (relative to this box) for each character.
         ///
         /// By having this here, we do not have to reevaluate the font
         /// each time we want to get the position of a character,
@@ -1264,10 +1694,12 @@ pub enum LayoutContent<'gc> {
         /// For instance, for the text "hello", this field may contain:
         ///
         /// ```text
-        /// [100, 200, 250, 300, 400]
+        /// [(0, 100), (100, 200), (200, 250), (250, 300), (300, 400)]
         /// ```
+        ///
+        /// For right-to-left text, bounds of subsequent characters decrease.
         #[collect(require_static)]
-        char_end_pos: Vec<Twips>,
+        char_bounds: Vec<(Twips, Twips)>,
     },
 
     /// A layout box containing a bullet.
@@ -1335,16 +1767,33 @@ impl<'gc> LayoutBox<'gc> {
         text: &WStr,
         start: usize,
         end: usize,
+        bidi_level: u8,
         font: Font<'gc>,
         span: &TextSpan,
     ) -> Self {
-        let params = EvalParameters::from_span(span);
-        let mut char_end_pos = Vec::with_capacity(end - start);
-
-        font.evaluate(text, Default::default(), params, |_, _, _, advance, x| {
-            char_end_pos.push(x + advance);
+        let params = EvalParameters::from_span(span).with_right_to_left(bidi_level % 2 == 1);
+        let mut char_bounds: Vec<Option<(Twips, Twips)>> = vec![None; end - start];
+
+        font.evaluate(text, Default::default(), params, |pos, _, _, advance, x| {
+            if let Some(bounds) = char_bounds.get_mut(pos) {
+                // A character may produce multiple glyphs when it's shaped.
+                *bounds = Some(match *bounds {
+                    Some((start, end)) => (start.min(x), end.max(x + advance)),
+                    None => (x, x + advance),
+                });
+            }
         });
 
+        // Code units which aren't the start of a character share bounds with it.
+        let mut last_bounds = Default::default();
+        let char_bounds = char_bounds
+            .into_iter()
+            .map(|bounds| {
+                last_bounds = bounds.unwrap_or(last_bounds);
+                last_bounds
+            })
+            .collect();
+
         Self {
             bounds: Default::default(),
             content: LayoutContent::Text {
@@ -1354,7 +1803,8 @@ impl<'gc> LayoutBox<'gc> {
                 font,
                 params,
                 color: span.font.color,
-                char_end_pos,
+                bidi_level,
+                char_bounds,
             },
         }
     }
@@ -1477,24 +1927,95 @@ impl<'gc> LayoutBox<'gc> {
         }
     }
 
+    /// The bidi embedding level of this box.
+    ///
+    /// Boxes other than text are always left-to-right.
+    pub fn bidi_level(&self) -> u8 {
+        match &self.content {
+            LayoutContent::Text { bidi_level, .. } => *bidi_level,
+            _ => 0,
+        }
+    }
+
+    pub fn is_right_to_left(&self) -> bool {
+        self.bidi_level() % 2 == 1
+    }
+
     /// Return x-axis char bounds of the given char relative to the whole layout.
     pub fn char_x_bounds(&self, position: usize) -> Option<(Twips, Twips)> {
         let relative_position = position.checked_sub(self.start())?;
 
-        let LayoutContent::Text { char_end_pos, .. } = &self.content else {
+        let LayoutContent::Text { char_bounds, .. } = &self.content else {
             return None;
         };
 
         let origin_x = self.bounds().origin().x();
+        let (start, end) = *char_bounds.get(relative_position)?;
 
-        Some(if relative_position == 0 {
-            (origin_x, origin_x + *char_end_pos.get(0)?)
-        } else {
-            (
-                origin_x + *char_end_pos.get(relative_position - 1)?,
-                origin_x + *char_end_pos.get(relative_position)?,
-            )
-        })
+        Some((origin_x + start, origin_x + end))
+    }
+
+    /// Return x-axis bounds of characters from the given range which are
+    /// contained in this box, relative to the whole layout.
+    ///
+    /// As the text of a box has a single direction, these characters are
+    /// always displayed next to each other.
+    pub fn range_x_bounds(&self, range: Range<usize>) -> Option<(Twips, Twips)> {
+        let start = range.start.max(self.start());
+        let end = range.end.min(self.end());
+
+        (start..end)
+            .filter_map(|position| self.char_x_bounds(position))
+            .reduce(|(start_a, end_a), (start_b, end_b)| (start_a.min(start_b), end_a.max(end_b)))
+    }
+
+    /// Return the x-axis position of the caret placed before the given
+    /// character, relative to this box.
+    ///
+    /// In right-to-left text, the caret is placed on the right of the
+    /// character.
+    pub fn caret_x(&self, position: usize) -> Option<Twips> {
+        let relative_position = position.checked_sub(self.start())?;
+
+        let LayoutContent::Text { char_bounds, .. } = &self.content else {
+            return None;
+        };
+
+        Some(caret_x(
+            char_bounds,
+            relative_position,
+            self.is_right_to_left(),
+        ))
+    }
+}
+
+/// Find the x-axis position of the caret placed before the character at the
+/// given position, from the x-axis bounds of each character of a box.
+///
+/// In right-to-left text, the caret is placed on the right of the character.
+/// After the last character, it's placed on its other side.
+pub(super) fn caret_x(
+    char_bounds: &[(Twips, Twips)],
+    position: usize,
+    right_to_left: bool,
+) -> Twips {
+    let previous = position.checked_sub(1).and_then(|p| char_bounds.get(p));
+    match (char_bounds.get(position), previous) {
+        (Some(&(start, end)), _) => {
+            if right_to_left {
+                end
+            } else {
+                start
+            }
+        }
+        (None, Some(&(start, end))) => {
+            if right_to_left {
+                start
+            } else {
+                end
+            }
+        }
+        (None, None) => Twips::ZERO,
     }
 }
 
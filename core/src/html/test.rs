@@ -1,8 +1,13 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
-use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::html::layout::{caret_x, resolve_bidi_levels, visual_order};
+use crate::html::text_format::{
+    FormatSpans, HtmlImage, ImageAlign, LinkState, TextDisplay, TextFormat, TextSpan,
+};
+use crate::html::StyleSheet;
 use crate::string::{WStr, WString};
+use gc_arena::arena::rootless_mutate;
 use swf::{Rectangle, Twips};
 
 #[test]
@@ -868,3 +873,459 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn formatspans_slice() {
+    let tf1 = TextFormat {
+        font: Some(WString::from_utf8("same!")),
+        ..Default::default()
+    };
+
+    let tf2 = TextFormat {
+        font: Some(WString::from_utf8("difference!")),
+        ..Default::default()
+    };
+
+    let fs = FormatSpans::from_str_and_spans(
+        WStr::from_units(b"abcdefghi"),
+        &[
+            TextSpan::with_length_and_format(5, &tf1),
+            TextSpan::with_length_and_format(4, &tf2),
+        ],
+    );
+
+    let slice = fs.slice(3, 7);
+
+    assert_eq!(WStr::from_units(b"defg"), slice.text());
+
+    assert_eq!((0, 1), slice.get_span_boundaries(0, 2));
+    assert_eq!((1, 2), slice.get_span_boundaries(2, 4));
+    assert_eq!(tf1.font, slice.get_text_format(0, 2).font);
+    assert_eq!(tf2.font, slice.get_text_format(2, 4).font);
+}
+
+#[test]
+fn formatspans_from_html_image() {
+    let fs = FormatSpans::from_html(
+        WStr::from_units(
+            b"ab<img src=\"pic.png\" width=\"20\" align=\"right\" vspace=\"2\" id=\"pic\">cd",
+        ),
+        TextFormat::default(),
+        None,
+        None,
+        true,
+        false,
+        10,
+    );
+
+    assert_eq!(WStr::from_units(b"abcd"), fs.text());
+    assert_eq!(
+        &[HtmlImage {
+            position: 2,
+            src: WString::from_utf8("pic.png"),
+            id: WString::from_utf8("pic"),
+            width: Some(20.0),
+            height: None,
+            align: ImageAlign::Right,
+            hspace: 8.0,
+            vspace: 2.0,
+        }],
+        fs.images()
+    );
+}
+
+#[test]
+fn formatspans_replace_text_images() {
+    let mut fs = FormatSpans::from_html(
+        WStr::from_units(b"a<img src=\"1\">bcd<img src=\"2\">efg<img src=\"3\">h"),
+        TextFormat::default(),
+        None,
+        None,
+        true,
+        false,
+        10,
+    );
+
+    fs.replace_text(2, 6, WStr::from_units(b"12"));
+
+    assert_eq!(WStr::from_units(b"ab12gh"), fs.text());
+    let images: Vec<_> = fs
+        .images()
+        .iter()
+        .map(|image| (image.src.to_string(), image.position))
+        .collect();
+    assert_eq!(
+        vec![
+            ("1".to_string(), 1),
+            ("2".to_string(), 2),
+            ("3".to_string(), 5)
+        ],
+        images
+    );
+}
+
+#[test]
+fn formatspans_flash_xml_roundtrip() {
+    let tf1 = TextFormat {
+        font: Some(WString::from_utf8("Times \"New\" Roman")),
+        size: Some(14.0),
+        color: Some(swf::Color::from_rgb(0xFF0000, 0)),
+        bold: Some(true),
+        ..Default::default()
+    };
+
+    let tf2 = TextFormat {
+        font: Some(WString::from_utf8("Arial")),
+        italic: Some(true),
+        url: Some(WString::from_utf8("http://example.com/?a=1&b=2")),
+        tab_stops: Some(vec![10.0, 20.5]),
+        ..Default::default()
+    };
+
+    let fs = FormatSpans::from_str_and_spans(
+        WStr::from_units(b"a<b>\rcd"),
+        &[
+            TextSpan::with_length_and_format(4, &tf1),
+            TextSpan::with_length_and_format(3, &tf2),
+        ],
+    );
+
+    let xml = fs.to_flash_xml();
+    let parsed = FormatSpans::from_flash_xml(&xml, TextFormat::default())
+        .expect("fragment should be well-formed");
+
+    assert_eq!(WStr::from_units(b"a<b>\rcd"), parsed.text());
+    assert_eq!((0, 1), parsed.get_span_boundaries(0, 4));
+    assert_eq!((1, 2), parsed.get_span_boundaries(4, 7));
+
+    let format = parsed.get_text_format(0, 4);
+    assert_eq!(tf1.font, format.font);
+    assert_eq!(tf1.size, format.size);
+    assert_eq!(tf1.color, format.color);
+    assert_eq!(Some(true), format.bold);
+    assert_eq!(Some(false), format.italic);
+
+    let format = parsed.get_text_format(4, 7);
+    assert_eq!(tf2.font, format.font);
+    assert_eq!(Some(true), format.italic);
+    assert_eq!(tf2.url, format.url);
+    assert_eq!(tf2.tab_stops, format.tab_stops);
+}
+
+#[test]
+fn formatspans_flash_xml_defaults() {
+    let default_format = TextFormat {
+        font: Some(WString::from_utf8("Verdana")),
+        size: Some(10.0),
+        ..Default::default()
+    };
+
+    let parsed = FormatSpans::from_flash_xml(
+        WStr::from_units(
+            b"<flashrichtext version=\"1\"><span bold=\"true\">hi</span> ignored</flashrichtext>",
+        ),
+        default_format.clone(),
+    )
+    .expect("fragment should be well-formed");
+
+    assert_eq!(WStr::from_units(b"hi"), parsed.text());
+    let format = parsed.get_text_format(0, 2);
+    assert_eq!(default_format.font, format.font);
+    assert_eq!(default_format.size, format.size);
+    assert_eq!(Some(true), format.bold);
+
+    assert!(FormatSpans::from_flash_xml(
+        WStr::from_units(b"<flashrichtext><span>broken</flashrichtext"),
+        TextFormat::default(),
+    )
+    .is_none());
+}
+
+#[test]
+fn formatspans_replace_with_spans() {
+    let tf1 = TextFormat {
+        font: Some(WString::from_utf8("outer")),
+        ..Default::default()
+    };
+
+    let tf2 = TextFormat {
+        font: Some(WString::from_utf8("inner")),
+        ..Default::default()
+    };
+
+    let mut fs = FormatSpans::from_str_and_spans(
+        WStr::from_units(b"abcdef"),
+        &[TextSpan::with_length_and_format(6, &tf1)],
+    );
+    let inserted = FormatSpans::from_str_and_spans(
+        WStr::from_units(b"XY"),
+        &[TextSpan::with_length_and_format(2, &tf2)],
+    );
+
+    fs.replace_with_spans(2, 4, &inserted);
+
+    assert_eq!(WStr::from_units(b"abXYef"), fs.text());
+    assert_eq!(tf1.font, fs.get_text_format(0, 2).font);
+    assert_eq!(tf2.font, fs.get_text_format(2, 4).font);
+    assert_eq!(tf1.font, fs.get_text_format(4, 6).font);
+}
+
+/// Lower some HTML with a style sheet made of the given styles.
+fn with_styled_html<F>(
+    html: &[u8],
+    styles: &[(&str, TextFormat)],
+    link_state: Option<(usize, LinkState)>,
+    callback: F,
+) where
+    F: FnOnce(FormatSpans),
+{
+    rootless_mutate(|mc| {
+        let style_sheet = StyleSheet::new(mc);
+        for (selector, style) in styles {
+            style_sheet.set_style(WString::from_utf8(selector), style.clone());
+        }
+        callback(FormatSpans::from_html(
+            WStr::from_units(html),
+            TextFormat::default(),
+            Some(style_sheet),
+            link_state,
+            true,
+            false,
+            10,
+        ));
+    });
+}
+
+/// A style as produced by `StyleSheet.transform`, which always sets `display`.
+fn style(format: TextFormat) -> TextFormat {
+    TextFormat {
+        display: format.display.or(Some(TextDisplay::Block)),
+        ..format
+    }
+}
+
+#[test]
+fn style_sheet_cascade() {
+    let styles = [
+        (
+            "p",
+            style(TextFormat {
+                font: Some(WString::from_utf8("tag")),
+                size: Some(10.0),
+                bold: Some(true),
+                ..Default::default()
+            }),
+        ),
+        (
+            ".title",
+            style(TextFormat {
+                font: Some(WString::from_utf8("class")),
+                size: Some(20.0),
+                ..Default::default()
+            }),
+        ),
+        (
+            "p.title",
+            style(TextFormat {
+                italic: Some(true),
+                ..Default::default()
+            }),
+        ),
+        (
+            "span",
+            style(TextFormat {
+                underline: Some(true),
+                ..Default::default()
+            }),
+        ),
+    ];
+
+    with_styled_html(
+        b"<p class=\"Title\">ab</p><p>cd</p><span class=\"title\">ef</span>",
+        &styles,
+        None,
+        |fs| {
+            assert_eq!(WStr::from_units(b"ab\rcd\ref"), fs.text());
+
+            // The class overrides the tag, tags combined with classes are ignored.
+            let title = fs.get_text_format(0, 2);
+            assert_eq!(Some(WString::from_utf8("class")), title.font);
+            assert_eq!(Some(20.0), title.size);
+            assert_eq!(Some(true), title.bold);
+            assert_eq!(Some(false), title.italic);
+
+            let paragraph = fs.get_text_format(3, 5);
+            assert_eq!(Some(WString::from_utf8("tag")), paragraph.font);
+            assert_eq!(Some(10.0), paragraph.size);
+
+            // Spans are only styled by their class.
+            let span = fs.get_text_format(6, 8);
+            assert_eq!(Some(WString::from_utf8("class")), span.font);
+            assert_eq!(Some(false), span.bold);
+            assert_eq!(Some(false), span.underline);
+        },
+    );
+}
+
+#[test]
+fn style_sheet_display() {
+    let styles = [
+        ("block", style(TextFormat::default())),
+        (
+            "inline",
+            style(TextFormat {
+                display: Some(TextDisplay::Inline),
+                ..Default::default()
+            }),
+        ),
+        (
+            ".hidden",
+            style(TextFormat {
+                display: Some(TextDisplay::None),
+                ..Default::default()
+            }),
+        ),
+    ];
+
+    with_styled_html(
+        b"<block>a</block>b<inline>c</inline>d<span class=\"hidden\">e<b>f</b><br></span>g<unstyled>h</unstyled>",
+        &styles,
+        None,
+        |fs| {
+            assert_eq!(WStr::from_units(b"a\rbcdgh"), fs.text());
+        },
+    );
+}
+
+#[test]
+fn style_sheet_link_pseudo_classes() {
+    let styles = [
+        (
+            "a",
+            style(TextFormat {
+                font: Some(WString::from_utf8("link")),
+                ..Default::default()
+            }),
+        ),
+        (
+            "a:link",
+            style(TextFormat {
+                size: Some(12.0),
+                ..Default::default()
+            }),
+        ),
+        (
+            "a:hover",
+            style(TextFormat {
+                underline: Some(true),
+                ..Default::default()
+            }),
+        ),
+        (
+            "a:active",
+            style(TextFormat {
+                size: Some(20.0),
+                ..Default::default()
+            }),
+        ),
+    ];
+    let html = b"<a href=\"1\">ab</a>cd<a href=\"2\">ef</a>";
+
+    with_styled_html(html, &styles, None, |fs| {
+        assert_eq!(None, fs.link_index_at(2));
+        assert_eq!(Some(0), fs.link_index_at(1));
+        assert_eq!(Some(1), fs.link_index_at(4));
+
+        let link = fs.get_text_format(0, 2);
+        assert_eq!(Some(WString::from_utf8("link")), link.font);
+        assert_eq!(Some(12.0), link.size);
+        assert_eq!(Some(false), link.underline);
+    });
+
+    with_styled_html(html, &styles, Some((1, LinkState::Hover)), |fs| {
+        assert_eq!(Some(false), fs.get_text_format(0, 2).underline);
+        assert_eq!(Some(true), fs.get_text_format(4, 6).underline);
+        assert_eq!(Some(12.0), fs.get_text_format(4, 6).size);
+    });
+
+    with_styled_html(html, &styles, Some((0, LinkState::Active)), |fs| {
+        assert_eq!(Some(20.0), fs.get_text_format(0, 2).size);
+        assert_eq!(Some(false), fs.get_text_format(0, 2).underline);
+        assert_eq!(Some(12.0), fs.get_text_format(4, 6).size);
+    });
+}
+
+#[test]
+fn formatspans_replace_text_links() {
+    let mut fs = FormatSpans::from_html(
+        WStr::from_units(b"<a href=\"1\">abc</a>d<a href=\"2\">ef</a>"),
+        TextFormat::default(),
+        None,
+        None,
+        true,
+        false,
+        10,
+    );
+
+    fs.replace_text(1, 4, WStr::from_units(b"12345"));
+
+    assert_eq!(WStr::from_units(b"a12345ef"), fs.text());
+    assert_eq!(Some(0), fs.link_index_at(0));
+    assert_eq!(None, fs.link_index_at(1));
+    assert_eq!(Some(1), fs.link_index_at(6));
+}
+
+#[test]
+fn bidi_levels_of_left_to_right_text() {
+    assert_eq!(
+        resolve_bidi_levels(WStr::from_units(b"Hello world")),
+        vec![]
+    );
+}
+
+#[test]
+fn bidi_levels_of_mixed_text() {
+    // Latin, then Hebrew and an Arabic letter taking two code units.
+    let text = WString::from_utf8("ab \u{5D0}\u{5D1} \u{1EE00}");
+    let levels = resolve_bidi_levels(&text);
+    assert_eq!(levels.len(), text.len());
+    assert_eq!(levels, vec![0, 0, 0, 1, 1, 1, 1, 1]);
+}
+
+#[test]
+fn bidi_levels_of_right_to_left_paragraph() {
+    // Each paragraph has its own base direction.
+    let text = WString::from_utf8("\u{5D0} 1\nab");
+    assert_eq!(resolve_bidi_levels(&text), vec![1, 1, 2, 1, 0, 0]);
+}
+
+#[test]
+fn visual_order_of_runs() {
+    assert_eq!(visual_order(&[]), Vec::<usize>::new());
+    assert_eq!(visual_order(&[0, 0, 0]), vec![0, 1, 2]);
+    assert_eq!(visual_order(&[1, 1, 1]), vec![2, 1, 0]);
+    assert_eq!(visual_order(&[0, 1, 1, 0]), vec![0, 2, 1, 3]);
+
+    // Numbers in right-to-left text keep their order.
+    assert_eq!(visual_order(&[1, 2, 2, 1]), vec![3, 1, 2, 0]);
+    assert_eq!(visual_order(&[0, 1, 2, 1, 0]), vec![0, 3, 2, 1, 4]);
+}
+
+#[test]
+fn caret_x_in_bidi_text() {
+    let px = Twips::from_pixels_i32;
+    let bounds = [(px(0), px(10)), (px(10), px(20))];
+
+    // Left-to-right text has the caret on the left of characters.
+    assert_eq!(caret_x(&bounds, 0, false), px(0));
+    assert_eq!(caret_x(&bounds, 1, false), px(10));
+    assert_eq!(caret_x(&bounds, 2, false), px(20));
+
+    // Right-to-left text has the caret on their right.
+    let bounds = [(px(10), px(20)), (px(0), px(10))];
+    assert_eq!(caret_x(&bounds, 0, true), px(20));
+    assert_eq!(caret_x(&bounds, 1, true), px(10));
+    assert_eq!(caret_x(&bounds, 2, true), px(0));
+
+    assert_eq!(caret_x(&[], 0, true), Twips::ZERO);
+}
@@ -11,6 +11,7 @@ use std::borrow::Cow;
 use std::cmp::{min, Ordering};
 use std::collections::VecDeque;
 use std::fmt::Write;
+use std::ops::Range;
 use std::sync::Arc;
 
 use super::StyleSheet;
@@ -110,6 +111,57 @@ pub enum TextDisplay {
     None,
 }
 
+/// How the mouse is interacting with a link, which selects the `a:hover`
+/// or `a:active` style of the link.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LinkState {
+    Hover,
+    Active,
+}
+
+/// The side of a text field an inline image is placed on.
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ImageAlign {
+    #[default]
+    Left,
+    Right,
+}
+
+/// An image embedded in HTML text using the `<img>` tag.
+///
+/// Images don't occupy any characters of the text. They are placed on the
+/// line containing their position, and text flows around them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HtmlImage {
+    /// The position in text at which the image appeared.
+    pub position: usize,
+
+    /// The URL of an image or SWF, or the name of a library symbol.
+    pub src: WString,
+
+    /// The identifier used to retrieve the image with `getImageReference`.
+    pub id: WString,
+
+    /// The width of the image in pixels, or `None` to use its natural width.
+    pub width: Option<f64>,
+
+    /// The height of the image in pixels, or `None` to use its natural height.
+    pub height: Option<f64>,
+
+    pub align: ImageAlign,
+
+    /// Horizontal space between the image and text, in pixels.
+    pub hspace: f64,
+
+    /// Vertical space between the image and text, in pixels.
+    pub vspace: f64,
+}
+
+impl HtmlImage {
+    /// The default space between images and text.
+    const DEFAULT_SPACE: f64 = 8.0;
+}
+
 /// A set of text formatting options to be applied to some part, or the whole
 /// of, a given text field.
 ///
@@ -303,6 +355,63 @@ impl TextFormat {
         }
     }
 
+    /// Set the property named by an attribute of a Flash XML `<span>`.
+    ///
+    /// Unknown attributes and malformed values are ignored.
+    fn set_flash_xml_attribute(&mut self, name: &[u8], value: &str) {
+        let number = || value.parse::<f64>().ok();
+        let boolean = || Some(value == "true");
+        match name {
+            b"font" => self.font = Some(WString::from_utf8(value)),
+            b"size" => self.size = number(),
+            b"color" => {
+                self.color = value
+                    .strip_prefix('#')
+                    .and_then(|rgb| u32::from_str_radix(rgb, 16).ok())
+                    .map(|rgb| swf::Color::from_rgb(rgb, 0))
+            }
+            b"bold" => self.bold = boolean(),
+            b"italic" => self.italic = boolean(),
+            b"underline" => self.underline = boolean(),
+            b"kerning" => self.kerning = boolean(),
+            b"letterSpacing" => self.letter_spacing = number(),
+            b"align" => {
+                self.align = match value {
+                    "left" => Some(swf::TextAlign::Left),
+                    "center" => Some(swf::TextAlign::Center),
+                    "right" => Some(swf::TextAlign::Right),
+                    "justify" => Some(swf::TextAlign::Justify),
+                    _ => None,
+                }
+            }
+            b"leftMargin" => self.left_margin = number(),
+            b"rightMargin" => self.right_margin = number(),
+            b"indent" => self.indent = number(),
+            b"blockIndent" => self.block_indent = number(),
+            b"leading" => self.leading = number(),
+            b"tabStops" => {
+                self.tab_stops = Some(
+                    value
+                        .split(',')
+                        .filter_map(|tab_stop| tab_stop.trim().parse().ok())
+                        .collect(),
+                )
+            }
+            b"bullet" => self.bullet = boolean(),
+            b"url" => self.url = Some(WString::from_utf8(value)),
+            b"target" => self.target = Some(WString::from_utf8(value)),
+            b"display" => {
+                self.display = match value {
+                    "block" => Some(TextDisplay::Block),
+                    "inline" => Some(TextDisplay::Inline),
+                    "none" => Some(TextDisplay::None),
+                    _ => None,
+                }
+            }
+            _ => {}
+        }
+    }
+
     /// Given two text formats, construct a new `TextFormat` where properties
     /// defined in either `TextFormat` are defined.
     ///
@@ -578,6 +687,13 @@ pub struct FormatSpans {
     displayed_text: WString,
     spans: Vec<TextSpan>,
     default_format: TextFormat,
+
+    /// Images embedded in the text, sorted by their position.
+    images: Vec<HtmlImage>,
+
+    /// Text ranges of the `<a>` tags the text was lowered from, in the
+    /// order they appear in.
+    links: Vec<Range<usize>>,
 }
 
 impl Default for FormatSpans {
@@ -593,6 +709,8 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: vec![TextSpan::default()],
             default_format: TextFormat::default(),
+            images: Vec::new(),
+            links: Vec::new(),
         }
     }
 
@@ -604,6 +722,8 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: spans.to_vec(),
             default_format: Default::default(),
+            images: Vec::new(),
+            links: Vec::new(),
         }
     }
 
@@ -614,6 +734,8 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: vec![TextSpan::with_length_and_format(len, &format)],
             default_format: format,
+            images: Vec::new(),
+            links: Vec::new(),
         }
     }
 
@@ -623,10 +745,14 @@ impl FormatSpans {
     /// a handful of presentational attributes in the HTML tree to generate
     /// styling. There's also a `lower_from_css` that respects both
     /// presentational markup and CSS stylesheets.
+    ///
+    /// `link_state` names the `<a>` tag (counting from zero) that the mouse
+    /// is interacting with, so that its `a:hover` or `a:active` style is used.
     pub fn from_html(
         html: &WStr,
         default_format: TextFormat,
         style_sheet: Option<StyleSheet<'_>>,
+        link_state: Option<(usize, LinkState)>,
         is_multiline: bool,
         condense_white: bool,
         swf_version: u8,
@@ -638,6 +764,13 @@ impl FormatSpans {
         let mut format_stack = vec![default_format.clone()];
         let mut text = WString::new();
         let mut spans: Vec<TextSpan> = Vec::new();
+        let mut images: Vec<HtmlImage> = Vec::new();
+        let mut links: Vec<Range<usize>> = Vec::new();
+        let mut open_links: Vec<usize> = Vec::new();
+
+        // The display of each open element, parallel to `format_stack`.
+        // Everything inside an element with `display: none` is skipped.
+        let mut display_stack = vec![TextDisplay::Inline];
 
         // quick_xml::Reader requires a [u8] slice, but doesn't actually care about Unicode;
         // this means we can pass the raw buffer in the Latin1 case.
@@ -679,26 +812,38 @@ impl FormatSpans {
         reader_config.check_end_names = false;
         reader_config.allow_unmatched_ends = true;
 
-        fn class_name_to_selector(class: &WStr) -> WString {
-            let mut selector = WString::from_utf8(".");
-            selector.push_str(&class.to_ascii_lowercase());
-            selector
-        }
-
-        fn apply_style(
+        /// Find the style of an element by cascading the styles of the
+        /// selectors matching it, in the same order as Flash Player: the tag,
+        /// then the class, and finally the pseudo-classes (like `a:hover`).
+        /// Later styles override earlier ones.
+        ///
+        /// Flash doesn't support selectors combining a tag with a class
+        /// (like `p.title`), the element just gets both styles.
+        fn element_style(
             style_sheet: Option<StyleSheet<'_>>,
-            format: TextFormat,
-            selector: &WStr,
-        ) -> TextFormat {
-            let Some(style_sheet) = style_sheet else {
-                return format;
-            };
-
-            if let Some(style) = style_sheet.get_style(selector) {
-                style.clone().mix_with(format)
-            } else {
-                format
+            tag: Option<&[u8]>,
+            class: Option<&WStr>,
+            pseudo_selectors: &[&[u8]],
+        ) -> Option<TextFormat> {
+            let style_sheet = style_sheet?;
+
+            let mut selectors = Vec::new();
+            if let Some(tag) = tag {
+                selectors.push(WStr::from_units(tag).to_owned());
+            }
+            if let Some(class) = class {
+                let mut selector = WString::from_utf8(".");
+                selector.push_str(&class.to_ascii_lowercase());
+                selectors.push(selector);
             }
+            for selector in pseudo_selectors {
+                selectors.push(WStr::from_units(*selector).to_owned());
+            }
+
+            selectors
+                .iter()
+                .filter_map(|selector| style_sheet.get_style(selector))
+                .reduce(|style, overriding| overriding.mix_with(style))
         }
 
         loop {
@@ -723,9 +868,12 @@ impl FormatSpans {
                         })
                     };
                     let mut format = format_stack.last().unwrap().clone();
+                    let hidden = display_stack.last() == Some(&TextDisplay::None);
+                    let class = attribute(b"class");
+                    let mut style = None;
                     match tag_name {
                         b"br" => {
-                            if is_multiline {
+                            if is_multiline && !hidden {
                                 text.push(HTML_NEWLINE);
                                 spans.push(TextSpan::with_length_and_format(1, &format));
                             }
@@ -736,8 +884,42 @@ impl FormatSpans {
                         b"sbr" => {
                             // TODO: <sbr> tags do not add a newline, but rather only break
                             // the format span.
-                            text.push(HTML_NEWLINE);
-                            spans.push(TextSpan::with_length_and_format(1, &format));
+                            if !hidden {
+                                text.push(HTML_NEWLINE);
+                                spans.push(TextSpan::with_length_and_format(1, &format));
+                            }
+
+                            // Skip push to `format_stack`.
+                            continue;
+                        }
+                        b"img" => {
+                            if let Some(src) = attribute(b"src").filter(|_| !hidden) {
+                                let parse_size = |name: &[u8]| {
+                                    attribute(name)
+                                        .and_then(|v| v.trim().parse::<f64>().ok())
+                                        .filter(|v| v.is_finite() && *v >= 0.0)
+                                };
+                                let align = match attribute(b"align") {
+                                    Some(align)
+                                        if align.eq_ignore_case(WStr::from_units(b"right")) =>
+                                    {
+                                        ImageAlign::Right
+                                    }
+                                    _ => ImageAlign::Left,
+                                };
+                                images.push(HtmlImage {
+                                    position: text.len(),
+                                    src,
+                                    id: attribute(b"id").unwrap_or_default(),
+                                    width: parse_size(b"width"),
+                                    height: parse_size(b"height"),
+                                    align,
+                                    hspace: parse_size(b"hspace")
+                                        .unwrap_or(HtmlImage::DEFAULT_SPACE),
+                                    vspace: parse_size(b"vspace")
+                                        .unwrap_or(HtmlImage::DEFAULT_SPACE),
+                                });
+                            }
 
                             // Skip push to `format_stack`.
                             continue;
@@ -745,11 +927,9 @@ impl FormatSpans {
                         tag @ b"p" => {
                             p_open = true;
 
-                            format = apply_style(style_sheet, format, WStr::from_units(tag));
-
-                            if let Some(class) = attribute(b"class") {
-                                let selector = &class_name_to_selector(&class);
-                                format = apply_style(style_sheet, format, selector);
+                            style = element_style(style_sheet, Some(tag), class.as_deref(), &[]);
+                            if let Some(style) = &style {
+                                format = style.clone().mix_with(format);
                             }
 
                             if let Some(align) = attribute(b"align") {
@@ -765,7 +945,7 @@ impl FormatSpans {
                                 }
                             }
                         }
-                        tag @ b"a" => {
+                        b"a" => {
                             if let Some(href) = attribute(b"href") {
                                 format.url = Some(href);
                             }
@@ -774,13 +954,27 @@ impl FormatSpans {
                                 format.target = Some(target);
                             }
 
-                            // TODO Docs claim that a:link, a:hover, a:active, should work.
-                            format = apply_style(style_sheet, format, WStr::from_units(tag));
-
-                            if let Some(class) = attribute(b"class") {
-                                let selector = &class_name_to_selector(&class);
-                                format = apply_style(style_sheet, format, selector);
+                            let pseudo_selectors: &[&[u8]] = match link_state {
+                                Some((index, LinkState::Hover)) if index == links.len() => {
+                                    &[b"a:link", b"a:hover"]
+                                }
+                                Some((index, LinkState::Active)) if index == links.len() => {
+                                    &[b"a:link", b"a:active"]
+                                }
+                                _ => &[b"a:link"],
+                            };
+                            style = element_style(
+                                style_sheet,
+                                Some(&b"a"[..]),
+                                class.as_deref(),
+                                pseudo_selectors,
+                            );
+                            if let Some(style) = &style {
+                                format = style.clone().mix_with(format);
                             }
+
+                            open_links.push(links.len());
+                            links.push(text.len()..text.len());
                         }
                         b"font" => {
                             if let Some(face) = attribute(b"face") {
@@ -866,10 +1060,13 @@ impl FormatSpans {
                             format.underline = Some(true);
                         }
                         tag @ b"li" => {
-                            format = apply_style(style_sheet, format, WStr::from_units(tag));
+                            style = element_style(style_sheet, Some(tag), class.as_deref(), &[]);
+                            if let Some(style) = &style {
+                                format = style.clone().mix_with(format);
+                            }
 
                             let is_last_nl = text.iter().next_back() == Some(HTML_NEWLINE);
-                            if is_multiline && !is_last_nl && !text.is_empty() {
+                            if is_multiline && !is_last_nl && !text.is_empty() && !hidden {
                                 // If the last paragraph was not closed and
                                 // there was some text since then,
                                 // we need to close it here.
@@ -914,21 +1111,39 @@ impl FormatSpans {
                             }
                         }
                         b"span" => {
-                            if let Some(class) = attribute(b"class") {
-                                let selector = &class_name_to_selector(&class);
-                                format = apply_style(style_sheet, format, selector);
+                            // Unlike other tags, spans can only be styled using classes.
+                            style = element_style(style_sheet, None, class.as_deref(), &[]);
+                            if let Some(style) = &style {
+                                format = style.clone().mix_with(format);
                             }
                         }
                         tag => {
-                            // TODO Add 'display' style support, Flash adds a newline on 'display: block'
-                            format = apply_style(style_sheet, format, WStr::from_units(tag));
+                            style = element_style(style_sheet, Some(tag), class.as_deref(), &[]);
+                            if let Some(style) = &style {
+                                format = style.clone().mix_with(format);
+                            }
                         }
                     }
+
+                    // Only tags without a meaning of their own are laid out
+                    // as blocks, but any styled tag may be hidden.
+                    let is_custom_tag = !matches!(tag_name, b"p" | b"a" | b"li" | b"span");
+                    let display = match style.and_then(|style| style.display) {
+                        _ if hidden => TextDisplay::None,
+                        Some(TextDisplay::None) => TextDisplay::None,
+                        Some(TextDisplay::Block) if is_custom_tag => TextDisplay::Block,
+                        _ => TextDisplay::Inline,
+                    };
+
                     opened_starts.push(opened_buffer.len());
                     opened_buffer.extend(tag_name);
                     format_stack.push(format);
+                    display_stack.push(display);
                 }
                 Ok(Event::Text(e)) if !e.is_empty() => 'text: {
+                    if display_stack.last() == Some(&TextDisplay::None) {
+                        break 'text;
+                    }
                     let e = decode_to_wstr(&e.into_inner());
                     let e = process_html_entity(&e).unwrap_or(e);
                     let format = format_stack.last().unwrap().clone();
@@ -961,12 +1176,18 @@ impl FormatSpans {
                         None => continue,
                     }
 
+                    let hidden = display_stack.last() == Some(&TextDisplay::None);
                     match tag_name {
-                        b"br" | b"sbr" => {
+                        b"br" | b"sbr" | b"img" => {
                             // Skip pop from `format_stack`.
                             continue;
                         }
-                        b"li" if is_multiline => {
+                        b"a" => {
+                            if let Some(index) = open_links.pop() {
+                                links[index].end = text.len();
+                            }
+                        }
+                        b"li" if is_multiline && !hidden => {
                             text.push(HTML_NEWLINE);
                             spans.push(TextSpan::with_length_and_format(
                                 1,
@@ -979,6 +1200,9 @@ impl FormatSpans {
                                 break 'p;
                             }
                             p_open = false;
+                            if hidden {
+                                break 'p;
+                            }
 
                             text.push(HTML_NEWLINE);
                             let mut span =
@@ -1006,6 +1230,14 @@ impl FormatSpans {
                         }
                         _ => {}
                     }
+                    if display_stack.pop() == Some(TextDisplay::Block) && is_multiline {
+                        // Flash ends a block the same way as a paragraph.
+                        text.push(HTML_NEWLINE);
+                        spans.push(TextSpan::with_length_and_format(
+                            1,
+                            format_stack.last().unwrap(),
+                        ));
+                    }
                     format_stack.pop();
                 }
                 Ok(Event::Eof) => break,
@@ -1017,11 +1249,18 @@ impl FormatSpans {
             }
         }
 
+        // Links which were never closed extend to the end of the text.
+        for index in open_links {
+            links[index].end = text.len();
+        }
+
         let mut ret = Self {
             text,
             displayed_text: WString::new(),
             spans,
             default_format,
+            images,
+            links,
         };
         if condense_white && swf_version >= 8 {
             ret.condense_white_swf8();
@@ -1402,6 +1641,24 @@ impl FormatSpans {
 
         self.text = new_string;
 
+        // Images and link bounds within the replaced text stay before the
+        // new text, the ones after it are moved along with the text.
+        let move_position = |position: usize| {
+            if position >= to && position > from {
+                position - (to - from) + with.len()
+            } else if position > from {
+                from
+            } else {
+                position
+            }
+        };
+        for image in self.images.iter_mut() {
+            image.position = move_position(image.position);
+        }
+        for link in self.links.iter_mut() {
+            *link = move_position(link.start)..move_position(link.end);
+        }
+
         self.normalize();
     }
 
@@ -1419,8 +1676,57 @@ impl FormatSpans {
         TextSpanIter::for_format_spans(self)
     }
 
+    /// Copy the text and formatting of a range of characters into a new set
+    /// of spans.
+    pub fn slice(&self, from: usize, to: usize) -> Self {
+        let to = to.min(self.text.len());
+        let from = from.min(to);
+
+        let mut spans = Vec::new();
+        for (start, end, _text, span) in self.iter_spans() {
+            let (start, end) = (start.max(from), end.min(to));
+            if start < end {
+                let mut span = span.clone();
+                span.span_length = end - start;
+                spans.push(span);
+            }
+        }
+
+        let images = self
+            .images
+            .iter()
+            .filter(|image| from <= image.position && image.position < to)
+            .map(|image| HtmlImage {
+                position: image.position - from,
+                ..image.clone()
+            })
+            .collect();
+
+        Self {
+            text: self.text[from..to].into(),
+            displayed_text: WString::new(),
+            spans,
+            default_format: self.default_format.clone(),
+            images,
+            links: Vec::new(),
+        }
+    }
+
+    /// Images embedded in the text, sorted by their position.
+    pub fn images(&self) -> &[HtmlImage] {
+        &self.images
+    }
+
+    /// Find the `<a>` tag that the character at the given position was
+    /// lowered from, counting from zero.
+    ///
+    /// Nested links resolve to the innermost one.
+    pub fn link_index_at(&self, position: usize) -> Option<usize> {
+        self.links.iter().rposition(|link| link.contains(&position))
+    }
+
     pub fn to_html(&self) -> WString {
-        if self.text.is_empty() {
+        if self.text.is_empty() && self.images.is_empty() {
             return WString::new();
         }
 
@@ -1432,15 +1738,157 @@ impl FormatSpans {
         };
 
         let spans = self.iter_spans();
+        let mut images = self.images.iter().peekable();
 
-        for (_start, _end, text, span) in spans {
+        for (start, end, text, span) in spans {
             state.set_span(span);
-            state.push_text(text);
+
+            let mut pos = start;
+            while let Some(image) = images.next_if(|image| image.position < end) {
+                let image_pos = image.position.max(pos);
+                state.push_text(&text[pos - start..image_pos - start]);
+                state.push_image(image);
+                pos = image_pos;
+            }
+            state.push_text(&text[pos - start..]);
+        }
+
+        for image in images {
+            state.push_image(image);
         }
 
         state.close_all_tags();
         state.result
     }
+
+    /// Serialize the text as a Flash XML fragment, which keeps all of its formatting.
+    ///
+    /// This is the format of the rich text clipboard and of
+    /// `TextField.insertXMLText`. Every span becomes a `<span>` element
+    /// carrying all properties of its `TextFormat`, named as in ActionScript.
+    pub fn to_flash_xml(&self) -> WString {
+        let mut result = WString::from_utf8("<flashrichtext version=\"1\">");
+        for (_start, _end, text, span) in self.iter_spans() {
+            let align = match span.align {
+                swf::TextAlign::Left => "left",
+                swf::TextAlign::Center => "center",
+                swf::TextAlign::Right => "right",
+                swf::TextAlign::Justify => "justify",
+            };
+            let display = match span.display {
+                TextDisplay::Block => "block",
+                TextDisplay::Inline => "inline",
+                TextDisplay::None => "none",
+            };
+            let tab_stops = span
+                .tab_stops
+                .iter()
+                .map(|tab_stop| tab_stop.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            let _ = write!(
+                result,
+                "<span font=\"{}\" size=\"{}\" color=\"#{:06X}\" bold=\"{}\" italic=\"{}\" \
+                underline=\"{}\" kerning=\"{}\" letterSpacing=\"{}\" align=\"{align}\" \
+                leftMargin=\"{}\" rightMargin=\"{}\" indent=\"{}\" blockIndent=\"{}\" \
+                leading=\"{}\" tabStops=\"{tab_stops}\" bullet=\"{}\" url=\"{}\" target=\"{}\" \
+                display=\"{display}\">{}</span>",
+                escape_flash_xml(&span.font.face),
+                span.font.size,
+                span.font.color.to_rgb(),
+                span.style.bold,
+                span.style.italic,
+                span.style.underline,
+                span.font.kerning,
+                span.font.letter_spacing,
+                span.left_margin,
+                span.right_margin,
+                span.indent,
+                span.block_indent,
+                span.leading,
+                span.bullet,
+                escape_flash_xml(&span.url),
+                escape_flash_xml(&span.target),
+                escape_flash_xml(text),
+            );
+        }
+        result.push_str(WStr::from_units(b"</flashrichtext>"));
+        result
+    }
+
+    /// Parse a Flash XML fragment, as produced by [`FormatSpans::to_flash_xml`].
+    ///
+    /// Properties missing from a span are taken from `default_format`, and
+    /// text outside of spans is ignored. Returns `None` if the fragment is
+    /// malformed.
+    pub fn from_flash_xml(xml: &WStr, default_format: TextFormat) -> Option<Self> {
+        let xml = xml.to_utf8_lossy();
+        let mut reader = Reader::from_str(&xml);
+        reader.config_mut().expand_empty_elements = true;
+
+        let mut text = WString::new();
+        let mut spans = Vec::new();
+        let mut span_format: Option<TextFormat> = None;
+        loop {
+            match reader.read_event().ok()? {
+                Event::Start(ref e) if e.name().as_ref() == b"span" => {
+                    let mut format = TextFormat::default();
+                    for attribute in e.attributes() {
+                        let attribute = attribute.ok()?;
+                        let value = attribute.unescape_value().ok()?;
+                        format.set_flash_xml_attribute(attribute.key.as_ref(), &value);
+                    }
+                    span_format = Some(format.mix_with(default_format.clone()));
+                }
+                Event::End(ref e) if e.name().as_ref() == b"span" => span_format = None,
+                Event::Text(ref e) => {
+                    let Some(format) = &span_format else {
+                        continue;
+                    };
+                    let span_text = WString::from_utf8(&e.unescape().ok()?);
+                    spans.push(TextSpan::with_length_and_format(span_text.len(), format));
+                    text.push_str(&span_text);
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        if spans.is_empty() {
+            spans.push(TextSpan::with_length_and_format(0, &default_format));
+        }
+        let mut result = Self {
+            text,
+            displayed_text: WString::new(),
+            spans,
+            default_format,
+            images: Vec::new(),
+            links: Vec::new(),
+        };
+        result.normalize();
+        Some(result)
+    }
+
+    /// Replace the text in the given range with formatted text, keeping its formatting.
+    pub fn replace_with_spans(&mut self, from: usize, to: usize, with: &FormatSpans) {
+        self.replace_text(from, to, with.text());
+
+        let mut position = from;
+        for (_start, _end, _text, span) in with.iter_spans() {
+            let end = position + span.span_length;
+            self.set_text_format(position, end, &span.get_text_format());
+            position = end;
+        }
+    }
+}
+
+/// Escape text for a Flash XML fragment.
+///
+/// Newlines are escaped too, so that they survive in attributes.
+fn escape_flash_xml(text: &WStr) -> String {
+    escape(&*text.to_utf8_lossy())
+        .replace('\r', "&#13;")
+        .replace('\n', "&#10;")
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
@@ -1753,6 +2201,30 @@ impl<'a> FormatState<'a> {
         }
     }
 
+    fn push_image(&mut self, image: &HtmlImage) {
+        let _ = write!(self.result, "<IMG SRC=\"{}\"", image.src);
+        if let Some(width) = image.width {
+            let _ = write!(self.result, " WIDTH=\"{width}\"");
+        }
+        if let Some(height) = image.height {
+            let _ = write!(self.result, " HEIGHT=\"{height}\"");
+        }
+        let _ = write!(
+            self.result,
+            " ALIGN=\"{}\" HSPACE=\"{}\" VSPACE=\"{}\"",
+            match image.align {
+                ImageAlign::Left => "left",
+                ImageAlign::Right => "right",
+            },
+            image.hspace,
+            image.vspace,
+        );
+        if !image.id.is_empty() {
+            let _ = write!(self.result, " ID=\"{}\"", image.id);
+        }
+        self.result.push_byte(b'>');
+    }
+
     fn push_line(&mut self, line: &WStr) {
         if line.is_empty() {
             return;
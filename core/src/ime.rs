@@ -0,0 +1,70 @@
+//! Input method state
+//!
+//! This keeps track of whether the movie allows input methods in text fields,
+//! and turns finished compositions into the events of the `IME` singleton.
+
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject, Object as Avm2Object,
+};
+use crate::context::UpdateContext;
+use crate::string::AvmString;
+use gc_arena::Collect;
+
+/// The state behind `flash.system.IME`.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct Ime<'gc> {
+    /// The `IME` instance, once the movie has accessed `System.ime`.
+    avm2_object: Option<Avm2Object<'gc>>,
+
+    /// Whether text fields accept input methods, as set by `IME.enabled`.
+    enabled: bool,
+}
+
+impl Default for Ime<'_> {
+    fn default() -> Self {
+        Self {
+            avm2_object: None,
+            enabled: true,
+        }
+    }
+}
+
+impl<'gc> Ime<'gc> {
+    pub fn set_avm2_object(&mut self, object: Avm2Object<'gc>) {
+        self.avm2_object = Some(object);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables input methods.
+    ///
+    /// Movies usually do this whenever a text field gains focus, so the
+    /// focused text field is updated right away. Disabling input methods
+    /// commits the composition in progress.
+    pub fn set_enabled(context: &mut UpdateContext<'gc>, enabled: bool) {
+        if context.ime.enabled == enabled {
+            return;
+        }
+        context.ime.enabled = enabled;
+
+        if let Some(text) = context.focus_tracker.get_as_edit_text() {
+            if !enabled {
+                text.ensure_ime_committed(context);
+            }
+            text.notify_ime(context);
+        }
+    }
+
+    /// Dispatches `imeComposition` once the user has finished a composition.
+    pub fn composition_finished(context: &mut UpdateContext<'gc>, text: AvmString<'gc>) {
+        let Some(object) = context.ime.avm2_object else {
+            return;
+        };
+        let mut activation = Avm2Activation::from_nothing(context);
+        let event = Avm2EventObject::ime_event(&mut activation, "imeComposition", text);
+        Avm2::dispatch_event(activation.context, event, object);
+    }
+}
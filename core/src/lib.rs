@@ -15,15 +15,18 @@ extern crate smallvec;
 #[macro_use]
 extern crate num_derive;
 
+mod accessibility;
 #[macro_use]
 mod avm1;
 mod avm2;
 mod binary_data;
 pub mod bitmap;
 pub mod buffer;
+mod capture;
 mod character;
 pub mod context;
 pub mod context_menu;
+pub mod datagram_socket;
 mod drawing;
 mod ecma_conversions;
 pub mod events;
@@ -31,12 +34,15 @@ pub mod focus_tracker;
 mod font;
 mod frame_lifecycle;
 mod html;
+mod ime;
 mod input;
 mod library;
 pub mod limits;
 pub mod loader;
 mod local_connection;
 mod locale;
+mod native_application;
+mod native_process;
 mod net_connection;
 pub mod pixel_bender;
 mod player;
@@ -67,6 +73,7 @@ pub use events::PlayerEvent;
 pub use font::DefaultFont;
 pub use indexmap;
 pub use loader::LoadBehavior;
+pub use native_application::AirApplication;
 pub use player::{Player, PlayerBuilder, PlayerRuntime, StaticCallstack};
 pub use ruffle_render::backend::ViewportDimensions;
 pub use swf;
@@ -5,7 +5,7 @@ use crate::character::Character;
 use std::borrow::Cow;
 
 use crate::display_object::{Bitmap, Graphic, MorphShape, Text};
-use crate::font::{Font, FontDescriptor, FontType};
+use crate::font::{Font, FontDescriptor, FontType, TextRenderer};
 use crate::prelude::*;
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
@@ -422,6 +422,17 @@ pub struct Library<'gc> {
     /// The cached list of implementations per default font.
     default_font_cache: FnvHashMap<(DefaultFont, bool, bool), Vec<Font<'gc>>>,
 
+    /// The names of device fonts used for characters that other device fonts
+    /// have no glyphs for, in order of preference.
+    font_fallback_names: Vec<String>,
+
+    /// The fallback fonts loaded so far, along with the number of fallback
+    /// names they were loaded from.
+    font_fallback_cache: FnvHashMap<(bool, bool), (usize, Vec<Font<'gc>>)>,
+
+    /// Settings of the advanced anti-aliasing text renderer.
+    text_renderer: TextRenderer,
+
     /// A list of the symbols associated with specific AVM2 constructor
     /// prototypes.
     avm2_class_registry: Avm2ClassRegistry<'gc>,
@@ -437,6 +448,9 @@ unsafe impl<'gc> Collect<'gc> for Library<'gc> {
         for (_, val) in self.default_font_cache.iter() {
             cc.trace(val);
         }
+        for (_, (_, val)) in self.font_fallback_cache.iter() {
+            cc.trace(val);
+        }
         cc.trace(&self.device_fonts);
         cc.trace(&self.global_fonts);
         cc.trace(&self.avm2_class_registry);
@@ -452,6 +466,9 @@ impl<'gc> Library<'gc> {
             font_lookup_cache: Default::default(),
             default_font_names: Default::default(),
             default_font_cache: Default::default(),
+            font_fallback_names: Default::default(),
+            font_fallback_cache: Default::default(),
+            text_renderer: Default::default(),
             avm2_class_registry: Default::default(),
         }
     }
@@ -472,7 +489,10 @@ impl<'gc> Library<'gc> {
         self.movie_libraries.keys().collect()
     }
 
-    /// Returns the default Font implementations behind the built in names (ie `_sans`)
+    /// Returns the default Font implementations behind the built in names (ie `_sans`).
+    ///
+    /// The first font is the preferred one, the others are used for characters
+    /// it has no glyphs for.
     pub fn default_font(
         &mut self,
         name: DefaultFont,
@@ -494,8 +514,9 @@ impl<'gc> Library<'gc> {
             if let Some(font) = self
                 .get_or_load_exact_device_font(&name, is_bold, is_italic, ui, renderer, gc_context)
             {
-                result.push(font);
-                break; // TODO: Return multiple fonts when it's needed.
+                if !result.iter().any(|&other| Font::ptr_eq(other, font)) {
+                    result.push(font);
+                }
             }
         }
 
@@ -586,6 +607,103 @@ impl<'gc> Library<'gc> {
     pub fn set_default_font(&mut self, font: DefaultFont, names: Vec<String>) {
         self.default_font_names.insert(font, names);
         self.default_font_cache.clear();
+        self.font_fallback_cache.clear();
+    }
+
+    /// Returns all device fonts available to movies, such as for `Font.enumerateFonts(true)`.
+    ///
+    /// Fonts that haven't been loaded yet are not loaded by this; they are
+    /// described by fonts without any glyphs instead.
+    pub fn available_device_fonts(
+        &self,
+        ui: &dyn UiBackend,
+        gc_context: &Mutation<'gc>,
+    ) -> Vec<Font<'gc>> {
+        let mut fonts: Vec<Font<'gc>> = self
+            .device_fonts
+            .0
+            .values()
+            .copied()
+            .filter(|font| font.font_type() == FontType::Device)
+            .collect();
+        for descriptor in ui.device_fonts() {
+            if self
+                .device_fonts
+                .get(
+                    &descriptor.name,
+                    FontType::Device,
+                    descriptor.is_bold,
+                    descriptor.is_italic,
+                )
+                .is_none()
+            {
+                fonts.push(Font::empty_font(
+                    gc_context,
+                    &descriptor.name,
+                    descriptor.is_bold,
+                    descriptor.is_italic,
+                    FontType::Device,
+                ));
+            }
+        }
+        fonts
+    }
+
+    /// Returns the device fonts used for characters that other device fonts
+    /// have no glyphs for, in order of preference.
+    ///
+    /// Fallback fonts are loaded lazily: only as many of them are loaded as
+    /// needed to cover the `missing` characters.
+    pub fn font_fallbacks(
+        &mut self,
+        missing: &[char],
+        is_bold: bool,
+        is_italic: bool,
+        ui: &dyn UiBackend,
+        renderer: &mut dyn RenderBackend,
+        gc_context: &Mutation<'gc>,
+    ) -> Vec<Font<'gc>> {
+        let (mut loaded, mut result) = self
+            .font_fallback_cache
+            .remove(&(is_bold, is_italic))
+            .unwrap_or_default();
+
+        while loaded < self.font_fallback_names.len()
+            && !missing.iter().all(|&c| {
+                result
+                    .iter()
+                    .any(|font: &Font<'gc>| font.get_glyph_for_char(c).is_some())
+            })
+        {
+            let name = self.font_fallback_names[loaded].clone();
+            loaded += 1;
+
+            let fonts = if let Some(default_font) = DefaultFont::from_name(&name) {
+                self.default_font(default_font, is_bold, is_italic, ui, renderer, gc_context)
+            } else {
+                self.get_or_load_device_font(&name, is_bold, is_italic, ui, renderer, gc_context)
+                    .into_iter()
+                    .collect()
+            };
+            for font in fonts {
+                if !result.iter().any(|&other| Font::ptr_eq(other, font)) {
+                    result.push(font);
+                }
+            }
+        }
+
+        self.font_fallback_cache
+            .insert((is_bold, is_italic), (loaded, result.clone()));
+        result
+    }
+
+    /// Sets the names of device fonts used for characters that other device
+    /// fonts have no glyphs for, in order of preference.
+    ///
+    /// Names of default fonts (ie `_sans`) may be used as well.
+    pub fn set_font_fallbacks(&mut self, names: Vec<String>) {
+        self.font_fallback_names = names;
+        self.font_fallback_cache.clear();
     }
 
     pub fn register_device_font(
@@ -628,6 +746,7 @@ impl<'gc> Library<'gc> {
             }
         }
         self.default_font_cache.clear();
+        self.font_fallback_cache.clear();
     }
 
     /// Find a font by it's name and parameters.
@@ -660,6 +779,16 @@ impl<'gc> Library<'gc> {
         self.global_fonts.register(font);
     }
 
+    /// Get the settings of the advanced anti-aliasing text renderer.
+    pub fn text_renderer(&self) -> &TextRenderer {
+        &self.text_renderer
+    }
+
+    /// Mutate the settings of the advanced anti-aliasing text renderer.
+    pub fn text_renderer_mut(&mut self) -> &mut TextRenderer {
+        &mut self.text_renderer
+    }
+
     /// Get the AVM2 class registry.
     pub fn avm2_class_registry(&self) -> &Avm2ClassRegistry<'gc> {
         &self.avm2_class_registry
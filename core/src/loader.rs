@@ -27,6 +27,7 @@ use crate::events::ClipEvent;
 use crate::frame_lifecycle::catchup_display_object_to_frame;
 use crate::limits::ExecutionLimit;
 use crate::player::{Player, PostFrameCallback};
+use crate::sandbox;
 use crate::streams::NetStream;
 use crate::string::{AvmString, StringContext};
 use crate::tag_utils::SwfMovie;
@@ -213,6 +214,9 @@ pub enum Error {
     #[error("Could not fetch: {0:?}")]
     FetchError(String),
 
+    #[error("Security sandbox violation: {0} cannot load data from {1}")]
+    SecuritySandboxViolation(String, String),
+
     // TODO: We can't support lifetimes on this error object yet (or we'll need some backends inside
     // the GC arena). We're losing info here. How do we fix that?
     #[error("Error running avm1 script: {0}")]
@@ -485,6 +489,7 @@ impl<'gc> LoadManager<'gc> {
         player: Weak<Mutex<Player>>,
         target_object: Object<'gc>,
         request: Request,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::Form {
             self_handle: None,
@@ -492,7 +497,7 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.form_loader(player, request)
+        loader.form_loader(player, request, sandboxed_movie)
     }
 
     /// Kick off a form data load into an AVM1 object.
@@ -503,6 +508,7 @@ impl<'gc> LoadManager<'gc> {
         player: Weak<Mutex<Player>>,
         target_object: Object<'gc>,
         request: Request,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::LoadVars {
             self_handle: None,
@@ -510,7 +516,7 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.load_vars_loader(player, request)
+        loader.load_vars_loader(player, request, sandboxed_movie)
     }
 
     /// Kick off an AVM1 StyleSheet load
@@ -521,6 +527,7 @@ impl<'gc> LoadManager<'gc> {
         player: Weak<Mutex<Player>>,
         target_object: Object<'gc>,
         request: Request,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::StyleSheet {
             self_handle: None,
@@ -528,18 +535,22 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.load_stylesheet_loader(player, request)
+        loader.load_stylesheet_loader(player, request, sandboxed_movie)
     }
 
     /// Kick off a data load into a `URLLoader`, updating
     /// its `data` property when the load completes.
     ///
+    /// If `sandboxed_movie` is given, the load only happens once the
+    /// security sandbox allows that movie to access the URL.
+    ///
     /// Returns the loader's async process, which you will need to spawn.
     pub fn load_data_into_url_loader(
         &mut self,
         player: Weak<Mutex<Player>>,
         target_object: Avm2Object<'gc>,
         request: Request,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::LoadURLLoader {
             self_handle: None,
@@ -547,7 +558,7 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.load_url_loader(player, request)
+        loader.load_url_loader(player, request, sandboxed_movie)
     }
 
     /// Kick off an AVM1 audio load.
@@ -559,6 +570,7 @@ impl<'gc> LoadManager<'gc> {
         target_object: Object<'gc>,
         request: Request,
         is_streaming: bool,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::SoundAvm1 {
             self_handle: None,
@@ -566,7 +578,7 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.sound_loader_avm1(player, request, is_streaming)
+        loader.sound_loader_avm1(player, request, is_streaming, sandboxed_movie)
     }
 
     /// Kick off an AVM2 audio load.
@@ -577,6 +589,7 @@ impl<'gc> LoadManager<'gc> {
         player: Weak<Mutex<Player>>,
         target_object: Avm2Object<'gc>,
         request: Request,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::SoundAvm2 {
             self_handle: None,
@@ -584,7 +597,7 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.sound_loader_avm2(player, request)
+        loader.sound_loader_avm2(player, request, sandboxed_movie)
     }
 
     pub fn load_netstream(
@@ -592,6 +605,7 @@ impl<'gc> LoadManager<'gc> {
         player: Weak<Mutex<Player>>,
         target_stream: NetStream<'gc>,
         request: Request,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::NetStream {
             self_handle: None,
@@ -599,7 +613,7 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.stream_loader(player, request)
+        loader.stream_loader(player, request, sandboxed_movie)
     }
 
     /// Process tags on all loaders in the Parsing phase.
@@ -1038,6 +1052,21 @@ impl<'gc> Loader<'gc> {
         }
     }
 
+    /// Starts fetching `request`.
+    ///
+    /// If `sandboxed_movie` is given, the request is only made once the
+    /// security sandbox allows that movie to load data from its URL.
+    fn fetch(
+        player: &Arc<Mutex<Player>>,
+        request: Request,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
+    ) -> OwnedFuture<Box<dyn SuccessResponse>, ErrorResponse> {
+        match sandboxed_movie {
+            Some(movie) => sandbox::sandboxed_fetch(player.clone(), movie, request),
+            None => player.lock().unwrap().navigator().fetch(request),
+        }
+    }
+
     /// Construct a future for the root movie loader.
     fn root_movie_loader(
         &mut self,
@@ -1311,6 +1340,7 @@ impl<'gc> Loader<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         request: Request,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::Form { self_handle, .. } => self_handle.expect("Loader not self-introduced"),
@@ -1322,7 +1352,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = Self::fetch(&player, request, sandboxed_movie);
 
             let response = fetch.await.map_err(|e| e.error)?;
             let response_encoding = response.text_encoding();
@@ -1393,6 +1423,7 @@ impl<'gc> Loader<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         request: Request,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::LoadVars { self_handle, .. } => {
@@ -1406,7 +1437,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = Self::fetch(&player, request, sandboxed_movie);
             let response = Self::wait_for_full_response(fetch).await;
 
             // Fire the load handler.
@@ -1489,6 +1520,7 @@ impl<'gc> Loader<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         request: Request,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::StyleSheet { self_handle, .. } => {
@@ -1502,7 +1534,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = Self::fetch(&player, request, sandboxed_movie);
             let response = Self::wait_for_full_response(fetch).await;
 
             // Fire the load handler.
@@ -1558,6 +1590,7 @@ impl<'gc> Loader<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         request: Request,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::LoadURLLoader { self_handle, .. } => {
@@ -1571,7 +1604,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = Self::fetch(&player, request, sandboxed_movie);
             let response = Self::wait_for_full_response(fetch).await;
 
             player.lock().unwrap().update(|uc| {
@@ -1677,6 +1710,17 @@ impl<'gc> Loader<'gc> {
                             Avm2EventObject::bare_default_event(activation.context, "complete");
                         Avm2::dispatch_event(uc, complete_evt, target);
                     }
+                    Err(ErrorResponse {
+                        error: Error::SecuritySandboxViolation(movie_url, url),
+                        ..
+                    }) => {
+                        let message = format!(
+                            "Error #2048: Security sandbox violation: {movie_url} cannot load data from {url}."
+                        );
+                        let security_error_evt =
+                            Avm2EventObject::security_error_event(&mut activation, &message, 2048);
+                        Avm2::dispatch_event(uc, security_error_evt, target);
+                    }
                     Err(response) => {
                         tracing::error!(
                             "Error during URLLoader load of {:?}: {:?}",
@@ -1726,6 +1770,7 @@ impl<'gc> Loader<'gc> {
         player: Weak<Mutex<Player>>,
         request: Request,
         is_streaming: bool,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::SoundAvm1 { self_handle, .. } => {
@@ -1739,7 +1784,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = Self::fetch(&player, request, sandboxed_movie);
             let response = Self::wait_for_full_response(fetch).await;
 
             // Fire the load handler.
@@ -1793,6 +1838,7 @@ impl<'gc> Loader<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         request: Request,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::SoundAvm2 { self_handle, .. } => {
@@ -1806,7 +1852,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = Self::fetch(&player, request, sandboxed_movie);
             let response = Self::wait_for_full_response(fetch).await;
 
             player.lock().unwrap().update(|uc| {
@@ -1855,6 +1901,19 @@ impl<'gc> Loader<'gc> {
                             Avm2EventObject::bare_default_event(activation.context, "complete");
                         Avm2::dispatch_event(activation.context, complete_evt, sound_object);
                     }
+                    Err(ErrorResponse {
+                        error: Error::SecuritySandboxViolation(movie_url, url),
+                        ..
+                    }) => {
+                        let mut activation = Avm2Activation::from_nothing(uc);
+
+                        let message = format!(
+                            "Error #2048: Security sandbox violation: {movie_url} cannot load data from {url}."
+                        );
+                        let security_error_evt =
+                            Avm2EventObject::security_error_event(&mut activation, &message, 2048);
+                        Avm2::dispatch_event(uc, security_error_evt, sound_object);
+                    }
                     Err(_err) => {
                         let mut activation = Avm2Activation::from_nothing(uc);
 
@@ -1878,6 +1937,7 @@ impl<'gc> Loader<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         request: Request,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::NetStream { self_handle, .. } => {
@@ -1891,7 +1951,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = Self::fetch(&player, request, sandboxed_movie);
             match fetch.await {
                 Ok(mut response) => {
                     let expected_length = response.expected_length();
@@ -1924,7 +1984,7 @@ impl<'gc> Loader<'gc> {
                             };
 
                             match chunk {
-                                Ok(Some(mut data)) => stream.load_buffer(uc, &mut data),
+                                Ok(Some(mut data)) => stream.append_buffer(uc, &mut data),
                                 Ok(None) => stream.finish_buffer(),
                                 Err(err) => stream.report_error(err),
                             }
@@ -0,0 +1,137 @@
+//! AIR `NativeApplication` lifecycle
+//!
+//! This keeps track of how an AIR application was launched, and turns focus
+//! changes and user input into the events of the `NativeApplication` singleton.
+
+use crate::avm2::{Avm2, EventObject as Avm2EventObject, Object as Avm2Object};
+use crate::context::UpdateContext;
+use gc_arena::Collect;
+use std::time::Duration;
+use web_time::Instant;
+
+/// The default time without user input before the application is considered idle.
+const DEFAULT_IDLE_THRESHOLD: Duration = Duration::from_secs(300);
+
+/// How an AIR application was launched.
+#[derive(Clone, Debug, Default)]
+pub struct AirApplication {
+    /// The arguments the application was invoked with.
+    pub arguments: Vec<String>,
+
+    /// The native path of the directory relative arguments should be resolved against.
+    pub current_directory: Option<String>,
+
+    /// The contents of the application descriptor file, if there is one.
+    pub descriptor: Option<String>,
+}
+
+/// The state behind `flash.desktop.NativeApplication`.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct NativeApplication<'gc> {
+    /// The `NativeApplication` instance, once the movie has created it.
+    avm2_object: Option<Avm2Object<'gc>>,
+
+    #[collect(require_static)]
+    launch: AirApplication,
+
+    #[collect(require_static)]
+    last_user_input: Instant,
+
+    #[collect(require_static)]
+    idle_threshold: Duration,
+
+    is_idle: bool,
+
+    is_active: bool,
+}
+
+impl<'gc> NativeApplication<'gc> {
+    pub fn new(launch: AirApplication) -> Self {
+        Self {
+            avm2_object: None,
+            launch,
+            last_user_input: Instant::now(),
+            idle_threshold: DEFAULT_IDLE_THRESHOLD,
+            is_idle: false,
+            is_active: false,
+        }
+    }
+
+    pub fn launch(&self) -> &AirApplication {
+        &self.launch
+    }
+
+    pub fn set_avm2_object(&mut self, object: Avm2Object<'gc>) {
+        self.avm2_object = Some(object);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    pub fn time_since_last_user_input(&self) -> Duration {
+        self.last_user_input.elapsed()
+    }
+
+    pub fn idle_threshold(&self) -> Duration {
+        self.idle_threshold
+    }
+
+    pub fn set_idle_threshold(&mut self, idle_threshold: Duration) {
+        self.idle_threshold = idle_threshold;
+    }
+
+    /// Records that the user interacted with the application,
+    /// dispatching `userPresent` if it was idle.
+    pub fn user_input(context: &mut UpdateContext<'gc>) {
+        let application = &mut *context.native_application;
+        application.last_user_input = Instant::now();
+        if application.is_idle {
+            application.is_idle = false;
+            Self::dispatch_event(context, "userPresent", false);
+        }
+    }
+
+    /// Dispatches `userIdle` once the user has been away for longer than the idle threshold.
+    pub fn tick(context: &mut UpdateContext<'gc>) {
+        let application = &mut *context.native_application;
+        if !application.is_idle
+            && application.time_since_last_user_input() >= application.idle_threshold
+        {
+            application.is_idle = true;
+            Self::dispatch_event(context, "userIdle", false);
+        }
+    }
+
+    /// Dispatches `activate` or `deactivate` when the application gains or loses focus.
+    pub fn set_active(context: &mut UpdateContext<'gc>, is_active: bool) {
+        if context.native_application.is_active == is_active {
+            return;
+        }
+        context.native_application.is_active = is_active;
+        let event_type = if is_active { "activate" } else { "deactivate" };
+        Self::dispatch_event(context, event_type, false);
+    }
+
+    /// Dispatches the cancelable `exiting` event.
+    ///
+    /// Returns `false` if the movie prevented the application from exiting.
+    pub fn request_exit(context: &mut UpdateContext<'gc>) -> bool {
+        Self::dispatch_event(context, "exiting", true)
+    }
+
+    /// Returns `false` if the event was cancelled.
+    fn dispatch_event(
+        context: &mut UpdateContext<'gc>,
+        event_type: &str,
+        cancelable: bool,
+    ) -> bool {
+        let Some(object) = context.native_application.avm2_object else {
+            return true;
+        };
+        let event = Avm2EventObject::bare_event(context, event_type, false, cancelable);
+        Avm2::dispatch_event(context, event, object);
+        !event.event().is_cancelled()
+    }
+}
@@ -0,0 +1,161 @@
+//! AIR `NativeProcess` implementation
+//!
+//! Processes are run by the `ProcessBackend`; this module keeps track of the
+//! processes a movie has started and turns their output into events.
+
+use crate::avm2::globals::methods::flash_desktop_native_process as native_process_methods;
+use crate::avm2::globals::slots::flash_desktop_native_process as native_process_slots;
+use crate::avm2::object::TObject;
+use crate::avm2::{Activation as Avm2Activation, Avm2, EventObject, Object as Avm2Object};
+use crate::backend::process::{ProcessEvent, ProcessHandle};
+use crate::context::UpdateContext;
+use gc_arena::Collect;
+
+/// All native processes that are still running.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct NativeProcesses<'gc> {
+    processes: Vec<NativeProcess<'gc>>,
+}
+
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+struct NativeProcess<'gc> {
+    #[collect(require_static)]
+    handle: ProcessHandle,
+
+    /// The `NativeProcess` that started this process.
+    object: Avm2Object<'gc>,
+}
+
+impl<'gc> NativeProcesses<'gc> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, handle: ProcessHandle, object: Avm2Object<'gc>) {
+        self.processes.push(NativeProcess { handle, object });
+    }
+
+    /// The process started by a `NativeProcess`, if it's still running.
+    pub fn handle_for(&self, object: Avm2Object<'gc>) -> Option<ProcessHandle> {
+        self.processes
+            .iter()
+            .find(|process| Avm2Object::ptr_eq(process.object, object))
+            .map(|process| process.handle)
+    }
+
+    /// Sends anything written to `standardInput` to the process.
+    pub fn flush_standard_input(
+        context: &mut UpdateContext<'gc>,
+        handle: ProcessHandle,
+        object: Avm2Object<'gc>,
+    ) {
+        let Some(input) = object
+            .get_slot(native_process_slots::_STANDARD_INPUT)
+            .as_object()
+        else {
+            return;
+        };
+        let data = match input.as_bytearray_mut() {
+            Some(mut input) if input.len() > 0 => {
+                let data = input.bytes().to_vec();
+                input.clear();
+                data
+            }
+            _ => return,
+        };
+        context.process.write_standard_input(handle, &data);
+
+        let mut activation = Avm2Activation::from_nothing(context);
+        let event =
+            EventObject::progress_event(&mut activation, "standardInputProgress", data.len(), 0);
+        Avm2::dispatch_event(activation.context, event, object);
+    }
+
+    pub fn tick(context: &mut UpdateContext<'gc>) {
+        if context.native_processes.processes.is_empty() {
+            return;
+        }
+
+        let processes = context.native_processes.processes.clone();
+        let mut events = Vec::new();
+        for process in processes {
+            Self::flush_standard_input(context, process.handle, process.object);
+
+            context.process.poll(process.handle, &mut events);
+            for event in events.drain(..) {
+                process.handle_event(context, event);
+            }
+        }
+    }
+}
+
+impl<'gc> NativeProcess<'gc> {
+    fn handle_event(self, context: &mut UpdateContext<'gc>, event: ProcessEvent) {
+        let mut activation = Avm2Activation::from_nothing(context);
+        match event {
+            ProcessEvent::StandardOutput(data) => {
+                self.receive(native_process_slots::_STANDARD_OUTPUT, &data);
+                self.dispatch_progress(&mut activation, "standardOutputData", data.len());
+            }
+            ProcessEvent::StandardError(data) => {
+                self.receive(native_process_slots::_STANDARD_ERROR, &data);
+                self.dispatch_progress(&mut activation, "standardErrorData", data.len());
+            }
+            ProcessEvent::StandardOutputClosed => {
+                let event =
+                    EventObject::bare_default_event(activation.context, "standardOutputClose");
+                Avm2::dispatch_event(activation.context, event, self.object);
+            }
+            ProcessEvent::StandardErrorClosed => {
+                let event =
+                    EventObject::bare_default_event(activation.context, "standardErrorClose");
+                Avm2::dispatch_event(activation.context, event, self.object);
+            }
+            ProcessEvent::Exit(exit_code) => {
+                activation
+                    .context
+                    .native_processes
+                    .processes
+                    .retain(|process| process.handle != self.handle);
+
+                let exit_code = exit_code.map_or(f64::NAN, f64::from);
+                if let Err(e) = self.object.call_method(
+                    native_process_methods::PROCESS_EXITED,
+                    &[exit_code.into()],
+                    &mut activation,
+                ) {
+                    tracing::error!("Error finishing NativeProcess: {e:?}");
+                }
+            }
+        }
+    }
+
+    /// Appends data to one of the output buffers, keeping the read position.
+    fn receive(self, slot: u32, data: &[u8]) {
+        let Some(buffer) = self.object.get_slot(slot).as_object() else {
+            return;
+        };
+        if let Some(mut buffer) = buffer.as_bytearray_mut() {
+            // Fully read data is dropped, so the buffers don't grow forever.
+            if buffer.bytes_available() == 0 {
+                buffer.clear();
+            }
+            let end = buffer.len();
+            if let Err(e) = buffer.write_at(data, end) {
+                tracing::warn!("NativeProcess output dropped: {e:?}");
+            }
+        }
+    }
+
+    fn dispatch_progress(
+        self,
+        activation: &mut Avm2Activation<'_, 'gc>,
+        event_type: &str,
+        bytes_loaded: usize,
+    ) {
+        let event = EventObject::progress_event(activation, event_type, bytes_loaded, 0);
+        Avm2::dispatch_event(activation.context, event, self.object);
+    }
+}
@@ -1,24 +1,47 @@
 use crate::avm1::globals::netconnection::NetConnection as Avm1NetConnectionObject;
+use crate::avm1::globals::shared_object as avm1_shared_object;
 use crate::avm1::Object as Avm1Object;
+use crate::avm2::globals::flash::net::shared_object as avm2_shared_object;
 use crate::avm2::object::{
     NetConnectionObject as Avm2NetConnectionObject, ResponderObject as Avm2ResponderObject,
 };
-use crate::avm2::{Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject};
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, Error as Avm2Error, EventObject as Avm2EventObject,
+    Object as Avm2Object, Value as Avm2Value,
+};
 use crate::backend::navigator::{ErrorResponse, NavigatorBackend, OwnedFuture, Request};
 use crate::context::UpdateContext;
 use crate::loader::Error;
+use crate::sandbox;
+use crate::socket::SandboxedConnection;
+use crate::streams::NetStream;
+use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 use crate::Player;
 use flash_lso::packet::{Header, Message, Packet};
-use flash_lso::types::{AMFVersion, Value as AmfValue};
+use flash_lso::types::{AMFVersion, Element, Value as AmfValue};
 use gc_arena::collect::Trace;
 use gc_arena::{Collect, DynamicRoot, Gc, Rootable};
+use rtmp::{write_amf0, Rtmp, RtmpEvent};
+use ruffle_macros::istr;
+use shared_object::{
+    SharedObjectEvent, SharedObjectMessage, SharedObjectServer, MAX_STRING_LENGTH,
+};
 use slotmap::{new_key_type, SlotMap};
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
-use std::sync::{Mutex, Weak};
+use std::sync::{Arc, Mutex, Weak};
+use web_time::Instant;
+
+pub use shared_object::SyncUpdate;
+
+mod rtmp;
+mod shared_object;
 
 new_key_type! {
     pub struct NetConnectionHandle;
+    pub struct RemoteSharedObjectHandle;
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -68,7 +91,7 @@ impl ResponderHandle {
     }
 }
 
-#[derive(Copy, Clone, Collect)]
+#[derive(Copy, Clone, Debug, Collect)]
 #[collect(no_drop)]
 pub enum NetConnectionObject<'gc> {
     Avm2(Avm2NetConnectionObject<'gc>),
@@ -76,6 +99,15 @@ pub enum NetConnectionObject<'gc> {
 }
 
 impl NetConnectionObject<'_> {
+    pub fn handle(&self) -> Option<NetConnectionHandle> {
+        match self {
+            NetConnectionObject::Avm2(object) => object.handle(),
+            NetConnectionObject::Avm1(object) => {
+                Avm1NetConnectionObject::cast((*object).into()).and_then(|nc| nc.handle())
+            }
+        }
+    }
+
     pub fn set_handle(&self, handle: Option<NetConnectionHandle>) -> Option<NetConnectionHandle> {
         match self {
             NetConnectionObject::Avm2(object) => object.set_handle(handle),
@@ -102,9 +134,75 @@ impl<'gc> From<Avm1Object<'gc>> for NetConnectionObject<'gc> {
     }
 }
 
+/// A remote shared object, as returned by `SharedObject.getRemote`.
+#[derive(Copy, Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub enum SharedObjectTarget<'gc> {
+    Avm2(Avm2Object<'gc>),
+    Avm1(Avm1Object<'gc>),
+}
+
+impl SharedObjectTarget<'_> {
+    fn ptr_eq(self, other: Self) -> bool {
+        match (self, other) {
+            (SharedObjectTarget::Avm2(a), SharedObjectTarget::Avm2(b)) => Avm2Object::ptr_eq(a, b),
+            (SharedObjectTarget::Avm1(a), SharedObjectTarget::Avm1(b)) => Avm1Object::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+/// The synchronization state of a remote shared object.
+struct RemoteSharedObject<'gc> {
+    target: SharedObjectTarget<'gc>,
+    name: String,
+
+    /// The `remotePath` given to `getRemote`, which identifies the server.
+    uri: String,
+
+    persistent: bool,
+
+    /// The connection set by `connect`.
+    connection: Option<NetConnectionHandle>,
+
+    /// Whether the server accepted `connect`, after which changes are sent.
+    connected: bool,
+
+    /// The version of the data last received from the server.
+    version: u32,
+
+    /// How many times per second changes are sent, as set by `setFps`.
+    ///
+    /// `None` sends changes on every update, and 0 stops sending them.
+    fps: Option<f64>,
+
+    /// Whether changes should be sent on the next update regardless of `fps`.
+    force_sync: bool,
+
+    last_sync: Option<Instant>,
+
+    /// The serialized value of every slot, as last sent to or received from
+    /// the server. Changes are found by comparing against these.
+    synced: HashMap<String, Vec<u8>>,
+
+    /// Slots changed by us, for which the server didn't answer yet.
+    pending: HashSet<String>,
+
+    /// Slots to send on the next update even if they look unchanged.
+    dirty: HashSet<String>,
+}
+
 /// Manages the collection of NetConnections.
 pub struct NetConnections<'gc> {
     connections: SlotMap<NetConnectionHandle, NetConnection<'gc>>,
+
+    shared_objects: SlotMap<RemoteSharedObjectHandle, RemoteSharedObject<'gc>>,
+
+    /// The server for shared objects of connections connected to `null`.
+    local_server: SharedObjectServer<NetConnectionHandle>,
+
+    /// Messages from the local server, delivered on the next update.
+    local_messages: Vec<(NetConnectionHandle, SharedObjectMessage)>,
 }
 
 unsafe impl<'gc> Collect<'gc> for NetConnections<'gc> {
@@ -112,6 +210,9 @@ unsafe impl<'gc> Collect<'gc> for NetConnections<'gc> {
         for (_, connection) in self.connections.iter() {
             cc.trace(connection);
         }
+        for (_, shared_object) in self.shared_objects.iter() {
+            cc.trace(&shared_object.target);
+        }
     }
 }
 
@@ -119,6 +220,9 @@ impl Default for NetConnections<'_> {
     fn default() -> Self {
         Self {
             connections: SlotMap::with_key(),
+            shared_objects: SlotMap::with_key(),
+            local_server: SharedObjectServer::default(),
+            local_messages: vec![],
         }
     }
 }
@@ -132,6 +236,7 @@ impl<'gc> NetConnections<'gc> {
         let connection = NetConnection {
             object: target,
             protocol: NetConnectionProtocol::Local,
+            streams: vec![],
         };
         let handle = context.net_connections.connections.insert(connection);
 
@@ -163,10 +268,15 @@ impl<'gc> NetConnections<'gc> {
         }
     }
 
+    /// Connects to a Flash Remoting gateway.
+    ///
+    /// If `sandboxed_movie` is given, calls are only sent once the security
+    /// sandbox allows that movie to access the gateway.
     pub fn connect_to_flash_remoting<O: Into<NetConnectionObject<'gc>>>(
         context: &mut UpdateContext<'gc>,
         target: O,
         url: String,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
     ) {
         let target = target.into();
         let connection = NetConnection {
@@ -175,7 +285,9 @@ impl<'gc> NetConnections<'gc> {
                 url,
                 headers: vec![],
                 outgoing_queue: vec![],
+                sandboxed_movie,
             }),
+            streams: vec![],
         };
         let handle = context.net_connections.connections.insert(connection);
 
@@ -186,11 +298,77 @@ impl<'gc> NetConnections<'gc> {
         // No open event here
     }
 
+    /// Starts connecting to an RTMP server, with the status being reported
+    /// once the server answers.
+    ///
+    /// `arguments` are passed to the server along with the `connect` command.
+    /// If `sandboxed_movie` is given, the server is only contacted once the
+    /// security sandbox allows that movie to connect to it.
+    pub fn connect_to_rtmp<O: Into<NetConnectionObject<'gc>>>(
+        context: &mut UpdateContext<'gc>,
+        target: O,
+        url: String,
+        object_encoding: AMFVersion,
+        arguments: Vec<Rc<AmfValue>>,
+        sandboxed_movie: Option<Arc<SwfMovie>>,
+    ) {
+        let target = target.into();
+        let flash_version = context.system.get_version_string(context.player_version);
+        let swf_url = context.swf.url().to_string();
+        let sandboxed = sandboxed_movie.map(|movie| SandboxedConnection {
+            player: context.player.clone(),
+            movie,
+        });
+        let Some(rtmp) = Rtmp::connect(
+            context.navigator,
+            url,
+            flash_version,
+            swf_url,
+            object_encoding,
+            arguments,
+            sandboxed,
+        ) else {
+            if let Some(existing_handle) = target.set_handle(None) {
+                NetConnections::close(context, existing_handle, false);
+            }
+            Self::dispatch_status(
+                context,
+                target,
+                vec![("code", "NetConnection.Connect.Failed"), ("level", "error")],
+            );
+            return;
+        };
+
+        let connection = NetConnection {
+            object: target,
+            protocol: NetConnectionProtocol::Rtmp(rtmp),
+            streams: vec![],
+        };
+        let handle = context.net_connections.connections.insert(connection);
+
+        if let Some(existing_handle) = target.set_handle(Some(handle)) {
+            NetConnections::close(context, existing_handle, false);
+        }
+    }
+
     pub fn close(context: &mut UpdateContext<'gc>, handle: NetConnectionHandle, is_explicit: bool) {
-        let Some(connection) = context.net_connections.connections.remove(handle) else {
+        let Some(mut connection) = context.net_connections.connections.remove(handle) else {
             return;
         };
 
+        if let NetConnectionProtocol::Rtmp(rtmp) = &mut connection.protocol {
+            rtmp.close(context.navigator);
+        }
+
+        // Shared objects need to be connected again, to a new connection.
+        context.net_connections.local_server.disconnect(handle);
+        for (_, shared_object) in context.net_connections.shared_objects.iter_mut() {
+            if shared_object.connection == Some(handle) {
+                shared_object.connection = None;
+                shared_object.connected = false;
+            }
+        }
+
         match connection.object {
             NetConnectionObject::Avm2(object) => {
                 let mut activation = Avm2Activation::from_nothing(context);
@@ -240,11 +418,598 @@ impl<'gc> NetConnections<'gc> {
     }
 
     pub fn update_connections(context: &mut UpdateContext<'gc>) {
+        let mut events = vec![];
         for (handle, connection) in context.net_connections.connections.iter_mut() {
             connection.update(handle, context.navigator, context.player.clone());
+
+            if let NetConnectionProtocol::Rtmp(rtmp) = &mut connection.protocol {
+                events.extend(rtmp.take_events().into_iter().map(|event| (handle, event)));
+            }
+        }
+
+        for (handle, event) in events {
+            Self::handle_rtmp_event(context, handle, event);
+        }
+
+        for (handle, message) in std::mem::take(&mut context.net_connections.local_messages) {
+            Self::handle_shared_object_message(context, handle, message);
+        }
+        Self::sync_shared_objects(context);
+    }
+
+    fn handle_rtmp_event(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        event: RtmpEvent,
+    ) {
+        // The connection may have been closed by an earlier event.
+        let Some(connection) = context.net_connections.connections.get(handle) else {
+            return;
+        };
+        let object = connection.object;
+
+        match event {
+            RtmpEvent::Failed => {
+                context.net_connections.connections.remove(handle);
+                if object.handle() == Some(handle) {
+                    object.set_handle(None);
+                }
+                Self::dispatch_status(
+                    context,
+                    object,
+                    vec![("code", "NetConnection.Connect.Failed"), ("level", "error")],
+                );
+            }
+            RtmpEvent::Closed => {
+                if object.handle() == Some(handle) {
+                    object.set_handle(None);
+                }
+                NetConnections::close(context, handle, false);
+            }
+            RtmpEvent::Status(info) => {
+                let info = info.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+                Self::dispatch_status(context, object, info);
+            }
+            RtmpEvent::Response(responder_handle, callback, message) => {
+                responder_handle.call(context, callback, message);
+            }
+            RtmpEvent::Invoke(method, arguments) => {
+                Self::invoke_client(context, object, &method, &arguments);
+            }
+            RtmpEvent::StreamStatus(index, info) => {
+                if let Some(stream) = connection.streams.get(index).copied() {
+                    let info = info.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+                    stream.trigger_status_event(context, info);
+                }
+            }
+            RtmpEvent::StreamData(index, mut data) => {
+                if let Some(stream) = connection.streams.get(index).copied() {
+                    stream.append_buffer(context, &mut data);
+                }
+            }
+            RtmpEvent::StreamEnd(index) => {
+                if let Some(stream) = connection.streams.get(index).copied() {
+                    stream.finish_buffer();
+                }
+            }
+            RtmpEvent::SharedObject(message) => {
+                Self::handle_shared_object_message(context, handle, message);
+            }
+        }
+    }
+
+    /// Dispatches a `netStatus` event (AVM2) or calls `onStatus` (AVM1).
+    fn dispatch_status(
+        context: &mut UpdateContext<'gc>,
+        object: NetConnectionObject<'gc>,
+        info: Vec<(&str, &str)>,
+    ) {
+        match object {
+            NetConnectionObject::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(context);
+                let event = Avm2EventObject::net_status_event(&mut activation, info);
+                Avm2::dispatch_event(activation.context, event, object.into());
+            }
+            NetConnectionObject::Avm1(object) => {
+                if let Err(e) = Avm1NetConnectionObject::on_status_info_event(context, object, info)
+                {
+                    tracing::error!("Unhandled error sending connection callback: {e}");
+                }
+            }
+        }
+    }
+
+    /// Calls a method of the connection's client on behalf of the server.
+    fn invoke_client(
+        context: &mut UpdateContext<'gc>,
+        object: NetConnectionObject<'gc>,
+        method: &str,
+        arguments: &[Rc<AmfValue>],
+    ) {
+        match object {
+            NetConnectionObject::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(context);
+                if let Err(e) = Self::invoke_avm2_client(&mut activation, object, method, arguments)
+                {
+                    tracing::error!("Unhandled error calling NetConnection client method: {e:?}");
+                }
+            }
+            NetConnectionObject::Avm1(object) => {
+                if let Err(e) =
+                    Avm1NetConnectionObject::call_client_method(context, object, method, arguments)
+                {
+                    tracing::error!("Unhandled error calling NetConnection client method: {e}");
+                }
+            }
+        }
+    }
+
+    fn invoke_avm2_client(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        object: Avm2NetConnectionObject<'gc>,
+        method: &str,
+        arguments: &[Rc<AmfValue>],
+    ) -> Result<(), Avm2Error<'gc>> {
+        let client = Avm2Value::from(Avm2Object::from(object))
+            .get_public_property(istr!("client"), activation)?;
+
+        let mut args = Vec::with_capacity(arguments.len());
+        for argument in arguments {
+            args.push(crate::avm2::amf::deserialize_value(activation, argument)?);
+        }
+
+        let method = AvmString::new_utf8(activation.gc(), method);
+        client.call_public_property(method, &args, activation)?;
+        Ok(())
+    }
+
+    /// Finds the remote shared object with the given name on a server.
+    pub fn find_shared_object(&self, uri: &str, name: &str) -> Option<SharedObjectTarget<'gc>> {
+        self.shared_objects
+            .values()
+            .find(|so| so.uri == uri && so.name == name)
+            .map(|so| so.target)
+    }
+
+    pub fn add_shared_object(
+        &mut self,
+        target: SharedObjectTarget<'gc>,
+        uri: String,
+        name: String,
+        persistent: bool,
+    ) -> RemoteSharedObjectHandle {
+        self.shared_objects.insert(RemoteSharedObject {
+            target,
+            name,
+            uri,
+            persistent,
+            connection: None,
+            connected: false,
+            version: 0,
+            fps: None,
+            force_sync: false,
+            last_sync: None,
+            synced: HashMap::new(),
+            pending: HashSet::new(),
+            dirty: HashSet::new(),
+        })
+    }
+
+    /// Finds the remote shared object of a `SharedObject`, if it is one.
+    pub fn shared_object_handle(
+        &self,
+        target: SharedObjectTarget<'gc>,
+    ) -> Option<RemoteSharedObjectHandle> {
+        self.shared_objects
+            .iter()
+            .find(|(_, so)| so.target.ptr_eq(target))
+            .map(|(handle, _)| handle)
+    }
+
+    /// Starts synchronizing a remote shared object over a connection.
+    ///
+    /// Returns `false` if the connection isn't connected, can't be used
+    /// for shared objects, or the name of the shared object is too long.
+    pub fn connect_shared_object(
+        &mut self,
+        handle: RemoteSharedObjectHandle,
+        connection: NetConnectionHandle,
+    ) -> bool {
+        let supported = self.connections.get(connection).is_some_and(|c| {
+            c.is_connected()
+                && matches!(
+                    c.protocol,
+                    NetConnectionProtocol::Local | NetConnectionProtocol::Rtmp(_)
+                )
+        });
+        let name_fits = self
+            .shared_objects
+            .get(handle)
+            .is_some_and(|so| so.name.len() <= MAX_STRING_LENGTH);
+        if !supported || !name_fits {
+            return false;
+        }
+
+        self.close_shared_object(handle);
+        let Some(so) = self.shared_objects.get_mut(handle) else {
+            return false;
+        };
+        so.connection = Some(connection);
+        so.synced.clear();
+        so.pending.clear();
+        so.dirty.clear();
+
+        let mut message = SharedObjectMessage::new(so.name.clone(), so.version, so.persistent);
+        message.events.push(SharedObjectEvent::Use);
+        self.send_shared_object_message(connection, message);
+        true
+    }
+
+    /// Stops synchronizing a remote shared object.
+    pub fn close_shared_object(&mut self, handle: RemoteSharedObjectHandle) {
+        let Some(so) = self.shared_objects.get_mut(handle) else {
+            return;
+        };
+        let Some(connection) = so.connection.take() else {
+            return;
+        };
+        so.connected = false;
+
+        let mut message = SharedObjectMessage::new(so.name.clone(), so.version, so.persistent);
+        message.events.push(SharedObjectEvent::Release);
+        self.send_shared_object_message(connection, message);
+    }
+
+    /// Calls a method on every client of a remote shared object, including
+    /// this one.
+    ///
+    /// `values` holds the name of the method followed by its arguments.
+    pub fn send_to_shared_object(
+        &mut self,
+        handle: RemoteSharedObjectHandle,
+        values: Vec<Rc<AmfValue>>,
+    ) {
+        let Some(so) = self.shared_objects.get(handle) else {
+            return;
+        };
+        let Some(connection) = so.connection else {
+            return;
+        };
+
+        let mut message = SharedObjectMessage::new(so.name.clone(), so.version, so.persistent);
+        message.events.push(SharedObjectEvent::SendMessage(values));
+        self.send_shared_object_message(connection, message);
+    }
+
+    /// Sets how many times per second changes to a remote shared object are
+    /// sent. Returns `false` for invalid values.
+    ///
+    /// With 0, pending changes are sent once and then no longer.
+    pub fn set_shared_object_fps(&mut self, handle: RemoteSharedObjectHandle, fps: f64) -> bool {
+        let Some(so) = self.shared_objects.get_mut(handle) else {
+            return false;
+        };
+        if fps.is_nan() || fps < 0.0 {
+            return false;
+        }
+        so.fps = Some(fps);
+        so.force_sync = fps == 0.0;
+        true
+    }
+
+    /// Marks a slot of a remote shared object as changed, for when its value
+    /// was modified in place.
+    pub fn set_shared_object_dirty(&mut self, handle: RemoteSharedObjectHandle, name: String) {
+        if let Some(so) = self.shared_objects.get_mut(handle) {
+            so.dirty.insert(name);
+        }
+    }
+
+    fn send_shared_object_message(
+        &mut self,
+        connection: NetConnectionHandle,
+        message: SharedObjectMessage,
+    ) {
+        let Some(net_connection) = self.connections.get_mut(connection) else {
+            return;
+        };
+        match &mut net_connection.protocol {
+            NetConnectionProtocol::Local => {
+                let replies = self.local_server.handle(connection, message);
+                self.local_messages.extend(replies);
+            }
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.send_shared_object(&message),
+            NetConnectionProtocol::FlashRemoting(_) => {}
+        }
+    }
+
+    /// Sends the changes made to the data of connected remote shared objects.
+    fn sync_shared_objects(context: &mut UpdateContext<'gc>) {
+        let now = context.update_start;
+        let due: Vec<_> = context
+            .net_connections
+            .shared_objects
+            .iter()
+            .filter(|(_, so)| {
+                so.connected
+                    && (so.force_sync
+                        || match so.fps {
+                            None => true,
+                            Some(fps) if fps > 0.0 => so.last_sync.is_none_or(|last| {
+                                now.duration_since(last).as_secs_f64() * fps >= 1.0
+                            }),
+                            Some(_) => false,
+                        })
+            })
+            .map(|(handle, so)| (handle, so.target))
+            .collect();
+
+        for (handle, target) in due {
+            let Some(slots) = Self::serialize_slots(context, target) else {
+                continue;
+            };
+            let net_connections = &mut context.net_connections;
+            let Some(so) = net_connections.shared_objects.get_mut(handle) else {
+                continue;
+            };
+            let Some(connection) = so.connection else {
+                continue;
+            };
+            so.force_sync = false;
+            so.last_sync = Some(now);
+
+            let mut message = SharedObjectMessage::new(so.name.clone(), so.version, so.persistent);
+            let mut present = HashSet::new();
+            for Element { name, value } in slots {
+                if name.len() > MAX_STRING_LENGTH {
+                    tracing::warn!(
+                        "Not synchronizing slot of remote shared object {} with a name of {} bytes",
+                        so.name,
+                        name.len()
+                    );
+                    continue;
+                }
+                let serialized = write_amf0(value.clone());
+                if so.synced.get(&name) != Some(&serialized) || so.dirty.contains(&name) {
+                    so.synced.insert(name.clone(), serialized);
+                    so.pending.insert(name.clone());
+                    message
+                        .events
+                        .push(SharedObjectEvent::RequestChange(name.clone(), value));
+                }
+                present.insert(name);
+            }
+            let removed: Vec<_> = so
+                .synced
+                .keys()
+                .filter(|name| !present.contains(*name))
+                .cloned()
+                .collect();
+            for name in removed {
+                so.synced.remove(&name);
+                so.pending.insert(name.clone());
+                message.events.push(SharedObjectEvent::RequestRemove(name));
+            }
+            so.dirty.clear();
+
+            if !message.events.is_empty() {
+                net_connections.send_shared_object_message(connection, message);
+            }
+        }
+    }
+
+    fn serialize_slots(
+        context: &mut UpdateContext<'gc>,
+        target: SharedObjectTarget<'gc>,
+    ) -> Option<Vec<Element>> {
+        match target {
+            SharedObjectTarget::Avm2(object) => {
+                avm2_shared_object::serialize_slots(context, object)
+            }
+            SharedObjectTarget::Avm1(object) => {
+                avm1_shared_object::serialize_slots(context, object)
+            }
+        }
+    }
+
+    /// Applies the events the server sent for a remote shared object, and
+    /// reports them to it.
+    fn handle_shared_object_message(
+        context: &mut UpdateContext<'gc>,
+        connection: NetConnectionHandle,
+        message: SharedObjectMessage,
+    ) {
+        let Some((handle, so)) = context
+            .net_connections
+            .shared_objects
+            .iter_mut()
+            .find(|(_, so)| so.connection == Some(connection) && so.name == message.name)
+        else {
+            return;
+        };
+        so.version = message.version;
+        let target = so.target;
+
+        let mut updates = vec![];
+        let mut calls = vec![];
+        let mut statuses = vec![];
+        for event in message.events {
+            match event {
+                SharedObjectEvent::UseSuccess => so.connected = true,
+                SharedObjectEvent::Clear => {
+                    so.synced.clear();
+                    so.pending.clear();
+                    updates.push(SyncUpdate::Clear);
+                }
+                SharedObjectEvent::Change(name, value) => {
+                    // Another client changed the slot before our change got
+                    // through, so ours was rejected.
+                    let code = if so.pending.remove(&name) {
+                        "reject"
+                    } else {
+                        "change"
+                    };
+                    updates.push(SyncUpdate::Change(name, value, code));
+                }
+                SharedObjectEvent::Success(name) => {
+                    so.pending.remove(&name);
+                    updates.push(SyncUpdate::Success(name));
+                }
+                SharedObjectEvent::Remove(name) => {
+                    so.synced.remove(&name);
+                    so.pending.remove(&name);
+                    updates.push(SyncUpdate::Delete(name));
+                }
+                SharedObjectEvent::SendMessage(values) => calls.push(values),
+                SharedObjectEvent::Status(code, level) => statuses.push((code, level)),
+                SharedObjectEvent::Use
+                | SharedObjectEvent::Release
+                | SharedObjectEvent::RequestChange(..)
+                | SharedObjectEvent::RequestRemove(_) => {}
+            }
+        }
+
+        if !updates.is_empty() {
+            Self::dispatch_sync(context, handle, target, updates);
+        }
+
+        for values in calls {
+            let mut values = values.into_iter();
+            let Some(method) = values.next() else {
+                continue;
+            };
+            let AmfValue::String(method) = &*method else {
+                continue;
+            };
+            let arguments: Vec<_> = values.collect();
+            match target {
+                SharedObjectTarget::Avm2(object) => {
+                    let mut activation = Avm2Activation::from_nothing(context);
+                    if let Err(e) = avm2_shared_object::call_handler(
+                        &mut activation,
+                        object,
+                        method,
+                        &arguments,
+                    ) {
+                        tracing::error!("Unhandled error calling SharedObject method: {e:?}");
+                    }
+                }
+                SharedObjectTarget::Avm1(object) => {
+                    if let Err(e) =
+                        avm1_shared_object::call_handler(context, object, method, &arguments)
+                    {
+                        tracing::error!("Unhandled error calling SharedObject method: {e}");
+                    }
+                }
+            }
+        }
+
+        for (code, level) in statuses {
+            let info = vec![("code", code.as_str()), ("level", level.as_str())];
+            match target {
+                SharedObjectTarget::Avm2(object) => {
+                    let mut activation = Avm2Activation::from_nothing(context);
+                    let event = Avm2EventObject::net_status_event(&mut activation, info);
+                    Avm2::dispatch_event(activation.context, event, object);
+                }
+                SharedObjectTarget::Avm1(object) => {
+                    if let Err(e) = avm1_shared_object::on_status_event(context, object, info) {
+                        tracing::error!("Unhandled error sending SharedObject status: {e}");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Applies changes from the server to the data of a remote shared object,
+    /// then dispatches its `sync` event.
+    fn dispatch_sync(
+        context: &mut UpdateContext<'gc>,
+        handle: RemoteSharedObjectHandle,
+        target: SharedObjectTarget<'gc>,
+        updates: Vec<SyncUpdate>,
+    ) {
+        let changed: HashSet<_> = updates
+            .iter()
+            .filter_map(|update| match update {
+                SyncUpdate::Change(name, _, _) => Some(name.clone()),
+                _ => None,
+            })
+            .collect();
+
+        let change_list = match target {
+            SharedObjectTarget::Avm2(object) => {
+                avm2_shared_object::apply_sync(context, object, updates).map(SyncList::Avm2)
+            }
+            SharedObjectTarget::Avm1(object) => {
+                avm1_shared_object::apply_sync(context, object, updates).map(SyncList::Avm1)
+            }
+        };
+        let Some(change_list) = change_list else {
+            return;
+        };
+
+        // Remember the new values the way we serialize them, so that they
+        // aren't mistaken for changes of ours and sent back.
+        if let Some(slots) = Self::serialize_slots(context, target) {
+            if let Some(so) = context.net_connections.shared_objects.get_mut(handle) {
+                for Element { name, value } in slots {
+                    if changed.contains(&name) {
+                        so.synced.insert(name, write_amf0(value));
+                    }
+                }
+            }
+        }
+
+        match (target, change_list) {
+            (SharedObjectTarget::Avm2(object), SyncList::Avm2(list)) => {
+                avm2_shared_object::dispatch_sync(context, object, list);
+            }
+            (SharedObjectTarget::Avm1(object), SyncList::Avm1(list)) => {
+                if let Err(e) = avm1_shared_object::on_sync_event(context, object, list) {
+                    tracing::error!("Unhandled error sending SharedObject sync: {e}");
+                }
+            }
+            _ => {}
         }
     }
 
+    /// Whether streams of this connection are played from the server it's
+    /// connected to, instead of being downloaded.
+    pub fn is_streaming(&self, handle: NetConnectionHandle) -> bool {
+        self.connections
+            .get(handle)
+            .is_some_and(|c| matches!(c.protocol, NetConnectionProtocol::Rtmp(_)))
+    }
+
+    /// Plays a stream from the server of an RTMP connection.
+    pub fn play_stream(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        stream: NetStream<'gc>,
+        name: String,
+    ) {
+        let Some(connection) = context.net_connections.connections.get_mut(handle) else {
+            return;
+        };
+        let NetConnectionProtocol::Rtmp(rtmp) = &mut connection.protocol else {
+            return;
+        };
+
+        let index = match connection.streams.iter().position(|s| *s == stream) {
+            Some(index) => index,
+            None => {
+                connection.streams.push(stream);
+                connection.streams.len() - 1
+            }
+        };
+        let buffer_length = (stream.buffer_time() * 1000.0) as u32;
+        rtmp.play(index, name, buffer_length);
+
+        stream.reset_buffer(context);
+        stream.append_buffer(context, &mut rtmp::FLV_HEADER.to_vec());
+    }
+
     pub fn send_without_response(
         context: &mut UpdateContext<'gc>,
         handle: NetConnectionHandle,
@@ -336,6 +1101,12 @@ impl<'gc> NetConnections<'gc> {
     }
 }
 
+/// The change list of a `sync` event, before it's dispatched.
+enum SyncList<'gc> {
+    Avm2(Avm2Object<'gc>),
+    Avm1(Avm1Object<'gc>),
+}
+
 #[derive(Collect)]
 #[collect(no_drop)]
 pub struct NetConnection<'gc> {
@@ -343,59 +1114,73 @@ pub struct NetConnection<'gc> {
 
     #[collect(require_static)]
     protocol: NetConnectionProtocol,
+
+    /// The `NetStream`s played over this connection.
+    streams: Vec<NetStream<'gc>>,
 }
 
 impl NetConnection<'_> {
     pub fn is_connected(&self) -> bool {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => true,
             NetConnectionProtocol::FlashRemoting(_) => false,
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.is_connected(),
         }
     }
 
     pub fn connected_proxy_type(&self) -> Option<&'static str> {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => Some("none"),
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.is_connected().then_some("none"),
         }
     }
 
     pub fn far_id(&self) -> Option<&'static str> {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => Some(""),
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.is_connected().then_some(""),
         }
     }
 
     pub fn far_nonce(&self) -> Option<&'static str> {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => {
                 Some("0000000000000000000000000000000000000000000000000000000000000000")
             }
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp
+                .is_connected()
+                .then_some("0000000000000000000000000000000000000000000000000000000000000000"),
         }
     }
 
     pub fn near_id(&self) -> Option<&'static str> {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => Some(""),
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.is_connected().then_some(""),
         }
     }
 
     pub fn near_nonce(&self) -> Option<&'static str> {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => {
                 Some("0000000000000000000000000000000000000000000000000000000000000000")
             }
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp
+                .is_connected()
+                .then_some("0000000000000000000000000000000000000000000000000000000000000000"),
         }
     }
 
     pub fn protocol(&self) -> Option<&'static str> {
-        match self.protocol {
+        match &self.protocol {
             NetConnectionProtocol::Local => Some("rtmp"),
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) => Some(rtmp.protocol()),
         }
     }
 
@@ -403,6 +1188,7 @@ impl NetConnection<'_> {
         match &self.protocol {
             NetConnectionProtocol::Local => Some("null".to_string()), // Yes, it's a string "null", not a real null.
             NetConnectionProtocol::FlashRemoting(remoting) => Some(remoting.url.to_string()),
+            NetConnectionProtocol::Rtmp(rtmp) => Some(rtmp.url().to_string()),
         }
     }
 
@@ -410,6 +1196,7 @@ impl NetConnection<'_> {
         match &self.protocol {
             NetConnectionProtocol::Local => Some(false),
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.is_connected().then_some(false),
         }
     }
 
@@ -424,6 +1211,7 @@ impl NetConnection<'_> {
             NetConnectionProtocol::FlashRemoting(remoting) => {
                 remoting.send(command, responder_handle, message)
             }
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.call(command, responder_handle, message),
         }
     }
 
@@ -440,6 +1228,7 @@ impl NetConnection<'_> {
                     navigator.spawn_future(remoting.flush_queue(self_handle, player));
                 }
             }
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.update(navigator),
         }
     }
 
@@ -449,6 +1238,8 @@ impl NetConnection<'_> {
             NetConnectionProtocol::FlashRemoting(remoting) => {
                 remoting.set_header(header);
             }
+            // Headers are a Flash Remoting thing.
+            NetConnectionProtocol::Rtmp(_) => {}
         }
     }
 }
@@ -460,6 +1251,9 @@ pub enum NetConnectionProtocol {
 
     /// Flash Remoting protocol, caused by connecting to a `http://` address.
     FlashRemoting(FlashRemoting),
+
+    /// RTMP protocol, caused by connecting to a `rtmp://` or `rtmpt://` address.
+    Rtmp(Rtmp),
 }
 
 #[derive(Debug)]
@@ -467,6 +1261,9 @@ pub struct FlashRemoting {
     url: String,
     headers: Vec<Header>,
     outgoing_queue: Vec<(Message, Option<ResponderHandle>)>,
+
+    /// The movie which connected, if its requests have to pass the security sandbox.
+    sandboxed_movie: Option<Arc<SwfMovie>>,
 }
 
 impl FlashRemoting {
@@ -511,6 +1308,7 @@ impl FlashRemoting {
             messages,
         };
         let url = self.url.clone();
+        let sandboxed_movie = self.sandboxed_movie.clone();
 
         Box::pin(async move {
             let player = player
@@ -519,7 +1317,10 @@ impl FlashRemoting {
             let bytes = flash_lso::packet::write::write_to_bytes(&packet, true)
                 .expect("Must be able to serialize a packet");
             let request = Request::post(url, Some((bytes, "application/x-amf".to_string())));
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = match sandboxed_movie {
+                Some(movie) => sandbox::sandboxed_fetch(player.clone(), movie, request),
+                None => player.lock().unwrap().navigator().fetch(request),
+            };
             let response: Result<_, ErrorResponse> = async {
                 let response = fetch.await?;
                 let url = response.url().to_string();
@@ -544,6 +1345,24 @@ impl FlashRemoting {
                             match connection.object {
                                 NetConnectionObject::Avm2(object) => {
                                     let mut activation = Avm2Activation::from_nothing(uc);
+                                    if let Error::SecuritySandboxViolation(movie_url, url) =
+                                        &response.error
+                                    {
+                                        let message = format!(
+                                            "Error #2048: Security sandbox violation: {movie_url} cannot load data from {url}."
+                                        );
+                                        let event = Avm2EventObject::security_error_event(
+                                            &mut activation,
+                                            &message,
+                                            2048,
+                                        );
+                                        Avm2::dispatch_event(
+                                            activation.context,
+                                            event,
+                                            object.into(),
+                                        );
+                                        return;
+                                    }
                                     let event = Avm2EventObject::net_status_event(
                                         &mut activation,
                                         vec![
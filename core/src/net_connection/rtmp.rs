@@ -0,0 +1,1662 @@
+//! RTMP client, used by `NetConnection`s to `rtmp://` and `rtmpt://` servers.
+//!
+//! Only the unencrypted "simple" handshake is supported, which every server
+//! still accepts from Flash Player. Media received for a `NetStream` is
+//! rewrapped into FLV tags, so that it can be played by the same pipeline as
+//! progressively downloaded FLV files.
+
+use crate::backend::navigator::{NavigatorBackend, Request};
+use crate::net_connection::shared_object::SharedObjectMessage;
+use crate::net_connection::{ResponderCallback, ResponderHandle};
+use crate::sandbox;
+use crate::socket::{ConnectionState, SandboxedConnection, SocketAction, SocketHandle};
+use async_channel::{unbounded, Receiver, Sender};
+use flash_lso::amf0::read::AMF0Decoder;
+use flash_lso::types::{AMFVersion, Element, Lso, ObjectId, Value as AmfValue};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+use url::Url;
+
+/// The protocol version sent and expected in the first handshake byte.
+const RTMP_VERSION: u8 = 3;
+
+/// The size of the C1/C2 and S1/S2 handshake packets.
+const HANDSHAKE_SIZE: usize = 1536;
+
+/// The chunk size both sides start with, until changed by `Set Chunk Size`.
+const DEFAULT_CHUNK_SIZE: usize = 128;
+
+const DEFAULT_RTMP_PORT: u16 = 1935;
+const DEFAULT_RTMPT_PORT: u16 = 80;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// The acknowledgement window we ask the server to use, same as Flash Player.
+const WINDOW_ACK_SIZE: u32 = 2_500_000;
+
+/// A 24 bit timestamp field with this value means a 32 bit timestamp follows.
+const EXTENDED_TIMESTAMP: u32 = 0xFF_FFFF;
+
+const CONTROL_CHUNK_STREAM: u8 = 2;
+const COMMAND_CHUNK_STREAM: u8 = 3;
+const STREAM_CHUNK_STREAM: u8 = 8;
+
+const MSG_SET_CHUNK_SIZE: u8 = 1;
+const MSG_ABORT: u8 = 2;
+const MSG_ACKNOWLEDGEMENT: u8 = 3;
+const MSG_USER_CONTROL: u8 = 4;
+const MSG_WINDOW_ACK_SIZE: u8 = 5;
+const MSG_SET_PEER_BANDWIDTH: u8 = 6;
+const MSG_AUDIO: u8 = 8;
+const MSG_VIDEO: u8 = 9;
+const MSG_DATA_AMF3: u8 = 15;
+const MSG_SHARED_OBJECT_AMF3: u8 = 16;
+const MSG_COMMAND_AMF3: u8 = 17;
+const MSG_DATA_AMF0: u8 = 18;
+const MSG_SHARED_OBJECT_AMF0: u8 = 19;
+const MSG_COMMAND_AMF0: u8 = 20;
+const MSG_AGGREGATE: u8 = 22;
+
+const USER_CONTROL_STREAM_EOF: u16 = 1;
+const USER_CONTROL_SET_BUFFER_LENGTH: u16 = 3;
+const USER_CONTROL_PING_REQUEST: u16 = 6;
+const USER_CONTROL_PING_RESPONSE: u16 = 7;
+
+/// The FLV header that precedes the tags of every stream, announcing both
+/// audio and video, followed by the first (empty) previous tag size.
+pub const FLV_HEADER: [u8; 13] = *b"FLV\x01\x05\x00\x00\x00\x09\x00\x00\x00\x00";
+
+/// A complete RTMP message, reassembled from its chunks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RtmpMessage {
+    pub type_id: u8,
+    pub stream_id: u32,
+    pub timestamp: u32,
+    pub payload: Vec<u8>,
+}
+
+/// Something that happened on an RTMP connection, which needs to be reported
+/// to the AVM.
+#[derive(Debug)]
+pub enum RtmpEvent {
+    /// The connection couldn't be established at all.
+    Failed,
+
+    /// The connection was closed by the server, after it answered `connect`.
+    Closed,
+
+    /// The server sent a status for the connection.
+    Status(Vec<(String, String)>),
+
+    /// The server answered a `NetConnection.call`.
+    Response(ResponderHandle, ResponderCallback, Rc<AmfValue>),
+
+    /// The server called a method on the connection's client.
+    Invoke(String, Vec<Rc<AmfValue>>),
+
+    /// The server sent a status for the stream with the given index.
+    StreamStatus(usize, Vec<(String, String)>),
+
+    /// FLV tags were received for the stream with the given index.
+    StreamData(usize, Vec<u8>),
+
+    /// The server has no more data for the stream with the given index.
+    StreamEnd(usize),
+
+    /// The server sent events for a remote shared object.
+    SharedObject(SharedObjectMessage),
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct ChunkStreamState {
+    timestamp: u32,
+
+    /// The raw value of the last timestamp field, which is reused by type 3
+    /// chunks that start a new message.
+    timestamp_field: u32,
+
+    /// Whether the last header had an extended timestamp, which type 3 chunks
+    /// repeat.
+    extended: bool,
+
+    length: usize,
+    type_id: u8,
+    stream_id: u32,
+}
+
+/// Reassembles RTMP messages from chunks.
+#[derive(Debug)]
+pub struct ChunkReader {
+    chunk_size: usize,
+
+    /// The last header of each chunk stream, along with the payload of the
+    /// message currently being received on it.
+    streams: HashMap<u32, (ChunkStreamState, Vec<u8>)>,
+
+    /// Received data which doesn't form a complete chunk yet.
+    buffer: Vec<u8>,
+}
+
+impl Default for ChunkReader {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            streams: HashMap::new(),
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl ChunkReader {
+    pub fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.chunk_size = chunk_size.max(1);
+    }
+
+    /// Discards the partially received message of a chunk stream.
+    pub fn abort(&mut self, chunk_stream: u32) {
+        if let Some((_, payload)) = self.streams.get_mut(&chunk_stream) {
+            payload.clear();
+        }
+    }
+
+    /// Reads all messages completed by `data`.
+    ///
+    /// Incomplete chunks are kept until more data arrives.
+    pub fn read(&mut self, data: &[u8]) -> Vec<RtmpMessage> {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.extend_from_slice(data);
+
+        let mut messages = vec![];
+        let mut position = 0;
+        while let Some((length, message)) = self.read_chunk(&buffer[position..]) {
+            position += length;
+            messages.extend(message);
+        }
+
+        buffer.drain(..position);
+        self.buffer = buffer;
+        messages
+    }
+
+    /// Reads a single chunk, returning its length and the message it completes.
+    ///
+    /// Returns `None` without changing any state if `data` doesn't contain a
+    /// complete chunk.
+    fn read_chunk(&mut self, data: &[u8]) -> Option<(usize, Option<RtmpMessage>)> {
+        let first = *data.first()?;
+        let format = first >> 6;
+        let (chunk_stream, mut position) = match first & 0x3F {
+            0 => (64 + u32::from(*data.get(1)?), 2),
+            1 => (
+                64 + u32::from(*data.get(1)?) + (u32::from(*data.get(2)?) << 8),
+                3,
+            ),
+            id => (u32::from(id), 1),
+        };
+
+        let (previous, partial) = self
+            .streams
+            .get(&chunk_stream)
+            .map(|(state, payload)| (*state, payload.len()))
+            .unwrap_or_default();
+        let header_length = [11, 7, 3, 0][usize::from(format)];
+        let header = data.get(position..position + header_length)?;
+        position += header_length;
+
+        let mut state = previous;
+        if format < 3 {
+            state.timestamp_field = read_u24(&header[0..3]);
+            state.extended = state.timestamp_field == EXTENDED_TIMESTAMP;
+        }
+        if format < 2 {
+            state.length = read_u24(&header[3..6]) as usize;
+            state.type_id = header[6];
+        }
+        if format == 0 {
+            state.stream_id = u32::from_le_bytes(header[7..11].try_into().ok()?);
+        }
+        if state.extended {
+            state.timestamp_field = read_u32(data.get(position..position + 4)?);
+            position += 4;
+        }
+
+        // A type 3 chunk continues the current message, if there is one.
+        let continuation = format == 3 && partial > 0;
+        if !continuation {
+            state.timestamp = match format {
+                0 => state.timestamp_field,
+                _ => previous.timestamp.wrapping_add(state.timestamp_field),
+            };
+        }
+
+        let received = if continuation { partial } else { 0 };
+        let chunk_length = state.length.saturating_sub(received).min(self.chunk_size);
+        let chunk = data.get(position..position + chunk_length)?;
+        position += chunk_length;
+
+        let (stored, payload) = self.streams.entry(chunk_stream).or_default();
+        *stored = state;
+        if !continuation {
+            payload.clear();
+        }
+        payload.extend_from_slice(chunk);
+
+        let message = (payload.len() >= state.length).then(|| RtmpMessage {
+            type_id: state.type_id,
+            stream_id: state.stream_id,
+            timestamp: state.timestamp,
+            payload: std::mem::take(payload),
+        });
+        Some((position, message))
+    }
+}
+
+/// Splits RTMP messages into chunks.
+///
+/// Every message starts with a full (type 0) header, so no header state needs
+/// to be tracked.
+#[derive(Debug)]
+pub struct ChunkWriter {
+    chunk_size: usize,
+}
+
+impl Default for ChunkWriter {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+}
+
+impl ChunkWriter {
+    pub fn write(&self, output: &mut Vec<u8>, chunk_stream: u8, message: &RtmpMessage) {
+        debug_assert!((2..64).contains(&chunk_stream));
+
+        let extended = message.timestamp >= EXTENDED_TIMESTAMP;
+        let timestamp_field = message.timestamp.min(EXTENDED_TIMESTAMP);
+        output.push(chunk_stream);
+        output.extend_from_slice(&timestamp_field.to_be_bytes()[1..]);
+        output.extend_from_slice(&(message.payload.len() as u32).to_be_bytes()[1..]);
+        output.push(message.type_id);
+        output.extend_from_slice(&message.stream_id.to_le_bytes());
+        if extended {
+            output.extend_from_slice(&message.timestamp.to_be_bytes());
+        }
+
+        for (i, chunk) in message.payload.chunks(self.chunk_size).enumerate() {
+            if i > 0 {
+                output.push(0xC0 | chunk_stream);
+                if extended {
+                    output.extend_from_slice(&message.timestamp.to_be_bytes());
+                }
+            }
+            output.extend_from_slice(chunk);
+        }
+    }
+}
+
+fn read_u24(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]])
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+/// Builds an FLV tag (including its trailing previous tag size).
+pub fn flv_tag(tag_type: u8, timestamp: u32, data: &[u8]) -> Vec<u8> {
+    let mut tag = Vec::with_capacity(data.len() + 15);
+    tag.push(tag_type);
+    tag.extend_from_slice(&(data.len() as u32).to_be_bytes()[1..]);
+    tag.extend_from_slice(&timestamp.to_be_bytes()[1..]);
+    tag.push((timestamp >> 24) as u8);
+    tag.extend_from_slice(&[0, 0, 0]);
+    tag.extend_from_slice(data);
+    tag.extend_from_slice(&(data.len() as u32 + 11).to_be_bytes());
+    tag
+}
+
+/// The C0 and C1 handshake packets.
+fn client_hello() -> Vec<u8> {
+    let mut hello = Vec::with_capacity(1 + HANDSHAKE_SIZE);
+    hello.push(RTMP_VERSION);
+    // Time and zero fields, followed by "random" data that's never checked.
+    hello.extend_from_slice(&[0; 8]);
+    hello.extend((0..HANDSHAKE_SIZE - 8).map(|i| (i * 7) as u8));
+    hello
+}
+
+/// Encodes a single AMF0 value.
+pub fn write_amf0(value: Rc<AmfValue>) -> Vec<u8> {
+    let mut lso = Lso::new(vec![Element::new("", value)], "", AMFVersion::AMF0);
+    match flash_lso::write::write_to_bytes(&mut lso) {
+        // Like `ByteArray.writeObject`, strip the LSO header and padding to
+        // get at the value.
+        Ok(bytes) => {
+            bytes[flash_lso::write::header_length(&lso.header) + 8..bytes.len() - 1].to_vec()
+        }
+        Err(_) => {
+            tracing::error!("Couldn't serialize AMF value");
+            vec![0x06] // undefined
+        }
+    }
+}
+
+/// Decodes a sequence of AMF0 values, as found in command and data messages.
+pub fn read_amf0(mut data: &[u8]) -> Vec<AmfValue> {
+    let mut decoder = AMF0Decoder::default();
+    let mut values = vec![];
+    while !data.is_empty() {
+        match decoder.parse_single_element(data) {
+            Ok((rest, value)) => {
+                values.push(value);
+                data = rest;
+            }
+            Err(_) => {
+                tracing::warn!("Couldn't parse AMF value in RTMP message");
+                break;
+            }
+        }
+    }
+    values
+}
+
+/// The string properties of an info object, such as the `code`, `level` and
+/// `description` of a status.
+fn string_properties(value: &AmfValue) -> Vec<(String, String)> {
+    let elements = match value {
+        AmfValue::AMF3(value) => return string_properties(value),
+        AmfValue::Object(_, elements, _) | AmfValue::ECMAArray(_, _, elements, _) => elements,
+        _ => return vec![],
+    };
+
+    elements
+        .iter()
+        .filter_map(|element| match &*element.value {
+            AmfValue::String(value) => Some((element.name.clone(), value.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+fn status_code(info: &[(String, String)]) -> Option<&str> {
+    info.iter()
+        .find(|(key, _)| key == "code")
+        .map(|(_, value)| value.as_str())
+}
+
+enum TransportEvent {
+    Connected,
+    Data(Vec<u8>),
+    Closed,
+}
+
+#[derive(Debug)]
+enum Transport {
+    /// A plain TCP connection, for `rtmp://` URLs.
+    Socket {
+        /// Data sent to this channel is sent by the backend.
+        sender: Sender<Vec<u8>>,
+        receiver: Receiver<SocketAction>,
+    },
+
+    /// RTMP tunneled over HTTP requests, for `rtmpt://` URLs.
+    Tunnel(Tunnel),
+}
+
+impl Transport {
+    fn connect_socket(
+        navigator: &mut dyn NavigatorBackend,
+        host: String,
+        port: u16,
+        sandboxed: Option<SandboxedConnection>,
+    ) -> Self {
+        let (sender, socket_receiver) = unbounded();
+        let (socket_sender, receiver) = unbounded();
+        let Some((player, movie)) =
+            sandboxed.and_then(|sandboxed| Some((sandboxed.player.upgrade()?, sandboxed.movie)))
+        else {
+            // The socket belongs to us rather than to `Sockets`, so its handle
+            // doesn't matter.
+            navigator.connect_socket(
+                host,
+                port,
+                CONNECT_TIMEOUT,
+                SocketHandle::default(),
+                socket_receiver,
+                socket_sender,
+            );
+            return Transport::Socket { sender, receiver };
+        };
+
+        navigator.spawn_future(Box::pin(async move {
+            if sandbox::request_socket_access(&player, &movie, &host, port).await {
+                player.lock().unwrap().navigator_mut().connect_socket(
+                    host,
+                    port,
+                    CONNECT_TIMEOUT,
+                    SocketHandle::default(),
+                    socket_receiver,
+                    socket_sender,
+                );
+            } else {
+                tracing::warn!(
+                    "Security sandbox violation: {} cannot connect to {host}:{port}",
+                    movie.url()
+                );
+                let _ = socket_sender.try_send(SocketAction::Connect(
+                    SocketHandle::default(),
+                    ConnectionState::Failed,
+                ));
+            }
+            Ok(())
+        }));
+        Transport::Socket { sender, receiver }
+    }
+
+    fn poll(&mut self) -> Vec<TransportEvent> {
+        match self {
+            Transport::Socket { receiver, .. } => {
+                let mut events = vec![];
+                while let Ok(action) = receiver.try_recv() {
+                    events.push(match action {
+                        SocketAction::Connect(_, ConnectionState::Connected) => {
+                            TransportEvent::Connected
+                        }
+                        SocketAction::Connect(_, _) | SocketAction::Close(_) => {
+                            TransportEvent::Closed
+                        }
+                        SocketAction::Data(_, data) => TransportEvent::Data(data),
+                    });
+                }
+                events
+            }
+            Transport::Tunnel(tunnel) => tunnel.poll(),
+        }
+    }
+
+    fn send(&mut self, data: Vec<u8>) {
+        match self {
+            Transport::Socket { sender, .. } => {
+                // We use an unbounded channel, so this should only ever error if the channel is closed
+                // (the receiver was dropped)
+                if let Err(e) = sender.try_send(data) {
+                    tracing::error!("Failed to send RTMP data: {:?}", e);
+                }
+            }
+            Transport::Tunnel(tunnel) => tunnel.outgoing.extend(data),
+        }
+    }
+
+    fn flush(&mut self, navigator: &mut dyn NavigatorBackend) {
+        if let Transport::Tunnel(tunnel) = self {
+            tunnel.flush(navigator);
+        }
+    }
+
+    fn close(&mut self, navigator: &mut dyn NavigatorBackend) {
+        if let Transport::Tunnel(tunnel) = self {
+            tunnel.close(navigator);
+        }
+    }
+}
+
+#[derive(Debug)]
+enum TunnelResponse {
+    Opened(String),
+
+    /// Data received from the server, along with how long it wants us to wait
+    /// before polling it again.
+    Data(u8, Vec<u8>),
+
+    Failed,
+}
+
+/// An RTMPT session, which sends and polls for RTMP data with HTTP `POST`s.
+///
+/// Only one request is in flight at any time, as required by the protocol.
+#[derive(Debug)]
+struct Tunnel {
+    base_url: String,
+    session: Option<String>,
+    sequence: u32,
+    in_flight: bool,
+
+    /// The number of updates to wait before polling an idle server.
+    idle_delay: u8,
+
+    /// Data waiting to be sent with the next request.
+    outgoing: Vec<u8>,
+
+    sender: Sender<TunnelResponse>,
+    receiver: Receiver<TunnelResponse>,
+}
+
+impl Tunnel {
+    fn open(
+        navigator: &mut dyn NavigatorBackend,
+        base_url: String,
+        sandboxed: Option<SandboxedConnection>,
+    ) -> Self {
+        let (sender, receiver) = unbounded();
+        let mut tunnel = Self {
+            base_url,
+            session: None,
+            sequence: 0,
+            in_flight: false,
+            idle_delay: 0,
+            outgoing: vec![],
+            sender,
+            receiver,
+        };
+        tunnel.post(navigator, "/open/1".to_string(), true, sandboxed);
+        tunnel
+    }
+
+    /// Sends a request to the server.
+    ///
+    /// Only the request opening the session needs to pass the sandbox, as
+    /// the server can't change afterwards.
+    fn post(
+        &mut self,
+        navigator: &mut dyn NavigatorBackend,
+        path: String,
+        opening: bool,
+        sandboxed: Option<SandboxedConnection>,
+    ) {
+        // The server expects a body even when there's nothing to send.
+        let body = match std::mem::take(&mut self.outgoing) {
+            body if body.is_empty() => vec![0],
+            body => body,
+        };
+        let request = Request::post(
+            format!("{}{}", self.base_url, path),
+            Some((body, "application/x-fcs".to_string())),
+        );
+        let fetch = match sandboxed
+            .and_then(|sandboxed| Some((sandboxed.player.upgrade()?, sandboxed.movie)))
+        {
+            Some((player, movie)) => sandbox::sandboxed_fetch(player, movie, request),
+            None => navigator.fetch(request),
+        };
+        let sender = self.sender.clone();
+        self.in_flight = true;
+
+        navigator.spawn_future(Box::pin(async move {
+            let body = match fetch.await {
+                Ok(response) => response.body().await.ok(),
+                Err(response) => {
+                    tracing::warn!("RTMPT request to {} failed", response.url);
+                    None
+                }
+            };
+            let response = match body {
+                Some(body) if opening => {
+                    TunnelResponse::Opened(String::from_utf8_lossy(&body).trim().to_string())
+                }
+                Some(body) => match body.split_first() {
+                    Some((delay, data)) => TunnelResponse::Data(*delay, data.to_vec()),
+                    None => TunnelResponse::Data(1, vec![]),
+                },
+                None => TunnelResponse::Failed,
+            };
+            // The connection may have been closed in the meantime.
+            let _ = sender.try_send(response);
+            Ok(())
+        }));
+    }
+
+    fn poll(&mut self) -> Vec<TransportEvent> {
+        let mut events = vec![];
+        while let Ok(response) = self.receiver.try_recv() {
+            self.in_flight = false;
+            match response {
+                TunnelResponse::Opened(session) if !session.is_empty() => {
+                    self.session = Some(session);
+                    events.push(TransportEvent::Connected);
+                }
+                TunnelResponse::Opened(_) | TunnelResponse::Failed => {
+                    self.session = None;
+                    events.push(TransportEvent::Closed);
+                }
+                TunnelResponse::Data(delay, data) => {
+                    if data.is_empty() {
+                        self.idle_delay = delay;
+                    } else {
+                        // There may be more where that came from.
+                        self.idle_delay = 0;
+                        events.push(TransportEvent::Data(data));
+                    }
+                }
+            }
+        }
+        events
+    }
+
+    fn flush(&mut self, navigator: &mut dyn NavigatorBackend) {
+        let Some(session) = self.session.clone() else {
+            return;
+        };
+        if self.in_flight {
+            return;
+        }
+
+        let command = if !self.outgoing.is_empty() {
+            "send"
+        } else if self.idle_delay == 0 {
+            "idle"
+        } else {
+            self.idle_delay -= 1;
+            return;
+        };
+        self.sequence += 1;
+        let path = format!("/{command}/{session}/{}", self.sequence);
+        self.post(navigator, path, false, None);
+    }
+
+    fn close(&mut self, navigator: &mut dyn NavigatorBackend) {
+        if let Some(session) = self.session.take() {
+            self.outgoing.clear();
+            self.post(navigator, format!("/close/{session}"), false, None);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RtmpState {
+    /// Waiting for the transport to connect.
+    Opening,
+
+    /// Waiting for the server's half of the handshake.
+    Handshake,
+
+    /// Waiting for the server to answer `connect`.
+    Connecting,
+
+    Connected,
+
+    /// The server refused `connect`, and will close the connection.
+    Rejected,
+
+    Closed,
+}
+
+/// A command which is waiting for a `_result` or `_error`.
+#[derive(Debug)]
+enum Transaction {
+    Connect,
+    Call(Option<ResponderHandle>),
+    CreateStream(usize),
+}
+
+/// The state of a `NetStream` played over this connection.
+#[derive(Debug, Default)]
+struct RtmpStream {
+    /// The message stream ID, once `createStream` was answered.
+    id: Option<u32>,
+
+    /// Whether `createStream` was sent.
+    creating: bool,
+
+    /// The stream name to play once the stream is created.
+    pending_play: Option<String>,
+
+    /// The buffer length to report to the server, in milliseconds.
+    buffer_length: u32,
+
+    /// The timestamp of the first media message, which becomes time 0.
+    ///
+    /// Live streams don't start at 0, but FLV playback expects them to.
+    base_timestamp: Option<u32>,
+}
+
+#[derive(Debug)]
+pub struct Rtmp {
+    url: String,
+    protocol: &'static str,
+    transport: Transport,
+    state: RtmpState,
+
+    /// The server's handshake, while it's being received.
+    handshake: Vec<u8>,
+
+    reader: ChunkReader,
+    writer: ChunkWriter,
+
+    bytes_received: u32,
+    last_acknowledged: u32,
+    window_ack_size: Option<u32>,
+
+    /// The payload of the `connect` command, sent once the handshake is done.
+    connect_command: Vec<u8>,
+
+    next_transaction: u32,
+    transactions: HashMap<u32, Transaction>,
+
+    /// Commands made before the connection was established.
+    queued: Vec<(u8, RtmpMessage)>,
+
+    /// The streams played over this connection, by `NetConnection` stream
+    /// index.
+    streams: Vec<RtmpStream>,
+
+    events: Vec<RtmpEvent>,
+}
+
+impl Rtmp {
+    /// Starts connecting to an `rtmp://` or `rtmpt://` URL.
+    ///
+    /// Returns `None` if the URL isn't one of those.
+    ///
+    /// If `sandboxed` is given, the server is only contacted once the
+    /// security sandbox allows it.
+    pub fn connect(
+        navigator: &mut dyn NavigatorBackend,
+        url: String,
+        flash_version: String,
+        swf_url: String,
+        object_encoding: AMFVersion,
+        arguments: Vec<Rc<AmfValue>>,
+        sandboxed: Option<SandboxedConnection>,
+    ) -> Option<Self> {
+        let parsed = Url::parse(&url).ok()?;
+        let host = parsed.host_str()?.to_string();
+        let (protocol, default_port) = match parsed.scheme() {
+            "rtmp" => ("rtmp", DEFAULT_RTMP_PORT),
+            "rtmpt" => ("rtmpt", DEFAULT_RTMPT_PORT),
+            _ => return None,
+        };
+        let port = parsed.port().unwrap_or(default_port);
+
+        let transport = match protocol {
+            "rtmpt" => Transport::Tunnel(Tunnel::open(
+                navigator,
+                format!("http://{host}:{port}"),
+                sandboxed,
+            )),
+            _ => Transport::connect_socket(navigator, host, port, sandboxed),
+        };
+
+        let object_encoding = match object_encoding {
+            AMFVersion::AMF0 => 0.0,
+            AMFVersion::AMF3 => 3.0,
+        };
+        let property = |name: &str, value: AmfValue| Element::new(name, Rc::new(value));
+        let command_object = AmfValue::Object(
+            ObjectId::INVALID,
+            vec![
+                property(
+                    "app",
+                    AmfValue::String(parsed.path().trim_start_matches('/').to_string()),
+                ),
+                property("flashVer", AmfValue::String(flash_version)),
+                property("swfUrl", AmfValue::String(swf_url)),
+                property("tcUrl", AmfValue::String(url.clone())),
+                property("fpad", AmfValue::Bool(false)),
+                property("capabilities", AmfValue::Number(239.0)),
+                property("audioCodecs", AmfValue::Number(3575.0)),
+                property("videoCodecs", AmfValue::Number(252.0)),
+                property("videoFunction", AmfValue::Number(1.0)),
+                property("objectEncoding", AmfValue::Number(object_encoding)),
+            ],
+            None,
+        );
+        let connect_command = command_payload("connect", 1, command_object, arguments);
+
+        Some(Self::new(url, protocol, transport, connect_command))
+    }
+
+    fn new(
+        url: String,
+        protocol: &'static str,
+        transport: Transport,
+        connect_command: Vec<u8>,
+    ) -> Self {
+        Self {
+            url,
+            protocol,
+            transport,
+            state: RtmpState::Opening,
+            handshake: vec![],
+            reader: ChunkReader::default(),
+            writer: ChunkWriter::default(),
+            bytes_received: 0,
+            last_acknowledged: 0,
+            window_ack_size: None,
+            connect_command,
+            next_transaction: 2,
+            transactions: HashMap::from([(1, Transaction::Connect)]),
+            queued: vec![],
+            streams: vec![],
+            events: vec![],
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn protocol(&self) -> &'static str {
+        self.protocol
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.state == RtmpState::Connected
+    }
+
+    /// Processes everything the server sent since the last update.
+    pub fn update(&mut self, navigator: &mut dyn NavigatorBackend) {
+        for event in self.transport.poll() {
+            match event {
+                TransportEvent::Connected => {
+                    if self.state == RtmpState::Opening {
+                        self.state = RtmpState::Handshake;
+                        self.transport.send(client_hello());
+                    }
+                }
+                TransportEvent::Data(data) => self.receive(&data),
+                TransportEvent::Closed => self.closed(),
+            }
+        }
+
+        if self.state != RtmpState::Closed {
+            self.transport.flush(navigator);
+        }
+    }
+
+    /// Takes the events which happened since the last call.
+    pub fn take_events(&mut self) -> Vec<RtmpEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn close(&mut self, navigator: &mut dyn NavigatorBackend) {
+        if self.state != RtmpState::Closed {
+            self.state = RtmpState::Closed;
+            self.transport.close(navigator);
+        }
+    }
+
+    /// Calls a method on the server.
+    ///
+    /// `message` holds the arguments, as an array.
+    pub fn call(
+        &mut self,
+        command: String,
+        responder_handle: Option<ResponderHandle>,
+        message: AmfValue,
+    ) {
+        let arguments = match message {
+            AmfValue::StrictArray(_, arguments) => arguments,
+            message => vec![Rc::new(message)],
+        };
+        let transaction = self.add_transaction(Transaction::Call(responder_handle));
+        let payload = command_payload(&command, transaction, AmfValue::Null, arguments);
+        self.send_command(0, payload);
+    }
+
+    /// Plays a stream on the server, creating the message stream for the
+    /// `NetStream` with the given index first if needed.
+    pub fn play(&mut self, index: usize, name: String, buffer_length: u32) {
+        if self.streams.len() <= index {
+            self.streams.resize_with(index + 1, Default::default);
+        }
+        let stream = &mut self.streams[index];
+        stream.buffer_length = buffer_length;
+        stream.base_timestamp = None;
+
+        if let Some(id) = stream.id {
+            self.send_play(id, name, buffer_length);
+        } else {
+            stream.pending_play = Some(name);
+            if !stream.creating {
+                stream.creating = true;
+                let transaction = self.add_transaction(Transaction::CreateStream(index));
+                let payload = command_payload("createStream", transaction, AmfValue::Null, vec![]);
+                self.send_command(0, payload);
+            }
+        }
+    }
+
+    /// Sends events for a remote shared object to the server.
+    pub fn send_shared_object(&mut self, message: &SharedObjectMessage) {
+        self.send_when_connected(
+            COMMAND_CHUNK_STREAM,
+            RtmpMessage {
+                type_id: MSG_SHARED_OBJECT_AMF0,
+                stream_id: 0,
+                timestamp: 0,
+                payload: message.write(),
+            },
+        );
+    }
+
+    fn send_play(&mut self, stream_id: u32, name: String, buffer_length: u32) {
+        let payload = command_payload(
+            "play",
+            0,
+            AmfValue::Null,
+            vec![Rc::new(AmfValue::String(name))],
+        );
+        self.send_command(stream_id, payload);
+
+        let mut control = USER_CONTROL_SET_BUFFER_LENGTH.to_be_bytes().to_vec();
+        control.extend_from_slice(&stream_id.to_be_bytes());
+        control.extend_from_slice(&buffer_length.to_be_bytes());
+        self.send_control(MSG_USER_CONTROL, control);
+    }
+
+    fn add_transaction(&mut self, transaction: Transaction) -> u32 {
+        let id = self.next_transaction;
+        self.next_transaction += 1;
+        self.transactions.insert(id, transaction);
+        id
+    }
+
+    fn send_command(&mut self, stream_id: u32, payload: Vec<u8>) {
+        let chunk_stream = if stream_id == 0 {
+            COMMAND_CHUNK_STREAM
+        } else {
+            STREAM_CHUNK_STREAM
+        };
+        let message = RtmpMessage {
+            type_id: MSG_COMMAND_AMF0,
+            stream_id,
+            timestamp: 0,
+            payload,
+        };
+        self.send_when_connected(chunk_stream, message);
+    }
+
+    /// Sends a message once `connect` succeeded.
+    fn send_when_connected(&mut self, chunk_stream: u8, message: RtmpMessage) {
+        match self.state {
+            RtmpState::Connected => self.send_message(chunk_stream, &message),
+            RtmpState::Opening | RtmpState::Handshake | RtmpState::Connecting => {
+                self.queued.push((chunk_stream, message))
+            }
+            RtmpState::Rejected | RtmpState::Closed => {}
+        }
+    }
+
+    fn send_control(&mut self, type_id: u8, payload: Vec<u8>) {
+        let message = RtmpMessage {
+            type_id,
+            stream_id: 0,
+            timestamp: 0,
+            payload,
+        };
+        self.send_message(CONTROL_CHUNK_STREAM, &message);
+    }
+
+    fn send_message(&mut self, chunk_stream: u8, message: &RtmpMessage) {
+        let mut data = vec![];
+        self.writer.write(&mut data, chunk_stream, message);
+        self.transport.send(data);
+    }
+
+    fn closed(&mut self) {
+        let event = match self.state {
+            RtmpState::Connected | RtmpState::Rejected => RtmpEvent::Closed,
+            RtmpState::Closed => return,
+            _ => RtmpEvent::Failed,
+        };
+        self.state = RtmpState::Closed;
+        self.events.push(event);
+    }
+
+    fn receive(&mut self, data: &[u8]) {
+        self.bytes_received = self.bytes_received.wrapping_add(data.len() as u32);
+
+        let data = match self.state {
+            RtmpState::Handshake => {
+                self.handshake.extend_from_slice(data);
+                if self.handshake.len() < 1 + 2 * HANDSHAKE_SIZE {
+                    return;
+                }
+                if self.handshake[0] != RTMP_VERSION {
+                    tracing::warn!("Unsupported RTMP version {}", self.handshake[0]);
+                    self.closed();
+                    return;
+                }
+
+                // C2 echoes S1, after which we can immediately connect.
+                let rest = self.handshake.split_off(1 + 2 * HANDSHAKE_SIZE);
+                let echo = self.handshake[1..1 + HANDSHAKE_SIZE].to_vec();
+                self.handshake = vec![];
+                self.transport.send(echo);
+
+                self.state = RtmpState::Connecting;
+                let connect = RtmpMessage {
+                    type_id: MSG_COMMAND_AMF0,
+                    stream_id: 0,
+                    timestamp: 0,
+                    payload: std::mem::take(&mut self.connect_command),
+                };
+                self.send_message(COMMAND_CHUNK_STREAM, &connect);
+                rest
+            }
+            RtmpState::Connecting | RtmpState::Connected | RtmpState::Rejected => data.to_vec(),
+            RtmpState::Opening | RtmpState::Closed => return,
+        };
+
+        for message in self.reader.read(&data) {
+            self.handle_message(message);
+        }
+
+        if let Some(window) = self.window_ack_size {
+            if self.bytes_received.wrapping_sub(self.last_acknowledged) >= window {
+                self.last_acknowledged = self.bytes_received;
+                self.send_control(
+                    MSG_ACKNOWLEDGEMENT,
+                    self.bytes_received.to_be_bytes().to_vec(),
+                );
+            }
+        }
+    }
+
+    fn handle_message(&mut self, message: RtmpMessage) {
+        let payload = &message.payload;
+        match message.type_id {
+            MSG_SET_CHUNK_SIZE if payload.len() >= 4 => {
+                self.reader
+                    .set_chunk_size((read_u32(payload) & 0x7FFF_FFFF) as usize);
+            }
+            MSG_ABORT if payload.len() >= 4 => self.reader.abort(read_u32(payload)),
+            MSG_ACKNOWLEDGEMENT => {}
+            MSG_USER_CONTROL if payload.len() >= 6 => {
+                let event = u16::from_be_bytes([payload[0], payload[1]]);
+                match event {
+                    USER_CONTROL_STREAM_EOF => {
+                        if let Some(index) = self.stream_index(read_u32(&payload[2..])) {
+                            self.events.push(RtmpEvent::StreamEnd(index));
+                        }
+                    }
+                    USER_CONTROL_PING_REQUEST => {
+                        let mut response = USER_CONTROL_PING_RESPONSE.to_be_bytes().to_vec();
+                        response.extend_from_slice(&payload[2..]);
+                        self.send_control(MSG_USER_CONTROL, response);
+                    }
+                    _ => {}
+                }
+            }
+            MSG_WINDOW_ACK_SIZE if payload.len() >= 4 => {
+                self.window_ack_size = Some(read_u32(payload));
+            }
+            MSG_SET_PEER_BANDWIDTH => {
+                self.send_control(MSG_WINDOW_ACK_SIZE, WINDOW_ACK_SIZE.to_be_bytes().to_vec());
+            }
+            MSG_AUDIO | MSG_VIDEO | MSG_DATA_AMF0 => {
+                self.stream_tag(&message, message.type_id, &message.payload);
+            }
+            MSG_DATA_AMF3 => {
+                // AMF3 data messages are AMF0 after a leading format byte.
+                if let Some(data) = payload.get(1..) {
+                    self.stream_tag(&message, MSG_DATA_AMF0, data);
+                }
+            }
+            MSG_COMMAND_AMF0 => self.handle_command(message.stream_id, read_amf0(payload)),
+            MSG_COMMAND_AMF3 => {
+                if let Some(data) = payload.get(1..) {
+                    self.handle_command(message.stream_id, read_amf0(data));
+                }
+            }
+            MSG_SHARED_OBJECT_AMF0 => self.handle_shared_object(payload),
+            MSG_SHARED_OBJECT_AMF3 => {
+                if let Some(data) = payload.get(1..) {
+                    self.handle_shared_object(data);
+                }
+            }
+            MSG_AGGREGATE => self.handle_aggregate(&message),
+            type_id => tracing::debug!("Ignoring RTMP message of type {}", type_id),
+        }
+    }
+
+    fn handle_shared_object(&mut self, data: &[u8]) {
+        match SharedObjectMessage::read(data) {
+            Some(message) => self.events.push(RtmpEvent::SharedObject(message)),
+            None => tracing::warn!("Couldn't parse RTMP shared object message"),
+        }
+    }
+
+    /// Splits an aggregate message, which is a sequence of FLV tags.
+    ///
+    /// Aggregates nested in other aggregates are ignored, so a message can't make us recurse.
+    fn handle_aggregate(&mut self, message: &RtmpMessage) {
+        let mut data = &message.payload[..];
+        let mut first_timestamp = None;
+        while data.len() >= 11 {
+            let length = read_u24(&data[1..4]) as usize;
+            let timestamp = read_u24(&data[4..7]) | (u32::from(data[7]) << 24);
+            let Some(payload) = data.get(11..11 + length) else {
+                break;
+            };
+
+            if data[0] == MSG_AGGREGATE {
+                tracing::warn!("Ignoring RTMP aggregate message nested in another");
+                data = data.get(11 + length + 4..).unwrap_or_default();
+                continue;
+            }
+
+            // Timestamps are relative to the aggregate message's.
+            let first_timestamp = *first_timestamp.get_or_insert(timestamp);
+            self.handle_message(RtmpMessage {
+                type_id: data[0],
+                stream_id: message.stream_id,
+                timestamp: message
+                    .timestamp
+                    .wrapping_add(timestamp.wrapping_sub(first_timestamp)),
+                payload: payload.to_vec(),
+            });
+
+            data = data.get(11 + length + 4..).unwrap_or_default();
+        }
+    }
+
+    fn stream_index(&self, stream_id: u32) -> Option<usize> {
+        self.streams
+            .iter()
+            .position(|stream| stream.id == Some(stream_id))
+    }
+
+    fn stream_tag(&mut self, message: &RtmpMessage, tag_type: u8, data: &[u8]) {
+        let Some(index) = self.stream_index(message.stream_id) else {
+            return;
+        };
+
+        let stream = &mut self.streams[index];
+        let timestamp = if tag_type == MSG_DATA_AMF0 {
+            stream
+                .base_timestamp
+                .map_or(0, |base| message.timestamp.saturating_sub(base))
+        } else {
+            let base = *stream.base_timestamp.get_or_insert(message.timestamp);
+            message.timestamp.saturating_sub(base)
+        };
+
+        self.events.push(RtmpEvent::StreamData(
+            index,
+            flv_tag(tag_type, timestamp, data),
+        ));
+    }
+
+    fn handle_command(&mut self, stream_id: u32, values: Vec<AmfValue>) {
+        let mut values = values.into_iter();
+        let Some(AmfValue::String(name)) = values.next() else {
+            return;
+        };
+        let transaction = match values.next() {
+            Some(AmfValue::Number(transaction)) => transaction as u32,
+            _ => 0,
+        };
+        // The command object, which is only meaningful for `connect`.
+        let _ = values.next();
+        let arguments: Vec<_> = values.collect();
+
+        match name.as_str() {
+            "_result" | "_error" => {
+                if let Some(pending) = self.transactions.remove(&transaction) {
+                    self.handle_response(pending, name == "_error", arguments);
+                }
+            }
+            "onStatus" => {
+                let info = arguments.first().map(string_properties).unwrap_or_default();
+                if stream_id == 0 {
+                    self.events.push(RtmpEvent::Status(info));
+                } else if let Some(index) = self.stream_index(stream_id) {
+                    if status_code(&info) == Some("NetStream.Play.Stop") {
+                        // Our playback reports the stop once the buffer has
+                        // actually been played.
+                        self.events.push(RtmpEvent::StreamEnd(index));
+                    } else {
+                        self.events.push(RtmpEvent::StreamStatus(index, info));
+                    }
+                }
+            }
+            _ if stream_id == 0 => {
+                let arguments = arguments.into_iter().map(Rc::new).collect();
+                self.events.push(RtmpEvent::Invoke(name, arguments));
+            }
+            _ => tracing::debug!("Ignoring RTMP stream command {}", name),
+        }
+    }
+
+    fn handle_response(
+        &mut self,
+        transaction: Transaction,
+        is_error: bool,
+        arguments: Vec<AmfValue>,
+    ) {
+        match transaction {
+            Transaction::Connect => {
+                let info = arguments.first().map(string_properties).unwrap_or_default();
+                if is_error {
+                    self.state = RtmpState::Rejected;
+                } else {
+                    self.state = RtmpState::Connected;
+                    for (chunk_stream, message) in std::mem::take(&mut self.queued) {
+                        self.send_message(chunk_stream, &message);
+                    }
+                }
+                self.events.push(RtmpEvent::Status(info));
+            }
+            Transaction::Call(Some(responder_handle)) => {
+                let callback = if is_error {
+                    ResponderCallback::Status
+                } else {
+                    ResponderCallback::Result
+                };
+                let value = arguments.into_iter().next().unwrap_or(AmfValue::Null);
+                self.events.push(RtmpEvent::Response(
+                    responder_handle,
+                    callback,
+                    Rc::new(value),
+                ));
+            }
+            Transaction::Call(None) => {}
+            Transaction::CreateStream(index) => {
+                let stream = &mut self.streams[index];
+                stream.creating = false;
+                match arguments.first() {
+                    Some(AmfValue::Number(id)) if !is_error => {
+                        stream.id = Some(*id as u32);
+                        let buffer_length = stream.buffer_length;
+                        if let Some(name) = stream.pending_play.take() {
+                            self.send_play(*id as u32, name, buffer_length);
+                        }
+                    }
+                    _ => {
+                        stream.pending_play = None;
+                        self.events.push(RtmpEvent::StreamStatus(
+                            index,
+                            vec![
+                                ("code".to_string(), "NetStream.Play.Failed".to_string()),
+                                ("level".to_string(), "error".to_string()),
+                            ],
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Builds the payload of a command message.
+fn command_payload(
+    name: &str,
+    transaction: u32,
+    command_object: AmfValue,
+    arguments: Vec<Rc<AmfValue>>,
+) -> Vec<u8> {
+    let mut payload = write_amf0(Rc::new(AmfValue::String(name.to_string())));
+    payload.extend(write_amf0(Rc::new(AmfValue::Number(transaction.into()))));
+    payload.extend(write_amf0(Rc::new(command_object)));
+    for argument in arguments {
+        payload.extend(write_amf0(argument));
+    }
+    payload
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::navigator::NullNavigatorBackend;
+
+    fn message(type_id: u8, stream_id: u32, timestamp: u32, length: usize) -> RtmpMessage {
+        RtmpMessage {
+            type_id,
+            stream_id,
+            timestamp,
+            payload: (0..length).map(|i| i as u8).collect(),
+        }
+    }
+
+    #[test]
+    fn chunk_round_trip() {
+        let writer = ChunkWriter::default();
+        let messages = [
+            message(MSG_VIDEO, 1, 0, 300),
+            message(MSG_AUDIO, 1, 40, 20),
+            message(MSG_VIDEO, 1, 0x0100_0000, 200),
+            message(MSG_COMMAND_AMF0, 0, 0, 0),
+        ];
+        let mut data = vec![];
+        for (i, message) in messages.iter().enumerate() {
+            writer.write(&mut data, 4 + i as u8, message);
+        }
+
+        // Feeding the data byte by byte must give the same result as feeding
+        // it all at once.
+        let mut reader = ChunkReader::default();
+        let received: Vec<_> = data
+            .iter()
+            .flat_map(|byte| reader.read(std::slice::from_ref(byte)))
+            .collect();
+        assert_eq!(received, messages);
+        assert_eq!(ChunkReader::default().read(&data), messages);
+    }
+
+    #[test]
+    fn chunk_header_compression() {
+        let data = [
+            // Type 0: chunk stream 4, timestamp 1000, 2 bytes of type 9 on stream 1.
+            &[
+                0x04, 0x00, 0x03, 0xE8, 0x00, 0x00, 0x02, 0x09, 0x01, 0x00, 0x00, 0x00, 0xAA, 0xBB,
+            ][..],
+            // Type 1: delta 40, 1 byte of type 8.
+            &[0x44, 0x00, 0x00, 0x28, 0x00, 0x00, 0x01, 0x08, 0xCC],
+            // Type 2: delta 10.
+            &[0x84, 0x00, 0x00, 0x0A, 0xDD],
+            // Type 3: same delta again.
+            &[0xC4, 0xEE],
+            // Type 0 with a two byte basic header (chunk stream 64 + 1) and
+            // an extended timestamp.
+            &[
+                0x00, 0x01, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x01, 0x12, 0x00, 0x00, 0x00, 0x00, 0x01,
+                0x00, 0x00, 0x00, 0xFF,
+            ],
+        ]
+        .concat();
+
+        let mut reader = ChunkReader::default();
+        let received: Vec<_> = reader
+            .read(&data)
+            .into_iter()
+            .map(|message| {
+                (
+                    message.type_id,
+                    message.stream_id,
+                    message.timestamp,
+                    message.payload,
+                )
+            })
+            .collect();
+        assert_eq!(
+            received,
+            vec![
+                (MSG_VIDEO, 1, 1000, vec![0xAA, 0xBB]),
+                (MSG_AUDIO, 1, 1040, vec![0xCC]),
+                (MSG_AUDIO, 1, 1050, vec![0xDD]),
+                (MSG_AUDIO, 1, 1060, vec![0xEE]),
+                (MSG_DATA_AMF0, 0, 0x0100_0000, vec![0xFF]),
+            ]
+        );
+    }
+
+    #[test]
+    fn flv_tag_layout() {
+        assert_eq!(
+            flv_tag(MSG_AUDIO, 0x0102_0304, &[0xAF, 0x01]),
+            vec![
+                0x08, 0x00, 0x00, 0x02, 0x02, 0x03, 0x04, 0x01, 0x00, 0x00, 0x00, 0xAF, 0x01, 0x00,
+                0x00, 0x00, 0x0D,
+            ]
+        );
+    }
+
+    /// Acts as a server for an `Rtmp` connected over a fake socket.
+    struct Server {
+        client: Rtmp,
+        navigator: NullNavigatorBackend,
+        to_client: Sender<SocketAction>,
+        from_client: Receiver<Vec<u8>>,
+        reader: ChunkReader,
+        writer: ChunkWriter,
+    }
+
+    impl Server {
+        fn new() -> Self {
+            let (sender, from_client) = unbounded();
+            let (to_client, receiver) = unbounded();
+            let connect_command = command_payload(
+                "connect",
+                1,
+                AmfValue::Null,
+                vec![Rc::new(AmfValue::String("hello".to_string()))],
+            );
+            let client = Rtmp::new(
+                "rtmp://localhost/app".to_string(),
+                "rtmp",
+                Transport::Socket { sender, receiver },
+                connect_command,
+            );
+            Self {
+                client,
+                navigator: NullNavigatorBackend::new(),
+                to_client,
+                from_client,
+                reader: ChunkReader::default(),
+                writer: ChunkWriter::default(),
+            }
+        }
+
+        fn send(&mut self, data: Vec<u8>) {
+            self.to_client
+                .try_send(SocketAction::Data(SocketHandle::default(), data))
+                .unwrap();
+            self.client.update(&mut self.navigator);
+        }
+
+        fn send_command(&mut self, stream_id: u32, values: Vec<AmfValue>) {
+            let payload = values
+                .into_iter()
+                .flat_map(|v| write_amf0(Rc::new(v)))
+                .collect();
+            let mut data = vec![];
+            self.writer.write(
+                &mut data,
+                COMMAND_CHUNK_STREAM,
+                &RtmpMessage {
+                    type_id: MSG_COMMAND_AMF0,
+                    stream_id,
+                    timestamp: 0,
+                    payload,
+                },
+            );
+            self.send(data);
+        }
+
+        fn received(&mut self) -> Vec<u8> {
+            let mut data = vec![];
+            while let Ok(bytes) = self.from_client.try_recv() {
+                data.extend(bytes);
+            }
+            data
+        }
+
+        /// The commands received from the client, as `(stream, name, transaction)`.
+        fn received_commands(&mut self) -> Vec<(u32, String, u32)> {
+            let data = self.received();
+            self.reader
+                .read(&data)
+                .into_iter()
+                .filter(|message| message.type_id == MSG_COMMAND_AMF0)
+                .map(|message| {
+                    let values = read_amf0(&message.payload);
+                    let name = match &values[0] {
+                        AmfValue::String(name) => name.clone(),
+                        value => panic!("Unexpected command name {value:?}"),
+                    };
+                    let transaction = match &values[1] {
+                        AmfValue::Number(transaction) => *transaction as u32,
+                        value => panic!("Unexpected transaction {value:?}"),
+                    };
+                    (message.stream_id, name, transaction)
+                })
+                .collect()
+        }
+    }
+
+    fn status(code: &str) -> AmfValue {
+        AmfValue::Object(
+            ObjectId::INVALID,
+            vec![
+                Element::new("level", Rc::new(AmfValue::String("status".to_string()))),
+                Element::new("code", Rc::new(AmfValue::String(code.to_string()))),
+            ],
+            None,
+        )
+    }
+
+    #[test]
+    fn connect_call_and_play() {
+        let mut server = Server::new();
+
+        // Commands made while connecting wait for the connection.
+        server.client.call(
+            "echo".to_string(),
+            None,
+            AmfValue::StrictArray(ObjectId::INVALID, vec![]),
+        );
+        server.client.play(0, "video".to_string(), 100);
+
+        server
+            .to_client
+            .try_send(SocketAction::Connect(
+                SocketHandle::default(),
+                ConnectionState::Connected,
+            ))
+            .unwrap();
+        server.client.update(&mut server.navigator);
+        assert_eq!(server.received(), client_hello());
+
+        // Split the server's handshake, to make sure it's reassembled.
+        let mut handshake = vec![RTMP_VERSION];
+        handshake.extend((0..2 * HANDSHAKE_SIZE).map(|i| (i % 251) as u8));
+        server.send(handshake[..1000].to_vec());
+        assert!(server.received().is_empty());
+        server.send(handshake[1000..].to_vec());
+
+        let data = server.received();
+        assert_eq!(data[..HANDSHAKE_SIZE], handshake[1..1 + HANDSHAKE_SIZE]);
+        let connect = server.reader.read(&data[HANDSHAKE_SIZE..]);
+        assert_eq!(connect.len(), 1);
+        let values = read_amf0(&connect[0].payload);
+        assert!(matches!(&values[0], AmfValue::String(name) if name == "connect"));
+        assert!(matches!(&values[3], AmfValue::String(arg) if arg == "hello"));
+        assert!(!server.client.is_connected());
+
+        server.send_command(
+            0,
+            vec![
+                AmfValue::String("_result".to_string()),
+                AmfValue::Number(1.0),
+                AmfValue::Null,
+                status("NetConnection.Connect.Success"),
+            ],
+        );
+        assert!(server.client.is_connected());
+        assert!(matches!(
+            &server.client.take_events()[..],
+            [RtmpEvent::Status(info)] if status_code(info) == Some("NetConnection.Connect.Success")
+        ));
+        assert_eq!(
+            server.received_commands(),
+            vec![
+                (0, "echo".to_string(), 2),
+                (0, "createStream".to_string(), 3)
+            ]
+        );
+
+        server.send_command(
+            0,
+            vec![
+                AmfValue::String("_result".to_string()),
+                AmfValue::Number(3.0),
+                AmfValue::Null,
+                AmfValue::Number(5.0),
+            ],
+        );
+        assert_eq!(server.received_commands(), vec![(5, "play".to_string(), 0)]);
+
+        let mut data = vec![];
+        server.writer.write(
+            &mut data,
+            STREAM_CHUNK_STREAM,
+            &RtmpMessage {
+                type_id: MSG_VIDEO,
+                stream_id: 5,
+                timestamp: 5000,
+                payload: vec![0x17, 0x00],
+            },
+        );
+        server.send(data);
+        server.send_command(
+            5,
+            vec![
+                AmfValue::String("onStatus".to_string()),
+                AmfValue::Number(0.0),
+                AmfValue::Null,
+                status("NetStream.Play.Stop"),
+            ],
+        );
+
+        let events = server.client.take_events();
+        assert!(matches!(
+            &events[..],
+            [RtmpEvent::StreamData(0, tag), RtmpEvent::StreamEnd(0)] if *tag == flv_tag(MSG_VIDEO, 0, &[0x17, 0x00])
+        ));
+
+        server
+            .to_client
+            .try_send(SocketAction::Close(SocketHandle::default()))
+            .unwrap();
+        server.client.update(&mut server.navigator);
+        assert!(matches!(
+            &server.client.take_events()[..],
+            [RtmpEvent::Closed]
+        ));
+    }
+
+    #[test]
+    fn nested_aggregate() {
+        let mut server = Server::new();
+        let ping = |timestamp: u32| {
+            let mut payload = USER_CONTROL_PING_REQUEST.to_be_bytes().to_vec();
+            payload.extend_from_slice(&timestamp.to_be_bytes());
+            flv_tag(MSG_USER_CONTROL, 0, &payload)
+        };
+        let nested = flv_tag(MSG_AGGREGATE, 0, &ping(2));
+        server.client.handle_message(RtmpMessage {
+            type_id: MSG_AGGREGATE,
+            stream_id: 0,
+            timestamp: 0,
+            payload: [ping(1), nested, ping(3)].concat(),
+        });
+
+        // Only the pings which aren't in the nested aggregate are answered.
+        let data = server.received();
+        let responses: Vec<_> = server
+            .reader
+            .read(&data)
+            .into_iter()
+            .filter(|message| message.type_id == MSG_USER_CONTROL)
+            .map(|message| message.payload)
+            .collect();
+        assert_eq!(
+            responses,
+            vec![
+                vec![0x00, 0x07, 0x00, 0x00, 0x00, 0x01],
+                vec![0x00, 0x07, 0x00, 0x00, 0x00, 0x03],
+            ]
+        );
+    }
+
+    #[test]
+    fn connect_failure() {
+        let mut server = Server::new();
+        server
+            .to_client
+            .try_send(SocketAction::Connect(
+                SocketHandle::default(),
+                ConnectionState::Failed,
+            ))
+            .unwrap();
+        server.client.update(&mut server.navigator);
+        assert!(matches!(
+            &server.client.take_events()[..],
+            [RtmpEvent::Failed]
+        ));
+        assert!(server.received().is_empty());
+    }
+}
@@ -0,0 +1,529 @@
+//! Remote shared object messages, and an in-process server for them.
+//!
+//! Remote shared objects are synchronized by exchanging messages made of
+//! events with the server: clients request changes to slots, and the server
+//! tells every client using the object what changed. The same messages are
+//! used over RTMP (as shared object messages) and with the local server which
+//! stands in for a real one when a `NetConnection` is connected to `null`.
+
+use super::rtmp::{read_amf0, write_amf0};
+use flash_lso::types::Value as AmfValue;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+/// The longest string a message can hold, as strings are prefixed with
+/// their length as a `u16`.
+///
+/// Messages must not contain longer strings.
+pub const MAX_STRING_LENGTH: usize = u16::MAX as usize;
+
+/// The flag marking a shared object as persistent on the server.
+const FLAG_PERSISTENT: u32 = 2;
+
+const EVENT_USE: u8 = 1;
+const EVENT_RELEASE: u8 = 2;
+const EVENT_REQUEST_CHANGE: u8 = 3;
+const EVENT_CHANGE: u8 = 4;
+const EVENT_SUCCESS: u8 = 5;
+const EVENT_SEND_MESSAGE: u8 = 6;
+const EVENT_STATUS: u8 = 7;
+const EVENT_CLEAR: u8 = 8;
+const EVENT_REMOVE: u8 = 9;
+const EVENT_REQUEST_REMOVE: u8 = 10;
+const EVENT_USE_SUCCESS: u8 = 11;
+
+#[derive(Clone, Debug)]
+pub enum SharedObjectEvent {
+    /// The client starts using the shared object.
+    Use,
+
+    /// The client stops using the shared object.
+    Release,
+
+    /// The client wants to change a slot.
+    RequestChange(String, Rc<AmfValue>),
+
+    /// A slot was changed, by another client or because the change requested
+    /// by this one was rejected.
+    Change(String, Rc<AmfValue>),
+
+    /// The change (or removal) of a slot requested by the client was accepted.
+    Success(String),
+
+    /// A method is called on every client, with its name followed by its
+    /// arguments.
+    SendMessage(Vec<Rc<AmfValue>>),
+
+    /// A status from the server, as its code and level.
+    Status(String, String),
+
+    /// All slots were removed.
+    Clear,
+
+    /// A slot was removed.
+    Remove(String),
+
+    /// The client wants to remove a slot.
+    RequestRemove(String),
+
+    /// The server accepted `Use`.
+    UseSuccess,
+}
+
+#[derive(Clone, Debug)]
+pub struct SharedObjectMessage {
+    pub name: String,
+
+    /// The version of the shared object, which the server increases with
+    /// every change.
+    pub version: u32,
+
+    pub persistent: bool,
+
+    pub events: Vec<SharedObjectEvent>,
+}
+
+impl SharedObjectMessage {
+    pub fn new(name: String, version: u32, persistent: bool) -> Self {
+        Self {
+            name,
+            version,
+            persistent,
+            events: vec![],
+        }
+    }
+
+    /// Encodes the message as the payload of an RTMP shared object message.
+    pub fn write(&self) -> Vec<u8> {
+        let mut output = vec![];
+        write_string(&mut output, &self.name);
+        output.extend_from_slice(&self.version.to_be_bytes());
+        let flags = if self.persistent { FLAG_PERSISTENT } else { 0 };
+        output.extend_from_slice(&flags.to_be_bytes());
+        output.extend_from_slice(&[0; 4]);
+
+        for event in &self.events {
+            let mut data = vec![];
+            let event_type = match event {
+                SharedObjectEvent::Use => EVENT_USE,
+                SharedObjectEvent::Release => EVENT_RELEASE,
+                SharedObjectEvent::RequestChange(name, value) => {
+                    write_string(&mut data, name);
+                    data.extend(write_amf0(value.clone()));
+                    EVENT_REQUEST_CHANGE
+                }
+                SharedObjectEvent::Change(name, value) => {
+                    write_string(&mut data, name);
+                    data.extend(write_amf0(value.clone()));
+                    EVENT_CHANGE
+                }
+                SharedObjectEvent::Success(name) => {
+                    write_string(&mut data, name);
+                    EVENT_SUCCESS
+                }
+                SharedObjectEvent::SendMessage(values) => {
+                    for value in values {
+                        data.extend(write_amf0(value.clone()));
+                    }
+                    EVENT_SEND_MESSAGE
+                }
+                SharedObjectEvent::Status(code, level) => {
+                    write_string(&mut data, code);
+                    write_string(&mut data, level);
+                    EVENT_STATUS
+                }
+                SharedObjectEvent::Clear => EVENT_CLEAR,
+                SharedObjectEvent::Remove(name) => {
+                    write_string(&mut data, name);
+                    EVENT_REMOVE
+                }
+                SharedObjectEvent::RequestRemove(name) => {
+                    write_string(&mut data, name);
+                    EVENT_REQUEST_REMOVE
+                }
+                SharedObjectEvent::UseSuccess => EVENT_USE_SUCCESS,
+            };
+            output.push(event_type);
+            output.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            output.extend(data);
+        }
+
+        output
+    }
+
+    /// Decodes the payload of an RTMP shared object message.
+    ///
+    /// Unknown events are skipped.
+    pub fn read(mut data: &[u8]) -> Option<Self> {
+        let name = read_string(&mut data)?;
+        let version = read_u32(&mut data)?;
+        let flags = read_u32(&mut data)?;
+        read_u32(&mut data)?;
+
+        let mut message = Self::new(name, version, flags & FLAG_PERSISTENT != 0);
+        while !data.is_empty() {
+            let event_type = take(&mut data, 1)?[0];
+            let length = read_u32(&mut data)? as usize;
+            let mut event = take(&mut data, length)?;
+
+            message.events.push(match event_type {
+                EVENT_USE => SharedObjectEvent::Use,
+                EVENT_RELEASE => SharedObjectEvent::Release,
+                EVENT_REQUEST_CHANGE | EVENT_CHANGE => {
+                    let name = read_string(&mut event)?;
+                    let value = Rc::new(read_amf0(event).into_iter().next()?);
+                    if event_type == EVENT_CHANGE {
+                        SharedObjectEvent::Change(name, value)
+                    } else {
+                        SharedObjectEvent::RequestChange(name, value)
+                    }
+                }
+                EVENT_SUCCESS => SharedObjectEvent::Success(read_string(&mut event)?),
+                EVENT_SEND_MESSAGE => SharedObjectEvent::SendMessage(
+                    read_amf0(event).into_iter().map(Rc::new).collect(),
+                ),
+                EVENT_STATUS => {
+                    let code = read_string(&mut event)?;
+                    let level = read_string(&mut event)?;
+                    SharedObjectEvent::Status(code, level)
+                }
+                EVENT_CLEAR => SharedObjectEvent::Clear,
+                EVENT_REMOVE => SharedObjectEvent::Remove(read_string(&mut event)?),
+                EVENT_REQUEST_REMOVE => SharedObjectEvent::RequestRemove(read_string(&mut event)?),
+                EVENT_USE_SUCCESS => SharedObjectEvent::UseSuccess,
+                _ => {
+                    tracing::warn!("Ignoring unknown shared object event {}", event_type);
+                    continue;
+                }
+            });
+        }
+
+        Some(message)
+    }
+}
+
+fn take<'a>(data: &mut &'a [u8], length: usize) -> Option<&'a [u8]> {
+    if data.len() < length {
+        return None;
+    }
+    let (taken, rest) = data.split_at(length);
+    *data = rest;
+    Some(taken)
+}
+
+fn read_u32(data: &mut &[u8]) -> Option<u32> {
+    Some(u32::from_be_bytes(take(data, 4)?.try_into().ok()?))
+}
+
+fn read_string(data: &mut &[u8]) -> Option<String> {
+    let length = u16::from_be_bytes(take(data, 2)?.try_into().ok()?);
+    Some(String::from_utf8_lossy(take(data, length.into())?).into_owned())
+}
+
+fn write_string(output: &mut Vec<u8>, value: &str) {
+    debug_assert!(value.len() <= MAX_STRING_LENGTH);
+    output.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    output.extend_from_slice(value.as_bytes());
+}
+
+/// A change to the data of a remote shared object, to be applied to it and
+/// reported by its `sync` event.
+#[derive(Debug)]
+pub enum SyncUpdate {
+    /// All slots were removed, which happens before the initial data is
+    /// received.
+    Clear,
+
+    /// A slot was changed, with the given code (`"change"` or `"reject"`).
+    Change(String, Rc<AmfValue>, &'static str),
+
+    /// A change requested by us was accepted.
+    Success(String),
+
+    /// A slot was removed.
+    Delete(String),
+}
+
+struct ServedObject<C> {
+    version: u32,
+    persistent: bool,
+    slots: BTreeMap<String, Rc<AmfValue>>,
+    clients: Vec<C>,
+}
+
+/// An in-process stand-in for the shared objects of a server, shared by all
+/// of its clients.
+///
+/// Clients are identified by `C`, and every change made by one client is sent
+/// to all others using the same shared object.
+pub struct SharedObjectServer<C> {
+    objects: HashMap<String, ServedObject<C>>,
+}
+
+impl<C> Default for SharedObjectServer<C> {
+    fn default() -> Self {
+        Self {
+            objects: HashMap::new(),
+        }
+    }
+}
+
+impl<C: Copy + PartialEq> SharedObjectServer<C> {
+    /// Handles a message from a client, returning the messages to send to
+    /// clients in response.
+    pub fn handle(
+        &mut self,
+        client: C,
+        message: SharedObjectMessage,
+    ) -> Vec<(C, SharedObjectMessage)> {
+        let mut replies: Vec<(C, SharedObjectMessage)> = vec![];
+        let mut reply = |object: &ServedObject<C>, to: C, event: SharedObjectEvent| match replies
+            .iter_mut()
+            .find(|(client, _)| *client == to)
+        {
+            Some((_, message)) => message.events.push(event),
+            None => {
+                let mut reply =
+                    SharedObjectMessage::new(message.name.clone(), 0, object.persistent);
+                reply.events.push(event);
+                replies.push((to, reply));
+            }
+        };
+
+        let object = self
+            .objects
+            .entry(message.name.clone())
+            .or_insert_with(|| ServedObject {
+                version: 0,
+                persistent: message.persistent,
+                slots: BTreeMap::new(),
+                clients: vec![],
+            });
+
+        for event in message.events.iter().cloned() {
+            match event {
+                SharedObjectEvent::Use => {
+                    if !object.clients.contains(&client) {
+                        object.clients.push(client);
+                    }
+                    reply(object, client, SharedObjectEvent::UseSuccess);
+                    reply(object, client, SharedObjectEvent::Clear);
+                    for (name, value) in &object.slots {
+                        reply(
+                            object,
+                            client,
+                            SharedObjectEvent::Change(name.clone(), value.clone()),
+                        );
+                    }
+                }
+                SharedObjectEvent::Release => object.clients.retain(|c| *c != client),
+                SharedObjectEvent::RequestChange(name, value) => {
+                    object.version += 1;
+                    object.slots.insert(name.clone(), value.clone());
+                    for &other in &object.clients {
+                        let event = if other == client {
+                            SharedObjectEvent::Success(name.clone())
+                        } else {
+                            SharedObjectEvent::Change(name.clone(), value.clone())
+                        };
+                        reply(object, other, event);
+                    }
+                }
+                SharedObjectEvent::RequestRemove(name) => {
+                    if object.slots.remove(&name).is_some() {
+                        object.version += 1;
+                    }
+                    for &other in &object.clients {
+                        let event = if other == client {
+                            SharedObjectEvent::Success(name.clone())
+                        } else {
+                            SharedObjectEvent::Remove(name.clone())
+                        };
+                        reply(object, other, event);
+                    }
+                }
+                SharedObjectEvent::SendMessage(values) => {
+                    // Messages are also sent back to their sender.
+                    for &other in &object.clients {
+                        reply(
+                            object,
+                            other,
+                            SharedObjectEvent::SendMessage(values.clone()),
+                        );
+                    }
+                }
+                // These are only ever sent by servers.
+                SharedObjectEvent::Change(..)
+                | SharedObjectEvent::Success(_)
+                | SharedObjectEvent::Status(..)
+                | SharedObjectEvent::Clear
+                | SharedObjectEvent::Remove(_)
+                | SharedObjectEvent::UseSuccess => {}
+            }
+        }
+
+        let version = object.version;
+        if object.clients.is_empty() && !object.persistent {
+            self.objects.remove(&message.name);
+        }
+
+        for (_, reply) in &mut replies {
+            reply.version = version;
+        }
+        replies
+    }
+
+    /// Releases all shared objects used by a client, e.g. because its
+    /// connection was closed.
+    pub fn disconnect(&mut self, client: C) {
+        self.objects.retain(|_, object| {
+            object.clients.retain(|c| *c != client);
+            object.persistent || !object.clients.is_empty()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(events: Vec<SharedObjectEvent>) -> SharedObjectMessage {
+        SharedObjectMessage {
+            name: "chat".to_string(),
+            version: 0,
+            persistent: false,
+            events,
+        }
+    }
+
+    fn number(value: f64) -> Rc<AmfValue> {
+        Rc::new(AmfValue::Number(value))
+    }
+
+    #[test]
+    fn message_round_trip() {
+        let mut original = message(vec![
+            SharedObjectEvent::Use,
+            SharedObjectEvent::RequestChange("x".to_string(), number(1.5)),
+            SharedObjectEvent::SendMessage(vec![
+                Rc::new(AmfValue::String("say".to_string())),
+                number(2.0),
+            ]),
+            SharedObjectEvent::Status("SharedObject.Flush.Failed".to_string(), "error".to_string()),
+            SharedObjectEvent::RequestRemove("y".to_string()),
+        ]);
+        original.version = 7;
+        original.persistent = true;
+
+        let data = original.write();
+        // Name, version and flags.
+        assert_eq!(
+            data[..16],
+            [0, 4, b'c', b'h', b'a', b't', 0, 0, 0, 7, 0, 0, 0, 2, 0, 0]
+        );
+        // The `Use` event, which has no data.
+        assert_eq!(data[16..21], [EVENT_USE, 0, 0, 0, 0]);
+
+        let read = SharedObjectMessage::read(&data).expect("valid message");
+        assert_eq!(read.name, "chat");
+        assert_eq!(read.version, 7);
+        assert!(read.persistent);
+        assert!(matches!(
+            &read.events[..],
+            [
+                SharedObjectEvent::Use,
+                SharedObjectEvent::RequestChange(name, value),
+                SharedObjectEvent::SendMessage(values),
+                SharedObjectEvent::Status(code, level),
+                SharedObjectEvent::RequestRemove(removed),
+            ] if name == "x"
+                && matches!(**value, AmfValue::Number(n) if n == 1.5)
+                && values.len() == 2
+                && code == "SharedObject.Flush.Failed"
+                && level == "error"
+                && removed == "y"
+        ));
+
+        assert!(SharedObjectMessage::read(&data[..data.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn server_synchronizes_clients() {
+        let mut server = SharedObjectServer::default();
+
+        let replies = server.handle(1, message(vec![SharedObjectEvent::Use]));
+        assert!(matches!(
+            &replies[..],
+            [(1, reply)] if matches!(
+                &reply.events[..],
+                [SharedObjectEvent::UseSuccess, SharedObjectEvent::Clear]
+            )
+        ));
+
+        let replies = server.handle(
+            1,
+            message(vec![SharedObjectEvent::RequestChange(
+                "x".to_string(),
+                number(1.0),
+            )]),
+        );
+        assert!(matches!(
+            &replies[..],
+            [(1, reply)] if reply.version == 1
+                && matches!(&reply.events[..], [SharedObjectEvent::Success(name)] if name == "x")
+        ));
+
+        // A new client gets the current data.
+        let replies = server.handle(2, message(vec![SharedObjectEvent::Use]));
+        assert!(matches!(
+            &replies[..],
+            [(2, reply)] if matches!(
+                &reply.events[..],
+                [
+                    SharedObjectEvent::UseSuccess,
+                    SharedObjectEvent::Clear,
+                    SharedObjectEvent::Change(name, _),
+                ] if name == "x"
+            )
+        ));
+
+        // Changes and removals are sent to the other clients.
+        let replies = server.handle(
+            2,
+            message(vec![
+                SharedObjectEvent::RequestChange("y".to_string(), number(2.0)),
+                SharedObjectEvent::RequestRemove("x".to_string()),
+            ]),
+        );
+        assert_eq!(replies.len(), 2);
+        assert!(replies.iter().all(|(_, reply)| reply.version == 3));
+        assert!(matches!(
+            &replies.iter().find(|(client, _)| *client == 1).unwrap().1.events[..],
+            [SharedObjectEvent::Change(changed, _), SharedObjectEvent::Remove(removed)]
+                if changed == "y" && removed == "x"
+        ));
+        assert!(matches!(
+            &replies.iter().find(|(client, _)| *client == 2).unwrap().1.events[..],
+            [SharedObjectEvent::Success(changed), SharedObjectEvent::Success(removed)]
+                if changed == "y" && removed == "x"
+        ));
+
+        // Messages go to everyone, including the sender.
+        let replies = server.handle(
+            1,
+            message(vec![SharedObjectEvent::SendMessage(vec![Rc::new(
+                AmfValue::String("ping".to_string()),
+            )])]),
+        );
+        assert_eq!(replies.len(), 2);
+
+        // A non-persistent object is gone once nobody uses it.
+        server.handle(1, message(vec![SharedObjectEvent::Release]));
+        server.disconnect(2);
+        let replies = server.handle(3, message(vec![SharedObjectEvent::Use]));
+        assert!(matches!(
+            &replies[..],
+            [(3, reply)] if reply.version == 0 && reply.events.len() == 2
+        ));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::accessibility::AccessibilityTracker;
 use crate::avm1::Attribute;
 use crate::avm1::Avm1;
 use crate::avm1::Object;
@@ -8,18 +9,24 @@ use crate::avm2::object::{EventObject as Avm2EventObject, Object as Avm2Object};
 use crate::avm2::{Activation as Avm2Activation, Avm2, CallStack};
 use crate::backend::ui::FontDefinition;
 use crate::backend::{
+    accessibility::{AccessibilityBackend, AccessibilityTree},
     audio::{AudioBackend, AudioManager},
+    capture::CaptureBackend,
+    filesystem::FileSystemBackend,
     log::LogBackend,
     navigator::{NavigatorBackend, Request},
+    process::ProcessBackend,
     storage::StorageBackend,
-    ui::{MouseCursor, UiBackend},
+    ui::{CustomCursorId, MouseCursor, UiBackend},
 };
+use crate::capture::CaptureDevices;
 use crate::compatibility_rules::CompatibilityRules;
 use crate::config::Letterbox;
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::context_menu::{
     BuiltInItemFlags, ContextMenuCallback, ContextMenuItem, ContextMenuState,
 };
+use crate::datagram_socket::DatagramSockets;
 use crate::display_object::Avm2MousePick;
 use crate::display_object::{
     EditText, InteractiveObject, Stage, StageAlign, StageDisplayState, StageScaleMode,
@@ -32,6 +39,7 @@ use crate::external::{ExternalInterface, ExternalInterfaceProvider, NullFsComman
 use crate::external::{FsCommandProvider, Value as ExternalValue};
 use crate::focus_tracker::NavigationDirection;
 use crate::frame_lifecycle::{run_all_phases_avm2, FramePhase};
+use crate::ime::Ime;
 use crate::input::InputEvent;
 use crate::input::InputManager;
 use crate::library::Library;
@@ -39,13 +47,16 @@ use crate::limits::ExecutionLimit;
 use crate::loader::{LoadBehavior, LoadManager};
 use crate::local_connection::LocalConnections;
 use crate::locale::get_current_date_time;
+use crate::native_application::{AirApplication, NativeApplication};
+use crate::native_process::NativeProcesses;
 use crate::net_connection::NetConnections;
 use crate::prelude::*;
+use crate::sandbox::SecuritySandbox;
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::{AvmStringInterner, StringContext};
 use crate::stub::StubCollection;
-use crate::system_properties::SystemProperties;
+use crate::system_properties::{SystemCapabilities, SystemProperties};
 use crate::tag_utils::SwfMovie;
 use crate::timer::Timers;
 use crate::vminterface::Instantiator;
@@ -114,9 +125,49 @@ pub struct MouseData<'gc> {
     pub pressed: Option<InteractiveObject<'gc>>,
     pub right_pressed: Option<InteractiveObject<'gc>>,
     pub middle_pressed: Option<InteractiveObject<'gc>>,
+
+    /// The mouse cursor icon determined by the hovered object.
+    #[collect(require_static)]
+    pub cursor: MouseCursor,
+
+    /// The cursor forced by AS3 `Mouse.cursor`, or `None` for `MouseCursor.AUTO`.
+    #[collect(require_static)]
+    pub cursor_override: Option<MouseCursor>,
+
+    /// The mouse cursor icon currently shown by the UI backend.
+    #[collect(require_static)]
+    shown_cursor: MouseCursor,
+
+    /// Custom cursors registered by AS3 `Mouse.registerCursor`, by name.
+    #[collect(require_static)]
+    pub registered_cursors: HashMap<String, CustomCursorId>,
+    next_custom_cursor_id: u32,
 }
 
 impl<'gc> MouseData<'gc> {
+    /// Shows the cursor forced by content, or the one determined by the
+    /// hovered object, if it isn't shown already.
+    pub fn update_cursor(&mut self, ui: &mut dyn UiBackend) {
+        let cursor = self.cursor_override.unwrap_or(self.cursor);
+        if cursor != self.shown_cursor {
+            self.shown_cursor = cursor;
+            ui.set_mouse_cursor(cursor);
+        }
+    }
+
+    /// Returns the ID to register a custom cursor with the given name under,
+    /// reusing the ID of any cursor previously registered with that name.
+    pub fn custom_cursor_id(&mut self, name: &str) -> CustomCursorId {
+        if let Some(id) = self.registered_cursors.get(name) {
+            return *id;
+        }
+
+        let id = CustomCursorId(self.next_custom_cursor_id);
+        self.next_custom_cursor_id = self.next_custom_cursor_id.wrapping_add(1);
+        self.registered_cursors.insert(name.to_string(), id);
+        id
+    }
+
     pub fn pressed(&self, button: MouseButton) -> Option<InteractiveObject<'gc>> {
         match button {
             MouseButton::Unknown => None,
@@ -186,8 +237,22 @@ struct GcRootData<'gc> {
     /// List of actively playing streams to decode.
     stream_manager: StreamManager<'gc>,
 
+    /// Cameras and microphones requested by the movie.
+    capture_devices: CaptureDevices<'gc>,
+
+    /// The lifecycle state of an AIR application.
+    native_application: NativeApplication<'gc>,
+
+    /// The input method state of the movie.
+    ime: Ime<'gc>,
+
+    /// Native processes started by the movie.
+    native_processes: NativeProcesses<'gc>,
+
     sockets: Sockets<'gc>,
 
+    datagram_sockets: DatagramSockets<'gc>,
+
     /// List of active NetConnection objects.
     net_connections: NetConnections<'gc>,
 
@@ -231,7 +296,12 @@ impl<'gc> GcRootData<'gc> {
         &mut ExternalInterface<'gc>,
         &mut AudioManager<'gc>,
         &mut StreamManager<'gc>,
+        &mut CaptureDevices<'gc>,
+        &mut NativeApplication<'gc>,
+        &mut Ime<'gc>,
+        &mut NativeProcesses<'gc>,
         &mut Sockets<'gc>,
+        &mut DatagramSockets<'gc>,
         &mut NetConnections<'gc>,
         &mut LocalConnections<'gc>,
         &mut Vec<PostFrameCallback<'gc>>,
@@ -255,7 +325,12 @@ impl<'gc> GcRootData<'gc> {
             &mut self.external_interface,
             &mut self.audio_manager,
             &mut self.stream_manager,
+            &mut self.capture_devices,
+            &mut self.native_application,
+            &mut self.ime,
+            &mut self.native_processes,
             &mut self.sockets,
+            &mut self.datagram_sockets,
             &mut self.net_connections,
             &mut self.local_connections,
             &mut self.post_frame_callbacks,
@@ -304,6 +379,15 @@ pub struct Player {
     log: Box<dyn LogBackend>,
     ui: Box<dyn UiBackend>,
     video: Box<dyn VideoBackend>,
+    capture: Box<dyn CaptureBackend>,
+    filesystem: Box<dyn FileSystemBackend>,
+    process: Box<dyn ProcessBackend>,
+    accessibility: Box<dyn AccessibilityBackend>,
+
+    /// The accessibility tree last sent to the accessibility backend.
+    accessibility_tree: Option<AccessibilityTree>,
+
+    accessibility_tracker: AccessibilityTracker,
 
     transform_stack: TransformStack,
 
@@ -334,8 +418,6 @@ pub struct Player {
     mouse_in_stage: bool,
     mouse_position: Point<Twips>,
 
-    /// The current mouse cursor icon.
-    mouse_cursor: MouseCursor,
     mouse_cursor_needs_check: bool,
 
     system: SystemProperties,
@@ -376,6 +458,9 @@ pub struct Player {
     /// Any compatibility rules to apply for this movie.
     compatibility_rules: CompatibilityRules,
 
+    /// Policy files and `allowDomain` grants, and whether they're enforced.
+    security_sandbox: SecuritySandbox,
+
     /// Sends notifications back from the core player to the frontend.
     notification_sender: Option<Sender<PlayerNotification>>,
 
@@ -566,10 +651,39 @@ impl Player {
         self.update_timers(dt);
         self.update(|context| {
             StreamManager::tick(context, dt);
+            CaptureDevices::tick(context, dt);
+            NativeApplication::tick(context);
+            NativeProcesses::tick(context);
         });
+        self.update_accessibility();
         self.audio.tick();
     }
 
+    /// Performs the actions requested by assistive technologies,
+    /// and sends them the accessibility tree if it has changed.
+    fn update_accessibility(&mut self) {
+        if !self.accessibility.is_active() {
+            self.accessibility_tree = None;
+            return;
+        }
+
+        let requests = self.accessibility.poll_requests();
+        let has_tree = self.accessibility_tree.is_some();
+        let tree = self.mutate_with_update_context(|context| {
+            for request in requests {
+                crate::accessibility::handle_request(context, request);
+            }
+            Self::run_actions(context);
+            crate::accessibility::update_tree(context, !has_tree)
+        });
+        if let Some(tree) = tree {
+            if self.accessibility_tree.as_ref() != Some(&tree) {
+                self.accessibility.update_tree(&tree);
+                self.accessibility_tree = Some(tree);
+            }
+        }
+    }
+
     pub fn time_til_next_timer(&self) -> Option<f64> {
         self.time_til_next_timer
     }
@@ -933,6 +1047,15 @@ impl Player {
         self.mutate_with_update_context(|context| {
             context.renderer.set_viewport_dimensions(dimensions);
             context.stage.build_matrices(context);
+            context.accessibility_tracker.invalidate();
+        })
+    }
+
+    /// Should be called when the window the player is displayed in is moved, resized,
+    /// minimized or maximized, so that AIR content can be notified.
+    pub fn update_native_window(&mut self) {
+        self.mutate_with_update_context(|context| {
+            context.stage.update_native_window(context);
         })
     }
 
@@ -1018,9 +1141,20 @@ impl Player {
             });
         }
 
+        let is_active = matches!(event, PlayerEvent::FocusGained);
+        self.update(|context| NativeApplication::set_active(context, is_active));
+
         true
     }
 
+    /// Asks the movie whether the application may exit, by dispatching
+    /// `NativeApplication`'s cancelable `exiting` event.
+    ///
+    /// Returns `false` if the movie cancelled the event.
+    pub fn request_exit(&mut self) -> bool {
+        self.update(|context| NativeApplication::request_exit(context))
+    }
+
     /// Input event handling is a complicated affair, involving several different
     /// concerns that need to resolve with specific priority.
     ///
@@ -1044,6 +1178,10 @@ impl Player {
     /// 8. Mouse state is updated. This triggers button rollovers, which are a
     ///    second wave of event processing.
     fn handle_input_event(&mut self, event: PlayerEvent) -> bool {
+        if !matches!(event, PlayerEvent::MouseLeave) {
+            self.update(|context| NativeApplication::user_input(context));
+        }
+
         let mut player_event_handled = false;
         let prev_mouse_buttons = self.input.get_mouse_down_buttons();
         let Some(event) = self.input.process_event(event) else {
@@ -1308,6 +1446,14 @@ impl Player {
                     if let InputEvent::Ime(ime) = &event {
                         text.ime(ime.clone(), context);
                     }
+                    if matches!(
+                        event,
+                        InputEvent::TextInput { .. }
+                            | InputEvent::TextControl { .. }
+                            | InputEvent::Ime(_)
+                    ) {
+                        text.update_ime_cursor_area(context);
+                    }
                 }
             }
 
@@ -1398,6 +1544,11 @@ impl Player {
                     target.event_dispatch_to_avm2(context, event);
                     target.handle_clip_event(context, event);
                 }
+
+                // Text fields scroll after the event has been dispatched.
+                if let Some(text) = EditText::mouse_wheel_target(context) {
+                    text.scroll_by_mouse_wheel(context, *delta);
+                }
             });
         }
 
@@ -1508,13 +1659,14 @@ impl Player {
         is_mouse_moved: bool,
         player_event_handled: &mut bool,
     ) -> bool {
-        let mut new_cursor = self.mouse_cursor;
         let mut mouse_cursor_needs_check = self.mouse_cursor_needs_check;
         let mouse_in_stage = self.mouse_in_stage();
 
         // Determine the display object the mouse is hovering over.
         // Search through levels from top-to-bottom, returning the first display object that is under the mouse.
         let needs_render = self.mutate_with_update_context(|context| {
+            let mut new_cursor = context.mouse_data.cursor;
+
             // Objects may be hovered using Tab,
             // skip mouse hover when it's not necessary.
             let mut skip_mouse_hover = !is_mouse_moved
@@ -1809,14 +1961,13 @@ impl Player {
                 refresh
             };
             Self::run_actions(context);
+
+            // Update mouse cursor if it has changed.
+            context.mouse_data.cursor = new_cursor;
+            context.mouse_data.update_cursor(context.ui);
             needs_render
         });
 
-        // Update mouse cursor if it has changed.
-        if new_cursor != self.mouse_cursor {
-            self.mouse_cursor = new_cursor;
-            self.ui.set_mouse_cursor(new_cursor)
-        }
         self.mouse_cursor_needs_check = mouse_cursor_needs_check;
 
         needs_render
@@ -1991,6 +2142,12 @@ impl Player {
                 stage,
             };
 
+            // Anything changing the look of the stage, such as a changed display list
+            // or edited text, may also change what is exposed to assistive technologies.
+            if stage.is_cached_bitmap_invalidated() {
+                this.accessibility_tracker.invalidate();
+            }
+
             stage.render(&mut render_context);
 
             #[cfg(feature = "egui")]
@@ -2074,6 +2231,46 @@ impl Player {
         &mut *self.ui
     }
 
+    pub fn capture(&self) -> &dyn CaptureBackend {
+        &*self.capture
+    }
+
+    pub fn capture_mut(&mut self) -> &mut dyn CaptureBackend {
+        &mut *self.capture
+    }
+
+    pub fn filesystem(&self) -> &dyn FileSystemBackend {
+        &*self.filesystem
+    }
+
+    pub fn filesystem_mut(&mut self) -> &mut dyn FileSystemBackend {
+        &mut *self.filesystem
+    }
+
+    pub fn process(&self) -> &dyn ProcessBackend {
+        &*self.process
+    }
+
+    pub fn process_mut(&mut self) -> &mut dyn ProcessBackend {
+        &mut *self.process
+    }
+
+    pub fn accessibility(&self) -> &dyn AccessibilityBackend {
+        &*self.accessibility
+    }
+
+    pub fn accessibility_mut(&mut self) -> &mut dyn AccessibilityBackend {
+        &mut *self.accessibility
+    }
+
+    pub fn security_sandbox(&self) -> &SecuritySandbox {
+        &self.security_sandbox
+    }
+
+    pub fn security_sandbox_mut(&mut self) -> &mut SecuritySandbox {
+        &mut self.security_sandbox
+    }
+
     pub fn run_actions(context: &mut UpdateContext<'_>) {
         // Note that actions can queue further actions, so a while loop is necessary here.
         while let Some(action) = context.action_queue.pop_action() {
@@ -2182,7 +2379,12 @@ impl Player {
                 external_interface,
                 audio_manager,
                 stream_manager,
+                capture_devices,
+                native_application,
+                ime,
+                native_processes,
                 sockets,
+                datagram_sockets,
                 net_connections,
                 local_connections,
                 post_frame_callbacks,
@@ -2215,6 +2417,12 @@ impl Player {
                 storage: this.storage.deref_mut(),
                 log: this.log.deref_mut(),
                 video: this.video.deref_mut(),
+                capture: this.capture.deref_mut(),
+                filesystem: this.filesystem.deref_mut(),
+                process: this.process.deref_mut(),
+                accessibility: this.accessibility.deref_mut(),
+                accessibility_tracker: &mut this.accessibility_tracker,
+                security_sandbox: &mut this.security_sandbox,
                 avm1_shared_objects,
                 avm2_shared_objects,
                 unbound_text_fields,
@@ -2237,7 +2445,12 @@ impl Player {
                 frame_phase: &mut this.frame_phase,
                 stub_tracker: &mut this.stub_tracker,
                 stream_manager,
+                capture_devices,
+                native_application,
+                ime,
+                native_processes,
                 sockets,
+                datagram_sockets,
                 net_connections,
                 local_connections,
                 dynamic_root,
@@ -2353,6 +2566,7 @@ impl Player {
     pub fn update_sockets(&mut self) {
         self.mutate_with_update_context(|context| {
             Sockets::update_sockets(context);
+            DatagramSockets::update_sockets(context);
         })
     }
 
@@ -2363,10 +2577,33 @@ impl Player {
         })
     }
 
-    /// Returns whether this player consumes mouse wheel events.
-    /// Used by web to prevent scrolling.
+    /// Returns whether this player consumes mouse wheel events at the current
+    /// mouse position. Used by web to prevent scrolling.
+    ///
+    /// That's the case when a text field under the mouse can be scrolled, or
+    /// when the content listens to the mouse wheel.
     pub fn should_prevent_scrolling(&mut self) -> bool {
-        self.mutate_with_update_context(|context| context.avm1.has_mouse_listener())
+        self.mutate_with_update_context(|context| {
+            if context.avm1.has_mouse_listener() {
+                return true;
+            }
+
+            if EditText::mouse_wheel_target(context)
+                .is_some_and(|text| text.is_mouse_wheel_scrollable())
+            {
+                return true;
+            }
+
+            let target = context
+                .mouse_data
+                .hovered
+                .map(|hovered| hovered.as_displayobject())
+                .unwrap_or_else(|| context.stage.into());
+            target
+                .object2()
+                .as_object()
+                .is_some_and(|object| Avm2::will_trigger(object, istr!(context, "mouseWheel")))
+        })
     }
 
     pub fn set_external_interface_provider(
@@ -2434,6 +2671,14 @@ impl Player {
             context.library.set_default_font(font, names);
         });
     }
+
+    /// Sets the device fonts used for characters that the requested device
+    /// font has no glyphs for, in order of preference.
+    pub fn set_font_fallbacks(&mut self, names: Vec<String>) {
+        self.mutate_with_update_context(|context| {
+            context.library.set_font_fallbacks(names);
+        });
+    }
 }
 
 impl Drop for Player {
@@ -2454,6 +2699,10 @@ pub struct PlayerBuilder {
     storage: Option<Box<dyn StorageBackend>>,
     ui: Option<Box<dyn UiBackend>>,
     video: Option<Box<dyn VideoBackend>>,
+    capture: Option<Box<dyn CaptureBackend>>,
+    filesystem: Option<Box<dyn FileSystemBackend>>,
+    process: Option<Box<dyn ProcessBackend>>,
+    accessibility: Option<Box<dyn AccessibilityBackend>>,
 
     // Notifications
     notification_sender: Option<Sender<PlayerNotification>>,
@@ -2482,9 +2731,11 @@ pub struct PlayerBuilder {
     frame_rate: Option<f64>,
     external_interface_provider: Option<Box<dyn ExternalInterfaceProvider>>,
     fs_command_provider: Box<dyn FsCommandProvider>,
+    air_application: AirApplication,
     #[cfg(feature = "known_stubs")]
     stub_report_output: Option<std::path::PathBuf>,
     avm2_optimizer_enabled: bool,
+    strict_sandbox: bool,
 }
 
 impl PlayerBuilder {
@@ -2504,6 +2755,10 @@ impl PlayerBuilder {
             storage: None,
             ui: None,
             video: None,
+            capture: None,
+            filesystem: None,
+            process: None,
+            accessibility: None,
 
             notification_sender: None,
 
@@ -2535,9 +2790,11 @@ impl PlayerBuilder {
             frame_rate: None,
             external_interface_provider: None,
             fs_command_provider: Box::new(NullFsCommandProvider),
+            air_application: AirApplication::default(),
             #[cfg(feature = "known_stubs")]
             stub_report_output: None,
             avm2_optimizer_enabled: true,
+            strict_sandbox: false,
         }
     }
 
@@ -2611,6 +2868,37 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the capture backend of the player.
+    #[inline]
+    pub fn with_capture(mut self, capture: impl 'static + CaptureBackend) -> Self {
+        self.capture = Some(Box::new(capture));
+        self
+    }
+
+    /// Sets the filesystem backend of the player, used by AIR content.
+    #[inline]
+    pub fn with_filesystem(mut self, filesystem: impl 'static + FileSystemBackend) -> Self {
+        self.filesystem = Some(Box::new(filesystem));
+        self
+    }
+
+    /// Sets the process backend of the player, used by AIR content.
+    #[inline]
+    pub fn with_process(mut self, process: impl 'static + ProcessBackend) -> Self {
+        self.process = Some(Box::new(process));
+        self
+    }
+
+    /// Sets the accessibility backend of the player, used to expose the movie to assistive technologies.
+    #[inline]
+    pub fn with_accessibility(
+        mut self,
+        accessibility: impl 'static + AccessibilityBackend,
+    ) -> Self {
+        self.accessibility = Some(Box::new(accessibility));
+        self
+    }
+
     /// Sets the channel for player notifications.
     #[inline]
     pub fn with_notification_sender(mut self, sender: Sender<PlayerNotification>) -> Self {
@@ -2711,6 +2999,12 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets how an AIR application was launched, exposed through `NativeApplication`.
+    pub fn with_air_application(mut self, air_application: AirApplication) -> Self {
+        self.air_application = air_application;
+        self
+    }
+
     // Configure the embedding page's URL (if applicable)
     pub fn with_page_url(mut self, page_url: Option<String>) -> Self {
         self.page_url = page_url;
@@ -2753,6 +3047,14 @@ impl PlayerBuilder {
         self
     }
 
+    /// Enforces the security sandbox, blocking cross-domain access that isn't
+    /// allowed by policy files or `Security.allowDomain`.
+    pub fn with_strict_sandbox(mut self, value: bool) -> Self {
+        self.strict_sandbox = value;
+        self
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn create_gc_root<'gc>(
         gc_context: &'gc Mutation<'gc>,
         player_version: u8,
@@ -2761,6 +3063,7 @@ impl PlayerBuilder {
         fake_movie: Arc<SwfMovie>,
         external_interface_provider: Option<Box<dyn ExternalInterfaceProvider>>,
         fs_command_provider: Box<dyn FsCommandProvider>,
+        air_application: AirApplication,
     ) -> GcRoot<'gc> {
         let mut interner = AvmStringInterner::new(gc_context);
         let (avm1, avm2) = {
@@ -2793,6 +3096,11 @@ impl PlayerBuilder {
                 pressed: None,
                 right_pressed: None,
                 middle_pressed: None,
+                cursor: MouseCursor::Arrow,
+                cursor_override: None,
+                shown_cursor: MouseCursor::Arrow,
+                registered_cursors: HashMap::new(),
+                next_custom_cursor_id: 0,
             },
             avm1_shared_objects: HashMap::new(),
             avm2_shared_objects: HashMap::new(),
@@ -2800,7 +3108,12 @@ impl PlayerBuilder {
             timers: Timers::new(),
             unbound_text_fields: Vec::new(),
             stream_manager: StreamManager::new(),
+            capture_devices: CaptureDevices::new(),
+            native_application: NativeApplication::new(air_application),
+            ime: Ime::default(),
+            native_processes: NativeProcesses::new(),
             sockets: Sockets::empty(),
+            datagram_sockets: DatagramSockets::empty(),
             net_connections: NetConnections::default(),
             local_connections: LocalConnections::empty(),
             dynamic_root: DynamicRootSet::new(gc_context),
@@ -2842,9 +3155,22 @@ impl PlayerBuilder {
         let video = self
             .video
             .unwrap_or_else(|| Box::new(null::NullVideoBackend::new()));
+        let capture = self
+            .capture
+            .unwrap_or_else(|| Box::new(capture::NullCaptureBackend::new()));
+        let filesystem = self
+            .filesystem
+            .unwrap_or_else(|| Box::new(filesystem::MemoryFileSystemBackend::new()));
+        let process = self
+            .process
+            .unwrap_or_else(|| Box::new(process::NullProcessBackend::new()));
+        let accessibility = self
+            .accessibility
+            .unwrap_or_else(|| Box::new(accessibility::NullAccessibilityBackend::new()));
 
         let player_version = self.player_version.unwrap_or(NEWEST_PLAYER_VERSION);
         let language = ui.language();
+        let has_accessibility = accessibility.is_supported();
 
         // Instantiate the player.
         let fake_movie = Arc::new(SwfMovie::empty(player_version));
@@ -2860,6 +3186,12 @@ impl PlayerBuilder {
                 storage,
                 ui,
                 video,
+                capture,
+                filesystem,
+                process,
+                accessibility,
+                accessibility_tree: None,
+                accessibility_tracker: AccessibilityTracker::new(),
 
                 // SWF info
                 swf: fake_movie.clone(),
@@ -2881,12 +3213,17 @@ impl PlayerBuilder {
                 input: InputManager::new(self.gamepad_button_mapping),
                 mouse_in_stage: true,
                 mouse_position: Point::ZERO,
-                mouse_cursor: MouseCursor::Arrow,
                 mouse_cursor_needs_check: false,
 
                 // Misc. state
                 rng: SmallRng::seed_from_u64(get_current_date_time().timestamp_millis() as u64),
-                system: SystemProperties::new(language),
+                system: {
+                    let mut system = SystemProperties::new(language);
+                    system
+                        .capabilities
+                        .set(SystemCapabilities::ACCESSIBILITY, has_accessibility);
+                    system
+                },
                 page_url: self.page_url.clone(),
                 transform_stack: TransformStack::new(),
                 instance_counter: 0,
@@ -2902,6 +3239,7 @@ impl PlayerBuilder {
                 load_behavior: self.load_behavior,
                 spoofed_url: self.spoofed_url.clone(),
                 compatibility_rules: self.compatibility_rules.clone(),
+                security_sandbox: SecuritySandbox::new(self.strict_sandbox),
                 stub_tracker: StubCollection::new(),
                 notification_sender: self.notification_sender,
                 #[cfg(feature = "egui")]
@@ -2917,6 +3255,7 @@ impl PlayerBuilder {
                         fake_movie.clone(),
                         self.external_interface_provider,
                         self.fs_command_provider,
+                        self.air_application,
                     )
                 }))),
             })
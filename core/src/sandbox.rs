@@ -1,9 +1,26 @@
 //! Security Sandbox implementation, see
 //! https://help.adobe.com/en_US/as3/dev/WS5b3ccc516d4fbf351e63e3d118a9b90204-7e3f.html
 
+use crate::backend::navigator::{ErrorResponse, OwnedFuture, Request, SuccessResponse};
+use crate::loader::Error;
+use crate::player::Player;
+use crate::socket::{ConnectionState, SocketAction, SocketHandle};
+use crate::tag_utils::SwfMovie;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use swf::HeaderExt;
 use url::Url;
 
+/// The port socket policy files are requested from, unless the movie loaded one elsewhere.
+const SOCKET_POLICY_PORT: u16 = 843;
+
+/// How long to wait for a socket policy server to accept the connection.
+const SOCKET_POLICY_TIMEOUT: Duration = Duration::from_secs(3);
+
 /// Type of sandbox that defines what a movie can access
 /// and how movies interact with each other.
 ///
@@ -60,4 +77,636 @@ impl SandboxType {
             }
         }
     }
+
+    /// Whether movies in this sandbox are trusted with access to everything.
+    pub fn is_trusted(self) -> bool {
+        matches!(self, Self::LocalTrusted | Self::Application)
+    }
+
+    pub fn is_local(self) -> bool {
+        !matches!(self, Self::Remote)
+    }
+}
+
+/// A cross-domain policy file, either served over HTTP (usually as
+/// `/crossdomain.xml`) or by a socket policy server.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicyFile {
+    allow_access_from: Vec<AllowAccessFrom>,
+
+    /// The `permitted-cross-domain-policies` meta-policy of the site, if given.
+    permitted_policies: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AllowAccessFrom {
+    domain: String,
+
+    /// The ports sockets may connect to; only used by socket policy files.
+    to_ports: Vec<RangeInclusive<u16>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PolicyFileError {
+    InvalidXml,
+
+    /// The document isn't a `<cross-domain-policy>`.
+    NotAPolicyFile,
+}
+
+impl PolicyFile {
+    pub fn parse(data: &[u8]) -> Result<Self, PolicyFileError> {
+        let mut reader = Reader::from_reader(data);
+        reader.config_mut().trim_text(true);
+
+        let mut policy = Self::default();
+        let mut depth = 0;
+        let mut found_root = false;
+        loop {
+            let (element, is_empty) = match reader.read_event() {
+                Ok(Event::Start(element)) => (element, false),
+                Ok(Event::Empty(element)) => (element, true),
+                Ok(Event::End(_)) => {
+                    depth -= 1;
+                    continue;
+                }
+                Ok(Event::Eof) => break,
+                Ok(_) => continue,
+                Err(_) => return Err(PolicyFileError::InvalidXml),
+            };
+
+            match (depth, element.local_name().as_ref()) {
+                (0, b"cross-domain-policy") => found_root = true,
+                (0, _) => return Err(PolicyFileError::NotAPolicyFile),
+                (1, b"allow-access-from") => {
+                    if let Some(domain) = attribute(&element, b"domain") {
+                        let to_ports = attribute(&element, b"to-ports")
+                            .map(|ports| parse_ports(&ports))
+                            .unwrap_or_default();
+                        policy
+                            .allow_access_from
+                            .push(AllowAccessFrom { domain, to_ports });
+                    }
+                }
+                (1, b"site-control") => {
+                    policy.permitted_policies =
+                        attribute(&element, b"permitted-cross-domain-policies");
+                }
+                _ => {}
+            }
+            if !is_empty {
+                depth += 1;
+            }
+        }
+
+        if found_root {
+            Ok(policy)
+        } else {
+            Err(PolicyFileError::NotAPolicyFile)
+        }
+    }
+
+    /// Whether a movie from `domain` may load data covered by this policy.
+    pub fn allows(&self, domain: &str) -> bool {
+        self.allow_access_from
+            .iter()
+            .any(|allow| domain_matches(&allow.domain, domain))
+    }
+
+    /// Whether a movie from `domain` may open a socket to `port`.
+    pub fn allows_socket(&self, domain: &str, port: u16) -> bool {
+        self.allow_access_from.iter().any(|allow| {
+            domain_matches(&allow.domain, domain)
+                && allow.to_ports.iter().any(|ports| ports.contains(&port))
+        })
+    }
+
+    /// Whether the site allows policy files other than this one to be used.
+    fn permits_other_policies(&self) -> bool {
+        !matches!(
+            self.permitted_policies.as_deref(),
+            Some("none" | "master-only")
+        )
+    }
+
+    /// Whether the site doesn't allow any policy files, including this one.
+    fn permits_nothing(&self) -> bool {
+        self.permitted_policies.as_deref() == Some("none")
+    }
+}
+
+fn attribute(element: &BytesStart, name: &[u8]) -> Option<String> {
+    let attribute = element.try_get_attribute(name).ok()??;
+    Some(attribute.unescape_value().ok()?.trim().to_string())
+}
+
+/// Parses a `to-ports` attribute, such as `80,443,1000-2000` or `*`.
+fn parse_ports(ports: &str) -> Vec<RangeInclusive<u16>> {
+    ports
+        .split(',')
+        .filter_map(|ports| {
+            let ports = ports.trim();
+            if ports == "*" {
+                return Some(1..=u16::MAX);
+            }
+            match ports.split_once('-') {
+                Some((start, end)) => Some(start.trim().parse().ok()?..=end.trim().parse().ok()?),
+                None => {
+                    let port = ports.parse().ok()?;
+                    Some(port..=port)
+                }
+            }
+        })
+        .collect()
+}
+
+/// Matches a domain against a pattern from a policy file or `Security.allowDomain`.
+///
+/// Patterns may be `*`, an exact domain, or `*.example.com`, which also matches
+/// `example.com` itself.
+pub fn domain_matches(pattern: &str, domain: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+    if let Some(suffix) = pattern.strip_prefix("*.") {
+        return domain.eq_ignore_ascii_case(suffix)
+            || domain
+                .len()
+                .checked_sub(suffix.len() + 1)
+                .is_some_and(|dot| {
+                    domain.as_bytes()[dot] == b'.' && domain[dot + 1..].eq_ignore_ascii_case(suffix)
+                });
+    }
+    pattern.eq_ignore_ascii_case(domain)
+}
+
+/// The domain of a movie, as used by policy files and `allowDomain`.
+///
+/// Local movies have no domain, and are only matched by `*`.
+fn movie_domain(movie: &SwfMovie) -> String {
+    Url::parse(movie.url())
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn same_origin(a: &str, b: &str) -> bool {
+    match (Url::parse(a), Url::parse(b)) {
+        (Ok(a), Ok(b)) => a.origin().is_tuple() && a.origin() == b.origin(),
+        _ => false,
+    }
+}
+
+/// Whether something is allowed by the security sandbox.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Allowed,
+    Denied,
+
+    /// The answer depends on a policy file that hasn't been loaded yet.
+    RequiresPolicy,
+}
+
+struct UrlPolicy {
+    /// The directory of the policy file; it only covers URLs within.
+    directory: String,
+
+    policy: PolicyFile,
+}
+
+/// The state of the security sandbox shared by all movies of a player.
+///
+/// Movies are always given the answers Flash Player would give through
+/// `LoaderInfo` and friends, but access is only actually blocked in strict mode.
+#[derive(Default)]
+pub struct SecuritySandbox {
+    strict: bool,
+
+    /// Loaded URL policy files, by the origin they were loaded from.
+    url_policies: HashMap<String, Vec<UrlPolicy>>,
+
+    /// Loaded socket policy files, by host.
+    socket_policies: HashMap<String, PolicyFile>,
+
+    /// Ports given to `Security.loadPolicyFile` to request socket policies from, by host.
+    socket_policy_ports: HashMap<String, u16>,
+
+    /// Domains allowed by `Security.allowDomain`, by the URL of the movie that allowed them.
+    allowed_domains: HashMap<String, Vec<String>>,
+}
+
+impl SecuritySandbox {
+    pub fn new(strict: bool) -> Self {
+        Self {
+            strict,
+            ..Default::default()
+        }
+    }
+
+    /// Whether the sandbox rules are enforced.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Lets movies from `domain` script `movie`, as done by `Security.allowDomain`.
+    pub fn allow_domain(&mut self, movie: &SwfMovie, domain: String) {
+        self.allowed_domains
+            .entry(movie.url().to_string())
+            .or_default()
+            .push(domain);
+    }
+
+    /// Whether two movies come from the same domain.
+    pub fn is_same_domain(a: &SwfMovie, b: &SwfMovie) -> bool {
+        if a.sandbox_type().is_local() && b.sandbox_type().is_local() {
+            return a.sandbox_type() == b.sandbox_type();
+        }
+        same_origin(a.url(), b.url())
+    }
+
+    /// Whether `accessor` may script `target`, or draw it into a `BitmapData`.
+    pub fn can_access_movie(&self, accessor: &SwfMovie, target: &SwfMovie) -> bool {
+        if accessor.sandbox_type().is_trusted()
+            || accessor.url() == target.url()
+            || Self::is_same_domain(accessor, target)
+        {
+            return true;
+        }
+        let domain = movie_domain(accessor);
+        self.allowed_domains
+            .get(target.url())
+            .is_some_and(|domains| {
+                domains
+                    .iter()
+                    .any(|pattern| domain_matches(pattern, &domain))
+            })
+    }
+
+    /// Whether `accessor` may access loaded content, when the sandbox is enforced.
+    ///
+    /// Movies need to be allowed with `allowDomain`; other media need a policy file.
+    pub fn can_access_content(&self, accessor: &SwfMovie, target: &SwfMovie) -> bool {
+        if !self.strict {
+            return true;
+        }
+        if target.is_movie() {
+            return self.can_access_movie(accessor, target);
+        }
+        Url::parse(target.url()).is_ok_and(|url| self.url_access(accessor, &url) == Access::Allowed)
+    }
+
+    /// Whether `accessor` may load data from `url`.
+    pub fn url_access(&self, accessor: &SwfMovie, url: &Url) -> Access {
+        let sandbox_type = accessor.sandbox_type();
+        if !self.strict || sandbox_type.is_trusted() {
+            return Access::Allowed;
+        }
+        match url.scheme() {
+            "file" => {
+                return if sandbox_type == SandboxType::LocalWithFile {
+                    Access::Allowed
+                } else {
+                    Access::Denied
+                };
+            }
+            "http" | "https" => {}
+            // Anything else can't be covered by a policy file.
+            _ if same_origin(accessor.url(), url.as_str()) => return Access::Allowed,
+            _ => return Access::Denied,
+        }
+        if sandbox_type == SandboxType::LocalWithFile {
+            return Access::Denied;
+        }
+        if same_origin(accessor.url(), url.as_str()) {
+            return Access::Allowed;
+        }
+
+        let Some(policies) = self.url_policies.get(&url.origin().ascii_serialization()) else {
+            return Access::RequiresPolicy;
+        };
+        let master = policies.iter().find(|policy| policy.directory == "/");
+        let Some(master) = master else {
+            return Access::RequiresPolicy;
+        };
+        if master.policy.permits_nothing() {
+            return Access::Denied;
+        }
+
+        let domain = movie_domain(accessor);
+        let allowed = if master.policy.permits_other_policies() {
+            policies.iter().any(|policy| {
+                url.path().starts_with(&policy.directory) && policy.policy.allows(&domain)
+            })
+        } else {
+            master.policy.allows(&domain)
+        };
+        if allowed {
+            Access::Allowed
+        } else {
+            Access::Denied
+        }
+    }
+
+    /// Whether `accessor` may open a socket to `host` on `port`.
+    pub fn socket_access(&self, accessor: &SwfMovie, host: &str, port: u16) -> Access {
+        let sandbox_type = accessor.sandbox_type();
+        if !self.strict || sandbox_type.is_trusted() {
+            return Access::Allowed;
+        }
+        if sandbox_type == SandboxType::LocalWithFile {
+            return Access::Denied;
+        }
+        match self.socket_policies.get(host) {
+            Some(policy) if policy.allows_socket(&movie_domain(accessor), port) => Access::Allowed,
+            Some(_) => Access::Denied,
+            None => Access::RequiresPolicy,
+        }
+    }
+
+    /// Adds a URL policy file loaded from `url`.
+    ///
+    /// A policy file that couldn't be loaded should still be added, as an
+    /// empty policy, so that it's not requested again.
+    pub fn add_url_policy(&mut self, url: &Url, policy: PolicyFile) {
+        let path = url.path();
+        let directory = path[..path.rfind('/').map_or(0, |slash| slash + 1)].to_string();
+        let policies = self
+            .url_policies
+            .entry(url.origin().ascii_serialization())
+            .or_default();
+        policies.retain(|policy| policy.directory != directory);
+        policies.push(UrlPolicy { directory, policy });
+    }
+
+    /// Adds a socket policy file served by `host`.
+    pub fn add_socket_policy(&mut self, host: String, policy: PolicyFile) {
+        self.socket_policies.insert(host, policy);
+    }
+
+    /// Requests socket policies for `host` from `port` instead of the default.
+    pub fn set_socket_policy_port(&mut self, host: String, port: u16) {
+        self.socket_policy_ports.insert(host, port);
+    }
+
+    fn socket_policy_port(&self, host: &str) -> u16 {
+        self.socket_policy_ports
+            .get(host)
+            .copied()
+            .unwrap_or(SOCKET_POLICY_PORT)
+    }
+}
+
+/// The URL of the master policy file of the site serving `url`.
+pub fn master_policy_url(url: &Url) -> Url {
+    let mut policy_url = url.clone();
+    policy_url.set_path("/crossdomain.xml");
+    policy_url.set_query(None);
+    policy_url.set_fragment(None);
+    policy_url
+}
+
+/// Loads a URL policy file into the sandbox of a player.
+pub async fn load_url_policy(player: Arc<Mutex<Player>>, url: Url) {
+    let fetch = player
+        .lock()
+        .unwrap()
+        .navigator()
+        .fetch(Request::get(url.to_string()));
+    let body = match fetch.await {
+        Ok(response) => response.body().await.ok(),
+        Err(_) => None,
+    };
+    let policy = match body.map(|body| PolicyFile::parse(&body)) {
+        Some(Ok(policy)) => policy,
+        Some(Err(e)) => {
+            tracing::warn!("Ignoring invalid policy file {url}: {e:?}");
+            PolicyFile::default()
+        }
+        None => {
+            tracing::info!("No policy file at {url}");
+            PolicyFile::default()
+        }
+    };
+    player
+        .lock()
+        .unwrap()
+        .security_sandbox_mut()
+        .add_url_policy(&url, policy);
+}
+
+/// Requests a socket policy file from a policy server and loads it into
+/// the sandbox of a player.
+pub async fn load_socket_policy(player: Arc<Mutex<Player>>, host: String, port: u16) {
+    let (sender, receiver) = async_channel::unbounded();
+    let (action_sender, action_receiver) = async_channel::unbounded();
+    player.lock().unwrap().navigator_mut().connect_socket(
+        host.clone(),
+        port,
+        SOCKET_POLICY_TIMEOUT,
+        SocketHandle::default(),
+        receiver,
+        action_sender,
+    );
+
+    // The policy is a null-terminated XML document, sent after a null-terminated request.
+    let mut response = Vec::new();
+    while let Ok(action) = action_receiver.recv().await {
+        match action {
+            SocketAction::Connect(_, ConnectionState::Connected) => {
+                let _ = sender.try_send(b"<policy-file-request/>\0".to_vec());
+            }
+            SocketAction::Connect(_, _) | SocketAction::Close(_) => break,
+            SocketAction::Data(_, data) => {
+                response.extend(data);
+                if let Some(end) = response.iter().position(|&b| b == 0) {
+                    response.truncate(end);
+                    break;
+                }
+            }
+        }
+    }
+    // Closes the connection.
+    drop(sender);
+
+    let policy = PolicyFile::parse(&response).unwrap_or_else(|e| {
+        tracing::warn!("No valid socket policy from {host}:{port}: {e:?}");
+        PolicyFile::default()
+    });
+    player
+        .lock()
+        .unwrap()
+        .security_sandbox_mut()
+        .add_socket_policy(host, policy);
+}
+
+/// Waits until the sandbox of a player decides whether `movie` may load data from `url`.
+pub async fn request_url_access(player: &Arc<Mutex<Player>>, movie: &SwfMovie, url: &Url) -> bool {
+    let access = player
+        .lock()
+        .unwrap()
+        .security_sandbox()
+        .url_access(movie, url);
+    let access = match access {
+        Access::RequiresPolicy => {
+            load_url_policy(player.clone(), master_policy_url(url)).await;
+            player
+                .lock()
+                .unwrap()
+                .security_sandbox()
+                .url_access(movie, url)
+        }
+        access => access,
+    };
+    access == Access::Allowed
+}
+
+/// Like [`request_url_access`], but for a URL which still has to be resolved
+/// by the navigator.
+///
+/// URLs that can't be resolved are never allowed.
+pub async fn request_unresolved_url_access(
+    player: &Arc<Mutex<Player>>,
+    movie: &SwfMovie,
+    url: &str,
+) -> bool {
+    let resolved = player.lock().unwrap().navigator().resolve_url(url);
+    match resolved {
+        Ok(url) => request_url_access(player, movie, &url).await,
+        Err(e) => {
+            tracing::warn!("Security sandbox denied access to unresolvable URL {url}: {e}");
+            false
+        }
+    }
+}
+
+/// Fetches `request` once the sandbox of a player allows `movie` to load data
+/// from its URL.
+///
+/// Denied requests are never made, and fail with [`Error::SecuritySandboxViolation`].
+pub fn sandboxed_fetch(
+    player: Arc<Mutex<Player>>,
+    movie: Arc<SwfMovie>,
+    request: Request,
+) -> OwnedFuture<Box<dyn SuccessResponse>, ErrorResponse> {
+    Box::pin(async move {
+        if !request_unresolved_url_access(&player, &movie, request.url()).await {
+            let url = request.url().to_string();
+            return Err(ErrorResponse {
+                error: Error::SecuritySandboxViolation(movie.url().to_string(), url.clone()),
+                url,
+            });
+        }
+        let fetch = player.lock().unwrap().navigator().fetch(request);
+        fetch.await
+    })
+}
+
+/// Waits until the sandbox of a player decides whether `movie` may open
+/// a socket to `host` on `port`.
+pub async fn request_socket_access(
+    player: &Arc<Mutex<Player>>,
+    movie: &SwfMovie,
+    host: &str,
+    port: u16,
+) -> bool {
+    let (access, policy_port) = {
+        let player = player.lock().unwrap();
+        let sandbox = player.security_sandbox();
+        (
+            sandbox.socket_access(movie, host, port),
+            sandbox.socket_policy_port(host),
+        )
+    };
+    let access = match access {
+        Access::RequiresPolicy => {
+            load_socket_policy(player.clone(), host.to_string(), policy_port).await;
+            player
+                .lock()
+                .unwrap()
+                .security_sandbox()
+                .socket_access(movie, host, port)
+        }
+        access => access,
+    };
+    access == Access::Allowed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &[u8] = br#"<?xml version="1.0"?>
+<!DOCTYPE cross-domain-policy SYSTEM "http://www.adobe.com/xml/dtds/cross-domain-policy.dtd">
+<cross-domain-policy>
+    <site-control permitted-cross-domain-policies="master-only"/>
+    <allow-access-from domain="*.example.com"/>
+    <allow-access-from domain="games.example.org" to-ports="507,1000-1010"/>
+</cross-domain-policy>"#;
+
+    fn movie(url: &str) -> SwfMovie {
+        SwfMovie::from_loaded_image(url.to_string(), 0)
+    }
+
+    #[test]
+    fn parse_policy_file() {
+        let policy = PolicyFile::parse(POLICY).unwrap();
+        assert!(policy.allows("example.com"));
+        assert!(policy.allows("www.example.com"));
+        assert!(!policy.allows("badexample.com"));
+        assert!(policy.allows("games.example.org"));
+        assert!(policy.allows_socket("games.example.org", 1005));
+        assert!(!policy.allows_socket("games.example.org", 1011));
+        assert!(!policy.allows_socket("www.example.com", 507));
+        assert!(!policy.permits_other_policies());
+    }
+
+    #[test]
+    fn parse_invalid_policy_file() {
+        assert_eq!(
+            PolicyFile::parse(b"<html><body>Not found</body></html>"),
+            Err(PolicyFileError::NotAPolicyFile)
+        );
+        assert_eq!(PolicyFile::parse(b""), Err(PolicyFileError::NotAPolicyFile));
+    }
+
+    #[test]
+    fn match_domains() {
+        assert!(domain_matches("*", ""));
+        assert!(domain_matches("Example.com", "example.COM"));
+        assert!(domain_matches("*.example.com", "a.b.example.com"));
+        assert!(!domain_matches("*.example.com", "example.org"));
+        assert!(!domain_matches("example.com", "www.example.com"));
+    }
+
+    #[test]
+    fn url_access_is_only_checked_when_strict() {
+        let accessor = movie("https://games.example.org/game.swf");
+        let url = Url::parse("https://cdn.example.net/level.xml").unwrap();
+        assert_eq!(
+            SecuritySandbox::new(false).url_access(&accessor, &url),
+            Access::Allowed
+        );
+
+        let mut sandbox = SecuritySandbox::new(true);
+        assert_eq!(sandbox.url_access(&accessor, &url), Access::RequiresPolicy);
+        let same_origin = Url::parse("https://games.example.org/data/level.xml").unwrap();
+        assert_eq!(sandbox.url_access(&accessor, &same_origin), Access::Allowed);
+
+        sandbox.add_url_policy(&master_policy_url(&url), PolicyFile::parse(POLICY).unwrap());
+        assert_eq!(sandbox.url_access(&accessor, &url), Access::Allowed);
+
+        sandbox.add_url_policy(&master_policy_url(&url), PolicyFile::default());
+        assert_eq!(sandbox.url_access(&accessor, &url), Access::Denied);
+    }
+
+    #[test]
+    fn allow_domain() {
+        let parent = movie("https://www.example.com/main.swf");
+        let child = movie("https://cdn.example.net/child.swf");
+        let mut sandbox = SecuritySandbox::new(true);
+        assert!(!sandbox.can_access_movie(&parent, &child));
+        sandbox.allow_domain(&child, "*.example.com".to_string());
+        assert!(sandbox.can_access_movie(&parent, &child));
+        assert!(!sandbox.can_access_movie(&child, &parent));
+    }
 }
@@ -6,7 +6,10 @@ use crate::avm2::object::{EventObject, SocketObject};
 use crate::avm2::{Activation as Avm2Activation, Avm2};
 use crate::backend::navigator::NavigatorBackend;
 use crate::context::UpdateContext;
+use crate::player::Player;
+use crate::sandbox;
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 
 use async_channel::{unbounded, Receiver, Sender as AsyncSender, Sender};
 use gc_arena::collect::Trace;
@@ -15,6 +18,7 @@ use ruffle_macros::istr;
 use slotmap::{new_key_type, SlotMap};
 use std::{
     cell::{Cell, RefCell},
+    sync::{Arc, Mutex, Weak},
     time::Duration,
 };
 
@@ -61,6 +65,14 @@ pub enum SocketAction {
     Close(SocketHandle),
 }
 
+/// A connection which has to wait for the security sandbox to allow it.
+pub struct SandboxedConnection {
+    pub player: Weak<Mutex<Player>>,
+
+    /// The movie opening the connection.
+    pub movie: Arc<SwfMovie>,
+}
+
 /// Manages the collection of Sockets.
 pub struct Sockets<'gc> {
     sockets: SlotMap<SocketHandle, Socket<'gc>>,
@@ -94,20 +106,21 @@ impl<'gc> Sockets<'gc> {
         target: SocketObject<'gc>,
         host: String,
         port: u16,
+        sandboxed: Option<SandboxedConnection>,
     ) {
         let (sender, receiver) = unbounded();
 
         let socket = Socket::new(SocketKind::Avm2(target), sender);
         let handle = self.sockets.insert(socket);
 
-        // NOTE: This call will send SocketAction::Connect to sender with connection status.
-        backend.connect_socket(
+        self.connect(
+            backend,
+            handle,
             sanitize_host(&host).to_string(),
             port,
             Duration::from_millis(target.timeout().into()),
-            handle,
             receiver,
-            self.sender.clone(),
+            sandboxed,
         );
 
         if let Some(existing_handle) = target.set_handle(handle) {
@@ -123,6 +136,7 @@ impl<'gc> Sockets<'gc> {
         target: Avm1Object<'gc>,
         host: String,
         port: u16,
+        sandboxed: Option<SandboxedConnection>,
     ) {
         let (sender, receiver) = unbounded();
 
@@ -134,14 +148,14 @@ impl<'gc> Sockets<'gc> {
         let socket = Socket::new(SocketKind::Avm1(target), sender);
         let handle = self.sockets.insert(socket);
 
-        // NOTE: This call will send SocketAction::Connect to sender with connection status.
-        backend.connect_socket(
+        self.connect(
+            backend,
+            handle,
             sanitize_host(&host).to_string(),
             port,
             Duration::from_millis(xml_socket.timeout().into()),
-            handle,
             receiver,
-            self.sender.clone(),
+            sandboxed,
         );
 
         if let Some(existing_handle) = xml_socket.set_handle(handle) {
@@ -151,6 +165,44 @@ impl<'gc> Sockets<'gc> {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn connect(
+        &self,
+        backend: &mut dyn NavigatorBackend,
+        handle: SocketHandle,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        receiver: Receiver<Vec<u8>>,
+        sandboxed: Option<SandboxedConnection>,
+    ) {
+        let sender = self.sender.clone();
+        let Some((player, movie)) =
+            sandboxed.and_then(|sandboxed| Some((sandboxed.player.upgrade()?, sandboxed.movie)))
+        else {
+            // NOTE: This call will send SocketAction::Connect to sender with connection status.
+            backend.connect_socket(host, port, timeout, handle, receiver, sender);
+            return;
+        };
+
+        // The socket policy has to be checked first, which may need to be requested from the host.
+        backend.spawn_future(Box::pin(async move {
+            if sandbox::request_socket_access(&player, &movie, &host, port).await {
+                player
+                    .lock()
+                    .unwrap()
+                    .navigator_mut()
+                    .connect_socket(host, port, timeout, handle, receiver, sender);
+            } else {
+                player
+                    .lock()
+                    .unwrap()
+                    .update(|context| deny_connection(context, handle, &movie, &host, port));
+            }
+            Ok(())
+        }));
+    }
+
     pub fn is_connected(&self, handle: SocketHandle) -> bool {
         if let Some(socket) = self.sockets.get(handle) {
             socket.connected.get()
@@ -415,6 +467,48 @@ impl<'gc> Sockets<'gc> {
     }
 }
 
+/// Fails a connection that isn't allowed by the security sandbox.
+fn deny_connection<'gc>(
+    context: &mut UpdateContext<'gc>,
+    handle: SocketHandle,
+    movie: &SwfMovie,
+    host: &str,
+    port: u16,
+) {
+    let Some(socket) = context.sockets.sockets.remove(handle) else {
+        // The socket was closed in the meantime.
+        return;
+    };
+    tracing::warn!(
+        "Security sandbox violation: {} cannot connect to {host}:{port}",
+        movie.url()
+    );
+
+    match socket.target {
+        SocketKind::Avm2(target) => {
+            let mut activation = Avm2Activation::from_nothing(context);
+            let message = format!(
+                "Error #2048: Security sandbox violation: {} cannot load data from {host}:{port}.",
+                movie.url()
+            );
+            let security_error_evt =
+                EventObject::security_error_event(&mut activation, &message, 2048);
+            Avm2::dispatch_event(activation.context, security_error_evt, target.into());
+        }
+        SocketKind::Avm1(target) => {
+            let mut activation =
+                Avm1Activation::from_stub(context, ActivationIdentifier::root("[XMLSocket]"));
+
+            let _ = target.call_method(
+                istr!("onConnect"),
+                &[false.into()],
+                &mut activation,
+                ExecutionReason::Special,
+            );
+        }
+    }
+}
+
 /// Flash treats a socket host as a cstring, and stops reading at a null byte.
 /// We need to account for this here.
 fn sanitize_host(host: &str) -> &str {
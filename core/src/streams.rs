@@ -1,13 +1,15 @@
 //! NetStream implementation
 
+mod mp4;
+
 use crate::avm1::{
     Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
     ExecutionReason as Avm1ExecutionReason, FlvValueAvm1Ext, Object as Avm1Object,
     Value as Avm1Value,
 };
 use crate::avm2::{
-    Activation as Avm2Activation, Avm2, Error as Avm2Error, EventObject as Avm2EventObject,
-    FlvValueAvm2Ext, Object as Avm2Object, Value as Avm2Value,
+    Activation as Avm2Activation, Avm2, Domain as Avm2Domain, Error as Avm2Error,
+    EventObject as Avm2EventObject, FlvValueAvm2Ext, Object as Avm2Object, Value as Avm2Value,
 };
 use crate::backend::audio::{
     DecodeError, SoundInstanceHandle, SoundStreamInfo, SoundStreamWrapping,
@@ -17,6 +19,8 @@ use crate::buffer::{Buffer, Slice, Substream, SubstreamError};
 use crate::context::UpdateContext;
 use crate::display_object::{MovieClip, TDisplayObject};
 use crate::loader::Error;
+use crate::net_connection::{NetConnectionObject, NetConnections};
+use crate::streams::mp4::{Mp4Codec, Mp4Error, Mp4Media, Mp4Movie, Mp4Track};
 use crate::string::AvmString;
 use crate::vminterface::AvmObject;
 use flv_rs::{
@@ -24,7 +28,7 @@ use flv_rs::{
     FrameType as FlvFrameType, Header as FlvHeader, ScriptData as FlvScriptData,
     SoundFormat as FlvSoundFormat, SoundRate as FlvSoundRate, SoundSize as FlvSoundSize,
     SoundType as FlvSoundType, Tag as FlvTag, TagData as FlvTagData, Value as FlvValue,
-    VideoData as FlvVideoData, VideoPacket as FlvVideoPacket,
+    Variable as FlvVariable, VideoData as FlvVideoData, VideoPacket as FlvVideoPacket,
 };
 use gc_arena::barrier::unlock;
 use gc_arena::{Collect, Gc, Lock, Mutation};
@@ -34,7 +38,9 @@ use ruffle_video::frame::EncodedFrame;
 use ruffle_video::VideoStreamHandle;
 use std::cell::{Cell, RefCell};
 use std::cmp::max;
+use std::collections::VecDeque;
 use std::io::{Seek, SeekFrom};
+use std::rc::Rc;
 use swf::{AudioCompression, SoundFormat, VideoCodec, VideoDeblocking};
 use thiserror::Error;
 use url::Url;
@@ -120,6 +126,22 @@ impl<'gc> StreamManager<'gc> {
         }
     }
 
+    /// Close every active stream that was started by AVM2 code from `domain`
+    /// or one of its child domains.
+    pub fn close_streams_in_domain(context: &mut UpdateContext<'gc>, domain: Avm2Domain<'gc>) {
+        let streams = context.stream_manager.active_streams.clone();
+        for stream in streams {
+            if stream
+                .0
+                .avm2_domain
+                .get()
+                .is_some_and(|owner| owner.is_within(domain))
+            {
+                stream.close(context);
+            }
+        }
+    }
+
     /// Process all active media streams.
     ///
     /// This is an unlocked timestep; the `dt` parameter indicates how many
@@ -161,6 +183,131 @@ impl PartialEq for NetStream<'_> {
 
 impl Eq for NetStream<'_> {}
 
+/// An action for a stream in data generation mode, which corresponds to
+/// `NetStreamAppendBytesAction`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppendBytesAction {
+    /// Discard all data and expect a new FLV header.
+    ResetBegin,
+
+    /// Discard all data and expect FLV tags following a seek.
+    ResetSeek,
+
+    /// No more data will be appended.
+    EndSequence,
+}
+
+/// The kind of media carried by a tag or sample of a stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MediaKind {
+    Audio,
+    Video,
+    Data,
+}
+
+impl MediaKind {
+    fn of_flv_tag(data: &FlvTagData<'_>) -> Self {
+        match data {
+            FlvTagData::Audio(_) => MediaKind::Audio,
+            FlvTagData::Video(_) => MediaKind::Video,
+            FlvTagData::Script(_) | FlvTagData::Invalid(_) => MediaKind::Data,
+        }
+    }
+}
+
+/// An amount of bytes (or bytes per second) for each kind of media.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MediaBytes {
+    pub audio: f64,
+    pub video: f64,
+    pub data: f64,
+}
+
+impl MediaBytes {
+    fn add(&mut self, kind: MediaKind, bytes: usize) {
+        let count = match kind {
+            MediaKind::Audio => &mut self.audio,
+            MediaKind::Video => &mut self.video,
+            MediaKind::Data => &mut self.data,
+        };
+        *count += bytes as f64;
+    }
+
+    pub fn total(&self) -> f64 {
+        self.audio + self.video + self.data
+    }
+}
+
+/// Statistics of the media in a stream buffer, which back `NetStream.info`
+/// and the buffering model.
+#[derive(Clone, Debug, Default)]
+struct StreamStats {
+    /// The buffer position of the first FLV tag.
+    first_tag_offset: usize,
+
+    /// The buffer position up to which FLV tags have been counted.
+    scan_offset: usize,
+
+    /// The indices of the next MP4 video and audio samples to be counted.
+    scanned_samples: (usize, usize),
+
+    /// The timestamp of the first media in the buffer, in milliseconds.
+    start_time: Option<f64>,
+
+    /// The timestamp up to which media has been downloaded, in milliseconds.
+    buffered_until: Option<f64>,
+
+    /// The bytes of media downloaded into the buffer.
+    buffered: MediaBytes,
+
+    /// The bytes of media that have been played.
+    played: MediaBytes,
+
+    /// The timestamps of the video frames decoded in the last second, in
+    /// milliseconds.
+    recent_frames: VecDeque<f64>,
+}
+
+impl StreamStats {
+    /// Count a newly downloaded tag or sample.
+    fn buffer_media(&mut self, kind: MediaKind, time: f64, bytes: usize) {
+        self.buffered.add(kind, bytes);
+        self.start_time.get_or_insert(time);
+        self.buffered_until = Some(self.buffered_until.map_or(time, |until| until.max(time)));
+    }
+
+    /// Record a decoded video frame.
+    fn record_frame(&mut self, time: f64) {
+        while self
+            .recent_frames
+            .front()
+            .is_some_and(|frame| *frame <= time - 1000.0)
+        {
+            self.recent_frames.pop_front();
+        }
+        self.recent_frames.push_back(time);
+    }
+}
+
+/// Playback statistics of a stream, as reported by `NetStream.info`.
+#[derive(Clone, Debug, Default)]
+pub struct NetStreamInfo {
+    /// The bytes of media downloaded so far.
+    pub byte_count: MediaBytes,
+
+    /// The bytes of media buffered ahead of the playhead.
+    pub buffer_byte_length: MediaBytes,
+
+    /// The seconds of media buffered ahead of the playhead.
+    pub buffer_length: f64,
+
+    /// The rate at which media has been played, in bytes per second.
+    pub bytes_per_second: MediaBytes,
+
+    /// The highest total rate seen so far, in bytes per second.
+    pub max_bytes_per_second: f64,
+}
+
 /// The current type of the data in the stream buffer.
 #[derive(Clone, Debug)]
 pub enum NetStreamType {
@@ -179,6 +326,21 @@ pub enum NetStreamType {
         /// frame IDs ourselves for various API related purposes.
         frame_id: u32,
     },
+
+    /// The stream is an MP4 or F4V.
+    Mp4 {
+        /// The parsed movie header, with the sample tables of every track.
+        movie: Rc<Mp4Movie>,
+
+        /// The currently playing video track's stream instance.
+        video_stream: Option<VideoStreamHandle>,
+
+        /// The index of the next video sample to decode.
+        video_sample: usize,
+
+        /// The index of the next audio sample to queue.
+        audio_sample: usize,
+    },
 }
 
 #[derive(Clone, Debug, Collect)]
@@ -225,6 +387,21 @@ pub struct NetStreamSource {
 
     /// The currently playing sound stream
     sound_instance: Cell<Option<SoundInstanceHandle>>,
+
+    /// Whether the stream time should jump to the timestamp of the next tag.
+    ///
+    /// Data appended after a reset starts a new timeline, which may begin at
+    /// any timestamp.
+    resync_time: Cell<bool>,
+
+    /// Statistics of the media downloaded to and played from this source.
+    stats: RefCell<StreamStats>,
+
+    /// True if playback is held until enough media has been buffered.
+    buffering: Cell<bool>,
+
+    /// The highest total playback rate seen so far, in bytes per second.
+    max_bytes_per_second: Cell<f64>,
 }
 
 #[derive(Clone, Debug, Collect)]
@@ -233,10 +410,15 @@ pub struct NetStreamData<'gc> {
     /// Stream source.
     source: Lock<Gc<'gc, NetStreamSource>>,
 
-    /// The number of seconds of video data that should be buffered. This is
-    /// currently unsupported and changing it has no effect.
+    /// The number of seconds of media that should be buffered before
+    /// playback starts or resumes.
     buffer_time: Cell<f64>,
 
+    /// The maximum number of seconds of media that a live stream may buffer.
+    ///
+    /// This is only reported back to scripts; we do not limit buffering.
+    buffer_time_max: Cell<f64>,
+
     /// The last decoded bitmap.
     ///
     /// Any `Video`s on the stage will display the bitmap here when attached to
@@ -249,6 +431,9 @@ pub struct NetStreamData<'gc> {
     /// The AVM2 client object, which corresponds to `NetStream.client`.
     avm2_client: Lock<Option<Avm2Object<'gc>>>,
 
+    /// The `NetConnection` this stream was created with.
+    connection: Lock<Option<NetConnectionObject<'gc>>>,
+
     /// The URL of the requested FLV if one exists.
     url: RefCell<Option<String>>,
 
@@ -257,6 +442,17 @@ pub struct NetStreamData<'gc> {
 
     /// True if the stream should play when ticked.
     playing: Cell<bool>,
+
+    /// True if the stream plays data appended with `appendBytes`, rather
+    /// than a file or a server stream.
+    data_generation: Cell<bool>,
+
+    /// True if a seek was made in data generation mode, and appended data
+    /// should be discarded until `RESET_SEEK`.
+    awaiting_reset_seek: Cell<bool>,
+
+    /// The domain of the AVM2 code that started playback, if any.
+    avm2_domain: Lock<Option<Avm2Domain<'gc>>>,
 }
 
 impl Default for NetStreamSource {
@@ -271,6 +467,10 @@ impl Default for NetStreamSource {
             queued_seek_time: Cell::new(None),
             audio_stream: RefCell::new(None),
             sound_instance: Cell::new(None),
+            resync_time: Cell::new(false),
+            stats: RefCell::new(Default::default()),
+            buffering: Cell::new(true),
+            max_bytes_per_second: Cell::new(0.0),
         }
     }
 }
@@ -284,12 +484,17 @@ impl<'gc> NetStream<'gc> {
             NetStreamData {
                 source: Lock::new(Gc::new(gc_context, Default::default())),
                 buffer_time: Cell::new(0.1),
+                buffer_time_max: Cell::new(0.0),
                 last_decoded_bitmap: RefCell::new(None),
                 avm_object: Lock::new(avm_object),
                 avm2_client: Lock::new(None),
+                connection: Lock::new(None),
                 url: RefCell::new(None),
                 attached_to: Lock::new(None),
                 playing: Cell::new(false),
+                data_generation: Cell::new(false),
+                awaiting_reset_seek: Cell::new(false),
+                avm2_domain: Lock::new(None),
             },
         ))
     }
@@ -306,10 +511,18 @@ impl<'gc> NetStream<'gc> {
         self.0.avm2_client.get()
     }
 
+    pub fn set_connection(self, gc_context: &Mutation<'gc>, connection: NetConnectionObject<'gc>) {
+        unlock!(Gc::write(gc_context, self.0), NetStreamData, connection).set(Some(connection));
+    }
+
     pub fn set_avm_object(self, gc_context: &Mutation<'gc>, avm_object: AvmObject<'gc>) {
         unlock!(Gc::write(gc_context, self.0), NetStreamData, avm_object).set(Some(avm_object));
     }
 
+    pub fn set_avm2_domain(self, gc_context: &Mutation<'gc>, domain: Option<Avm2Domain<'gc>>) {
+        unlock!(Gc::write(gc_context, self.0), NetStreamData, avm2_domain).set(domain);
+    }
+
     fn set_attached_to(self, gc_context: &Mutation<'gc>, attached_to: Option<MovieClip<'gc>>) {
         unlock!(Gc::write(gc_context, self.0), NetStreamData, attached_to).set(attached_to);
     }
@@ -317,7 +530,7 @@ impl<'gc> NetStream<'gc> {
     /// Reset the `NetStream` buffer to accept new source data.
     ///
     /// This must be done once per source change and should ideally be done
-    /// immediately before the first `load_buffer` call for a particular source
+    /// immediately before the first `append_buffer` call for a particular source
     /// file.
     ///
     /// Externally visible AVM state must not be reinitialized here - i.e. the
@@ -358,17 +571,70 @@ impl<'gc> NetStream<'gc> {
     ///
     /// Buffer loading can be done in chunks but must be done in such a way
     /// that all data is appended in the correct order and that data from
-    /// separate streams is not mixed together.
-    pub fn load_buffer(self, context: &mut UpdateContext<'gc>, data: &mut Vec<u8>) {
+    /// separate streams is not mixed together. Buffer status events are
+    /// reported at tick time, once enough media has been buffered.
+    pub fn append_buffer(self, context: &mut UpdateContext<'gc>, data: &mut Vec<u8>) {
         self.source().buffer.borrow_mut().append(data);
 
         StreamManager::activate(context, self);
+    }
 
-        // NOTE: The onMetaData event triggers before this event in Flash due to its streaming behavior.
-        self.trigger_status_event(
-            context,
-            vec![("code", "NetStream.Buffer.Full"), ("level", "status")],
-        );
+    /// Append data from `appendBytes` to a stream in data generation mode.
+    pub fn append_bytes(self, context: &mut UpdateContext<'gc>, data: &mut Vec<u8>) {
+        if !self.0.data_generation.get() {
+            tracing::warn!("NetStream.appendBytes called outside of data generation mode");
+            return;
+        }
+
+        // Data is dropped until the script tells us where it resumes.
+        if self.0.awaiting_reset_seek.get() {
+            return;
+        }
+
+        self.append_buffer(context, data);
+    }
+
+    /// Handle an `appendBytesAction` call on a stream in data generation mode.
+    pub fn append_bytes_action(self, context: &mut UpdateContext<'gc>, action: AppendBytesAction) {
+        if !self.0.data_generation.get() {
+            tracing::warn!("NetStream.appendBytesAction called outside of data generation mode");
+            return;
+        }
+
+        match action {
+            AppendBytesAction::ResetBegin => {
+                self.0.awaiting_reset_seek.set(false);
+                self.reset_appended_data(context, false);
+            }
+            AppendBytesAction::ResetSeek => {
+                self.0.awaiting_reset_seek.set(false);
+                self.reset_appended_data(context, true);
+            }
+            AppendBytesAction::EndSequence => self.finish_buffer(),
+        }
+    }
+
+    /// Discard all data appended in data generation mode.
+    ///
+    /// If `keep_header` is set, the stream type that was sniffed from the
+    /// last header is kept, and the next data is expected to start right at
+    /// a tag.
+    fn reset_appended_data(self, context: &mut UpdateContext<'gc>, keep_header: bool) {
+        let old_source = self.source();
+        let stream_type = if keep_header {
+            old_source.stream_type.borrow().clone()
+        } else {
+            None
+        };
+        let stream_time = old_source.stream_time.get();
+
+        self.reset_buffer(context);
+
+        let source = self.source();
+        source.stream_type.replace(stream_type);
+        source.stream_time.set(stream_time);
+        source.resync_time.set(true);
+        StreamManager::activate(context, self);
     }
 
     /// Indicate that the buffer has finished loading and that no further data
@@ -403,6 +669,199 @@ impl<'gc> NetStream<'gc> {
         self.0.buffer_time.set(buffer_time);
     }
 
+    pub fn buffer_time_max(self) -> f64 {
+        self.0.buffer_time_max.get()
+    }
+
+    pub fn set_buffer_time_max(self, buffer_time_max: f64) {
+        self.0.buffer_time_max.set(buffer_time_max);
+    }
+
+    /// The number of seconds of media buffered ahead of the playhead.
+    pub fn buffer_length(self) -> f64 {
+        let source = self.source();
+        let stats = source.stats.borrow();
+        let Some(buffered_until) = stats.buffered_until else {
+            return 0.0;
+        };
+
+        // Appended data starts a new timeline, which playback jumps to.
+        let playhead = match stats.start_time {
+            Some(start_time) if source.resync_time.get() => start_time,
+            _ => source.stream_time.get(),
+        };
+        (buffered_until - playhead).max(0.0) / 1000.0
+    }
+
+    /// The number of seconds of media kept in the buffer behind the playhead.
+    pub fn back_buffer_length(self) -> f64 {
+        let source = self.source();
+        match source.stats.borrow().start_time {
+            Some(start_time) if !source.resync_time.get() => {
+                (source.stream_time.get() - start_time).max(0.0) / 1000.0
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// The number of video frames decoded over the last second of playback.
+    pub fn current_fps(self) -> f64 {
+        let source = self.source();
+        let stream_time = source.stream_time.get();
+        source
+            .stats
+            .borrow()
+            .recent_frames
+            .iter()
+            .filter(|time| **time > stream_time - 1000.0 && **time <= stream_time)
+            .count() as f64
+    }
+
+    /// Gather the playback statistics of this stream.
+    pub fn info(self) -> NetStreamInfo {
+        let source = self.source();
+        let stats = source.stats.borrow();
+        let buffer_byte_length = MediaBytes {
+            audio: stats.buffered.audio - stats.played.audio,
+            video: stats.buffered.video - stats.played.video,
+            data: stats.buffered.data - stats.played.data,
+        };
+
+        let elapsed = match stats.start_time {
+            Some(start_time) => (source.stream_time.get() - start_time) / 1000.0,
+            None => 0.0,
+        };
+        let bytes_per_second = if elapsed > 0.0 {
+            MediaBytes {
+                audio: stats.played.audio / elapsed,
+                video: stats.played.video / elapsed,
+                data: stats.played.data / elapsed,
+            }
+        } else {
+            MediaBytes::default()
+        };
+        let max_bytes_per_second = source
+            .max_bytes_per_second
+            .get()
+            .max(bytes_per_second.total());
+        source.max_bytes_per_second.set(max_bytes_per_second);
+        let byte_count = stats.buffered;
+        drop(stats);
+
+        NetStreamInfo {
+            byte_count,
+            buffer_byte_length,
+            buffer_length: self.buffer_length(),
+            bytes_per_second,
+            max_bytes_per_second,
+        }
+    }
+
+    /// Count the media that was downloaded since the last tick.
+    ///
+    /// This tracks how much media is buffered ahead of the playhead.
+    fn scan_buffered_media(self, buffer: &[u8]) {
+        let source = self.source();
+        let mut stats = source.stats.borrow_mut();
+
+        match &*source.stream_type.borrow() {
+            Some(NetStreamType::Flv { .. }) => {
+                let mut scan_offset = stats.scan_offset;
+                for (kind, time, size, end) in Self::flv_tag_sizes(buffer, scan_offset) {
+                    stats.buffer_media(kind, time, size);
+                    scan_offset = end;
+                }
+                stats.scan_offset = scan_offset;
+            }
+            Some(NetStreamType::Mp4 { movie, .. }) => {
+                let (mut video_sample, mut audio_sample) = stats.scanned_samples;
+                let mut buffered_until = f64::INFINITY;
+                for (track, kind, next_sample) in [
+                    (movie.video_track(), MediaKind::Video, &mut video_sample),
+                    (movie.audio_track(), MediaKind::Audio, &mut audio_sample),
+                ] {
+                    let Some(track) = track else {
+                        continue;
+                    };
+                    while *next_sample < track.samples.len() {
+                        let Some(data) = Self::mp4_sample_data(track, *next_sample, buffer) else {
+                            break;
+                        };
+                        stats.buffer_media(kind, track.sample_time(*next_sample), data.len());
+                        *next_sample += 1;
+                    }
+                    let track_buffered_until = if *next_sample < track.samples.len() {
+                        track.sample_time(*next_sample)
+                    } else {
+                        track.duration()
+                    };
+                    buffered_until = buffered_until.min(track_buffered_until);
+                }
+                stats.scanned_samples = (video_sample, audio_sample);
+                if buffered_until.is_finite() {
+                    stats.start_time.get_or_insert(0.0);
+                    stats.buffered_until = Some(buffered_until);
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Recount the media played up to the current position after a seek.
+    fn recount_played_media(self, buffer: &[u8]) {
+        let source = self.source();
+        let mut stats = source.stats.borrow_mut();
+        let mut played = MediaBytes::default();
+
+        match &*source.stream_type.borrow() {
+            Some(NetStreamType::Flv { .. }) => {
+                for (kind, _, size, end) in Self::flv_tag_sizes(buffer, stats.first_tag_offset) {
+                    if end > source.offset.get() {
+                        break;
+                    }
+                    played.add(kind, size);
+                }
+            }
+            Some(NetStreamType::Mp4 {
+                movie,
+                video_sample,
+                audio_sample,
+                ..
+            }) => {
+                for (track, kind, next_sample) in [
+                    (movie.video_track(), MediaKind::Video, *video_sample),
+                    (movie.audio_track(), MediaKind::Audio, *audio_sample),
+                ] {
+                    for sample in track.iter().flat_map(|track| &track.samples[..next_sample]) {
+                        played.add(kind, sample.size as usize);
+                    }
+                }
+            }
+            None => {}
+        }
+
+        stats.played = played;
+        stats.recent_frames.clear();
+    }
+
+    /// Iterate over the complete FLV tags in a buffer, starting at `offset`.
+    ///
+    /// This yields the kind of media, timestamp and size of each tag, and the
+    /// buffer position just past it.
+    fn flv_tag_sizes(
+        buffer: &[u8],
+        offset: usize,
+    ) -> impl Iterator<Item = (MediaKind, f64, usize, usize)> + '_ {
+        let mut reader = FlvReader::from_parts(buffer, offset);
+        std::iter::from_fn(move || {
+            let start = reader.stream_position().ok()? as usize;
+            let tag = FlvTag::parse(&mut reader).ok()?;
+            let end = reader.stream_position().ok()? as usize;
+            let kind = MediaKind::of_flv_tag(&tag.data);
+            Some((kind, tag.timestamp as f64, end - start, end))
+        })
+    }
+
     /// Queue a seek to be executed on the next frame tick.
     ///
     /// `offset` is in milliseconds.
@@ -440,6 +899,20 @@ impl<'gc> NetStream<'gc> {
     /// This function should be run during stream ticks and *not* called by AVM
     /// code to service seek requests.
     pub fn execute_seek(self, context: &mut UpdateContext<'gc>, offset: f64) {
+        if self.0.data_generation.get() {
+            // The script provides the data to seek to. The buffer is flushed
+            // before notifying it, as it will append the new data (after a
+            // `RESET_SEEK`) in the event handler.
+            self.reset_appended_data(context, true);
+            self.source().stream_time.set(offset);
+            self.0.awaiting_reset_seek.set(true);
+            self.trigger_status_event(
+                context,
+                vec![("code", "NetStream.Seek.Notify"), ("level", "status")],
+            );
+            return;
+        }
+
         self.trigger_status_event(
             context,
             vec![("code", "NetStream.Seek.Notify"), ("level", "status")],
@@ -541,6 +1014,31 @@ impl<'gc> NetStream<'gc> {
             source.offset.set(offset);
         }
 
+        if let Some(NetStreamType::Mp4 {
+            movie,
+            video_sample,
+            audio_sample,
+            ..
+        }) = &mut *source.stream_type.borrow_mut()
+        {
+            // Video decoding has to restart from a sync sample; audio picks up
+            // from wherever the video ends up.
+            let stream_time = match movie.video_track() {
+                Some(track) => {
+                    *video_sample = track.sync_sample_before(offset);
+                    track.sample_time(*video_sample)
+                }
+                None => offset,
+            };
+            if let Some(track) = movie.audio_track() {
+                *audio_sample = track.sample_at(stream_time);
+            }
+            source.stream_time.set(stream_time);
+        }
+
+        let slice = source.buffer.borrow().to_full_slice();
+        self.recount_played_media(&slice.data());
+
         if let Some(AvmObject::Avm2(_)) = self.0.avm_object.get() {
             self.trigger_status_event(
                 context,
@@ -556,9 +1054,28 @@ impl<'gc> NetStream<'gc> {
     /// Start playing media from this NetStream.
     ///
     /// If `name` is specified, this will also trigger streaming download of
-    /// the given resource. Otherwise, the stream will play whatever data is
-    /// available in the buffer.
+    /// the given resource. Otherwise, the stream enters data generation mode
+    /// and plays whatever data is appended with `appendBytes`.
     pub fn play(self, context: &mut UpdateContext<'gc>, name: Option<AvmString<'gc>>) {
+        // `play(null)` puts the stream into data generation mode.
+        self.0.data_generation.set(name.is_none());
+        self.0.awaiting_reset_seek.set(false);
+
+        let streaming_connection = self
+            .0
+            .connection
+            .get()
+            .and_then(|connection| connection.handle())
+            .filter(|handle| context.net_connections.is_streaming(*handle));
+        if let (Some(name), Some(handle)) = (name, streaming_connection) {
+            // The server sends the media, along with the status events.
+            self.0.url.replace(None);
+            NetConnections::play_stream(context, handle, self, name.to_string());
+            self.0.playing.set(true);
+            StreamManager::activate(context, self);
+            return;
+        }
+
         if let Some(name) = name {
             let request = if let Ok(stream_url) =
                 Url::parse(context.swf.url()).and_then(|url| url.join(name.to_string().as_str()))
@@ -569,11 +1086,22 @@ impl<'gc> NetStream<'gc> {
             };
             self.0.url.replace(Some(request.url().to_string()));
             self.source().preload_offset.set(0);
-            let future = context
-                .load_manager
-                .load_netstream(context.player.clone(), self, request);
+            let sandboxed_movie = context
+                .security_sandbox
+                .is_strict()
+                .then(|| context.swf.clone());
+            let future = context.load_manager.load_netstream(
+                context.player.clone(),
+                self,
+                request,
+                sandboxed_movie,
+            );
 
             context.navigator.spawn_future(future);
+        } else {
+            self.0.url.replace(None);
+            self.reset_buffer(context);
+            self.source().resync_time.set(true);
         }
 
         self.0.playing.set(true);
@@ -585,6 +1113,14 @@ impl<'gc> NetStream<'gc> {
         );
     }
 
+    /// Stop playback and discard all stream data.
+    pub fn close(self, context: &mut UpdateContext<'gc>) {
+        self.0.playing.set(false);
+        self.0.url.replace(None);
+        self.reset_buffer(context);
+        StreamManager::deactivate(context, self);
+    }
+
     /// Pause stream playback.
     pub fn pause(self, context: &mut UpdateContext<'gc>, notify: bool) {
         // NOTE: We do not deactivate the stream here as there may be other
@@ -812,6 +1348,24 @@ impl<'gc> NetStream<'gc> {
             return false;
         }
 
+        if Mp4Movie::sniff(&buffer) {
+            return match Mp4Movie::parse(&buffer) {
+                Ok(movie) => {
+                    drop(buffer);
+                    self.start_mp4(context, movie);
+                    true
+                }
+                // The movie header may be at the end of the file.
+                Err(Mp4Error::EndOfData) => false,
+                Err(e) => {
+                    //TODO: Fire an error event to AS & stop playing too
+                    tracing::error!("MP4 movie header parsing failed: {}", e);
+                    source.preload_offset.set(8);
+                    false
+                }
+            };
+        }
+
         match buffer.get(0..3) {
             Some([0x46, 0x4C, 0x56]) => {
                 let mut reader = FlvReader::from_parts(&buffer, source.offset.get());
@@ -819,6 +1373,11 @@ impl<'gc> NetStream<'gc> {
                     Ok(header) => {
                         source.offset.set(reader.into_parts().1);
                         source.preload_offset.set(source.offset.get());
+
+                        let mut stats = source.stats.borrow_mut();
+                        stats.first_tag_offset = source.offset.get();
+                        stats.scan_offset = source.offset.get();
+                        drop(stats);
                         source.stream_type.replace(Some(NetStreamType::Flv {
                             header,
                             video_stream: None,
@@ -835,6 +1394,8 @@ impl<'gc> NetStream<'gc> {
                     }
                 }
             }
+            // Not enough data to tell if this is an MP4 yet.
+            Some(_) if buffer.len() < 8 => false,
             Some(magic) => {
                 //Unrecognized signature
                 //TODO: Fire an error event to AS & stop playing too
@@ -863,6 +1424,350 @@ impl<'gc> NetStream<'gc> {
         }
     }
 
+    /// Set up playback of an MP4 once its movie header has been parsed.
+    ///
+    /// This registers the video track with the video backend and reports the
+    /// movie's metadata, like Flash does for F4V files.
+    fn start_mp4(self, context: &mut UpdateContext<'gc>, movie: Mp4Movie) {
+        let source = self.source();
+
+        let mut video_stream = None;
+        if let Some(track) = movie.video_track() {
+            match (&track.media, &track.codec) {
+                (Mp4Media::Video { width, height }, Mp4Codec::Avc { config }) => {
+                    match context.video.register_video_stream(
+                        track.samples.len() as u32,
+                        (*width, *height),
+                        VideoCodec::H264,
+                        VideoDeblocking::UseVideoPacketValue,
+                    ) {
+                        Ok(handle) => {
+                            if let Err(e) =
+                                context.video.configure_video_stream_decoder(handle, config)
+                            {
+                                tracing::error!("Configuring MP4 video decoder failed: {}", e);
+                            }
+                            video_stream = Some(handle);
+                        }
+                        Err(e) => {
+                            tracing::error!("Got error when registering MP4 video stream: {}", e)
+                        }
+                    }
+                }
+                (_, codec) => tracing::error!("Unsupported MP4 video codec {:?}", codec),
+            }
+        }
+
+        let movie = Rc::new(movie);
+        source.stream_type.replace(Some(NetStreamType::Mp4 {
+            movie: movie.clone(),
+            video_stream,
+            video_sample: 0,
+            audio_sample: 0,
+        }));
+
+        let avm_object = self.0.avm_object.get();
+        let _ = self.handle_script_data(
+            avm_object,
+            context,
+            b"onMetaData",
+            Self::mp4_metadata(&movie),
+        );
+    }
+
+    /// Build the `onMetaData` object of an MP4.
+    fn mp4_metadata(movie: &Mp4Movie) -> FlvValue<'_> {
+        let mut metadata = vec![
+            FlvVariable {
+                name: b"duration",
+                data: FlvValue::Number(movie.duration() / 1000.0),
+            },
+            FlvVariable {
+                name: b"moovposition",
+                data: FlvValue::Number(movie.moov_position as f64),
+            },
+        ];
+
+        if let Some(track) = movie.video_track() {
+            if let Mp4Media::Video { width, height } = track.media {
+                metadata.push(FlvVariable {
+                    name: b"width",
+                    data: FlvValue::Number(width.into()),
+                });
+                metadata.push(FlvVariable {
+                    name: b"height",
+                    data: FlvValue::Number(height.into()),
+                });
+            }
+            metadata.push(FlvVariable {
+                name: b"videocodecid",
+                data: FlvValue::String(Self::mp4_codec_id(track)),
+            });
+            metadata.push(FlvVariable {
+                name: b"framerate",
+                data: FlvValue::Number(track.frame_rate()),
+            });
+        }
+
+        if let Some(track) = movie.audio_track() {
+            if let Mp4Media::Audio {
+                sample_rate,
+                channels,
+                ..
+            } = track.media
+            {
+                metadata.push(FlvVariable {
+                    name: b"audiosamplerate",
+                    data: FlvValue::Number(sample_rate.into()),
+                });
+                metadata.push(FlvVariable {
+                    name: b"audiochannels",
+                    data: FlvValue::Number(channels.into()),
+                });
+            }
+            metadata.push(FlvVariable {
+                name: b"audiocodecid",
+                data: FlvValue::String(Self::mp4_codec_id(track)),
+            });
+        }
+
+        let track_info = movie
+            .tracks
+            .iter()
+            .map(|track| {
+                FlvValue::Object(vec![
+                    FlvVariable {
+                        name: b"id",
+                        data: FlvValue::Number(track.id.into()),
+                    },
+                    FlvVariable {
+                        name: b"timescale",
+                        data: FlvValue::Number(track.timescale.into()),
+                    },
+                    FlvVariable {
+                        name: b"length",
+                        data: FlvValue::Number(track.duration as f64),
+                    },
+                ])
+            })
+            .collect();
+        metadata.push(FlvVariable {
+            name: b"trackinfo",
+            data: FlvValue::StrictArray(track_info),
+        });
+
+        FlvValue::EcmaArray(metadata)
+    }
+
+    /// The codec name Flash reports in the metadata of an MP4 track.
+    fn mp4_codec_id(track: &Mp4Track) -> &[u8] {
+        match &track.codec {
+            Mp4Codec::Avc { .. } => b"avc1",
+            Mp4Codec::Aac { .. } => b"mp4a",
+            Mp4Codec::Mp3 => b".mp3",
+            Mp4Codec::Unsupported(name) => name,
+        }
+    }
+
+    /// Process the MP4 samples that are due by `max_time`.
+    ///
+    /// Returns whether we ran out of downloaded samples, and whether every
+    /// sample of the movie has been played.
+    fn mp4_samples(
+        self,
+        context: &mut UpdateContext<'gc>,
+        buffer: &[u8],
+        max_time: f64,
+    ) -> (bool, bool) {
+        let source = self.source();
+        let (movie, video_stream, mut video_sample, mut audio_sample) =
+            match &*source.stream_type.borrow() {
+                Some(NetStreamType::Mp4 {
+                    movie,
+                    video_stream,
+                    video_sample,
+                    audio_sample,
+                }) => (movie.clone(), *video_stream, *video_sample, *audio_sample),
+                _ => unreachable!(),
+            };
+        let mut buffer_underrun = false;
+
+        if let Some(track) = movie.video_track() {
+            while video_sample < track.samples.len() && track.sample_time(video_sample) < max_time {
+                let Some(data) = Self::mp4_sample_data(track, video_sample, buffer) else {
+                    buffer_underrun = true;
+                    break;
+                };
+                if let Some(video_handle) = video_stream {
+                    self.mp4_video_sample(context, video_handle, data, video_sample as u32);
+                }
+                let mut stats = source.stats.borrow_mut();
+                stats.played.add(MediaKind::Video, data.len());
+                stats.record_frame(track.sample_time(video_sample));
+                drop(stats);
+                video_sample += 1;
+            }
+        }
+
+        if let Some(track) = movie.audio_track() {
+            // Like with FLVs, queue a few samples ahead to avoid audio underruns.
+            let mut max_lookahead_audio_samples = 5;
+            while audio_sample < track.samples.len() {
+                let is_lookahead_sample = track.sample_time(audio_sample) >= max_time;
+                if is_lookahead_sample {
+                    if max_lookahead_audio_samples == 0 {
+                        break;
+                    }
+                    max_lookahead_audio_samples -= 1;
+                }
+
+                let Some(data) = Self::mp4_sample_data(track, audio_sample, buffer) else {
+                    buffer_underrun |= !is_lookahead_sample;
+                    break;
+                };
+                if let Err(e) = self.mp4_audio_sample(track, data) {
+                    //TODO: Fire an error event at AS.
+                    tracing::error!("Error queueing MP4 audio sample: {}", e);
+                    break;
+                }
+                source
+                    .stats
+                    .borrow_mut()
+                    .played
+                    .add(MediaKind::Audio, data.len());
+                audio_sample += 1;
+            }
+        }
+
+        if let Some(NetStreamType::Mp4 {
+            video_sample: next_video_sample,
+            audio_sample: next_audio_sample,
+            ..
+        }) = &mut *source.stream_type.borrow_mut()
+        {
+            *next_video_sample = video_sample;
+            *next_audio_sample = audio_sample;
+        }
+
+        let is_finished = movie
+            .video_track()
+            .is_none_or(|track| video_sample >= track.samples.len())
+            && movie
+                .audio_track()
+                .is_none_or(|track| audio_sample >= track.samples.len());
+        (buffer_underrun || is_finished, is_finished)
+    }
+
+    /// Get the data of an MP4 sample, if it has been downloaded.
+    fn mp4_sample_data<'a>(track: &Mp4Track, index: usize, buffer: &'a [u8]) -> Option<&'a [u8]> {
+        let sample = &track.samples[index];
+        let start = usize::try_from(sample.offset).ok()?;
+        buffer.get(start..start.checked_add(sample.size as usize)?)
+    }
+
+    /// Decode an MP4 video sample.
+    fn mp4_video_sample(
+        self,
+        context: &mut UpdateContext<'gc>,
+        video_handle: VideoStreamHandle,
+        data: &[u8],
+        frame_id: u32,
+    ) {
+        // MP4 samples use the same length-prefixed NAL units as FLV.
+        let encoded_frame = EncodedFrame {
+            codec: VideoCodec::H264,
+            data,
+            frame_id,
+        };
+
+        match context
+            .video
+            .decode_video_stream_frame(video_handle, encoded_frame, context.renderer)
+        {
+            Ok(bitmap_info) => {
+                self.0.last_decoded_bitmap.replace(Some(bitmap_info));
+                if let Some(mc) = self.0.attached_to.get() {
+                    mc.invalidate_cached_bitmap(context.gc());
+                    *context.needs_render = true;
+                }
+            }
+            Err(e) => {
+                tracing::error!("Decoding video frame {} failed: {}", frame_id, e);
+            }
+        }
+    }
+
+    /// Queue an MP4 audio sample into the sound stream.
+    fn mp4_audio_sample(self, track: &Mp4Track, data: &[u8]) -> Result<(), NetstreamError> {
+        let source = self.source();
+        let audio_stream = &mut *source.audio_stream.borrow_mut();
+
+        // Unlike FLV, the sample data doesn't carry the packet type byte the
+        // AAC decoder expects, so the samples are copied into a buffer of
+        // their own.
+        let aac_packet_type = match track.codec {
+            Mp4Codec::Aac { .. } => Some(1),
+            _ => None,
+        };
+
+        let substream = match audio_stream {
+            Some((substream, _sound_stream_info)) => substream,
+            audio_stream => {
+                // None
+                let (compression, config) = match &track.codec {
+                    Mp4Codec::Aac { config } => (AudioCompression::Aac, Some(config)),
+                    Mp4Codec::Mp3 => (AudioCompression::Mp3, None),
+                    _ => return Err(NetstreamError::UnknownCodec),
+                };
+                let Mp4Media::Audio {
+                    sample_rate,
+                    channels,
+                    sample_size,
+                } = track.media
+                else {
+                    unreachable!();
+                };
+
+                let sound_stream_head = SoundStreamInfo {
+                    wrapping: SoundStreamWrapping::Unwrapped,
+                    stream_format: SoundFormat {
+                        compression,
+                        sample_rate: u16::try_from(sample_rate).unwrap_or(44_100),
+                        is_stereo: channels > 1,
+                        is_16_bit: sample_size != 8,
+                    },
+                    num_samples_per_block: 0,
+                    latency_seek: 0,
+                };
+
+                let mut substream = Substream::new(Buffer::new());
+                if let Some(config) = config {
+                    Self::append_mp4_audio_chunk(&mut substream, Some(0), config)?;
+                }
+
+                &mut audio_stream.insert((substream, sound_stream_head)).0
+            }
+        };
+
+        Self::append_mp4_audio_chunk(substream, aac_packet_type, data)
+    }
+
+    fn append_mp4_audio_chunk(
+        substream: &mut Substream,
+        packet_type: Option<u8>,
+        data: &[u8],
+    ) -> Result<(), NetstreamError> {
+        let mut buffer = substream.buffer().clone();
+        let start = buffer.len();
+        if let Some(packet_type) = packet_type {
+            buffer.extend_from_slice(&[packet_type]);
+        }
+        buffer.extend_from_slice(data);
+
+        let chunk = buffer.get(start..).expect("chunk was just appended");
+        Ok(substream.append(chunk)?)
+    }
+
     /// Process a parsed FLV video tag.
     ///
     /// `write` must be an active borrow of the current `NetStream`. `slice`
@@ -1141,12 +2046,14 @@ impl<'gc> NetStream<'gc> {
     ///
     /// `dt` is in milliseconds.
     pub fn tick(self, context: &mut UpdateContext<'gc>, dt: f64) {
-        let source = self.source();
-        let seek_offset = source.queued_seek_time.take();
+        let seek_offset = self.source().queued_seek_time.take();
         if let Some(offset) = seek_offset {
             self.execute_seek(context, offset);
         }
 
+        // Seeking in data generation mode replaces the source.
+        let source = self.source();
+
         // Paused streams deactivate themselves after seek processing.
         if !self.0.playing.get() {
             StreamManager::deactivate(context, self);
@@ -1158,12 +2065,33 @@ impl<'gc> NetStream<'gc> {
             return;
         }
 
+        {
+            let slice = source.buffer.borrow().to_full_slice();
+            self.scan_buffered_media(&slice.data());
+        }
+
+        // Hold playback until enough media is buffered, or until no more
+        // media is coming.
+        if source.buffering.get() {
+            let is_downloaded = source.expected_length.get().is_none();
+            if !is_downloaded && self.buffer_length() < self.buffer_time() {
+                return;
+            }
+
+            source.buffering.set(false);
+            self.trigger_status_event(
+                context,
+                vec![("code", "NetStream.Buffer.Full"), ("level", "status")],
+            );
+        }
+
         self.cleanup_sound_stream(context);
         let slice = source.buffer.borrow().to_full_slice();
         let buffer = slice.data();
 
-        let max_time = source.stream_time.get() + dt;
+        let mut max_time = source.stream_time.get() + dt;
         let mut buffer_underrun = false;
+        let mut end_of_media = false;
         let mut error = false;
         let mut max_lookahead_audio_tags = 5;
         let mut is_lookahead_tag = false;
@@ -1194,6 +2122,9 @@ impl<'gc> NetStream<'gc> {
                 }
 
                 let tag = tag.expect("valid tag");
+                if source.resync_time.take() {
+                    max_time = tag.timestamp as f64 + dt;
+                }
                 is_lookahead_tag = tag.timestamp as f64 >= max_time; //FLV timestamps are also ms
                 if is_lookahead_tag && max_lookahead_audio_tags == 0 {
                     break;
@@ -1202,6 +2133,7 @@ impl<'gc> NetStream<'gc> {
                 let tag_needs_preloading = reader.stream_position().expect("valid position")
                     as usize
                     >= source.preload_offset.get();
+                let tag_kind = MediaKind::of_flv_tag(&tag.data);
 
                 match tag.data {
                     FlvTagData::Audio(audio_data) => {
@@ -1231,6 +2163,12 @@ impl<'gc> NetStream<'gc> {
                         .stream_position()
                         .expect("FLV reader stream position")
                         as usize;
+                    let mut stats = source.stats.borrow_mut();
+                    stats.played.add(tag_kind, offset - source.offset.get());
+                    if tag_kind == MediaKind::Video {
+                        stats.record_frame(tag.timestamp as f64);
+                    }
+                    drop(stats);
                     source.offset.set(offset);
                     source
                         .preload_offset
@@ -1239,6 +2177,13 @@ impl<'gc> NetStream<'gc> {
             }
         }
 
+        if matches!(
+            &*source.stream_type.borrow(),
+            Some(NetStreamType::Mp4 { .. })
+        ) {
+            (buffer_underrun, end_of_media) = self.mp4_samples(context, &buffer, max_time);
+        }
+
         source.stream_time.set(max_time);
         if let Err(e) = self.commit_sound_stream(context) {
             //TODO: Fire an error event at AS.
@@ -1246,18 +2191,20 @@ impl<'gc> NetStream<'gc> {
         }
 
         if buffer_underrun {
-            let is_end_of_video = source.expected_length.get().is_none();
-
-            self.trigger_status_event(
-                context,
-                vec![("code", "NetStream.Buffer.Flush"), ("level", "status")],
-            );
+            let is_end_of_video = end_of_media || source.expected_length.get().is_none();
 
             if is_end_of_video {
+                self.trigger_status_event(
+                    context,
+                    vec![("code", "NetStream.Buffer.Flush"), ("level", "status")],
+                );
                 self.trigger_status_event(
                     context,
                     vec![("code", "NetStream.Play.Stop"), ("level", "status")],
                 );
+            } else {
+                // Playback stalls until the buffer refills.
+                source.buffering.set(true);
             }
 
             self.trigger_status_event(
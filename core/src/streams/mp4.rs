@@ -0,0 +1,928 @@
+//! MP4/F4V (ISO base media file format) demuxing
+//!
+//! Only the `moov` box is parsed up front; sample data is read straight out
+//! of the `NetStream` buffer using the offsets from the sample tables, so the
+//! `mdat` box can keep downloading while we play.
+//!
+//! Fragmented files (`moof` boxes) are not supported.
+
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Mp4Error {
+    #[error("Not enough data to parse the movie header")]
+    EndOfData,
+
+    #[error("Invalid {0} box")]
+    InvalidBox(&'static str),
+
+    #[error("Missing {0} box")]
+    MissingBox(&'static str),
+}
+
+/// The codec of a track, along with any configuration the decoder needs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Mp4Codec {
+    /// H.264 video, with its `AVCDecoderConfigurationRecord`.
+    Avc { config: Vec<u8> },
+
+    /// AAC audio, with its `AudioSpecificConfig`.
+    Aac { config: Vec<u8> },
+
+    /// MP3 audio.
+    Mp3,
+
+    /// Any other codec, identified by its sample entry type.
+    Unsupported([u8; 4]),
+}
+
+/// The kind of media held in a track.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Mp4Media {
+    Video {
+        width: u16,
+        height: u16,
+    },
+    Audio {
+        sample_rate: u32,
+        channels: u16,
+        sample_size: u16,
+    },
+}
+
+/// A single sample (video frame or audio access unit) of a track.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mp4Sample {
+    /// The offset of the sample data from the start of the file.
+    pub offset: u64,
+
+    /// The size of the sample data in bytes.
+    pub size: u32,
+
+    /// The decoding time of the sample, in the track's timescale.
+    pub time: u64,
+
+    /// Whether this sample can be decoded without any prior samples.
+    pub is_sync: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct Mp4Track {
+    pub id: u32,
+    pub media: Mp4Media,
+    pub codec: Mp4Codec,
+
+    /// The number of time units per second of this track.
+    pub timescale: u32,
+
+    /// The duration of this track, in its timescale.
+    pub duration: u64,
+
+    /// All samples of this track, in decoding order.
+    pub samples: Vec<Mp4Sample>,
+}
+
+impl Mp4Track {
+    /// The decoding time of the given sample in milliseconds.
+    pub fn sample_time(&self, index: usize) -> f64 {
+        match self.samples.get(index) {
+            Some(sample) => self.to_millis(sample.time),
+            None => self.to_millis(self.duration),
+        }
+    }
+
+    pub fn duration(&self) -> f64 {
+        self.to_millis(self.duration)
+    }
+
+    /// The average number of samples per second.
+    pub fn frame_rate(&self) -> f64 {
+        if self.duration == 0 {
+            return 0.0;
+        }
+        self.samples.len() as f64 * 1000.0 / self.duration()
+    }
+
+    /// The index of the first sample decoded at or after `time` milliseconds.
+    pub fn sample_at(&self, time: f64) -> usize {
+        self.samples
+            .partition_point(|sample| self.to_millis(sample.time) < time)
+    }
+
+    /// The index of the last sync sample decoded at or before `time`
+    /// milliseconds, which is where decoding has to start to show that time.
+    pub fn sync_sample_before(&self, time: f64) -> usize {
+        let end = self
+            .samples
+            .partition_point(|sample| self.to_millis(sample.time) <= time);
+        self.samples[..end]
+            .iter()
+            .rposition(|sample| sample.is_sync)
+            .unwrap_or(0)
+    }
+
+    fn to_millis(&self, time: u64) -> f64 {
+        if self.timescale == 0 {
+            return 0.0;
+        }
+        time as f64 * 1000.0 / self.timescale as f64
+    }
+}
+
+/// The parsed `moov` box of a file.
+#[derive(Clone, Debug)]
+pub struct Mp4Movie {
+    /// The offset of the `moov` box from the start of the file.
+    pub moov_position: u64,
+
+    /// The number of time units per second of the movie.
+    pub timescale: u32,
+
+    /// The duration of the movie, in its timescale.
+    pub duration: u64,
+
+    pub tracks: Vec<Mp4Track>,
+}
+
+impl Mp4Movie {
+    /// Checks whether the data starts like an MP4 file.
+    pub fn sniff(data: &[u8]) -> bool {
+        matches!(
+            data.get(4..8),
+            Some(b"ftyp" | b"moov" | b"mdat" | b"free" | b"skip" | b"wide")
+        )
+    }
+
+    /// Parses the movie header out of the start of a file.
+    ///
+    /// `EndOfData` is returned if the `moov` box hasn't been downloaded yet,
+    /// which may be at the end of the file.
+    pub fn parse(data: &[u8]) -> Result<Self, Mp4Error> {
+        let mut position = 0;
+        loop {
+            let header = BoxHeader::read(data.get(position..).unwrap_or_default())
+                .ok_or(Mp4Error::EndOfData)?;
+            let body_start = position + header.header_size;
+            let body_end = match header.size {
+                Some(size) => position
+                    .checked_add(size)
+                    .ok_or(Mp4Error::InvalidBox("top-level"))?,
+                // The last box extends to the end of the file, which we
+                // can't know before it's downloaded.
+                None if &header.name == b"moov" => data.len(),
+                None => return Err(Mp4Error::EndOfData),
+            };
+            if body_end < body_start {
+                return Err(Mp4Error::InvalidBox("top-level"));
+            }
+
+            if &header.name == b"moov" {
+                let body = data.get(body_start..body_end).ok_or(Mp4Error::EndOfData)?;
+                return Self::parse_moov(body, position as u64);
+            }
+            if &header.name == b"moof" {
+                return Err(Mp4Error::InvalidBox("moof"));
+            }
+
+            position = body_end;
+        }
+    }
+
+    fn parse_moov(data: &[u8], moov_position: u64) -> Result<Self, Mp4Error> {
+        let mut movie = Self {
+            moov_position,
+            timescale: 0,
+            duration: 0,
+            tracks: Vec::new(),
+        };
+        let mut has_header = false;
+
+        for (name, body) in Boxes(data) {
+            match &name {
+                b"mvhd" => {
+                    let (timescale, duration) =
+                        parse_media_header(body).ok_or(Mp4Error::InvalidBox("mvhd"))?;
+                    movie.timescale = timescale;
+                    movie.duration = duration;
+                    has_header = true;
+                }
+                b"trak" => {
+                    if let Some(track) = parse_trak(body)? {
+                        movie.tracks.push(track);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if !has_header {
+            return Err(Mp4Error::MissingBox("mvhd"));
+        }
+        Ok(movie)
+    }
+
+    /// The duration of the movie in milliseconds.
+    pub fn duration(&self) -> f64 {
+        if self.timescale == 0 {
+            return 0.0;
+        }
+        self.duration as f64 * 1000.0 / self.timescale as f64
+    }
+
+    /// The first video track of the movie.
+    pub fn video_track(&self) -> Option<&Mp4Track> {
+        self.tracks
+            .iter()
+            .find(|track| matches!(track.media, Mp4Media::Video { .. }))
+    }
+
+    /// The first audio track of the movie.
+    pub fn audio_track(&self) -> Option<&Mp4Track> {
+        self.tracks
+            .iter()
+            .find(|track| matches!(track.media, Mp4Media::Audio { .. }))
+    }
+}
+
+struct BoxHeader {
+    name: [u8; 4],
+
+    /// The size of the whole box, or `None` if it extends to the end of the
+    /// file.
+    size: Option<usize>,
+    header_size: usize,
+}
+
+impl BoxHeader {
+    fn read(data: &[u8]) -> Option<Self> {
+        let mut reader = Reader(data);
+        let size = reader.u32()?;
+        let name = reader.name()?;
+        match size {
+            0 => Some(Self {
+                name,
+                size: None,
+                header_size: 8,
+            }),
+            1 => Some(Self {
+                name,
+                size: Some(usize::try_from(reader.u64()?).ok()?),
+                header_size: 16,
+            }),
+            size => Some(Self {
+                name,
+                size: Some(size as usize),
+                header_size: 8,
+            }),
+        }
+    }
+}
+
+/// Iterates over the boxes contained in a box's body, stopping at the first
+/// malformed one.
+struct Boxes<'a>(&'a [u8]);
+
+impl<'a> Iterator for Boxes<'a> {
+    type Item = ([u8; 4], &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let header = BoxHeader::read(self.0)?;
+        let size = header.size.unwrap_or(self.0.len());
+        let body = self.0.get(header.header_size..size)?;
+        self.0 = &self.0[size..];
+        Some((header.name, body))
+    }
+}
+
+fn find_box<'a>(data: &'a [u8], name: &[u8; 4]) -> Option<&'a [u8]> {
+    Boxes(data).find(|(n, _)| n == name).map(|(_, body)| body)
+}
+
+/// A big-endian reader over box data.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.0.get(..len)?;
+        self.0 = &self.0[len..];
+        Some(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> Option<()> {
+        self.bytes(len).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        Some(u16::from_be_bytes(self.bytes(2)?.try_into().ok()?))
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_be_bytes(self.bytes(4)?.try_into().ok()?))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_be_bytes(self.bytes(8)?.try_into().ok()?))
+    }
+
+    fn name(&mut self) -> Option<[u8; 4]> {
+        self.bytes(4)?.try_into().ok()
+    }
+
+    /// Reads the entry count of a table, checking that the table fits in the
+    /// remaining data.
+    fn table_len(&mut self, entry_size: usize) -> Option<usize> {
+        let len = self.u32()? as usize;
+        (len <= self.0.len() / entry_size).then_some(len)
+    }
+
+    /// Reads the variable-length size of an MPEG-4 descriptor.
+    fn descriptor_len(&mut self) -> Option<usize> {
+        let mut len = 0;
+        for _ in 0..4 {
+            let byte = self.u8()?;
+            len = (len << 7) | (byte & 0x7F) as usize;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        Some(len)
+    }
+}
+
+/// Parses the timescale and duration out of an `mvhd` or `mdhd` box.
+fn parse_media_header(data: &[u8]) -> Option<(u32, u64)> {
+    let mut reader = Reader(data);
+    let version = reader.u8()?;
+    reader.skip(3)?;
+    if version == 1 {
+        reader.skip(16)?;
+        Some((reader.u32()?, reader.u64()?))
+    } else {
+        reader.skip(8)?;
+        Some((reader.u32()?, reader.u32()? as u64))
+    }
+}
+
+/// Parses a `trak` box, returning `None` for tracks that aren't audio or
+/// video.
+fn parse_trak(data: &[u8]) -> Result<Option<Mp4Track>, Mp4Error> {
+    let tkhd = find_box(data, b"tkhd").ok_or(Mp4Error::MissingBox("tkhd"))?;
+    let id = parse_track_id(tkhd).ok_or(Mp4Error::InvalidBox("tkhd"))?;
+
+    let mdia = find_box(data, b"mdia").ok_or(Mp4Error::MissingBox("mdia"))?;
+    let mdhd = find_box(mdia, b"mdhd").ok_or(Mp4Error::MissingBox("mdhd"))?;
+    let (timescale, duration) = parse_media_header(mdhd).ok_or(Mp4Error::InvalidBox("mdhd"))?;
+
+    let hdlr = find_box(mdia, b"hdlr").ok_or(Mp4Error::MissingBox("hdlr"))?;
+    let handler = hdlr.get(8..12).ok_or(Mp4Error::InvalidBox("hdlr"))?;
+    if handler != b"vide" && handler != b"soun" {
+        return Ok(None);
+    }
+
+    let stbl = find_box(mdia, b"minf")
+        .and_then(|minf| find_box(minf, b"stbl"))
+        .ok_or(Mp4Error::MissingBox("stbl"))?;
+    let stsd = find_box(stbl, b"stsd").ok_or(Mp4Error::MissingBox("stsd"))?;
+    let (media, codec) = parse_stsd(stsd).ok_or(Mp4Error::InvalidBox("stsd"))?;
+    let samples = parse_sample_table(stbl)?;
+
+    Ok(Some(Mp4Track {
+        id,
+        media,
+        codec,
+        timescale,
+        duration,
+        samples,
+    }))
+}
+
+fn parse_track_id(data: &[u8]) -> Option<u32> {
+    let mut reader = Reader(data);
+    let version = reader.u8()?;
+    reader.skip(3)?;
+    reader.skip(if version == 1 { 16 } else { 8 })?;
+    reader.u32()
+}
+
+/// Parses the first sample entry of an `stsd` box.
+fn parse_stsd(data: &[u8]) -> Option<(Mp4Media, Mp4Codec)> {
+    let mut reader = Reader(data);
+    reader.skip(4)?;
+    if reader.u32()? == 0 {
+        return None;
+    }
+    let (name, entry) = Boxes(reader.0).next()?;
+
+    let mut reader = Reader(entry);
+    // Reserved bytes and the data reference index.
+    reader.skip(8)?;
+    match &name {
+        b"avc1" | b"avc3" | b"encv" | b"hev1" | b"hvc1" | b"vp08" | b"vp09" => {
+            reader.skip(16)?;
+            let width = reader.u16()?;
+            let height = reader.u16()?;
+            reader.skip(50)?;
+
+            let codec = match (&name, find_box(reader.0, b"avcC")) {
+                (b"avc1" | b"avc3", Some(config)) => Mp4Codec::Avc {
+                    config: config.to_vec(),
+                },
+                _ => Mp4Codec::Unsupported(name),
+            };
+            Some((Mp4Media::Video { width, height }, codec))
+        }
+        b"mp4a" | b".mp3" | b"enca" | b"ac-3" | b"ec-3" | b"Opus" => {
+            let version = reader.u16()?;
+            reader.skip(6)?;
+            let channels = reader.u16()?;
+            let sample_size = reader.u16()?;
+            reader.skip(4)?;
+            let sample_rate = reader.u32()? >> 16;
+            // QuickTime sound descriptions carry extra fields.
+            match version {
+                1 => reader.skip(16)?,
+                2 => reader.skip(36)?,
+                _ => {}
+            }
+
+            let codec = match &name {
+                b"mp4a" => find_box(reader.0, b"esds")
+                    .and_then(parse_esds)
+                    .unwrap_or(Mp4Codec::Unsupported(name)),
+                b".mp3" => Mp4Codec::Mp3,
+                _ => Mp4Codec::Unsupported(name),
+            };
+            let media = Mp4Media::Audio {
+                sample_rate,
+                channels,
+                sample_size,
+            };
+            Some((media, codec))
+        }
+        _ => None,
+    }
+}
+
+/// Finds the codec of an `mp4a` sample entry from its elementary stream
+/// descriptor.
+fn parse_esds(data: &[u8]) -> Option<Mp4Codec> {
+    const ES_DESCRIPTOR: u8 = 3;
+    const DECODER_CONFIG_DESCRIPTOR: u8 = 4;
+    const DECODER_SPECIFIC_INFO: u8 = 5;
+
+    let mut reader = Reader(data);
+    reader.skip(4)?;
+
+    if reader.u8()? != ES_DESCRIPTOR {
+        return None;
+    }
+    reader.descriptor_len()?;
+    reader.skip(2)?;
+    let flags = reader.u8()?;
+    if flags & 0x80 != 0 {
+        reader.skip(2)?;
+    }
+    if flags & 0x40 != 0 {
+        let url_len = reader.u8()?;
+        reader.skip(url_len as usize)?;
+    }
+    if flags & 0x20 != 0 {
+        reader.skip(2)?;
+    }
+
+    if reader.u8()? != DECODER_CONFIG_DESCRIPTOR {
+        return None;
+    }
+    reader.descriptor_len()?;
+    let object_type = reader.u8()?;
+    reader.skip(12)?;
+
+    match object_type {
+        // MPEG-4 audio and the MPEG-2 AAC profiles.
+        0x40 | 0x66..=0x68 => {
+            if reader.u8()? != DECODER_SPECIFIC_INFO {
+                return None;
+            }
+            let len = reader.descriptor_len()?;
+            let config = reader.bytes(len)?.to_vec();
+            Some(Mp4Codec::Aac { config })
+        }
+        // MPEG-1 and MPEG-2 audio.
+        0x69 | 0x6B => Some(Mp4Codec::Mp3),
+        _ => None,
+    }
+}
+
+/// The most samples a single track may have.
+///
+/// This is over 19 hours of video at 60 frames per second.
+const MAX_SAMPLES: usize = 1 << 22;
+
+/// Builds the list of samples out of the tables of an `stbl` box.
+fn parse_sample_table(data: &[u8]) -> Result<Vec<Mp4Sample>, Mp4Error> {
+    let stsz = find_box(data, b"stsz").ok_or(Mp4Error::MissingBox("stsz"))?;
+    let sizes = parse_stsz(stsz).ok_or(Mp4Error::InvalidBox("stsz"))?;
+
+    let chunk_offsets = if let Some(stco) = find_box(data, b"stco") {
+        parse_chunk_offsets(stco, false).ok_or(Mp4Error::InvalidBox("stco"))?
+    } else if let Some(co64) = find_box(data, b"co64") {
+        parse_chunk_offsets(co64, true).ok_or(Mp4Error::InvalidBox("co64"))?
+    } else {
+        return Err(Mp4Error::MissingBox("stco"));
+    };
+
+    let stsc = find_box(data, b"stsc").ok_or(Mp4Error::MissingBox("stsc"))?;
+    let chunks = parse_stsc(stsc).ok_or(Mp4Error::InvalidBox("stsc"))?;
+
+    let stts = find_box(data, b"stts").ok_or(Mp4Error::MissingBox("stts"))?;
+    let durations = parse_stts(stts).ok_or(Mp4Error::InvalidBox("stts"))?;
+
+    // Without a sync sample table, every sample is a sync sample.
+    let sync_samples = match find_box(data, b"stss") {
+        Some(stss) => Some(parse_stss(stss).ok_or(Mp4Error::InvalidBox("stss"))?),
+        None => None,
+    };
+
+    // Work out how many samples each chunk holds.
+    let samples_per_chunk = (1..=chunk_offsets.len() as u32)
+        .map(|chunk_number| {
+            let entry = chunks.partition_point(|(first_chunk, _)| *first_chunk <= chunk_number);
+            entry
+                .checked_sub(1)
+                .and_then(|e| chunks.get(e))
+                .map(|&(_, samples_per_chunk)| samples_per_chunk)
+                .ok_or(Mp4Error::InvalidBox("stsc"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // There can't be more samples than the chunks hold, whatever the sizes say.
+    let sample_count = samples_per_chunk
+        .iter()
+        .fold(0usize, |total, &count| total.saturating_add(count as usize))
+        .min(sizes.len());
+    // Neither a constant sample size nor the chunk table is backed by any
+    // data, so a few bytes of tables could otherwise ask for any number of samples.
+    if sample_count > MAX_SAMPLES {
+        return Err(Mp4Error::InvalidBox("stsz"));
+    }
+    let mut samples = Vec::with_capacity(sample_count);
+
+    // Lay the samples out in their chunks.
+    'chunks: for (&chunk_offset, samples_per_chunk) in chunk_offsets.iter().zip(samples_per_chunk) {
+        let mut offset = chunk_offset;
+        for _ in 0..samples_per_chunk {
+            let Some(size) = sizes
+                .get(samples.len())
+                .filter(|_| samples.len() < sample_count)
+            else {
+                break 'chunks;
+            };
+            samples.push(Mp4Sample {
+                offset,
+                size,
+                time: 0,
+                is_sync: true,
+            });
+            offset = offset
+                .checked_add(size as u64)
+                .ok_or(Mp4Error::InvalidBox("stco"))?;
+        }
+    }
+
+    // Then give them their timings.
+    let mut time = 0;
+    let mut samples_iter = samples.iter_mut();
+    'durations: for (count, delta) in durations {
+        for _ in 0..count {
+            let Some(sample) = samples_iter.next() else {
+                break 'durations;
+            };
+            sample.time = time;
+            time = time
+                .checked_add(delta as u64)
+                .ok_or(Mp4Error::InvalidBox("stts"))?;
+        }
+    }
+
+    if let Some(sync_samples) = sync_samples {
+        for sample in &mut samples {
+            sample.is_sync = false;
+        }
+        for number in sync_samples {
+            if let Some(sample) = (number as usize)
+                .checked_sub(1)
+                .and_then(|index| samples.get_mut(index))
+            {
+                sample.is_sync = true;
+            }
+        }
+    }
+
+    Ok(samples)
+}
+
+/// The sizes of the samples of a track, as given by an `stsz` box.
+#[derive(Debug, PartialEq, Eq)]
+enum SampleSizes {
+    /// Every sample has the same size.
+    ///
+    /// The count isn't backed by any table, so the sizes aren't expanded into one.
+    Constant { size: u32, count: u32 },
+
+    /// The size of each sample.
+    Table(Vec<u32>),
+}
+
+impl SampleSizes {
+    fn len(&self) -> usize {
+        match self {
+            Self::Constant { count, .. } => *count as usize,
+            Self::Table(sizes) => sizes.len(),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<u32> {
+        match self {
+            Self::Constant { size, count } => (index < *count as usize).then_some(*size),
+            Self::Table(sizes) => sizes.get(index).copied(),
+        }
+    }
+}
+
+fn parse_stsz(data: &[u8]) -> Option<SampleSizes> {
+    let mut reader = Reader(data);
+    reader.skip(4)?;
+    let size = reader.u32()?;
+    if size != 0 {
+        let count = reader.u32()?;
+        return Some(SampleSizes::Constant { size, count });
+    }
+    let count = reader.table_len(4)?;
+    let sizes = (0..count).map(|_| reader.u32()).collect::<Option<_>>()?;
+    Some(SampleSizes::Table(sizes))
+}
+
+fn parse_chunk_offsets(data: &[u8], is_64_bit: bool) -> Option<Vec<u64>> {
+    let mut reader = Reader(data);
+    reader.skip(4)?;
+    if is_64_bit {
+        let count = reader.table_len(8)?;
+        (0..count).map(|_| reader.u64()).collect()
+    } else {
+        let count = reader.table_len(4)?;
+        (0..count).map(|_| reader.u32().map(u64::from)).collect()
+    }
+}
+
+/// Parses the first chunk number and samples per chunk of each `stsc` entry.
+fn parse_stsc(data: &[u8]) -> Option<Vec<(u32, u32)>> {
+    let mut reader = Reader(data);
+    reader.skip(4)?;
+    let count = reader.table_len(12)?;
+    (0..count)
+        .map(|_| {
+            let first_chunk = reader.u32()?;
+            let samples_per_chunk = reader.u32()?;
+            reader.skip(4)?;
+            Some((first_chunk, samples_per_chunk))
+        })
+        .collect()
+}
+
+/// Parses the sample count and sample duration of each `stts` entry.
+fn parse_stts(data: &[u8]) -> Option<Vec<(u32, u32)>> {
+    let mut reader = Reader(data);
+    reader.skip(4)?;
+    let count = reader.table_len(8)?;
+    (0..count)
+        .map(|_| Some((reader.u32()?, reader.u32()?)))
+        .collect()
+}
+
+fn parse_stss(data: &[u8]) -> Option<Vec<u32>> {
+    let mut reader = Reader(data);
+    reader.skip(4)?;
+    let count = reader.table_len(4)?;
+    (0..count).map(|_| reader.u32()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mp4_box(name: &[u8; 4], parts: &[&[u8]]) -> Vec<u8> {
+        let body: Vec<u8> = parts.concat();
+        let mut data = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(name);
+        data.extend_from_slice(&body);
+        data
+    }
+
+    fn full_box(name: &[u8; 4], parts: &[&[u8]]) -> Vec<u8> {
+        let mut body = vec![0; 4];
+        body.extend_from_slice(&parts.concat());
+        mp4_box(name, &[&body])
+    }
+
+    fn table(entries: &[u32]) -> Vec<u8> {
+        entries.iter().flat_map(|e| e.to_be_bytes()).collect()
+    }
+
+    fn media_header(timescale: u32, duration: u32) -> Vec<u8> {
+        table(&[0, 0, timescale, duration])
+    }
+
+    fn video_trak(chunk_offsets: &[u32]) -> Vec<u8> {
+        let mut entry = vec![0; 24];
+        entry.extend_from_slice(&320u16.to_be_bytes());
+        entry.extend_from_slice(&240u16.to_be_bytes());
+        entry.extend_from_slice(&[0; 50]);
+        entry.extend_from_slice(&mp4_box(b"avcC", &[&[1, 2, 3]]));
+        let stsd = full_box(b"stsd", &[&table(&[1]), &mp4_box(b"avc1", &[&entry])]);
+
+        let stbl = mp4_box(
+            b"stbl",
+            &[
+                &stsd,
+                &full_box(b"stts", &[&table(&[1, 4, 100])]),
+                &full_box(b"stss", &[&table(&[2, 1, 3])]),
+                &full_box(b"stsz", &[&table(&[0, 4, 10, 20, 30, 40])]),
+                &full_box(b"stsc", &[&table(&[2, 1, 2, 1, 2, 1, 1])]),
+                &full_box(
+                    b"stco",
+                    &[&table(&[chunk_offsets.len() as u32]), &table(chunk_offsets)],
+                ),
+            ],
+        );
+        let mdia = mp4_box(
+            b"mdia",
+            &[
+                &full_box(b"mdhd", &[&media_header(1000, 400)]),
+                &full_box(b"hdlr", &[&table(&[0]), b"vide"]),
+                &mp4_box(b"minf", &[&stbl]),
+            ],
+        );
+        mp4_box(b"trak", &[&full_box(b"tkhd", &[&table(&[0, 0, 7])]), &mdia])
+    }
+
+    fn movie(chunk_offsets: &[u32]) -> Vec<u8> {
+        mp4_box(
+            b"moov",
+            &[
+                &full_box(b"mvhd", &[&media_header(600, 240)]),
+                &video_trak(chunk_offsets),
+            ],
+        )
+    }
+
+    #[test]
+    fn parse_sample_tables() {
+        let mut data = mp4_box(b"ftyp", &[b"isom"]);
+        data.extend_from_slice(&movie(&[1000, 2000, 3000]));
+        assert!(Mp4Movie::sniff(&data));
+
+        let movie = Mp4Movie::parse(&data).unwrap();
+        assert_eq!(movie.moov_position, 12);
+        assert_eq!(movie.duration(), 400.0);
+
+        let track = movie.video_track().unwrap();
+        assert!(movie.audio_track().is_none());
+        assert_eq!(track.id, 7);
+        assert_eq!(
+            track.media,
+            Mp4Media::Video {
+                width: 320,
+                height: 240
+            }
+        );
+        assert_eq!(
+            track.codec,
+            Mp4Codec::Avc {
+                config: vec![1, 2, 3]
+            }
+        );
+
+        let layout: Vec<_> = track
+            .samples
+            .iter()
+            .map(|sample| (sample.offset, sample.size, sample.time, sample.is_sync))
+            .collect();
+        assert_eq!(
+            layout,
+            vec![
+                (1000, 10, 0, true),
+                (1010, 20, 100, false),
+                (2000, 30, 200, true),
+                (3000, 40, 300, false),
+            ]
+        );
+        assert_eq!(track.frame_rate(), 10.0);
+    }
+
+    #[test]
+    fn seek_to_sync_samples() {
+        let movie = Mp4Movie::parse(&movie(&[0, 0, 0])).unwrap();
+        let track = movie.video_track().unwrap();
+
+        assert_eq!(track.sync_sample_before(0.0), 0);
+        assert_eq!(track.sync_sample_before(150.0), 0);
+        assert_eq!(track.sync_sample_before(200.0), 2);
+        assert_eq!(track.sync_sample_before(399.0), 2);
+        assert_eq!(track.sample_at(150.0), 2);
+        assert_eq!(track.sample_at(1000.0), 4);
+    }
+
+    #[test]
+    fn wait_for_trailing_moov() {
+        let mut data = mp4_box(b"ftyp", &[b"isom"]);
+        data.extend_from_slice(&mp4_box(b"mdat", &[&[0; 100]]));
+        let moov = movie(&[20]);
+
+        assert_eq!(
+            Mp4Movie::parse(&data[..50]).unwrap_err(),
+            Mp4Error::EndOfData
+        );
+        data.extend_from_slice(&moov[..moov.len() - 1]);
+        assert_eq!(Mp4Movie::parse(&data).unwrap_err(), Mp4Error::EndOfData);
+        data.push(*moov.last().unwrap());
+        assert_eq!(Mp4Movie::parse(&data).unwrap().moov_position, 120);
+    }
+
+    #[test]
+    fn parse_aac_descriptor() {
+        let mut esds = vec![0; 4];
+        esds.extend_from_slice(&[3, 25, 0, 1, 0]);
+        esds.extend_from_slice(&[4, 17, 0x40, 0x15]);
+        esds.extend_from_slice(&[0; 11]);
+        esds.extend_from_slice(&[5, 2, 0x12, 0x10]);
+        assert_eq!(
+            parse_esds(&esds),
+            Some(Mp4Codec::Aac {
+                config: vec![0x12, 0x10]
+            })
+        );
+
+        esds[11] = 0x6B;
+        assert_eq!(parse_esds(&esds), Some(Mp4Codec::Mp3));
+    }
+
+    #[test]
+    fn constant_sample_size() {
+        let stsz = full_box(b"stsz", &[&table(&[8, u32::MAX])]);
+        let stbl = mp4_box(
+            b"stbl",
+            &[
+                &full_box(b"stts", &[&table(&[1, 3, 100])]),
+                &stsz,
+                &full_box(b"stsc", &[&table(&[1, 1, 3, 1])]),
+                &full_box(b"stco", &[&table(&[1, 500])]),
+            ],
+        );
+
+        // The samples are capped by what the chunks hold, not by the count of `stsz`.
+        let samples = parse_sample_table(&stbl[8..]).unwrap();
+        let layout: Vec<_> = samples
+            .iter()
+            .map(|sample| (sample.offset, sample.size, sample.time))
+            .collect();
+        assert_eq!(layout, vec![(500, 8, 0), (508, 8, 100), (516, 8, 200)]);
+    }
+
+    #[test]
+    fn too_many_samples() {
+        let stbl = mp4_box(
+            b"stbl",
+            &[
+                &full_box(b"stts", &[&table(&[1, u32::MAX, 1])]),
+                &full_box(b"stsz", &[&table(&[1, u32::MAX])]),
+                &full_box(b"stsc", &[&table(&[1, 1, u32::MAX, 1])]),
+                &full_box(b"stco", &[&table(&[1, 0])]),
+            ],
+        );
+        assert_eq!(
+            parse_sample_table(&stbl[8..]).unwrap_err(),
+            Mp4Error::InvalidBox("stsz")
+        );
+    }
+
+    #[test]
+    fn overflowing_sample_offset() {
+        let stbl = mp4_box(
+            b"stbl",
+            &[
+                &full_box(b"stts", &[&table(&[1, 2, 100])]),
+                &full_box(b"stsz", &[&table(&[0, 2, u32::MAX, 1])]),
+                &full_box(b"stsc", &[&table(&[1, 1, 2, 1])]),
+                &full_box(b"co64", &[&table(&[1, u32::MAX, u32::MAX - 10])]),
+            ],
+        );
+        assert_eq!(
+            parse_sample_table(&stbl[8..]).unwrap_err(),
+            Mp4Error::InvalidBox("stco")
+        );
+    }
+}
@@ -54,6 +54,11 @@ impl<'gc> AvmString<'gc> {
         self.0.is_dependent()
     }
 
+    /// Returns the string that this dependent string is a slice of.
+    pub fn owner(&self) -> Option<AvmString<'gc>> {
+        self.0.owner().map(Self)
+    }
+
     pub fn as_wstr(&self) -> &'gc WStr {
         Gc::as_ref(self.0).as_wstr()
     }
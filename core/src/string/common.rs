@@ -51,6 +51,7 @@ define_common_strings! {
     str___constructor__: b"__constructor__",
     str___proto__: b"__proto__",
     str___resolve: b"__resolve",
+    str__accProps: b"_accProps",
     str__bytesLoaded: b"_bytesLoaded",
     str__bytesTotal: b"_bytesTotal",
     str__css: b"_css",
@@ -58,8 +59,10 @@ define_common_strings! {
     str__styles: b"_styles",
     str_aa: b"aa",
     str_ab: b"ab",
+    str_accDoDefaultAction: b"accDoDefaultAction",
     str_access: b"access",
     str_accessors: b"accessors",
+    str_activity: b"activity",
     str_addListener: b"addListener",
     str_advanced: b"advanced",
     str_alphaMultiplier: b"alphaMultiplier",
@@ -92,6 +95,7 @@ define_common_strings! {
     str_center: b"center",
     str_clamp: b"clamp",
     str_click: b"click",
+    str_client: b"client",
     str_code: b"code",
     str_color: b"color",
     str_complete: b"complete",
@@ -115,12 +119,16 @@ define_common_strings! {
     str_focusEnabled: b"focusEnabled",
     str_fontStyle: b"fontStyle",
     str_fontWeight: b"fontWeight",
+    str_forceSimple: b"forceSimple",
     str_forward_back: b"forward_back",
     str_full: b"full",
     str_fullScreen: b"fullScreen",
     str_function: b"function",
     str_ga: b"ga",
     str_gb: b"gb",
+    str_get_accName: b"get_accName",
+    str_get_accRole: b"get_accRole",
+    str_get_accValue: b"get_accValue",
     str_global: b"global",
     str_greenMultiplier: b"greenMultiplier",
     str_greenOffset: b"greenOffset",
@@ -182,6 +190,7 @@ define_common_strings! {
     str_null: b"null",
     str_number: b"number",
     str_object: b"object",
+    str_objectEncoding: b"objectEncoding",
     str_onCancel: b"onCancel",
     str_onChanged: b"onChanged",
     str_onClose: b"onClose",
@@ -253,9 +262,13 @@ define_common_strings! {
     str_rollOut: b"rollOut",
     str_rollOver: b"rollOver",
     str_rr: b"rr",
+    str_sampleData: b"sampleData",
     str_save: b"save",
+    str_securityError: b"securityError",
     str_Selection: b"Selection",
     str_separatorBefore: b"separatorBefore",
+    str_shortcut: b"shortcut",
+    str_silent: b"silent",
     str_splice: b"splice",
     str_standard: b"standard",
     str_standardConstrained: b"standardConstrained",
@@ -6,7 +6,9 @@
 
 use crate::avm1::ExecutionReason;
 use crate::avm1::{Activation, ActivationIdentifier, Object as Avm1Object, Value as Avm1Value};
-use crate::avm2::{Activation as Avm2Activation, Object as Avm2Object, Value as Avm2Value};
+use crate::avm2::{
+    Activation as Avm2Activation, Domain as Avm2Domain, Object as Avm2Object, Value as Avm2Value,
+};
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, TDisplayObject};
 use crate::string::AvmString;
@@ -135,7 +137,9 @@ impl<'gc> Timers<'gc> {
                         true
                     }
                 }
-                TimerCallback::Avm2Callback { closure, params } => {
+                TimerCallback::Avm2Callback {
+                    closure, params, ..
+                } => {
                     let domain = context.avm2.stage_domain();
                     let mut avm2_activation = Avm2Activation::from_domain(context, domain);
                     match Avm2Value::from(closure).call(
@@ -257,6 +261,30 @@ impl<'gc> Timers<'gc> {
         len < old_len
     }
 
+    /// Removes every AVM2 timer that was started by code from `domain` or
+    /// one of its child domains.
+    ///
+    /// Returns the `flash.utils.Timer` objects of the removed timers, which
+    /// the caller has to mark as stopped.
+    pub fn remove_avm2_timers_in_domain(
+        &mut self,
+        domain: Avm2Domain<'gc>,
+    ) -> Vec<Avm2Object<'gc>> {
+        let mut removed = Vec::new();
+        self.timers.retain(|t| match &t.callback {
+            TimerCallback::Avm2Callback {
+                domain: Some(timer_domain),
+                timer,
+                ..
+            } if timer_domain.is_within(domain) => {
+                removed.extend(*timer);
+                false
+            }
+            _ => true,
+        });
+        removed
+    }
+
     pub fn remove_all(&mut self) {
         self.timers.clear()
     }
@@ -366,5 +394,9 @@ pub enum TimerCallback<'gc> {
     Avm2Callback {
         closure: Avm2Object<'gc>,
         params: Vec<Avm2Value<'gc>>,
+        /// The domain of the code that started the timer.
+        domain: Option<Avm2Domain<'gc>>,
+        /// The `flash.utils.Timer` whose `_timerId` refers to this timer, if any.
+        timer: Option<Avm2Object<'gc>>,
     },
 }
@@ -0,0 +1,6 @@
+process-access-dialog-title = Requesting Process Access
+
+process-access-dialog-message = The current movie is attempting to run the following program. Do you want to allow it?
+process-access-dialog-arguments = Arguments: { $arguments }
+
+process-access-dialog-allow = Allow
@@ -1,3 +1,4 @@
+use crate::backends::{AccessKitBridge, DesktopUiBackend};
 use crate::custom_event::RuffleEvent;
 use crate::gui::{GuiController, MENU_HEIGHT};
 use crate::player::{LaunchOptions, PlayerController};
@@ -12,6 +13,7 @@ use ruffle_core::events::{ImeEvent, ImeNotification, PlayerNotification};
 use ruffle_core::swf::HeaderExt;
 use ruffle_core::PlayerEvent;
 use ruffle_render::backend::ViewportDimensions;
+use std::any::Any;
 use std::sync::Arc;
 use std::time::Instant;
 use url::Url;
@@ -39,10 +41,14 @@ struct MainWindow {
     time: Instant,
     next_frame_time: Option<Instant>,
     event_loop_proxy: EventLoopProxy<RuffleEvent>,
+    accessibility: AccessKitBridge,
 }
 
 impl MainWindow {
     pub fn window_event(&mut self, event_loop: &ActiveEventLoop, event: WindowEvent) {
+        self.accessibility
+            .process_window_event(self.gui.window(), &event);
+
         if matches!(event, WindowEvent::RedrawRequested) {
             // Don't render when minimized to avoid potential swap chain errors in `wgpu`.
             if !self.minimized {
@@ -67,7 +73,14 @@ impl MainWindow {
         }
         match event {
             WindowEvent::CloseRequested => {
-                event_loop.exit();
+                // AIR content can cancel this through `NativeApplication`'s `exiting` event.
+                let may_exit = match self.player.get() {
+                    Some(mut player) => player.request_exit(),
+                    None => true,
+                };
+                if may_exit {
+                    event_loop.exit();
+                }
             }
             WindowEvent::Resized(size) => {
                 // TODO: Change this when winit adds a `Window::minimized` or `WindowEvent::Minimize`.
@@ -80,12 +93,20 @@ impl MainWindow {
                         height: size.height.saturating_sub(self.gui.height_offset() as u32),
                         scale_factor: viewport_scale_factor,
                     });
+                    player.update_native_window();
                 }
+                self.accessibility
+                    .set_height_offset(self.gui.height_offset());
                 self.gui.window().request_redraw();
                 if matches!(self.loaded, LoadingState::WaitingForResize) {
                     self.loaded = LoadingState::Loaded;
                 }
             }
+            WindowEvent::Moved(_) => {
+                if let Some(mut player) = self.player.get() {
+                    player.update_native_window();
+                }
+            }
             WindowEvent::CursorMoved { position, .. } => {
                 if self.gui.is_context_menu_visible() {
                     return;
@@ -338,6 +359,8 @@ impl MainWindow {
                 scale_factor: viewport_scale_factor,
             });
         }
+        self.accessibility
+            .set_height_offset(self.gui.height_offset());
     }
 
     fn about_to_wait(&mut self, gilrs: Option<&mut Gilrs>) {
@@ -394,6 +417,9 @@ pub struct App {
     event_loop_proxy: EventLoopProxy<RuffleEvent>,
     preferences: GlobalPreferences,
     font_database: fontdb::Database,
+
+    /// The status the movie asked to exit with, through `NativeApplication.exit`.
+    exit_code: i32,
 }
 
 impl App {
@@ -419,10 +445,15 @@ impl App {
                 event_loop_proxy,
                 font_database,
                 preferences,
+                exit_code: 0,
             },
             event_loop,
         ))
     }
+
+    pub fn exit_code(&self) -> i32 {
+        self.exit_code
+    }
 }
 
 impl ApplicationHandler<RuffleEvent> for App {
@@ -471,6 +502,8 @@ impl ApplicationHandler<RuffleEvent> for App {
                 .expect("Window should be created");
             let max_window_size = get_screen_size(&window);
             window.set_max_inner_size(Some(max_window_size));
+            // The adapter has to be created before the window is shown.
+            let accessibility = AccessKitBridge::new(event_loop, &window, event_loop_proxy.clone());
             let window = Arc::new(window);
             let font_database = self.font_database.clone();
 
@@ -491,6 +524,7 @@ impl ApplicationHandler<RuffleEvent> for App {
                 font_database,
                 preferences.clone(),
                 gui.file_picker(),
+                accessibility.clone(),
             );
 
             if let Some(movie_url) = &movie_url {
@@ -528,6 +562,7 @@ impl ApplicationHandler<RuffleEvent> for App {
                 time: Instant::now(),
                 next_frame_time: None,
                 event_loop_proxy,
+                accessibility,
             });
         }
     }
@@ -617,9 +652,25 @@ impl ApplicationHandler<RuffleEvent> for App {
                     PlayerNotification::ImeNotification(ImeNotification::ImeNotReady) => {
                         main_window.gui.set_ime_allowed(false);
                     }
+                    PlayerNotification::Exit(exit_code) => {
+                        self.exit_code = exit_code;
+                        event_loop.exit();
+                    }
                 }
             }
 
+            (Some(main_window), RuffleEvent::CreateCustomCursors) => {
+                if let Some(mut player) = main_window.player.get() {
+                    if let Some(ui) = <dyn Any>::downcast_mut::<DesktopUiBackend>(player.ui_mut()) {
+                        ui.create_custom_cursors(event_loop);
+                    }
+                }
+            }
+
+            (Some(main_window), RuffleEvent::Accessibility(event)) => {
+                main_window.accessibility.handle_event(event.window_event);
+            }
+
             (_, RuffleEvent::ExitRequested) => {
                 event_loop.exit();
             }
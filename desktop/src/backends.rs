@@ -1,10 +1,14 @@
+mod accessibility;
 mod external_interface;
 mod fscommand;
 mod navigator;
+mod process;
 mod ui;
 
+pub use accessibility::{AccessKitBridge, DesktopAccessibilityBackend};
 pub use external_interface::DesktopExternalInterfaceProvider;
 pub use fscommand::DesktopFSCommandProvider;
 pub use navigator::DesktopNavigatorInterface;
 pub use navigator::PathAllowList;
+pub use process::DesktopProcessBackend;
 pub use ui::DesktopUiBackend;
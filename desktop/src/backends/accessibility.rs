@@ -0,0 +1,187 @@
+use accesskit::{Action, Node, NodeId, Rect, Role, Tree, TreeUpdate};
+use accesskit_winit::Adapter;
+use ruffle_core::backend::accessibility::{
+    AccessibilityAction, AccessibilityBackend, AccessibilityNode, AccessibilityNodeId,
+    AccessibilityRequest, AccessibilityRole, AccessibilityTree,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use winit::event::WindowEvent;
+use winit::event_loop::{ActiveEventLoop, EventLoopProxy};
+use winit::window::Window;
+
+use crate::custom_event::RuffleEvent;
+
+struct AccessKitState {
+    adapter: Adapter,
+
+    /// Whether an assistive technology has asked for the tree.
+    is_active: bool,
+
+    /// The latest tree of the movie, which is sent again whenever it's requested.
+    tree: Option<TreeUpdate>,
+
+    requests: Vec<AccessibilityRequest>,
+
+    /// The height of the menu bar above the movie, in physical pixels.
+    height_offset: f64,
+}
+
+/// Connects the window to assistive technologies through AccessKit.
+///
+/// This is owned by the event loop, which forwards window and AccessKit
+/// events to it, and shared with the [`DesktopAccessibilityBackend`] of
+/// the player.
+#[derive(Clone)]
+pub struct AccessKitBridge(Rc<RefCell<AccessKitState>>);
+
+impl AccessKitBridge {
+    /// Creates the AccessKit adapter of the window, which must not have been shown yet.
+    pub fn new(
+        event_loop: &ActiveEventLoop,
+        window: &Window,
+        event_loop_proxy: EventLoopProxy<RuffleEvent>,
+    ) -> Self {
+        Self(Rc::new(RefCell::new(AccessKitState {
+            adapter: Adapter::with_event_loop_proxy(event_loop, window, event_loop_proxy),
+            is_active: false,
+            tree: None,
+            requests: Vec::new(),
+            height_offset: 0.0,
+        })))
+    }
+
+    pub fn process_window_event(&self, window: &Window, event: &WindowEvent) {
+        self.0.borrow_mut().adapter.process_event(window, event);
+    }
+
+    pub fn handle_event(&self, event: accesskit_winit::WindowEvent) {
+        let mut state = self.0.borrow_mut();
+        match event {
+            accesskit_winit::WindowEvent::InitialTreeRequested => {
+                state.is_active = true;
+                let tree = state.tree.clone().unwrap_or_else(empty_tree);
+                state.adapter.update_if_active(|| tree);
+            }
+            accesskit_winit::WindowEvent::ActionRequested(request) => {
+                let action = match request.action {
+                    Action::Focus => AccessibilityAction::Focus,
+                    Action::Click => AccessibilityAction::Click,
+                    _ => return,
+                };
+                state.requests.push(AccessibilityRequest {
+                    node: AccessibilityNodeId(request.target.0),
+                    action,
+                });
+            }
+            accesskit_winit::WindowEvent::AccessibilityDeactivated => {
+                state.is_active = false;
+                state.tree = None;
+            }
+        }
+    }
+
+    pub fn set_height_offset(&self, height_offset: f64) {
+        self.0.borrow_mut().height_offset = height_offset;
+    }
+}
+
+/// The tree exposed while no movie is playing.
+fn empty_tree() -> TreeUpdate {
+    let root = NodeId(0);
+    TreeUpdate {
+        nodes: vec![(root, Node::new(Role::Window))],
+        tree: Some(Tree::new(root)),
+        focus: root,
+    }
+}
+
+fn add_node(node: &AccessibilityNode, height_offset: f64, nodes: &mut Vec<(NodeId, Node)>) {
+    let mut output = Node::new(match node.role {
+        AccessibilityRole::Movie => Role::Window,
+        AccessibilityRole::Button => Role::Button,
+        AccessibilityRole::Text => Role::Label,
+        AccessibilityRole::InputText => Role::TextInput,
+        AccessibilityRole::Graphic => Role::Image,
+    });
+    match node.role {
+        AccessibilityRole::Button => {
+            output.add_action(Action::Focus);
+            output.add_action(Action::Click);
+        }
+        AccessibilityRole::InputText => output.add_action(Action::Focus),
+        _ => {}
+    }
+
+    if !node.name.is_empty() {
+        output.set_label(node.name.as_str());
+    }
+    if !node.description.is_empty() {
+        output.set_description(node.description.as_str());
+    }
+    if !node.shortcut.is_empty() {
+        output.set_keyboard_shortcut(node.shortcut.as_str());
+    }
+    if let Some(value) = &node.value {
+        output.set_value(value.as_str());
+    }
+    output.set_bounds(Rect {
+        x0: node.bounds.x_min.to_pixels(),
+        y0: node.bounds.y_min.to_pixels() + height_offset,
+        x1: node.bounds.x_max.to_pixels(),
+        y1: node.bounds.y_max.to_pixels() + height_offset,
+    });
+    output.set_children(
+        node.children
+            .iter()
+            .map(|child| NodeId(child.id.0))
+            .collect::<Vec<_>>(),
+    );
+    nodes.push((NodeId(node.id.0), output));
+
+    for child in &node.children {
+        add_node(child, height_offset, nodes);
+    }
+}
+
+/// Publishes the accessibility tree of the movie through the [`AccessKitBridge`] of the window.
+///
+/// AccessKit has no notion of a tab order, so only the focus is exposed;
+/// assistive technologies follow it as the movie handles the tab key itself.
+pub struct DesktopAccessibilityBackend {
+    bridge: AccessKitBridge,
+}
+
+impl DesktopAccessibilityBackend {
+    pub fn new(bridge: AccessKitBridge) -> Self {
+        Self { bridge }
+    }
+}
+
+impl AccessibilityBackend for DesktopAccessibilityBackend {
+    fn is_supported(&self) -> bool {
+        true
+    }
+
+    fn is_active(&self) -> bool {
+        self.bridge.0.borrow().is_active
+    }
+
+    fn update_tree(&mut self, tree: &AccessibilityTree) {
+        let mut state = self.bridge.0.borrow_mut();
+        let mut nodes = Vec::new();
+        add_node(&tree.root, state.height_offset, &mut nodes);
+        let update = TreeUpdate {
+            nodes,
+            tree: Some(Tree::new(NodeId(tree.root.id.0))),
+            focus: NodeId(tree.focus.unwrap_or(tree.root.id).0),
+        };
+
+        state.tree = Some(update.clone());
+        state.adapter.update_if_active(|| update);
+    }
+
+    fn poll_requests(&mut self) -> Vec<AccessibilityRequest> {
+        std::mem::take(&mut self.bridge.0.borrow_mut().requests)
+    }
+}
@@ -0,0 +1,549 @@
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+
+use ruffle_core::backend::process::{
+    ProcessBackend, ProcessError, ProcessEvent, ProcessHandle, ProcessStartupInfo,
+};
+use tokio::sync::oneshot;
+use winit::event_loop::EventLoopProxy;
+
+use crate::cli::ProcessAccessMode;
+use crate::custom_event::RuffleEvent;
+use crate::gui::dialogs::process_access_dialog::{
+    ProcessAccessDialogConfiguration, ProcessAccessDialogResult,
+};
+use crate::gui::DialogDescriptor;
+
+enum Process {
+    /// Waiting for the user to allow the process to start.
+    Pending {
+        permission: oneshot::Receiver<ProcessAccessDialogResult>,
+        command: Command,
+        executable: PathBuf,
+        input: Vec<u8>,
+        input_closed: bool,
+    },
+    Running(RunningProcess),
+
+    /// The process never started, or was stopped before it could.
+    Finished,
+}
+
+struct RunningProcess {
+    child: Child,
+
+    /// Dropping this closes the standard input of the process.
+    input: Option<mpsc::Sender<Vec<u8>>>,
+
+    events: mpsc::Receiver<ProcessEvent>,
+
+    /// How many of stdout and stderr haven't been closed yet.
+    open_streams: u8,
+}
+
+impl RunningProcess {
+    fn spawn(command: &mut Command) -> std::io::Result<Self> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let (sender, events) = mpsc::channel();
+        if let Some(stdout) = child.stdout.take() {
+            let sender = sender.clone();
+            thread::spawn(move || {
+                read_stream(
+                    stdout,
+                    sender,
+                    ProcessEvent::StandardOutput,
+                    ProcessEvent::StandardOutputClosed,
+                )
+            });
+        }
+        if let Some(stderr) = child.stderr.take() {
+            thread::spawn(move || {
+                read_stream(
+                    stderr,
+                    sender,
+                    ProcessEvent::StandardError,
+                    ProcessEvent::StandardErrorClosed,
+                )
+            });
+        }
+
+        let input = child.stdin.take().map(|stdin| {
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || write_stream(stdin, receiver));
+            sender
+        });
+
+        Ok(Self {
+            child,
+            input,
+            events,
+            open_streams: 2,
+        })
+    }
+
+    /// Asks the process to exit by closing its standard input, or kills it if `force` is set.
+    ///
+    /// There's no portable way to signal a process to exit gracefully, but
+    /// most processes reading their input exit once it's closed.
+    fn exit(&mut self, force: bool) {
+        if !force {
+            self.input = None;
+        } else if let Err(e) = self.child.kill() {
+            tracing::warn!("Couldn't kill native process: {e}");
+        }
+    }
+
+    fn poll(&mut self, output: &mut Vec<ProcessEvent>) -> bool {
+        for event in self.events.try_iter() {
+            if matches!(
+                event,
+                ProcessEvent::StandardOutputClosed | ProcessEvent::StandardErrorClosed
+            ) {
+                self.open_streams -= 1;
+            }
+            output.push(event);
+        }
+
+        if self.open_streams > 0 {
+            return false;
+        }
+        match self.child.try_wait() {
+            Ok(Some(status)) => {
+                output.push(ProcessEvent::Exit(status.code()));
+                true
+            }
+            Ok(None) => false,
+            Err(e) => {
+                tracing::warn!("Couldn't get the status of a native process: {e}");
+                output.push(ProcessEvent::Exit(None));
+                true
+            }
+        }
+    }
+}
+
+impl Drop for RunningProcess {
+    fn drop(&mut self) {
+        // Processes don't outlive the content that started them.
+        if let Ok(None) = self.child.try_wait() {
+            if let Err(e) = self.child.kill() {
+                tracing::warn!("Couldn't kill native process: {e}");
+            }
+            let _ = self.child.wait();
+        }
+    }
+}
+
+fn read_stream(
+    mut stream: impl Read,
+    sender: mpsc::Sender<ProcessEvent>,
+    data: fn(Vec<u8>) -> ProcessEvent,
+    closed: ProcessEvent,
+) {
+    let mut buffer = [0; 4096];
+    loop {
+        match stream.read(&mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(read) => {
+                if sender.send(data(buffer[..read].to_vec())).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+    let _ = sender.send(closed);
+}
+
+fn write_stream(mut stdin: ChildStdin, receiver: mpsc::Receiver<Vec<u8>>) {
+    for data in receiver {
+        if stdin.write_all(&data).and_then(|_| stdin.flush()).is_err() {
+            break;
+        }
+    }
+}
+
+/// Whether an executable may be started.
+#[derive(Debug, PartialEq)]
+enum ProcessAccess {
+    /// The executable may be started right away.
+    Allowed,
+
+    /// The user has to be asked before starting the executable.
+    Ask,
+}
+
+/// Decides which executables may be started.
+///
+/// Executables given on the command line may always be started; depending on
+/// the [`ProcessAccessMode`], the user is asked about any other executable.
+struct ProcessAccessList {
+    mode: ProcessAccessMode,
+
+    /// Executables that may be started without asking,
+    /// including the ones the user allowed during this session.
+    allowed_executables: Vec<PathBuf>,
+}
+
+impl ProcessAccessList {
+    fn new(mode: ProcessAccessMode, allowed_executables: Vec<PathBuf>) -> Self {
+        Self {
+            mode,
+            allowed_executables: allowed_executables
+                .into_iter()
+                .filter_map(|path| path.canonicalize().ok())
+                .collect(),
+        }
+    }
+
+    fn is_supported(&self) -> bool {
+        self.mode != ProcessAccessMode::Deny
+    }
+
+    /// Resolves the given executable, and checks whether it may be started.
+    fn check(&self, executable: &str) -> Result<(PathBuf, ProcessAccess), ProcessError> {
+        if !self.is_supported() {
+            return Err(ProcessError::Unsupported);
+        }
+
+        let executable = Path::new(executable)
+            .canonicalize()
+            .map_err(|_| ProcessError::InvalidExecutable)?;
+        if !executable.is_file() {
+            return Err(ProcessError::InvalidExecutable);
+        }
+
+        if self.allowed_executables.contains(&executable) {
+            return Ok((executable, ProcessAccess::Allowed));
+        }
+
+        if self.mode == ProcessAccessMode::AllowListed {
+            tracing::warn!(
+                "Refusing to start {}, as it isn't allowed with --process-allow",
+                executable.display()
+            );
+            return Err(ProcessError::InvalidExecutable);
+        }
+
+        Ok((executable, ProcessAccess::Ask))
+    }
+
+    /// Allows starting the given executable without asking for the rest of the session.
+    fn allow(&mut self, executable: PathBuf) {
+        if !self.allowed_executables.contains(&executable) {
+            self.allowed_executables.push(executable);
+        }
+    }
+}
+
+/// Starts native processes for AIR content, as allowed by the user.
+pub struct DesktopProcessBackend {
+    event_loop: EventLoopProxy<RuffleEvent>,
+
+    access: ProcessAccessList,
+
+    processes: HashMap<ProcessHandle, Process>,
+
+    next_handle: u32,
+}
+
+impl DesktopProcessBackend {
+    pub fn new(
+        event_loop: EventLoopProxy<RuffleEvent>,
+        mode: ProcessAccessMode,
+        allowed_executables: Vec<PathBuf>,
+    ) -> Self {
+        Self {
+            event_loop,
+            access: ProcessAccessList::new(mode, allowed_executables),
+            processes: HashMap::new(),
+            next_handle: 0,
+        }
+    }
+
+    fn add(&mut self, process: Process) -> ProcessHandle {
+        let handle = ProcessHandle(self.next_handle);
+        self.next_handle = self.next_handle.wrapping_add(1);
+        self.processes.insert(handle, process);
+        handle
+    }
+
+    fn ask_for_process_access(
+        &self,
+        executable: &Path,
+        arguments: Vec<String>,
+    ) -> oneshot::Receiver<ProcessAccessDialogResult> {
+        let (notifier, receiver) = oneshot::channel();
+        let _ =
+            self.event_loop
+                .send_event(RuffleEvent::OpenDialog(DialogDescriptor::ProcessAccess(
+                    ProcessAccessDialogConfiguration::new(
+                        notifier,
+                        executable.to_path_buf(),
+                        arguments,
+                    ),
+                )));
+        receiver
+    }
+
+    /// Starts a process once the user has answered, returning `true` if it won't start.
+    fn poll_pending(&mut self, handle: ProcessHandle) -> bool {
+        let Some(Process::Pending { permission, .. }) = self.processes.get_mut(&handle) else {
+            return false;
+        };
+        let allowed = match permission.try_recv() {
+            Ok(result) => result == ProcessAccessDialogResult::Allow,
+            Err(oneshot::error::TryRecvError::Empty) => return false,
+            Err(oneshot::error::TryRecvError::Closed) => false,
+        };
+
+        let Some(Process::Pending {
+            mut command,
+            executable,
+            input,
+            input_closed,
+            ..
+        }) = self.processes.remove(&handle)
+        else {
+            return true;
+        };
+        if !allowed {
+            tracing::warn!("The user didn't allow starting {}", executable.display());
+            return true;
+        }
+        self.access.allow(executable);
+
+        match RunningProcess::spawn(&mut command) {
+            Ok(mut process) => {
+                if let Some(sender) = &process.input {
+                    if !input.is_empty() {
+                        let _ = sender.send(input);
+                    }
+                }
+                if input_closed {
+                    process.input = None;
+                }
+                self.processes.insert(handle, Process::Running(process));
+                false
+            }
+            Err(e) => {
+                tracing::error!("Couldn't start native process: {e}");
+                true
+            }
+        }
+    }
+}
+
+impl ProcessBackend for DesktopProcessBackend {
+    fn is_supported(&self) -> bool {
+        self.access.is_supported()
+    }
+
+    fn start(&mut self, info: ProcessStartupInfo) -> Result<ProcessHandle, ProcessError> {
+        let (executable, access) = self.access.check(&info.executable)?;
+
+        let mut command = Command::new(&executable);
+        command.args(&info.arguments);
+        if let Some(working_directory) = &info.working_directory {
+            command.current_dir(working_directory);
+        }
+
+        if access == ProcessAccess::Allowed {
+            let process = RunningProcess::spawn(&mut command).map_err(|e| {
+                tracing::error!("Couldn't start native process: {e}");
+                ProcessError::InvalidExecutable
+            })?;
+            return Ok(self.add(Process::Running(process)));
+        }
+
+        let permission = self.ask_for_process_access(&executable, info.arguments);
+        Ok(self.add(Process::Pending {
+            permission,
+            command,
+            executable,
+            input: Vec::new(),
+            input_closed: false,
+        }))
+    }
+
+    fn write_standard_input(&mut self, handle: ProcessHandle, data: &[u8]) {
+        match self.processes.get_mut(&handle) {
+            Some(Process::Pending {
+                input,
+                input_closed: false,
+                ..
+            }) => input.extend_from_slice(data),
+            Some(Process::Running(RunningProcess {
+                input: Some(sender),
+                ..
+            })) => {
+                let _ = sender.send(data.to_vec());
+            }
+            _ => {}
+        }
+    }
+
+    fn close_standard_input(&mut self, handle: ProcessHandle) {
+        match self.processes.get_mut(&handle) {
+            Some(Process::Pending { input_closed, .. }) => *input_closed = true,
+            Some(Process::Running(process)) => process.input = None,
+            _ => {}
+        }
+    }
+
+    fn exit(&mut self, handle: ProcessHandle, force: bool) {
+        match self.processes.get_mut(&handle) {
+            Some(process @ Process::Pending { .. }) => *process = Process::Finished,
+            Some(Process::Running(process)) => process.exit(force),
+            _ => {}
+        }
+    }
+
+    fn poll(&mut self, handle: ProcessHandle, output: &mut Vec<ProcessEvent>) {
+        if self.poll_pending(handle) {
+            self.processes.insert(handle, Process::Finished);
+        }
+
+        let finished = match self.processes.get_mut(&handle) {
+            Some(Process::Running(process)) => process.poll(output),
+            Some(Process::Finished) => {
+                output.push(ProcessEvent::Exit(None));
+                true
+            }
+            Some(Process::Pending { .. }) | None => false,
+        };
+        if finished {
+            self.processes.remove(&handle);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(unix)]
+    use std::time::{Duration, Instant};
+
+    fn listed_executable() -> PathBuf {
+        std::env::current_exe().expect("test executable has a path")
+    }
+
+    fn unlisted_executable() -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "ruffle_process_test_{}_{:?}",
+            std::process::id(),
+            thread::current().id()
+        ));
+        std::fs::write(&path, b"").expect("temporary file can be created");
+        path
+    }
+
+    fn check(list: &ProcessAccessList, executable: &Path) -> Result<ProcessAccess, ProcessError> {
+        list.check(&executable.to_string_lossy())
+            .map(|(_, access)| access)
+    }
+
+    #[test]
+    fn deny_mode() {
+        let list = ProcessAccessList::new(ProcessAccessMode::Deny, vec![listed_executable()]);
+
+        assert!(!list.is_supported());
+        assert!(matches!(
+            check(&list, &listed_executable()),
+            Err(ProcessError::Unsupported)
+        ));
+    }
+
+    #[test]
+    fn allow_listed_mode() {
+        let unlisted = unlisted_executable();
+        let list =
+            ProcessAccessList::new(ProcessAccessMode::AllowListed, vec![listed_executable()]);
+
+        assert!(list.is_supported());
+        assert_eq!(
+            ProcessAccess::Allowed,
+            check(&list, &listed_executable()).unwrap()
+        );
+        assert!(matches!(
+            check(&list, &unlisted),
+            Err(ProcessError::InvalidExecutable)
+        ));
+
+        let _ = std::fs::remove_file(unlisted);
+    }
+
+    #[test]
+    fn ask_mode() {
+        let unlisted = unlisted_executable();
+        let mut list = ProcessAccessList::new(ProcessAccessMode::Ask, vec![listed_executable()]);
+
+        assert_eq!(
+            ProcessAccess::Allowed,
+            check(&list, &listed_executable()).unwrap()
+        );
+        assert_eq!(ProcessAccess::Ask, check(&list, &unlisted).unwrap());
+
+        let (resolved, _) = list.check(&unlisted.to_string_lossy()).unwrap();
+        list.allow(resolved);
+        assert_eq!(ProcessAccess::Allowed, check(&list, &unlisted).unwrap());
+
+        let _ = std::fs::remove_file(unlisted);
+    }
+
+    #[test]
+    fn invalid_executables() {
+        let list = ProcessAccessList::new(ProcessAccessMode::Ask, vec![]);
+
+        assert!(matches!(
+            check(&list, &std::env::temp_dir()),
+            Err(ProcessError::InvalidExecutable)
+        ));
+        assert!(matches!(
+            check(&list, Path::new("/this/executable/does/not/exist")),
+            Err(ProcessError::InvalidExecutable)
+        ));
+    }
+
+    /// Polls the process until it exits, returning its exit code.
+    #[cfg(unix)]
+    fn wait_for_exit(process: &mut RunningProcess) -> Option<i32> {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut events = Vec::new();
+        while Instant::now() < deadline {
+            if process.poll(&mut events) {
+                return match events.last() {
+                    Some(ProcessEvent::Exit(code)) => *code,
+                    _ => panic!("process finished without an exit event"),
+                };
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("process didn't exit in time");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn exit_closes_standard_input() {
+        let mut process = RunningProcess::spawn(&mut Command::new("cat")).unwrap();
+        process.exit(false);
+        assert_eq!(Some(0), wait_for_exit(&mut process));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn forced_exit_kills() {
+        let mut process = RunningProcess::spawn(Command::new("sleep").arg("60")).unwrap();
+        process.exit(true);
+        // Killed processes have no exit code.
+        assert_eq!(None, wait_for_exit(&mut process));
+    }
+}
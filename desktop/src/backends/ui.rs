@@ -11,18 +11,24 @@ use rfd::{
     AsyncFileDialog, FileHandle, MessageButtons, MessageDialog, MessageDialogResult, MessageLevel,
 };
 use ruffle_core::backend::ui::{
+    ClipboardData, ClipboardFormat, CustomCursor, CustomCursorId, DeviceFontDescriptor,
     DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend,
+    FullscreenError, LanguageIdentifier, MemoryClipboard, MouseCursor, UiBackend, WindowBounds,
+    WindowDisplayState, WindowEdge,
 };
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::path::Path;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::io::AsyncReadExt;
 use tracing::error;
 use url::Url;
-use winit::event_loop::EventLoopProxy;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event_loop::{ActiveEventLoop, EventLoopProxy};
 use winit::raw_window_handle::HasDisplayHandle;
-use winit::window::{Fullscreen, Window};
+use winit::window::{Fullscreen, ResizeDirection, Window};
 
 pub struct DesktopFileDialogResult {
     handle: Option<FileHandle>,
@@ -143,13 +149,31 @@ impl FileDialogResult for DesktopFileDialogResult {
     }
 }
 
+/// A custom cursor registered by the movie.
+struct RegisteredCursor {
+    cursor: CustomCursor,
+
+    /// The frames of the cursor, once they have been created by the event loop.
+    frames: Vec<winit::window::CustomCursor>,
+}
+
 pub struct DesktopUiBackend {
     window: Arc<Window>,
     event_loop: EventLoopProxy<RuffleEvent>,
     cursor_visible: bool,
     clipboard: Clipboard,
+    /// Used for the clipboard formats that `clipboard` doesn't support (HTML and images).
+    rich_clipboard: Option<arboard::Clipboard>,
+    /// The data we last placed on the clipboard, in every format, including those
+    /// that can't be represented on the system clipboard.
+    owned_clipboard: MemoryClipboard,
+    /// The text the system clipboard held after we last wrote to it,
+    /// used to tell whether another application has replaced our data since.
+    owned_clipboard_text: Option<String>,
     preferences: GlobalPreferences,
     preferred_cursor: MouseCursor,
+    preferred_cursor_since: Instant,
+    custom_cursors: HashMap<CustomCursorId, RegisteredCursor>,
     font_database: Rc<fontdb::Database>,
     file_picker: FilePicker,
 }
@@ -171,18 +195,107 @@ impl DesktopUiBackend {
                 .ok()
                 .map(|handle| handle.as_raw()),
         );
+        let rich_clipboard = arboard::Clipboard::new()
+            .inspect_err(|e| tracing::warn!("Rich clipboard formats are unavailable: {e}"))
+            .ok();
         Ok(Self {
             window,
             event_loop,
             cursor_visible: true,
             clipboard,
+            rich_clipboard,
+            owned_clipboard: MemoryClipboard::new(),
+            owned_clipboard_text: None,
             preferences,
             preferred_cursor: MouseCursor::Arrow,
+            preferred_cursor_since: Instant::now(),
+            custom_cursors: HashMap::new(),
             font_database,
             file_picker,
         })
     }
 
+    /// Finds the installed font face best matching a requested device font,
+    /// similarly to how fontconfig matches patterns.
+    ///
+    /// The name may be a family name, a generic family (such as `sans-serif`),
+    /// or the full or PostScript name of a single face (such as `Arial Bold`).
+    /// Names are compared case-insensitively, and trailing style words
+    /// are ignored if nothing matches the name as given.
+    fn find_device_font(&self, name: &str, is_bold: bool, is_italic: bool) -> Option<fontdb::ID> {
+        let query_family = |family: Family| {
+            self.font_database.query(&fontdb::Query {
+                families: &[family],
+                weight: if is_bold {
+                    fontdb::Weight::BOLD
+                } else {
+                    fontdb::Weight::NORMAL
+                },
+                style: if is_italic {
+                    fontdb::Style::Italic
+                } else {
+                    fontdb::Style::Normal
+                },
+                ..Default::default()
+            })
+        };
+
+        let generic_family = match name.to_ascii_lowercase().as_str() {
+            "sans-serif" | "sans" => Some(Family::SansSerif),
+            "serif" => Some(Family::Serif),
+            "monospace" | "mono" => Some(Family::Monospace),
+            "cursive" => Some(Family::Cursive),
+            "fantasy" => Some(Family::Fantasy),
+            _ => None,
+        };
+        if let Some(family) = generic_family {
+            return query_family(family);
+        }
+
+        if let Some(id) = query_family(Family::Name(name)) {
+            return Some(id);
+        }
+
+        let mut name = name.trim();
+        loop {
+            // Family names differing only in case.
+            let family = self.font_database.faces().find_map(|face| {
+                face.families
+                    .iter()
+                    .find(|(family, _)| family.eq_ignore_ascii_case(name))
+                    .map(|(family, _)| family.as_str())
+            });
+            if let Some(id) = family.and_then(|family| query_family(Family::Name(family))) {
+                return Some(id);
+            }
+
+            // The name of a single face, which already implies its style.
+            let compact_name: String = name.chars().filter(|c| !c.is_whitespace()).collect();
+            if let Some(face) = self.font_database.faces().find(|face| {
+                face.post_script_name.eq_ignore_ascii_case(&compact_name)
+                    || face
+                        .post_script_name
+                        .replace('-', "")
+                        .eq_ignore_ascii_case(&compact_name)
+            }) {
+                return Some(face.id);
+            }
+
+            // Retry without a trailing style word, such as in `Arial Bold`.
+            let (family, style) = name.rsplit_once([' ', '-'])?;
+            const STYLE_WORDS: [&str; 8] = [
+                "regular", "bold", "italic", "oblique", "medium", "light", "book", "normal",
+            ];
+            if !STYLE_WORDS
+                .iter()
+                .any(|word| style.eq_ignore_ascii_case(word))
+            {
+                return None;
+            }
+            name = family.trim_end();
+        }
+    }
+
     pub fn cursor(&self) -> egui::CursorIcon {
         if self.cursor_visible {
             match self.preferred_cursor {
@@ -190,11 +303,138 @@ impl DesktopUiBackend {
                 MouseCursor::Hand => egui::CursorIcon::PointingHand,
                 MouseCursor::IBeam => egui::CursorIcon::Text,
                 MouseCursor::Grab => egui::CursorIcon::Grab,
+                // Custom cursors are applied to the window directly, see `custom_cursor`.
+                MouseCursor::Custom(_) => egui::CursorIcon::Default,
             }
         } else {
             egui::CursorIcon::None
         }
     }
+
+    /// The current frame of the custom cursor to display, if any,
+    /// along with how long until the next frame should be displayed.
+    pub fn custom_cursor(&self) -> Option<(winit::window::CustomCursor, Option<Duration>)> {
+        let MouseCursor::Custom(id) = self.preferred_cursor else {
+            return None;
+        };
+        if !self.cursor_visible {
+            return None;
+        }
+
+        let registered = self.custom_cursors.get(&id)?;
+        let elapsed = self.preferred_cursor_since.elapsed();
+        let frame = registered
+            .frames
+            .get(registered.cursor.frame_at(elapsed))?
+            .clone();
+        let next_frame = registered.cursor.frame_duration().map(|frame_duration| {
+            let into_frame = elapsed.as_secs_f64() % frame_duration.as_secs_f64();
+            frame_duration.saturating_sub(Duration::from_secs_f64(into_frame))
+        });
+        Some((frame, next_frame))
+    }
+
+    /// Creates the window system cursors for any newly registered custom cursors.
+    pub fn create_custom_cursors(&mut self, event_loop: &ActiveEventLoop) {
+        for registered in self.custom_cursors.values_mut() {
+            if !registered.frames.is_empty() {
+                continue;
+            }
+
+            let cursor = &registered.cursor;
+            let (Ok(width), Ok(height), Ok(hotspot_x), Ok(hotspot_y)) = (
+                u16::try_from(cursor.width),
+                u16::try_from(cursor.height),
+                u16::try_from(cursor.hotspot.0),
+                u16::try_from(cursor.hotspot.1),
+            ) else {
+                error!(
+                    "Custom cursor of size {}x{} is too large",
+                    cursor.width, cursor.height
+                );
+                continue;
+            };
+
+            let frames = cursor
+                .frames
+                .iter()
+                .map(|frame| {
+                    winit::window::CustomCursor::from_rgba(
+                        frame.clone(),
+                        width,
+                        height,
+                        hotspot_x,
+                        hotspot_y,
+                    )
+                    .map(|source| event_loop.create_custom_cursor(source))
+                })
+                .collect::<Result<Vec<_>, _>>();
+            match frames {
+                Ok(frames) => registered.frames = frames,
+                Err(e) => error!("Couldn't create custom cursor: {e}"),
+            }
+        }
+    }
+
+    fn owns_clipboard(&mut self) -> bool {
+        let text = self.clipboard.get().unwrap_or_default();
+        self.owned_clipboard_text.as_ref() == Some(&text)
+    }
+
+    /// Places the data in `owned_clipboard` on the system clipboard, in the richest
+    /// format it supports. Plain text is included whenever possible.
+    fn write_clipboard(&mut self) {
+        let text = match (
+            self.owned_clipboard.get(ClipboardFormat::Text),
+            self.owned_clipboard.get(ClipboardFormat::Url),
+            self.owned_clipboard.get(ClipboardFormat::FileList),
+        ) {
+            (Some(ClipboardData::Text(text)), _, _) | (_, Some(ClipboardData::Url(text)), _) => {
+                text.clone()
+            }
+            (_, _, Some(ClipboardData::FileList(paths))) => paths.join("\n"),
+            _ => String::new(),
+        };
+
+        let mut written_text = None;
+        if let Some(clipboard) = &mut self.rich_clipboard {
+            match (
+                self.owned_clipboard.get(ClipboardFormat::Bitmap),
+                self.owned_clipboard.get(ClipboardFormat::Html),
+            ) {
+                (
+                    Some(ClipboardData::Bitmap {
+                        width,
+                        height,
+                        rgba,
+                    }),
+                    _,
+                ) => {
+                    let image = arboard::ImageData {
+                        width: *width as usize,
+                        height: *height as usize,
+                        bytes: Cow::Borrowed(rgba),
+                    };
+                    if clipboard.set_image(image).is_ok() {
+                        // Images can't be accompanied by text.
+                        written_text = Some(String::new());
+                    }
+                }
+                (_, Some(ClipboardData::Html(html))) => {
+                    if clipboard.set_html(html, Some(&text)).is_ok() {
+                        written_text = Some(text.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let written_text = written_text.unwrap_or_else(|| {
+            self.clipboard.set_text(text.clone());
+            text
+        });
+        self.owned_clipboard_text = Some(written_text);
+    }
 }
 
 impl UiBackend for DesktopUiBackend {
@@ -207,7 +447,30 @@ impl UiBackend for DesktopUiBackend {
     }
 
     fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
-        self.preferred_cursor = cursor;
+        if cursor != self.preferred_cursor {
+            self.preferred_cursor = cursor;
+            self.preferred_cursor_since = Instant::now();
+        }
+    }
+
+    fn supports_custom_cursors(&self) -> bool {
+        true
+    }
+
+    fn register_mouse_cursor(&mut self, id: CustomCursorId, cursor: CustomCursor) {
+        self.custom_cursors.insert(
+            id,
+            RegisteredCursor {
+                cursor,
+                frames: Vec::new(),
+            },
+        );
+        // Cursors can only be created with access to the event loop.
+        let _ = self.event_loop.send_event(RuffleEvent::CreateCustomCursors);
+    }
+
+    fn unregister_mouse_cursor(&mut self, id: CustomCursorId) {
+        self.custom_cursors.remove(&id);
     }
 
     fn clipboard_content(&mut self) -> String {
@@ -215,7 +478,72 @@ impl UiBackend for DesktopUiBackend {
     }
 
     fn set_clipboard_content(&mut self, content: String) {
-        self.clipboard.set_text(content);
+        self.owned_clipboard.clear();
+        if !content.is_empty() {
+            self.owned_clipboard.set(ClipboardData::Text(content));
+        }
+        self.write_clipboard();
+    }
+
+    fn clipboard_formats(&mut self) -> Vec<ClipboardFormat> {
+        if self.owns_clipboard() {
+            return self.owned_clipboard.formats();
+        }
+
+        let mut formats = Vec::new();
+        if !self.clipboard_content().is_empty() {
+            formats.push(ClipboardFormat::Text);
+        }
+        if let Some(clipboard) = &mut self.rich_clipboard {
+            if clipboard.get_image().is_ok() {
+                formats.push(ClipboardFormat::Bitmap);
+            }
+        }
+        formats
+    }
+
+    fn clipboard_data(&mut self, format: ClipboardFormat) -> Option<ClipboardData> {
+        if self.owns_clipboard() {
+            return self.owned_clipboard.get(format).cloned();
+        }
+
+        match format {
+            ClipboardFormat::Text => {
+                let text = self.clipboard_content();
+                (!text.is_empty()).then_some(ClipboardData::Text(text))
+            }
+            ClipboardFormat::Bitmap => {
+                let image = self.rich_clipboard.as_mut()?.get_image().ok()?;
+                Some(ClipboardData::Bitmap {
+                    width: image.width as u32,
+                    height: image.height as u32,
+                    rgba: image.bytes.into_owned(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn set_clipboard_data(&mut self, data: ClipboardData) {
+        if !self.owns_clipboard() {
+            // Don't mix our data with whatever another application left behind.
+            self.owned_clipboard.clear();
+        }
+        self.owned_clipboard.set(data);
+        self.write_clipboard();
+    }
+
+    fn clear_clipboard_format(&mut self, format: ClipboardFormat) {
+        if !self.owns_clipboard() {
+            self.owned_clipboard.clear();
+        }
+        self.owned_clipboard.remove(format);
+        self.write_clipboard();
+    }
+
+    fn clear_clipboard(&mut self) {
+        self.owned_clipboard.clear();
+        self.write_clipboard();
     }
 
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
@@ -299,49 +627,62 @@ impl UiBackend for DesktopUiBackend {
         is_italic: bool,
         register: &mut dyn FnMut(FontDefinition),
     ) {
-        let query = fontdb::Query {
-            families: &[Family::Name(name)],
-            weight: if is_bold {
-                fontdb::Weight::BOLD
-            } else {
-                fontdb::Weight::NORMAL
-            },
-            style: if is_italic {
-                fontdb::Style::Italic
-            } else {
-                fontdb::Style::Normal
-            },
-            ..Default::default()
+        let Some(face) = self
+            .find_device_font(name, is_bold, is_italic)
+            .and_then(|id| self.font_database.face(id))
+        else {
+            return;
         };
+        tracing::info!(
+            "Loading device font \"{}\" for \"{name}\" (italic: {is_italic}, bold: {is_bold})",
+            face.post_script_name
+        );
 
-        // It'd be nice if we can get the full list of candidates... Feature request?
-        if let Some(id) = self.font_database.query(&query) {
-            if let Some(face) = self.font_database.face(id) {
-                tracing::info!("Loading device font \"{}\" for \"{name}\" (italic: {is_italic}, bold: {is_bold})", face.post_script_name);
-
-                match &face.source {
-                    fontdb::Source::File(path) => match std::fs::read(path) {
-                        Ok(bytes) => register(FontDefinition::FontFile {
-                            name: name.to_owned(),
-                            is_bold,
-                            is_italic,
-                            data: bytes,
-                            index: face.index,
-                        }),
-                        Err(e) => error!("Couldn't read font file at {path:?}: {e}"),
-                    },
-                    fontdb::Source::Binary(bin) | fontdb::Source::SharedFile(_, bin) => {
-                        register(FontDefinition::FontFile {
-                            name: name.to_owned(),
-                            is_bold,
-                            is_italic,
-                            data: bin.as_ref().as_ref().to_vec(),
-                            index: face.index,
-                        })
-                    }
-                };
+        match &face.source {
+            fontdb::Source::File(path) => match std::fs::read(path) {
+                Ok(bytes) => register(FontDefinition::FontFile {
+                    name: name.to_owned(),
+                    is_bold,
+                    is_italic,
+                    data: bytes,
+                    index: face.index,
+                }),
+                Err(e) => error!("Couldn't read font file at {path:?}: {e}"),
+            },
+            fontdb::Source::Binary(bin) | fontdb::Source::SharedFile(_, bin) => {
+                register(FontDefinition::FontFile {
+                    name: name.to_owned(),
+                    is_bold,
+                    is_italic,
+                    data: bin.as_ref().as_ref().to_vec(),
+                    index: face.index,
+                })
             }
-        }
+        };
+    }
+
+    fn device_fonts(&self) -> Vec<DeviceFontDescriptor> {
+        let mut fonts: Vec<DeviceFontDescriptor> = self
+            .font_database
+            .faces()
+            .filter_map(|face| {
+                let (family, _) = face.families.first()?;
+                Some(DeviceFontDescriptor {
+                    name: family.clone(),
+                    is_bold: face.weight >= fontdb::Weight::SEMIBOLD,
+                    is_italic: face.style != fontdb::Style::Normal,
+                })
+            })
+            .collect();
+        fonts.sort_by(|a, b| {
+            (a.name.to_lowercase(), a.is_bold, a.is_italic).cmp(&(
+                b.name.to_lowercase(),
+                b.is_bold,
+                b.is_italic,
+            ))
+        });
+        fonts.dedup();
+        fonts
     }
 
     // Unused on desktop
@@ -400,4 +741,79 @@ impl UiBackend for DesktopUiBackend {
     }
 
     fn close_file_dialog(&mut self) {}
+
+    fn window_bounds(&self) -> Option<WindowBounds> {
+        // Not every platform lets us know where the window is (e.g. Wayland).
+        let position = self.window.outer_position().unwrap_or_default();
+        let size = self.window.outer_size();
+        Some(WindowBounds {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        })
+    }
+
+    fn set_window_bounds(&mut self, bounds: WindowBounds) {
+        self.window
+            .set_outer_position(PhysicalPosition::new(bounds.x, bounds.y));
+
+        // winit can only resize the inside of the window, so account for the decorations.
+        let outer_size = self.window.outer_size();
+        let inner_size = self.window.inner_size();
+        let _ = self.window.request_inner_size(PhysicalSize::new(
+            bounds
+                .width
+                .saturating_sub(outer_size.width.saturating_sub(inner_size.width)),
+            bounds
+                .height
+                .saturating_sub(outer_size.height.saturating_sub(inner_size.height)),
+        ));
+    }
+
+    fn window_display_state(&self) -> WindowDisplayState {
+        if self.window.is_minimized().unwrap_or_default() {
+            WindowDisplayState::Minimized
+        } else if self.window.is_maximized() {
+            WindowDisplayState::Maximized
+        } else {
+            WindowDisplayState::Normal
+        }
+    }
+
+    fn set_window_display_state(&mut self, state: WindowDisplayState) {
+        match state {
+            WindowDisplayState::Normal => {
+                self.window.set_minimized(false);
+                self.window.set_maximized(false);
+            }
+            WindowDisplayState::Maximized => {
+                self.window.set_minimized(false);
+                self.window.set_maximized(true);
+            }
+            WindowDisplayState::Minimized => self.window.set_minimized(true),
+        }
+    }
+
+    fn start_window_move(&mut self) -> bool {
+        self.window.drag_window().is_ok()
+    }
+
+    fn start_window_resize(&mut self, edge: WindowEdge) -> bool {
+        let direction = match edge {
+            WindowEdge::Top => ResizeDirection::North,
+            WindowEdge::Bottom => ResizeDirection::South,
+            WindowEdge::Left => ResizeDirection::West,
+            WindowEdge::Right => ResizeDirection::East,
+            WindowEdge::TopLeft => ResizeDirection::NorthWest,
+            WindowEdge::TopRight => ResizeDirection::NorthEast,
+            WindowEdge::BottomLeft => ResizeDirection::SouthWest,
+            WindowEdge::BottomRight => ResizeDirection::SouthEast,
+        };
+        self.window.drag_resize_window(direction).is_ok()
+    }
+
+    fn close_window(&mut self) {
+        let _ = self.event_loop.send_event(RuffleEvent::ExitRequested);
+    }
 }
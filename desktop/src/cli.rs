@@ -144,10 +144,14 @@ pub struct Opt {
     pub proxy: Option<Url>,
 
     /// Add an endpoint (`[host]:[port]`) to the socket whitelist.
+    ///
+    /// This covers both TCP connections and UDP datagrams sent to the endpoint.
     #[clap(long = "socket-allow", number_of_values = 1, action = clap::ArgAction::Append)]
     pub socket_allow: Vec<String>,
 
     /// Define how to deal with TCP Socket connections.
+    ///
+    /// This also applies to UDP datagrams sent to endpoints which aren't whitelisted.
     #[clap(long = "tcp-connections")]
     pub tcp_connections: Option<SocketMode>,
 
@@ -199,6 +203,21 @@ pub struct Opt {
     #[clap(long, default_value = "ask")]
     pub filesystem_access_mode: FilesystemAccessMode,
 
+    /// How to handle AIR content starting native processes.
+    #[clap(long, default_value = "deny")]
+    pub process_access_mode: ProcessAccessMode,
+
+    /// Add a device font to use for characters that the requested font has no glyphs for.
+    ///
+    /// Fallback fonts are tried in the order given. When none are given,
+    /// a list of common CJK, emoji and symbol fonts is used.
+    #[clap(long = "font-fallback", number_of_values = 1, action = clap::ArgAction::Append)]
+    pub font_fallback: Vec<String>,
+
+    /// Add an executable that AIR content is allowed to start as a native process.
+    #[clap(long = "process-allow", number_of_values = 1, action = clap::ArgAction::Append)]
+    pub process_allow: Vec<std::path::PathBuf>,
+
     /// Provide a dummy (completely empty) External Interface to the movie.
     /// This may break some movies that expect an External Interface to be functional,
     /// but may fix others that always require an External Interface.
@@ -243,6 +262,20 @@ pub struct Opt {
     /// (like inlining constant pool entries) can't be disabled.
     #[clap(long)]
     pub no_avm2_optimizer: bool,
+
+    /// Enforce the Flash Player security sandbox.
+    ///
+    /// Cross-domain data loads, socket connections, scripting and `BitmapData.draw`
+    /// are then only allowed when permitted by policy files or `Security.allowDomain`.
+    /// Use this when running untrusted third-party content.
+    #[clap(long)]
+    pub strict_sandbox: bool,
+
+    /// Arguments to pass to an AIR application, given after `--`.
+    ///
+    /// These are delivered through the `InvokeEvent` of `NativeApplication`.
+    #[clap(last = true, value_name = "AIR ARGUMENTS")]
+    pub air_arguments: Vec<String>,
 }
 
 fn parse_movie_file_or_url(path: &str) -> Result<Url, Error> {
@@ -505,3 +538,15 @@ pub enum FilesystemAccessMode {
     /// Ask the user before accessing the filesystem non-interactively.
     Ask,
 }
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum ProcessAccessMode {
+    /// Refuse to start any native process.
+    Deny,
+
+    /// Only start executables given with `--process-allow`.
+    AllowListed,
+
+    /// Start executables given with `--process-allow`, and ask the user about any other.
+    Ask,
+}
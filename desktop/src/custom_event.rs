@@ -36,6 +36,18 @@ pub enum RuffleEvent {
     /// The movie wants to open a dialog.
     OpenDialog(DialogDescriptor),
 
+    /// The movie registered custom cursors, which need to be created by the event loop.
+    CreateCustomCursors,
+
     /// Ruffle core has a notification to handle.
     PlayerNotification(PlayerNotification),
+
+    /// An assistive technology interacted with the window.
+    Accessibility(accesskit_winit::Event),
+}
+
+impl From<accesskit_winit::Event> for RuffleEvent {
+    fn from(event: accesskit_winit::Event) -> Self {
+        Self::Accessibility(event)
+    }
 }
@@ -22,7 +22,7 @@ use winit::dpi::{PhysicalPosition, PhysicalSize};
 use winit::event::WindowEvent;
 use winit::event_loop::EventLoopProxy;
 use winit::keyboard::{Key, NamedKey};
-use winit::window::{ImePurpose as WinitImePurpose, Theme, Window};
+use winit::window::{CursorIcon, ImePurpose as WinitImePurpose, Theme, Window};
 
 use super::{DialogDescriptor, FilePicker};
 
@@ -35,6 +35,7 @@ pub struct GuiController {
     window: Arc<Window>,
     last_update: Instant,
     repaint_after: Duration,
+    showing_custom_cursor: bool,
     surface: wgpu::Surface<'static>,
     surface_format: wgpu::TextureFormat,
     movie_view_renderer: Arc<MovieViewRenderer>,
@@ -142,6 +143,7 @@ impl GuiController {
             window,
             last_update: Instant::now(),
             repaint_after: Duration::ZERO,
+            showing_custom_cursor: false,
             surface,
             surface_format,
             movie_view_renderer,
@@ -328,17 +330,41 @@ impl GuiController {
             .repaint_delay;
 
         // If we're not in a UI, tell egui which cursor we prefer to use instead
+        let mut custom_cursor = None;
         if !self.egui_winit.egui_ctx().wants_pointer_input() {
             if let Some(player) = player.as_deref() {
-                full_output.platform_output.cursor_icon =
-                    <dyn Any>::downcast_ref::<DesktopUiBackend>(player.ui())
-                        .unwrap_or_else(|| panic!("UI Backend should be DesktopUiBackend"))
-                        .cursor();
+                let ui = <dyn Any>::downcast_ref::<DesktopUiBackend>(player.ui())
+                    .unwrap_or_else(|| panic!("UI Backend should be DesktopUiBackend"));
+                full_output.platform_output.cursor_icon = ui.cursor();
+                custom_cursor = ui.custom_cursor();
             }
         }
+        let cursor_icon = full_output.platform_output.cursor_icon;
         self.egui_winit
             .handle_platform_output(&self.window, full_output.platform_output);
 
+        // egui only knows about system cursors, so custom cursors are set on the window directly.
+        match custom_cursor {
+            Some((cursor, next_frame)) => {
+                self.window.set_cursor(cursor);
+                self.showing_custom_cursor = true;
+                if let Some(next_frame) = next_frame {
+                    self.repaint_after = self.repaint_after.min(next_frame);
+                }
+            }
+            None if self.showing_custom_cursor => {
+                // egui won't restore its cursor on its own, as it thinks it is still shown.
+                self.showing_custom_cursor = false;
+                self.window.set_cursor(match cursor_icon {
+                    egui::CursorIcon::PointingHand => CursorIcon::Pointer,
+                    egui::CursorIcon::Text => CursorIcon::Text,
+                    egui::CursorIcon::Grab => CursorIcon::Grab,
+                    _ => CursorIcon::Default,
+                });
+            }
+            None => {}
+        }
+
         let clipped_primitives = self
             .egui_winit
             .egui_ctx()
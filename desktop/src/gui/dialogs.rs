@@ -6,6 +6,7 @@ pub mod network_access_dialog;
 mod open_dialog;
 mod open_url_dialog;
 mod preferences_dialog;
+pub mod process_access_dialog;
 mod volume_controls;
 
 use crate::custom_event::RuffleEvent;
@@ -18,6 +19,7 @@ use network_access_dialog::{NetworkAccessDialog, NetworkAccessDialogConfiguratio
 use open_dialog::OpenDialog;
 use open_url_dialog::OpenUrlDialog;
 use preferences_dialog::PreferencesDialog;
+use process_access_dialog::{ProcessAccessDialog, ProcessAccessDialogConfiguration};
 use ruffle_core::Player;
 use std::{collections::VecDeque, sync::Weak};
 use unic_langid::LanguageIdentifier;
@@ -44,6 +46,7 @@ pub struct Dialogs {
     network_access_dialog_queue: VecDeque<NetworkAccessDialog>,
     filesystem_access_dialog: Option<FilesystemAccessDialog>,
     filesystem_access_dialog_queue: VecDeque<FilesystemAccessDialogConfiguration>,
+    process_access_dialog_queue: VecDeque<ProcessAccessDialog>,
 
     open_dialog: OpenDialog,
     is_open_dialog_visible: bool,
@@ -61,6 +64,7 @@ pub enum DialogDescriptor {
     ShowMessage(MessageDialogConfiguration),
     NetworkAccess(NetworkAccessDialogConfiguration),
     FilesystemAccess(FilesystemAccessDialogConfiguration),
+    ProcessAccess(ProcessAccessDialogConfiguration),
 }
 
 impl Dialogs {
@@ -82,6 +86,7 @@ impl Dialogs {
             network_access_dialog_queue: VecDeque::new(),
             filesystem_access_dialog: None,
             filesystem_access_dialog_queue: VecDeque::new(),
+            process_access_dialog_queue: VecDeque::new(),
 
             open_dialog: OpenDialog::new(
                 player_options,
@@ -114,6 +119,7 @@ impl Dialogs {
         self.network_access_dialog_queue.clear();
         self.filesystem_access_dialog = None;
         self.filesystem_access_dialog_queue.clear();
+        self.process_access_dialog_queue.clear();
     }
 
     pub fn recreate_open_dialog(
@@ -172,6 +178,9 @@ impl Dialogs {
             DialogDescriptor::FilesystemAccess(config) => {
                 self.filesystem_access_dialog_queue.push_back(config)
             }
+            DialogDescriptor::ProcessAccess(config) => self
+                .process_access_dialog_queue
+                .push_back(ProcessAccessDialog::new(config)),
         }
     }
 
@@ -191,6 +200,7 @@ impl Dialogs {
         self.show_message_dialog(locale, egui_ctx);
         self.show_network_access_dialog(locale, egui_ctx);
         self.show_filesystem_access_dialog(locale, egui_ctx);
+        self.show_process_access_dialog(locale, egui_ctx);
     }
 
     fn show_open_dialog(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
@@ -291,6 +301,21 @@ impl Dialogs {
         }
     }
 
+    fn show_process_access_dialog(
+        &mut self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+    ) {
+        let keep_open = if let Some(dialog) = &mut self.process_access_dialog_queue.front_mut() {
+            dialog.show(locale, egui_ctx)
+        } else {
+            true
+        };
+        if !keep_open {
+            self.process_access_dialog_queue.pop_front();
+        }
+    }
+
     fn show_filesystem_access_dialog(
         &mut self,
         locale: &LanguageIdentifier,
@@ -0,0 +1,112 @@
+use crate::gui::{text, text_with_args};
+use egui::{Align2, Ui, Window};
+use fluent_templates::fluent_bundle::FluentValue;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tokio::sync::oneshot::Sender;
+use unic_langid::LanguageIdentifier;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ProcessAccessDialogResult {
+    Allow,
+    Cancel,
+}
+
+pub struct ProcessAccessDialogConfiguration {
+    notifier: Option<Sender<ProcessAccessDialogResult>>,
+
+    /// Path of the executable to run.
+    executable: PathBuf,
+
+    arguments: Vec<String>,
+}
+
+impl ProcessAccessDialogConfiguration {
+    pub fn new(
+        notifier: Sender<ProcessAccessDialogResult>,
+        executable: PathBuf,
+        arguments: Vec<String>,
+    ) -> Self {
+        Self {
+            notifier: Some(notifier),
+            executable,
+            arguments,
+        }
+    }
+}
+
+pub struct ProcessAccessDialog {
+    config: ProcessAccessDialogConfiguration,
+}
+
+impl Drop for ProcessAccessDialog {
+    fn drop(&mut self) {
+        self.respond(ProcessAccessDialogResult::Cancel);
+    }
+}
+
+impl ProcessAccessDialog {
+    pub fn new(config: ProcessAccessDialogConfiguration) -> Self {
+        Self { config }
+    }
+
+    fn respond(&mut self, result: ProcessAccessDialogResult) {
+        if let Some(notifier) = std::mem::take(&mut self.config.notifier) {
+            let _ = notifier.send(result);
+        }
+    }
+
+    pub fn show(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) -> bool {
+        let mut keep_open = true;
+        let mut should_close = false;
+
+        Window::new(text(locale, "process-access-dialog-title"))
+            .open(&mut keep_open)
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .collapsible(false)
+            .resizable(false)
+            .show(egui_ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    should_close = self.render_window_contents(locale, ui)
+                });
+            });
+
+        keep_open && !should_close
+    }
+
+    pub fn render_window_contents(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) -> bool {
+        let mut should_close = false;
+
+        ui.label(text(locale, "process-access-dialog-message"));
+        ui.label("");
+        ui.monospace(self.config.executable.to_string_lossy());
+        if !self.config.arguments.is_empty() {
+            ui.label(text_with_args(
+                locale,
+                "process-access-dialog-arguments",
+                &HashMap::from([(
+                    "arguments".into(),
+                    FluentValue::String(self.config.arguments.join(" ").into()),
+                )]),
+            ));
+        }
+        ui.label("");
+
+        ui.horizontal(|ui| {
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui
+                    .button(text(locale, "process-access-dialog-allow"))
+                    .clicked()
+                {
+                    self.respond(ProcessAccessDialogResult::Allow);
+                    should_close = true;
+                }
+                if ui.button(text(locale, "dialog-cancel")).clicked() {
+                    should_close = true;
+                }
+            })
+        });
+
+        should_close
+    }
+}
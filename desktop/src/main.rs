@@ -30,6 +30,7 @@ use std::cell::RefCell;
 use std::env;
 use std::fs::File;
 use std::panic::PanicHookInfo;
+use std::process::ExitCode;
 use tracing_subscriber::fmt::Layer;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
@@ -149,7 +150,7 @@ fn shutdown() {
 }
 
 #[tokio::main]
-async fn main() -> Result<(), Error> {
+async fn main() -> Result<ExitCode, Error> {
     init();
 
     let opt = Opt::parse();
@@ -191,7 +192,9 @@ async fn main() -> Result<(), Error> {
     let result = App::new(preferences)
         .await
         .and_then(|(mut app, event_loop)| {
-            event_loop.run_app(&mut app).context("Event loop failure")
+            event_loop.run_app(&mut app).context("Event loop failure")?;
+            // Exit statuses are truncated to a byte, as on Unix.
+            Ok(ExitCode::from(app.exit_code() as u8))
         });
 
     #[cfg(windows)]
@@ -1,22 +1,26 @@
 use crate::backends::{
-    DesktopExternalInterfaceProvider, DesktopFSCommandProvider, DesktopNavigatorInterface,
-    DesktopUiBackend,
+    AccessKitBridge, DesktopAccessibilityBackend, DesktopExternalInterfaceProvider,
+    DesktopFSCommandProvider, DesktopNavigatorInterface, DesktopProcessBackend, DesktopUiBackend,
 };
-use crate::cli::FilesystemAccessMode;
 use crate::cli::GameModePreference;
+use crate::cli::{FilesystemAccessMode, ProcessAccessMode};
 use crate::custom_event::RuffleEvent;
 use crate::gui::{FilePicker, MovieView};
 use crate::preferences::GlobalPreferences;
 use crate::{CALLSTACK, RENDER_INFO, SWF_INFO};
 use anyhow::anyhow;
+use ruffle_core::backend::filesystem::FileSystemRoot;
 use ruffle_core::backend::navigator::SocketMode;
 use ruffle_core::config::Letterbox;
 use ruffle_core::events::{GamepadButton, KeyCode};
-use ruffle_core::{DefaultFont, LoadBehavior, Player, PlayerBuilder, PlayerEvent};
+use ruffle_core::{
+    AirApplication, DefaultFont, LoadBehavior, Player, PlayerBuilder, PlayerEvent, PlayerRuntime,
+};
 use ruffle_frontend_utils::backends::audio::CpalAudioBackend;
 use ruffle_frontend_utils::backends::executor::{AsyncExecutor, PollRequester};
+use ruffle_frontend_utils::backends::filesystem::DiskFileSystemBackend;
 use ruffle_frontend_utils::backends::navigator::ExternalNavigatorBackend;
-use ruffle_frontend_utils::bundle::source::BundleSourceError;
+use ruffle_frontend_utils::bundle::source::{BundleSource, BundleSourceError};
 use ruffle_frontend_utils::bundle::{Bundle, BundleError};
 use ruffle_frontend_utils::content::PlayingContent;
 use ruffle_frontend_utils::player_options::PlayerOptions;
@@ -37,6 +41,79 @@ use url::Url;
 use winit::event_loop::EventLoopProxy;
 use winit::window::Window;
 
+/// Creates the sandboxed filesystem exposed to AIR movies through `flash.filesystem`.
+///
+/// The application directory is the directory the movie was loaded from (or the content of its bundle),
+/// while the storage and documents directories are kept per movie next to its saved data.
+fn air_filesystem(opt: &LaunchOptions, content: &PlayingContent) -> DiskFileSystemBackend {
+    let mut filesystem = DiskFileSystemBackend::new();
+
+    let application_directory = match content {
+        PlayingContent::DirectFile(url) => url
+            .to_file_path()
+            .ok()
+            .and_then(|path| path.parent().map(ToOwned::to_owned)),
+        PlayingContent::Bundle(_, bundle) => match bundle.source() {
+            BundleSource::Directory(path) => Some(path.join("content")),
+            // Zipped bundles can't be exposed as a directory.
+            BundleSource::ZipFile(_) => None,
+        },
+    };
+    if let Some(directory) = application_directory {
+        filesystem = filesystem.with_root(FileSystemRoot::Application, directory);
+    }
+
+    // Keep the name safe to use as a single path component.
+    let movie_name: String = content
+        .name()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect();
+    let data_directory = opt.save_directory.join("AIR").join(movie_name);
+    filesystem
+        .with_root(
+            FileSystemRoot::ApplicationStorage,
+            data_directory.join("Local Store"),
+        )
+        .with_root(FileSystemRoot::Documents, data_directory.join("Documents"))
+}
+
+/// Where packaged AIR applications keep their application descriptor.
+const AIR_DESCRIPTOR_PATH: &str = "META-INF/AIR/application.xml";
+
+/// Describes how an AIR movie was launched, for `flash.desktop.NativeApplication`.
+///
+/// The application descriptor is read from the packaged location inside the content,
+/// or for a local movie, from the `<movie>-app.xml` file that AIR projects keep next to it.
+fn air_application(opt: &LaunchOptions, content: &PlayingContent) -> AirApplication {
+    let descriptor = match content {
+        PlayingContent::DirectFile(url) => url.to_file_path().ok().and_then(|path| {
+            let directory = path.parent()?;
+            let mut candidates = vec![directory.join(AIR_DESCRIPTOR_PATH)];
+            if let Some(stem) = path.file_stem() {
+                let mut name = stem.to_owned();
+                name.push("-app.xml");
+                candidates.push(directory.join(name));
+            }
+            candidates
+                .into_iter()
+                .find_map(|candidate| std::fs::read(candidate).ok())
+        }),
+        PlayingContent::Bundle(_, bundle) => bundle.source().read_content(AIR_DESCRIPTOR_PATH).ok(),
+    };
+
+    AirApplication {
+        arguments: opt.air_arguments.clone(),
+        current_directory: std::env::current_dir()
+            .ok()
+            .map(|directory| directory.to_string_lossy().into_owned()),
+        descriptor: descriptor.map(|descriptor| String::from_utf8_lossy(&descriptor).into_owned()),
+    }
+}
+
 /// Options used when creating a Player (& passed through to a PlayerBuilder).
 /// These may be primed by command line arguments.
 #[derive(Debug, Clone)]
@@ -49,8 +126,13 @@ pub struct LaunchOptions {
     pub save_directory: PathBuf,
     pub cache_directory: PathBuf,
     pub filesystem_access_mode: FilesystemAccessMode,
+    pub process_access_mode: ProcessAccessMode,
+    pub process_allowed: Vec<PathBuf>,
     pub gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
     pub avm2_optimizer_enabled: bool,
+    pub strict_sandbox: bool,
+    pub air_arguments: Vec<String>,
+    pub font_fallbacks: Vec<String>,
 }
 
 impl From<&GlobalPreferences> for LaunchOptions {
@@ -97,10 +179,15 @@ impl From<&GlobalPreferences> for LaunchOptions {
             save_directory: value.cli.save_directory.clone(),
             cache_directory: value.cli.cache_directory.clone(),
             filesystem_access_mode: value.cli.filesystem_access_mode,
+            process_access_mode: value.cli.process_access_mode,
+            process_allowed: value.cli.process_allow.clone(),
             socket_allowed: HashSet::from_iter(value.cli.socket_allow.iter().cloned()),
             tcp_connections: value.cli.tcp_connections,
             gamepad_button_mapping: HashMap::from_iter(value.cli.gamepad_button.iter().cloned()),
             avm2_optimizer_enabled: !value.cli.no_avm2_optimizer,
+            strict_sandbox: value.cli.strict_sandbox,
+            air_arguments: value.cli.air_arguments.clone(),
+            font_fallbacks: value.cli.font_fallback.clone(),
         }
     }
 }
@@ -138,8 +225,10 @@ impl ActivePlayer {
         font_database: Rc<fontdb::Database>,
         preferences: GlobalPreferences,
         file_picker: FilePicker,
+        accessibility: AccessKitBridge,
     ) -> Self {
-        let mut builder = PlayerBuilder::new();
+        let mut builder = PlayerBuilder::new()
+            .with_accessibility(DesktopAccessibilityBackend::new(accessibility));
 
         match CpalAudioBackend::new(preferences.output_device_name().as_deref()) {
             Ok(audio) => {
@@ -205,8 +294,13 @@ impl ActivePlayer {
                     save_directory: opt.save_directory.clone(),
                     cache_directory: opt.cache_directory.clone(),
                     filesystem_access_mode: opt.filesystem_access_mode,
+                    process_access_mode: opt.process_access_mode,
+                    process_allowed: opt.process_allowed.clone(),
                     gamepad_button_mapping: opt.gamepad_button_mapping.clone(),
                     avm2_optimizer_enabled: opt.avm2_optimizer_enabled,
+                    strict_sandbox: opt.strict_sandbox,
+                    air_arguments: opt.air_arguments.clone(),
+                    font_fallbacks: opt.font_fallbacks.clone(),
                 })
             }
         };
@@ -214,6 +308,17 @@ impl ActivePlayer {
         let (executor, future_spawner) = AsyncExecutor::new(WinitWaker(event_loop.clone()));
         let movie_url = content.initial_swf_url().clone();
         let readable_name = content.name();
+
+        if opt.player.player_runtime == Some(PlayerRuntime::AIR) {
+            builder = builder
+                .with_filesystem(air_filesystem(&opt, &content))
+                .with_air_application(air_application(&opt, &content))
+                .with_process(DesktopProcessBackend::new(
+                    event_loop.clone(),
+                    opt.process_access_mode,
+                    opt.process_allowed.clone(),
+                ));
+        }
         let navigator = ExternalNavigatorBackend::new(
             opt.player
                 .base
@@ -340,7 +445,8 @@ impl ActivePlayer {
             .with_player_version(opt.player.player_version)
             .with_player_runtime(opt.player.player_runtime.unwrap_or_default())
             .with_frame_rate(opt.player.frame_rate)
-            .with_avm2_optimizer_enabled(opt.avm2_optimizer_enabled);
+            .with_avm2_optimizer_enabled(opt.avm2_optimizer_enabled)
+            .with_strict_sandbox(opt.strict_sandbox);
         let player = builder.build();
 
         window.set_title(&format!("Ruffle - {readable_name}"));
@@ -416,6 +522,23 @@ impl ActivePlayer {
                     "Arial Unicode MS".into(),    // Mac fallback
                 ],
             );
+            player_lock.set_font_fallbacks(if opt.font_fallbacks.is_empty() {
+                vec![
+                    "Noto Sans CJK JP".into(), // Linux
+                    "Microsoft YaHei".into(),  // Windows
+                    "Yu Gothic".into(),        // Windows
+                    "Malgun Gothic".into(),    // Windows
+                    "PingFang SC".into(),      // Mac
+                    "Hiragino Sans".into(),    // Mac
+                    "Arial Unicode MS".into(), // Mac
+                    "Segoe UI Symbol".into(),  // Windows
+                    "Segoe UI Emoji".into(),   // Windows
+                    "Noto Color Emoji".into(), // Linux
+                    "DejaVu Sans".into(),      // Linux
+                ]
+            } else {
+                opt.font_fallbacks.clone()
+            });
         }
 
         Self {
@@ -437,6 +560,7 @@ pub struct PlayerController {
     font_database: Rc<fontdb::Database>,
     preferences: GlobalPreferences,
     file_picker: FilePicker,
+    accessibility: AccessKitBridge,
 }
 
 impl PlayerController {
@@ -447,6 +571,7 @@ impl PlayerController {
         font_database: fontdb::Database,
         preferences: GlobalPreferences,
         file_picker: FilePicker,
+        accessibility: AccessKitBridge,
     ) -> Self {
         Self {
             player: None,
@@ -456,6 +581,7 @@ impl PlayerController {
             font_database: Rc::new(font_database),
             preferences,
             file_picker,
+            accessibility,
         }
     }
 
@@ -470,6 +596,7 @@ impl PlayerController {
             self.font_database.clone(),
             self.preferences.clone(),
             self.file_picker.clone(),
+            self.accessibility.clone(),
         ));
     }
 
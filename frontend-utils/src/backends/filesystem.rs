@@ -0,0 +1,272 @@
+use ruffle_core::backend::filesystem::{
+    FileMetadata, FileSystemBackend, FileSystemRoot, SandboxPath,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::{Error, ErrorKind, Seek, SeekFrom, Write};
+use std::path::{Component, Path, PathBuf};
+
+/// A `FileSystemBackend` which maps each sandbox root onto a directory on disk.
+///
+/// Roots without a directory behave as if they were empty and read-only.
+pub struct DiskFileSystemBackend {
+    roots: HashMap<FileSystemRoot, PathBuf>,
+}
+
+impl DiskFileSystemBackend {
+    pub fn new() -> Self {
+        Self {
+            roots: HashMap::new(),
+        }
+    }
+
+    /// Maps a sandbox root onto a directory, creating it for writable roots.
+    pub fn with_root(mut self, root: FileSystemRoot, directory: PathBuf) -> Self {
+        if root.is_writable() && !directory.exists() {
+            tracing::info!("Creating AIR {} dir", root.name());
+            if let Err(e) = fs::create_dir_all(&directory) {
+                tracing::warn!("Unable to create AIR {} dir {}", root.name(), e);
+            }
+        }
+        self.roots.insert(root, directory);
+        self
+    }
+
+    /// The location of a sandbox path on disk.
+    fn disk_path(&self, path: &SandboxPath) -> Result<PathBuf, Error> {
+        let root = self
+            .roots
+            .get(&path.root)
+            .ok_or_else(|| Error::from(ErrorKind::NotFound))?;
+
+        let mut disk_path = root.clone();
+        for component in path.components() {
+            // The player never gives us paths which escape their root, but double check.
+            let mut components = Path::new(component).components();
+            match (components.next(), components.next()) {
+                (Some(Component::Normal(component)), None) => disk_path.push(component),
+                _ => return Err(ErrorKind::PermissionDenied.into()),
+            }
+        }
+        Ok(disk_path)
+    }
+
+    /// Like `disk_path`, but also creates the parent directories of the path.
+    fn disk_path_with_parents(&self, path: &SandboxPath) -> Result<PathBuf, Error> {
+        let disk_path = self.disk_path(path)?;
+        if let Some(parent) = disk_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(disk_path)
+    }
+}
+
+impl Default for DiskFileSystemBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn copy_recursively(from: &Path, to: &Path) -> Result<(), Error> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursively(&entry.path(), &to.join(entry.file_name()))?;
+        }
+    } else {
+        fs::copy(from, to)?;
+    }
+    Ok(())
+}
+
+fn remove(path: &Path) -> Result<(), Error> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+impl FileSystemBackend for DiskFileSystemBackend {
+    fn separator(&self) -> &'static str {
+        std::path::MAIN_SEPARATOR_STR
+    }
+
+    fn native_path(&self, path: &SandboxPath) -> String {
+        match self.disk_path(path) {
+            Ok(disk_path) => disk_path.to_string_lossy().into_owned(),
+            // Fall back to a path which won't resolve to anything.
+            Err(_) => format!("{}:/{}", path.root.name(), path.path),
+        }
+    }
+
+    fn resolve_native_path(&self, native_path: &str) -> Option<SandboxPath> {
+        let native_path = Path::new(native_path);
+        self.roots.iter().find_map(|(root, directory)| {
+            let relative = native_path.strip_prefix(directory).ok()?;
+            let components = relative
+                .components()
+                .map(|component| match component {
+                    Component::Normal(component) => Some(component.to_string_lossy()),
+                    Component::CurDir => Some(".".into()),
+                    Component::ParentDir => Some("..".into()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(SandboxPath::new(*root, components.join("/")))
+        })
+    }
+
+    fn metadata(&self, path: &SandboxPath) -> Result<FileMetadata, Error> {
+        let metadata = fs::metadata(self.disk_path(path)?)?;
+        Ok(FileMetadata {
+            is_directory: metadata.is_dir(),
+            size: if metadata.is_dir() { 0 } else { metadata.len() },
+            creation_time: metadata.created().ok().map(Into::into),
+            modification_time: metadata.modified().ok().map(Into::into),
+        })
+    }
+
+    fn read(&self, path: &SandboxPath) -> Result<Vec<u8>, Error> {
+        fs::read(self.disk_path(path)?)
+    }
+
+    fn write(&mut self, path: &SandboxPath, offset: u64, data: &[u8]) -> Result<(), Error> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(self.disk_path_with_parents(path)?)?;
+        file.seek(SeekFrom::Start(offset))?;
+        file.write_all(data)
+    }
+
+    fn truncate(&mut self, path: &SandboxPath, length: u64) -> Result<(), Error> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(self.disk_path_with_parents(path)?)?;
+        file.set_len(length)
+    }
+
+    fn create_directory(&mut self, path: &SandboxPath) -> Result<(), Error> {
+        fs::create_dir_all(self.disk_path(path)?)
+    }
+
+    fn read_directory(&self, path: &SandboxPath) -> Result<Vec<String>, Error> {
+        fs::read_dir(self.disk_path(path)?)?
+            .map(|entry| Ok(entry?.file_name().to_string_lossy().into_owned()))
+            .collect()
+    }
+
+    fn copy(&mut self, from: &SandboxPath, to: &SandboxPath) -> Result<(), Error> {
+        let from = self.disk_path(from)?;
+        let to = self.disk_path_with_parents(to)?;
+        if to.exists() {
+            remove(&to)?;
+        }
+        copy_recursively(&from, &to)
+    }
+
+    fn rename(&mut self, from: &SandboxPath, to: &SandboxPath) -> Result<(), Error> {
+        let from = self.disk_path(from)?;
+        let to = self.disk_path_with_parents(to)?;
+        if from == to {
+            return Ok(());
+        }
+        if to.exists() {
+            remove(&to)?;
+        }
+        // Renaming fails across devices, so fall back to copying.
+        if fs::rename(&from, &to).is_err() {
+            copy_recursively(&from, &to)?;
+            remove(&from)?;
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, path: &SandboxPath) -> Result<(), Error> {
+        if path.path.is_empty() {
+            return Err(ErrorKind::PermissionDenied.into());
+        }
+        remove(&self.disk_path(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DiskFileSystemBackend;
+    use ruffle_core::backend::filesystem::{FileSystemBackend, FileSystemRoot, SandboxPath};
+    use std::io::ErrorKind;
+    use tempfile::tempdir;
+
+    /*
+    As with the bundle source tests, be careful with panicking here:
+    tempdir() relies on Drop to clean up the directory.
+
+    Construct the test, perform the test, drop the tmp_dir and *then* assert.
+     */
+
+    fn storage(path: &str) -> SandboxPath {
+        SandboxPath::new(FileSystemRoot::ApplicationStorage, path)
+    }
+
+    #[test]
+    fn write_then_read() {
+        let tmp_dir = tempdir().unwrap();
+        let mut backend = DiskFileSystemBackend::new().with_root(
+            FileSystemRoot::ApplicationStorage,
+            tmp_dir.path().join("store"),
+        );
+        let written = backend
+            .write(&storage("saves/slot1.dat"), 2, b"cd")
+            .and_then(|_| backend.write(&storage("saves/slot1.dat"), 0, b"ab"));
+        let read = backend.read(&storage("saves/slot1.dat"));
+        let listing = backend.read_directory(&storage("saves"));
+        drop(tmp_dir);
+
+        assert!(written.is_ok());
+        assert_eq!(read.unwrap(), b"abcd");
+        assert_eq!(listing.unwrap(), vec!["slot1.dat".to_string()]);
+    }
+
+    #[test]
+    fn escaping_path_denied() {
+        let tmp_dir = tempdir().unwrap();
+        let mut backend = DiskFileSystemBackend::new().with_root(
+            FileSystemRoot::ApplicationStorage,
+            tmp_dir.path().join("store"),
+        );
+        let result = backend.write(&storage("../outside.txt"), 0, b"data");
+        let escaped = tmp_dir.path().join("outside.txt").exists();
+        drop(tmp_dir);
+
+        assert!(matches!(result, Err(e) if e.kind() == ErrorKind::PermissionDenied));
+        assert!(!escaped);
+    }
+
+    #[test]
+    fn missing_root_not_found() {
+        let backend = DiskFileSystemBackend::new();
+        let result = backend.read(&SandboxPath::new(FileSystemRoot::Documents, "file.txt"));
+        assert!(matches!(result, Err(e) if e.kind() == ErrorKind::NotFound));
+    }
+
+    #[test]
+    fn resolve_native_path() {
+        let tmp_dir = tempdir().unwrap();
+        let root = tmp_dir.path().join("documents");
+        let backend =
+            DiskFileSystemBackend::new().with_root(FileSystemRoot::Documents, root.clone());
+        let path = SandboxPath::new(FileSystemRoot::Documents, "a/b.txt");
+        let resolved = backend.resolve_native_path(&backend.native_path(&path));
+        let outside = backend.resolve_native_path(&tmp_dir.path().to_string_lossy());
+        drop(tmp_dir);
+
+        assert_eq!(resolved, Some(path));
+        assert_eq!(outside, None);
+    }
+}
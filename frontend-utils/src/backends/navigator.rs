@@ -11,6 +11,7 @@ use ruffle_core::backend::navigator::{
     async_return, create_fetch_error, get_encoding, ErrorResponse, NavigationMethod,
     NavigatorBackend, OwnedFuture, Request, SocketMode, SuccessResponse,
 };
+use ruffle_core::datagram_socket::{Datagram, DatagramSocketAction, DatagramSocketHandle};
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
@@ -23,7 +24,7 @@ use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::net::TcpStream;
+use tokio::net::{TcpStream, UdpSocket};
 use tracing::warn;
 use url::{ParseError, Url};
 
@@ -465,6 +466,146 @@ impl<F: FutureSpawner + 'static, I: NavigatorInterface> NavigatorBackend
 
         tokio::spawn(future);
     }
+
+    fn bind_udp_socket(
+        &mut self,
+        address: String,
+        port: u16,
+        handle: DatagramSocketHandle,
+        receiver: Receiver<Datagram>,
+        sender: Sender<DatagramSocketAction>,
+    ) -> Option<(String, u16)> {
+        // Binding a socket is treated like connecting a TCP socket, as it lets
+        // anyone on the network send data to the SWF.
+        let bind_addr = format!("{address}:{port}");
+        let needs_confirmation = match (self.socket_allowed.contains(&bind_addr), self.socket_mode)
+        {
+            (false, SocketMode::Allow) | (true, _) => false,
+            (false, SocketMode::Deny) => {
+                warn!("SWF tried to bind a UDP socket, but binding sockets is not allowed");
+                return None;
+            }
+            (false, SocketMode::Ask) => true,
+        };
+
+        let socket = match std::net::UdpSocket::bind((address.as_str(), port)) {
+            Ok(socket) => socket,
+            Err(e) => {
+                warn!(
+                    "Failed to bind UDP socket to {}:{}, error: {}",
+                    address, port, e
+                );
+                return None;
+            }
+        };
+        // Broadcasting is commonly used for LAN discovery.
+        if let Err(e) = socket.set_broadcast(true) {
+            warn!("Failed to enable broadcast on UDP socket: {}", e);
+        }
+        let socket = match socket
+            .set_nonblocking(true)
+            .and_then(|_| UdpSocket::from_std(socket))
+        {
+            Ok(socket) => socket,
+            Err(e) => {
+                warn!("Failed to register UDP socket, error: {}", e);
+                return None;
+            }
+        };
+        let local_addr = socket.local_addr().ok()?;
+
+        // Sending a datagram is treated like connecting a TCP socket:
+        // the destination has to be allowed, or confirmed by the user.
+        let socket_allowed = self.socket_allowed.clone();
+        let socket_mode = self.socket_mode;
+        let interface = self.interface.clone();
+
+        let future = Box::pin(async move {
+            // The user is asked before anything is received, so that the
+            // socket isn't held up waiting for an answer later on.
+            if needs_confirmation && !interface.confirm_socket(&address, port).await {
+                let _ = sender.send(DatagramSocketAction::Close(handle)).await;
+                return;
+            }
+
+            let mut confirmed = HashSet::new();
+            let mut refused = HashSet::new();
+            // The largest possible UDP payload.
+            let mut buffer = vec![0; 65535];
+
+            loop {
+                tokio::select! {
+                    received = socket.recv_from(&mut buffer) => match received {
+                        Ok((read, from)) => {
+                            let datagram = Datagram {
+                                address: from.ip().to_string(),
+                                port: from.port(),
+                                data: buffer[..read].to_vec(),
+                            };
+                            if sender
+                                .send(DatagramSocketAction::Data(handle, datagram))
+                                .await
+                                .is_err()
+                            {
+                                return;
+                            }
+                        }
+                        // NOTE: Some platforms report ICMP errors for previously sent datagrams here,
+                        //       which don't affect the socket itself.
+                        Err(e) => warn!("Failed to receive datagram: {}", e),
+                    },
+                    datagram = receiver.recv() => {
+                        let Ok(datagram) = datagram else {
+                            // The socket was closed on AVM side.
+                            return;
+                        };
+
+                        let addr = format!("{}:{}", datagram.address, datagram.port);
+                        let is_allowed = if socket_allowed.contains(&addr) || confirmed.contains(&addr) {
+                            true
+                        } else if refused.contains(&addr) {
+                            false
+                        } else {
+                            match socket_mode {
+                                SocketMode::Allow => true,
+                                SocketMode::Deny => {
+                                    tracing::warn!(
+                                        "SWF tried to send a datagram, but sending datagrams is not allowed"
+                                    );
+                                    refused.insert(addr.clone());
+                                    false
+                                }
+                                SocketMode::Ask => {
+                                    let confirm = interface
+                                        .confirm_socket(&datagram.address, datagram.port)
+                                        .await;
+                                    if confirm {
+                                        confirmed.insert(addr.clone());
+                                    } else {
+                                        refused.insert(addr.clone());
+                                    }
+                                    confirm
+                                }
+                            }
+                        };
+
+                        if is_allowed {
+                            if let Err(e) = socket
+                                .send_to(&datagram.data, (datagram.address.as_str(), datagram.port))
+                                .await
+                            {
+                                warn!("Failed to send datagram to {}, error: {}", addr, e);
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        tokio::spawn(future);
+
+        Some((local_addr.ip().to_string(), local_addr.port()))
+    }
 }
 
 #[cfg(test)]
@@ -540,6 +681,18 @@ mod tests {
     }
 
     fn new_test_backend(socket_allow: bool) -> ExternalNavigatorBackend<TestFutureSpawner, ()> {
+        let socket_mode = if socket_allow {
+            SocketMode::Allow
+        } else {
+            SocketMode::Deny
+        };
+        new_test_backend_with_allowed(socket_mode, Default::default())
+    }
+
+    fn new_test_backend_with_allowed(
+        socket_mode: SocketMode,
+        socket_allowed: HashSet<String>,
+    ) -> ExternalNavigatorBackend<TestFutureSpawner, ()> {
         let url = Url::parse("https://example.com/path/").unwrap();
         ExternalNavigatorBackend::new(
             url.clone(),
@@ -548,12 +701,8 @@ mod tests {
             TestFutureSpawner,
             None,
             false,
-            Default::default(),
-            if socket_allow {
-                SocketMode::Allow
-            } else {
-                SocketMode::Deny
-            },
+            socket_allowed,
+            socket_mode,
             Rc::new(PlayingContent::DirectFile(url)),
             (),
         )
@@ -756,4 +905,145 @@ mod tests {
 
         assert_eq!(read_server(&mut server_socket).await, "Sending some data");
     }
+
+    fn try_bind_test_udp_socket(
+        socket_mode: SocketMode,
+        socket_allowed: HashSet<String>,
+        port: u16,
+    ) -> Option<(SocketAddr, Sender<Datagram>, Receiver<DatagramSocketAction>)> {
+        let mut backend = new_test_backend_with_allowed(socket_mode, socket_allowed);
+
+        let (write, receiver) = async_channel::unbounded();
+        let (sender, read) = async_channel::unbounded();
+
+        let (address, port) = backend.bind_udp_socket(
+            "127.0.0.1".to_string(),
+            port,
+            DatagramSocketHandle::default(),
+            receiver,
+            sender,
+        )?;
+
+        Some((SocketAddr::new(address.parse().unwrap(), port), write, read))
+    }
+
+    fn bind_test_udp_socket(
+        socket_allow: bool,
+    ) -> (SocketAddr, Sender<Datagram>, Receiver<DatagramSocketAction>) {
+        let socket_mode = if socket_allow {
+            SocketMode::Allow
+        } else {
+            SocketMode::Deny
+        };
+        try_bind_test_udp_socket(socket_mode, Default::default(), 0).expect("bind UDP socket")
+    }
+
+    /// Finds a port that's free to bind a UDP socket to.
+    fn free_udp_port() -> u16 {
+        std::net::UdpSocket::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port()
+    }
+
+    #[macro_rules_attribute::apply(async_test)]
+    async fn test_udp_socket_basic_communication() {
+        let server = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        let (client_addr, client_write, client_read) = bind_test_udp_socket(true);
+
+        client_write
+            .send(Datagram {
+                address: server_addr.ip().to_string(),
+                port: server_addr.port(),
+                data: b"Hello from client".to_vec(),
+            })
+            .await
+            .unwrap();
+
+        let mut buffer = [0; 4096];
+        let (read, from) = server
+            .recv_from(&mut buffer)
+            .or(async_timeout!())
+            .await
+            .unwrap();
+        assert_eq!(&buffer[..read], b"Hello from client");
+        assert_eq!(from, client_addr);
+
+        server.send_to(b"Hello World!", client_addr).await.unwrap();
+
+        assert_eq!(
+            client_read
+                .recv()
+                .or(async_timeout!())
+                .await
+                .expect("receive action"),
+            DatagramSocketAction::Data(
+                DatagramSocketHandle::default(),
+                Datagram {
+                    address: server_addr.ip().to_string(),
+                    port: server_addr.port(),
+                    data: b"Hello World!".to_vec(),
+                }
+            )
+        );
+    }
+
+    #[macro_rules_attribute::apply(async_test)]
+    async fn test_udp_socket_deny() {
+        assert!(try_bind_test_udp_socket(SocketMode::Deny, Default::default(), 0).is_none());
+    }
+
+    #[macro_rules_attribute::apply(async_test)]
+    async fn test_udp_socket_ask() {
+        let server = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let (client_addr, _client_write, client_read) =
+            try_bind_test_udp_socket(SocketMode::Ask, Default::default(), 0)
+                .expect("bind UDP socket");
+
+        // The test interface confirms every socket.
+        server.send_to(b"Hello World!", client_addr).await.unwrap();
+        assert!(matches!(
+            client_read.recv().or(async_timeout!()).await,
+            Ok(DatagramSocketAction::Data(..))
+        ));
+    }
+
+    #[macro_rules_attribute::apply(async_test)]
+    async fn test_udp_socket_deny_send() {
+        let server = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        let port = free_udp_port();
+        let socket_allowed = HashSet::from([format!("127.0.0.1:{port}")]);
+        let (client_addr, client_write, client_read) =
+            try_bind_test_udp_socket(SocketMode::Deny, socket_allowed, port)
+                .expect("bind allowed UDP socket");
+
+        client_write
+            .send(Datagram {
+                address: server_addr.ip().to_string(),
+                port: server_addr.port(),
+                data: b"Not allowed".to_vec(),
+            })
+            .await
+            .unwrap();
+
+        let mut buffer = [0; 4096];
+        assert!(server
+            .recv_from(&mut buffer)
+            .or(async {
+                Timer::after(Duration::from_millis(100)).await;
+                Err(io::Error::new(ErrorKind::TimedOut, ""))
+            })
+            .await
+            .is_err());
+
+        // Receiving only depends on the socket being allowed to bind.
+        server.send_to(b"Hello World!", client_addr).await.unwrap();
+        assert!(matches!(
+            client_read.recv().or(async_timeout!()).await,
+            Ok(DatagramSocketAction::Data(..))
+        ));
+    }
 }
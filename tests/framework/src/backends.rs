@@ -1,8 +1,10 @@
+mod accessibility;
 mod audio;
 mod log;
 mod navigator;
 mod ui;
 
+pub use accessibility::TestAccessibilityBackend;
 pub use audio::TestAudioBackend;
 pub use log::TestLogBackend;
 pub use navigator::TestNavigatorBackend;
@@ -0,0 +1,162 @@
+use crate::backends::TestLogBackend;
+use ruffle_core::backend::accessibility::{
+    AccessibilityAction, AccessibilityBackend, AccessibilityNode, AccessibilityNodeId,
+    AccessibilityRequest, AccessibilityTree,
+};
+use ruffle_core::backend::log::LogBackend;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+/// An accessibility backend which acts as an always active screen reader.
+///
+/// Every accessibility tree sent by the player is dumped to the trace output.
+/// Nodes are numbered in tree order, as their identifiers aren't stable across runs.
+pub struct TestAccessibilityBackend {
+    log: TestLogBackend,
+    tree: Option<AccessibilityTree>,
+
+    /// The numbers of the nodes to click, queued by the `accessibilityClick` FS command.
+    clicks: Arc<Mutex<Vec<usize>>>,
+}
+
+impl TestAccessibilityBackend {
+    pub fn new(log: TestLogBackend) -> Self {
+        Self {
+            log,
+            tree: None,
+            clicks: Default::default(),
+        }
+    }
+
+    /// The queue of node numbers to click on the next tick.
+    pub fn clicks(&self) -> Arc<Mutex<Vec<usize>>> {
+        self.clicks.clone()
+    }
+
+    fn node_at(&self, number: usize) -> Option<AccessibilityNodeId> {
+        fn find(
+            node: &AccessibilityNode,
+            number: usize,
+            next: &mut usize,
+        ) -> Option<AccessibilityNodeId> {
+            if *next == number {
+                return Some(node.id);
+            }
+            node.children.iter().find_map(|child| {
+                *next += 1;
+                find(child, number, next)
+            })
+        }
+
+        let tree = self.tree.as_ref()?;
+        find(&tree.root, number, &mut 0)
+    }
+
+    fn node_number(&self, id: AccessibilityNodeId) -> Option<usize> {
+        fn find(node: &AccessibilityNode, id: AccessibilityNodeId, next: &mut usize) -> bool {
+            if node.id == id {
+                return true;
+            }
+            node.children.iter().any(|child| {
+                *next += 1;
+                find(child, id, next)
+            })
+        }
+
+        let tree = self.tree.as_ref()?;
+        let mut number = 0;
+        find(&tree.root, id, &mut number).then_some(number)
+    }
+
+    fn describe(&self, id: AccessibilityNodeId) -> String {
+        match self.node_number(id) {
+            Some(number) => format!("#{number}"),
+            None => "unknown node".to_string(),
+        }
+    }
+}
+
+fn dump_node(node: &AccessibilityNode, depth: usize, next: &mut usize, output: &mut String) {
+    let _ = write!(output, "{}#{next} {:?}", "  ".repeat(depth), node.role);
+    *next += 1;
+    if !node.name.is_empty() {
+        let _ = write!(output, " name={:?}", node.name);
+    }
+    if !node.description.is_empty() {
+        let _ = write!(output, " description={:?}", node.description);
+    }
+    if !node.shortcut.is_empty() {
+        let _ = write!(output, " shortcut={:?}", node.shortcut);
+    }
+    if let Some(value) = &node.value {
+        let _ = write!(output, " value={value:?}");
+    }
+    let _ = writeln!(
+        output,
+        " [{}, {}, {}, {}]",
+        node.bounds.x_min.to_pixels(),
+        node.bounds.y_min.to_pixels(),
+        node.bounds.width().to_pixels(),
+        node.bounds.height().to_pixels()
+    );
+
+    for child in &node.children {
+        dump_node(child, depth + 1, next, output);
+    }
+}
+
+impl AccessibilityBackend for TestAccessibilityBackend {
+    fn is_supported(&self) -> bool {
+        true
+    }
+
+    fn is_active(&self) -> bool {
+        true
+    }
+
+    fn update_tree(&mut self, tree: &AccessibilityTree) {
+        self.tree = Some(tree.clone());
+
+        let mut output = String::new();
+        dump_node(&tree.root, 1, &mut 0, &mut output);
+        if let Some(focus) = tree.focus {
+            let _ = writeln!(output, "  Focus: {}", self.describe(focus));
+        }
+        if !tree.tab_order.is_empty() {
+            let tab_order: Vec<String> =
+                tree.tab_order.iter().map(|&id| self.describe(id)).collect();
+            let _ = writeln!(output, "  Tab order: {}", tab_order.join(", "));
+        }
+
+        self.log.avm_trace("AccessibilityBackend::update_tree:");
+        self.log.avm_trace(output.trim_end());
+    }
+
+    fn send_event(&mut self, node: AccessibilityNodeId, child_id: u32, event_type: u32) {
+        self.log.avm_trace(&format!(
+            "AccessibilityBackend::send_event: {}, child {child_id}, event {event_type:#x}",
+            self.describe(node)
+        ));
+    }
+
+    fn poll_requests(&mut self) -> Vec<AccessibilityRequest> {
+        let clicks =
+            std::mem::take(&mut *self.clicks.lock().expect("Clicks should not be poisoned"));
+        clicks
+            .into_iter()
+            .filter_map(|number| {
+                let node = self.node_at(number);
+                if node.is_none() {
+                    self.log.avm_trace(&format!(
+                        "AccessibilityBackend::poll_requests: no node #{number} to click"
+                    ));
+                }
+                node
+            })
+            .map(|node| AccessibilityRequest {
+                node,
+                action: AccessibilityAction::Click,
+            })
+            .collect()
+    }
+}
@@ -5,8 +5,9 @@ use percent_encoding::percent_decode_str;
 use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::navigator::{
     async_return, create_fetch_error, ErrorResponse, NavigationMethod, NavigatorBackend,
-    NullExecutor, NullSpawner, OwnedFuture, Request, SuccessResponse,
+    NullExecutor, NullSpawner, OwnedFuture, Request, SuccessResponse, UdpLoopback,
 };
+use ruffle_core::datagram_socket::{Datagram, DatagramSocketAction, DatagramSocketHandle};
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
@@ -72,10 +73,13 @@ impl SuccessResponse for TestResponse {
 ///
 /// These are formatted as query params, rather than domains/whole URLs, so that real/real-invalid
 /// URLs can be used in Flash Player when writing tests
+///
+/// UDP sockets are bound to an in-process loopback network, so a test can talk to itself.
 pub struct TestNavigatorBackend {
     spawner: NullSpawner,
     relative_base_path: VfsPath,
     socket_events: Option<Vec<SocketEvent>>,
+    udp_loopback: UdpLoopback,
     log: Option<TestLogBackend>,
 }
 
@@ -90,6 +94,7 @@ impl TestNavigatorBackend {
             spawner: executor.spawner(),
             relative_base_path: path,
             socket_events,
+            udp_loopback: UdpLoopback::new(),
             log,
         })
     }
@@ -322,4 +327,24 @@ impl NavigatorBackend for TestNavigatorBackend {
             }));
         }
     }
+
+    fn bind_udp_socket(
+        &mut self,
+        address: String,
+        port: u16,
+        handle: DatagramSocketHandle,
+        receiver: Receiver<Datagram>,
+        sender: Sender<DatagramSocketAction>,
+    ) -> Option<(String, u16)> {
+        if let Some(log) = &self.log {
+            log.avm_trace("Navigator::bind_udp_socket");
+            log.avm_trace(&format!("    Address: {address}; Port: {port}"));
+        }
+
+        let (bound, future) = self
+            .udp_loopback
+            .bind(address, port, handle, receiver, sender)?;
+        self.spawn_future(future);
+        Some(bound)
+    }
 }
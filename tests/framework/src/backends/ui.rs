@@ -1,8 +1,12 @@
+use crate::backends::TestLogBackend;
 use crate::test::Font;
 use chrono::{DateTime, Utc};
+use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::ui::{
-    DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    ClipboardData, ClipboardFormat, CustomCursor, CustomCursorId, DialogLoaderError,
+    DialogResultFuture, FileDialogResult, FileFilter, FontDefinition, FullscreenError,
+    HeadlessWindow, LanguageIdentifier, MemoryClipboard, MouseCursor, UiBackend, WindowBounds,
+    WindowDisplayState, US_ENGLISH,
 };
 use url::Url;
 
@@ -75,17 +79,23 @@ impl FileDialogResult for TestFileDialogResult {
 ///   otherwise a user cancellation will be simulated
 /// * Attempting to display a file save dialog with a file name hint of "debug-success.txt" will simulate successfully selecting a destination
 ///   otherwise a user cancellation will be simulated
-/// * Simulated in-memory clipboard
+/// * Simulated in-memory clipboard, supporting every clipboard format
+/// * Custom cursors are supported, and cursor changes may be logged to the trace output
+/// * The player is displayed in a simulated window, which can be moved, resized, maximized and minimized
 pub struct TestUiBackend {
     fonts: Vec<Font>,
-    clipboard: String,
+    clipboard: MemoryClipboard,
+    log: Option<TestLogBackend>,
+    window: HeadlessWindow,
 }
 
 impl TestUiBackend {
-    pub fn new(fonts: Vec<Font>) -> Self {
+    pub fn new(fonts: Vec<Font>, log: Option<TestLogBackend>) -> Self {
         Self {
             fonts,
-            clipboard: "".to_string(),
+            clipboard: MemoryClipboard::new(),
+            log,
+            window: HeadlessWindow::default(),
         }
     }
 }
@@ -97,14 +107,65 @@ impl UiBackend for TestUiBackend {
 
     fn set_mouse_visible(&mut self, _visible: bool) {}
 
-    fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
+    fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
+        if let Some(log) = &self.log {
+            log.avm_trace(&format!("UiBackend::set_mouse_cursor: {cursor:?}"));
+        }
+    }
+
+    fn supports_custom_cursors(&self) -> bool {
+        true
+    }
+
+    fn register_mouse_cursor(&mut self, id: CustomCursorId, cursor: CustomCursor) {
+        if let Some(log) = &self.log {
+            log.avm_trace(&format!("UiBackend::register_mouse_cursor: {id:?}"));
+            log.avm_trace(&format!("  Size: {}x{}", cursor.width, cursor.height));
+            log.avm_trace(&format!("  Frames: {}", cursor.frames.len()));
+            log.avm_trace(&format!(
+                "  Hotspot: {}, {}",
+                cursor.hotspot.0, cursor.hotspot.1
+            ));
+            log.avm_trace(&format!("  Frame rate: {:?}", cursor.frame_rate));
+            log.avm_trace(&format!("  Frame duration: {:?}", cursor.frame_duration()));
+        }
+    }
+
+    fn unregister_mouse_cursor(&mut self, id: CustomCursorId) {
+        if let Some(log) = &self.log {
+            log.avm_trace(&format!("UiBackend::unregister_mouse_cursor: {id:?}"));
+        }
+    }
 
     fn clipboard_content(&mut self) -> String {
-        self.clipboard.clone()
+        self.clipboard.text()
     }
 
     fn set_clipboard_content(&mut self, content: String) {
-        self.clipboard = content;
+        self.clipboard.clear();
+        if !content.is_empty() {
+            self.clipboard.set(ClipboardData::Text(content));
+        }
+    }
+
+    fn clipboard_formats(&mut self) -> Vec<ClipboardFormat> {
+        self.clipboard.formats()
+    }
+
+    fn clipboard_data(&mut self, format: ClipboardFormat) -> Option<ClipboardData> {
+        self.clipboard.get(format).cloned()
+    }
+
+    fn set_clipboard_data(&mut self, data: ClipboardData) {
+        self.clipboard.set(data);
+    }
+
+    fn clear_clipboard_format(&mut self, format: ClipboardFormat) {
+        self.clipboard.remove(format);
+    }
+
+    fn clear_clipboard(&mut self) {
+        self.clipboard.clear();
     }
 
     fn set_fullscreen(&mut self, _is_full: bool) -> Result<(), FullscreenError> {
@@ -190,4 +251,20 @@ impl UiBackend for TestUiBackend {
     }
 
     fn close_file_dialog(&mut self) {}
+
+    fn window_bounds(&self) -> Option<WindowBounds> {
+        Some(self.window.bounds())
+    }
+
+    fn set_window_bounds(&mut self, bounds: WindowBounds) {
+        self.window.set_bounds(bounds);
+    }
+
+    fn window_display_state(&self) -> WindowDisplayState {
+        self.window.display_state()
+    }
+
+    fn set_window_display_state(&mut self, state: WindowDisplayState) {
+        self.window.set_display_state(state);
+    }
 }
@@ -30,6 +30,8 @@ impl FsCommandProvider for TestFsCommandProvider {
 pub enum FsCommand {
     Quit,
     CaptureImage(String),
+    /// Clicks the accessibility node with the given number, as a screen reader would.
+    AccessibilityClick(usize),
 }
 
 impl FsCommand {
@@ -37,6 +39,7 @@ impl FsCommand {
         match command {
             "quit" => Some(Self::Quit),
             "captureImage" => Some(Self::CaptureImage(args.to_string())),
+            "accessibilityClick" => args.parse().ok().map(Self::AccessibilityClick),
             _ => None,
         }
     }
@@ -29,8 +29,12 @@ pub struct TestOptions {
     pub approximations: Option<Approximations>,
     pub player_options: PlayerOptions,
     pub log_fetch: bool,
+    pub log_cursor: bool,
+    pub log_accessibility: bool,
     pub required_features: RequiredFeatures,
     pub fonts: HashMap<String, FontOptions>,
+    pub cameras: Vec<CameraOptions>,
+    pub microphones: Vec<MicrophoneOptions>,
 }
 
 impl Default for TestOptions {
@@ -47,8 +51,12 @@ impl Default for TestOptions {
             approximations: None,
             player_options: PlayerOptions::default(),
             log_fetch: false,
+            log_cursor: false,
+            log_accessibility: false,
             required_features: RequiredFeatures::default(),
             fonts: Default::default(),
+            cameras: Default::default(),
+            microphones: Default::default(),
         }
     }
 }
@@ -150,6 +158,7 @@ pub struct PlayerOptions {
     with_audio: bool,
     with_video: bool,
     runtime: PlayerRuntime,
+    strict_sandbox: bool,
 }
 
 impl PlayerOptions {
@@ -173,6 +182,7 @@ impl PlayerOptions {
         }
 
         player_builder = player_builder.with_player_runtime(self.runtime);
+        player_builder = player_builder.with_strict_sandbox(self.strict_sandbox);
 
         if self.with_video {
             #[cfg(feature = "ruffle_video_external")]
@@ -412,3 +422,43 @@ pub struct FontOptions {
     pub bold: bool,
     pub italic: bool,
 }
+
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct CameraOptions {
+    pub name: String,
+    /// A Y4M video to play back as the camera feed.
+    pub path: Option<String>,
+    /// A sequence of PNG images to play back as the camera feed, used when `path` is not set.
+    pub frames: Vec<String>,
+    /// The frame rate of `frames`.
+    pub fps: f64,
+}
+
+impl Default for CameraOptions {
+    fn default() -> Self {
+        Self {
+            name: "Test Camera".to_string(),
+            path: None,
+            frames: vec![],
+            fps: 15.0,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct MicrophoneOptions {
+    pub name: String,
+    /// A WAV file to play back as the microphone input.
+    pub path: String,
+}
+
+impl Default for MicrophoneOptions {
+    fn default() -> Self {
+        Self {
+            name: "Test Microphone".to_string(),
+            path: String::new(),
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use crate::backends::{TestLogBackend, TestNavigatorBackend, TestUiBackend};
+use crate::backends::{
+    TestAccessibilityBackend, TestLogBackend, TestNavigatorBackend, TestUiBackend,
+};
 use crate::environment::RenderInterface;
 use crate::fs_commands::{FsCommand, TestFsCommandProvider};
 use crate::image_trigger::ImageTrigger;
@@ -46,6 +48,7 @@ pub struct TestRunner {
     frame_time_duration: Duration,
     log: TestLogBackend,
     fs_commands: mpsc::Receiver<FsCommand>,
+    accessibility_clicks: Option<Arc<Mutex<Vec<usize>>>>,
     render_interface: Option<Box<dyn RenderInterface>>,
     images: HashMap<String, ImageComparison>,
     remaining_iterations: u32,
@@ -90,13 +93,24 @@ impl TestRunner {
             .with_navigator(navigator)
             .with_max_execution_duration(Duration::from_secs(300))
             .with_fs_commands(Box::new(fs_command_provider))
-            .with_ui(TestUiBackend::new(test.fonts()?))
+            .with_ui(TestUiBackend::new(
+                test.fonts()?,
+                test.options.log_cursor.then(|| log.clone()),
+            ))
+            .with_capture(test.capture()?)
             .with_viewport_dimensions(
                 viewport_dimensions.width,
                 viewport_dimensions.height,
                 viewport_dimensions.scale_factor,
             );
 
+        let mut accessibility_clicks = None;
+        if test.options.log_accessibility {
+            let accessibility = TestAccessibilityBackend::new(log.clone());
+            accessibility_clicks = Some(accessibility.clicks());
+            builder = builder.with_accessibility(accessibility);
+        }
+
         let render_interface = if let Some((interface, backend)) = renderer {
             builder = builder.with_boxed_renderer(backend);
             Some(interface)
@@ -132,6 +146,7 @@ impl TestRunner {
             frame_time_duration,
             log,
             fs_commands,
+            accessibility_clicks,
             images,
             remaining_iterations,
             current_iteration: 0,
@@ -196,6 +211,12 @@ impl TestRunner {
                         return Err(anyhow!("Encountered fscommand to capture and compare image '{name}', but no [image_comparison] was set up for this."));
                     }
                 }
+                FsCommand::AccessibilityClick(number) => {
+                    let Some(clicks) = &self.accessibility_clicks else {
+                        return Err(anyhow!("Encountered fscommand to click accessibility node #{number}, but log_accessibility isn't enabled."));
+                    };
+                    clicks.lock().unwrap().push(number);
+                }
             }
         }
 
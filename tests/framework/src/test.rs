@@ -3,6 +3,7 @@ use crate::options::TestOptions;
 use crate::runner::TestRunner;
 use crate::util::read_bytes;
 use anyhow::{anyhow, Result};
+use ruffle_core::backend::capture::{FileCamera, FileCaptureBackend, FileMicrophone};
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_input_format::InputInjector;
 use ruffle_socket_format::SocketEvent;
@@ -104,6 +105,36 @@ impl Test {
             .collect()
     }
 
+    pub fn capture(&self) -> Result<FileCaptureBackend> {
+        let mut backend = FileCaptureBackend::new();
+
+        for camera in &self.options.cameras {
+            let camera = if let Some(path) = &camera.path {
+                let data = read_bytes(&self.root_path.join(path)?)?;
+                FileCamera::from_y4m(&camera.name, &data)?
+            } else {
+                let frames = camera
+                    .frames
+                    .iter()
+                    .map(|path| read_bytes(&self.root_path.join(path)?))
+                    .collect::<Result<Vec<_>>>()?;
+                FileCamera::from_png_sequence(
+                    &camera.name,
+                    frames.iter().map(|frame| &frame[..]),
+                    camera.fps,
+                )?
+            };
+            backend = backend.with_camera(camera);
+        }
+
+        for microphone in &self.options.microphones {
+            let data = read_bytes(&self.root_path.join(&microphone.path)?)?;
+            backend = backend.with_microphone(FileMicrophone::from_wav(&microphone.name, &data)?);
+        }
+
+        Ok(backend)
+    }
+
     pub fn should_run(&self, check_renderer: bool, environment: &impl Environment) -> bool {
         if self.options.ignore {
             return false;
@@ -8,6 +8,8 @@ use std::{
 };
 use tracing_subscriber::filter::{EnvFilter, LevelFilter};
 
+mod rtmp;
+
 static POLICY: &[u8] = b"<?xml version=\"1.0\"?>
 <!DOCTYPE cross-domain-policy SYSTEM \"http://www.adobe.com/xml/dtds/cross-domain-policy.dtd\">
 <cross-domain-policy>
@@ -16,9 +18,14 @@ static POLICY: &[u8] = b"<?xml version=\"1.0\"?>
 
 #[derive(Parser, Debug)]
 struct Opt {
-    /// Path to a `socket.json` file.
+    /// Path to a `socket.json` file, or to an FLV file with `--rtmp`.
     #[clap(name = "FILE")]
     file_path: PathBuf,
+
+    /// Act as an RTMP server on port 1935, which plays the FLV file for any
+    /// stream name.
+    #[clap(long)]
+    rtmp: bool,
 }
 
 fn main() -> Result<(), Error> {
@@ -34,6 +41,10 @@ fn main() -> Result<(), Error> {
     // Ignore error if it's already been set
     let _ = tracing::subscriber::set_global_default(subscriber);
 
+    if opt.rtmp {
+        return rtmp::serve(&opt.file_path);
+    }
+
     let events = SocketEvent::from_file(opt.file_path)?;
     let event_count = events.len();
 
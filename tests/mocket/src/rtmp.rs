@@ -0,0 +1,452 @@
+//! A minimal RTMP server, for testing `NetConnection` and `NetStream` against.
+//!
+//! It accepts any `connect`, plays the given FLV file for any stream name, and
+//! answers any other call with its first argument.
+
+use anyhow::{anyhow, Error};
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    path::Path,
+};
+
+const HANDSHAKE_SIZE: usize = 1536;
+
+/// The chunk size we switch to after `connect`, to exercise `Set Chunk Size`.
+const SERVER_CHUNK_SIZE: usize = 4096;
+
+/// The message stream ID handed out by `createStream`.
+const STREAM_ID: u32 = 1;
+
+const MSG_SET_CHUNK_SIZE: u8 = 1;
+const MSG_USER_CONTROL: u8 = 4;
+const MSG_WINDOW_ACK_SIZE: u8 = 5;
+const MSG_SET_PEER_BANDWIDTH: u8 = 6;
+const MSG_COMMAND_AMF0: u8 = 20;
+
+const USER_CONTROL_STREAM_BEGIN: u16 = 0;
+const USER_CONTROL_STREAM_EOF: u16 = 1;
+
+/// The subset of AMF0 that RTMP commands need.
+#[derive(Clone, Debug, PartialEq)]
+enum Amf {
+    Number(f64),
+    Bool(bool),
+    String(String),
+    Object(Vec<(String, Amf)>),
+    Null,
+    Undefined,
+    Array(Vec<Amf>),
+}
+
+impl Amf {
+    fn object(properties: &[(&str, Amf)]) -> Self {
+        Amf::Object(
+            properties
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect(),
+        )
+    }
+
+    fn status(code: &str, description: &str) -> Self {
+        Amf::object(&[
+            ("level", Amf::String("status".to_string())),
+            ("code", Amf::String(code.to_string())),
+            ("description", Amf::String(description.to_string())),
+        ])
+    }
+
+    fn write(&self, output: &mut Vec<u8>) {
+        match self {
+            Amf::Number(value) => {
+                output.push(0x00);
+                output.extend(value.to_be_bytes());
+            }
+            Amf::Bool(value) => output.extend([0x01, *value as u8]),
+            Amf::String(value) => {
+                output.push(0x02);
+                write_string(output, value);
+            }
+            Amf::Object(properties) => {
+                output.push(0x03);
+                for (name, value) in properties {
+                    write_string(output, name);
+                    value.write(output);
+                }
+                output.extend([0x00, 0x00, 0x09]);
+            }
+            Amf::Null => output.push(0x05),
+            Amf::Undefined => output.push(0x06),
+            Amf::Array(values) => {
+                output.push(0x0A);
+                output.extend((values.len() as u32).to_be_bytes());
+                for value in values {
+                    value.write(output);
+                }
+            }
+        }
+    }
+
+    fn read(data: &mut &[u8]) -> Option<Self> {
+        let marker = take(data, 1)?[0];
+        Some(match marker {
+            0x00 => Amf::Number(f64::from_be_bytes(take(data, 8)?.try_into().ok()?)),
+            0x01 => Amf::Bool(take(data, 1)?[0] != 0),
+            0x02 => Amf::String(read_string(data, 2)?),
+            0x0C => Amf::String(read_string(data, 4)?),
+            0x03 => Amf::Object(read_properties(data)?),
+            0x08 => {
+                take(data, 4)?;
+                Amf::Object(read_properties(data)?)
+            }
+            0x05 => Amf::Null,
+            0x06 => Amf::Undefined,
+            0x0A => {
+                let length = u32::from_be_bytes(take(data, 4)?.try_into().ok()?);
+                let values = (0..length)
+                    .map(|_| Amf::read(data))
+                    .collect::<Option<_>>()?;
+                Amf::Array(values)
+            }
+            _ => return None,
+        })
+    }
+}
+
+fn take<'a>(data: &mut &'a [u8], length: usize) -> Option<&'a [u8]> {
+    if data.len() < length {
+        return None;
+    }
+    let (taken, rest) = data.split_at(length);
+    *data = rest;
+    Some(taken)
+}
+
+fn write_string(output: &mut Vec<u8>, value: &str) {
+    output.extend((value.len() as u16).to_be_bytes());
+    output.extend(value.as_bytes());
+}
+
+fn read_string(data: &mut &[u8], length_size: usize) -> Option<String> {
+    let length = take(data, length_size)?
+        .iter()
+        .fold(0, |length, byte| (length << 8) | *byte as usize);
+    Some(String::from_utf8_lossy(take(data, length)?).into_owned())
+}
+
+fn read_properties(data: &mut &[u8]) -> Option<Vec<(String, Amf)>> {
+    let mut properties = vec![];
+    loop {
+        let name = read_string(data, 2)?;
+        if name.is_empty() && data.first() == Some(&0x09) {
+            take(data, 1)?;
+            return Some(properties);
+        }
+        properties.push((name, Amf::read(data)?));
+    }
+}
+
+struct Message {
+    type_id: u8,
+    stream_id: u32,
+    timestamp: u32,
+    payload: Vec<u8>,
+}
+
+#[derive(Default)]
+struct ChunkStream {
+    timestamp: u32,
+    length: usize,
+    type_id: u8,
+    stream_id: u32,
+    payload: Vec<u8>,
+}
+
+struct Connection {
+    stream: TcpStream,
+    read_chunk_size: usize,
+    write_chunk_size: usize,
+    chunk_streams: HashMap<u8, ChunkStream>,
+}
+
+impl Connection {
+    fn read_exact(&mut self, length: usize) -> Result<Vec<u8>, Error> {
+        let mut data = vec![0; length];
+        self.stream.read_exact(&mut data)?;
+        Ok(data)
+    }
+
+    /// Reads the next message, assuming the client only uses one byte chunk
+    /// headers and no extended timestamps, like Ruffle does.
+    fn read_message(&mut self) -> Result<Message, Error> {
+        loop {
+            let first = self.read_exact(1)?[0];
+            let format = first >> 6;
+            let chunk_stream = first & 0x3F;
+            let header = self.read_exact([11, 7, 3, 0][format as usize])?;
+            let state = self.chunk_streams.entry(chunk_stream).or_default();
+
+            if format < 3 {
+                let timestamp = u32::from_be_bytes([0, header[0], header[1], header[2]]);
+                state.timestamp = if format == 0 {
+                    timestamp
+                } else {
+                    state.timestamp.wrapping_add(timestamp)
+                };
+            }
+            if format < 2 {
+                state.length = u32::from_be_bytes([0, header[3], header[4], header[5]]) as usize;
+                state.type_id = header[6];
+            }
+            if format == 0 {
+                state.stream_id = u32::from_le_bytes(header[7..11].try_into()?);
+            }
+
+            let length = (state.length - state.payload.len()).min(self.read_chunk_size);
+            let data = self.read_exact(length)?;
+            let state = self.chunk_streams.get_mut(&chunk_stream).unwrap();
+            state.payload.extend(data);
+            if state.payload.len() >= state.length {
+                return Ok(Message {
+                    type_id: state.type_id,
+                    stream_id: state.stream_id,
+                    timestamp: state.timestamp,
+                    payload: std::mem::take(&mut state.payload),
+                });
+            }
+        }
+    }
+
+    fn write_message(&mut self, chunk_stream: u8, message: &Message) -> Result<(), Error> {
+        let mut data = vec![chunk_stream];
+        data.extend(&message.timestamp.min(0xFF_FFFF).to_be_bytes()[1..]);
+        data.extend(&(message.payload.len() as u32).to_be_bytes()[1..]);
+        data.push(message.type_id);
+        data.extend(message.stream_id.to_le_bytes());
+        for (i, chunk) in message.payload.chunks(self.write_chunk_size).enumerate() {
+            if i > 0 {
+                data.push(0xC0 | chunk_stream);
+            }
+            data.extend(chunk);
+        }
+        self.stream.write_all(&data)?;
+        Ok(())
+    }
+
+    fn write_control(&mut self, type_id: u8, payload: Vec<u8>) -> Result<(), Error> {
+        self.write_message(
+            2,
+            &Message {
+                type_id,
+                stream_id: 0,
+                timestamp: 0,
+                payload,
+            },
+        )
+    }
+
+    fn write_user_control(&mut self, event: u16, stream_id: u32) -> Result<(), Error> {
+        let mut payload = event.to_be_bytes().to_vec();
+        payload.extend(stream_id.to_be_bytes());
+        self.write_control(MSG_USER_CONTROL, payload)
+    }
+
+    fn write_command(&mut self, stream_id: u32, values: &[Amf]) -> Result<(), Error> {
+        let mut payload = vec![];
+        for value in values {
+            value.write(&mut payload);
+        }
+        self.write_message(
+            3,
+            &Message {
+                type_id: MSG_COMMAND_AMF0,
+                stream_id,
+                timestamp: 0,
+                payload,
+            },
+        )
+    }
+
+    fn write_result(&mut self, transaction: f64, values: &[Amf]) -> Result<(), Error> {
+        let mut command = vec![Amf::String("_result".to_string()), Amf::Number(transaction)];
+        command.extend_from_slice(values);
+        self.write_command(0, &command)
+    }
+
+    fn write_stream_status(&mut self, code: &str, description: &str) -> Result<(), Error> {
+        self.write_command(
+            STREAM_ID,
+            &[
+                Amf::String("onStatus".to_string()),
+                Amf::Number(0.0),
+                Amf::Null,
+                Amf::status(code, description),
+            ],
+        )
+    }
+
+    fn handshake(&mut self) -> Result<(), Error> {
+        let hello = self.read_exact(1 + HANDSHAKE_SIZE)?;
+        if hello[0] != 3 {
+            return Err(anyhow!("Unsupported RTMP version {}", hello[0]));
+        }
+
+        let mut response = vec![3];
+        response.extend((0..HANDSHAKE_SIZE).map(|i| (i * 13) as u8));
+        response.extend(&hello[1..]);
+        self.stream.write_all(&response)?;
+
+        // We don't care whether the client echoed S1 correctly.
+        self.read_exact(HANDSHAKE_SIZE)?;
+        Ok(())
+    }
+
+    /// Plays the tags of an FLV file on the stream.
+    fn play(&mut self, flv: &[u8]) -> Result<(), Error> {
+        self.write_user_control(USER_CONTROL_STREAM_BEGIN, STREAM_ID)?;
+        self.write_stream_status("NetStream.Play.Reset", "Playing and resetting.")?;
+        self.write_stream_status("NetStream.Play.Start", "Started playing.")?;
+
+        let header_size = flv
+            .get(5..9)
+            .map(|size| u32::from_be_bytes(size.try_into().unwrap()) as usize)
+            .ok_or_else(|| anyhow!("Invalid FLV file"))?;
+        let mut data = flv.get(header_size + 4..).unwrap_or_default();
+        while data.len() >= 11 {
+            let type_id = data[0];
+            let length = u32::from_be_bytes([0, data[1], data[2], data[3]]) as usize;
+            let timestamp = u32::from_be_bytes([data[7], data[4], data[5], data[6]]);
+            let Some(payload) = data.get(11..11 + length) else {
+                break;
+            };
+            self.write_message(
+                4,
+                &Message {
+                    type_id,
+                    stream_id: STREAM_ID,
+                    timestamp,
+                    payload: payload.to_vec(),
+                },
+            )?;
+            data = data.get(11 + length + 4..).unwrap_or_default();
+        }
+
+        self.write_user_control(USER_CONTROL_STREAM_EOF, STREAM_ID)?;
+        self.write_stream_status("NetStream.Play.Stop", "Stopped playing.")?;
+        Ok(())
+    }
+
+    fn handle_command(&mut self, message: &Message, flv: &[u8]) -> Result<(), Error> {
+        let mut data = &message.payload[..];
+        let mut values = vec![];
+        while !data.is_empty() {
+            values.push(Amf::read(&mut data).ok_or_else(|| anyhow!("Invalid AMF0 data"))?);
+        }
+
+        let [Amf::String(name), Amf::Number(transaction), ..] = &values[..] else {
+            return Err(anyhow!("Invalid command {:?}", values));
+        };
+        let arguments = &values[3..];
+        tracing::info!("Received command {} {:?}", name, arguments);
+
+        match name.as_str() {
+            "connect" => {
+                self.write_control(MSG_WINDOW_ACK_SIZE, 2_500_000u32.to_be_bytes().to_vec())?;
+                let mut bandwidth = 2_500_000u32.to_be_bytes().to_vec();
+                bandwidth.push(2);
+                self.write_control(MSG_SET_PEER_BANDWIDTH, bandwidth)?;
+                self.write_control(
+                    MSG_SET_CHUNK_SIZE,
+                    (SERVER_CHUNK_SIZE as u32).to_be_bytes().to_vec(),
+                )?;
+                self.write_chunk_size = SERVER_CHUNK_SIZE;
+
+                self.write_result(
+                    *transaction,
+                    &[
+                        Amf::object(&[
+                            ("fmsVer", Amf::String("FMS/3,5,7,7009".to_string())),
+                            ("capabilities", Amf::Number(31.0)),
+                        ]),
+                        Amf::status("NetConnection.Connect.Success", "Connection succeeded."),
+                    ],
+                )?;
+            }
+            "createStream" => {
+                self.write_result(*transaction, &[Amf::Null, Amf::Number(STREAM_ID.into())])?;
+            }
+            "play" => self.play(flv)?,
+            "deleteStream" | "closeStream" => {}
+            _ if *transaction != 0.0 => {
+                let result = arguments.first().cloned().unwrap_or(Amf::Undefined);
+                self.write_result(*transaction, &[Amf::Null, result])?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+pub fn serve(flv: &Path) -> Result<(), Error> {
+    let flv = std::fs::read(flv)?;
+
+    let listener = TcpListener::bind("0.0.0.0:1935")?;
+    tracing::info!("Listening for RTMP on {}", listener.local_addr()?);
+    let (stream, addr) = listener.accept()?;
+    tracing::info!("Incoming connection from {}", addr);
+
+    let mut connection = Connection {
+        stream,
+        read_chunk_size: 128,
+        write_chunk_size: 128,
+        chunk_streams: HashMap::new(),
+    };
+    connection.handshake()?;
+    tracing::info!("Handshake complete");
+
+    loop {
+        let message = match connection.read_message() {
+            Ok(message) => message,
+            Err(_) => {
+                tracing::info!("Client has closed the connection!");
+                return Ok(());
+            }
+        };
+
+        match message.type_id {
+            MSG_SET_CHUNK_SIZE => {
+                let size: [u8; 4] = message.payload[..4].try_into()?;
+                connection.read_chunk_size = u32::from_be_bytes(size) as usize;
+            }
+            MSG_COMMAND_AMF0 => connection.handle_command(&message, &flv)?,
+            type_id => tracing::debug!(
+                "Ignoring message of type {} at {}",
+                type_id,
+                message.timestamp
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amf_round_trip() {
+        let value = Amf::Array(vec![
+            Amf::String("hello".to_string()),
+            Amf::Number(1.5),
+            Amf::object(&[("flag", Amf::Bool(true)), ("nothing", Amf::Null)]),
+            Amf::Undefined,
+        ]);
+        let mut data = vec![];
+        value.write(&mut data);
+
+        let mut reader = &data[..];
+        assert_eq!(Amf::read(&mut reader), Some(value));
+        assert!(reader.is_empty());
+    }
+}
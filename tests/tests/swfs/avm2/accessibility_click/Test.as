@@ -0,0 +1,55 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.MouseEvent;
+    import flash.system.fscommand;
+
+    [SWF(width="200", height="100")]
+    public class Test extends Sprite {
+        public function Test() {
+            var button:Sprite = box(10, 10);
+            button.buttonMode = true;
+            button.addEventListener(MouseEvent.MOUSE_DOWN, onMouseEvent);
+            button.addEventListener(MouseEvent.MOUSE_UP, onMouseEvent);
+            button.addEventListener(MouseEvent.CLICK, onMouseEvent);
+
+            var custom:Sprite = box(60, 10);
+            custom.accessibilityImplementation = new CustomImplementation();
+            custom.addEventListener(MouseEvent.CLICK, onMouseEvent);
+
+            trace("Clicking the button");
+            fscommand("accessibilityClick", "1");
+            trace("Clicking the custom button");
+            fscommand("accessibilityClick", "2");
+        }
+
+        private function onMouseEvent(event:MouseEvent):void {
+            trace(event.type + " on " + event.target.name);
+        }
+
+        private function box(x:Number, y:Number):Sprite {
+            var sprite:Sprite = new Sprite();
+            sprite.graphics.beginFill(0x0000FF);
+            sprite.graphics.drawRect(0, 0, 40, 20);
+            sprite.graphics.endFill();
+            sprite.x = x;
+            sprite.y = y;
+            sprite.tabEnabled = false;
+            sprite.name = x == 10 ? "button" : "custom";
+            addChild(sprite);
+            return sprite;
+        }
+    }
+}
+
+import flash.accessibility.AccessibilityImplementation;
+
+class CustomImplementation extends AccessibilityImplementation {
+    override public function get_accRole(childID:uint):uint {
+        // ROLE_SYSTEM_PUSHBUTTON
+        return 0x2B;
+    }
+
+    override public function accDoDefaultAction(childID:uint):void {
+        trace("accDoDefaultAction " + childID);
+    }
+}
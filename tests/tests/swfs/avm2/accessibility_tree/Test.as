@@ -0,0 +1,95 @@
+package {
+    import flash.accessibility.Accessibility;
+    import flash.accessibility.AccessibilityImplementation;
+    import flash.accessibility.AccessibilityProperties;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.text.TextField;
+    import flash.text.TextFieldType;
+
+    [SWF(width="200", height="100")]
+    public class Test extends Sprite {
+        private var picture:Sprite;
+        private var frame:int = 0;
+
+        public function Test() {
+            picture = box(10, 10, 50, 20);
+            picture.accessibilityProperties = properties("Picture", "A red box", "", false);
+
+            var button:Sprite = box(70, 10, 40, 20);
+            button.buttonMode = true;
+            button.accessibilityProperties = properties("OK", "", "Alt+O", false);
+
+            var label:TextField = field(10, 40, "Hello");
+
+            var input:TextField = field(100, 40, "abc");
+            input.type = TextFieldType.INPUT;
+
+            var hidden:Sprite = box(120, 70, 30, 20);
+            hidden.accessibilityProperties = properties("Hidden", "", "", true);
+
+            var custom:Sprite = box(10, 70, 30, 20);
+            custom.accessibilityImplementation = new CustomImplementation();
+
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onEnterFrame(event:Event):void {
+            frame++;
+            if (frame == 3) {
+                trace("Changing the name of the picture");
+                picture.accessibilityProperties.name = "Changed";
+                Accessibility.updateProperties();
+            } else if (frame == 6) {
+                trace("Done");
+                removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+            }
+        }
+
+        private function box(x:Number, y:Number, width:Number, height:Number):Sprite {
+            var sprite:Sprite = new Sprite();
+            sprite.graphics.beginFill(0xFF0000);
+            sprite.graphics.drawRect(0, 0, width, height);
+            sprite.graphics.endFill();
+            sprite.x = x;
+            sprite.y = y;
+            sprite.tabEnabled = false;
+            addChild(sprite);
+            return sprite;
+        }
+
+        private function field(x:Number, y:Number, text:String):TextField {
+            var field:TextField = new TextField();
+            field.x = x;
+            field.y = y;
+            field.width = 80;
+            field.height = 20;
+            field.text = text;
+            field.tabEnabled = false;
+            addChild(field);
+            return field;
+        }
+
+        private function properties(name:String, description:String, shortcut:String, silent:Boolean):AccessibilityProperties {
+            var properties:AccessibilityProperties = new AccessibilityProperties();
+            properties.name = name;
+            properties.description = description;
+            properties.shortcut = shortcut;
+            properties.silent = silent;
+            return properties;
+        }
+    }
+}
+
+import flash.accessibility.AccessibilityImplementation;
+
+class CustomImplementation extends AccessibilityImplementation {
+    override public function get_accName(childID:uint):String {
+        return "Custom";
+    }
+
+    override public function get_accRole(childID:uint):uint {
+        // ROLE_SYSTEM_PUSHBUTTON
+        return 0x2B;
+    }
+}
@@ -0,0 +1,46 @@
+package {
+import flash.display.MovieClip;
+import flash.desktop.NativeApplication;
+import flash.events.Event;
+import flash.events.InvokeEvent;
+
+public class Test extends MovieClip {
+    public function Test() {
+        var app:NativeApplication = NativeApplication.nativeApplication;
+        trace("Same instance: " + (app === NativeApplication.nativeApplication));
+        trace("isActive: " + app.isActive);
+        trace("applicationID: \"" + app.applicationID + "\"");
+        trace("applicationDescriptor: " + app.applicationDescriptor);
+
+        trace("idleThreshold: " + app.idleThreshold);
+        app.idleThreshold = 10;
+        trace("idleThreshold: " + app.idleThreshold);
+        try {
+            app.idleThreshold = 4;
+        } catch (e:Error) {
+            trace(e);
+        }
+        try {
+            app.idleThreshold = 86401;
+        } catch (e:Error) {
+            trace(e);
+        }
+        trace("idleThreshold: " + app.idleThreshold);
+
+        app.addEventListener(Event.ACTIVATE, function(e:Event):void {
+            trace("activate, isActive: " + app.isActive);
+        });
+        app.addEventListener(Event.DEACTIVATE, function(e:Event):void {
+            trace("deactivate, isActive: " + app.isActive);
+        });
+
+        app.addEventListener(InvokeEvent.INVOKE, function(e:InvokeEvent):void {
+            trace("invoke");
+            trace("  arguments: " + e.arguments.length);
+            trace("  currentDirectory: " + e.currentDirectory);
+            trace("  reason: " + e.reason);
+        });
+        trace("Constructor finished");
+    }
+}
+}
@@ -0,0 +1,111 @@
+package {
+import flash.display.MovieClip;
+import flash.display.NativeWindow;
+import flash.desktop.NativeApplication;
+import flash.events.Event;
+import flash.events.NativeWindowBoundsEvent;
+import flash.events.NativeWindowDisplayStateEvent;
+import flash.geom.Point;
+
+public class Test extends MovieClip {
+    private var cancelMoving:Boolean = false;
+
+    public function Test() {
+        var win:NativeWindow = stage.nativeWindow;
+        var app:NativeApplication = NativeApplication.nativeApplication;
+        trace("Same instance: " + (win === stage.nativeWindow));
+        trace("openedWindows: " + app.openedWindows.length + " " + (app.openedWindows[0] === win));
+        trace("activeWindow: " + app.activeWindow);
+        trace("bounds: " + win.bounds);
+        trace("displayState: " + win.displayState);
+
+        for each (var boundsType:String in [
+            NativeWindowBoundsEvent.MOVING,
+            NativeWindowBoundsEvent.MOVE,
+            NativeWindowBoundsEvent.RESIZING,
+            NativeWindowBoundsEvent.RESIZE
+        ]) {
+            win.addEventListener(boundsType, onBoundsEvent);
+        }
+        for each (var stateType:String in [
+            NativeWindowDisplayStateEvent.DISPLAY_STATE_CHANGING,
+            NativeWindowDisplayStateEvent.DISPLAY_STATE_CHANGE
+        ]) {
+            win.addEventListener(stateType, onDisplayStateEvent);
+        }
+        for each (var type:String in [Event.CLOSING, Event.CLOSE, Event.DEACTIVATE]) {
+            win.addEventListener(type, function(e:Event):void {
+                trace("  " + e.type);
+            });
+        }
+
+        trace("/// x = 10");
+        win.x = 10;
+        trace("/// width = 300");
+        win.width = 300;
+        trace("/// width = 5000");
+        win.width = 5000;
+        trace("/// width = 300");
+        win.width = 300;
+        trace("bounds: " + win.bounds);
+
+        trace("/// x = 50 (cancelled)");
+        cancelMoving = true;
+        win.x = 50;
+        cancelMoving = false;
+        trace("bounds: " + win.bounds);
+
+        trace("/// maximize()");
+        win.maximize();
+        trace("bounds: " + win.bounds);
+        trace("/// maximize() again");
+        win.maximize();
+        trace("/// restore()");
+        win.restore();
+        trace("bounds: " + win.bounds);
+        trace("/// minimize()");
+        win.minimize();
+        trace("displayState: " + win.displayState);
+        trace("/// restore()");
+        win.restore();
+
+        trace("globalToScreen: " + win.globalToScreen(new Point(5, 5)));
+        trace("startMove: " + win.startMove());
+        trace("startResize: " + win.startResize());
+        trace("startResize(NONE): " + win.startResize(""));
+        try {
+            win.startResize("X");
+        } catch (e:Error) {
+            trace(e);
+        }
+
+        trace("/// close()");
+        win.close();
+        trace("closed: " + win.closed);
+        trace("openedWindows: " + app.openedWindows.length);
+        trace("/// close() again");
+        win.close();
+        try {
+            win.x = 0;
+        } catch (e:Error) {
+            trace(e);
+        }
+        try {
+            win.maximize();
+        } catch (e:Error) {
+            trace(e);
+        }
+    }
+
+    private function onBoundsEvent(e:NativeWindowBoundsEvent):void {
+        trace("  " + e.type + " " + e.beforeBounds + " -> " + e.afterBounds);
+        if (cancelMoving && e.type == NativeWindowBoundsEvent.MOVING) {
+            e.preventDefault();
+        }
+    }
+
+    private function onDisplayStateEvent(e:NativeWindowDisplayStateEvent):void {
+        trace("  " + e.type + " " + e.beforeDisplayState + " -> " + e.afterDisplayState);
+    }
+}
+}
@@ -0,0 +1,45 @@
+package {
+    import flash.display.BitmapData;
+    import flash.display.Sprite;
+    import flash.events.ActivityEvent;
+    import flash.events.Event;
+    import flash.media.Camera;
+
+    public class Test extends Sprite {
+        private var camera:Camera;
+        private var frames:int = 0;
+
+        public function Test() {
+            trace("Camera.isSupported: " + Camera.isSupported);
+            trace("Camera.names: " + Camera.names);
+
+            camera = Camera.getCamera();
+            trace("name: " + camera.name);
+            trace("index: " + camera.index);
+            trace("width: " + camera.width);
+            trace("height: " + camera.height);
+            trace("fps: " + camera.fps);
+            trace("muted: " + camera.muted);
+            trace("activityLevel: " + camera.activityLevel);
+            trace("Same camera: " + (camera === Camera.getCamera("0")));
+            trace("Camera.getCamera(\"1\"): " + Camera.getCamera("1"));
+
+            camera.addEventListener("videoFrame", onVideoFrame);
+            camera.addEventListener(ActivityEvent.ACTIVITY, onActivity);
+        }
+
+        private function onVideoFrame(event:Event):void {
+            frames++;
+            var bitmap:BitmapData = new BitmapData(4, 4, true, 0);
+            camera.drawToBitmapData(bitmap);
+            trace("videoFrame " + frames + ": pixel " + bitmap.getPixel32(0, 0).toString(16) + ", activityLevel " + camera.activityLevel);
+            if (frames == 3) {
+                camera.removeEventListener("videoFrame", onVideoFrame);
+            }
+        }
+
+        private function onActivity(event:ActivityEvent):void {
+            trace("activity: " + event.activating);
+        }
+    }
+}
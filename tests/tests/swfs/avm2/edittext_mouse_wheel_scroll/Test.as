@@ -0,0 +1,42 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.MouseEvent;
+    import flash.text.TextField;
+
+    [SWF(width="300", height="200")]
+    public class Test extends Sprite {
+        public function Test() {
+            var enabled:TextField = newText("enabled", 0);
+            trace("enabled.mouseWheelEnabled: " + enabled.mouseWheelEnabled);
+
+            var disabled:TextField = newText("disabled", 150);
+            disabled.mouseWheelEnabled = false;
+            trace("disabled.mouseWheelEnabled: " + disabled.mouseWheelEnabled);
+
+            stage.addEventListener(MouseEvent.MOUSE_WHEEL, function(event:MouseEvent):void {
+                var target:String = event.target == stage ? "stage" : event.target.name;
+                trace("mouseWheel on " + target + ", delta " + event.delta);
+            });
+        }
+
+        private function newText(name:String, x:Number):TextField {
+            var text:TextField = new TextField();
+            text.name = name;
+            text.x = x;
+            text.width = 100;
+            text.height = 50;
+            text.multiline = true;
+            var lines:Array = [];
+            for (var i:int = 1; i <= 20; i++) {
+                lines.push("line " + i);
+            }
+            text.text = lines.join("\n");
+            text.addEventListener(Event.SCROLL, function(event:Event):void {
+                trace("scroll on " + name + ": scrollV " + text.scrollV);
+            });
+            addChild(text);
+            return text;
+        }
+    }
+}
@@ -0,0 +1,75 @@
+package {
+import flash.display.*;
+import flash.text.*;
+import flash.events.*;
+import flash.system.*;
+
+[SWF(width="100", height="100")]
+public class Test extends MovieClip {
+    private var tf:TextField;
+    private var frame:int = 0;
+
+    public function Test() {
+        tf = new TextField();
+        tf.type = "input";
+        tf.width = 100;
+        tf.height = 100;
+        addChild(tf);
+        stage.focus = tf;
+
+        trace("IME.isSupported: " + IME.isSupported);
+        trace("IME.enabled: " + IME.enabled);
+
+        tf.addEventListener("textInput", function(evt:TextEvent):void {
+            trace("textInput: " + evt.text);
+        });
+        System.ime.addEventListener(IMEEvent.IME_COMPOSITION, function(evt:IMEEvent):void {
+            trace("imeComposition: " + evt.text);
+            trace("  " + evt);
+        });
+
+        addEventListener(Event.ENTER_FRAME, onEnterFrame);
+    }
+
+    private function traceField(name:String):void {
+        trace(name + ": " + tf.text + " [" + tf.selectionBeginIndex + ", " + tf.selectionEndIndex + "]");
+    }
+
+    private function onEnterFrame(evt:Event):void {
+        frame++;
+        if (frame == 1) {
+            traceField("Platform composition");
+        } else if (frame == 2) {
+            traceField("Platform commit");
+
+            IME.setCompositionString("xy");
+            traceField("setCompositionString");
+            IME.compositionSelectionChanged(0, 1);
+            traceField("compositionSelectionChanged");
+            IME.doConversion();
+            traceField("doConversion");
+
+            IME.setCompositionString("q");
+            IME.compositionAbandoned();
+            traceField("compositionAbandoned");
+
+            IME.setCompositionString("z");
+            IME.enabled = false;
+            trace("IME.enabled: " + IME.enabled);
+            traceField("Disabled while composing");
+            IME.enabled = true;
+            trace("IME.enabled: " + IME.enabled);
+
+            try {
+                IME.conversionMode = "unknown";
+            } catch (e:Error) {
+                trace(e);
+            }
+            IME.conversionMode = IMEConversionMode.JAPANESE_HIRAGANA;
+            trace("IME.conversionMode: " + IME.conversionMode);
+
+            removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+    }
+}
+}
@@ -0,0 +1,45 @@
+package {
+    import flash.display.Loader;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.net.URLRequest;
+    import flash.system.ApplicationDomain;
+    import flash.system.LoaderContext;
+    import flash.utils.Timer;
+
+    public class Test extends Sprite {
+        private var loader:Loader = new Loader();
+        private var timer:Timer;
+        private var count:int;
+        private var frames:int = 0;
+
+        public function Test() {
+            loader.contentLoaderInfo.addEventListener(Event.COMPLETE, onComplete);
+            loader.load(
+                new URLRequest("child/child.swf"),
+                new LoaderContext(false, new ApplicationDomain(ApplicationDomain.currentDomain))
+            );
+        }
+
+        private function onComplete(event:Event):void {
+            timer = loader.content["timer"];
+            trace("Timer running before unloadAndStop: " + timer.running);
+            loader.unloadAndStop();
+            trace("Timer running after unloadAndStop: " + timer.running);
+            count = timer.currentCount;
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onEnterFrame(event:Event):void {
+            frames++;
+            if (frames == 3) {
+                trace("Timer ticked after unloadAndStop: " + (timer.currentCount != count));
+                timer.start();
+                trace("Timer running after restart: " + timer.running);
+                timer.stop();
+                trace("Timer running after stop: " + timer.running);
+                removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+            }
+        }
+    }
+}
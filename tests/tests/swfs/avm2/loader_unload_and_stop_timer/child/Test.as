@@ -0,0 +1,12 @@
+package {
+    import flash.display.Sprite;
+    import flash.utils.Timer;
+
+    public class Test extends Sprite {
+        public var timer:Timer = new Timer(10);
+
+        public function Test() {
+            timer.start();
+        }
+    }
+}
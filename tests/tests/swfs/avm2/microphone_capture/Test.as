@@ -0,0 +1,45 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.ActivityEvent;
+    import flash.events.SampleDataEvent;
+    import flash.media.Microphone;
+
+    public class Test extends Sprite {
+        private var microphone:Microphone;
+        private var packets:int = 0;
+
+        public function Test() {
+            trace("Microphone.isSupported: " + Microphone.isSupported);
+            trace("Microphone.names: " + Microphone.names);
+
+            microphone = Microphone.getMicrophone();
+            trace("name: " + microphone.name);
+            trace("index: " + microphone.index);
+            trace("rate: " + microphone.rate);
+            trace("gain: " + microphone.gain);
+            trace("muted: " + microphone.muted);
+            trace("activityLevel: " + microphone.activityLevel);
+            trace("Same microphone: " + (microphone === Microphone.getMicrophone(0)));
+            trace("Microphone.getMicrophone(1): " + Microphone.getMicrophone(1));
+
+            microphone.setSilenceLevel(10, 100);
+            trace("silenceLevel: " + microphone.silenceLevel);
+            trace("silenceTimeout: " + microphone.silenceTimeout);
+
+            microphone.addEventListener(SampleDataEvent.SAMPLE_DATA, onSampleData);
+            microphone.addEventListener(ActivityEvent.ACTIVITY, onActivity);
+        }
+
+        private function onSampleData(event:SampleDataEvent):void {
+            packets++;
+            trace("sampleData: position " + event.position + ", " + (event.data.length / 4) + " samples, first " + event.data.readFloat() + ", activityLevel " + microphone.activityLevel);
+            if (packets == 5) {
+                microphone.removeEventListener(SampleDataEvent.SAMPLE_DATA, onSampleData);
+            }
+        }
+
+        private function onActivity(event:ActivityEvent):void {
+            trace("activity: " + event.activating);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+package {
+    import flash.display.BitmapData;
+    import flash.display.Sprite;
+    import flash.geom.Point;
+    import flash.ui.Mouse;
+    import flash.ui.MouseCursor;
+    import flash.ui.MouseCursorData;
+
+    [SWF(width="100", height="100")]
+    public class Test extends Sprite {
+        public function Test() {
+            trace("Mouse.supportsNativeCursor: " + Mouse.supportsNativeCursor);
+            trace("Mouse.cursor: " + Mouse.cursor);
+
+            var data:MouseCursorData = new MouseCursorData();
+            var frames:Vector.<BitmapData> = new Vector.<BitmapData>();
+            frames.push(new BitmapData(4, 4, true, 0xFFFF0000));
+            frames.push(new BitmapData(4, 4, true, 0xFF0000FF));
+            data.data = frames;
+            data.hotSpot = new Point(2, 10);
+            data.frameRate = 12;
+            trace("Registering custom cursor");
+            Mouse.registerCursor("custom", data);
+
+            setCursor(MouseCursor.BUTTON);
+            setCursor(MouseCursor.BUTTON);
+            setCursor(MouseCursor.IBEAM);
+            setCursor("custom");
+            setCursor("unknown");
+
+            trace("Unregistering custom cursor");
+            Mouse.unregisterCursor("custom");
+            trace("Mouse.cursor: " + Mouse.cursor);
+
+            setCursor(MouseCursor.HAND);
+            setCursor(MouseCursor.AUTO);
+
+            for each (var frameRate:Number in [1e-300, 1e300, -5, NaN]) {
+                trace("Registering cursor with frame rate " + frameRate);
+                data.frameRate = frameRate;
+                Mouse.registerCursor("rate", data);
+            }
+        }
+
+        private function setCursor(name:String):void {
+            trace("Setting Mouse.cursor to " + name);
+            try {
+                Mouse.cursor = name;
+            } catch (e:Error) {
+                trace(e);
+            }
+            trace("Mouse.cursor: " + Mouse.cursor);
+        }
+    }
+}
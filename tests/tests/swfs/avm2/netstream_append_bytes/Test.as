@@ -0,0 +1,105 @@
+package {
+import flash.display.MovieClip;
+import flash.events.NetStatusEvent;
+import flash.net.NetConnection;
+import flash.net.NetStream;
+import flash.utils.ByteArray;
+
+[SWF(width="100", height="100", frameRate="20")]
+public class Test extends MovieClip {
+    private var ns:NetStream;
+    private var seeked:Boolean = false;
+
+    public function Test() {
+        var nc:NetConnection = new NetConnection();
+        nc.connect(null);
+        ns = new NetStream(nc);
+        ns.client = {
+            onTick: function(value:Number):void {
+                trace("onTick " + value);
+            }
+        };
+        ns.addEventListener(NetStatusEvent.NET_STATUS, onNetStatus);
+
+        // Outside of data generation mode, appended data is ignored.
+        ns.appendBytes(flvHeader());
+        trace("bytesLoaded: " + ns.bytesLoaded);
+        try {
+            ns.appendBytes(null);
+        } catch (e:Error) {
+            trace(e);
+        }
+        try {
+            ns.appendBytesAction("unknown");
+        } catch (e:Error) {
+            trace(e);
+        }
+
+        ns.play(null);
+        ns.appendBytesAction("resetBegin");
+        var bytes:ByteArray = flvHeader();
+        for (var time:int = 0; time <= 200; time += 100) {
+            writeTick(bytes, time);
+        }
+        ns.appendBytes(bytes);
+        trace("bytesLoaded: " + ns.bytesLoaded);
+    }
+
+    private function onNetStatus(e:NetStatusEvent):void {
+        var description:String = e.info.description ? " (" + e.info.description + ")" : "";
+        trace("netStatus: " + e.info.code + description);
+
+        if (e.info.code == "NetStream.Buffer.Empty" && !seeked) {
+            seeked = true;
+            ns.seek(5);
+        } else if (e.info.code == "NetStream.Seek.Notify") {
+            trace("time: " + ns.time);
+
+            // Data is dropped until the stream is told where it resumes.
+            var dropped:ByteArray = new ByteArray();
+            writeTick(dropped, 0);
+            ns.appendBytes(dropped);
+            trace("bytesLoaded: " + ns.bytesLoaded);
+
+            // The header from `resetBegin` still applies, and the timestamps start anew.
+            ns.appendBytesAction("resetSeek");
+            var bytes:ByteArray = new ByteArray();
+            for (var time:int = 5000; time <= 5200; time += 100) {
+                writeTick(bytes, time);
+            }
+            ns.appendBytes(bytes);
+            trace("bytesLoaded: " + ns.bytesLoaded);
+            ns.appendBytesAction("endSequence");
+        }
+    }
+
+    private static function flvHeader():ByteArray {
+        var bytes:ByteArray = new ByteArray();
+        bytes.writeUTFBytes("FLV");
+        bytes.writeByte(1);
+        bytes.writeByte(0);
+        bytes.writeUnsignedInt(9);
+        return bytes;
+    }
+
+    // Writes a script tag that calls `client.onTick(time)` at `time` milliseconds.
+    private static function writeTick(bytes:ByteArray, time:int):void {
+        var data:ByteArray = new ByteArray();
+        data.writeByte(2);
+        data.writeUTF("onTick");
+        data.writeByte(0);
+        data.writeDouble(time);
+
+        bytes.writeUnsignedInt(0);
+        bytes.writeByte(18);
+        bytes.writeByte(data.length >> 16);
+        bytes.writeShort(data.length);
+        bytes.writeByte(time >> 16);
+        bytes.writeShort(time);
+        bytes.writeByte(time >> 24);
+        bytes.writeByte(0);
+        bytes.writeShort(0);
+        bytes.writeBytes(data);
+    }
+}
+}
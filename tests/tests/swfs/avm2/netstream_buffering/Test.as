@@ -0,0 +1,107 @@
+package {
+import flash.display.MovieClip;
+import flash.events.Event;
+import flash.events.NetStatusEvent;
+import flash.net.NetConnection;
+import flash.net.NetStream;
+import flash.net.NetStreamInfo;
+import flash.utils.ByteArray;
+
+[SWF(width="100", height="100", frameRate="20")]
+public class Test extends MovieClip {
+    private var ns:NetStream;
+    private var frame:int = 0;
+
+    public function Test() {
+        var nc:NetConnection = new NetConnection();
+        nc.connect(null);
+        ns = new NetStream(nc);
+        ns.client = {
+            onTick: function(value:Number):void {
+                trace("onTick " + value);
+            }
+        };
+        ns.addEventListener(NetStatusEvent.NET_STATUS, function(e:NetStatusEvent):void {
+            trace("netStatus: " + e.info.code + " (bufferLength: " + ns.bufferLength + ")");
+        });
+
+        trace("bufferTime: " + ns.bufferTime);
+        ns.bufferTime = 0.5;
+        trace("bufferTime: " + ns.bufferTime);
+        trace("bufferTimeMax: " + ns.bufferTimeMax);
+        ns.bufferTimeMax = 2;
+        trace("bufferTimeMax: " + ns.bufferTimeMax);
+
+        ns.play(null);
+        ns.appendBytesAction("resetBegin");
+        var bytes:ByteArray = flvHeader();
+        for (var time:int = 0; time <= 200; time += 100) {
+            writeTick(bytes, time);
+        }
+        ns.appendBytes(bytes);
+
+        addEventListener(Event.ENTER_FRAME, onEnterFrame);
+    }
+
+    private function onEnterFrame(e:Event):void {
+        frame++;
+        var bytes:ByteArray = new ByteArray();
+        if (frame == 1) {
+            traceStream("Waiting for 0.5s of media");
+            for (var time:int = 300; time <= 600; time += 100) {
+                writeTick(bytes, time);
+            }
+            ns.appendBytes(bytes);
+        } else if (frame == 2) {
+            traceStream("Playing");
+        } else if (frame == 14) {
+            traceStream("Stalled");
+            writeTick(bytes, 700);
+            ns.appendBytes(bytes);
+            ns.appendBytesAction("endSequence");
+            removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+    }
+
+    private function traceStream(name:String):void {
+        var info:NetStreamInfo = ns.info;
+        trace("/// " + name);
+        trace("time: " + ns.time);
+        trace("bufferLength: " + ns.bufferLength);
+        trace("backBufferLength: " + ns.backBufferLength);
+        trace("currentFPS: " + ns.currentFPS);
+        trace("info.dataByteCount: " + info.dataByteCount);
+        trace("info.dataBufferByteLength: " + info.dataBufferByteLength);
+        trace("info.dataBufferLength: " + info.dataBufferLength);
+    }
+
+    private static function flvHeader():ByteArray {
+        var bytes:ByteArray = new ByteArray();
+        bytes.writeUTFBytes("FLV");
+        bytes.writeByte(1);
+        bytes.writeByte(0);
+        bytes.writeUnsignedInt(9);
+        return bytes;
+    }
+
+    // Writes a script tag that calls `client.onTick(time)` at `time` milliseconds.
+    private static function writeTick(bytes:ByteArray, time:int):void {
+        var data:ByteArray = new ByteArray();
+        data.writeByte(2);
+        data.writeUTF("onTick");
+        data.writeByte(0);
+        data.writeDouble(time);
+
+        bytes.writeUnsignedInt(0);
+        bytes.writeByte(18);
+        bytes.writeByte(data.length >> 16);
+        bytes.writeShort(data.length);
+        bytes.writeByte(time >> 16);
+        bytes.writeShort(time);
+        bytes.writeByte(time >> 24);
+        bytes.writeByte(0);
+        bytes.writeShort(0);
+        bytes.writeBytes(data);
+    }
+}
+}
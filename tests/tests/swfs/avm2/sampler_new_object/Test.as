@@ -0,0 +1,29 @@
+package {
+    import flash.display.Sprite;
+    import flash.sampler.NewObjectSample;
+    import flash.sampler.getSamples;
+    import flash.sampler.pauseSampling;
+    import flash.sampler.startSampling;
+    import flash.sampler.stopSampling;
+    import flash.utils.getQualifiedClassName;
+
+    public class Test extends Sprite {
+        public function Test() {
+            startSampling();
+            var literal:Object = {a: 1};
+            var object:Object = new Object();
+            var array:Array = [1, 2];
+            var split:Array = "a,b".split(",");
+            var closure:Function = function():void {};
+            var date:Date = new Date(0);
+            pauseSampling();
+
+            for each (var sample:Object in getSamples()) {
+                if (sample is NewObjectSample) {
+                    trace("NewObjectSample: " + getQualifiedClassName(NewObjectSample(sample).type));
+                }
+            }
+            stopSampling();
+        }
+    }
+}
@@ -0,0 +1,133 @@
+package {
+    import flash.display.Loader;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.IOErrorEvent;
+    import flash.events.NetStatusEvent;
+    import flash.events.SecurityErrorEvent;
+    import flash.media.Sound;
+    import flash.media.SoundMixer;
+    import flash.net.NetConnection;
+    import flash.net.URLLoader;
+    import flash.net.URLRequest;
+    import flash.net.URLStream;
+    import flash.utils.ByteArray;
+
+    // Compile with network enabled, run with a strict sandbox.
+    // example.org serves a policy file allowing everyone, example.net doesn't.
+    public class Test extends Sprite {
+        private var steps:Array;
+
+        public function Test() {
+            steps = [
+                function():void { loadWithURLLoader("http://example.org/data.txt"); },
+                function():void { loadWithURLLoader("http://example.net/data.txt"); },
+                function():void { loadWithURLLoader("http://[invalid/data.txt"); },
+                function():void { loadWithURLStream("http://example.org/data.txt"); },
+                function():void { loadWithURLStream("http://example.net/data.txt"); },
+                function():void { loadSound("http://example.net/sound.mp3"); },
+                function():void { callRemoting("http://example.net/gateway"); },
+                function():void { loadWithURLLoader("ftp://example.net/data.txt"); },
+                function():void { loadMovie("http://example.net/child.swf"); },
+                function():void { computeSpectrum(); }
+            ];
+            next();
+        }
+
+        private function next():void {
+            if (steps.length > 0) {
+                steps.shift()();
+            } else {
+                trace("Done");
+            }
+        }
+
+        private function listen(target:Object, name:String):void {
+            target.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace(name + ": complete");
+                if (target is URLLoader) {
+                    trace("  data: " + target.data);
+                } else if (target is URLStream) {
+                    trace("  data: " + target.readUTFBytes(target.bytesAvailable));
+                }
+                next();
+            });
+            target.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace(name + ": ioError " + e.errorID);
+                next();
+            });
+            target.addEventListener(SecurityErrorEvent.SECURITY_ERROR, function(e:SecurityErrorEvent):void {
+                trace(name + ": securityError " + e.errorID);
+                next();
+            });
+        }
+
+        private function loadWithURLLoader(url:String):void {
+            trace("URLLoader.load(" + url + ")");
+            var loader:URLLoader = new URLLoader();
+            listen(loader, "URLLoader");
+            loader.load(new URLRequest(url));
+        }
+
+        private function loadWithURLStream(url:String):void {
+            trace("URLStream.load(" + url + ")");
+            var stream:URLStream = new URLStream();
+            listen(stream, "URLStream");
+            stream.load(new URLRequest(url));
+        }
+
+        private function loadSound(url:String):void {
+            trace("Sound.load(" + url + ")");
+            var sound:Sound = new Sound();
+            listen(sound, "Sound");
+            sound.load(new URLRequest(url));
+        }
+
+        private function loadMovie(url:String):void {
+            trace("Loader.load(" + url + ")");
+            var loader:Loader = new Loader();
+            loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace("Loader: complete");
+                tryAccess("Loader.content", function():* { return loader.content; });
+                tryAccess("LoaderInfo.content", function():* { return loader.contentLoaderInfo.content; });
+                tryAccess("LoaderInfo.bytes", function():* { return loader.contentLoaderInfo.bytes; });
+                tryAccess("LoaderInfo.applicationDomain", function():* { return loader.contentLoaderInfo.applicationDomain; });
+                next();
+            });
+            loader.contentLoaderInfo.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace("Loader: ioError " + e.errorID);
+                next();
+            });
+            loader.load(new URLRequest(url));
+        }
+
+        private function computeSpectrum():void {
+            tryAccess("SoundMixer.computeSpectrum", function():* {
+                SoundMixer.computeSpectrum(new ByteArray());
+                return "done";
+            });
+            next();
+        }
+
+        private function tryAccess(name:String, access:Function):void {
+            try {
+                access();
+                trace(name + ": allowed");
+            } catch (e:SecurityError) {
+                trace(name + ": SecurityError " + e.errorID);
+            }
+        }
+
+        private function callRemoting(url:String):void {
+            trace("NetConnection.call(" + url + ")");
+            var connection:NetConnection = new NetConnection();
+            listen(connection, "NetConnection");
+            connection.addEventListener(NetStatusEvent.NET_STATUS, function(e:NetStatusEvent):void {
+                trace("NetConnection: netStatus " + e.info.code);
+                next();
+            });
+            connection.connect(url);
+            connection.call("service.method", null);
+        }
+    }
+}
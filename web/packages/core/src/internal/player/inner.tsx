@@ -681,6 +681,10 @@ export class InnerPlayer {
             }
         }
 
+        if (this.loadedConfig?.fontFallbacks) {
+            builder.setFontFallbacks(this.loadedConfig.fontFallbacks);
+        }
+
         this.instance = await builder.build(this.container, this).catch((e) => {
             console.error(`Serious error loading Ruffle: ${e}`);
             this.panic(e);
@@ -50,6 +50,7 @@ export const DEFAULT_CONFIG: Required<BaseLoadOptions> = {
     socketProxy: [],
     fontSources: [],
     defaultFonts: {},
+    fontFallbacks: [],
     credentialAllowList: [],
     playerRuntime: PlayerRuntime.FlashPlayer,
     gamepadButtonMapping: {},
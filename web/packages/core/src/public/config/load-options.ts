@@ -672,6 +672,16 @@ export interface BaseLoadOptions {
      */
     defaultFonts?: DefaultFonts;
 
+    /**
+     * The font names to use, in order, for characters that a device font has no glyphs for.
+     *
+     * This allows text in a Latin font to contain CJK characters, emoji or symbols.
+     * Names of "default" Flash device fonts (such as `_sans`) may be used as well.
+     *
+     * @default []
+     */
+    fontFallbacks?: Array<string>;
+
     /**
      * An array of origins that credentials may be sent to.
      * Credentials are cookies, authorization headers, or TLS client certificates.
@@ -62,6 +62,7 @@ pub struct RuffleInstanceBuilder {
     pub(crate) player_runtime: PlayerRuntime,
     pub(crate) volume: f32,
     pub(crate) default_fonts: HashMap<DefaultFont, Vec<String>>,
+    pub(crate) font_fallbacks: Option<Vec<String>>,
     pub(crate) custom_fonts: Vec<(String, Vec<u8>)>,
     pub(crate) gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
     pub(crate) url_rewrite_rules: Vec<(RegExp, String)>,
@@ -100,6 +101,7 @@ impl Default for RuffleInstanceBuilder {
             player_runtime: PlayerRuntime::FlashPlayer,
             volume: 1.0,
             default_fonts: HashMap::new(),
+            font_fallbacks: None,
             custom_fonts: vec![],
             gamepad_button_mapping: HashMap::new(),
             url_rewrite_rules: vec![],
@@ -323,6 +325,16 @@ impl RuffleInstanceBuilder {
         );
     }
 
+    #[wasm_bindgen(js_name = "setFontFallbacks")]
+    pub fn set_font_fallbacks(&mut self, fonts: Vec<JsValue>) {
+        self.font_fallbacks = Some(
+            fonts
+                .into_iter()
+                .flat_map(|value| value.as_string())
+                .collect(),
+        );
+    }
+
     #[wasm_bindgen(js_name = "addGamepadButtonMapping")]
     pub fn add_gampepad_button_mapping(&mut self, button: &str, keycode: u32) {
         if let Ok(button) = GamepadButton::from_str(button) {
@@ -428,6 +440,10 @@ impl RuffleInstanceBuilder {
         for (default, names) in &self.default_fonts {
             player.set_default_font(*default, names.clone());
         }
+
+        if let Some(names) = &self.font_fallbacks {
+            player.set_font_fallbacks(names.clone());
+        }
     }
 
     #[inline]
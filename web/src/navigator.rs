@@ -10,6 +10,7 @@ use ruffle_core::backend::navigator::{
     NavigationMethod, NavigatorBackend, OwnedFuture, Request, SuccessResponse,
 };
 use ruffle_core::config::NetworkingAccessMode;
+use ruffle_core::datagram_socket::{Datagram, DatagramSocketAction, DatagramSocketHandle};
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
@@ -559,6 +560,19 @@ impl NavigatorBackend for WebNavigatorBackend {
             Ok(())
         }));
     }
+
+    fn bind_udp_socket(
+        &mut self,
+        address: String,
+        port: u16,
+        _handle: DatagramSocketHandle,
+        _receiver: Receiver<Datagram>,
+        _sender: Sender<DatagramSocketAction>,
+    ) -> Option<(String, u16)> {
+        // NOTE: Browsers have no way of sending raw UDP packets.
+        tracing::warn!("Can't bind UDP socket to {}:{} on web", address, port);
+        None
+    }
 }
 
 struct WebResponseWrapper {
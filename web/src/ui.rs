@@ -190,6 +190,8 @@ impl WebUiBackend {
                 MouseCursor::Hand => "pointer",
                 MouseCursor::IBeam => "text",
                 MouseCursor::Grab => "grab",
+                // Custom cursors are not supported on web yet.
+                MouseCursor::Custom(_) => "auto",
             }
         } else {
             "none"